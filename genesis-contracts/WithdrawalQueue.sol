@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.28;
+
+/// @title WithdrawalQueue - Governance-controlled EIP-4895 withdrawal source
+/// @notice Governance enqueues (recipient, amount) credits; the block producer
+///         dequeues up to `maxPerBlock` entries per block and includes them as
+///         EIP-4895 withdrawals (withdrawal index = queue id).
+///
+/// Storage layout (read directly by the node, see `onchain::withdrawal_queue_slots`):
+///   slot 0: governance (address)
+///   slot 1: head (uint64)  — next unprocessed id
+///   slot 2: tail (uint64)  — next id to assign
+///   slot 3: entries (mapping(uint256 => Entry)), Entry packed in one slot:
+///           recipient in the low 20 bytes, amountGwei in the next 8 bytes
+contract WithdrawalQueue {
+    struct Entry {
+        address recipient;
+        uint64 amountGwei;
+    }
+
+    // ---- State ----
+    address public governance;
+    uint64 public head;
+    uint64 public tail;
+    mapping(uint256 => Entry) public entries;
+
+    /// System caller used for protocol-level calls (same as EIP-4788/EIP-2935).
+    address public constant SYSTEM_ADDRESS = 0xffffFFFfFFffffffffffffffFfFFFfffFFFfFFfE;
+
+    // ---- Events ----
+    event GovernanceTransferred(address indexed previous, address indexed newGovernance);
+    event Enqueued(uint256 indexed id, address indexed recipient, uint64 amountGwei);
+    event Finalized(uint256 indexed id);
+
+    // ---- Modifiers ----
+    modifier onlyGovernance() {
+        require(msg.sender == governance, "WithdrawalQueue: not governance");
+        _;
+    }
+
+    modifier onlyFinalizer() {
+        require(
+            msg.sender == SYSTEM_ADDRESS || msg.sender == governance,
+            "WithdrawalQueue: not finalizer"
+        );
+        _;
+    }
+
+    // ---- Constructor ----
+    constructor(address _governance) {
+        governance = _governance;
+    }
+
+    // ---- Governance ----
+    function enqueue(address recipient, uint64 amountGwei) external onlyGovernance returns (uint256 id) {
+        require(recipient != address(0), "WithdrawalQueue: zero recipient");
+        require(amountGwei > 0, "WithdrawalQueue: zero amount");
+        id = tail;
+        entries[id] = Entry(recipient, amountGwei);
+        tail = uint64(id + 1);
+        emit Enqueued(id, recipient, amountGwei);
+    }
+
+    function transferGovernance(address newGovernance) external onlyGovernance {
+        require(newGovernance != address(0), "WithdrawalQueue: zero address");
+        emit GovernanceTransferred(governance, newGovernance);
+        governance = newGovernance;
+    }
+
+    // ---- Block producer ----
+
+    /// @notice Pending entries starting at `head`, at most `max` of them.
+    function dequeue(uint256 max) external view returns (uint256[] memory ids, Entry[] memory out) {
+        uint256 pending = tail - head;
+        uint256 n = pending < max ? pending : max;
+        ids = new uint256[](n);
+        out = new Entry[](n);
+        for (uint256 i = 0; i < n; i++) {
+            ids[i] = head + i;
+            out[i] = entries[head + i];
+        }
+    }
+
+    /// @notice Mark entries as processed. Ids must be the contiguous run starting at `head`.
+    function finalize(uint256[] calldata ids) external onlyFinalizer {
+        uint64 next = head;
+        for (uint256 i = 0; i < ids.length; i++) {
+            require(ids[i] == next, "WithdrawalQueue: out of order");
+            delete entries[ids[i]];
+            emit Finalized(ids[i]);
+            next++;
+        }
+        head = next;
+    }
+
+    function pendingCount() external view returns (uint256) {
+        return tail - head;
+    }
+}
@@ -0,0 +1,167 @@
+//! Sender access control.
+//!
+//! Permissioned chains may have to refuse transactions from sanctioned addresses
+//! (e.g. OFAC lists), or admit only known participants.
+//! [`PoaChainSpec::with_acl`](super::PoaChainSpec::with_acl) installs an
+//! [`AccessControlList`] from an activation block on; consensus then rejects blocks
+//! containing a transaction whose sender is blacklisted or, with a whitelist, not
+//! whitelisted. The pool refuses such transactions at admission and the payload
+//! builder skips those admitted before the list applied.
+//!
+//! The list is a consensus rule: every node must load the same one (`--acl-file`)
+//! with the same activation block (`--acl-from-block`).
+//!
+//! File format — a JSON array of blacklisted addresses, or an object:
+//!
+//! ```json
+//! { "blacklisted": ["0x…"], "whitelisted": ["0x…"] }
+//! ```
+//!
+//! where both fields are optional and a missing `whitelisted` admits everyone not
+//! blacklisted.
+
+use alloy_primitives::Address;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Senders refused (blacklist) and, optionally, the only senders admitted (whitelist).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccessControlList {
+    /// Senders whose transactions are rejected
+    #[serde(default)]
+    pub blacklisted: HashSet<Address>,
+    /// If set, senders outside this set are rejected too
+    #[serde(default)]
+    pub whitelisted: Option<HashSet<Address>>,
+}
+
+/// Failure loading an `--acl-file`.
+#[derive(Debug, thiserror::Error)]
+pub enum AclError {
+    /// Reading the file failed
+    #[error("ACL file {path}: {source}")]
+    Io {
+        /// ACL file
+        path: PathBuf,
+        /// Underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+    /// The file is neither an address array nor an ACL object
+    #[error("Invalid ACL file {path}: {source}")]
+    Parse {
+        /// ACL file
+        path: PathBuf,
+        /// Underlying JSON error
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// On-disk forms of the list.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AclFile {
+    Blacklist(HashSet<Address>),
+    List(AccessControlList),
+}
+
+impl AccessControlList {
+    /// Reject the transactions of `addresses`.
+    pub fn blacklist(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self {
+            blacklisted: addresses.into_iter().collect(),
+            whitelisted: None,
+        }
+    }
+
+    /// Admit only the transactions of `addresses` (minus any later blacklisted).
+    pub fn whitelist(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self {
+            blacklisted: HashSet::new(),
+            whitelisted: Some(addresses.into_iter().collect()),
+        }
+    }
+
+    /// Parse the JSON [file format](self).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        Ok(match serde_json::from_str(json)? {
+            AclFile::Blacklist(blacklisted) => Self::blacklist(blacklisted),
+            AclFile::List(acl) => acl,
+        })
+    }
+
+    /// Load the list at `path` (see [`from_json`](Self::from_json)).
+    pub fn load(path: &Path) -> Result<Self, AclError> {
+        let json = std::fs::read_to_string(path).map_err(|source| AclError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_json(&json).map_err(|source| AclError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Whether `sender` is blacklisted.
+    #[inline]
+    pub fn is_blacklisted(&self, sender: &Address) -> bool {
+        self.blacklisted.contains(sender)
+    }
+
+    /// Whether `sender` passes the whitelist (always, without one).
+    #[inline]
+    pub fn is_whitelisted(&self, sender: &Address) -> bool {
+        self.whitelisted
+            .as_ref()
+            .is_none_or(|whitelisted| whitelisted.contains(sender))
+    }
+
+    /// Whether transactions from `sender` are admitted.
+    #[inline]
+    pub fn allows(&self, sender: &Address) -> bool {
+        !self.is_blacklisted(sender) && self.is_whitelisted(sender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: Address = Address::with_last_byte(0xa1);
+    const BOB: Address = Address::with_last_byte(0xb0);
+
+    #[test]
+    fn test_parse_address_array_as_blacklist() {
+        let acl = AccessControlList::from_json(&format!(r#"["{ALICE}"]"#)).unwrap();
+        assert_eq!(acl, AccessControlList::blacklist([ALICE]));
+        assert!(!acl.allows(&ALICE));
+        assert!(acl.allows(&BOB));
+    }
+
+    #[test]
+    fn test_parse_object() {
+        let json =
+            format!(r#"{{ "blacklisted": ["{ALICE}"], "whitelisted": ["{ALICE}", "{BOB}"] }}"#);
+        let acl = AccessControlList::from_json(&json).unwrap();
+        // Blacklisting wins over whitelisting.
+        assert!(!acl.allows(&ALICE));
+        assert!(acl.allows(&BOB));
+        assert!(!acl.allows(&Address::ZERO));
+
+        assert_eq!(
+            AccessControlList::from_json("{}").unwrap(),
+            AccessControlList::default()
+        );
+        assert!(AccessControlList::from_json(r#"{ "blocked": [] }"#).is_err());
+        assert!(AccessControlList::from_json(r#"["0x12"]"#).is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let err = AccessControlList::load(Path::new("/nonexistent/acl.json")).unwrap_err();
+        assert!(matches!(err, AclError::Io { .. }));
+    }
+}
@@ -0,0 +1,111 @@
+//! EIP-1559 base fee against an explicit gas target.
+//!
+//! Ethereum derives the target from the limit (`gas_limit / elasticity`), so a chain
+//! with a 1B gas limit would target 500M gas per block and the base fee would barely
+//! move under realistic load. A POA chain can instead set the target directly (e.g.
+//! 50M) and keep the limit as burst headroom: the base fee starts rising as soon as
+//! blocks exceed the target, by at most `1 / max_change_denominator` per block.
+
+use reth_chainspec::BaseFeeParams;
+
+/// Base fee of the child of a block that used `parent_gas_used` gas at
+/// `parent_base_fee`, moving toward `gas_target` per EIP-1559.
+///
+/// A zero target leaves the base fee unchanged.
+pub fn next_base_fee(
+    parent_gas_used: u64,
+    parent_base_fee: u64,
+    gas_target: u64,
+    params: BaseFeeParams,
+) -> u64 {
+    if gas_target == 0 || parent_gas_used == gas_target {
+        return parent_base_fee;
+    }
+    let denominator = params.max_change_denominator.max(1);
+    if parent_gas_used > gas_target {
+        let delta = parent_base_fee as u128 * (parent_gas_used - gas_target) as u128
+            / gas_target as u128
+            / denominator;
+        // An increase is at least 1 wei so that a zero-ish base fee can still climb.
+        let delta = delta.max(1).min(u64::MAX as u128) as u64;
+        parent_base_fee.saturating_add(delta)
+    } else {
+        let delta = parent_base_fee as u128 * (gas_target - parent_gas_used) as u128
+            / gas_target as u128
+            / denominator;
+        parent_base_fee.saturating_sub(delta as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: u64 = 1_000_000_000;
+    const TARGET: u64 = 50_000_000;
+    const GWEI: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_at_target_is_unchanged() {
+        assert_eq!(
+            next_base_fee(TARGET, GWEI, TARGET, BaseFeeParams::ethereum()),
+            GWEI
+        );
+    }
+
+    #[test]
+    fn test_sustained_double_target_rises_by_denominator() {
+        let params = BaseFeeParams::ethereum();
+        let mut base_fee = GWEI;
+        for _ in 0..10 {
+            let next = next_base_fee(2 * TARGET, base_fee, TARGET, params);
+            // Twice the target is a full "one target" overshoot: +1/denominator.
+            assert_eq!(
+                next,
+                base_fee + base_fee / params.max_change_denominator as u64
+            );
+            base_fee = next;
+        }
+        assert!(
+            base_fee > GWEI * 3 / 2,
+            "ten 12.5% steps compound past 1.5 gwei"
+        );
+
+        // Load stops: empty blocks shrink it by 1/denominator each.
+        for _ in 0..10 {
+            let next = next_base_fee(0, base_fee, TARGET, params);
+            assert_eq!(
+                next,
+                base_fee - base_fee / params.max_change_denominator as u64
+            );
+            base_fee = next;
+        }
+        assert!(base_fee < GWEI * 3 / 2);
+    }
+
+    #[test]
+    fn test_respects_configured_denominator() {
+        let params = BaseFeeParams::new(50, 2);
+        assert_eq!(next_base_fee(2 * TARGET, 1_000, TARGET, params), 1_020);
+        assert_eq!(next_base_fee(0, 1_000, TARGET, params), 980);
+    }
+
+    #[test]
+    fn test_target_decoupled_from_limit() {
+        // Under the limit-derived target (500M) a 100M block would lower the fee;
+        // against the 50M target it raises it.
+        let params = BaseFeeParams::ethereum();
+        let implied = LIMIT / params.elasticity_multiplier as u64;
+        assert!(next_base_fee(100_000_000, GWEI, implied, params) < GWEI);
+        assert!(next_base_fee(100_000_000, GWEI, TARGET, params) > GWEI);
+    }
+
+    #[test]
+    fn test_increase_is_at_least_one_wei() {
+        assert_eq!(
+            next_base_fee(TARGET + 1, 7, TARGET, BaseFeeParams::ethereum()),
+            8
+        );
+        assert_eq!(next_base_fee(TARGET, 0, 0, BaseFeeParams::ethereum()), 0);
+    }
+}
@@ -0,0 +1,120 @@
+//! EIP-4844 blob fee market parameters.
+//!
+//! Ethereum targets 3 blobs (393216 blob gas) per block with a maximum of 6
+//! (786432). The blob base fee rises exponentially while blocks use more than the
+//! target and falls back while they use less. A POA chain can pick its own market:
+//! a larger target for a data-availability heavy L2, or a target of 0 so that every
+//! blob raises the price and blobs are quickly priced out.
+
+use alloy_eips::eip4844::{
+    DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK_DENCUN, TARGET_DATA_GAS_PER_BLOCK_DENCUN,
+};
+use alloy_eips::eip7840::BlobParams;
+
+/// Blob gas market of a POA chain, in blob gas (one blob = [`DATA_GAS_PER_BLOB`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobFeeParams {
+    /// Blob gas per block above which the blob base fee rises
+    pub target_gas_per_block: u64,
+    /// Most blob gas a block may use
+    pub max_gas_per_block: u64,
+    /// Denominator of the blob base fee exponent; larger = slower price moves
+    pub update_fraction: u64,
+}
+
+impl Default for BlobFeeParams {
+    /// Ethereum's Cancun parameters.
+    fn default() -> Self {
+        Self {
+            target_gas_per_block: TARGET_DATA_GAS_PER_BLOCK_DENCUN,
+            max_gas_per_block: MAX_DATA_GAS_PER_BLOCK_DENCUN,
+            update_fraction: BlobParams::cancun().update_fraction as u64,
+        }
+    }
+}
+
+impl BlobFeeParams {
+    /// `base` (a fork's default blob parameters) with this market's target, maximum
+    /// and update fraction. Gas amounts are rounded down to whole blobs.
+    pub fn apply(&self, base: BlobParams) -> BlobParams {
+        let max_blob_count = self.max_gas_per_block / DATA_GAS_PER_BLOB;
+        BlobParams {
+            target_blob_count: self.target_gas_per_block / DATA_GAS_PER_BLOB,
+            max_blob_count,
+            max_blobs_per_tx: base.max_blobs_per_tx.min(max_blob_count),
+            update_fraction: self.update_fraction.max(1) as u128,
+            ..base
+        }
+    }
+
+    /// Excess blob gas of the child of a block with `parent_excess_blob_gas` that
+    /// used `parent_blob_gas_used` (EIP-4844 `calc_excess_blob_gas`).
+    pub fn next_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u64,
+        parent_blob_gas_used: u64,
+    ) -> u64 {
+        (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(self.target_gas_per_block)
+    }
+
+    /// Blob base fee (wei per blob gas) at `excess_blob_gas`.
+    pub fn blob_base_fee(&self, excess_blob_gas: u64) -> u128 {
+        self.apply(BlobParams::cancun())
+            .calc_blob_fee(excess_blob_gas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Excess blob gas and blob base fee of `blocks` consecutive blocks each using
+    /// one blob.
+    fn one_blob_per_block(params: BlobFeeParams, blocks: usize) -> Vec<(u64, u128)> {
+        let mut excess = 0;
+        (0..blocks)
+            .map(|_| {
+                excess = params.next_excess_blob_gas(excess, DATA_GAS_PER_BLOB);
+                (excess, params.blob_base_fee(excess))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_zero_target_prices_blobs_out() {
+        let params = BlobFeeParams {
+            target_gas_per_block: 0,
+            ..Default::default()
+        };
+        let blocks = one_blob_per_block(params, 100);
+        // With a zero target every blob is above target, so the price only climbs.
+        assert!(blocks
+            .windows(2)
+            .all(|w| w[1].0 > w[0].0 && w[1].1 >= w[0].1));
+        // A single blob per block multiplies the price by ~1.04 each block.
+        assert!(blocks[99].1 > 40 * blocks[0].1, "{blocks:?}");
+
+        // At Ethereum's target of 3 blobs, one blob per block never moves the price.
+        let blocks = one_blob_per_block(BlobFeeParams::default(), 100);
+        assert!(blocks
+            .iter()
+            .all(|&(excess, fee)| excess == 0 && fee == blocks[0].1));
+    }
+
+    #[test]
+    fn test_apply_blob_counts() {
+        let params = BlobFeeParams::default().apply(BlobParams::cancun());
+        assert_eq!(params, BlobParams::cancun());
+
+        let params = BlobFeeParams {
+            target_gas_per_block: 0,
+            max_gas_per_block: 2 * DATA_GAS_PER_BLOB,
+            update_fraction: 1_000_000,
+        }
+        .apply(BlobParams::cancun());
+        assert_eq!(params.target_blob_count, 0);
+        assert_eq!(params.max_blob_count, 2);
+        assert_eq!(params.max_blobs_per_tx, 2);
+        assert_eq!(params.update_fraction, 1_000_000);
+    }
+}
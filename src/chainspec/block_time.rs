@@ -0,0 +1,110 @@
+//! Achieved block times.
+//!
+//! `block_period` is the target spacing; out-of-turn delays, slow builds and clock
+//! drift make the spacing actually achieved differ. These helpers measure it from the
+//! timestamps of consecutive headers (oldest first):
+//! [`PoaChainSpec::effective_block_time`](super::PoaChainSpec::effective_block_time)
+//! and [`PoaChainSpec::effective_block_time_p99`](super::PoaChainSpec::effective_block_time_p99).
+
+use alloy_consensus::Header;
+
+/// Mean and spread of the intervals between consecutive headers, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockTimeStats {
+    /// Mean interval
+    pub mean: f64,
+    /// Population standard deviation of the intervals
+    pub std_dev: f64,
+}
+
+/// Timestamp differences of consecutive `headers` (0 where a timestamp goes back).
+pub fn block_intervals(headers: &[Header]) -> Vec<u64> {
+    headers
+        .windows(2)
+        .map(|pair| pair[1].timestamp.saturating_sub(pair[0].timestamp))
+        .collect()
+}
+
+/// Mean and standard deviation of the block intervals; `None` below 2 headers.
+pub fn block_time_stats(headers: &[Header]) -> Option<BlockTimeStats> {
+    let intervals = block_intervals(headers);
+    if intervals.is_empty() {
+        return None;
+    }
+    let n = intervals.len() as f64;
+    let mean = intervals.iter().map(|&i| i as f64).sum::<f64>() / n;
+    let variance = intervals
+        .iter()
+        .map(|&i| (i as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    Some(BlockTimeStats {
+        mean,
+        std_dev: variance.sqrt(),
+    })
+}
+
+/// 99th percentile block interval (nearest rank); `None` below 2 headers.
+pub fn block_time_p99(headers: &[Header]) -> Option<u64> {
+    let mut intervals = block_intervals(headers);
+    if intervals.is_empty() {
+        return None;
+    }
+    intervals.sort_unstable();
+    let rank = (intervals.len() * 99).div_ceil(100);
+    Some(intervals[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(timestamps: impl IntoIterator<Item = u64>) -> Vec<Header> {
+        timestamps
+            .into_iter()
+            .map(|timestamp| Header {
+                timestamp,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_regular_sequence() {
+        let regular = headers((0..100).map(|i| 1_700_000_000 + i * 2));
+        let stats = block_time_stats(&regular).unwrap();
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(block_time_p99(&regular), Some(2));
+    }
+
+    #[test]
+    fn test_jittery_sequence() {
+        // 197 intervals alternating 1s and 3s, then three 10s stalls.
+        let mut timestamps = vec![0];
+        for i in 0..200u64 {
+            let interval = match i {
+                197.. => 10,
+                _ if i % 2 == 0 => 1,
+                _ => 3,
+            };
+            timestamps.push(timestamps.last().unwrap() + interval);
+        }
+        let jittery = headers(timestamps);
+        let stats = block_time_stats(&jittery).unwrap();
+        assert!((stats.mean - 2.115).abs() < 1e-9, "{stats:?}");
+        assert!(stats.std_dev > 1.0, "{stats:?}");
+        // Three stalls in 200 intervals reach the 99th percentile; one in 198 does not.
+        assert_eq!(block_time_p99(&jittery), Some(10));
+        assert_eq!(block_time_p99(&jittery[..199]), Some(3));
+    }
+
+    #[test]
+    fn test_too_few_headers() {
+        assert_eq!(block_time_stats(&[]), None);
+        assert_eq!(block_time_stats(&headers([5])), None);
+        assert_eq!(block_time_p99(&headers([5])), None);
+        // A timestamp going backwards counts as a zero interval.
+        assert_eq!(block_intervals(&headers([5, 3, 6])), vec![0, 3]);
+    }
+}
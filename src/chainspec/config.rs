@@ -22,3 +22,17 @@ impl Default for PoaConfig {
         }
     }
 }
+
+/// Governance-controlled EIP-4895 withdrawal source.
+///
+/// When set on the chain spec, the payload builder includes up to `max_per_block`
+/// pending entries from the `WithdrawalQueue` contract at `queue_address` in every
+/// block it builds (see `genesis-contracts/WithdrawalQueue.sol`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalConfig {
+    /// Address of the deployed WithdrawalQueue contract
+    pub queue_address: Address,
+    /// Maximum queue entries included per block
+    pub max_per_block: usize,
+}
@@ -0,0 +1,73 @@
+//! Fee market selection.
+//!
+//! A POA chain picks one of three fee markets with
+//! [`PoaChainSpec::with_fee_market`](super::PoaChainSpec::with_fee_market):
+//!
+//! | Market    | Base fee | Blobs | Transactions                         |
+//! |-----------|----------|-------|--------------------------------------|
+//! | `Legacy`  | none     | none  | legacy / EIP-2930 only (fixed price) |
+//! | `Eip1559` | yes      | none  | any non-blob type                    |
+//! | `Eip4844` | yes      | yes   | any type, blob fees per [`BlobFeeParams`] |
+//!
+//! Consensus enforces the choice: `Legacy` headers carry no base fee and blocks with
+//! dynamic-fee transactions are rejected, `Eip1559` headers may not use blob gas, and
+//! `Eip4844` headers must stay within the blob maximum and follow the blob fee
+//! market's excess blob gas. A chain without a fee market keeps the blob maximum and
+//! excess blob gas of its fork's default blob parameters.
+
+use super::blob_fee::BlobFeeParams;
+use reth_chainspec::BaseFeeParams;
+
+/// EIP-1559 base fee configuration of a fee market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaseFeeConfig {
+    /// Per-block gas target; None = Ethereum's `gas_limit / elasticity`
+    pub gas_target: Option<u64>,
+    /// Change denominator and elasticity
+    pub params: BaseFeeParams,
+}
+
+impl Default for BaseFeeConfig {
+    /// Ethereum mainnet's base fee rules.
+    fn default() -> Self {
+        Self {
+            gas_target: None,
+            params: BaseFeeParams::ethereum(),
+        }
+    }
+}
+
+/// Fee market of a POA chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeMarket {
+    /// Fixed gas price: no base fee, no dynamic-fee or blob transactions
+    Legacy,
+    /// Base fee plus priority fee, without blobs
+    Eip1559(BaseFeeConfig),
+    /// Base fee plus a blob fee market
+    Eip4844(BaseFeeConfig, BlobFeeParams),
+}
+
+impl FeeMarket {
+    /// Base fee configuration, `None` for [`FeeMarket::Legacy`].
+    pub fn base_fee(&self) -> Option<&BaseFeeConfig> {
+        match self {
+            Self::Legacy => None,
+            Self::Eip1559(base_fee) | Self::Eip4844(base_fee, _) => Some(base_fee),
+        }
+    }
+
+    /// Blob fee market, `None` when blobs are not allowed.
+    pub fn blob_fee_params(&self) -> Option<&BlobFeeParams> {
+        match self {
+            Self::Eip4844(_, blob) => Some(blob),
+            _ => None,
+        }
+    }
+
+    /// Most blob gas a block may use.
+    pub fn max_blob_gas_per_block(&self) -> u64 {
+        self.blob_fee_params()
+            .map_or(0, |blob| blob.max_gas_per_block)
+    }
+}
@@ -0,0 +1,84 @@
+//! Gradual gas limit changes requested by governance.
+//!
+//! The EIP-1559 drift rule lets the gas limit move by `parent_gas_limit / 1024` per
+//! block, so raising it from 30M to 1B would take thousands of blocks, while letting
+//! a block jump straight to the new limit would be invalid everywhere else. With a
+//! ramp of `N` blocks the change is instead spread evenly over the first `N` blocks
+//! after each epoch block: block `i` of the ramp may move the limit up to
+//! [`ramp_target`] with `N - i + 1` blocks remaining, toward the ChainConfig gas limit
+//! in the parent state, which reaches the governance value on the ramp's last block.
+//! Past the ramp only the drift applies, so a change made mid-epoch waits for the
+//! next ramp rather than landing in one block.
+
+/// Gas limit for the next block of a ramp from `current` to `target` with
+/// `blocks_remaining` blocks left (this one included).
+///
+/// Each block covers at least `1 / blocks_remaining` of the remaining distance
+/// (rounded up), so the ramp lands exactly on `target` after `blocks_remaining`
+/// blocks. With 0 or 1 blocks remaining the target is returned directly.
+pub fn ramp_target(current: u64, target: u64, blocks_remaining: u64) -> u64 {
+    if blocks_remaining <= 1 || current == target {
+        return target;
+    }
+    if target > current {
+        current + (target - current).div_ceil(blocks_remaining)
+    } else {
+        current - (current - target).div_ceil(blocks_remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walk a ramp block by block, returning every intermediate gas limit.
+    fn ramp(mut current: u64, target: u64, blocks: u64) -> Vec<u64> {
+        (0..blocks)
+            .map(|i| {
+                current = ramp_target(current, target, blocks - i);
+                current
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ramp_up_converges_on_last_block() {
+        let steps = ramp(30_000_000, 1_000_000_000, 100);
+        assert_eq!(steps.len(), 100);
+        assert_eq!(*steps.last().unwrap(), 1_000_000_000);
+        assert!(steps[98] < 1_000_000_000, "target reached early");
+        assert!(steps.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_ramp_down_converges_on_last_block() {
+        let steps = ramp(1_000_000_000, 30_000_000, 7);
+        assert_eq!(*steps.last().unwrap(), 30_000_000);
+        assert!(steps.windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_ramp_steps_are_even() {
+        // 9_700 over 97 blocks: exactly 100 gas per block.
+        let steps = ramp(10_000, 19_700, 97);
+        assert!(steps
+            .iter()
+            .enumerate()
+            .all(|(i, gas)| *gas == 10_000 + 100 * (i as u64 + 1)));
+    }
+
+    #[test]
+    fn test_ramp_uneven_distance_still_lands() {
+        let steps = ramp(5_000, 5_007, 3);
+        assert_eq!(steps, vec![5_003, 5_005, 5_007]);
+    }
+
+    #[test]
+    fn test_ramp_target_edges() {
+        assert_eq!(ramp_target(30_000_000, 60_000_000, 0), 60_000_000);
+        assert_eq!(ramp_target(30_000_000, 60_000_000, 1), 60_000_000);
+        assert_eq!(ramp_target(30_000_000, 30_000_000, 10), 30_000_000);
+        // Distances smaller than the block count still move by at least 1 gas.
+        assert_eq!(ramp_target(5_000, 5_002, 10), 5_001);
+    }
+}
@@ -0,0 +1,282 @@
+//! Importing geth Clique `genesis.json` files (`--genesis-file`).
+//!
+//! A Clique genesis carries its signers in `extraData`:
+//!
+//! ```text
+//! [vanity (32 bytes)][signer addresses (N * 20 bytes)][zero seal (65 bytes)]
+//! ```
+//!
+//! and the block period and epoch length in `config.clique`. Every hardfork of a
+//! [`PoaChainSpec`](super::PoaChainSpec) is active from genesis, so files leaving a
+//! fork out, scheduling one after genesis, or configuring proof of work, are refused
+//! rather than run under different rules and with a different genesis hash. geth's
+//! Clique engine stops at London, so a file written for geth needs the later forks
+//! (and `terminalTotalDifficulty: 0`) added before this chain runs it.
+
+use alloy_genesis::ChainConfig;
+use alloy_primitives::Address;
+use std::path::PathBuf;
+
+/// Vanity bytes before the signer list.
+const EXTRA_VANITY: usize = 32;
+/// Seal bytes after the signer list.
+const EXTRA_SEAL: usize = 65;
+
+/// Failure importing a genesis file.
+#[derive(Debug, thiserror::Error)]
+pub enum GenesisError {
+    /// Reading the file failed
+    #[error("Genesis file {path}: {source}")]
+    Io {
+        /// Genesis file
+        path: PathBuf,
+        /// Underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+    /// The file is not a genesis JSON object
+    #[error("Invalid genesis JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    /// `extraData` is not vanity, at least one signer and a seal
+    #[error(
+        "Malformed genesis extraData of {0} bytes: expected 32 vanity bytes, \
+         20 bytes per signer (at least one) and 65 seal bytes"
+    )]
+    MalformedExtraData(usize),
+    /// `config.clique` or its `period` is missing
+    #[error("Genesis config has no clique section with a period")]
+    MissingCliqueConfig,
+    /// A fork this chain activates at genesis is absent from the config
+    #[error("Genesis config has no {0}; this chain activates it at genesis")]
+    MissingHardfork(&'static str),
+    /// The genesis asks for rules this chain does not implement
+    #[error("Unsupported genesis feature: {0}")]
+    UnsupportedFeature(String),
+}
+
+/// Signers embedded in a Clique genesis `extraData`.
+pub fn clique_signers(extra_data: &[u8]) -> Result<Vec<Address>, GenesisError> {
+    let len = extra_data.len();
+    let signers_len = len
+        .checked_sub(EXTRA_VANITY + EXTRA_SEAL)
+        .filter(|&signers_len| signers_len > 0 && signers_len % Address::len_bytes() == 0)
+        .ok_or(GenesisError::MalformedExtraData(len))?;
+    Ok(extra_data[EXTRA_VANITY..EXTRA_VANITY + signers_len]
+        .chunks_exact(Address::len_bytes())
+        .map(Address::from_slice)
+        .collect())
+}
+
+/// Refuse chain configs whose rules differ from the genesis-activated hardforks.
+pub fn check_supported(config: &ChainConfig) -> Result<(), GenesisError> {
+    if config.ethash.is_some() {
+        return Err(GenesisError::UnsupportedFeature(
+            "ethash proof of work".into(),
+        ));
+    }
+    if let Some(dao_fork) = config.dao_fork_block.filter(|&block| block > 0) {
+        return Err(GenesisError::UnsupportedFeature(format!(
+            "daoForkBlock {dao_fork}: hardforks after genesis"
+        )));
+    }
+    let block_forks = [
+        ("homesteadBlock", config.homestead_block),
+        ("eip150Block", config.eip150_block),
+        ("eip155Block", config.eip155_block),
+        ("eip158Block", config.eip158_block),
+        ("byzantiumBlock", config.byzantium_block),
+        ("constantinopleBlock", config.constantinople_block),
+        ("petersburgBlock", config.petersburg_block),
+        ("istanbulBlock", config.istanbul_block),
+        ("berlinBlock", config.berlin_block),
+        ("londonBlock", config.london_block),
+    ];
+    let time_forks = [
+        ("shanghaiTime", config.shanghai_time),
+        ("cancunTime", config.cancun_time),
+        ("pragueTime", config.prague_time),
+    ];
+    for (name, activation) in block_forks.into_iter().chain(time_forks) {
+        match activation {
+            Some(0) => {}
+            Some(activation) => {
+                return Err(GenesisError::UnsupportedFeature(format!(
+                    "{name} {activation}: hardforks after genesis"
+                )))
+            }
+            None => return Err(GenesisError::MissingHardfork(name)),
+        }
+    }
+    // Paris: the merge rules (PREVRANDAO) apply from genesis.
+    match config.terminal_total_difficulty {
+        Some(ttd) if ttd.is_zero() => {}
+        Some(ttd) => {
+            return Err(GenesisError::UnsupportedFeature(format!(
+                "terminalTotalDifficulty {ttd}: the merge after genesis"
+            )))
+        }
+        None => return Err(GenesisError::MissingHardfork("terminalTotalDifficulty")),
+    }
+    if config.osaka_time.is_some() {
+        return Err(GenesisError::UnsupportedFeature("osakaTime".into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::{PoaChainSpec, PoaConfig};
+    use serde_json::json;
+
+    /// Fork fields of a config that activates every fork of this chain at genesis.
+    const FORKS: [&str; 14] = [
+        "homesteadBlock",
+        "eip150Block",
+        "eip155Block",
+        "eip158Block",
+        "byzantiumBlock",
+        "constantinopleBlock",
+        "petersburgBlock",
+        "istanbulBlock",
+        "berlinBlock",
+        "londonBlock",
+        "terminalTotalDifficulty",
+        "shanghaiTime",
+        "cancunTime",
+        "pragueTime",
+    ];
+
+    /// A `genesis.json` for a two-signer Clique network with every fork at genesis.
+    fn geth_genesis(config: &str, extra_data: &str) -> String {
+        let forks: String = FORKS
+            .iter()
+            .map(|fork| format!(r#""{fork}": 0, "#))
+            .collect();
+        format!(
+            r#"{{
+                "config": {{ "chainId": 1337, {forks}{config} }},
+                "nonce": "0x0",
+                "timestamp": "0x0",
+                "difficulty": "0x1",
+                "gasLimit": "0x1c9c380",
+                "extraData": "{extra_data}",
+                "alloc": {{
+                    "0x1111111111111111111111111111111111111111": {{ "balance": "0x64" }}
+                }}
+            }}"#
+        )
+    }
+
+    /// `json` with config field `field` set to `value`, or removed if `value` is null.
+    fn with_config_field(json: &str, field: &str, value: serde_json::Value) -> String {
+        let mut genesis: serde_json::Value = serde_json::from_str(json).unwrap();
+        let config = genesis["config"].as_object_mut().unwrap();
+        if value.is_null() {
+            config.remove(field);
+        } else {
+            config.insert(field.to_string(), value);
+        }
+        genesis.to_string()
+    }
+
+    fn two_signer_extra_data() -> String {
+        format!(
+            "0x{}{}{}{}",
+            "00".repeat(32),
+            "aa".repeat(20),
+            "bb".repeat(20),
+            "00".repeat(65)
+        )
+    }
+
+    #[test]
+    fn test_from_genesis_json_reads_clique_network() {
+        let json = geth_genesis(
+            r#""clique": { "period": 5, "epoch": 30000 }"#,
+            &two_signer_extra_data(),
+        );
+        let chain = PoaChainSpec::from_genesis_json(&json).unwrap();
+        assert_eq!(
+            chain.signers(),
+            [Address::repeat_byte(0xaa), Address::repeat_byte(0xbb)]
+        );
+        assert_eq!(chain.block_period(), 5);
+        assert_eq!(chain.epoch(), 30000);
+        assert_eq!(chain.inner().chain.id(), 1337);
+        assert_eq!(chain.inner().genesis.gas_limit, 30_000_000);
+
+        // geth's default epoch applies when it is left out.
+        let json = geth_genesis(r#""clique": { "period": 2 }"#, &two_signer_extra_data());
+        let chain = PoaChainSpec::from_genesis_json(&json).unwrap();
+        assert_eq!(chain.epoch(), PoaConfig::default().epoch);
+    }
+
+    #[test]
+    fn test_from_genesis_json_round_trips_alloy_genesis() {
+        let chain = PoaChainSpec::dev_chain().with_chain_id(4242);
+        let json = serde_json::to_string(&chain.to_alloy_genesis()).unwrap();
+        let imported = PoaChainSpec::from_genesis_json(&json).unwrap();
+        assert_eq!(imported.signers(), chain.signers());
+        assert_eq!(imported.block_period(), chain.block_period());
+        assert_eq!(imported.inner().chain.id(), 4242);
+        assert_eq!(imported.inner().genesis.alloc, chain.inner().genesis.alloc);
+    }
+
+    #[test]
+    fn test_from_genesis_json_rejects_bad_files() {
+        let clique = r#""clique": { "period": 5, "epoch": 30000 }"#;
+        for extra_data in [
+            format!("0x{}", "00".repeat(97)),
+            format!("0x{}", "00".repeat(96)),
+            format!(
+                "0x{}{}{}",
+                "00".repeat(32),
+                "aa".repeat(19),
+                "00".repeat(65)
+            ),
+        ] {
+            assert!(matches!(
+                PoaChainSpec::from_genesis_json(&geth_genesis(clique, &extra_data)),
+                Err(GenesisError::MalformedExtraData(_))
+            ));
+        }
+
+        let json = geth_genesis(clique, &two_signer_extra_data());
+        assert!(matches!(
+            PoaChainSpec::from_genesis_json(&with_config_field(&json, "clique", json!(null))),
+            Err(GenesisError::MissingCliqueConfig)
+        ));
+        for (field, value) in [
+            ("berlinBlock", json!(100)),
+            ("shanghaiTime", json!(1_700_000_000)),
+            ("terminalTotalDifficulty", json!(17_000_000)),
+            ("daoForkBlock", json!(1_920_000)),
+            ("ethash", json!({})),
+        ] {
+            assert!(matches!(
+                PoaChainSpec::from_genesis_json(&with_config_field(&json, field, value)),
+                Err(GenesisError::UnsupportedFeature(_))
+            ));
+        }
+        assert!(matches!(
+            PoaChainSpec::from_genesis_json("{ not json"),
+            Err(GenesisError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_genesis_json_requires_every_fork() {
+        // A pre-Shanghai geth Clique file would run under Prague rules otherwise.
+        let json = geth_genesis(
+            r#""clique": { "period": 5, "epoch": 30000 }"#,
+            &two_signer_extra_data(),
+        );
+        for fork in FORKS {
+            assert!(matches!(
+                PoaChainSpec::from_genesis_json(&with_config_field(&json, fork, json!(null))),
+                Err(GenesisError::MissingHardfork(missing)) if missing == fork
+            ));
+        }
+    }
+}
@@ -1,5 +1,6 @@
+use alloy_evm::revm::primitives::hardfork::SpecId;
 use alloy_primitives::U256;
-use reth_chainspec::{ChainHardforks, ForkCondition, Hardfork};
+use reth_chainspec::{ChainHardforks, EthereumHardforks, ForkCondition, Hardfork};
 use reth_ethereum_forks::EthereumHardfork;
 
 /// Creates hardforks configuration that matches Ethereum mainnet.
@@ -52,3 +53,60 @@ pub fn mainnet_compatible_hardforks() -> ChainHardforks {
         ),
     ])
 }
+
+/// EVM spec of a hardfork named in lowercase, e.g. `"cancun"`, `"spurious-dragon"`.
+///
+/// `paris` and `merge` both name [`SpecId::MERGE`].
+pub fn spec_id_by_name(name: &str) -> Option<SpecId> {
+    let spec = match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+        "frontier" => SpecId::FRONTIER,
+        "homestead" => SpecId::HOMESTEAD,
+        "tangerine" | "tangerine-whistle" => SpecId::TANGERINE,
+        "spurious-dragon" => SpecId::SPURIOUS_DRAGON,
+        "byzantium" => SpecId::BYZANTIUM,
+        "constantinople" => SpecId::CONSTANTINOPLE,
+        "petersburg" => SpecId::PETERSBURG,
+        "istanbul" => SpecId::ISTANBUL,
+        "berlin" => SpecId::BERLIN,
+        "london" => SpecId::LONDON,
+        "paris" | "merge" => SpecId::MERGE,
+        "shanghai" => SpecId::SHANGHAI,
+        "cancun" => SpecId::CANCUN,
+        "prague" => SpecId::PRAGUE,
+        "osaka" => SpecId::OSAKA,
+        _ => return None,
+    };
+    Some(spec)
+}
+
+/// Latest EVM spec active at `timestamp` / `block_number` for the given chain spec.
+pub fn spec_id_at(
+    chain_spec: &impl EthereumHardforks,
+    timestamp: u64,
+    block_number: u64,
+) -> SpecId {
+    const SPECS: &[(EthereumHardfork, SpecId)] = &[
+        (EthereumHardfork::Osaka, SpecId::OSAKA),
+        (EthereumHardfork::Prague, SpecId::PRAGUE),
+        (EthereumHardfork::Cancun, SpecId::CANCUN),
+        (EthereumHardfork::Shanghai, SpecId::SHANGHAI),
+        (EthereumHardfork::Paris, SpecId::MERGE),
+        (EthereumHardfork::London, SpecId::LONDON),
+        (EthereumHardfork::Berlin, SpecId::BERLIN),
+        (EthereumHardfork::Istanbul, SpecId::ISTANBUL),
+        (EthereumHardfork::Petersburg, SpecId::PETERSBURG),
+        (EthereumHardfork::Byzantium, SpecId::BYZANTIUM),
+        (EthereumHardfork::SpuriousDragon, SpecId::SPURIOUS_DRAGON),
+        (EthereumHardfork::Tangerine, SpecId::TANGERINE),
+        (EthereumHardfork::Homestead, SpecId::HOMESTEAD),
+    ];
+    SPECS
+        .iter()
+        .find(|(fork, _)| {
+            chain_spec
+                .ethereum_fork_activation(*fork)
+                .active_at_timestamp_or_number(timestamp, block_number)
+        })
+        .map(|(_, spec)| *spec)
+        .unwrap_or(SpecId::FRONTIER)
+}
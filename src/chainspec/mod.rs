@@ -3,15 +3,36 @@
 //! This module defines the chain specification for a POA network that maintains
 //! full compatibility with Ethereum mainnet's EVM and hardforks.
 
+pub mod acl;
+pub mod base_fee;
+pub mod blob_fee;
+pub mod block_time;
 pub mod config;
+pub mod fee_market;
+pub mod gas_ramp;
+pub mod genesis_json;
 pub mod hardforks;
-
-pub use config::PoaConfig;
+pub mod pruning;
+pub mod rewards;
+
+pub use acl::AccessControlList;
+pub use blob_fee::BlobFeeParams;
+pub use block_time::BlockTimeStats;
+pub use config::{PoaConfig, WithdrawalConfig};
+pub use fee_market::{BaseFeeConfig, FeeMarket};
+pub use genesis_json::GenesisError;
+pub use pruning::PruningConfig;
+pub use rewards::UncleRewardConfig;
 
 use alloy_consensus::Header;
+use alloy_eips::eip2935::HISTORY_STORAGE_ADDRESS;
+use alloy_eips::eip4788::BEACON_ROOTS_ADDRESS;
 use alloy_eips::eip7840::BlobParams;
-use alloy_genesis::Genesis;
-use alloy_primitives::{Address, B256, U256};
+use alloy_evm::revm::bytecode::Bytecode;
+use alloy_evm::revm::database::InMemoryDB;
+use alloy_evm::revm::state::AccountInfo;
+use alloy_genesis::{CliqueConfig, Genesis, GenesisAccount};
+use alloy_primitives::{keccak256, Address, B256, U256};
 use reth_chainspec::{
     BaseFeeParams, BaseFeeParamsKind, Chain, ChainSpec, DepositContract, EthChainSpec,
     EthereumHardforks, ForkCondition, ForkFilter, ForkId, Hardfork, Hardforks, Head,
@@ -19,8 +40,13 @@ use reth_chainspec::{
 use reth_ethereum_forks::EthereumHardfork;
 use reth_network_peers::NodeRecord;
 use reth_primitives_traits::SealedHeader;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
 
+/// Event emitted by the deposit contract for every deposit (EIP-6110).
+pub const DEPOSIT_EVENT_SIGNATURE: &str = "DepositEvent(bytes,bytes,bytes,bytes,bytes)";
+
 /// Custom POA chain specification
 #[derive(Debug, Clone)]
 pub struct PoaChainSpec {
@@ -34,6 +60,44 @@ pub struct PoaChainSpec {
     live_signers: Arc<RwLock<Option<Vec<Address>>>>,
     /// Static bootnodes for P2P peer discovery.
     boot_nodes: Vec<NodeRecord>,
+    /// Optional governance withdrawal queue (EIP-4895 credits from a contract).
+    withdrawal_config: Option<WithdrawalConfig>,
+    /// Reject headers whose vanity is not printable ASCII (see `consensus::vanity`).
+    strict_vanity: bool,
+    /// Legacy gas-only chain: London (EIP-1559) is not activated and headers carry no base fee.
+    eip1559_disabled: bool,
+    /// Production network: dev-only operations (dev keys, dev mode) are refused.
+    production: bool,
+    /// Per-block EIP-1559 gas target; None = Ethereum's `gas_limit / elasticity`.
+    gas_target: Option<u64>,
+    /// Base fee pinned on every post-London block; None = EIP-1559 adjustment.
+    base_fee_override: Option<u64>,
+    /// First block the `base_fee_override` applies to.
+    base_fee_override_block: u64,
+    /// Root answered by the stubbed EIP-4788 beacon roots contract; None = canonical contract.
+    eip4788_stub_root: Option<B256>,
+    /// Blocks a governance gas limit change is spread over; 0 = EIP-1559 drift only.
+    max_gas_ramp_blocks: u64,
+    /// Maximum RLP size of a block's transaction list in bytes; None = unlimited.
+    max_block_size_bytes: Option<usize>,
+    /// Serve the EIP-2537 BLS12-381 precompiles before Prague; false = fork schedule.
+    eip2537_enabled: bool,
+    /// First block whose EVMs start with the coinbase warm; None = never.
+    coinbase_warming_block: Option<u64>,
+    /// First block whose seal must have a low `s`; None = any `s` accepted.
+    low_s_seal_block: Option<u64>,
+    /// Fee market chosen with `with_fee_market`; None = Ethereum's rules, unchecked.
+    fee_market: Option<FeeMarket>,
+    /// Historical state retention; None = the node's `--archive` setting decides.
+    state_pruning: Option<PruningConfig>,
+    /// Sender blacklist / whitelist enforced by consensus; None = every sender admitted.
+    acl: Option<Arc<AccessControlList>>,
+    /// First block the `acl` applies to.
+    acl_activation_block: u64,
+    /// Balance every block signer must hold in the parent state; zero = no stake.
+    min_signer_stake_wei: U256,
+    /// Reward withdrawn to the signer of every block; None = no reward.
+    uncle_reward: Option<UncleRewardConfig>,
 }
 
 impl PoaChainSpec {
@@ -62,6 +126,25 @@ impl PoaChainSpec {
             poa_config,
             live_signers: Arc::new(RwLock::new(None)),
             boot_nodes: Vec::new(),
+            withdrawal_config: None,
+            strict_vanity: false,
+            eip1559_disabled: false,
+            production: false,
+            gas_target: None,
+            base_fee_override: None,
+            base_fee_override_block: 0,
+            eip4788_stub_root: None,
+            max_gas_ramp_blocks: 0,
+            max_block_size_bytes: None,
+            eip2537_enabled: false,
+            coinbase_warming_block: None,
+            low_s_seal_block: None,
+            fee_market: None,
+            state_pruning: None,
+            acl: None,
+            acl_activation_block: 0,
+            min_signer_stake_wei: U256::ZERO,
+            uncle_reward: None,
         }
     }
 
@@ -76,6 +159,36 @@ impl PoaChainSpec {
         Self::new(genesis, poa_config)
     }
 
+    /// Import a geth Clique `genesis.json` (see [`genesis_json`]).
+    ///
+    /// The signers come from `extraData`, the block period and epoch from
+    /// `config.clique` (epoch 0 or absent: geth's 30000) and the chain ID from
+    /// `config.chainId`.
+    pub fn from_genesis_json(json: &str) -> Result<Self, GenesisError> {
+        let genesis: Genesis = serde_json::from_str(json)?;
+        let clique = genesis.config.clique.ok_or(GenesisError::MissingCliqueConfig)?;
+        let period = clique.period.ok_or(GenesisError::MissingCliqueConfig)?;
+        genesis_json::check_supported(&genesis.config)?;
+        let poa_config = PoaConfig {
+            period,
+            epoch: clique
+                .epoch
+                .filter(|&epoch| epoch > 0)
+                .unwrap_or(PoaConfig::default().epoch),
+            signers: genesis_json::clique_signers(&genesis.extra_data)?,
+        };
+        Ok(Self::new(genesis, poa_config))
+    }
+
+    /// Load the genesis file at `path` (see [`from_genesis_json`](Self::from_genesis_json)).
+    pub fn from_genesis_file(path: &Path) -> Result<Self, GenesisError> {
+        let json = std::fs::read_to_string(path).map_err(|source| GenesisError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_genesis_json(&json)
+    }
+
     /// Returns the inner ChainSpec
     #[inline]
     pub fn inner(&self) -> &Arc<ChainSpec> {
@@ -153,18 +266,538 @@ impl PoaChainSpec {
         self.poa_config.period
     }
 
+    /// Mean achieved block time in seconds over consecutive `headers` (oldest first).
+    ///
+    /// `None` for fewer than 2 headers. See [`block_time`] for the spread.
+    pub fn effective_block_time(headers: &[Header]) -> Option<f64> {
+        block_time::block_time_stats(headers).map(|stats| stats.mean)
+    }
+
+    /// 99th percentile block time in seconds over consecutive `headers`.
+    ///
+    /// `None` for fewer than 2 headers.
+    pub fn effective_block_time_p99(headers: &[Header]) -> Option<u64> {
+        block_time::block_time_p99(headers)
+    }
+
     /// Returns the epoch length
     #[inline]
     pub fn epoch(&self) -> u64 {
         self.poa_config.epoch
     }
 
+    /// Replace the chain ID, keeping every other parameter.
+    ///
+    /// The chain ID is not part of the genesis header, so the genesis hash is unchanged.
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        let mut inner = (*self.inner).clone();
+        inner.chain = Chain::from_id(chain_id);
+        inner.genesis.config.chain_id = chain_id;
+        self.inner = Arc::new(inner);
+        self
+    }
+
+    /// Mark this spec as a production network.
+    ///
+    /// The dev chain shares the default chain ID, so production is declared explicitly
+    /// rather than inferred from the ID.
+    pub fn with_production(mut self, production: bool) -> Self {
+        self.production = production;
+        self
+    }
+
+    /// Whether this spec describes a production network, on which dev signers and
+    /// dev mode are refused.
+    #[inline]
+    pub fn is_production(&self) -> bool {
+        self.production
+    }
+
     /// Set static bootnodes for P2P peer discovery.
     pub fn with_bootnodes(mut self, bootnodes: Vec<NodeRecord>) -> Self {
         self.boot_nodes = bootnodes;
         self
     }
 
+    /// Merge dev account balances with a production genesis allocation.
+    ///
+    /// For hybrid test environments that need both easy-to-use funded dev accounts
+    /// and the production contracts (governance, vesting, system contracts).
+    /// Dev accounts become plain EOAs (`nonce = 0`, no code, no storage).
+    /// On address collision the production entry wins.
+    pub fn resolve_genesis_alloc(
+        dev_alloc: HashMap<Address, U256>,
+        prod_alloc: HashMap<Address, GenesisAccount>,
+    ) -> HashMap<Address, GenesisAccount> {
+        let mut merged: HashMap<Address, GenesisAccount> = dev_alloc
+            .into_iter()
+            .map(|(address, balance)| {
+                let account = GenesisAccount {
+                    balance,
+                    nonce: Some(0),
+                    code: None,
+                    storage: None,
+                    private_key: None,
+                };
+                (address, account)
+            })
+            .collect();
+        merged.extend(prod_alloc);
+        merged
+    }
+
+    /// Enable withdrawals sourced from a governance-controlled queue contract.
+    pub fn with_withdrawal_config(mut self, config: WithdrawalConfig) -> Self {
+        self.withdrawal_config = Some(config);
+        self
+    }
+
+    /// Withdrawal queue configuration, if enabled.
+    pub fn withdrawal_config(&self) -> Option<&WithdrawalConfig> {
+        self.withdrawal_config.as_ref()
+    }
+
+    /// Require every header vanity to be printable ASCII followed by zero padding.
+    pub fn with_strict_vanity(mut self, strict: bool) -> Self {
+        self.strict_vanity = strict;
+        self
+    }
+
+    /// Whether consensus rejects vanities containing non-printable bytes.
+    #[inline]
+    pub fn strict_vanity(&self) -> bool {
+        self.strict_vanity
+    }
+
+    /// Set the per-block EIP-1559 gas target independently of the gas limit.
+    ///
+    /// The base fee rises when a block uses more than `gas_target` and falls when it
+    /// uses less (see [`base_fee::next_base_fee`]), while the gas limit stays
+    /// available as burst capacity. All nodes must use the same target.
+    pub fn with_gas_target(mut self, gas_target: u64) -> Self {
+        self.gas_target = Some(gas_target);
+        self
+    }
+
+    /// Pin the base fee of every post-London block from `activation` on to `fee` wei,
+    /// whatever the gas used.
+    ///
+    /// For controlled environments that want stable gas pricing; this breaks the
+    /// EIP-1559 adjustment rule, so plain Ethereum tooling will disagree with the
+    /// chain's headers. Takes precedence over [`with_gas_target`](Self::with_gas_target).
+    /// The payload builder charges transactions this fee and consensus expects it in
+    /// headers, both through `next_block_base_fee`. All nodes must use the same value
+    /// and activation block.
+    pub fn with_base_fee_override(mut self, fee: u64, activation: u64) -> Self {
+        self.base_fee_override = Some(fee);
+        self.base_fee_override_block = activation;
+        self
+    }
+
+    /// Base fee pinned with [`with_base_fee_override`](Self::with_base_fee_override).
+    #[inline]
+    pub fn base_fee_override(&self) -> Option<u64> {
+        self.base_fee_override
+    }
+
+    /// Pinned base fee of block `number`, if the override is active there.
+    #[inline]
+    pub fn base_fee_override_at(&self, number: u64) -> Option<u64> {
+        self.base_fee_override
+            .filter(|_| number >= self.base_fee_override_block)
+    }
+
+    /// Gas target set with [`with_gas_target`](Self::with_gas_target).
+    #[inline]
+    pub fn gas_target(&self) -> Option<u64> {
+        self.gas_target
+    }
+
+    /// Spread governance gas limit changes over the first `blocks` blocks of each epoch.
+    ///
+    /// Without a ramp the gas limit only moves by the EIP-1559 drift
+    /// (`parent_gas_limit / 1024` per block). With one, a block may also move it up to
+    /// [`gas_ramp::ramp_target`] toward the ChainConfig gas limit of the parent state,
+    /// reaching it on the ramp's last block. All nodes must use the same value.
+    pub fn with_max_gas_per_block_ramp(mut self, blocks: u64) -> Self {
+        self.max_gas_ramp_blocks = blocks;
+        self
+    }
+
+    /// Length of the gas limit ramp in blocks (0 = disabled).
+    #[inline]
+    pub fn max_gas_ramp_blocks(&self) -> u64 {
+        self.max_gas_ramp_blocks
+    }
+
+    /// Blocks left in the gas limit ramp at `block_number`, this one included.
+    ///
+    /// `None` when no ramp is configured or the block lies past the ramp. The ramp
+    /// covers the first `max_gas_ramp_blocks` blocks after every epoch block, so block
+    /// `epoch * k + i` has `max_gas_ramp_blocks - i + 1` blocks remaining.
+    pub fn gas_limit_ramp_remaining(&self, block_number: u64) -> Option<u64> {
+        let position = block_number.saturating_sub(1) % self.epoch().max(1);
+        Some(self.max_gas_ramp_blocks.saturating_sub(position)).filter(|&left| left > 0)
+    }
+
+    /// Furthest gas limit block `block_number` may ramp to from `parent_gas_limit`
+    /// toward the on-chain ChainConfig gas limit `target` of the parent state.
+    ///
+    /// `None` outside the ramp (see
+    /// [`gas_limit_ramp_remaining`](Self::gas_limit_ramp_remaining)): only the
+    /// EIP-1559 drift applies there.
+    pub fn gas_limit_ramp_target(
+        &self,
+        parent_gas_limit: u64,
+        block_number: u64,
+        target: u64,
+    ) -> Option<u64> {
+        let remaining = self.gas_limit_ramp_remaining(block_number)?;
+        Some(gas_ramp::ramp_target(parent_gas_limit, target, remaining))
+    }
+
+    /// Reject blocks whose RLP-encoded transaction list exceeds `max` bytes.
+    ///
+    /// The gas limit bounds execution cost but not wire size: cheap calldata can make
+    /// blocks that are light in gas and huge on the network. Enforced in
+    /// `validate_block_pre_execution`; the payload builder stops selecting
+    /// transactions once a block is full. All nodes must use the same value.
+    pub fn with_max_block_size_bytes(mut self, max: usize) -> Self {
+        self.max_block_size_bytes = Some(max);
+        self
+    }
+
+    /// Maximum RLP size of a block's transactions in bytes, if limited.
+    #[inline]
+    pub fn max_block_size_bytes(&self) -> Option<usize> {
+        self.max_block_size_bytes
+    }
+
+    /// Enable the EIP-2537 BLS12-381 precompiles (`0x0b`–`0x11`) before Prague.
+    ///
+    /// For validator signature aggregation on chains that have not scheduled Prague.
+    /// Applied by the EVM factory; all nodes must agree, or calls to those addresses
+    /// execute differently. From Prague on the precompiles are active regardless.
+    pub fn with_eip2537_bls_precompiles(mut self, enabled: bool) -> Self {
+        self.eip2537_enabled = enabled;
+        self
+    }
+
+    /// Whether the BLS12-381 precompiles are enabled ahead of Prague.
+    #[inline]
+    pub fn eip2537_enabled(&self) -> bool {
+        self.eip2537_enabled
+    }
+
+    /// Warm the block coinbase before the first transaction from block `activation` on.
+    ///
+    /// Makes the first coinbase access of a block cost the EIP-2929 warm price before
+    /// Shanghai (EIP-3651 does it from Shanghai on). Applied by the EVM factory; it
+    /// changes gas used, so every node must schedule the same block.
+    pub fn with_coinbase_warming_block(mut self, activation: u64) -> Self {
+        self.coinbase_warming_block = Some(activation);
+        self
+    }
+
+    /// First block with the coinbase pre-warmed; None = never.
+    #[inline]
+    pub fn coinbase_warming_block(&self) -> Option<u64> {
+        self.coinbase_warming_block
+    }
+
+    /// Reject seals with a high `s` (the malleable twin of a low-s signature) from
+    /// block `activation` on.
+    ///
+    /// Sealers write low-s signatures, but older blocks of a running chain may carry
+    /// high-s seals that stay valid below the activation block.
+    pub fn with_low_s_seal_block(mut self, activation: u64) -> Self {
+        self.low_s_seal_block = Some(activation);
+        self
+    }
+
+    /// Whether the seal of block `number` must have a low `s`.
+    #[inline]
+    pub fn low_s_seal_required(&self, number: u64) -> bool {
+        self.low_s_seal_block
+            .is_some_and(|activation| number >= activation)
+    }
+
+    /// Replace the EIP-4844 blob fee market of every blob fork (Cancun onward).
+    ///
+    /// `EthEvmConfig` reads the blob parameters through
+    /// [`blob_params_at_timestamp`](EthChainSpec::blob_params_at_timestamp) when it
+    /// builds each block's EVM environment, so the target, maximum and update fraction
+    /// apply to blob base fee pricing and block validation alike. A target of 0 prices
+    /// blobs out (see [`blob_fee`]). All nodes must use the same parameters.
+    pub fn with_blob_fee_params(mut self, params: BlobFeeParams) -> Self {
+        let mut inner = (*self.inner).clone();
+        let schedule = &mut inner.blob_params;
+        schedule.cancun = params.apply(schedule.cancun);
+        schedule.prague = params.apply(schedule.prague);
+        schedule.osaka = params.apply(schedule.osaka);
+        for (_, scheduled) in &mut schedule.scheduled {
+            *scheduled = params.apply(*scheduled);
+        }
+        self.inner = Arc::new(inner);
+        self
+    }
+
+    /// Select the chain's fee market (see [`fee_market`]) and have consensus enforce it.
+    ///
+    /// `Legacy` is [`with_eip1559_disabled`](Self::with_eip1559_disabled) (the genesis
+    /// hash changes). `Eip1559` sets the base fee parameters and gas target and allows
+    /// no blob gas. `Eip4844` also installs the blob fee market with
+    /// [`with_blob_fee_params`](Self::with_blob_fee_params). Apply it to a fresh spec:
+    /// a `Legacy` chain cannot be switched back. All nodes must use the same market.
+    pub fn with_fee_market(mut self, market: FeeMarket) -> Self {
+        self = match market.base_fee() {
+            None => self.with_eip1559_disabled(),
+            Some(base_fee) => {
+                let mut inner = (*self.inner).clone();
+                inner.base_fee_params = BaseFeeParamsKind::Constant(base_fee.params);
+                self.inner = Arc::new(inner);
+                self.gas_target = base_fee.gas_target;
+                self
+            }
+        };
+        let blobs = market.blob_fee_params().copied().unwrap_or(BlobFeeParams {
+            target_gas_per_block: 0,
+            max_gas_per_block: 0,
+            ..Default::default()
+        });
+        self = self.with_blob_fee_params(blobs);
+        self.fee_market = Some(market);
+        self
+    }
+
+    /// Fee market set with [`with_fee_market`](Self::with_fee_market).
+    #[inline]
+    pub fn fee_market(&self) -> Option<&FeeMarket> {
+        self.fee_market.as_ref()
+    }
+
+    /// Retain historical state according to `config` (see [`pruning`]).
+    ///
+    /// Overrides `--archive`: the node configures reth's account and storage history
+    /// pruning from [`PruningConfig::history_distance`]. Node-local: peers may prune
+    /// differently.
+    pub fn with_state_pruning(mut self, config: PruningConfig) -> Self {
+        self.state_pruning = Some(config);
+        self
+    }
+
+    /// Pruning policy set with [`with_state_pruning`](Self::with_state_pruning).
+    #[inline]
+    pub fn state_pruning(&self) -> Option<PruningConfig> {
+        self.state_pruning
+    }
+
+    /// Reject transactions from senders refused by `acl` (see [`acl`]) in blocks
+    /// from `activation` on.
+    ///
+    /// Enforced in `validate_block_post_execution` on the senders recovered for
+    /// execution. All nodes must use the same list and activation block.
+    pub fn with_acl(mut self, acl: AccessControlList, activation: u64) -> Self {
+        self.acl = Some(Arc::new(acl));
+        self.acl_activation_block = activation;
+        self
+    }
+
+    /// Access control list set with [`with_acl`](Self::with_acl).
+    #[inline]
+    pub fn acl(&self) -> Option<&AccessControlList> {
+        self.acl.as_deref()
+    }
+
+    /// First block the access control list applies to.
+    #[inline]
+    pub fn acl_activation_block(&self) -> u64 {
+        self.acl_activation_block
+    }
+
+    /// Access control list in force at block `number`, if any.
+    #[inline]
+    pub fn acl_at(&self, number: u64) -> Option<&Arc<AccessControlList>> {
+        self.acl
+            .as_ref()
+            .filter(|_| number >= self.acl_activation_block)
+    }
+
+    /// Require every block's signer to hold at least `stake` wei in the parent state.
+    ///
+    /// Gives signers something to lose on a key-based chain: a signer that spends
+    /// its balance below the stake can no longer seal. Enforced on the recovered
+    /// sealer before a block executes, against the parent state, and the payload
+    /// builder does not build for an understaked key; all nodes must use the same
+    /// amount. Zero disables the requirement.
+    pub fn with_min_signer_stake(mut self, stake: U256) -> Self {
+        self.min_signer_stake_wei = stake;
+        self
+    }
+
+    /// Stake required by [`with_min_signer_stake`](Self::with_min_signer_stake), if any.
+    #[inline]
+    pub fn min_signer_stake(&self) -> Option<U256> {
+        (!self.min_signer_stake_wei.is_zero()).then_some(self.min_signer_stake_wei)
+    }
+
+    /// Credit the signer of every block with a reward, paid as an EIP-4895 withdrawal.
+    ///
+    /// In-turn blocks earn `per_block_wei`; out-of-turn blocks, sealed while the
+    /// in-turn signer is unavailable, earn `out_of_turn_reward_pct` percent of it.
+    /// Dev-mode blocks are unsigned and carry no reward. Enforced in
+    /// `validate_block_pre_execution`; all nodes must use the same configuration.
+    /// See [`rewards`].
+    pub fn with_uncle_block_reward(mut self, config: UncleRewardConfig) -> Self {
+        self.uncle_reward = Some(config);
+        self
+    }
+
+    /// Signer reward set with [`with_uncle_block_reward`](Self::with_uncle_block_reward).
+    #[inline]
+    pub fn uncle_reward(&self) -> Option<&UncleRewardConfig> {
+        self.uncle_reward.as_ref()
+    }
+
+    /// Process validator deposits from `contract` as EIP-6110 requests (Prague).
+    ///
+    /// For a hybrid chain that takes ETH stake for Sybil resistance next to POA
+    /// signing. The contract is reth's [`deposit_contract`](EthChainSpec::deposit_contract):
+    /// its executor turns the contract's `DepositEvent` logs into deposit requests, and
+    /// `validate_block_post_execution` rejects blocks whose `requests_hash` does not
+    /// commit to them. The contract code itself comes from the genesis
+    /// alloc, so deploy the deposit contract there; all nodes must use the same address.
+    pub fn with_eip6110_deposit_requests(mut self, contract: Address) -> Self {
+        let mut inner = (*self.inner).clone();
+        inner.deposit_contract = Some(DepositContract::new(
+            contract,
+            0,
+            keccak256(DEPOSIT_EVENT_SIGNATURE),
+        ));
+        self.inner = Arc::new(inner);
+        self
+    }
+
+    /// Run a legacy gas-only chain without EIP-1559 base fee mechanics.
+    ///
+    /// Removes London from the hardfork schedule and the base fee from the genesis,
+    /// so headers carry no `base_fee_per_gas`, no base fee is computed or burned, and
+    /// blocks may only contain legacy / EIP-2930 transactions. The genesis hash
+    /// changes, so every node on the network must use the same setting.
+    pub fn with_eip1559_disabled(mut self) -> Self {
+        let mut inner = (*self.inner).clone();
+        inner.hardforks.remove(&EthereumHardfork::London);
+        inner.genesis.base_fee_per_gas = None;
+        inner.genesis_header = SealedHeader::seal_slow(reth_chainspec::make_genesis_header(
+            &inner.genesis,
+            &inner.hardforks,
+        ));
+        self.inner = Arc::new(inner);
+        self.eip1559_disabled = true;
+        self
+    }
+
+    /// Whether EIP-1559 base fees are disabled (see [`with_eip1559_disabled`](Self::with_eip1559_disabled)).
+    #[inline]
+    pub fn eip1559_disabled(&self) -> bool {
+        self.eip1559_disabled
+    }
+
+    /// Stub the EIP-4788 beacon roots contract so every query returns `root`.
+    ///
+    /// A POA chain has no beacon chain, so L2 contracts that read beacon roots would
+    /// revert against the canonical contract. This replaces it in genesis with a stub
+    /// that returns `root` to any caller. The genesis hash changes, so every node on
+    /// the network must use the same root.
+    pub fn with_eip4788_beacon_root(mut self, root: B256) -> Self {
+        let mut inner = (*self.inner).clone();
+        inner
+            .genesis
+            .alloc
+            .insert(BEACON_ROOTS_ADDRESS, crate::genesis::beacon_root_stub_account(root));
+        inner.genesis_header = SealedHeader::seal_slow(reth_chainspec::make_genesis_header(
+            &inner.genesis,
+            &inner.hardforks,
+        ));
+        self.inner = Arc::new(inner);
+        self.eip4788_stub_root = Some(root);
+        self
+    }
+
+    /// Root returned by the stubbed beacon roots contract, if enabled.
+    #[inline]
+    pub fn eip4788_stub_root(&self) -> Option<B256> {
+        self.eip4788_stub_root
+    }
+
+    /// Serve historical block hashes from state (EIP-2935).
+    ///
+    /// Pre-deploys the history storage contract if the genesis allocation lacks it
+    /// (custom genesis files often omit system contracts); the genesis hash only
+    /// changes in that case. From Prague, the executor's system call at the start of
+    /// each block stores the parent hash, so `BLOCKHASH`-style lookups reach back
+    /// 8191 blocks through the contract.
+    pub fn with_eip2935_block_hashes(mut self) -> Self {
+        if !self.inner.genesis.alloc.contains_key(&HISTORY_STORAGE_ADDRESS) {
+            let mut inner = (*self.inner).clone();
+            inner
+                .genesis
+                .alloc
+                .insert(HISTORY_STORAGE_ADDRESS, crate::genesis::history_storage_account());
+            inner.genesis_header = SealedHeader::seal_slow(reth_chainspec::make_genesis_header(
+                &inner.genesis,
+                &inner.hardforks,
+            ));
+            self.inner = Arc::new(inner);
+        }
+        self
+    }
+
+    /// Genesis allocation loaded into an in-memory revm database.
+    ///
+    /// For executing calls against genesis state without a provider (tooling, tests).
+    pub fn genesis_alloc_as_revm_state(&self) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        for (&address, account) in &self.inner.genesis.alloc {
+            let mut info = AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce.unwrap_or_default(),
+                ..Default::default()
+            };
+            if let Some(code) = &account.code {
+                info.code_hash = keccak256(code);
+                info.code = Some(Bytecode::new_raw(code.clone()));
+            }
+            db.insert_account_info(address, info);
+            for (slot, value) in account.storage.iter().flatten() {
+                let (slot, value) = (U256::from_be_bytes(slot.0), U256::from_be_bytes(value.0));
+                db.insert_account_storage(address, slot, value)
+                    .expect("in-memory database is infallible");
+            }
+        }
+        db
+    }
+
+    /// This chain's genesis as an `alloy_genesis::Genesis`, for alloy test tooling.
+    ///
+    /// Carries the chain ID, genesis timestamp and allocations (including any stubbed
+    /// contracts), the clique period and epoch, and extra data rebuilt from the genesis
+    /// vanity and the configured signers.
+    pub fn to_alloy_genesis(&self) -> Genesis {
+        let mut genesis = self.inner.genesis.clone();
+        genesis.config.chain_id = self.inner.chain.id();
+        genesis.timestamp = self.inner.genesis_header.timestamp;
+        let mut vanity = [0u8; 32];
+        let len = genesis.extra_data.len().min(vanity.len());
+        vanity[..len].copy_from_slice(&genesis.extra_data[..len]);
+        genesis.extra_data = crate::genesis::genesis_extra_data(&vanity, self.signers());
+        genesis.config.clique = Some(CliqueConfig {
+            period: Some(self.block_period()),
+            epoch: Some(self.epoch()),
+        });
+        genesis
+    }
+
     /// Check if an address is an authorized signer (uses live on-chain list if available).
     ///
     /// Avoids cloning the signer list — checks membership while holding the read lock.
@@ -237,6 +870,21 @@ impl EthChainSpec for PoaChainSpec {
         self.inner.base_fee_params_at_timestamp(timestamp)
     }
 
+    fn next_block_base_fee(&self, parent: &Self::Header, target_timestamp: u64) -> Option<u64> {
+        if let Some(fee) = self.base_fee_override_at(parent.number + 1) {
+            return parent.base_fee_per_gas.map(|_| fee);
+        }
+        let Some(gas_target) = self.gas_target() else {
+            return self.inner.next_block_base_fee(parent, target_timestamp);
+        };
+        Some(base_fee::next_base_fee(
+            parent.gas_used,
+            parent.base_fee_per_gas?,
+            gas_target,
+            self.base_fee_params_at_timestamp(target_timestamp),
+        ))
+    }
+
     fn blob_params_at_timestamp(&self, timestamp: u64) -> Option<BlobParams> {
         self.inner.blob_params_at_timestamp(timestamp)
     }
@@ -301,6 +949,21 @@ mod tests {
         assert_eq!(chain.inner().chain.id(), 9323310);
     }
 
+    #[test]
+    fn test_dev_chain_is_not_production() {
+        assert!(!PoaChainSpec::dev_chain().is_production());
+    }
+
+    #[test]
+    fn test_production_chain_is_production() {
+        let genesis = crate::genesis::create_genesis(crate::genesis::GenesisConfig::production());
+        let chain = PoaChainSpec::new(genesis, PoaConfig::default()).with_production(true);
+        assert_eq!(chain.inner().chain.id(), 9323310);
+        assert!(chain.is_production());
+        // The flag survives a chain ID override.
+        assert!(chain.with_chain_id(12345).is_production());
+    }
+
     #[test]
     fn test_dev_chain_signers_count() {
         let chain = PoaChainSpec::dev_chain();
@@ -668,12 +1331,459 @@ mod tests {
         assert_eq!(params, inner_params);
     }
 
+    #[test]
+    fn test_next_block_base_fee_without_target_matches_ethereum() {
+        let chain = PoaChainSpec::dev_chain();
+        let parent = Header {
+            gas_limit: 1_000_000_000,
+            gas_used: 100_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            chain.next_block_base_fee(&parent, 0),
+            chain.inner().next_block_base_fee(&parent, 0)
+        );
+    }
+
+    #[test]
+    fn test_next_block_base_fee_uses_gas_target() {
+        let chain = PoaChainSpec::dev_chain().with_gas_target(50_000_000);
+        let parent = |gas_used| Header {
+            gas_limit: 1_000_000_000,
+            gas_used,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        // 100M used is 2x the target: +1/8 even though it is 10% of the limit.
+        assert_eq!(chain.next_block_base_fee(&parent(100_000_000), 0), Some(1_125_000_000));
+        assert_eq!(chain.next_block_base_fee(&parent(0), 0), Some(875_000_000));
+        assert_eq!(
+            chain.next_block_base_fee(&Header { base_fee_per_gas: None, ..parent(0) }, 0),
+            None
+        );
+
+        // Blocks before the activation block follow the gas target.
+        let early = Header {
+            number: 8,
+            ..parent(400_000_000)
+        };
+        assert_eq!(chain.base_fee_override_at(9), None);
+        assert!(chain.next_block_base_fee(&early, 0).unwrap() > 7_000_000_000);
+    }
+
+    #[test]
+    fn test_next_block_base_fee_override_is_pinned() {
+        let chain = PoaChainSpec::dev_chain()
+            .with_gas_target(50_000_000)
+            .with_base_fee_override(7_000_000_000, 10);
+        let parent = |gas_used| Header {
+            number: 9,
+            gas_limit: 1_000_000_000,
+            gas_used,
+            base_fee_per_gas: Some(7_000_000_000),
+            ..Default::default()
+        };
+        assert_eq!(chain.base_fee_override(), Some(7_000_000_000));
+        for gas_used in [0, 50_000_000, 400_000_000, 1_000_000_000] {
+            assert_eq!(chain.next_block_base_fee(&parent(gas_used), 0), Some(7_000_000_000));
+        }
+        assert_eq!(
+            chain.next_block_base_fee(&Header { base_fee_per_gas: None, ..parent(0) }, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_gas_target_is_configured_value() {
+        let chain = PoaChainSpec::dev_chain();
+        assert_eq!(chain.gas_target(), None);
+        assert_eq!(chain.with_gas_target(50_000_000).gas_target(), Some(50_000_000));
+    }
+
+    #[test]
+    fn test_gas_limit_ramp_target_converges_within_epoch() {
+        let target = 1_000_000_000;
+        let chain = PoaChainSpec::dev_chain();
+        assert_eq!(chain.gas_limit_ramp_target(30_000_000, 1, target), None);
+
+        let chain = chain.with_max_gas_per_block_ramp(4);
+        let epoch = chain.epoch();
+        let mut gas_limit = 30_000_000;
+        for number in epoch + 1..=epoch + 4 {
+            let next = chain
+                .gas_limit_ramp_target(gas_limit, number, target)
+                .unwrap();
+            // Even steps: a quarter of the distance each, never a jump at the end.
+            assert_eq!(next - gas_limit, 242_500_000);
+            gas_limit = next;
+        }
+        assert_eq!(gas_limit, target);
+
+        // Past the ramp only the drift applies, however far the target is.
+        assert_eq!(chain.gas_limit_ramp_remaining(epoch + 5), None);
+        assert_eq!(
+            chain.gas_limit_ramp_target(30_000_000, epoch + 5, target),
+            None
+        );
+    }
+
+    #[test]
+    fn test_with_max_block_size_bytes() {
+        assert_eq!(PoaChainSpec::dev_chain().max_block_size_bytes(), None);
+        let chain = PoaChainSpec::dev_chain().with_max_block_size_bytes(128 * 1024);
+        assert_eq!(chain.max_block_size_bytes(), Some(128 * 1024));
+    }
+
+    #[test]
+    fn test_with_coinbase_warming_block() {
+        assert_eq!(PoaChainSpec::dev_chain().coinbase_warming_block(), None);
+        let chain = PoaChainSpec::dev_chain().with_coinbase_warming_block(100);
+        assert_eq!(chain.coinbase_warming_block(), Some(100));
+    }
+
+    #[test]
+    fn test_with_low_s_seal_block() {
+        assert!(!PoaChainSpec::dev_chain().low_s_seal_required(u64::MAX));
+        let chain = PoaChainSpec::dev_chain().with_low_s_seal_block(100);
+        assert!(!chain.low_s_seal_required(99));
+        assert!(chain.low_s_seal_required(100) && chain.low_s_seal_required(101));
+    }
+
+    #[test]
+    fn test_acl_activation_block() {
+        assert!(PoaChainSpec::dev_chain().acl_at(0).is_none());
+        let acl = AccessControlList::blacklist([Address::ZERO]);
+        let chain = PoaChainSpec::dev_chain().with_acl(acl.clone(), 100);
+        assert_eq!(chain.acl(), Some(&acl));
+        assert!(chain.acl_at(99).is_none());
+        assert_eq!(chain.acl_at(100).map(|acl| acl.as_ref()), Some(&acl));
+    }
+
+    #[test]
+    fn test_with_eip2537_bls_precompiles() {
+        assert!(!PoaChainSpec::dev_chain().eip2537_enabled());
+        assert!(PoaChainSpec::dev_chain().with_eip2537_bls_precompiles(true).eip2537_enabled());
+    }
+
+    #[test]
+    fn test_spec_id_by_name() {
+        use alloy_evm::revm::primitives::hardfork::SpecId;
+        use hardforks::spec_id_by_name;
+
+        assert_eq!(spec_id_by_name("cancun"), Some(SpecId::CANCUN));
+        assert_eq!(spec_id_by_name("Shanghai"), Some(SpecId::SHANGHAI));
+        assert_eq!(spec_id_by_name("paris"), Some(SpecId::MERGE));
+        assert_eq!(spec_id_by_name("spurious_dragon"), Some(SpecId::SPURIOUS_DRAGON));
+        assert_eq!(spec_id_by_name("bedrock"), None);
+    }
+
+    #[test]
+    fn test_with_eip6110_deposit_requests() {
+        use alloy_primitives::b256;
+
+        let chain = PoaChainSpec::dev_chain();
+        assert!(chain.deposit_contract().is_none());
+
+        let contract = Address::repeat_byte(0x60);
+        let staking = PoaChainSpec::dev_chain().with_eip6110_deposit_requests(contract);
+        let deposit = staking.deposit_contract().unwrap();
+        assert_eq!(deposit.address, contract);
+        assert_eq!(
+            deposit.topic,
+            b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5")
+        );
+        assert_eq!(staking.genesis_hash(), chain.genesis_hash());
+    }
+
+    #[test]
+    fn test_with_blob_fee_params() {
+        let params = BlobFeeParams {
+            target_gas_per_block: 0,
+            ..Default::default()
+        };
+        let chain = PoaChainSpec::dev_chain().with_blob_fee_params(params);
+        let blob_params = chain.blob_params_at_timestamp(u64::MAX).unwrap();
+        assert_eq!(blob_params.target_blob_count, 0);
+        assert_eq!(blob_params.max_blob_count, 6);
+        // The genesis is untouched.
+        assert_eq!(chain.genesis_hash(), PoaChainSpec::dev_chain().genesis_hash());
+    }
+
+    #[test]
+    fn test_with_fee_market() {
+        let legacy = PoaChainSpec::dev_chain().with_fee_market(FeeMarket::Legacy);
+        assert!(legacy.eip1559_disabled());
+        assert_eq!(legacy.fee_market(), Some(&FeeMarket::Legacy));
+
+        let base_fee = BaseFeeConfig {
+            gas_target: Some(10_000_000),
+            params: BaseFeeParams::new(16, 4),
+        };
+        let eip1559 = PoaChainSpec::dev_chain().with_fee_market(FeeMarket::Eip1559(base_fee));
+        assert!(!eip1559.eip1559_disabled());
+        assert_eq!(eip1559.gas_target(), Some(10_000_000));
+        assert_eq!(eip1559.base_fee_params_at_timestamp(0), BaseFeeParams::new(16, 4));
+        assert_eq!(eip1559.blob_params_at_timestamp(u64::MAX).unwrap().max_blob_count, 0);
+
+        let blobs = BlobFeeParams {
+            max_gas_per_block: 2 * alloy_eips::eip4844::DATA_GAS_PER_BLOB,
+            ..Default::default()
+        };
+        let eip4844 = PoaChainSpec::dev_chain()
+            .with_fee_market(FeeMarket::Eip4844(BaseFeeConfig::default(), blobs));
+        assert_eq!(eip4844.gas_target(), None);
+        assert_eq!(eip4844.blob_params_at_timestamp(u64::MAX).unwrap().max_blob_count, 2);
+        assert_eq!(eip4844.fee_market().unwrap().max_blob_gas_per_block(), blobs.max_gas_per_block);
+    }
+
+    #[test]
+    fn test_with_state_pruning() {
+        assert!(PoaChainSpec::dev_chain().state_pruning().is_none());
+
+        let chain = PoaChainSpec::dev_chain().with_state_pruning(PruningConfig::Pruned {
+            keep_last_n_blocks: 100,
+        });
+        let config = chain.state_pruning().unwrap();
+        assert_eq!(
+            config,
+            PruningConfig::Pruned {
+                keep_last_n_blocks: 100
+            }
+        );
+        assert_eq!(chain.clone().state_pruning(), Some(config));
+    }
+
+    #[test]
+    fn test_to_alloy_genesis() {
+        let signer = Address::repeat_byte(0x51);
+        let genesis = crate::genesis::create_genesis(
+            crate::genesis::GenesisConfig::default().with_signers(vec![signer]),
+        );
+        let poa_config = PoaConfig {
+            period: 2,
+            epoch: 100,
+            signers: vec![signer],
+        };
+        let chain = PoaChainSpec::new(genesis, poa_config).with_chain_id(4242);
+
+        let alloy = chain.to_alloy_genesis();
+        assert_eq!(alloy.config.chain_id, chain.chain_id());
+        assert_eq!(alloy.timestamp, chain.genesis_header().timestamp);
+        assert_eq!(alloy.alloc, chain.genesis().alloc);
+        let clique = alloy.config.clique.unwrap();
+        assert_eq!((clique.period, clique.epoch), (Some(2), Some(100)));
+        // 32 vanity + 1 * 20 signer + 65 seal
+        assert_eq!(alloy.extra_data.len(), 117);
+        assert_eq!(&alloy.extra_data[32..52], signer.as_slice());
+    }
+
     #[test]
     fn test_bootnodes_returns_none() {
         let chain = PoaChainSpec::dev_chain();
         assert!(chain.bootnodes().is_none());
     }
 
+    #[test]
+    fn test_resolve_genesis_alloc_includes_dev_accounts() {
+        let dev: HashMap<Address, U256> = crate::genesis::dev_accounts()
+            .into_iter()
+            .map(|a| (a, U256::from(1_000u64)))
+            .collect();
+        let prod: HashMap<Address, GenesisAccount> = crate::genesis::create_genesis(
+            crate::genesis::GenesisConfig::production(),
+        )
+        .alloc
+        .into_iter()
+        .filter(|(addr, _)| !dev.contains_key(addr))
+        .collect();
+
+        let merged = PoaChainSpec::resolve_genesis_alloc(dev.clone(), prod.clone());
+        assert_eq!(merged.len(), dev.len() + prod.len());
+        for (addr, balance) in &dev {
+            let account = &merged[addr];
+            assert_eq!(account.balance, *balance);
+            assert_eq!(account.nonce, Some(0));
+            assert!(account.code.is_none());
+            assert!(account.storage.is_none());
+        }
+        // Production contracts (governance, system contracts) survive the merge.
+        for (addr, account) in &prod {
+            assert_eq!(merged[addr].code, account.code);
+        }
+        assert!(merged.contains_key(&crate::genesis::CHAIN_CONFIG_ADDRESS));
+    }
+
+    #[test]
+    fn test_resolve_genesis_alloc_production_wins_on_collision() {
+        let shared = Address::repeat_byte(0x11);
+        let dev_only = Address::repeat_byte(0x22);
+        let dev = HashMap::from([(shared, U256::from(5u64)), (dev_only, U256::from(7u64))]);
+        let prod_account = GenesisAccount {
+            balance: U256::from(99u64),
+            nonce: Some(1),
+            code: Some(alloy_primitives::Bytes::from_static(&[0x60, 0x00])),
+            storage: None,
+            private_key: None,
+        };
+        let prod = HashMap::from([(shared, prod_account.clone())]);
+
+        let merged = PoaChainSpec::resolve_genesis_alloc(dev, prod);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[&shared], prod_account);
+        assert_eq!(merged[&dev_only].balance, U256::from(7u64));
+    }
+
+    #[test]
+    fn test_resolve_genesis_alloc_empty_inputs() {
+        assert!(PoaChainSpec::resolve_genesis_alloc(HashMap::new(), HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_with_withdrawal_config() {
+        let chain = PoaChainSpec::dev_chain();
+        assert!(chain.withdrawal_config().is_none());
+
+        let config = WithdrawalConfig {
+            queue_address: Address::repeat_byte(0x42),
+            max_per_block: 16,
+        };
+        let chain = chain.with_withdrawal_config(config);
+        assert_eq!(chain.withdrawal_config(), Some(&config));
+        // Clones carry the config (shared by consensus + payload builder)
+        assert_eq!(chain.clone().withdrawal_config(), Some(&config));
+    }
+
+    #[test]
+    fn test_with_eip1559_disabled() {
+        let chain = PoaChainSpec::dev_chain();
+        assert!(!chain.eip1559_disabled());
+        assert!(chain.is_london_active_at_block(0));
+
+        let legacy = PoaChainSpec::dev_chain().with_eip1559_disabled();
+        assert!(legacy.eip1559_disabled());
+        assert!(!legacy.is_london_active_at_block(0));
+        assert!(!legacy.inner().is_london_active_at_block(100));
+        assert_eq!(legacy.genesis_header().base_fee_per_gas, None);
+        assert_ne!(legacy.genesis_hash(), chain.genesis_hash());
+        // Later forks are untouched.
+        assert!(legacy.is_shanghai_active_at_timestamp(0));
+        assert!(legacy.is_prague_active_at_timestamp(0));
+    }
+
+    /// Call the beacon roots contract from `caller` with a timestamp query.
+    fn query_beacon_root(
+        chain: &PoaChainSpec,
+        caller: Address,
+    ) -> alloy_evm::revm::context_interface::result::ExecutionResult {
+        use crate::evm::PoaEvmFactory;
+        use alloy_evm::revm::context::TxEnv;
+        use alloy_evm::revm::primitives::TxKind;
+        use alloy_evm::{Evm, EvmEnv, EvmFactory};
+
+        let mut evm = PoaEvmFactory::default()
+            .create_evm(chain.genesis_alloc_as_revm_state(), EvmEnv::default());
+        let tx = TxEnv {
+            caller,
+            kind: TxKind::Call(BEACON_ROOTS_ADDRESS),
+            data: U256::from(1_700_000_000u64).to_be_bytes_vec().into(),
+            gas_limit: 100_000,
+            ..Default::default()
+        };
+        evm.transact(tx).unwrap().result
+    }
+
+    #[test]
+    fn test_eip4788_stub_returns_root() {
+        let root = B256::repeat_byte(0x42);
+        let chain = PoaChainSpec::dev_chain();
+        let stubbed = PoaChainSpec::dev_chain().with_eip4788_beacon_root(root);
+        assert_eq!(chain.eip4788_stub_root(), None);
+        assert_eq!(stubbed.eip4788_stub_root(), Some(root));
+        assert_ne!(stubbed.genesis_hash(), chain.genesis_hash());
+
+        let result = query_beacon_root(&stubbed, Address::repeat_byte(0x11));
+        assert!(result.is_success(), "{result:?}");
+        assert_eq!(result.output().unwrap().as_ref(), root.as_slice());
+
+        // The canonical contract has no root stored for that timestamp.
+        assert!(!query_beacon_root(&chain, Address::repeat_byte(0x11)).is_success());
+    }
+
+    #[test]
+    fn test_eip4788_stub_ignores_system_call() {
+        let stubbed = PoaChainSpec::dev_chain().with_eip4788_beacon_root(B256::repeat_byte(0x42));
+        let result = query_beacon_root(&stubbed, alloy_eips::eip4788::SYSTEM_ADDRESS);
+        assert!(result.is_success(), "{result:?}");
+        assert!(result.output().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_eip2935_stores_and_returns_parent_hash() {
+        use crate::evm::PoaEvmFactory;
+        use alloy_evm::revm::context::TxEnv;
+        use alloy_evm::revm::database_interface::DatabaseCommit;
+        use alloy_evm::revm::primitives::TxKind;
+        use alloy_evm::{Evm, EvmEnv, EvmFactory};
+
+        // Custom genesis without system contracts gets the contract pre-deployed.
+        let mut bare = PoaChainSpec::dev_chain();
+        let mut inner = (*bare.inner).clone();
+        inner.genesis.alloc.remove(&HISTORY_STORAGE_ADDRESS);
+        bare.inner = Arc::new(inner);
+        assert!(!bare.genesis().alloc.contains_key(&HISTORY_STORAGE_ADDRESS));
+        let chain = bare.with_eip2935_block_hashes();
+        assert!(chain.genesis().alloc.contains_key(&HISTORY_STORAGE_ADDRESS));
+        // Already present on the dev chain: genesis unchanged.
+        let dev = PoaChainSpec::dev_chain();
+        assert_eq!(dev.genesis_hash(), dev.clone().with_eip2935_block_hashes().genesis_hash());
+
+        let parent_hash = B256::repeat_byte(0x5a);
+        let mut env = EvmEnv::default();
+        env.block_env.number = U256::from(100);
+        let mut evm = PoaEvmFactory::default().create_evm(chain.genesis_alloc_as_revm_state(), env);
+
+        // Start-of-block system call for block 100 stores the hash of block 99.
+        let stored = evm
+            .transact_system_call(
+                alloy_eips::eip4788::SYSTEM_ADDRESS,
+                HISTORY_STORAGE_ADDRESS,
+                parent_hash.into(),
+            )
+            .unwrap();
+        assert!(stored.result.is_success(), "{:?}", stored.result);
+        evm.db_mut().commit(stored.state);
+
+        // Block 99 is served; the current block is not.
+        for (number, expected) in [(99u64, Some(parent_hash)), (100, None)] {
+            let tx = TxEnv {
+                caller: Address::repeat_byte(0x11),
+                kind: TxKind::Call(HISTORY_STORAGE_ADDRESS),
+                data: U256::from(number).to_be_bytes_vec().into(),
+                gas_limit: 100_000,
+                ..Default::default()
+            };
+            let result = evm.transact(tx).unwrap().result;
+            match expected {
+                Some(hash) => assert_eq!(result.output().unwrap().as_ref(), hash.as_slice()),
+                None => assert!(!result.is_success(), "{result:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_genesis_alloc_as_revm_state() {
+        use alloy_evm::revm::database_interface::DatabaseRef;
+
+        let chain = PoaChainSpec::dev_chain();
+        let db = chain.genesis_alloc_as_revm_state();
+        for (address, account) in &chain.genesis().alloc {
+            let info = db.basic_ref(*address).unwrap().unwrap();
+            assert_eq!(info.balance, account.balance);
+            assert_eq!(info.code.is_some(), account.code.is_some());
+        }
+    }
+
     #[test]
     fn test_ethereum_fork_activation_all_forks() {
         let chain = PoaChainSpec::dev_chain();
@@ -0,0 +1,123 @@
+//! Historical state retention.
+//!
+//! Archive and RPC nodes keep the state of every block; a validator only needs the
+//! recent state it builds and validates on. [`PruningConfig`] selects the policy
+//! with [`PoaChainSpec::with_state_pruning`](super::PoaChainSpec::with_state_pruning)
+//! and maps it to reth's prune segments: account and storage history (the data
+//! historical state is read from) are pruned beyond the retained window, while
+//! headers, bodies and receipts are left to reth's defaults.
+
+use reth_config::PruneConfig;
+use reth_prune_types::{PruneMode, PruneModes, MINIMUM_PRUNING_DISTANCE};
+
+/// Which historical states a node retains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruningConfig {
+    /// Archive: the state of every block is kept
+    Full,
+    /// Only the state of the last `keep_last_n_blocks` blocks below the tip is kept
+    /// (at least [`MINIMUM_PRUNING_DISTANCE`], see
+    /// [`history_distance`](Self::history_distance))
+    Pruned {
+        /// Size of the retained window
+        keep_last_n_blocks: u64,
+    },
+}
+
+impl PruningConfig {
+    /// Whether the state of `block_number` is kept while the chain tip is `current_tip`.
+    ///
+    /// Follows reth's [`PruneMode::Distance`] for the configured history distance:
+    /// blocks at most that far below the tip are kept.
+    pub fn should_retain_block(&self, block_number: u64, current_tip: u64) -> bool {
+        self.history_distance().is_none_or(|distance| {
+            !PruneMode::Distance(distance).should_prune(block_number, current_tip)
+        })
+    }
+
+    /// Distance below the tip past which history is pruned; `None` = keep all.
+    ///
+    /// reth refuses history distances under [`MINIMUM_PRUNING_DISTANCE`] (it needs
+    /// them to unwind reorgs), so smaller windows are widened to it.
+    pub fn history_distance(&self) -> Option<u64> {
+        match self {
+            Self::Pruned { keep_last_n_blocks } => {
+                Some((*keep_last_n_blocks).max(MINIMUM_PRUNING_DISTANCE))
+            }
+            Self::Full => None,
+        }
+    }
+
+    /// reth prune segments for this policy; `None` = no pruning (archive).
+    pub fn prune_modes(&self) -> Option<PruneModes> {
+        let distance = self.history_distance()?;
+        Some(PruneModes {
+            account_history: Some(PruneMode::Distance(distance)),
+            storage_history: Some(PruneMode::Distance(distance)),
+            ..PruneModes::none()
+        })
+    }
+
+    /// reth pruner configuration for this policy; `None` = no pruning (archive).
+    pub fn prune_config(&self) -> Option<PruneConfig> {
+        self.prune_modes().map(|segments| PruneConfig {
+            segments,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_retains_every_block() {
+        let config = PruningConfig::Full;
+        assert!(config.should_retain_block(0, 1_000_000));
+        assert!(config.should_retain_block(999_999, 1_000_000));
+        assert!(config.prune_modes().is_none());
+        assert!(config.prune_config().is_none());
+    }
+
+    #[test]
+    fn test_pruned_keeps_last_n_blocks() {
+        let config = PruningConfig::Pruned {
+            keep_last_n_blocks: 100,
+        };
+        // The window is widened to the minimum distance reth accepts, and matches
+        // reth's distance: the block that far below the tip is still kept.
+        let tip = 100_000;
+        let oldest = tip - MINIMUM_PRUNING_DISTANCE;
+        assert!(config.should_retain_block(tip, tip));
+        assert!(config.should_retain_block(oldest, tip));
+        assert!(!config.should_retain_block(oldest - 1, tip));
+        assert!(!config.should_retain_block(0, tip));
+        // Near genesis nothing is old enough to prune.
+        assert!(config.should_retain_block(0, MINIMUM_PRUNING_DISTANCE - 1));
+
+        let modes = config.prune_modes().unwrap();
+        let distance = PruneMode::Distance(MINIMUM_PRUNING_DISTANCE);
+        assert_eq!(modes.account_history, Some(distance));
+        assert_eq!(modes.storage_history, Some(distance));
+        assert_eq!(modes.receipts, None);
+        assert_eq!(config.prune_config().unwrap().segments, modes);
+        for block in [oldest - 1, oldest, tip] {
+            assert_eq!(
+                config.should_retain_block(block, tip),
+                !distance.should_prune(block, tip)
+            );
+        }
+    }
+
+    #[test]
+    fn test_wide_window_kept_as_is() {
+        let keep = MINIMUM_PRUNING_DISTANCE * 2;
+        let config = PruningConfig::Pruned {
+            keep_last_n_blocks: keep,
+        };
+        assert_eq!(config.history_distance(), Some(keep));
+        assert!(config.should_retain_block(1_000_000 - keep, 1_000_000));
+        assert!(!config.should_retain_block(1_000_000 - keep - 1, 1_000_000));
+    }
+}
@@ -0,0 +1,157 @@
+//! Block rewards for the sealing signer.
+//!
+//! Clique pays no block reward, and an out-of-turn signer covering for an offline
+//! primary earns nothing extra for keeping the chain live. With
+//! [`PoaChainSpec::with_uncle_block_reward`](super::PoaChainSpec::with_uncle_block_reward)
+//! every signed block credits its signer through an EIP-4895 withdrawal (the same
+//! protocol-level balance credit as the infinite fund): `per_block_wei` for an
+//! in-turn block, `out_of_turn_reward_pct` percent of it for an out-of-turn one.
+//!
+//! The reward is a consensus rule: every node must use the same configuration, and
+//! consensus rejects blocks whose reward withdrawal differs from [`expected_reward`].
+//!
+//! Protocol credits of a block are indexed together ([`credit_index`]): the
+//! infinite-fund credits (validator index 0) first, then the reward.
+
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::Address;
+
+/// Validator index stamped on reward withdrawals, distinguishing them from
+/// infinite-fund credits (0) and queue withdrawals (`u64::MAX`).
+pub const REWARD_VALIDATOR_INDEX: u64 = u64::MAX - 1;
+
+/// Validator index stamped on infinite-fund withdrawals.
+pub const FUND_VALIDATOR_INDEX: u64 = 0;
+
+/// Wei per gwei: withdrawal amounts are denominated in gwei.
+const WEI_PER_GWEI: u128 = 1_000_000_000;
+
+/// Reward paid to the signer of each block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UncleRewardConfig {
+    /// Reward for an in-turn block, in wei
+    pub per_block_wei: u128,
+    /// Share of `per_block_wei` paid for an out-of-turn block (capped at 100)
+    pub out_of_turn_reward_pct: u8,
+}
+
+impl UncleRewardConfig {
+    /// Reward in wei for a block sealed in turn or out of turn.
+    pub fn reward_wei(&self, in_turn: bool) -> u128 {
+        if in_turn {
+            return self.per_block_wei;
+        }
+        let pct = u128::from(self.out_of_turn_reward_pct.min(100));
+        // Split to stay exact without overflowing for large rewards.
+        self.per_block_wei / 100 * pct + self.per_block_wei % 100 * pct / 100
+    }
+
+    /// Withdrawal crediting `signer` for a block, or `None` if the reward rounds
+    /// down to 0 gwei.
+    pub fn reward_withdrawal(
+        &self,
+        index: u64,
+        signer: Address,
+        in_turn: bool,
+    ) -> Option<Withdrawal> {
+        let gwei = self.reward_wei(in_turn) / WEI_PER_GWEI;
+        (gwei > 0).then(|| Withdrawal {
+            index,
+            validator_index: REWARD_VALIDATOR_INDEX,
+            address: signer,
+            amount: u64::try_from(gwei).unwrap_or(u64::MAX),
+        })
+    }
+}
+
+/// Withdrawal index of the `slot`-th of the `slots` protocol credits of block
+/// `block_number`.
+#[inline]
+pub fn credit_index(block_number: u64, slots: u64, slot: u64) -> u64 {
+    block_number.saturating_mul(slots).saturating_add(slot)
+}
+
+/// Reward withdrawal block `block_number` must carry among `withdrawals`, sealed by
+/// `signer` in turn or not: indexed after the block's infinite-fund credits.
+pub fn expected_reward(
+    config: &UncleRewardConfig,
+    block_number: u64,
+    withdrawals: &[Withdrawal],
+    signer: Address,
+    in_turn: bool,
+) -> Option<Withdrawal> {
+    let funded = withdrawals
+        .iter()
+        .filter(|w| w.validator_index == FUND_VALIDATOR_INDEX)
+        .count() as u64;
+    let index = credit_index(block_number, funded + 1, funded);
+    config.reward_withdrawal(index, signer, in_turn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ETHER: u128 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn test_reward_by_turn() {
+        let config = UncleRewardConfig {
+            per_block_wei: 2 * ETHER,
+            out_of_turn_reward_pct: 50,
+        };
+        assert_eq!(config.reward_wei(true), 2 * ETHER);
+        assert_eq!(config.reward_wei(false), ETHER);
+
+        let none = UncleRewardConfig {
+            out_of_turn_reward_pct: 0,
+            ..config
+        };
+        assert_eq!(none.reward_wei(false), 0);
+        assert!(none.reward_withdrawal(0, Address::ZERO, false).is_none());
+        let capped = UncleRewardConfig {
+            out_of_turn_reward_pct: 250,
+            ..config
+        };
+        assert_eq!(capped.reward_wei(false), 2 * ETHER);
+    }
+
+    #[test]
+    fn test_reward_is_exact_for_large_amounts() {
+        let config = UncleRewardConfig {
+            per_block_wei: u128::MAX,
+            out_of_turn_reward_pct: 100,
+        };
+        assert_eq!(config.reward_wei(false), u128::MAX);
+        let sub_gwei = UncleRewardConfig {
+            per_block_wei: 999_999_999,
+            out_of_turn_reward_pct: 50,
+        };
+        assert!(sub_gwei.reward_withdrawal(7, Address::ZERO, true).is_none());
+    }
+
+    #[test]
+    fn test_reward_indexed_after_fund_credits() {
+        let config = UncleRewardConfig {
+            per_block_wei: ETHER,
+            out_of_turn_reward_pct: 50,
+        };
+        let signer = Address::with_last_byte(1);
+        let alone = expected_reward(&config, 7, &[], signer, true).unwrap();
+        assert_eq!(alone.index, credit_index(7, 1, 0));
+
+        let fund: Vec<_> = (0..2)
+            .map(|slot| Withdrawal {
+                index: credit_index(7, 3, slot),
+                validator_index: FUND_VALIDATOR_INDEX,
+                address: Address::with_last_byte(0xf0),
+                amount: 1,
+            })
+            .collect();
+        let reward = expected_reward(&config, 7, &fund, signer, false).unwrap();
+        assert_eq!(reward.index, credit_index(7, 3, 2));
+        assert_eq!(reward.amount, 500_000_000);
+        // Indexes of consecutive blocks never collide.
+        assert!(reward.index < credit_index(8, 3, 0));
+    }
+}
@@ -1,15 +1,37 @@
-use alloy_primitives::Address;
-use clap::Parser;
+use crate::chainspec::hardforks::spec_id_by_name;
+use crate::consensus::Checkpoint;
+use crate::node::SyncMode;
+use crate::payload::SignerRotation;
+use crate::rpc::MethodRateLimit;
+use alloy_evm::revm::primitives::hardfork::SpecId;
+use alloy_primitives::{Address, B256, U256};
+use clap::{Args, Parser, Subcommand};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 /// CLI arguments for the POA node
 #[derive(Parser, Debug)]
 #[command(name = "meowchain", about = "Meowchain POA Node")]
 pub struct Cli {
+    /// Run a tool instead of the node
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Chain ID for the network
     #[arg(long, default_value = "9323310")]
     pub chain_id: u64,
 
+    /// geth Clique genesis.json to run instead of the built-in dev / production
+    /// genesis. Chain ID, block period, epoch and signers come from the file, so it
+    /// supersedes --chain-id and --block-time. Every fork through Prague (and the
+    /// merge, `terminalTotalDifficulty: 0`) must be active at genesis in the file.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["gas_limit", "fund", "prefund_all", "zero_gas"]
+    )]
+    pub genesis_file: Option<PathBuf>,
+
     /// Block production interval in seconds (Phase 2: default 1s for MegaETH-inspired throughput)
     #[arg(long, default_value = "1")]
     pub block_time: u64,
@@ -39,6 +61,50 @@ pub struct Cli {
     #[arg(long, env = "SIGNER_KEY")]
     pub signer_key: Option<String>,
 
+    /// Directory of geth/web3 JSON keystores; every one is decrypted and loaded
+    /// as a signer at startup (requires --password-file)
+    #[arg(long, requires = "password_file")]
+    pub keystore_dir: Option<PathBuf>,
+
+    /// File whose first line is the password of the keystores in --keystore-dir
+    #[arg(long, requires = "keystore_dir")]
+    pub password_file: Option<PathBuf>,
+
+    /// Keep watching --keystore-dir after startup: keystores added later are loaded
+    /// and the signers of removed ones dropped, so keys rotate without a restart.
+    /// A keystore that fails to load is retried instead of aborting startup, and
+    /// keys from the other signer options are never dropped
+    #[arg(long, requires = "keystore_dir")]
+    pub watch_keystore_dir: bool,
+
+    /// File whose first line is the BIP-39 mnemonic to derive signer keys from.
+    /// Without it the mnemonic is read from the SIGNER_MNEMONIC environment
+    /// variable; it is never taken on the command line, where `ps` would show it.
+    #[arg(long, value_name = "PATH")]
+    pub mnemonic_file: Option<PathBuf>,
+
+    /// Number of keys derived from the mnemonic (indices 0..N)
+    #[arg(long, default_value = "1")]
+    pub mnemonic_count: u32,
+
+    /// BIP-44 account path the mnemonic key index is appended to
+    #[arg(long, default_value = "m/44'/60'/0'/0")]
+    pub derivation_path: String,
+
+    /// URL of a web3signer-compatible service holding the key of
+    /// --remote-signer-address; seals by that key are signed there
+    #[arg(long, value_name = "URL", requires = "remote_signer_address")]
+    pub remote_signer: Option<String>,
+
+    /// Address of the signer key held by --remote-signer
+    #[arg(long, value_name = "ADDRESS", requires = "remote_signer")]
+    pub remote_signer_address: Option<Address>,
+
+    /// Append every canonical block sealed by a local key to this hash-chained
+    /// JSON-lines audit log (check it with `verify_audit_log`)
+    #[arg(long, value_name = "PATH")]
+    pub seal_audit_log: Option<PathBuf>,
+
     /// Use production genesis configuration (chain ID 9323310)
     #[arg(long)]
     pub production: bool,
@@ -51,11 +117,68 @@ pub struct Cli {
     #[arg(long)]
     pub gas_limit: Option<u64>,
 
+    /// EIP-1559 gas target per block, independent of the gas limit (e.g. 50000000
+    /// with a 1B limit). The base fee rises while blocks use more than this.
+    /// Defaults to gas limit / 2. Every node of the network must use the same value.
+    #[arg(long)]
+    pub gas_target: Option<u64>,
+
+    /// Spread governance gas limit changes over this many blocks after each epoch
+    /// block, instead of the EIP-1559 drift of 1/1024 per block. 0 disables the
+    /// ramp. Every node must use the same value.
+    #[arg(long, default_value = "0")]
+    pub max_gas_ramp_blocks: u64,
+
+    /// Reject blocks whose RLP-encoded transactions exceed this many bytes.
+    /// Unlimited by default. Every node must use the same value.
+    #[arg(long)]
+    pub max_block_bytes: Option<usize>,
+
+    /// Balance in ETH (decimals allowed) every block signer must hold in the parent
+    /// state; blocks sealed by a poorer signer are rejected. Every node must use the
+    /// same value.
+    #[arg(long, value_name = "ETH", value_parser = parse_eth)]
+    pub min_signer_stake_eth: Option<U256>,
+
+    /// JSON file of blacklisted sender addresses (or `{"blacklisted": [..],
+    /// "whitelisted": [..]}`). Blocks with transactions from refused senders are
+    /// rejected. Every node must use the same list.
+    #[arg(long, value_name = "PATH")]
+    pub acl_file: Option<PathBuf>,
+
+    /// First block the `--acl-file` list applies to; earlier blocks admit every
+    /// sender. Every node must use the same block.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0, requires = "acl_file")]
+    pub acl_from_block: u64,
+
+    /// Trusted checkpoint as `<number>:<hash>`. Headers below it are accepted
+    /// without validation and the header at `<number>` must have `<hash>`.
+    #[arg(long)]
+    pub trusted_checkpoint: Option<Checkpoint>,
+
+    /// Comma-separated EIP-2718 transaction types allowed in blocks, e.g. `0,1,2`
+    /// to keep blob and EIP-7702 transactions out. Default: every type of the
+    /// active forks.
+    #[arg(long, value_delimiter = ',', value_name = "TYPES")]
+    pub allowed_tx_types: Option<Vec<u8>>,
+
+    /// Directory to persist the signer set of every canonical epoch block to, as
+    /// `<block_hash>.json`. The head's last epoch block's file seeds the signer set
+    /// at startup.
+    #[arg(long, value_name = "DIR")]
+    pub epoch_snapshot_dir: Option<PathBuf>,
+
     /// Enable eager mining: build block immediately when transactions arrive
     /// instead of waiting for block-time interval
     #[arg(long)]
     pub eager_mining: bool,
 
+    /// Dev-mode automine for test frameworks: every transaction is mined into its
+    /// own block on arrival and `eth_sendRawTransaction` returns once that block is
+    /// canonical. Enables `evm_setAutomine` / `evm_setIntervalMining`.
+    #[arg(long, conflicts_with = "eager_mining")]
+    pub automine: bool,
+
     /// Force interval-based block production even in production mode.
     /// Useful for testing: node uses production signing (97-byte extra_data, strict POA)
     /// but still auto-mines blocks at --block-time interval.
@@ -71,6 +194,20 @@ pub struct Cli {
     #[arg(long, value_delimiter = ',')]
     pub bootnodes: Option<Vec<String>>,
 
+    /// Enode URL of a peer that stays connected even when the peer limit is reached
+    /// (fellow validators, monitoring nodes). Repeatable.
+    #[arg(long = "trusted-peer", value_name = "ENODE")]
+    pub trusted_peers: Vec<String>,
+
+    /// Only connect to `--trusted-peer` peers (reth's trusted-nodes-only mode).
+    #[arg(long)]
+    pub trusted_only: bool,
+
+    /// Enode URL of a peer kept connected regardless of discovery (unlike bootnodes,
+    /// which only seed discovery). Repeatable.
+    #[arg(long = "static-peer", value_name = "ENODE")]
+    pub static_peers: Vec<String>,
+
     /// Disable P2P peer discovery (useful for single-node testing).
     #[arg(long)]
     pub disable_discovery: bool,
@@ -81,11 +218,37 @@ pub struct Cli {
     #[arg(long, default_value = "1024")]
     pub cache_size: usize,
 
+    /// Number of validated headers whose recovered sealer is remembered, so
+    /// re-validating a header skips the ECDSA recovery.
+    #[arg(long, default_value = "4096", value_parser = clap::value_parser!(u64).range(1..))]
+    pub signature_cache_size: u64,
+
+    /// Preload state touched by recent blocks before building the first block.
+    ///
+    /// Reads the accounts and storage slots changed in the last `--warmup-blocks`
+    /// blocks into the OS page cache and the hot state cache, so the first block
+    /// after a restart is not built from a cold database.
+    #[arg(long)]
+    pub warmup: bool,
+
+    /// Number of recent blocks whose touched state `--warmup` preloads.
+    #[arg(long, default_value = "64")]
+    pub warmup_blocks: u64,
+
+    /// Maximum time `--warmup` may delay startup, in milliseconds.
+    #[arg(long, default_value = "10000")]
+    pub warmup_budget_ms: u64,
+
     /// Enable block production performance metrics logging every N blocks.
     /// Set to 0 to disable metrics output.
     #[arg(long, default_value = "10")]
     pub metrics_interval: u64,
 
+    /// Measure how well the body of every block sealed here compresses (zstd level
+    /// 3) and report the ratio with the metrics output.
+    #[arg(long)]
+    pub compression_stats: bool,
+
     /// Maximum deployed contract code size in bytes (Phase 2).
     ///
     /// Ethereum mainnet default is 24,576 bytes (EIP-170).
@@ -127,6 +290,147 @@ pub struct Cli {
     #[arg(long)]
     pub zero_gas: bool,
 
+    /// Minimum effective gas price (wei) for transactions included in produced
+    /// blocks. Cheaper transactions stay in the pool; those whose maximum fee is
+    /// below it are refused. 0 disables the floor.
+    #[arg(long, default_value = "0")]
+    pub min_gas_price: u128,
+
+    /// Sequencer mode: include every valid pending transaction in produced blocks,
+    /// limited only by the block gas limit. Overrides --min-gas-price.
+    #[arg(long)]
+    pub sequencer_mode: bool,
+
+    /// Which local key seals each produced block: `in-turn` (skip blocks whose
+    /// in-turn signer is not ours), `any` (also seal out of turn) or
+    /// `fixed:<address>` (always seal with that key).
+    #[arg(long, value_name = "POLICY", default_value = "any")]
+    pub signer_rotation: SignerRotation,
+
+    /// Client identifier written into the 32-byte vanity of produced blocks.
+    ///
+    /// Truncated to 32 bytes; characters outside printable ASCII are replaced with `?`.
+    #[arg(long, default_value = concat!("meowchain/", env!("CARGO_PKG_VERSION")))]
+    pub client_tag: String,
+
+    /// Directory of per-block metadata attached to signed blocks: `<number>.json`
+    /// holds a JSON object for block `number`, written before the block is sealed.
+    #[arg(long, value_name = "DIR")]
+    pub block_metadata_dir: Option<PathBuf>,
+
+    /// Commit to the block metadata (32-byte hash after the seal) instead of
+    /// embedding it; `poa_getBlockMetadata` serves it from `--block-metadata-dir`.
+    #[arg(long, requires = "block_metadata_dir")]
+    pub block_metadata_hash: bool,
+
+    /// Reject blocks whose vanity contains bytes outside printable ASCII.
+    ///
+    /// Protects explorers that render the vanity verbatim from ANSI escape codes
+    /// and look-alike unicode. All signers should run with the same setting.
+    #[arg(long)]
+    pub strict_vanity: bool,
+
+    /// Run a legacy gas-only chain without EIP-1559 base fees.
+    ///
+    /// London is not activated: headers carry no base fee and only legacy / EIP-2930
+    /// transactions are accepted (EIP-1559 transactions are dropped from the pool).
+    /// Changes the genesis hash, so every node must use the same setting.
+    #[arg(long = "disable-eip1559")]
+    pub disable_eip1559: bool,
+
+    /// Replace the EIP-4788 beacon roots contract with a stub that returns this
+    /// 32-byte root for every query (there is no beacon chain to source real roots).
+    /// Changes the genesis hash, so every node must use the same value.
+    #[arg(long)]
+    pub eip4788_stub_root: Option<B256>,
+
+    /// Pre-deploy the EIP-2935 history storage contract if the genesis lacks it, so
+    /// contracts can read block hashes up to 8191 blocks back. Changes the genesis
+    /// hash of such a genesis, so every node must use the same setting.
+    #[arg(long)]
+    pub eip2935_block_hashes: bool,
+
+    /// Enable the EIP-2537 BLS12-381 precompiles (0x0b-0x11) before Prague, e.g. for
+    /// validator signature aggregation. Every node must use the same setting.
+    #[arg(long)]
+    pub eip2537_bls: bool,
+
+    /// Warm the block coinbase before the first transaction from this block on, so its
+    /// first access costs the warm price before Shanghai. Changes gas used: every node
+    /// must use the same block.
+    #[arg(long, value_name = "BLOCK")]
+    pub coinbase_warming_block: Option<u64>,
+
+    /// Reject block seals with a high `s` value (malleable signatures) from this block
+    /// on. Every node must use the same block.
+    #[arg(long, value_name = "BLOCK")]
+    pub low_s_seal_block: Option<u64>,
+
+    /// Run the EVM under this hardfork (e.g. `shanghai`, `paris`) instead of the one
+    /// the chain spec activates. For testing older-fork behaviour only, so it is
+    /// refused outside dev mode: blocks built with it are invalid to nodes running
+    /// without it.
+    #[arg(long, value_name = "HARDFORK", value_parser = parse_evm_spec)]
+    pub evm_spec: Option<SpecId>,
+
+    /// How a syncing node catches up with the chain.
+    ///
+    /// `full` validates every header. `light-headers` verifies the epoch checkpoint
+    /// headers first (signer-set continuity checkpoint to checkpoint) to learn the
+    /// signer set of downloaded headers ahead of the executed head; checkpoints at or
+    /// below the head are not applied, and full validation still runs for every block.
+    /// Requires signed blocks (production mode). Use `--archive` to keep all
+    /// historical state.
+    #[arg(long, value_enum, default_value = "full")]
+    pub sync_mode: SyncMode,
+
+    // ── Disk space ─────────────────────────────────────────────────────
+    /// Warn when the datadir filesystem has less than this many GiB free.
+    #[arg(long, default_value = "10")]
+    pub disk_warn_gb: u64,
+
+    /// Pause block building when the datadir filesystem has less than this many GiB free.
+    ///
+    /// The node keeps serving RPC reads and resumes building once space is freed.
+    #[arg(long, default_value = "1")]
+    pub disk_critical_gb: u64,
+
+    /// Check the database in `<datadir>/db` for consistency (read-only) and exit.
+    ///
+    /// Run this after an out-of-space failure before restarting the node.
+    #[arg(long)]
+    pub verify_db: bool,
+
+    // ── Transaction ingress back-pressure ─────────────────────────────
+    /// Pool occupancy (transactions) at which `eth_sendRawTransaction` is refused
+    /// with a retry hint, gossip transactions are shed and eager mining is throttled.
+    #[arg(long, default_value = "8000")]
+    pub txpool_high_watermark: usize,
+
+    /// Pool occupancy at which normal ingress resumes after an overload.
+    #[arg(long, default_value = "4000")]
+    pub txpool_low_watermark: usize,
+
+    /// Upper bound in seconds for the retry hint returned to throttled clients.
+    #[arg(long, default_value = "30")]
+    pub txpool_max_retry_secs: u64,
+
+    /// Most pending transactions kept in the pool; the pool discards the surplus,
+    /// lowest priority first.
+    ///
+    /// 0 = reth's own pending pool limit.
+    #[arg(long, default_value = "0")]
+    pub txpool_max_pending: usize,
+
+    /// Keep gossip-originated transactions while overloaded instead of shedding them.
+    #[arg(long)]
+    pub no_gossip_shedding: bool,
+
+    /// Keep eager mining tx-triggered while overloaded instead of throttling it
+    /// to the block period.
+    #[arg(long)]
+    pub no_eager_throttling: bool,
+
     // ── Production-grade RPC & observability flags ────────────────────
     /// Enable Prometheus metrics endpoint.
     ///
@@ -139,6 +443,23 @@ pub struct Cli {
     #[arg(long, default_value = "9001")]
     pub metrics_port: u16,
 
+    /// Serve a plain `GET /health` check (200 OK / 503 SICK) for load balancers on
+    /// this address, e.g. 0.0.0.0:8080.
+    #[arg(long)]
+    pub health_probe: Option<SocketAddr>,
+
+    /// File holding the hex-encoded 32-byte secret that authenticates Engine API calls
+    /// (reth's `--authrpc.jwtsecret`; without it reth generates `jwt.hex` in the
+    /// datadir). Calls without a valid JWT are rejected with 401.
+    #[arg(long, value_name = "PATH")]
+    pub jwt_secret: Option<PathBuf>,
+
+    /// OpenTelemetry collector to push chain metrics to over gRPC every 10 seconds,
+    /// e.g. http://collector:4317 (feature `otlp-metrics`).
+    #[cfg(feature = "otlp-metrics")]
+    #[arg(long, value_name = "URL")]
+    pub otlp_endpoint: Option<String>,
+
     /// Comma-separated list of allowed CORS origins for the HTTP RPC server.
     ///
     /// Use "*" to allow all origins. Default: none (no CORS headers).
@@ -163,6 +484,11 @@ pub struct Cli {
     #[arg(long, default_value = "eth,net,web3,txpool")]
     pub ws_api: String,
 
+    /// Serve geth-compatible `txpool_content` / `txpool_inspect` (transactions by
+    /// sender and nonce) even when `txpool` is left out of `--http-api` / `--ws-api`.
+    #[arg(long)]
+    pub txpool_content_api: bool,
+
     /// Enable structured JSON logging instead of human-readable output.
     ///
     /// Useful for log aggregation systems (ELK, Loki, Datadog, etc.).
@@ -176,6 +502,12 @@ pub struct Cli {
     #[arg(long, default_value = "500")]
     pub rpc_max_connections: u32,
 
+    /// Per-method RPC rate limit as `<method:max_per_minute>` (repeatable), e.g.
+    /// `eth_getLogs:60`, applied to each client connection. Calls over the limit,
+    /// including calls inside a batch, get a `-32005` error with a retry hint.
+    #[arg(long = "rpc-rate-limit", value_name = "METHOD:MAX")]
+    pub rpc_rate_limits: Vec<MethodRateLimit>,
+
     /// Maximum RPC request payload size in megabytes.
     ///
     /// Applies to both HTTP and WebSocket requests. Increase for large
@@ -249,3 +581,58 @@ pub struct Cli {
     #[arg(long, value_delimiter = ',')]
     pub infinite_fund: Vec<Address>,
 }
+
+/// Tools run instead of the node.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generate a signer key for --signer-key, or check a saved one
+    Keygen(KeygenArgs),
+    /// Roll the chain in --datadir back to a block; run with the node stopped and
+    /// the same chain flags (--genesis-file, --production, ...) it runs with
+    Revert(RevertArgs),
+}
+
+/// Arguments of `meowchain revert`.
+#[derive(Args, Debug)]
+pub struct RevertArgs {
+    /// Keep blocks up to and including this one; every later block is removed
+    #[arg(long, value_name = "BLOCK")]
+    pub to: u64,
+
+    /// Why the chain is reverted, recorded in the log
+    #[arg(long)]
+    pub reason: String,
+}
+
+/// Arguments of `meowchain keygen`.
+#[derive(Args, Debug)]
+pub struct KeygenArgs {
+    /// Save the key's hex to this file (owner-readable only); never overwrites
+    #[arg(long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Derive the key from this BIP-39 mnemonic at m/44'/60'/0'/0/0 instead of
+    /// generating a random one
+    #[arg(long, value_name = "PHRASE")]
+    pub mnemonic: Option<String>,
+
+    /// Print the address of the key saved in this file, without its private key
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["output", "mnemonic"])]
+    pub verify: Option<PathBuf>,
+}
+
+/// Parse an ETH amount such as `32` or `0.5` into wei.
+fn parse_eth(s: &str) -> Result<U256, String> {
+    alloy_primitives::utils::parse_ether(s.trim()).map_err(|err| err.to_string())
+}
+
+/// Parse a hardfork name such as `cancun` into its EVM spec.
+fn parse_evm_spec(s: &str) -> Result<SpecId, String> {
+    spec_id_by_name(s).ok_or_else(|| {
+        format!(
+            "unknown hardfork `{s}`; expected one of frontier, homestead, tangerine, \
+             spurious-dragon, byzantium, constantinople, petersburg, istanbul, berlin, \
+             london, paris, shanghai, cancun, prague, osaka"
+        )
+    })
+}
@@ -0,0 +1,125 @@
+//! Wall-clock access for header timestamps.
+//!
+//! Block *scheduling* never uses the wall clock: reth's dev miner ticks on a
+//! `tokio::time::Interval` and all of our own durations (build/sign timing, block
+//! arrival budget, RPC uptime) are measured with [`std::time::Instant`], which is
+//! monotonic. The wall clock is only read through a [`ClockSource`].
+//!
+//! Header timestamps come from the payload attributes the engine hands the builder.
+//! An NTP step can move the clock behind them backwards, so [`TimestampScheduler`]
+//! raises any requested timestamp to at least `parent + min_gap`, and block
+//! production continues with strictly increasing timestamps even while it is behind.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of wall-clock time in Unix seconds.
+pub trait ClockSource: Send + Sync + Debug {
+    /// Current Unix time in seconds.
+    fn unix_secs(&self) -> u64;
+}
+
+/// The operating system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl ClockSource for SystemClock {
+    fn unix_secs(&self) -> u64 {
+        // A clock set before 1970 reads as 0 instead of panicking; the scheduler's
+        // parent clamp keeps timestamps moving forward regardless.
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+impl<C: ClockSource + ?Sized> ClockSource for Arc<C> {
+    fn unix_secs(&self) -> u64 {
+        (**self).unix_secs()
+    }
+}
+
+/// Clamps requested header timestamps past the parent.
+#[derive(Debug, Clone, Copy)]
+pub struct TimestampScheduler {
+    /// Minimum distance from the parent timestamp (the POA block period, at least 1).
+    min_gap: u64,
+}
+
+impl TimestampScheduler {
+    /// Create a scheduler enforcing at least `block_period` seconds between blocks.
+    pub fn new(block_period: u64) -> Self {
+        Self {
+            min_gap: block_period.max(1),
+        }
+    }
+
+    /// Timestamp for the child of a block stamped `parent_timestamp`.
+    ///
+    /// `max(parent + min_gap, requested)`: the attributes' timestamp when it is far
+    /// enough past the parent, otherwise the earliest one consensus accepts.
+    pub fn next_timestamp(&self, parent_timestamp: u64, requested: u64) -> u64 {
+        requested.max(parent_timestamp.saturating_add(self.min_gap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_is_after_2020() {
+        assert!(SystemClock.unix_secs() > 1_577_836_800);
+    }
+
+    #[test]
+    fn test_keeps_requested_timestamp_when_ahead_of_parent() {
+        let scheduler = TimestampScheduler::new(2);
+        assert_eq!(scheduler.next_timestamp(990, 1_000), 1_000);
+    }
+
+    #[test]
+    fn test_clamps_to_parent_plus_period() {
+        let scheduler = TimestampScheduler::new(2);
+        assert_eq!(scheduler.next_timestamp(999, 1_000), 1_001);
+        assert_eq!(scheduler.next_timestamp(1_000, 1_000), 1_002);
+    }
+
+    #[test]
+    fn test_zero_period_still_strictly_increases() {
+        let scheduler = TimestampScheduler::new(0);
+        assert_eq!(scheduler.next_timestamp(1_000, 1_000), 1_001);
+    }
+
+    #[test]
+    fn test_parent_at_u64_max_does_not_overflow() {
+        let scheduler = TimestampScheduler::new(1);
+        assert_eq!(scheduler.next_timestamp(u64::MAX, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_regressing_clock_keeps_timestamps_increasing() {
+        let scheduler = TimestampScheduler::new(1);
+
+        let mut parent = 9_999;
+        let mut stamps = Vec::new();
+        for block in 0..20u64 {
+            // NTP step of one hour backwards at block 5, then the clock runs normally.
+            let requested = if block < 5 {
+                10_000 + block
+            } else {
+                10_000 - 3_600 + block
+            };
+            let ts = scheduler.next_timestamp(parent, requested);
+            assert!(ts > parent, "block {block}: {ts} <= parent {parent}");
+            stamps.push(ts);
+            parent = ts;
+        }
+        assert!(stamps.windows(2).all(|w| w[1] > w[0]));
+
+        // Once the clock catches up again the requested timestamp is used as is.
+        assert_eq!(scheduler.next_timestamp(parent, parent + 100), parent + 100);
+    }
+}
@@ -0,0 +1,108 @@
+//! Audit trail of the consensus checks run on a header.
+//!
+//! When a block is disputed, operators need to know which checks it passed and which
+//! one rejected it.
+//! [`PoaConsensus::validate_header_with_audit`](super::PoaConsensus::validate_header_with_audit)
+//! runs the same checks as header validation against the parent and records, for each,
+//! its name, outcome and duration. Validation stops at the first failing check, so a
+//! rejected header's trail ends with the one failed entry.
+
+use reth_consensus::ConsensusError;
+use serde::Serialize;
+use std::fmt;
+use std::time::Instant;
+
+/// Outcome of one consensus check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Check name, as in the `check` field of consensus logs
+    pub check: &'static str,
+    /// `Ok` or the rejection message
+    pub result: Result<(), String>,
+    /// Wall-clock time spent on the check
+    pub duration_ns: u64,
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Ok(()) => write!(f, "{}: passed ({}ns)", self.check, self.duration_ns),
+            Err(err) => write!(f, "{}: FAILED ({}ns): {err}", self.check, self.duration_ns),
+        }
+    }
+}
+
+/// Records the checks of one validation; disabled on the regular validation path.
+#[derive(Debug, Default)]
+pub(crate) struct AuditRecorder(Option<Vec<AuditEntry>>);
+
+impl AuditRecorder {
+    /// Run checks without recording them.
+    pub(crate) fn disabled() -> Self {
+        Self(None)
+    }
+
+    /// Record every check run.
+    pub(crate) fn enabled() -> Self {
+        Self(Some(Vec::new()))
+    }
+
+    /// Run `f` as `check`, recording its outcome and duration when enabled.
+    pub(crate) fn check<T>(
+        &mut self,
+        check: &'static str,
+        f: impl FnOnce() -> Result<T, ConsensusError>,
+    ) -> Result<T, ConsensusError> {
+        let Some(entries) = &mut self.0 else {
+            return f();
+        };
+        let started = Instant::now();
+        let result = f();
+        entries.push(AuditEntry {
+            check,
+            result: result.as_ref().map(|_| ()).map_err(ToString::to_string),
+            duration_ns: started.elapsed().as_nanos() as u64,
+        });
+        result
+    }
+
+    /// The recorded entries, in the order the checks ran.
+    pub(crate) fn into_entries(self) -> Vec<AuditEntry> {
+        self.0.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_recorder_records_nothing() {
+        let mut audit = AuditRecorder::disabled();
+        assert_eq!(audit.check("gas_limit", || Ok(7)).unwrap(), 7);
+        assert!(audit.into_entries().is_empty());
+    }
+
+    #[test]
+    fn test_entry_serializes_to_json() {
+        let mut audit = AuditRecorder::enabled();
+        audit.check("gas_limit", || Ok(())).unwrap();
+        audit
+            .check("signer", || -> Result<(), _> {
+                Err(ConsensusError::TimestampIsInFuture {
+                    timestamp: 2,
+                    present_timestamp: 1,
+                })
+            })
+            .unwrap_err();
+        let entries = audit.into_entries();
+
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[0]["check"], "gas_limit");
+        assert_eq!(json[0]["result"], serde_json::json!({ "Ok": null }));
+        assert!(json[0]["durationNs"].is_u64());
+        assert!(json[1]["result"]["Err"].as_str().is_some());
+        assert!(entries[1].to_string().starts_with("signer: FAILED"));
+    }
+}
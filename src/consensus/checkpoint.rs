@@ -0,0 +1,229 @@
+//! Light verification from epoch checkpoints.
+//!
+//! Every epoch block carries the full signer list in its `extra_data`, so a syncing
+//! node can establish the current signer set from checkpoint headers alone instead
+//! of validating every header.
+//! [`PoaConsensus::verify_checkpoint_chain`](super::PoaConsensus::verify_checkpoint_chain)
+//! walks the checkpoints from genesis and requires, for each one:
+//!
+//! - it is exactly one epoch after the previous checkpoint (no gaps),
+//! - it is sealed by a signer of the previous set,
+//! - its signer list is well-formed and derivable from the previous set
+//!   ([`validate_transition`]).
+//!
+//! Headers between checkpoints are not downloaded, so individual signer changes
+//! cannot be replayed. Derivability is therefore checked with the light-sync trust
+//! rule: a strict majority of the previous set must still be present, i.e. the
+//! previous signers could have voted the transition through within one window.
+//! Larger rotations must be spread over several epochs (or verified by full sync).
+//!
+//! A trusted [`Checkpoint`] short-cuts full sync instead: headers below it are
+//! accepted without header validation, and the header at it must match the trusted
+//! hash (see [`PoaConsensus::with_checkpoint`](super::PoaConsensus::with_checkpoint)).
+
+use super::PoaConsensusError;
+use crate::chainspec::PoaChainSpec;
+use alloy_consensus::Header;
+use alloy_primitives::{Address, B256};
+use reth_chainspec::EthChainSpec;
+use reth_primitives_traits::SealedHeader;
+use reth_storage_api::HeaderProvider;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Signer set in effect after a verified checkpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Checkpoint block number (0 for genesis)
+    pub number: u64,
+    /// Checkpoint block hash
+    pub hash: B256,
+    /// Authorized signers from this checkpoint on
+    pub signers: Vec<Address>,
+}
+
+impl Snapshot {
+    /// Trusted starting point: the genesis signer set.
+    pub fn genesis(chain_spec: &PoaChainSpec) -> Self {
+        Self {
+            number: 0,
+            hash: chain_spec.genesis_hash(),
+            signers: chain_spec.signers().to_vec(),
+        }
+    }
+
+    /// Whether `address` is in this snapshot's signer set.
+    #[inline]
+    pub fn is_signer(&self, address: &Address) -> bool {
+        self.signers.contains(address)
+    }
+}
+
+/// Checkpoint block numbers `epoch, 2 * epoch, ...` up to and including `head`.
+pub fn checkpoint_numbers(epoch: u64, head: u64) -> impl Iterator<Item = u64> {
+    let epoch = epoch.max(1);
+    (1..=head / epoch).map(move |i| i * epoch)
+}
+
+/// Check that `next` is a well-formed signer list derivable from `previous`.
+///
+/// `next` must be non-empty without duplicates, and strictly more than half of
+/// `previous` must be retained (see the [module docs](self)).
+pub fn validate_transition(
+    number: u64,
+    previous: &[Address],
+    next: &[Address],
+) -> Result<(), PoaConsensusError> {
+    let unique: HashSet<&Address> = next.iter().collect();
+    if next.is_empty() || unique.len() != next.len() {
+        return Err(PoaConsensusError::InvalidSignerList);
+    }
+
+    let retained = previous.iter().filter(|s| unique.contains(s)).count();
+    if retained * 2 <= previous.len() {
+        return Err(PoaConsensusError::UnderivableSignerSet {
+            number,
+            retained,
+            previous: previous.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Source of checkpoint headers for light verification.
+///
+/// Implemented for every reth [`HeaderProvider`], so the node's provider can be used
+/// once the header stage has stored the headers.
+pub trait CheckpointSource {
+    /// Sealed header of block `number`, if available.
+    fn checkpoint_header(&self, number: u64) -> Option<SealedHeader<Header>>;
+}
+
+impl<P> CheckpointSource for P
+where
+    P: HeaderProvider<Header = Header>,
+{
+    fn checkpoint_header(&self, number: u64) -> Option<SealedHeader<Header>> {
+        self.sealed_header(number).ok().flatten()
+    }
+}
+
+/// Trusted `(number, hash)` pair below which full header validation is skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Number of the trusted block
+    pub block_number: u64,
+    /// Hash the trusted block must have
+    pub block_hash: B256,
+}
+
+impl Checkpoint {
+    /// Trust block `block_number` with hash `block_hash`.
+    pub fn new(block_number: u64, block_hash: B256) -> Self {
+        Self {
+            block_number,
+            block_hash,
+        }
+    }
+}
+
+/// Error parsing a [`Checkpoint`] from its `<number>:<hash>` form.
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointParseError {
+    /// The `:` separating number and hash is missing
+    #[error("expected <number>:<hash>, got {0:?}")]
+    MissingSeparator(String),
+    /// The block number is not a decimal integer
+    #[error("invalid checkpoint block number: {0}")]
+    InvalidNumber(#[from] std::num::ParseIntError),
+    /// The hash is not 32 hex bytes
+    #[error("invalid checkpoint block hash: {0}")]
+    InvalidHash(#[from] alloy_primitives::hex::FromHexError),
+}
+
+impl FromStr for Checkpoint {
+    type Err = CheckpointParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, hash) = s
+            .split_once(':')
+            .ok_or_else(|| CheckpointParseError::MissingSeparator(s.to_string()))?;
+        Ok(Self::new(number.trim().parse()?, hash.trim().parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs(bytes: &[u8]) -> Vec<Address> {
+        bytes.iter().map(|&b| Address::repeat_byte(b)).collect()
+    }
+
+    #[test]
+    fn test_checkpoint_numbers() {
+        assert_eq!(checkpoint_numbers(4, 13).collect::<Vec<_>>(), vec![4, 8, 12]);
+        assert_eq!(checkpoint_numbers(4, 3).count(), 0);
+        assert_eq!(checkpoint_numbers(0, 2).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_transition_keeps_majority() {
+        let prev = addrs(&[1, 2, 3]);
+        assert!(validate_transition(4, &prev, &addrs(&[1, 2, 3])).is_ok());
+        assert!(validate_transition(4, &prev, &addrs(&[1, 2, 3, 4])).is_ok());
+        assert!(validate_transition(4, &prev, &addrs(&[1, 2])).is_ok());
+        assert!(validate_transition(4, &prev, &addrs(&[1, 2, 7, 8])).is_ok());
+    }
+
+    #[test]
+    fn test_transition_losing_majority_is_underivable() {
+        let prev = addrs(&[1, 2, 3, 4]);
+        assert!(matches!(
+            validate_transition(8, &prev, &addrs(&[1, 2, 5, 6])),
+            Err(PoaConsensusError::UnderivableSignerSet {
+                number: 8,
+                retained: 2,
+                previous: 4
+            })
+        ));
+        assert!(matches!(
+            validate_transition(8, &prev, &addrs(&[9])),
+            Err(PoaConsensusError::UnderivableSignerSet { retained: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_transition_rejects_malformed_lists() {
+        let prev = addrs(&[1, 2, 3]);
+        assert!(matches!(
+            validate_transition(4, &prev, &[]),
+            Err(PoaConsensusError::InvalidSignerList)
+        ));
+        assert!(matches!(
+            validate_transition(4, &prev, &addrs(&[1, 2, 2])),
+            Err(PoaConsensusError::InvalidSignerList)
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint_from_str() {
+        let hash = B256::repeat_byte(0xab);
+        let checkpoint: Checkpoint = format!("1200:{hash}").parse().unwrap();
+        assert_eq!(checkpoint, Checkpoint::new(1200, hash));
+
+        assert!(matches!(
+            "1200".parse::<Checkpoint>(),
+            Err(CheckpointParseError::MissingSeparator(_))
+        ));
+        assert!(matches!(
+            format!("tip:{hash}").parse::<Checkpoint>(),
+            Err(CheckpointParseError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            "1200:0x1234".parse::<Checkpoint>(),
+            Err(CheckpointParseError::InvalidHash(_))
+        ));
+    }
+}
@@ -1,9 +1,23 @@
-use alloy_primitives::Address;
+use crate::signer::{SealError, SignerError};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{Address, B256, U256};
+use alloy_serde::quantity;
 use reth_consensus::ConsensusError;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
 /// POA-specific consensus errors
-#[derive(Debug, Error)]
+///
+/// Serializes as `{"kind": <kind()>, "details": {<camelCase fields>}}` for JSON-RPC error
+/// data: addresses and hashes as 0x-hex, `u64` block numbers, timestamps and gas as
+/// hex quantities, error sources as their messages.
+#[derive(Debug, Error, Serialize)]
+#[serde(
+    tag = "kind",
+    content = "details",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
 #[allow(missing_docs)]
 pub enum PoaConsensusError {
     /// Block signer is not in the authorized signers list
@@ -14,8 +28,12 @@ pub enum PoaConsensusError {
     },
 
     /// Block signature is invalid or cannot be recovered
-    #[error("Invalid block signature")]
-    InvalidSignature,
+    #[error("Invalid block signature: {0}")]
+    InvalidSignature(#[source] SealError),
+
+    /// A local signer could not produce a seal
+    #[error("Sealing failed: {0}")]
+    SealingFailed(#[source] SignerError),
 
     /// Extra data is too short to contain required POA information
     #[error("Extra data too short: expected at least {expected} bytes, got {got}")]
@@ -30,8 +48,10 @@ pub enum PoaConsensusError {
     #[error("Block timestamp {timestamp} is before parent timestamp {parent_timestamp}")]
     TimestampTooEarly {
         /// Block timestamp
+        #[serde(serialize_with = "quantity::serialize")]
         timestamp: u64,
         /// Parent block timestamp
+        #[serde(serialize_with = "quantity::serialize")]
         parent_timestamp: u64,
     },
 
@@ -39,6 +59,7 @@ pub enum PoaConsensusError {
     #[error("Block timestamp {timestamp} is too far in the future")]
     TimestampTooFarInFuture {
         /// Block timestamp
+        #[serde(serialize_with = "quantity::serialize")]
         timestamp: u64,
     },
 
@@ -58,6 +79,281 @@ pub enum PoaConsensusError {
     /// Signer list in epoch block is invalid
     #[error("Invalid signer list in epoch block")]
     InvalidSignerList,
+
+    /// The metadata suffix after the seal is not a JSON object
+    #[error("Invalid block metadata: {0}")]
+    InvalidBlockMetadata(
+        #[source]
+        #[serde(serialize_with = "serialize_display")]
+        serde_json::Error,
+    ),
+
+    /// Block gas limit is below the protocol minimum
+    #[error("Gas limit {got} is below the minimum of {min}")]
+    GasLimitTooLow {
+        /// Header gas limit
+        #[serde(serialize_with = "quantity::serialize")]
+        got: u64,
+        /// Minimum allowed gas limit
+        #[serde(serialize_with = "quantity::serialize")]
+        min: u64,
+    },
+
+    /// Block gas limit is above the protocol maximum
+    #[error("Gas limit {got} is above the maximum of {max}")]
+    GasLimitTooHigh {
+        /// Header gas limit
+        #[serde(serialize_with = "quantity::serialize")]
+        got: u64,
+        /// Maximum allowed gas limit
+        #[serde(serialize_with = "quantity::serialize")]
+        max: u64,
+    },
+
+    /// Header carries a base fee before London is active
+    #[error("Base fee present before London activation")]
+    BaseFeeBeforeLondon,
+
+    /// Strict vanity mode: vanity contains a byte outside printable ASCII
+    #[error("Vanity byte {byte:#04x} at offset {offset} is not printable ASCII")]
+    InvalidVanity {
+        /// Offset of the offending byte within the vanity
+        offset: usize,
+        /// The offending byte
+        byte: u8,
+    },
+
+    /// No checkpoint headers were supplied for light verification
+    #[error("Checkpoint chain is empty")]
+    EmptyCheckpointChain,
+
+    /// Checkpoint is not exactly one epoch after the previous one
+    #[error("Checkpoint gap: expected block {expected}, got {got}")]
+    CheckpointGap {
+        /// Next expected checkpoint number
+        #[serde(serialize_with = "quantity::serialize")]
+        expected: u64,
+        /// Block number supplied
+        #[serde(serialize_with = "quantity::serialize")]
+        got: u64,
+    },
+
+    /// Checkpoint signer list cannot be derived from the previous checkpoint
+    #[error("Checkpoint {number} keeps only {retained} of {previous} previous signers")]
+    UnderivableSignerSet {
+        /// Checkpoint block number
+        #[serde(serialize_with = "quantity::serialize")]
+        number: u64,
+        /// Previous signers still present
+        retained: usize,
+        /// Size of the previous signer set
+        previous: usize,
+    },
+
+    /// Header transactions root does not commit to the block body's transactions
+    #[error("Transactions root mismatch: header has {expected}, body computes to {computed}")]
+    TransactionsRootMismatch {
+        /// Root committed in the header
+        expected: B256,
+        /// Root of the body's transactions
+        computed: B256,
+    },
+
+    /// Header commits to ommers, which a POA chain never has
+    #[error("Ommers hash {got} is not the empty ommers hash")]
+    NonEmptyOmmersHash {
+        /// Header ommers hash
+        got: B256,
+    },
+
+    /// EIP-1559 is disabled but the block contains a dynamic-fee transaction
+    #[error("Transaction {index} has dynamic-fee type {tx_type} but EIP-1559 is disabled")]
+    DynamicFeeTransaction {
+        /// Index of the transaction in the block
+        index: usize,
+        /// EIP-2718 transaction type
+        tx_type: u8,
+    },
+
+    /// Header at the trusted checkpoint height does not have the trusted hash
+    #[error("Block {number} has hash {got}, trusted checkpoint expects {expected}")]
+    CheckpointMismatch {
+        /// Checkpoint block number
+        #[serde(serialize_with = "quantity::serialize")]
+        number: u64,
+        /// Trusted checkpoint hash
+        expected: B256,
+        /// Hash of the supplied header
+        got: B256,
+    },
+
+    /// RLP-encoded block transactions exceed the configured byte limit
+    #[error("Block transactions are {size} bytes, above the limit of {max}")]
+    BlockTooLarge {
+        /// RLP size of the block's transaction list
+        size: usize,
+        /// Configured maximum
+        max: usize,
+    },
+
+    /// Header `parent_beacon_block_root` does not match the fork and the EIP-4788 stub
+    #[error("Invalid parent beacon root: expected {expected:?}, got {got:?}")]
+    InvalidParentBeaconRoot {
+        /// Root required by the rules (`None` = field must be absent)
+        expected: Option<B256>,
+        /// Root carried by the header
+        got: Option<B256>,
+    },
+
+    /// Epoch block `extra_data` does not list exactly the current signer set
+    #[error("Epoch block lists signers {got:?}, expected {expected:?}")]
+    EpochExtraDataMismatch {
+        /// Current signer set, sorted
+        expected: Vec<Address>,
+        /// Signers listed in the header, sorted
+        got: Vec<Address>,
+    },
+
+    /// A receipt is attributed to another block than the one it was produced in
+    #[error("Receipt {receipt_index} has block hash {got:?}, expected {expected}")]
+    ReceiptBlockHashMismatch {
+        /// Index of the receipt in the block
+        receipt_index: usize,
+        /// Hash of the sealed block
+        expected: B256,
+        /// Block hash carried by the receipt
+        got: Option<B256>,
+    },
+
+    /// Signer backfill: the seal of a header does not recover to an address
+    #[error("Invalid signature at block {0}")]
+    InvalidSignatureAtBlock(#[serde(serialize_with = "quantity::serialize")] u64),
+
+    /// A transaction's sender is on the chain's blacklist
+    #[error("Transaction sender {0} is blacklisted")]
+    BlacklistedSender(Address),
+
+    /// The chain has a whitelist and a transaction's sender is not on it
+    #[error("Transaction sender {0} is not whitelisted")]
+    SenderNotWhitelisted(Address),
+
+    /// Metadata does not hash to the commitment in the block's `extra_data`
+    #[error("Block metadata hashes to {got}, block commits to {expected}")]
+    BlockMetadataHashMismatch {
+        /// Commitment in `extra_data`
+        expected: B256,
+        /// Hash of the metadata
+        got: B256,
+    },
+
+    /// Epoch block casts a signer vote in its `mix_hash`
+    #[error("Epoch block must not carry a vote, got mix hash {mix_hash}")]
+    VoteInEpochBlock {
+        /// Header mix hash
+        mix_hash: B256,
+    },
+
+    /// Header `mix_hash` is not a signer vote, or casts one in an epoch block
+    #[error("Header mix hash {mix_hash} is not a valid signer vote for this block")]
+    InvalidVote {
+        /// Header mix hash
+        mix_hash: B256,
+    },
+
+    /// Block signer holds less than the minimum signer stake in the parent state
+    #[error("Signer {signer} holds {stake} wei, below the required stake of {required} wei")]
+    SignerUnderstaked {
+        /// Recovered block signer
+        signer: Address,
+        /// Signer balance in the parent state
+        stake: U256,
+        /// Configured minimum stake
+        required: U256,
+    },
+
+    /// A rule reading the parent state could not: the state is pruned or not synced
+    #[error("State of parent block {parent_hash} is unavailable")]
+    ParentStateUnavailable {
+        /// Hash of the parent block
+        parent_hash: B256,
+    },
+
+    /// Block contains a transaction of a type the chain does not allow
+    #[error("Transaction {index} has type {tx_type}, which is not allowed on this chain")]
+    DisallowedTransactionType {
+        /// Index of the transaction in the block
+        index: usize,
+        /// EIP-2718 transaction type
+        tx_type: u8,
+    },
+
+    /// The block's signer reward withdrawals differ from the chain's reward rule
+    #[error("Block pays signer rewards {got:?}, expected {expected:?}")]
+    InvalidSignerReward {
+        /// Reward withdrawals carried by the block
+        got: Vec<Withdrawal>,
+        /// Reward withdrawal the block must carry, if any
+        expected: Option<Withdrawal>,
+    },
+}
+
+impl PoaConsensusError {
+    /// Stable machine-readable name of the violated rule, used in RPC error data.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::UnauthorizedSigner { .. } => "unauthorizedSigner",
+            Self::InvalidSignature(_) => "invalidSignature",
+            Self::SealingFailed(_) => "sealingFailed",
+            Self::ExtraDataTooShort { .. } => "extraDataTooShort",
+            Self::TimestampTooEarly { .. } => "timestampTooEarly",
+            Self::TimestampTooFarInFuture { .. } => "timestampTooFarInFuture",
+            Self::WrongSigner { .. } => "wrongSigner",
+            Self::InvalidDifficulty => "invalidDifficulty",
+            Self::InvalidSignerList => "invalidSignerList",
+            Self::InvalidBlockMetadata(_) => "invalidBlockMetadata",
+            Self::GasLimitTooLow { .. } => "gasLimitTooLow",
+            Self::GasLimitTooHigh { .. } => "gasLimitTooHigh",
+            Self::BaseFeeBeforeLondon => "baseFeeBeforeLondon",
+            Self::InvalidVanity { .. } => "invalidVanity",
+            Self::EmptyCheckpointChain => "emptyCheckpointChain",
+            Self::CheckpointGap { .. } => "checkpointGap",
+            Self::UnderivableSignerSet { .. } => "underivableSignerSet",
+            Self::TransactionsRootMismatch { .. } => "transactionsRootMismatch",
+            Self::NonEmptyOmmersHash { .. } => "nonEmptyOmmersHash",
+            Self::DynamicFeeTransaction { .. } => "dynamicFeeTransaction",
+            Self::CheckpointMismatch { .. } => "checkpointMismatch",
+            Self::BlockTooLarge { .. } => "blockTooLarge",
+            Self::InvalidParentBeaconRoot { .. } => "invalidParentBeaconRoot",
+            Self::EpochExtraDataMismatch { .. } => "epochExtraDataMismatch",
+            Self::VoteInEpochBlock { .. } => "voteInEpochBlock",
+            Self::InvalidVote { .. } => "invalidVote",
+            Self::ReceiptBlockHashMismatch { .. } => "receiptBlockHashMismatch",
+            Self::InvalidSignatureAtBlock(_) => "invalidSignatureAtBlock",
+            Self::BlockMetadataHashMismatch { .. } => "blockMetadataHashMismatch",
+            Self::BlacklistedSender(_) => "blacklistedSender",
+            Self::SenderNotWhitelisted(_) => "senderNotWhitelisted",
+            Self::DisallowedTransactionType { .. } => "disallowedTransactionType",
+            Self::SignerUnderstaked { .. } => "signerUnderstaked",
+            Self::ParentStateUnavailable { .. } => "parentStateUnavailable",
+            Self::InvalidSignerReward { .. } => "invalidSignerReward",
+        }
+    }
+
+    /// The seal failure behind an [`InvalidSignature`](Self::InvalidSignature) error.
+    pub fn seal_error(&self) -> Option<&SealError> {
+        match self {
+            Self::InvalidSignature(cause) => Some(cause),
+            _ => None,
+        }
+    }
+}
+
+/// Serialize an error source that has no serde form as its message.
+pub(crate) fn serialize_display<T: std::fmt::Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
 }
 
 impl From<PoaConsensusError> for ConsensusError {
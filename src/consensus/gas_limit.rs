@@ -0,0 +1,59 @@
+//! Governance gas limit of the parent state.
+//!
+//! With [`PoaChainSpec::with_max_gas_per_block_ramp`](crate::chainspec::PoaChainSpec::with_max_gas_per_block_ramp)
+//! the blocks of a ramp move the gas limit toward the ChainConfig gas limit of their
+//! parent state. Consensus and the payload builder both read it through a
+//! [`GasLimitReader`]: the node's provider in production, a map in tests. Every node
+//! thus ramps toward the same value, whether or not it builds blocks.
+
+use crate::onchain::{read_gas_limit, StateProviderStorageReader};
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use reth_storage_api::{HeaderProvider, StateProviderFactory};
+use std::fmt;
+use std::sync::Arc;
+
+/// Source of gas limits in historical state.
+pub trait GasLimitReader: Send + Sync {
+    /// Gas limit of block `block_hash` and the ChainConfig gas limit in the state
+    /// after it (the block's own gas limit while ChainConfig has none); None if the
+    /// block or its state is unavailable.
+    fn ramp_gas_limits(&self, block_hash: B256) -> Option<(u64, u64)>;
+}
+
+impl<P> GasLimitReader for P
+where
+    P: StateProviderFactory + HeaderProvider<Header = Header> + Send + Sync,
+{
+    fn ramp_gas_limits(&self, block_hash: B256) -> Option<(u64, u64)> {
+        let gas_limit = self.header(block_hash).ok()??.gas_limit;
+        let state = self.state_by_block_hash(block_hash).ok()?;
+        let target = read_gas_limit(&StateProviderStorageReader(state.as_ref()))
+            .filter(|&target| target > 0)
+            .unwrap_or(gas_limit);
+        Some((gas_limit, target))
+    }
+}
+
+/// Shared [`GasLimitReader`] held by consensus and the payload builder.
+#[derive(Clone)]
+pub struct GasLimitSource(Arc<dyn GasLimitReader>);
+
+impl GasLimitSource {
+    /// Wrap a gas limit reader.
+    pub fn new(reader: Arc<dyn GasLimitReader>) -> Self {
+        Self(reader)
+    }
+
+    /// Gas limit of block `block_hash` and the ramp target in its state.
+    #[inline]
+    pub fn ramp_gas_limits(&self, block_hash: B256) -> Option<(u64, u64)> {
+        self.0.ramp_gas_limits(block_hash)
+    }
+}
+
+impl fmt::Debug for GasLimitSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GasLimitSource(..)")
+    }
+}
@@ -0,0 +1,274 @@
+//! Block metadata suffix in `extra_data`.
+//!
+//! Chains that need per-block annotations (batch sequence numbers, L1 state roots,
+//! bridge nonces) can have the payload builder append a JSON object after the seal:
+//!
+//! ```text
+//! vanity (32) | signers (N*20, epoch only) | seal (65) | json | len (4, BE) | "POAm"
+//! ```
+//!
+//! The frame is read from the end: a trailing [`METADATA_MAGIC`] marks the suffix
+//! and the length before it gives the JSON size. The magic's last byte (`m`) is never
+//! a valid seal recovery byte (0/1/27/28), so blocks without metadata are never
+//! mistaken for framed ones. A malformed frame is ignored, which leaves the magic
+//! inside the "seal" and fails signature recovery.
+//!
+//! Instead of the JSON, a block can carry a 32-byte commitment to it,
+//! `keccak256(json)` ([`metadata_hash`]), so light clients verify metadata served by
+//! `poa_getBlockMetadata` without every header carrying it:
+//!
+//! ```text
+//! vanity (32) | signers (N*20, epoch only) | seal (65) | keccak256(json) (32)
+//! ```
+//!
+//! The commitment has no trailer: it is recognised by length, as signer lists are
+//! whole 20-byte addresses, so `len - 97` is a multiple of 20 without it and is not
+//! with it (129 bytes for a non-epoch block).
+//!
+//! The seal hash covers the metadata: only the 65 seal bytes are cut out when
+//! hashing, so metadata cannot be altered without invalidating the signature.
+
+use super::{ADDRESS_LENGTH, EXTRA_SEAL_LENGTH, EXTRA_VANITY_LENGTH};
+use alloy_primitives::{keccak256, Bytes, B256};
+use std::collections::HashMap;
+
+/// Chain-specific key-value annotations attached to a block.
+pub type BlockMetadata = HashMap<String, serde_json::Value>;
+
+/// Marker ending a metadata-carrying `extra_data`.
+pub const METADATA_MAGIC: [u8; 4] = *b"POAm";
+
+/// Length of a metadata commitment suffix.
+pub const METADATA_HASH_LENGTH: usize = 32;
+
+/// Length field plus magic.
+const TRAILER_LENGTH: usize = 4 + METADATA_MAGIC.len();
+
+/// Length of the well-formed metadata suffix (JSON + trailer, or commitment), or 0
+/// if there is none.
+pub fn metadata_suffix_len(extra_data: &[u8]) -> usize {
+    let Some(trailer_start) = extra_data.len().checked_sub(TRAILER_LENGTH) else {
+        return 0;
+    };
+    if extra_data[trailer_start + 4..] != METADATA_MAGIC {
+        return metadata_hash_suffix_len(extra_data);
+    }
+    let mut len = [0u8; 4];
+    len.copy_from_slice(&extra_data[trailer_start..trailer_start + 4]);
+    let json_len = u32::from_be_bytes(len) as usize;
+    if json_len > trailer_start {
+        return 0;
+    }
+    json_len + TRAILER_LENGTH
+}
+
+/// [`METADATA_HASH_LENGTH`] if `extra_data` ends in a metadata commitment, else 0.
+fn metadata_hash_suffix_len(extra_data: &[u8]) -> usize {
+    let unframed = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH + METADATA_HASH_LENGTH;
+    match extra_data.len().checked_sub(unframed) {
+        Some(signers_len) if signers_len.is_multiple_of(ADDRESS_LENGTH) => METADATA_HASH_LENGTH,
+        _ => 0,
+    }
+}
+
+/// Offset just past the seal, i.e. where the metadata suffix (if any) starts.
+#[inline]
+pub fn seal_end(extra_data: &[u8]) -> usize {
+    extra_data.len() - metadata_suffix_len(extra_data)
+}
+
+/// `extra_data` with only the seal removed, as covered by the seal hash.
+///
+/// Without metadata this is a zero-copy slice; shorter-than-seal input is returned
+/// unchanged.
+pub fn without_seal(extra_data: &Bytes) -> Bytes {
+    let end = seal_end(extra_data);
+    if end < EXTRA_SEAL_LENGTH {
+        return extra_data.clone();
+    }
+    if end == extra_data.len() {
+        return extra_data.slice(..end - EXTRA_SEAL_LENGTH);
+    }
+    let mut unsealed = Vec::with_capacity(extra_data.len() - EXTRA_SEAL_LENGTH);
+    unsealed.extend_from_slice(&extra_data[..end - EXTRA_SEAL_LENGTH]);
+    unsealed.extend_from_slice(&extra_data[end..]);
+    unsealed.into()
+}
+
+/// Append `metadata` as a framed suffix. Keys are written in sorted order, so equal
+/// metadata always encodes to the same bytes.
+pub fn append_metadata(
+    extra_data: &mut Vec<u8>,
+    metadata: &BlockMetadata,
+) -> Result<(), serde_json::Error> {
+    let json = encode_metadata(metadata)?;
+    extra_data.extend_from_slice(&json);
+    extra_data.extend_from_slice(&(json.len() as u32).to_be_bytes());
+    extra_data.extend_from_slice(&METADATA_MAGIC);
+    Ok(())
+}
+
+/// JSON encoding of `metadata` with keys in sorted order.
+fn encode_metadata(metadata: &BlockMetadata) -> Result<Vec<u8>, serde_json::Error> {
+    let object: serde_json::Map<String, serde_json::Value> = metadata
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    serde_json::to_vec(&object)
+}
+
+/// Commitment to `metadata`: keccak256 of the JSON [`append_metadata`] would write.
+pub fn metadata_hash(metadata: &BlockMetadata) -> Result<B256, serde_json::Error> {
+    encode_metadata(metadata).map(keccak256)
+}
+
+/// Append the [`metadata_hash`] of `metadata` as a commitment suffix.
+pub fn append_metadata_hash(
+    extra_data: &mut Vec<u8>,
+    metadata: &BlockMetadata,
+) -> Result<(), serde_json::Error> {
+    extra_data.extend_from_slice(metadata_hash(metadata)?.as_slice());
+    Ok(())
+}
+
+/// The metadata commitment suffix of `extra_data`, if present.
+pub fn decode_metadata_hash(extra_data: &[u8]) -> Option<B256> {
+    (metadata_suffix_len(extra_data) == METADATA_HASH_LENGTH
+        && metadata_hash_suffix_len(extra_data) == METADATA_HASH_LENGTH)
+        .then(|| B256::from_slice(&extra_data[extra_data.len() - METADATA_HASH_LENGTH..]))
+}
+
+/// Decode the metadata suffix of `extra_data`, if present.
+///
+/// A commitment suffix carries no metadata: `Ok(None)`.
+pub fn decode_metadata(extra_data: &[u8]) -> Result<Option<BlockMetadata>, serde_json::Error> {
+    let suffix = metadata_suffix_len(extra_data);
+    if suffix == 0 || decode_metadata_hash(extra_data).is_some() {
+        return Ok(None);
+    }
+    let start = extra_data.len() - suffix;
+    serde_json::from_slice(&extra_data[start..start + suffix - TRAILER_LENGTH]).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata() -> BlockMetadata {
+        BlockMetadata::from([
+            ("batchSeq".to_string(), json!(42)),
+            ("l1StateRoot".to_string(), json!("0xabc")),
+            ("bridge".to_string(), json!({ "nonce": 7 })),
+        ])
+    }
+
+    fn sealed_extra() -> Vec<u8> {
+        let mut extra = vec![1u8; 32];
+        extra.extend_from_slice(&[0xAA; EXTRA_SEAL_LENGTH]);
+        extra
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut extra = sealed_extra();
+        append_metadata(&mut extra, &metadata()).unwrap();
+
+        assert_eq!(seal_end(&extra), 32 + EXTRA_SEAL_LENGTH);
+        assert_eq!(decode_metadata(&extra).unwrap(), Some(metadata()));
+    }
+
+    #[test]
+    fn test_empty_metadata_round_trip() {
+        let mut extra = sealed_extra();
+        append_metadata(&mut extra, &BlockMetadata::new()).unwrap();
+        assert_eq!(decode_metadata(&extra).unwrap(), Some(BlockMetadata::new()));
+    }
+
+    #[test]
+    fn test_encoding_is_deterministic() {
+        let (mut a, mut b) = (sealed_extra(), sealed_extra());
+        append_metadata(&mut a, &metadata()).unwrap();
+        append_metadata(&mut b, &metadata().into_iter().rev().collect()).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_plain_extra_data_has_no_metadata() {
+        let extra = sealed_extra();
+        assert_eq!(metadata_suffix_len(&extra), 0);
+        assert_eq!(seal_end(&extra), extra.len());
+        assert_eq!(decode_metadata(&extra).unwrap(), None);
+        assert_eq!(decode_metadata(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_oversized_length_is_not_a_frame() {
+        let mut extra = sealed_extra();
+        extra.extend_from_slice(&u32::MAX.to_be_bytes());
+        extra.extend_from_slice(&METADATA_MAGIC);
+        assert_eq!(metadata_suffix_len(&extra), 0);
+    }
+
+    #[test]
+    fn test_invalid_json_is_an_error() {
+        let mut extra = sealed_extra();
+        extra.extend_from_slice(b"{oops");
+        extra.extend_from_slice(&5u32.to_be_bytes());
+        extra.extend_from_slice(&METADATA_MAGIC);
+        assert!(decode_metadata(&extra).is_err());
+    }
+
+    #[test]
+    fn test_without_seal_keeps_metadata() {
+        let mut extra = sealed_extra();
+        append_metadata(&mut extra, &metadata()).unwrap();
+        let extra = Bytes::from(extra);
+
+        let unsealed = without_seal(&extra);
+        assert_eq!(unsealed.len(), extra.len() - EXTRA_SEAL_LENGTH);
+        assert_eq!(&unsealed[..32], &[1u8; 32]);
+        assert_eq!(decode_metadata(&unsealed).unwrap(), Some(metadata()));
+
+        let plain = Bytes::from(sealed_extra());
+        assert_eq!(without_seal(&plain), plain.slice(..32));
+    }
+
+    #[test]
+    fn test_metadata_hash_suffix() {
+        let mut extra = sealed_extra();
+        append_metadata_hash(&mut extra, &metadata()).unwrap();
+
+        assert_eq!(extra.len(), 32 + EXTRA_SEAL_LENGTH + METADATA_HASH_LENGTH);
+        assert_eq!(seal_end(&extra), 32 + EXTRA_SEAL_LENGTH);
+        let hash = metadata_hash(&metadata()).unwrap();
+        assert_eq!(decode_metadata_hash(&extra), Some(hash));
+        // Order-independent, like the JSON it commits to.
+        assert_eq!(
+            metadata_hash(&metadata().into_iter().rev().collect()).unwrap(),
+            hash
+        );
+        // The commitment carries no JSON to decode.
+        assert_eq!(decode_metadata(&extra).unwrap(), None);
+    }
+
+    #[test]
+    fn test_metadata_hash_after_signer_list() {
+        let mut extra = vec![1u8; 32];
+        extra.extend_from_slice(&[0x11; 2 * ADDRESS_LENGTH]);
+        extra.extend_from_slice(&[0xAA; EXTRA_SEAL_LENGTH]);
+        assert_eq!(decode_metadata_hash(&extra), None);
+
+        append_metadata_hash(&mut extra, &metadata()).unwrap();
+        assert_eq!(
+            seal_end(&extra),
+            32 + 2 * ADDRESS_LENGTH + EXTRA_SEAL_LENGTH
+        );
+        assert!(decode_metadata_hash(&extra).is_some());
+
+        // A JSON frame is never read as a commitment.
+        let mut framed = sealed_extra();
+        append_metadata(&mut framed, &metadata()).unwrap();
+        assert_eq!(decode_metadata_hash(&framed), None);
+    }
+}
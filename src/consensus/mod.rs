@@ -6,20 +6,59 @@
 //! - Timing constraints are respected
 //! - The signer rotation follows the expected pattern
 
+pub mod audit;
+pub mod checkpoint;
 pub mod errors;
-
-pub use crate::constants::{ADDRESS_LENGTH, EXTRA_SEAL_LENGTH, EXTRA_VANITY_LENGTH};
+pub mod gas_limit;
+pub mod metadata;
+pub mod signer_set;
+pub mod snapshots;
+pub mod stake;
+pub mod tx_types;
+pub mod vanity;
+pub mod votes;
+
+pub use crate::constants::{
+    ADDRESS_LENGTH, EXTRA_SEAL_LENGTH, EXTRA_VANITY_LENGTH, MAX_GAS_LIMIT, MIN_GAS_LIMIT,
+};
+pub use audit::AuditEntry;
+pub use checkpoint::{Checkpoint, CheckpointParseError, CheckpointSource, Snapshot};
 pub use errors::PoaConsensusError;
-
-use crate::chainspec::PoaChainSpec;
-use alloy_consensus::{BlockHeader, Header};
-use alloy_primitives::{keccak256, Address, Signature, B256, U256};
+pub use gas_limit::{GasLimitReader, GasLimitSource};
+pub use metadata::BlockMetadata;
+pub use signer_set::{epoch_signers, SignerSetReader, SignerSetSource};
+pub use snapshots::{SnapshotError, SnapshotStore};
+pub use stake::{SignerStakeReader, StakeSource};
+pub use tx_types::TxTypes;
+pub use votes::{PendingVote, VoteType, VoterSet};
+
+use audit::AuditRecorder;
+use crate::chainspec::hardforks::spec_id_at;
+use crate::chainspec::rewards::{expected_reward, REWARD_VALIDATOR_INDEX};
+use crate::chainspec::{AccessControlList, FeeMarket, PoaChainSpec, UncleRewardConfig};
+use crate::signer::{
+    bytes_to_signature, is_low_s, signature_to_bytes, BlockSealer, SealError, SignatureCache,
+    SignerError, SignerManager,
+};
+use alloy_consensus::{BlockHeader, Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::eip2718::{
+    Encodable2718, Typed2718, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+};
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
+use alloy_eips::eip4895::Withdrawal;
+use alloy_evm::revm::primitives::hardfork::SpecId;
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator, ReceiptRootBloom};
 use reth_execution_types::BlockExecutionResult;
+use rayon::prelude::*;
 use reth_primitives_traits::{
-    Block, GotExpected, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
+    Block, BlockBody, GotExpected, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
 };
 use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, instrument, warn};
 
 // ─── Cold-path error constructors ─────────────────────────────────────────────
 //
@@ -37,8 +76,8 @@ fn cold_extra_data_too_short(expected: usize, got: usize) -> PoaConsensusError {
 
 #[cold]
 #[inline(never)]
-fn cold_invalid_signature() -> PoaConsensusError {
-    PoaConsensusError::InvalidSignature
+fn cold_invalid_signature(cause: SealError) -> PoaConsensusError {
+    PoaConsensusError::InvalidSignature(cause)
 }
 
 #[cold]
@@ -59,6 +98,39 @@ fn cold_timestamp_too_early(timestamp: u64, parent_timestamp: u64) -> PoaConsens
     PoaConsensusError::TimestampTooEarly { timestamp, parent_timestamp }
 }
 
+#[cold]
+#[inline(never)]
+fn cold_invalid_vanity(offset: usize, byte: u8) -> PoaConsensusError {
+    PoaConsensusError::InvalidVanity { offset, byte }
+}
+
+#[cold]
+#[inline(never)]
+fn cold_gas_limit_too_low(got: u64) -> PoaConsensusError {
+    PoaConsensusError::GasLimitTooLow { got, min: MIN_GAS_LIMIT }
+}
+
+#[cold]
+#[inline(never)]
+fn cold_gas_limit_too_high(got: u64) -> PoaConsensusError {
+    PoaConsensusError::GasLimitTooHigh { got, max: MAX_GAS_LIMIT }
+}
+
+/// A receipt that records the hash of the block it belongs to.
+///
+/// Implemented for RPC receipts, which is what indexers consume; see
+/// [`PoaConsensus::validate_block_hash_consistency`].
+pub trait ReceiptBlockHash {
+    /// Hash of the containing block, if the receipt carries one.
+    fn block_hash(&self) -> Option<B256>;
+}
+
+impl<T> ReceiptBlockHash for alloy_rpc_types_eth::TransactionReceipt<T> {
+    fn block_hash(&self) -> Option<B256> {
+        self.block_hash
+    }
+}
+
 /// POA Consensus implementation
 #[derive(Debug, Clone)]
 pub struct PoaConsensus {
@@ -66,6 +138,18 @@ pub struct PoaConsensus {
     chain_spec: Arc<PoaChainSpec>,
     /// Whether the node is in dev mode (relaxed validation - no signature checks)
     dev_mode: bool,
+    /// Trusted block below which header validation is skipped.
+    checkpoint: Option<Checkpoint>,
+    /// EIP-2718 transaction types allowed in blocks; None = those of the active forks.
+    allowed_tx_types: Option<TxTypes>,
+    /// Parent-state balances for the minimum signer stake; None = stake not checked.
+    stakes: Option<StakeSource>,
+    /// Parent-state gas limits for the gas limit ramp; None = ramp steps not checked.
+    gas_limits: Option<GasLimitSource>,
+    /// Parent-state signer sets for epoch blocks; None = epoch signer lists not checked.
+    signer_sets: Option<SignerSetSource>,
+    /// Recovered sealers of recently validated headers; None = every seal recovered.
+    signature_cache: Option<Arc<SignatureCache>>,
 }
 
 impl PoaConsensus {
@@ -74,6 +158,12 @@ impl PoaConsensus {
         Self {
             chain_spec,
             dev_mode: false,
+            checkpoint: None,
+            allowed_tx_types: None,
+            stakes: None,
+            gas_limits: None,
+            signer_sets: None,
+            signature_cache: None,
         }
     }
 
@@ -82,6 +172,12 @@ impl PoaConsensus {
         Self {
             chain_spec,
             dev_mode: true,
+            checkpoint: None,
+            allowed_tx_types: None,
+            stakes: None,
+            gas_limits: None,
+            signer_sets: None,
+            signature_cache: None,
         }
     }
 
@@ -97,11 +193,136 @@ impl PoaConsensus {
         self.dev_mode
     }
 
+    /// Memoize recovered sealers in `cache`, the node's one shared by every instance.
+    pub fn with_signature_cache(mut self, cache: Arc<SignatureCache>) -> Self {
+        self.signature_cache = Some(cache);
+        self
+    }
+
+    /// Cache of the sealers recovered by
+    /// [`recover_sealed_signer`](Self::recover_sealed_signer).
+    #[inline]
+    pub fn signature_cache(&self) -> Option<&Arc<SignatureCache>> {
+        self.signature_cache.as_ref()
+    }
+
+    /// Trust every header below `checkpoint` and require its hash at its height.
+    ///
+    /// Speeds up syncing a long chain from a known-good block: headers before the
+    /// checkpoint pass [`validate_header`](HeaderValidator::validate_header) without
+    /// any checks, while the checkpoint header itself must hash to the trusted value
+    /// before it is fully validated.
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// The trusted checkpoint, if one is configured.
+    #[inline]
+    pub fn checkpoint(&self) -> Option<&Checkpoint> {
+        self.checkpoint.as_ref()
+    }
+
+    /// Only accept transactions whose EIP-2718 type is in `types`.
+    ///
+    /// For networks whose tooling predates typed transactions, e.g. `{0, 1}` keeps
+    /// EIP-1559 and blob transactions out of blocks even where the forks allow them.
+    /// Without this the types of the forks active at each block are allowed.
+    pub fn with_allowed_tx_types(mut self, types: TxTypes) -> Self {
+        self.allowed_tx_types = Some(types);
+        self
+    }
+
+    /// Transaction types allowed in a block with this `number` and `timestamp`.
+    pub fn allowed_tx_types_at(&self, number: u64, timestamp: u64) -> TxTypes {
+        match self.allowed_tx_types {
+            Some(types) => types,
+            None => Self::fork_tx_types(self.chain_spec.as_ref(), number, timestamp),
+        }
+    }
+
+    /// Transaction types introduced by the forks active at `number` / `timestamp`:
+    /// legacy and EIP-2930 always, EIP-1559 from London, EIP-4844 from Cancun and
+    /// EIP-7702 from Prague.
+    pub fn fork_tx_types(
+        chain_spec: &impl EthereumHardforks,
+        number: u64,
+        timestamp: u64,
+    ) -> TxTypes {
+        let mut types = TxTypes::from_iter([LEGACY_TX_TYPE_ID, EIP2930_TX_TYPE_ID]);
+        if chain_spec.is_london_active_at_block(number) {
+            types.insert(EIP1559_TX_TYPE_ID);
+        }
+        if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+            types.insert(EIP4844_TX_TYPE_ID);
+        }
+        if chain_spec.is_prague_active_at_timestamp(timestamp) {
+            types.insert(EIP7702_TX_TYPE_ID);
+        }
+        types
+    }
+
+    /// Read signer balances from `stakes` to enforce
+    /// [`PoaChainSpec::min_signer_stake`]; without a source the stake is not checked.
+    pub fn with_stake_source(mut self, stakes: StakeSource) -> Self {
+        self.stakes = Some(stakes);
+        self
+    }
+
+    /// Read parent gas limits from `gas_limits` to bound the steps of the gas limit
+    /// ramp; without a source, ramp blocks are only checked against the gas limit
+    /// bounds.
+    pub fn with_gas_limit_source(mut self, gas_limits: GasLimitSource) -> Self {
+        self.gas_limits = Some(gas_limits);
+        self
+    }
+
+    /// Read the parent's signer set from `signer_sets` to check the signer list of
+    /// epoch blocks ([`validate_epoch_signers`](Self::validate_epoch_signers));
+    /// without a source only its format is checked.
+    pub fn with_signer_set_source(mut self, signer_sets: SignerSetSource) -> Self {
+        self.signer_sets = Some(signer_sets);
+        self
+    }
+
+    /// [`Snapshot`] of epoch block `header`, for the [`SnapshotStore`]; None for other
+    /// blocks, genesis and dev mode, whose blocks carry no signer list.
+    pub fn epoch_snapshot(
+        &self,
+        header: &SealedHeader<Header>,
+    ) -> Result<Option<Snapshot>, PoaConsensusError> {
+        if self.dev_mode || header.number == 0 || !self.is_epoch_block(header.number) {
+            return Ok(None);
+        }
+        let signers = self.extract_signers_from_epoch_block(header.header())?;
+        Ok(Some(Snapshot {
+            number: header.number,
+            hash: header.hash(),
+            signers,
+        }))
+    }
+
     /// Create an Arc-wrapped instance
     pub fn arc(chain_spec: Arc<PoaChainSpec>) -> Arc<Self> {
         Arc::new(Self::new(chain_spec))
     }
 
+    /// Recover the signer of a sealed header.
+    ///
+    /// Memoized in the [`signature_cache`](Self::signature_cache), if any, by the hash
+    /// the header was sealed with: a header validated again is a map hit instead of
+    /// an ECDSA recovery, and no lookup hashes the header.
+    #[inline]
+    pub fn recover_sealed_signer(
+        &self,
+        header: &SealedHeader<Header>,
+    ) -> Result<Address, PoaConsensusError> {
+        match &self.signature_cache {
+            Some(cache) => cache.get_or_recover(header.hash(), || self.recover_signer(header)),
+            None => self.recover_signer(header),
+        }
+    }
+
     /// Extract the signer address from the block's extra data.
     ///
     /// This is on the hot path for every validated block header.  The two error
@@ -110,20 +331,25 @@ impl PoaConsensus {
     #[inline]
     pub fn recover_signer(&self, header: &Header) -> Result<Address, PoaConsensusError> {
         let extra_data = &header.extra_data;
+        let seal_end = metadata::seal_end(extra_data);
 
         // Extra data must contain at least vanity + seal
         let min_length = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH;
-        if extra_data.len() < min_length {
-            return Err(cold_extra_data_too_short(min_length, extra_data.len()));
+        if seal_end < min_length {
+            return Err(cold_extra_data_too_short(min_length, seal_end));
         }
 
-        // Extract the signature from the end of extra data
-        let signature_start = extra_data.len() - EXTRA_SEAL_LENGTH;
-        let signature_bytes = &extra_data[signature_start..];
+        // Extract the signature from the end of extra data (before any metadata suffix)
+        let signature_start = seal_end - EXTRA_SEAL_LENGTH;
+        let signature_bytes = &extra_data[signature_start..seal_end];
 
-        // Parse signature (r, s, v format)
-        let signature = Signature::try_from(signature_bytes)
-            .map_err(|_| cold_invalid_signature())?;
+        // Parse signature (r, s, v format; v = recovery id 0/1, legacy 27/28 accepted)
+        let signature = bytes_to_signature(signature_bytes)
+            .map_err(|e| cold_invalid_signature(SealError::MalformedSignature(e)))?;
+        if self.chain_spec.low_s_seal_required(header.number) && !is_low_s(&signature) {
+            let malleable = SealError::MalformedSignature(SignerError::MalleableSignature);
+            return Err(cold_invalid_signature(malleable));
+        }
 
         // Calculate the seal hash (header hash without the signature)
         let seal_hash = self.seal_hash(header);
@@ -131,23 +357,18 @@ impl PoaConsensus {
         // Recover the signer address
         signature
             .recover_address_from_prehash(&seal_hash)
-            .map_err(|_| cold_invalid_signature())
+            .map_err(|e| cold_invalid_signature(SealError::RecoveryFailed(e)))
     }
 
     /// Calculate the hash used for sealing (excludes the signature from extra data).
     ///
-    /// Uses `Bytes::slice` to produce the truncated extra_data view — this shares
-    /// the underlying buffer (O(1) arc bump, no heap copy).  The Header struct copy
-    /// that follows is cheap: all other fields are fixed-size primitives (B256, Bloom,
-    /// u64, etc.) with no heap allocation.
+    /// Without a metadata suffix, `Bytes::slice` produces the truncated extra_data
+    /// view — this shares the underlying buffer (O(1) arc bump, no heap copy).  The
+    /// Header struct copy that follows is cheap: all other fields are fixed-size
+    /// primitives (B256, Bloom, u64, etc.) with no heap allocation.
     pub fn seal_hash(&self, header: &Header) -> B256 {
-        let extra_len = header.extra_data.len();
-        // Produce a truncated extra_data that shares the same Bytes buffer (arc bump).
-        let truncated_extra = if extra_len >= EXTRA_SEAL_LENGTH {
-            header.extra_data.slice(..extra_len - EXTRA_SEAL_LENGTH)
-        } else {
-            header.extra_data.clone()
-        };
+        // Cut out only the seal; a metadata suffix stays covered by the signature.
+        let truncated_extra = metadata::without_seal(&header.extra_data);
 
         // Struct-copy the header with only extra_data replaced; no additional heap
         // allocation because all other Header fields are Copy/fixed-size.
@@ -180,6 +401,11 @@ impl PoaConsensus {
     /// always sets it to U256::ZERO on block deserialization. For Engine API compatibility,
     /// all POA blocks must use difficulty = 0. POA authority is determined by the ECDSA
     /// signature in extra_data, not by difficulty.
+    ///
+    /// Clique's difficulty 2 (in turn) / 1 (out of turn) cannot be carried: a header
+    /// sealed with either would reach peers through the Engine API with difficulty 0
+    /// and a different hash. The in-turn preference it encodes is applied by
+    /// [`score_chain`](Self::score_chain) from the recovered sealer instead.
     pub fn validate_difficulty(
         &self,
         header: &Header,
@@ -192,158 +418,335 @@ impl PoaConsensus {
         Ok(())
     }
 
-    /// Extract the signer list from an epoch block's extra data
-    pub fn extract_signers_from_epoch_block(
-        &self,
+    /// Validate fork-gated header fields against the chain's hardfork schedule.
+    ///
+    /// Each field must be absent before its fork activates and present after:
+    ///
+    /// | Fork     | Field(s)                                                     |
+    /// |----------|--------------------------------------------------------------|
+    /// | London   | `base_fee_per_gas`                                           |
+    /// | Shanghai | `withdrawals_root`                                           |
+    /// | Cancun   | `blob_gas_used`, `excess_blob_gas`, `parent_beacon_block_root` |
+    /// | Prague   | `requests_hash`                                              |
+    ///
+    /// `blob_gas_used` must also be a multiple of `DATA_GAS_PER_BLOB` (EIP-4844).
+    /// The clique checks never look at these fields, so without this a buggy
+    /// builder could produce blocks other clients reject.
+    pub fn validate_fork_fields(
+        chain_spec: &impl EthereumHardforks,
         header: &Header,
-    ) -> Result<Vec<Address>, PoaConsensusError> {
-        let extra_data = &header.extra_data;
+    ) -> Result<(), ConsensusError> {
+        let london = chain_spec.is_london_active_at_block(header.number);
+        match (london, header.base_fee_per_gas.is_some()) {
+            (true, false) => return Err(ConsensusError::BaseFeeMissing),
+            (false, true) => return Err(PoaConsensusError::BaseFeeBeforeLondon.into()),
+            _ => {}
+        }
 
-        let min_length = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH;
-        if extra_data.len() < min_length {
-            return Err(PoaConsensusError::ExtraDataTooShort {
-                expected: min_length,
-                got: extra_data.len(),
-            });
+        let shanghai = chain_spec.is_shanghai_active_at_timestamp(header.timestamp);
+        match (shanghai, header.withdrawals_root.is_some()) {
+            (true, false) => return Err(ConsensusError::WithdrawalsRootMissing),
+            (false, true) => return Err(ConsensusError::WithdrawalsRootUnexpected),
+            _ => {}
         }
 
-        // In epoch blocks, format is: vanity (32) + signers (N*20) + seal (65)
-        let signers_data_len = extra_data.len() - EXTRA_VANITY_LENGTH - EXTRA_SEAL_LENGTH;
+        let cancun = chain_spec.is_cancun_active_at_timestamp(header.timestamp);
+        match (cancun, header.blob_gas_used) {
+            (true, None) => return Err(ConsensusError::BlobGasUsedMissing),
+            (false, Some(_)) => return Err(ConsensusError::BlobGasUsedUnexpected),
+            (true, Some(blob_gas_used)) if blob_gas_used % DATA_GAS_PER_BLOB != 0 => {
+                return Err(ConsensusError::BlobGasUsedNotMultipleOfBlobGasPerBlob {
+                    blob_gas_used,
+                    blob_gas_per_blob: DATA_GAS_PER_BLOB,
+                })
+            }
+            _ => {}
+        }
+        match (cancun, header.excess_blob_gas.is_some()) {
+            (true, false) => return Err(ConsensusError::ExcessBlobGasMissing),
+            (false, true) => return Err(ConsensusError::ExcessBlobGasUnexpected),
+            _ => {}
+        }
+        match (cancun, header.parent_beacon_block_root.is_some()) {
+            (true, false) => return Err(ConsensusError::ParentBeaconBlockRootMissing),
+            (false, true) => return Err(ConsensusError::ParentBeaconBlockRootUnexpected),
+            _ => {}
+        }
 
-        if !signers_data_len.is_multiple_of(ADDRESS_LENGTH) {
-            return Err(PoaConsensusError::InvalidSignerList);
+        let prague = chain_spec.is_prague_active_at_timestamp(header.timestamp);
+        match (prague, header.requests_hash.is_some()) {
+            (true, false) => return Err(ConsensusError::RequestsHashMissing),
+            (false, true) => return Err(ConsensusError::RequestsHashUnexpected),
+            _ => {}
         }
 
-        let num_signers = signers_data_len / ADDRESS_LENGTH;
-        let mut signers = Vec::with_capacity(num_signers);
+        Ok(())
+    }
 
-        // `chunks_exact` gives the compiler a stronger alignment/stride guarantee
-        // than manual index arithmetic, enabling better auto-vectorisation for the
-        // address-copy loop.  The slice is already checked to be a multiple of
-        // ADDRESS_LENGTH (20 bytes) above, so `remainder()` will always be empty.
-        let signers_slice =
-            &extra_data[EXTRA_VANITY_LENGTH..EXTRA_VANITY_LENGTH + signers_data_len];
-        for chunk in signers_slice.chunks_exact(ADDRESS_LENGTH) {
-            signers.push(Address::from_slice(chunk));
+    /// Validate that `header` directly extends `parent`.
+    ///
+    /// Checks both linkage fields: `header.number == parent.number + 1` and
+    /// `header.parent_hash == parent.hash()`. Reports
+    /// [`ConsensusError::ParentBlockNumberMismatch`] or
+    /// [`ConsensusError::ParentHashMismatch`] respectively.
+    pub fn validate_parent_hash(
+        header: &Header,
+        parent: &SealedHeader<Header>,
+    ) -> Result<(), ConsensusError> {
+        let parent_number = parent.header().number;
+        if parent_number.checked_add(1) != Some(header.number) {
+            return Err(ConsensusError::ParentBlockNumberMismatch {
+                parent_block_number: parent_number,
+                block_number: header.number,
+            });
         }
 
-        Ok(signers)
-    }
+        if header.parent_hash != parent.hash() {
+            return Err(ConsensusError::ParentHashMismatch(
+                GotExpected {
+                    got: header.parent_hash,
+                    expected: parent.hash(),
+                }
+                .into(),
+            ));
+        }
 
-    /// Returns a reference to the chain spec
-    #[inline]
-    pub fn chain_spec(&self) -> &Arc<PoaChainSpec> {
-        &self.chain_spec
+        Ok(())
     }
 
-    // ─── Fork Choice Rule ─────────────────────────────────────────────
-    //
-    // POA uses difficulty=0 for Engine API compatibility, so we can't use
-    // cumulative difficulty for fork choice. Instead, we score chains by
-    // counting how many blocks were signed by their in-turn signer.
-    // In-turn blocks are preferred because they represent orderly round-robin
-    // block production, indicating a healthier chain.
+    /// Validate the vanity against the strict policy (printable ASCII + zero padding).
+    ///
+    /// Enforced by `validate_header` only when the chain spec enables
+    /// [`strict_vanity`](PoaChainSpec::strict_vanity).
+    pub fn validate_vanity(header: &Header) -> Result<(), ConsensusError> {
+        match vanity::invalid_vanity_byte(&header.extra_data) {
+            Some((offset, byte)) => Err(cold_invalid_vanity(offset, byte).into()),
+            None => Ok(()),
+        }
+    }
 
-    /// Check if a block was signed by the expected in-turn signer.
+    /// Reject dynamic-fee transactions on a chain with EIP-1559 disabled.
     ///
-    /// The in-turn signer for block N is `signers[N % signers.len()]`.
-    /// Returns `None` if the signer cannot be recovered (dev mode, missing sig).
-    #[inline]
-    pub fn is_in_turn(&self, header: &Header) -> Option<bool> {
-        let expected = self.chain_spec.expected_signer(header.number)?;
-        let actual = self.recover_signer(header).ok()?;
-        Some(actual == expected)
+    /// Without a base fee, `maxFeePerGas` / `maxPriorityFeePerGas` have no meaning, so
+    /// only legacy and EIP-2930 transactions are allowed. Applied in
+    /// `validate_block_pre_execution` when [`PoaChainSpec::eip1559_disabled`] is set.
+    pub fn validate_legacy_fee_transactions<T: alloy_consensus::Transaction>(
+        transactions: &[T],
+    ) -> Result<(), ConsensusError> {
+        match transactions.iter().position(|tx| tx.is_dynamic_fee()) {
+            Some(index) => Err(PoaConsensusError::DynamicFeeTransaction {
+                index,
+                tx_type: transactions[index].ty(),
+            }
+            .into()),
+            None => Ok(()),
+        }
     }
 
-    /// Score a chain segment by counting in-turn blocks.
+    /// Reject transactions whose EIP-2718 type is not in `allowed`.
     ///
-    /// Higher score = more blocks signed by their expected in-turn signer.
-    /// This is used for fork choice: the chain with more in-turn blocks is preferred.
-    pub fn score_chain(&self, headers: &[Header]) -> u64 {
-        headers
+    /// Applied in `validate_block_pre_execution` with the types allowed at the block
+    /// (see [`allowed_tx_types_at`](Self::allowed_tx_types_at)).
+    pub fn validate_transaction_types<T: Typed2718>(
+        transactions: &[T],
+        allowed: TxTypes,
+    ) -> Result<(), ConsensusError> {
+        match transactions
             .iter()
-            .fold(0u64, |acc, h| acc + self.is_in_turn(h).unwrap_or(false) as u64)
+            .position(|tx| !allowed.contains(tx.ty()))
+        {
+            Some(index) => Err(PoaConsensusError::DisallowedTransactionType {
+                index,
+                tx_type: transactions[index].ty(),
+            }
+            .into()),
+            None => Ok(()),
+        }
     }
 
-    /// Compare two chain segments for fork choice.
+    /// Validate that a signer holding `stake` wei meets the `required` stake.
     ///
-    /// Returns `std::cmp::Ordering`:
-    /// - `Greater` if chain_a is preferred (more in-turn blocks)
-    /// - `Less` if chain_b is preferred
-    /// - `Equal` if tied (fall back to longest chain)
+    /// Applied in `validate_block_pre_execution` to the block's sealer and its balance
+    /// in the parent state when [`PoaChainSpec::min_signer_stake`] is set.
+    #[inline]
+    pub fn validate_signer_stake(
+        signer: Address,
+        stake: U256,
+        required: U256,
+    ) -> Result<(), ConsensusError> {
+        if stake < required {
+            return Err(PoaConsensusError::SignerUnderstaked {
+                signer,
+                stake,
+                required,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Validate that the RLP encoding of `transactions` is at most `max` bytes.
     ///
-    /// When scores are equal, the longer chain wins.
-    pub fn compare_chains(&self, chain_a: &[Header], chain_b: &[Header]) -> std::cmp::Ordering {
-        let score_a = self.score_chain(chain_a);
-        let score_b = self.score_chain(chain_b);
-        score_a
-            .cmp(&score_b)
-            .then_with(|| chain_a.len().cmp(&chain_b.len()))
+    /// Applied in `validate_block_pre_execution` when
+    /// [`PoaChainSpec::max_block_size_bytes`] is set.
+    pub fn validate_block_size<T: alloy_rlp::Encodable>(
+        transactions: &[T],
+        max: usize,
+    ) -> Result<(), ConsensusError> {
+        let size = alloy_rlp::list_length::<T, T>(transactions);
+        if size > max {
+            return Err(PoaConsensusError::BlockTooLarge { size, max }.into());
+        }
+        Ok(())
     }
-}
 
-// Use concrete Header type instead of generic H so we can access extra_data
-// for POA signature verification. This is safe because PoaNode always uses EthPrimitives
-// which has Header = alloy_consensus::Header.
-impl HeaderValidator<Header> for PoaConsensus {
-    #[inline]
-    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
-        // In production mode, verify POA signature
-        if !self.dev_mode {
-            // Recover signer from the signature in extra_data
-            // (recover_signer validates extra_data length internally)
-            let signer = self
-                .recover_signer(header.header())
-                .map_err(Into::<ConsensusError>::into)?;
+    /// Validate that every transaction sender is admitted by `acl`.
+    ///
+    /// Applied in `validate_block_post_execution`, on the senders recovered for
+    /// execution, from [`PoaChainSpec::acl_activation_block`] on.
+    pub fn validate_sender_access(
+        senders: &[Address],
+        acl: &AccessControlList,
+    ) -> Result<(), ConsensusError> {
+        for &sender in senders {
+            if acl.is_blacklisted(&sender) {
+                return Err(PoaConsensusError::BlacklistedSender(sender).into());
+            }
+            if !acl.is_whitelisted(&sender) {
+                return Err(PoaConsensusError::SenderNotWhitelisted(sender).into());
+            }
+        }
+        Ok(())
+    }
 
-            // Verify the signer is in the authorized signers list
-            self.validate_signer(&signer)
-                .map_err(Into::<ConsensusError>::into)?;
+    /// Validate the signer reward withdrawal of block `block_number`, sealed by
+    /// `signer` in turn or not.
+    ///
+    /// The block must carry exactly the withdrawal of [`expected_reward`] under
+    /// `reward` and no other reward withdrawal; without a reward rule it carries none.
+    pub fn validate_signer_reward(
+        block_number: u64,
+        withdrawals: &[Withdrawal],
+        reward: Option<&UncleRewardConfig>,
+        signer: Address,
+        in_turn: bool,
+    ) -> Result<(), ConsensusError> {
+        let got: Vec<_> = withdrawals
+            .iter()
+            .filter(|w| w.validator_index == REWARD_VALIDATOR_INDEX)
+            .copied()
+            .collect();
+        let expected = reward
+            .and_then(|reward| expected_reward(reward, block_number, withdrawals, signer, in_turn));
+        if got.as_slice() != expected.as_slice() {
+            return Err(PoaConsensusError::InvalidSignerReward { got, expected }.into());
         }
+        Ok(())
+    }
 
+    /// Validate that the header's `transactions_root` is the trie root of `transactions`.
+    ///
+    /// Without this a block could carry a body other than the one its (signed) header
+    /// commits to.
+    pub fn validate_transactions_root<H: BlockHeader, T: Encodable2718>(
+        header: &H,
+        transactions: &[T],
+    ) -> Result<(), ConsensusError> {
+        let expected = header.transactions_root();
+        let computed = alloy_consensus::proofs::calculate_transaction_root(transactions);
+        if expected != computed {
+            return Err(PoaConsensusError::TransactionsRootMismatch { expected, computed }.into());
+        }
         Ok(())
     }
 
+    /// Validate that the header commits to no ommers (POA has no uncles).
     #[inline]
-    fn validate_header_against_parent(
-        &self,
+    pub fn validate_ommers_hash<H: BlockHeader>(header: &H) -> Result<(), ConsensusError> {
+        let got = header.ommers_hash();
+        if got != EMPTY_OMMER_ROOT_HASH {
+            return Err(PoaConsensusError::NonEmptyOmmersHash { got }.into());
+        }
+        Ok(())
+    }
+
+    /// Validate that the header at the checkpoint height has the trusted hash.
+    #[inline]
+    pub fn validate_checkpoint_hash(
+        checkpoint: &Checkpoint,
         header: &SealedHeader<Header>,
-        parent: &SealedHeader<Header>,
     ) -> Result<(), ConsensusError> {
-        let h = header.header();
-        let p = parent.header();
-
-        // Validate block number
-        if h.number() != p.number() + 1 {
-            return Err(ConsensusError::ParentBlockNumberMismatch {
-                parent_block_number: p.number(),
-                block_number: h.number(),
-            });
+        if header.hash() != checkpoint.block_hash {
+            return Err(PoaConsensusError::CheckpointMismatch {
+                number: header.number,
+                expected: checkpoint.block_hash,
+                got: header.hash(),
+            }
+            .into());
         }
+        Ok(())
+    }
 
-        // Validate parent hash
-        if h.parent_hash() != parent.hash() {
-            return Err(ConsensusError::ParentHashMismatch(
-                GotExpected {
-                    got: h.parent_hash(),
-                    expected: parent.hash(),
-                }
-                .into(),
-            ));
+    /// Validate the EIP-4788 `parent_beacon_block_root` against the active `spec`.
+    ///
+    /// A POA chain has no beacon chain, so the field carries no information:
+    /// - before Cancun it must be absent,
+    /// - after Cancun with a stubbed beacon roots contract
+    ///   ([`PoaChainSpec::with_eip4788_beacon_root`]) it must be the stub's root,
+    /// - after Cancun without a stub any value is accepted.
+    pub fn validate_parent_beacon_root(
+        header: &Header,
+        spec: SpecId,
+        stub_root: Option<B256>,
+    ) -> Result<(), ConsensusError> {
+        let got = header.parent_beacon_block_root;
+        let expected = match (spec.is_enabled_in(SpecId::CANCUN), stub_root) {
+            (false, _) => None,
+            (true, Some(root)) => Some(root),
+            (true, None) => return Ok(()),
+        };
+        if got != expected {
+            return Err(PoaConsensusError::InvalidParentBeaconRoot { expected, got }.into());
         }
+        Ok(())
+    }
 
-        // Validate timestamp (must be after parent + minimum period)
-        let min_timestamp = p.timestamp() + self.chain_spec.block_period();
-        if h.timestamp() < min_timestamp {
-            return Err(cold_timestamp_too_early(h.timestamp(), p.timestamp()).into());
+    /// Validate that the header gas limit lies within the absolute protocol bounds.
+    ///
+    /// `MIN_GAS_LIMIT` (5000) ≤ `gas_limit` ≤ `MAX_GAS_LIMIT` (2^63 - 1), the same
+    /// bounds geth enforces. Independent of the parent, so it runs in `validate_header`.
+    #[inline]
+    pub fn validate_gas_limit_bounds(header: &Header) -> Result<(), ConsensusError> {
+        let gas_limit = header.gas_limit;
+        if gas_limit < MIN_GAS_LIMIT {
+            return Err(cold_gas_limit_too_low(gas_limit).into());
+        }
+        if gas_limit > MAX_GAS_LIMIT {
+            return Err(cold_gas_limit_too_high(gas_limit).into());
         }
+        Ok(())
+    }
+
+    /// Validate the block gas limit against its parent.
+    ///
+    /// Combines the absolute bounds check with the EIP-1559 drift rule: the gas limit
+    /// may not move by more than `parent_gas_limit / 1024` in either direction.
+    pub fn validate_block_gas_target(
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), ConsensusError> {
+        Self::validate_gas_limit_bounds(header)?;
+        Self::validate_gas_limit_drift(header.gas_limit, parent.gas_limit)
+    }
 
-        // Validate gas limit changes (EIP-1559 compatible)
-        let parent_gas_limit = p.gas_limit();
-        let current_gas_limit = h.gas_limit();
+    /// The EIP-1559 drift rule: at most `parent_gas_limit / 1024` either way.
+    fn validate_gas_limit_drift(
+        current_gas_limit: u64,
+        parent_gas_limit: u64,
+    ) -> Result<(), ConsensusError> {
         let max_change = parent_gas_limit / 1024;
 
-        if current_gas_limit > parent_gas_limit + max_change {
+        if current_gas_limit > parent_gas_limit.saturating_add(max_change) {
             return Err(ConsensusError::GasLimitInvalidIncrease {
                 parent_gas_limit,
                 child_gas_limit: current_gas_limit,
@@ -359,463 +762,2933 @@ impl HeaderValidator<Header> for PoaConsensus {
 
         Ok(())
     }
-}
 
-impl<B: Block> Consensus<B> for PoaConsensus
-where
-    PoaConsensus: HeaderValidator<B::Header>,
-{
-    fn validate_body_against_header(
+    /// Validate the block gas limit against its parent, leaving ramp steps to
+    /// [`Self::validate_gas_limit_ramp`].
+    ///
+    /// Inside the gas limit ramp (see [`PoaChainSpec::with_max_gas_per_block_ramp`]) a
+    /// step depends on the governance gas limit of the parent state, which header
+    /// validation has no access to: only the bounds are checked here, the step before
+    /// execution. Elsewhere this is [`Self::validate_block_gas_target`].
+    pub fn validate_gas_limit_change(
         &self,
-        _body: &B::Body,
-        header: &SealedHeader<B::Header>,
+        header: &Header,
+        parent: &Header,
     ) -> Result<(), ConsensusError> {
-        let h = header.header();
-        // Validate that gas used doesn't exceed gas limit
-        if h.gas_used() > h.gas_limit() {
-            return Err(ConsensusError::HeaderGasUsedExceedsGasLimit {
-                gas_used: h.gas_used(),
-                gas_limit: h.gas_limit(),
+        if self
+            .chain_spec
+            .gas_limit_ramp_remaining(header.number)
+            .is_none()
+        {
+            return Self::validate_block_gas_target(header, parent);
+        }
+        Self::validate_gas_limit_bounds(header)
+    }
+
+    /// Validate a gas limit step of the ramp, `remaining` blocks before its end.
+    ///
+    /// Any gas limit between the parent's and
+    /// [`ramp_target`](crate::chainspec::gas_ramp::ramp_target) toward the
+    /// ChainConfig gas limit `target` is accepted, as is the EIP-1559 drift.
+    pub fn validate_gas_limit_ramp(
+        gas_limit: u64,
+        parent_gas_limit: u64,
+        target: u64,
+        remaining: u64,
+    ) -> Result<(), ConsensusError> {
+        let ramp = crate::chainspec::gas_ramp::ramp_target(parent_gas_limit, target, remaining);
+        let (low, high) = (parent_gas_limit.min(ramp), parent_gas_limit.max(ramp));
+        if (low..=high).contains(&gas_limit) {
+            return Ok(());
+        }
+        Self::validate_gas_limit_drift(gas_limit, parent_gas_limit)
+    }
+
+    /// Validate the header's blob gas against the chain's [`FeeMarket`].
+    ///
+    /// `Legacy` and `Eip1559` markets allow no blob gas; `Eip4844` allows up to its
+    /// maximum. The base fee's presence is checked by [`Self::validate_fork_fields`]
+    /// (a `Legacy` chain has no London).
+    pub fn validate_fee_market(header: &Header, market: &FeeMarket) -> Result<(), ConsensusError> {
+        let max_blob_gas_per_block = market.max_blob_gas_per_block();
+        match header.blob_gas_used {
+            Some(blob_gas_used) if blob_gas_used > max_blob_gas_per_block => {
+                Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                    blob_gas_used,
+                    max_blob_gas_per_block,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate the header's blob gas fields against its parent (EIP-4844).
+    ///
+    /// Before Cancun both `blob_gas_used` and `excess_blob_gas` must be absent. From
+    /// Cancun on `blob_gas_used` may not exceed the maximum of the chain's blob
+    /// parameters at the header's timestamp, and `excess_blob_gas` must equal the one
+    /// alloy derives from the parent under those parameters (EIP-7918 from Osaka).
+    /// Missing fields and parents without blob fields are left to
+    /// [`Self::validate_fork_fields`].
+    pub fn validate_blob_gas_fields(
+        chain_spec: &(impl EthChainSpec + EthereumHardforks),
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), ConsensusError> {
+        if !chain_spec.is_cancun_active_at_timestamp(header.timestamp) {
+            if header.blob_gas_used.is_some() {
+                return Err(ConsensusError::BlobGasUsedUnexpected);
+            }
+            if header.excess_blob_gas.is_some() {
+                return Err(ConsensusError::ExcessBlobGasUnexpected);
+            }
+            return Ok(());
+        }
+
+        let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp);
+        if let (Some(blob_gas_used), Some(params)) = (header.blob_gas_used, blob_params) {
+            let max_blob_gas_per_block = params.max_blob_gas_per_block();
+            if blob_gas_used > max_blob_gas_per_block {
+                return Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                    blob_gas_used,
+                    max_blob_gas_per_block,
+                });
+            }
+        }
+
+        let (Some(got), Some(expected)) = (
+            header.excess_blob_gas,
+            parent.maybe_next_block_excess_blob_gas(blob_params),
+        ) else {
+            return Ok(());
+        };
+        if got != expected {
+            return Err(ConsensusError::ExcessBlobGasDiff {
+                diff: GotExpected { got, expected },
+                parent_excess_blob_gas: parent.excess_blob_gas.unwrap_or_default(),
+                parent_blob_gas_used: parent.blob_gas_used.unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate the header base fee against the one derived from its parent.
+    ///
+    /// The expected value comes from the chain spec's `next_block_base_fee`, so it is
+    /// the pinned [`base_fee_override`](PoaChainSpec::base_fee_override) when one is
+    /// set, follows the chain's gas target when one is set and Ethereum's
+    /// `gas_limit / elasticity` otherwise. Headers without a base fee (pre-London
+    /// parents, EIP-1559 disabled) are left to [`Self::validate_fork_fields`].
+    pub fn validate_base_fee(
+        &self,
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), ConsensusError> {
+        let Some(got) = header.base_fee_per_gas else {
+            return Ok(());
+        };
+        match self.chain_spec.next_block_base_fee(parent, header.timestamp) {
+            Some(expected) if expected != got => {
+                Err(ConsensusError::BaseFeeDiff(GotExpected { got, expected }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Extract the signer list from an epoch block's extra data
+    pub fn extract_signers_from_epoch_block(
+        &self,
+        header: &Header,
+    ) -> Result<Vec<Address>, PoaConsensusError> {
+        let extra_data = &header.extra_data;
+        let seal_end = metadata::seal_end(extra_data);
+
+        let min_length = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH;
+        if seal_end < min_length {
+            return Err(PoaConsensusError::ExtraDataTooShort {
+                expected: min_length,
+                got: seal_end,
+            });
+        }
+
+        // In epoch blocks, format is: vanity (32) + signers (N*20) + seal (65)
+        // [+ metadata suffix]
+        let signers_data_len = seal_end - EXTRA_VANITY_LENGTH - EXTRA_SEAL_LENGTH;
+
+        if !signers_data_len.is_multiple_of(ADDRESS_LENGTH) {
+            return Err(PoaConsensusError::InvalidSignerList);
+        }
+
+        let num_signers = signers_data_len / ADDRESS_LENGTH;
+        let mut signers = Vec::with_capacity(num_signers);
+
+        // `chunks_exact` gives the compiler a stronger alignment/stride guarantee
+        // than manual index arithmetic, enabling better auto-vectorisation for the
+        // address-copy loop.  The slice is already checked to be a multiple of
+        // ADDRESS_LENGTH (20 bytes) above, so `remainder()` will always be empty.
+        let signers_slice =
+            &extra_data[EXTRA_VANITY_LENGTH..EXTRA_VANITY_LENGTH + signers_data_len];
+        for chunk in signers_slice.chunks_exact(ADDRESS_LENGTH) {
+            signers.push(Address::from_slice(chunk));
+        }
+
+        Ok(signers)
+    }
+
+    /// Check that `header.extra_data` has the epoch-block layout:
+    /// vanity (32) + one or more signer addresses (N*20) + seal (65), optionally
+    /// followed by a metadata suffix whose JSON must parse, or by a 32-byte metadata
+    /// commitment ([`metadata`]).
+    pub fn check_extra_data_format(header: &Header) -> Result<(), PoaConsensusError> {
+        metadata::decode_metadata(&header.extra_data)
+            .map_err(PoaConsensusError::InvalidBlockMetadata)?;
+        let len = metadata::seal_end(&header.extra_data);
+        let min_length = EXTRA_VANITY_LENGTH + ADDRESS_LENGTH + EXTRA_SEAL_LENGTH;
+        if len < min_length {
+            return Err(cold_extra_data_too_short(min_length, len));
+        }
+        if !(len - EXTRA_VANITY_LENGTH - EXTRA_SEAL_LENGTH).is_multiple_of(ADDRESS_LENGTH) {
+            return Err(PoaConsensusError::InvalidSignerList);
+        }
+        Ok(())
+    }
+
+    /// Validate the signer vote in `header.mix_hash` (see [`votes`]): zero, or a
+    /// well-formed vote outside epoch blocks.
+    pub fn validate_vote(header: &Header, epoch: u64) -> Result<(), PoaConsensusError> {
+        let vote = votes::decode_vote(&header.mix_hash)?;
+        if vote.is_some() && header.number.is_multiple_of(epoch) {
+            return Err(PoaConsensusError::InvalidVote {
+                mix_hash: header.mix_hash,
             });
         }
-        Ok(())
+        Ok(())
+    }
+
+    /// Validate that an epoch block's `extra_data` resets to the genesis layout.
+    ///
+    /// Epoch blocks carry vanity + the full signer list + seal and no vote: the
+    /// `mix_hash` must decode to no vote ([`votes::decode_vote`]) and the signer list
+    /// must be well-formed. Which signers it lists depends on the parent state and is
+    /// checked before execution ([`validate_epoch_signers`](Self::validate_epoch_signers)).
+    /// Other blocks pass unchecked.
+    pub fn validate_epoch_extra_data(
+        header: &Header,
+        chain_spec: &PoaChainSpec,
+    ) -> Result<(), ConsensusError> {
+        if !header.number.is_multiple_of(chain_spec.epoch()) {
+            return Ok(());
+        }
+        if votes::decode_vote(&header.mix_hash)?.is_some() {
+            return Err(PoaConsensusError::VoteInEpochBlock {
+                mix_hash: header.mix_hash,
+            }
+            .into());
+        }
+        Self::check_extra_data_format(header)?;
+        Ok(())
+    }
+
+    /// Validate that the signers listed in an epoch block's `extra_data`, compared
+    /// sorted, are exactly `expected`: the parent state's signer set (see
+    /// [`signer_set`]).
+    pub fn validate_epoch_signers(
+        extra_data: &Bytes,
+        expected: &[Address],
+    ) -> Result<(), ConsensusError> {
+        let seal_end = metadata::seal_end(extra_data);
+        let mut got: Vec<Address> = extra_data
+            .get(EXTRA_VANITY_LENGTH..seal_end.saturating_sub(EXTRA_SEAL_LENGTH))
+            .unwrap_or_default()
+            .chunks_exact(ADDRESS_LENGTH)
+            .map(Address::from_slice)
+            .collect();
+        let mut expected = expected.to_vec();
+        got.sort_unstable();
+        expected.sort_unstable();
+        if got != expected {
+            return Err(PoaConsensusError::EpochExtraDataMismatch { expected, got }.into());
+        }
+        Ok(())
+    }
+
+    /// Validate that every receipt names `sealed` as its block.
+    ///
+    /// Indexers join receipts to blocks by hash; a receipt carrying another hash (or
+    /// none) was fetched from a different fork or block and would be filed under the
+    /// wrong one.
+    pub fn validate_block_hash_consistency<R: ReceiptBlockHash>(
+        sealed: &SealedBlock<reth_ethereum::Block>,
+        receipts: &[R],
+    ) -> Result<(), ConsensusError> {
+        let expected = sealed.hash();
+        for (receipt_index, receipt) in receipts.iter().enumerate() {
+            let got = receipt.block_hash();
+            if got != Some(expected) {
+                return Err(PoaConsensusError::ReceiptBlockHashMismatch {
+                    receipt_index,
+                    expected,
+                    got,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the genesis block of `chain_spec` and seal it with `primary_signer`.
+    ///
+    /// The header is taken from the chain spec with a zero difficulty and the empty
+    /// ommers hash; its `extra_data` must list exactly the spec's signers and
+    /// `primary_signer` must be one of them. The seal changes the genesis hash, so a
+    /// launch that uses the sealed block must publish its `extra_data` in the genesis
+    /// file.
+    pub async fn sign_and_seal_genesis(
+        chain_spec: &PoaChainSpec,
+        signer_manager: &SignerManager,
+        primary_signer: Address,
+    ) -> Result<SealedBlock<reth_ethereum::Block>, ConsensusError> {
+        let mut header = Header {
+            difficulty: U256::ZERO,
+            ommers_hash: alloy_consensus::EMPTY_OMMER_ROOT_HASH,
+            ..chain_spec.genesis_header().clone()
+        };
+        Self::check_extra_data_format(&header)?;
+
+        let seal_end = metadata::seal_end(&header.extra_data);
+        let listed = &header.extra_data[EXTRA_VANITY_LENGTH..seal_end - EXTRA_SEAL_LENGTH];
+        let expected: Vec<u8> = chain_spec.signers().iter().flat_map(|a| a.into_array()).collect();
+        if listed != expected.as_slice() {
+            return Err(PoaConsensusError::InvalidSignerList.into());
+        }
+        if !chain_spec.is_authorized_signer(&primary_signer) {
+            return Err(cold_unauthorized_signer(primary_signer).into());
+        }
+
+        let signature = signer_manager
+            .sign_hash(&primary_signer, BlockSealer::seal_hash(&header))
+            .await
+            .map_err(PoaConsensusError::SealingFailed)?;
+        let mut extra_data = header.extra_data.to_vec();
+        extra_data[seal_end - EXTRA_SEAL_LENGTH..seal_end]
+            .copy_from_slice(&signature_to_bytes(&signature));
+        header.extra_data = extra_data.into();
+
+        let body = reth_ethereum::BlockBody {
+            transactions: Vec::new(),
+            ommers: Vec::new(),
+            withdrawals: header.withdrawals_root.map(|_| Default::default()),
+        };
+        Ok(SealedBlock::seal_slow(reth_ethereum::Block { header, body }))
+    }
+
+    /// Verify a chain of epoch checkpoints from genesis and return the head signer set.
+    ///
+    /// `checkpoints` must be the epoch blocks `epoch, 2 * epoch, ...` in order. Each
+    /// must be sealed by a signer of the previous set and carry a signer list
+    /// derivable from it (see [`checkpoint`] for the rule).
+    pub fn verify_checkpoint_chain(
+        &self,
+        checkpoints: &[SealedHeader<Header>],
+    ) -> Result<Snapshot, ConsensusError> {
+        if checkpoints.is_empty() {
+            return Err(PoaConsensusError::EmptyCheckpointChain.into());
+        }
+        let epoch = self.chain_spec.epoch();
+        let mut snapshot = Snapshot::genesis(&self.chain_spec);
+
+        for checkpoint in checkpoints {
+            let expected = snapshot.number.saturating_add(epoch);
+            if checkpoint.number != expected {
+                return Err(PoaConsensusError::CheckpointGap {
+                    expected,
+                    got: checkpoint.number,
+                }
+                .into());
+            }
+
+            let sealer = self.recover_sealed_signer(checkpoint)?;
+            if !snapshot.is_signer(&sealer) {
+                return Err(cold_unauthorized_signer(sealer).into());
+            }
+
+            let signers = self.extract_signers_from_epoch_block(checkpoint.header())?;
+            checkpoint::validate_transition(checkpoint.number, &snapshot.signers, &signers)?;
+
+            snapshot = Snapshot {
+                number: checkpoint.number,
+                hash: checkpoint.hash(),
+                signers,
+            };
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Recover the sealer of every header in `headers`, in order.
+    ///
+    /// Rebuilds the signer history when the snapshot database is lost: only the
+    /// headers themselves are needed. Seals are recovered in parallel; if several
+    /// are invalid, any one of their block numbers may be reported.
+    pub fn backfill_missing_signers(headers: &[Header]) -> Result<Vec<Address>, ConsensusError> {
+        headers
+            .par_iter()
+            .map(|header| {
+                BlockSealer::verify_signature(header).map_err(|err| {
+                    warn!(target: "poa::consensus", number = header.number, %err, "Invalid seal");
+                    ConsensusError::from(PoaConsensusError::InvalidSignatureAtBlock(header.number))
+                })
+            })
+            .collect()
+    }
+
+    /// Verify the checkpoints up to `header_tip` from `source` when the latest one is
+    /// newer than the executed `head`.
+    ///
+    /// Used by `--sync-mode light-headers` to establish the signer set of headers the
+    /// node has downloaded but not yet executed, before full verification resumes
+    /// from the latest checkpoint. `None` when no checkpoint lies above `head`: the
+    /// signer set the node already derived from its own chain is at least as recent.
+    pub fn light_sync_snapshot(
+        &self,
+        source: &impl CheckpointSource,
+        head: u64,
+        header_tip: u64,
+    ) -> Result<Option<Snapshot>, ConsensusError> {
+        let numbers: Vec<u64> =
+            checkpoint::checkpoint_numbers(self.chain_spec.epoch(), header_tip).collect();
+        if numbers.last().is_none_or(|&latest| latest <= head) {
+            return Ok(None);
+        }
+        let mut checkpoints = Vec::with_capacity(numbers.len());
+        for number in numbers {
+            match source.checkpoint_header(number) {
+                Some(header) => checkpoints.push(header),
+                None => {
+                    return Err(PoaConsensusError::CheckpointGap {
+                        expected: number,
+                        got: checkpoints.last().map_or(0, |h| h.number),
+                    }
+                    .into())
+                }
+            }
+        }
+        self.verify_checkpoint_chain(&checkpoints).map(Some)
+    }
+
+    /// Returns a reference to the chain spec
+    #[inline]
+    pub fn chain_spec(&self) -> &Arc<PoaChainSpec> {
+        &self.chain_spec
+    }
+
+    // ─── Fork Choice Rule ─────────────────────────────────────────────
+    //
+    // POA uses difficulty=0 for Engine API compatibility, so we can't use
+    // cumulative difficulty for fork choice. Instead, we score chains by
+    // counting how many blocks were signed by their in-turn signer.
+    // In-turn blocks are preferred because they represent orderly round-robin
+    // block production, indicating a healthier chain.
+
+    /// In-turn signer of `block_number` among `signers`: `signers[N % len]`.
+    ///
+    /// # Panics
+    ///
+    /// If `signers` is empty.
+    #[inline]
+    pub fn expected_signer(block_number: u64, signers: &[Address]) -> Address {
+        signers[(block_number % signers.len() as u64) as usize]
+    }
+
+    /// Extra delay before an out-of-turn signer seals a block of a `period`-second
+    /// chain: `period / 2 + 1` seconds, Clique's "wiggle", giving the in-turn signer
+    /// the first chance to seal.
+    #[inline]
+    pub fn out_of_turn_delay(period: u64) -> Duration {
+        Duration::from_secs(period / 2 + 1)
+    }
+
+    /// Check if a block was signed by the expected in-turn signer.
+    ///
+    /// The in-turn signer for block N is `signers[N % signers.len()]`.
+    /// Returns `None` if the signer cannot be recovered (dev mode, missing sig).
+    #[inline]
+    pub fn is_in_turn(&self, header: &Header) -> Option<bool> {
+        let expected = self.chain_spec.expected_signer(header.number)?;
+        let actual = self.recover_signer(header).ok()?;
+        Some(actual == expected)
+    }
+
+    /// In-turn signer of `next_block` under `snapshot`'s signer set.
+    ///
+    /// Lets a producer check whether it is expected to sign before building a payload.
+    /// Same round-robin as [`PoaChainSpec::expected_signer`]: `signers[N % len]`, in
+    /// the snapshot's list order. `None` for an empty signer set.
+    #[inline]
+    pub fn estimate_next_signer(snapshot: &Snapshot, next_block: u64) -> Option<Address> {
+        if snapshot.signers.is_empty() {
+            return None;
+        }
+        Some(Self::expected_signer(next_block, &snapshot.signers))
+    }
+
+    /// Score a chain segment by counting in-turn blocks.
+    ///
+    /// Higher score = more blocks signed by their expected in-turn signer.
+    /// This is used for fork choice: the chain with more in-turn blocks is preferred.
+    pub fn score_chain(&self, headers: &[Header]) -> u64 {
+        headers
+            .iter()
+            .fold(0u64, |acc, h| acc + self.is_in_turn(h).unwrap_or(false) as u64)
+    }
+
+    /// Compare two chain segments for fork choice.
+    ///
+    /// Returns `std::cmp::Ordering`:
+    /// - `Greater` if chain_a is preferred (more in-turn blocks)
+    /// - `Less` if chain_b is preferred
+    /// - `Equal` if tied (fall back to longest chain)
+    ///
+    /// When scores are equal, the longer chain wins.
+    pub fn compare_chains(&self, chain_a: &[Header], chain_b: &[Header]) -> std::cmp::Ordering {
+        let score_a = self.score_chain(chain_a);
+        let score_b = self.score_chain(chain_b);
+        score_a
+            .cmp(&score_b)
+            .then_with(|| chain_a.len().cmp(&chain_b.len()))
+    }
+}
+
+/// Log a failed consensus check at `warn` before the error is propagated.
+///
+/// Emitted inside the validation span, so the event carries the block number and hash.
+fn rejected(check: &'static str) -> impl Fn(&ConsensusError) {
+    move |err| warn!(target: "poa::consensus", check, %err, "Consensus check failed")
+}
+
+impl PoaConsensus {
+    /// Validate `header` on its own and against `parent`, recording every check run.
+    ///
+    /// Runs the checks of [`validate_header`](HeaderValidator::validate_header) and
+    /// [`validate_header_against_parent`](HeaderValidator::validate_header_against_parent)
+    /// and stops at the first failure, which is the last entry of the trail. Headers
+    /// below a trusted checkpoint are accepted without checks, leaving the trail empty.
+    /// Unlike regular validation, no epoch snapshot is persisted. See [`audit`].
+    #[instrument(
+        target = "poa::consensus",
+        skip_all,
+        fields(block_number = header.number, block_hash = ?header.hash())
+    )]
+    pub fn validate_header_with_audit(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+    ) -> (Result<(), ConsensusError>, Vec<AuditEntry>) {
+        let mut audit = AuditRecorder::enabled();
+        let result = self
+            .check_header(header, &mut audit)
+            .and_then(|()| self.check_header_against_parent(header, parent, &mut audit));
+        (result, audit.into_entries())
+    }
+
+    /// Body of [`validate_header`](HeaderValidator::validate_header), checks run
+    /// through `audit`.
+    fn check_header(
+        &self,
+        header: &SealedHeader<Header>,
+        audit: &mut AuditRecorder,
+    ) -> Result<(), ConsensusError> {
+        // Headers below a trusted checkpoint are accepted as-is
+        if let Some(checkpoint) = &self.checkpoint {
+            if header.number < checkpoint.block_number {
+                return Ok(());
+            }
+            if header.number == checkpoint.block_number {
+                debug!(target: "poa::consensus", check = "checkpoint", "Validating header");
+                audit
+                    .check("checkpoint", || Self::validate_checkpoint_hash(checkpoint, header))
+                    .inspect_err(rejected("checkpoint"))?;
+            }
+        }
+
+        // Gas limit bounds and fork-gated fields apply in every mode
+        debug!(target: "poa::consensus", check = "gas_limit", "Validating header");
+        audit
+            .check("gas_limit", || Self::validate_gas_limit_bounds(header.header()))
+            .inspect_err(rejected("gas_limit"))?;
+        debug!(target: "poa::consensus", check = "fork_fields", "Validating header");
+        audit
+            .check("fork_fields", || {
+                Self::validate_fork_fields(self.chain_spec.as_ref(), header.header())
+            })
+            .inspect_err(rejected("fork_fields"))?;
+        if let Some(market) = self.chain_spec.fee_market() {
+            debug!(target: "poa::consensus", check = "fee_market", "Validating header");
+            audit
+                .check("fee_market", || Self::validate_fee_market(header.header(), market))
+                .inspect_err(rejected("fee_market"))?;
+        }
+        debug!(target: "poa::consensus", check = "parent_beacon_root", "Validating header");
+        let spec = spec_id_at(self.chain_spec.as_ref(), header.timestamp, header.number);
+        let stub_root = self.chain_spec.eip4788_stub_root();
+        audit
+            .check("parent_beacon_root", || {
+                Self::validate_parent_beacon_root(header.header(), spec, stub_root)
+            })
+            .inspect_err(rejected("parent_beacon_root"))?;
+        if self.chain_spec.strict_vanity() {
+            debug!(target: "poa::consensus", check = "vanity", "Validating header");
+            audit
+                .check("vanity", || Self::validate_vanity(header.header()))
+                .inspect_err(rejected("vanity"))?;
+        }
+
+        // In production mode, verify POA signature
+        if !self.dev_mode {
+            // Recover signer from the signature in extra_data
+            // (recover_signer validates extra_data length internally)
+            debug!(target: "poa::consensus", check = "extra_data", "Validating header");
+            let signer = audit
+                .check("extra_data", || {
+                    self.recover_sealed_signer(header).map_err(Into::into)
+                })
+                .inspect_err(rejected("extra_data"))?;
+
+            // Verify the signer is in the authorized signers list
+            debug!(target: "poa::consensus", check = "signer", %signer, "Validating header");
+            audit
+                .check("signer", || self.validate_signer(&signer).map_err(Into::into))
+                .inspect_err(rejected("signer"))?;
+
+            // An out-of-turn seal is valid; it only scores lower in fork choice. The
+            // turn is only worked out when it is logged.
+            if tracing::enabled!(target: "poa::consensus", tracing::Level::DEBUG) {
+                let expected = self.chain_spec.with_effective_signers(|signers| {
+                    (!signers.is_empty()).then(|| Self::expected_signer(header.number, signers))
+                });
+                if let Some(expected) = expected {
+                    debug!(
+                        target: "poa::consensus",
+                        %signer,
+                        %expected,
+                        in_turn = signer == expected,
+                        "Sealer turn"
+                    );
+                }
+            }
+
+            debug!(target: "poa::consensus", check = "vote", "Validating header");
+            audit
+                .check("vote", || {
+                    let epoch = self.chain_spec.epoch();
+                    Self::validate_vote(header.header(), epoch).map_err(Into::into)
+                })
+                .inspect_err(rejected("vote"))?;
+
+            debug!(target: "poa::consensus", check = "epoch_extra_data", "Validating header");
+            audit
+                .check("epoch_extra_data", || {
+                    Self::validate_epoch_extra_data(header.header(), &self.chain_spec)
+                })
+                .inspect_err(rejected("epoch_extra_data"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Body of
+    /// [`validate_header_against_parent`](HeaderValidator::validate_header_against_parent),
+    /// checks run through `audit`.
+    fn check_header_against_parent(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+        audit: &mut AuditRecorder,
+    ) -> Result<(), ConsensusError> {
+        let h = header.header();
+        let p = parent.header();
+
+        // Validate block number and parent hash linkage
+        debug!(target: "poa::consensus", check = "parent_hash", "Validating against parent");
+        audit
+            .check("parent_hash", || Self::validate_parent_hash(h, parent))
+            .inspect_err(rejected("parent_hash"))?;
+
+        // Validate timestamp (must be after parent + minimum period)
+        debug!(target: "poa::consensus", check = "timestamp", "Validating against parent");
+        let min_timestamp = p.timestamp() + self.chain_spec.block_period();
+        audit
+            .check("timestamp", || {
+                if h.timestamp() < min_timestamp {
+                    return Err(cold_timestamp_too_early(h.timestamp(), p.timestamp()).into());
+                }
+                Ok(())
+            })
+            .inspect_err(rejected("timestamp"))?;
+
+        // Validate gas limit bounds and changes (EIP-1559 compatible)
+        debug!(target: "poa::consensus", check = "gas_target", "Validating against parent");
+        audit
+            .check("gas_target", || self.validate_gas_limit_change(h, p))
+            .inspect_err(rejected("gas_target"))?;
+
+        // Validate the base fee follows the parent's gas usage against the gas target
+        debug!(target: "poa::consensus", check = "base_fee", "Validating against parent");
+        audit
+            .check("base_fee", || self.validate_base_fee(h, p))
+            .inspect_err(rejected("base_fee"))?;
+
+        // Blob gas follows the chain's blob parameters (a configured blob fee market
+        // or the fork defaults) from Cancun on.
+        debug!(target: "poa::consensus", check = "excess_blob_gas", "Validating against parent");
+        let chain_spec = self.chain_spec.as_ref();
+        audit
+            .check("excess_blob_gas", || {
+                Self::validate_blob_gas_fields(chain_spec, h, p)
+            })
+            .inspect_err(rejected("excess_blob_gas"))?;
+        Ok(())
+    }
+}
+
+// Use concrete Header type instead of generic H so we can access extra_data
+// for POA signature verification. This is safe because PoaNode always uses EthPrimitives
+// which has Header = alloy_consensus::Header.
+impl HeaderValidator<Header> for PoaConsensus {
+    #[instrument(
+        target = "poa::consensus",
+        skip_all,
+        fields(block_number = header.number, block_hash = ?header.hash())
+    )]
+    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
+        self.check_header(header, &mut AuditRecorder::disabled())
+    }
+
+    #[instrument(
+        target = "poa::consensus",
+        skip_all,
+        fields(block_number = header.number, block_hash = ?header.hash())
+    )]
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+    ) -> Result<(), ConsensusError> {
+        self.check_header_against_parent(header, parent, &mut AuditRecorder::disabled())
+    }
+}
+
+impl<B: Block<Header = Header>> Consensus<B> for PoaConsensus
+where
+    PoaConsensus: HeaderValidator<B::Header>,
+{
+    fn validate_body_against_header(
+        &self,
+        _body: &B::Body,
+        header: &SealedHeader<B::Header>,
+    ) -> Result<(), ConsensusError> {
+        let h = header.header();
+        // Validate that gas used doesn't exceed gas limit
+        if h.gas_used() > h.gas_limit() {
+            return Err(ConsensusError::HeaderGasUsedExceedsGasLimit {
+                gas_used: h.gas_used(),
+                gas_limit: h.gas_limit(),
+            });
+        }
+        Ok(())
+    }
+
+    #[instrument(
+        target = "poa::consensus",
+        skip_all,
+        fields(block_number = block.header().number(), block_hash = ?block.hash())
+    )]
+    fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), ConsensusError> {
+        // Validate extra_data has minimum length for POA (vanity + seal)
+        if !self.dev_mode {
+            debug!(target: "poa::consensus", check = "extra_data", "Validating block");
+            let extra_data = block.header().extra_data();
+            let min_length = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH;
+            if extra_data.len() < min_length {
+                let err: ConsensusError = PoaConsensusError::ExtraDataTooShort {
+                    expected: min_length,
+                    got: extra_data.len(),
+                }
+                .into();
+                rejected("extra_data")(&err);
+                return Err(err);
+            }
+        }
+        debug!(target: "poa::consensus", check = "ommers_hash", "Validating block");
+        Self::validate_ommers_hash(block.header()).inspect_err(rejected("ommers_hash"))?;
+
+        debug!(target: "poa::consensus", check = "transactions_root", "Validating block");
+        Self::validate_transactions_root(block.header(), block.body().transactions())
+            .inspect_err(rejected("transactions_root"))?;
+
+        if self.chain_spec.eip1559_disabled() {
+            debug!(target: "poa::consensus", check = "legacy_fees", "Validating block");
+            Self::validate_legacy_fee_transactions(block.body().transactions())
+                .inspect_err(rejected("legacy_fees"))?;
+        }
+        debug!(target: "poa::consensus", check = "transaction_types", "Validating block");
+        let allowed = self.allowed_tx_types_at(block.header().number(), block.header().timestamp());
+        Self::validate_transaction_types(block.body().transactions(), allowed)
+            .inspect_err(rejected("transaction_types"))?;
+        if let Some(max) = self.chain_spec.max_block_size_bytes() {
+            debug!(target: "poa::consensus", check = "block_size", "Validating block");
+            Self::validate_block_size(block.body().transactions(), max)
+                .inspect_err(rejected("block_size"))?;
+        }
+        let header = block.header();
+        let withdrawals = block.body().withdrawals().map_or(&[][..], |w| w.as_slice());
+        let pays_reward = withdrawals
+            .iter()
+            .any(|w| w.validator_index == REWARD_VALIDATOR_INDEX);
+        if !self.dev_mode && (pays_reward || self.chain_spec.uncle_reward().is_some()) {
+            debug!(target: "poa::consensus", check = "signer_reward", "Validating block");
+            self.recover_sealed_signer(block.sealed_header())
+                .map_err(ConsensusError::from)
+                .and_then(|signer| {
+                    let in_turn = self.chain_spec.expected_signer(header.number) == Some(signer);
+                    Self::validate_signer_reward(
+                        header.number,
+                        withdrawals,
+                        self.chain_spec.uncle_reward(),
+                        signer,
+                        in_turn,
+                    )
+                })
+                .inspect_err(rejected("signer_reward"))?;
+        }
+        if let (Some(required), Some(stakes), false) = (
+            self.chain_spec.min_signer_stake(),
+            &self.stakes,
+            self.dev_mode,
+        ) {
+            debug!(target: "poa::consensus", check = "signer_stake", "Validating block");
+            let parent_hash = header.parent_hash();
+            self.recover_sealed_signer(block.sealed_header())
+                .map_err(ConsensusError::from)
+                .and_then(|signer| {
+                    let stake = stakes.stake_at(parent_hash, signer).ok_or_else(|| {
+                        ConsensusError::from(PoaConsensusError::ParentStateUnavailable {
+                            parent_hash,
+                        })
+                    })?;
+                    Self::validate_signer_stake(signer, stake, required)
+                })
+                .inspect_err(rejected("signer_stake"))?;
+        }
+        if let (Some(remaining), Some(gas_limits)) = (
+            self.chain_spec.gas_limit_ramp_remaining(header.number()),
+            &self.gas_limits,
+        ) {
+            debug!(target: "poa::consensus", check = "gas_limit_ramp", "Validating block");
+            let parent_hash = header.parent_hash();
+            gas_limits
+                .ramp_gas_limits(parent_hash)
+                .ok_or_else(|| {
+                    ConsensusError::from(PoaConsensusError::ParentStateUnavailable { parent_hash })
+                })
+                .and_then(|(parent_gas_limit, target)| {
+                    Self::validate_gas_limit_ramp(
+                        header.gas_limit(),
+                        parent_gas_limit,
+                        target,
+                        remaining,
+                    )
+                })
+                .inspect_err(rejected("gas_limit_ramp"))?;
+        }
+        if let Some(signer_sets) = &self.signer_sets {
+            if header.number() > 0 && self.is_epoch_block(header.number()) {
+                debug!(target: "poa::consensus", check = "epoch_signers", "Validating block");
+                let parent_hash = header.parent_hash();
+                signer_sets
+                    .registry_signers(parent_hash)
+                    .ok_or_else(|| {
+                        ConsensusError::from(PoaConsensusError::ParentStateUnavailable {
+                            parent_hash,
+                        })
+                    })
+                    .and_then(|registry| {
+                        let expected = epoch_signers(&self.chain_spec, registry);
+                        Self::validate_epoch_signers(header.extra_data(), &expected)
+                    })
+                    .inspect_err(rejected("epoch_signers"))?;
+            }
+        }
+        // Gas check is already done in validate_body_against_header
+        Ok(())
+    }
+}
+
+impl<N: NodePrimitives> FullConsensus<N> for PoaConsensus
+where
+    PoaConsensus: Consensus<N::Block>,
+{
+    #[instrument(
+        target = "poa::consensus",
+        skip_all,
+        fields(block_number = block.header().number(), block_hash = ?block.hash())
+    )]
+    fn validate_block_post_execution(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        receipt_root_bloom: Option<ReceiptRootBloom>,
+    ) -> Result<(), ConsensusError> {
+        let h = block.header();
+
+        if let Some(acl) = self.chain_spec.acl_at(h.number()) {
+            debug!(
+                target: "poa::consensus",
+                check = "sender_access",
+                "Validating execution result"
+            );
+            Self::validate_sender_access(block.senders(), acl)
+                .inspect_err(rejected("sender_access"))?;
+        }
+
+        // Validate gas used matches what's in the header
+        debug!(target: "poa::consensus", check = "gas_used", "Validating execution result");
+        let header_gas_used = h.gas_used();
+        if result.gas_used != header_gas_used {
+            let err = ConsensusError::BlockGasUsed {
+                gas: GotExpected {
+                    got: result.gas_used,
+                    expected: header_gas_used,
+                },
+                gas_spent_by_tx: vec![],
+            };
+            rejected("gas_used")(&err);
+            return Err(err);
+        }
+
+        // Validate receipt root and logs bloom if pre-computed values are provided
+        if let Some((receipt_root, logs_bloom)) = receipt_root_bloom {
+            debug!(target: "poa::consensus", check = "receipts", "Validating execution result");
+            let header_receipt_root = h.receipts_root();
+            if header_receipt_root != receipt_root {
+                let err = ConsensusError::BodyReceiptRootDiff(
+                    GotExpected {
+                        got: receipt_root,
+                        expected: header_receipt_root,
+                    }
+                    .into(),
+                );
+                rejected("receipts_root")(&err);
+                return Err(err);
+            }
+
+            let header_logs_bloom = h.logs_bloom();
+            if header_logs_bloom != logs_bloom {
+                let err = ConsensusError::BodyBloomLogDiff(
+                    GotExpected {
+                        got: logs_bloom,
+                        expected: header_logs_bloom,
+                    }
+                    .into(),
+                );
+                rejected("logs_bloom")(&err);
+                return Err(err);
+            }
+        }
+
+        // Execution collects the EIP-6110 deposit requests of the chain spec's deposit
+        // contract; from Prague the header must commit to exactly those.
+        if let Some(header_requests_hash) = h.requests_hash() {
+            debug!(
+                target: "poa::consensus",
+                check = "requests_hash",
+                "Validating execution result"
+            );
+            let requests_hash = result.requests.requests_hash();
+            if requests_hash != header_requests_hash {
+                let err = ConsensusError::BodyRequestsHashDiff(
+                    GotExpected {
+                        got: requests_hash,
+                        expected: header_requests_hash,
+                    }
+                    .into(),
+                );
+                rejected("requests_hash")(&err);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::BlobFeeParams;
+    use crate::signer::{dev, BlockSealer, SignerError, SignerManager};
+    use reth_chainspec::{Chain, ChainSpec, ChainSpecBuilder};
+    use reth_tracing::tracing_subscriber;
+    use std::sync::Mutex;
+
+    /// Install a global `fmt` subscriber so instrumented validation runs with spans
+    /// enabled (output is captured by the test harness).
+    fn init_tracing() {
+        let _ = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_test_writer()
+            .try_init();
+    }
+
+    fn dev_consensus() -> PoaConsensus {
+        init_tracing();
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        PoaConsensus::new_dev(chain)
+    }
+
+    fn production_consensus() -> PoaConsensus {
+        init_tracing();
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        PoaConsensus::new(chain)
+    }
+
+    /// In-memory writer for asserting on formatted trace output.
+    #[derive(Clone, Default)]
+    struct TraceBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for TraceBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Run `f` under a local `fmt` subscriber and return everything it logged.
+    fn traced(f: impl FnOnce()) -> String {
+        let buffer = TraceBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, f);
+        let output = buffer.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Default header carrying every fork-gated field the dev chain (Prague at
+    /// genesis) requires, so tests only spell out the fields they exercise.
+    fn fork_header() -> Header {
+        Header {
+            base_fee_per_gas: Some(0),
+            withdrawals_root: Some(alloy_consensus::constants::EMPTY_WITHDRAWALS),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            requests_hash: Some(alloy_eips::eip7685::EMPTY_REQUESTS_HASH),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consensus_creation() {
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        let consensus = PoaConsensus::new(chain);
+        assert!(!consensus.chain_spec.signers().is_empty());
+        assert!(!consensus.is_dev_mode());
+    }
+
+    #[test]
+    fn test_consensus_dev_mode() {
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        let consensus = PoaConsensus::new_dev(chain);
+        assert!(consensus.is_dev_mode());
+    }
+
+    #[test]
+    fn test_consensus_with_dev_mode() {
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        let consensus = PoaConsensus::new(chain).with_dev_mode(true);
+        assert!(consensus.is_dev_mode());
+    }
+
+    #[test]
+    fn test_epoch_block_detection() {
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        let consensus = PoaConsensus::new(chain.clone());
+
+        let epoch = chain.epoch();
+        assert!(consensus.is_epoch_block(0));
+        assert!(consensus.is_epoch_block(epoch));
+        assert!(consensus.is_epoch_block(epoch * 2));
+        assert!(!consensus.is_epoch_block(1));
+        assert!(!consensus.is_epoch_block(epoch + 1));
+    }
+
+    #[test]
+    fn test_validate_signer_authorized() {
+        let consensus = production_consensus();
+        let signers = consensus.chain_spec.signers().to_vec();
+        assert!(!signers.is_empty());
+        // First signer should be authorized
+        assert!(consensus.validate_signer(&signers[0]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_signer_unauthorized() {
+        let consensus = production_consensus();
+        let fake_signer: Address = "0x0000000000000000000000000000000000000099"
+            .parse()
+            .unwrap();
+        let result = consensus.validate_signer(&fake_signer);
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            PoaConsensusError::UnauthorizedSigner { signer } => {
+                assert_eq!(signer, fake_signer);
+            }
+            other => panic!("Expected UnauthorizedSigner, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recover_signer_short_extra_data() {
+        let consensus = production_consensus();
+        let header = Header {
+            extra_data: vec![0u8; 10].into(), // Too short
+            ..Default::default()
+        };
+        let result = consensus.recover_signer(&header);
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            PoaConsensusError::ExtraDataTooShort { expected, got } => {
+                assert_eq!(expected, EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH);
+                assert_eq!(got, 10);
+            }
+            other => panic!("Expected ExtraDataTooShort, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recover_signer_malformed_seal() {
+        let consensus = production_consensus();
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH];
+        *extra_data.last_mut().unwrap() = 30;
+        let header = Header {
+            extra_data: extra_data.into(),
+            ..fork_header()
+        };
+        match consensus.recover_signer(&header) {
+            Err(PoaConsensusError::InvalidSignature(SealError::MalformedSignature(
+                SignerError::InvalidRecoveryId(30),
+            ))) => {}
+            other => panic!("Expected malformed seal, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_signer_valid_signature() {
+        let consensus = production_consensus();
+        let manager = Arc::new(SignerManager::new());
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+
+        let sealer = BlockSealer::new(manager);
+
+        // Create a header with space for vanity + seal
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+            ..Default::default()
+        };
+
+        // Sign the header
+        let sealed_header = sealer.seal_header(header, &address).await.unwrap();
+
+        // Recover the signer
+        let recovered = consensus.recover_signer(&sealed_header).unwrap();
+        assert_eq!(recovered, address);
+    }
+
+    #[tokio::test]
+    async fn test_recover_signer_rejects_high_s_from_activation() {
+        use alloy_primitives::Signature;
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_low_s_seal_block(2);
+        let consensus = PoaConsensus::new(Arc::new(chain));
+        let manager = Arc::new(SignerManager::new());
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0])
+            .unwrap();
+        let high_s_seal = |number| {
+            let manager = manager.clone();
+            async move {
+                let header = Header {
+                    number,
+                    extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+                    ..fork_header()
+                };
+                let sealed = BlockSealer::new(manager)
+                    .seal_header(header, &address)
+                    .await
+                    .unwrap();
+                let seal = bytes_to_signature(&sealed.extra_data[EXTRA_VANITY_LENGTH..]).unwrap();
+                let order = crate::signer::signature::SECP256K1_ORDER;
+                let high = Signature::new(seal.r(), order - seal.s(), !seal.v());
+                let mut extra_data = sealed.extra_data.to_vec();
+                extra_data[EXTRA_VANITY_LENGTH..].copy_from_slice(&signature_to_bytes(&high));
+                Header {
+                    extra_data: extra_data.into(),
+                    ..sealed
+                }
+            }
+        };
+
+        // Below the activation block the high-s twin still recovers the sealer.
+        let before = high_s_seal(1).await;
+        assert_eq!(consensus.recover_signer(&before).unwrap(), address);
+        match consensus.recover_signer(&high_s_seal(2).await) {
+            Err(PoaConsensusError::InvalidSignature(SealError::MalformedSignature(
+                SignerError::MalleableSignature,
+            ))) => {}
+            other => panic!("Expected malleable seal, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_signer_memoized_by_header_hash() {
+        let cache = Arc::new(SignatureCache::new(16));
+        let consensus = production_consensus().with_signature_cache(cache.clone());
+        let manager = Arc::new(SignerManager::new());
+        let address = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+        let header = Header {
+            number: 1,
+            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+            ..fork_header()
+        };
+        let sealed = BlockSealer::new(manager).seal_header(header, &address).await.unwrap();
+
+        let sealed = SealedHeader::seal_slow(sealed);
+        for _ in 0..1000 {
+            assert_eq!(consensus.recover_sealed_signer(&sealed).unwrap(), address);
+        }
+        assert_eq!((cache.stats().misses, cache.stats().hits), (1, 999));
+
+        // A different seal is a different header: recovered, not served from the cache.
+        let mut tampered = sealed.header().clone();
+        let mut extra_data = tampered.extra_data.to_vec();
+        extra_data[EXTRA_VANITY_LENGTH] ^= 1;
+        tampered.extra_data = extra_data.into();
+        let tampered = SealedHeader::seal_slow(tampered);
+        assert_ne!(
+            consensus.recover_sealed_signer(&tampered).ok(),
+            Some(address)
+        );
+        assert_eq!(cache.stats().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_validate_header_with_valid_signature() {
+        let consensus = production_consensus();
+        let manager = Arc::new(SignerManager::new());
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+
+        // Verify the signer address is authorized
+        assert!(consensus.chain_spec.is_authorized_signer(&address));
+
+        let sealer = BlockSealer::new(manager);
+
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+            ..fork_header()
+        };
+
+        let signed_header = sealer.seal_header(header, &address).await.unwrap();
+        let sealed = SealedHeader::seal_slow(signed_header);
+
+        // Should pass validation in production mode
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_header_short_extra_data_production() {
+        let consensus = production_consensus();
+
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; 10].into(), // Too short for POA
+            ..fork_header()
+        };
+        let sealed = SealedHeader::seal_slow(header);
+
+        // Production mode should reject
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_header_short_extra_data_dev_mode() {
+        let consensus = dev_consensus();
+
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; 10].into(), // Too short for POA
+            ..fork_header()
+        };
+        let sealed = SealedHeader::seal_slow(header);
+
+        // Dev mode should pass (no signature checks)
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_header_trusted_checkpoint() {
+        // Unsigned headers: only the checkpoint shortcut can let them through.
+        let unsigned = |number| {
+            SealedHeader::seal_slow(Header {
+                number,
+                gas_limit: 30_000_000,
+                timestamp: 12345,
+                extra_data: vec![0u8; 10].into(),
+                ..fork_header()
+            })
+        };
+        let at = unsigned(10);
+        let consensus =
+            production_consensus().with_checkpoint(Checkpoint::new(10, at.hash()));
+
+        // Below: accepted without validation.
+        assert!(HeaderValidator::validate_header(&consensus, &unsigned(9)).is_ok());
+
+        // At, hash matches: the hash check passes and full validation still runs.
+        let err = HeaderValidator::validate_header(&consensus, &at).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::ExtraDataTooShort { .. }
+        ));
+
+        // At, different hash: rejected as a checkpoint mismatch.
+        let forked = SealedHeader::seal_slow(Header {
+            timestamp: 12346,
+            ..at.header().clone()
+        });
+        let err = HeaderValidator::validate_header(&consensus, &forked).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::CheckpointMismatch { number: 10, expected, got }
+                if *expected == at.hash() && *got == forked.hash()
+        ));
+
+        // Above: fully validated.
+        assert!(HeaderValidator::validate_header(&consensus, &unsigned(11)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_header_unauthorized_signer() {
+        // Use a key that produces an address NOT in the authorized signers list
+        let consensus = production_consensus();
+        let manager = Arc::new(SignerManager::new());
+        // DEV_PRIVATE_KEYS[5] corresponds to account index 5 which is NOT in dev_signers() (only first 3)
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[5]).unwrap();
+
+        // Verify the address is NOT authorized
+        assert!(!consensus.chain_spec.is_authorized_signer(&address));
+
+        let sealer = BlockSealer::new(manager);
+
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+            ..fork_header()
+        };
+
+        let signed_header = sealer.seal_header(header, &address).await.unwrap();
+        let sealed = SealedHeader::seal_slow(signed_header);
+
+        // Should fail - signer not authorized
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_header_against_parent_valid() {
+        let consensus = dev_consensus();
+
+        let parent = Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 0,
+            ..Default::default()
+        };
+        let sealed_parent = SealedHeader::seal_slow(parent);
+
+        let child = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 2, // At least block_period (2s) after parent
+            parent_hash: sealed_parent.hash(),
+            ..Default::default()
+        };
+        let sealed_child = SealedHeader::seal_slow(child);
+
+        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_default_chain_checks_excess_blob_gas_against_parent() {
+        // No fee market configured: the fork's default blob parameters apply.
+        let consensus = dev_consensus();
+        assert!(consensus.chain_spec.fee_market().is_none());
+
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            ..fork_header()
+        });
+        let child = |excess_blob_gas| {
+            SealedHeader::seal_slow(Header {
+                number: 1,
+                gas_limit: 30_000_000,
+                timestamp: 2,
+                parent_hash: parent.hash(),
+                excess_blob_gas: Some(excess_blob_gas),
+                ..fork_header()
+            })
+        };
+        let result = consensus.validate_header_against_parent(&child(0), &parent);
+        assert!(result.is_ok());
+        assert!(matches!(
+            consensus.validate_header_against_parent(&child(DATA_GAS_PER_BLOB), &parent),
+            Err(ConsensusError::ExcessBlobGasDiff { .. })
+        ));
+    }
+
+    /// Block 1 on top of an unsigned parent, sealed by dev key 0 at `timestamp`.
+    async fn signed_child(parent: &SealedHeader<Header>, timestamp: u64) -> SealedHeader<Header> {
+        let manager = Arc::new(SignerManager::new());
+        let address = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp,
+            parent_hash: parent.hash(),
+            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+            ..fork_header()
+        };
+        let signed = BlockSealer::new(manager)
+            .seal_header(header, &address)
+            .await
+            .unwrap();
+        SealedHeader::seal_slow(signed)
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail_of_valid_header() {
+        let consensus = production_consensus();
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            timestamp: 1_000,
+            ..fork_header()
+        });
+        let child = signed_child(&parent, 1_002).await;
+
+        let (result, trail) = consensus.validate_header_with_audit(&child, &parent);
+        assert!(result.is_ok());
+        assert!(trail.len() >= 8, "{trail:?}");
+        assert!(trail.iter().all(|entry| entry.result.is_ok()));
+        let checks: Vec<_> = trail.iter().map(|entry| entry.check).collect();
+        assert!(checks.contains(&"signer"));
+        assert!(checks.contains(&"timestamp"));
+        assert!(serde_json::to_string(&trail).unwrap().contains("\"durationNs\""));
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail_of_invalid_header_ends_at_failure() {
+        let consensus = production_consensus();
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            timestamp: 1_000,
+            ..fork_header()
+        });
+        // Same timestamp as the parent: earlier than the block period allows.
+        let child = signed_child(&parent, 1_000).await;
+
+        let (result, trail) = consensus.validate_header_with_audit(&child, &parent);
+        assert!(matches!(
+            poa_error(&result.unwrap_err()),
+            PoaConsensusError::TimestampTooEarly { .. }
+        ));
+        let failed: Vec<_> = trail.iter().filter(|entry| entry.result.is_err()).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].check, "timestamp");
+        assert_eq!(trail.last(), Some(failed[0]));
+    }
+
+    #[test]
+    fn test_validate_header_against_parent_wrong_number() {
+        let consensus = dev_consensus();
+
+        let parent = Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            ..Default::default()
+        };
+        let sealed_parent = SealedHeader::seal_slow(parent);
+
+        let child = Header {
+            number: 5, // Wrong - should be 1
+            gas_limit: 30_000_000,
+            timestamp: 2,
+            parent_hash: sealed_parent.hash(),
+            ..Default::default()
+        };
+        let sealed_child = SealedHeader::seal_slow(child);
+
+        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rejection_logged_inside_validation_span() {
+        let consensus = dev_consensus();
+        let parent = SealedHeader::seal_slow(Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 100,
+            ..fork_header()
+        });
+        let child = SealedHeader::seal_slow(Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 100,
+            parent_hash: parent.hash(),
+            ..fork_header()
+        });
+
+        let output = traced(|| {
+            assert!(consensus.validate_header_against_parent(&child, &parent).is_err());
+        });
+        assert!(output.contains("validate_header_against_parent{block_number=1"));
+        assert!(output.contains(&format!("block_hash={:?}", child.hash())));
+        assert!(output.contains("DEBUG"));
+        let warning = output
+            .lines()
+            .find(|line| line.contains("WARN"))
+            .expect("rejection warning");
+        assert!(warning.contains("Consensus check failed"));
+        assert!(warning.contains("timestamp"));
+    }
+
+    #[test]
+    fn test_accepted_header_logs_no_warning() {
+        let consensus = dev_consensus();
+        let header = SealedHeader::seal_slow(Header {
+            gas_limit: 30_000_000,
+            ..fork_header()
+        });
+        let output = traced(|| assert!(consensus.validate_header(&header).is_ok()));
+        assert!(output.contains("validate_header{block_number=0"));
+        assert!(!output.contains("WARN"));
+    }
+
+    #[test]
+    fn test_validate_header_against_parent_wrong_hash() {
+        let consensus = dev_consensus();
+
+        let parent = Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            ..Default::default()
+        };
+        let sealed_parent = SealedHeader::seal_slow(parent);
+
+        let child = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 2,
+            parent_hash: B256::ZERO, // Wrong parent hash
+            ..Default::default()
+        };
+        let sealed_child = SealedHeader::seal_slow(child);
+
+        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_header_against_parent_timestamp_too_early() {
+        let consensus = dev_consensus();
+
+        let parent = Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 100,
+            ..Default::default()
+        };
+        let sealed_parent = SealedHeader::seal_slow(parent);
+
+        let child = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 100, // Same as parent — too early (block_period is 1s, need > parent)
+            parent_hash: sealed_parent.hash(),
+            ..Default::default()
+        };
+        let sealed_child = SealedHeader::seal_slow(child);
+
+        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_header_against_parent_gas_limit_increase_too_large() {
+        let consensus = dev_consensus();
+
+        let parent = Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 0,
+            ..Default::default()
+        };
+        let sealed_parent = SealedHeader::seal_slow(parent);
+
+        // Max increase is parent_gas_limit / 1024 = ~29,296
+        let child = Header {
+            number: 1,
+            gas_limit: 31_000_000, // 1M increase, way over limit
+            timestamp: 2,
+            parent_hash: sealed_parent.hash(),
+            ..Default::default()
+        };
+        let sealed_child = SealedHeader::seal_slow(child);
+
+        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_base_fee_override_pinned_across_blocks() {
+        init_tracing();
+        const FEE: u64 = 7_000_000_000;
+        let chain = crate::chainspec::PoaChainSpec::dev_chain()
+            .with_gas_target(50_000_000)
+            .with_base_fee_override(FEE, 1);
+        let consensus = PoaConsensus::new_dev(Arc::new(chain));
+
+        let mut parent = SealedHeader::seal_slow(Header {
+            gas_limit: 1_000_000_000,
+            base_fee_per_gas: Some(FEE),
+            ..fork_header()
+        });
+        for gas_used in [0, 10_000_000, 50_000_000, 400_000_000, 1_000_000_000] {
+            let child = |base_fee| {
+                SealedHeader::seal_slow(Header {
+                    number: parent.number + 1,
+                    timestamp: parent.timestamp + 2,
+                    parent_hash: parent.hash(),
+                    gas_used,
+                    base_fee_per_gas: Some(base_fee),
+                    ..parent.header().clone()
+                })
+            };
+            // The EIP-1559 adjustment is not applied: only the pinned fee is valid.
+            let adjusted = consensus.validate_header_against_parent(&child(FEE + 1), &parent);
+            assert!(matches!(adjusted, Err(ConsensusError::BaseFeeDiff(_))), "{adjusted:?}");
+            let next = child(FEE);
+            consensus.validate_header_against_parent(&next, &parent).unwrap();
+            parent = next;
+        }
     }
 
-    fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), ConsensusError> {
-        // Validate extra_data has minimum length for POA (vanity + seal)
-        if !self.dev_mode {
-            let extra_data = block.header().extra_data();
-            let min_length = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH;
-            if extra_data.len() < min_length {
-                return Err(PoaConsensusError::ExtraDataTooShort {
-                    expected: min_length,
-                    got: extra_data.len(),
-                }
-                .into());
-            }
+    #[test]
+    fn test_base_fee_follows_gas_target_under_load() {
+        init_tracing();
+        const TARGET: u64 = 50_000_000;
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_gas_target(TARGET);
+        let denominator = chain.base_fee_params_at_timestamp(0).max_change_denominator as u64;
+        let consensus = PoaConsensus::new_dev(Arc::new(chain));
+
+        let mut parent = SealedHeader::seal_slow(Header {
+            gas_limit: 1_000_000_000,
+            gas_used: 2 * TARGET,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..fork_header()
+        });
+        let mut fees = vec![1_000_000_000];
+        // Each child's base fee is set by its parent's usage: five blocks at 2x the
+        // target (10% of the limit), then the load stops.
+        for gas_used in [2 * TARGET; 5].into_iter().chain([0; 6]) {
+            let fee = parent.base_fee_per_gas.unwrap();
+            let expected = if parent.gas_used > TARGET {
+                fee + fee / denominator
+            } else {
+                fee - fee / denominator
+            };
+            let child = |base_fee| {
+                SealedHeader::seal_slow(Header {
+                    number: parent.number + 1,
+                    timestamp: parent.timestamp + 2,
+                    parent_hash: parent.hash(),
+                    gas_used,
+                    base_fee_per_gas: Some(base_fee),
+                    ..parent.header().clone()
+                })
+            };
+
+            let stale = consensus.validate_header_against_parent(&child(fee), &parent);
+            assert!(matches!(stale, Err(ConsensusError::BaseFeeDiff(_))), "{stale:?}");
+            let next = child(expected);
+            consensus.validate_header_against_parent(&next, &parent).unwrap();
+            parent = next;
+            fees.push(expected);
         }
-        // Gas check is already done in validate_body_against_header
-        Ok(())
+
+        assert!(fees[..7].windows(2).all(|w| w[1] > w[0]), "rises under load: {fees:?}");
+        assert!(fees[6..].windows(2).all(|w| w[1] < w[0]), "drops when idle: {fees:?}");
     }
-}
 
-impl<N: NodePrimitives> FullConsensus<N> for PoaConsensus
-where
-    PoaConsensus: Consensus<N::Block>,
-{
-    fn validate_block_post_execution(
-        &self,
-        block: &RecoveredBlock<N::Block>,
-        result: &BlockExecutionResult<N::Receipt>,
-        receipt_root_bloom: Option<ReceiptRootBloom>,
-    ) -> Result<(), ConsensusError> {
-        let h = block.header();
+    #[test]
+    fn test_validate_header_against_parent_gas_limit_decrease_too_large() {
+        let consensus = dev_consensus();
 
-        // Validate gas used matches what's in the header
-        let header_gas_used = h.gas_used();
-        if result.gas_used != header_gas_used {
-            return Err(ConsensusError::BlockGasUsed {
-                gas: GotExpected {
-                    got: result.gas_used,
-                    expected: header_gas_used,
-                },
-                gas_spent_by_tx: vec![],
-            });
+        let parent = Header {
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 0,
+            ..Default::default()
+        };
+        let sealed_parent = SealedHeader::seal_slow(parent);
+
+        let child = Header {
+            number: 1,
+            gas_limit: 29_000_000, // 1M decrease, way over limit
+            timestamp: 2,
+            parent_hash: sealed_parent.hash(),
+            ..Default::default()
+        };
+        let sealed_child = SealedHeader::seal_slow(child);
+
+        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_gas_limit_bounds_minimum() {
+        let below = Header {
+            gas_limit: MIN_GAS_LIMIT - 1,
+            ..Default::default()
+        };
+        match PoaConsensus::validate_gas_limit_bounds(&below).unwrap_err() {
+            ConsensusError::Custom(err) => match err.downcast_ref::<PoaConsensusError>() {
+                Some(PoaConsensusError::GasLimitTooLow { got, min }) => {
+                    assert_eq!(*got, 4999);
+                    assert_eq!(*min, 5000);
+                }
+                other => panic!("Expected GasLimitTooLow, got {:?}", other),
+            },
+            other => panic!("Expected Custom, got {:?}", other),
         }
 
-        // Validate receipt root and logs bloom if pre-computed values are provided
-        if let Some((receipt_root, logs_bloom)) = receipt_root_bloom {
-            let header_receipt_root = h.receipts_root();
-            if header_receipt_root != receipt_root {
-                return Err(ConsensusError::BodyReceiptRootDiff(
-                    GotExpected {
-                        got: receipt_root,
-                        expected: header_receipt_root,
-                    }
-                    .into(),
-                ));
-            }
+        let at_min = Header {
+            gas_limit: MIN_GAS_LIMIT,
+            ..Default::default()
+        };
+        assert!(PoaConsensus::validate_gas_limit_bounds(&at_min).is_ok());
+    }
 
-            let header_logs_bloom = h.logs_bloom();
-            if header_logs_bloom != logs_bloom {
-                return Err(ConsensusError::BodyBloomLogDiff(
-                    GotExpected {
-                        got: logs_bloom,
-                        expected: header_logs_bloom,
-                    }
-                    .into(),
-                ));
-            }
+    #[test]
+    fn test_validate_gas_limit_bounds_maximum() {
+        let at_max = Header {
+            gas_limit: MAX_GAS_LIMIT,
+            ..Default::default()
+        };
+        assert!(PoaConsensus::validate_gas_limit_bounds(&at_max).is_ok());
+
+        let above = Header {
+            gas_limit: MAX_GAS_LIMIT + 1,
+            ..Default::default()
+        };
+        match PoaConsensus::validate_gas_limit_bounds(&above).unwrap_err() {
+            ConsensusError::Custom(err) => match err.downcast_ref::<PoaConsensusError>() {
+                Some(PoaConsensusError::GasLimitTooHigh { got, max }) => {
+                    assert_eq!(*got, MAX_GAS_LIMIT + 1);
+                    assert_eq!(*max, (1u64 << 63) - 1);
+                }
+                other => panic!("Expected GasLimitTooHigh, got {:?}", other),
+            },
+            other => panic!("Expected Custom, got {:?}", other),
         }
+    }
 
-        Ok(())
+    fn spec(configure: impl FnOnce(ChainSpecBuilder) -> ChainSpecBuilder) -> ChainSpec {
+        configure(
+            ChainSpecBuilder::default()
+                .chain(Chain::from_id(9323310))
+                .genesis(alloy_genesis::Genesis::default()),
+        )
+        .build()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::signer::{dev, BlockSealer, SignerManager};
+    /// `fork_header()` with every fork-gated field stripped (pre-London shape).
+    fn frontier_header() -> Header {
+        Header {
+            base_fee_per_gas: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+            ..fork_header()
+        }
+    }
 
-    fn dev_consensus() -> PoaConsensus {
-        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
-        PoaConsensus::new_dev(chain)
+    #[test]
+    fn test_fork_fields_prague_header_accepted_on_dev_chain() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        assert!(PoaConsensus::validate_fork_fields(&chain, &fork_header()).is_ok());
     }
 
-    fn production_consensus() -> PoaConsensus {
-        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
-        PoaConsensus::new(chain)
+    #[test]
+    fn test_fork_fields_prague_requires_every_field() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let cases: [(Header, ConsensusError); 6] = [
+            (Header { base_fee_per_gas: None, ..fork_header() }, ConsensusError::BaseFeeMissing),
+            (
+                Header { withdrawals_root: None, ..fork_header() },
+                ConsensusError::WithdrawalsRootMissing,
+            ),
+            (Header { blob_gas_used: None, ..fork_header() }, ConsensusError::BlobGasUsedMissing),
+            (
+                Header { excess_blob_gas: None, ..fork_header() },
+                ConsensusError::ExcessBlobGasMissing,
+            ),
+            (
+                Header { parent_beacon_block_root: None, ..fork_header() },
+                ConsensusError::ParentBeaconBlockRootMissing,
+            ),
+            (Header { requests_hash: None, ..fork_header() }, ConsensusError::RequestsHashMissing),
+        ];
+        for (header, expected) in cases {
+            let err = PoaConsensus::validate_fork_fields(&chain, &header).unwrap_err();
+            assert_eq!(err.to_string(), expected.to_string());
+        }
     }
 
     #[test]
-    fn test_consensus_creation() {
-        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
-        let consensus = PoaConsensus::new(chain);
-        assert!(!consensus.chain_spec.signers().is_empty());
-        assert!(!consensus.is_dev_mode());
+    fn test_fork_fields_frontier_header_accepted_before_london() {
+        let chain = spec(|b| b.berlin_activated());
+        assert!(PoaConsensus::validate_fork_fields(&chain, &frontier_header()).is_ok());
     }
 
     #[test]
-    fn test_consensus_dev_mode() {
-        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
-        let consensus = PoaConsensus::new_dev(chain);
-        assert!(consensus.is_dev_mode());
+    fn test_fork_fields_base_fee_rejected_before_london() {
+        let chain = spec(|b| b.berlin_activated());
+        let header = Header { base_fee_per_gas: Some(7), ..frontier_header() };
+        match PoaConsensus::validate_fork_fields(&chain, &header).unwrap_err() {
+            ConsensusError::Custom(err) => assert!(matches!(
+                err.downcast_ref::<PoaConsensusError>(),
+                Some(PoaConsensusError::BaseFeeBeforeLondon)
+            )),
+            other => panic!("Expected BaseFeeBeforeLondon, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_consensus_with_dev_mode() {
-        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
-        let consensus = PoaConsensus::new(chain).with_dev_mode(true);
-        assert!(consensus.is_dev_mode());
+    fn test_fork_fields_withdrawals_root_gated_by_shanghai() {
+        let london = spec(|b| b.london_activated());
+        let header = Header { base_fee_per_gas: Some(7), ..frontier_header() };
+        assert!(PoaConsensus::validate_fork_fields(&london, &header).is_ok());
+
+        let early = Header { withdrawals_root: Some(B256::ZERO), ..header.clone() };
+        assert!(matches!(
+            PoaConsensus::validate_fork_fields(&london, &early),
+            Err(ConsensusError::WithdrawalsRootUnexpected)
+        ));
+
+        let shanghai = spec(|b| b.shanghai_activated());
+        assert!(matches!(
+            PoaConsensus::validate_fork_fields(&shanghai, &header),
+            Err(ConsensusError::WithdrawalsRootMissing)
+        ));
+        assert!(PoaConsensus::validate_fork_fields(&shanghai, &early).is_ok());
     }
 
     #[test]
-    fn test_epoch_block_detection() {
-        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
-        let consensus = PoaConsensus::new(chain.clone());
+    fn test_fork_fields_blob_fields_gated_by_cancun() {
+        let shanghai = spec(|b| b.shanghai_activated());
+        let pre_cancun = Header {
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+            ..fork_header()
+        };
+        assert!(PoaConsensus::validate_fork_fields(&shanghai, &pre_cancun).is_ok());
+
+        let cases: [(Header, ConsensusError); 3] = [
+            (
+                Header { blob_gas_used: Some(0), ..pre_cancun.clone() },
+                ConsensusError::BlobGasUsedUnexpected,
+            ),
+            (
+                Header { excess_blob_gas: Some(0), ..pre_cancun.clone() },
+                ConsensusError::ExcessBlobGasUnexpected,
+            ),
+            (
+                Header { parent_beacon_block_root: Some(B256::ZERO), ..pre_cancun.clone() },
+                ConsensusError::ParentBeaconBlockRootUnexpected,
+            ),
+        ];
+        for (header, expected) in cases {
+            let err = PoaConsensus::validate_fork_fields(&shanghai, &header).unwrap_err();
+            assert_eq!(err.to_string(), expected.to_string());
+        }
 
-        let epoch = chain.epoch();
-        assert!(consensus.is_epoch_block(0));
-        assert!(consensus.is_epoch_block(epoch));
-        assert!(consensus.is_epoch_block(epoch * 2));
-        assert!(!consensus.is_epoch_block(1));
-        assert!(!consensus.is_epoch_block(epoch + 1));
+        let cancun = spec(|b| b.cancun_activated());
+        let post_cancun = Header { requests_hash: None, ..fork_header() };
+        assert!(PoaConsensus::validate_fork_fields(&cancun, &post_cancun).is_ok());
+        assert!(matches!(
+            PoaConsensus::validate_fork_fields(&cancun, &pre_cancun),
+            Err(ConsensusError::BlobGasUsedMissing)
+        ));
     }
 
     #[test]
-    fn test_validate_signer_authorized() {
-        let consensus = production_consensus();
-        let signers = consensus.chain_spec.signers().to_vec();
-        assert!(!signers.is_empty());
-        // First signer should be authorized
-        assert!(consensus.validate_signer(&signers[0]).is_ok());
+    fn test_fork_fields_blob_gas_used_must_be_blob_multiple() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let two_blobs = Header { blob_gas_used: Some(2 * DATA_GAS_PER_BLOB), ..fork_header() };
+        assert!(PoaConsensus::validate_fork_fields(&chain, &two_blobs).is_ok());
+
+        let ragged = Header { blob_gas_used: Some(DATA_GAS_PER_BLOB + 1), ..fork_header() };
+        match PoaConsensus::validate_fork_fields(&chain, &ragged).unwrap_err() {
+            ConsensusError::BlobGasUsedNotMultipleOfBlobGasPerBlob {
+                blob_gas_used,
+                blob_gas_per_blob,
+            } => {
+                assert_eq!(blob_gas_used, DATA_GAS_PER_BLOB + 1);
+                assert_eq!(blob_gas_per_blob, DATA_GAS_PER_BLOB);
+            }
+            other => panic!("Expected BlobGasUsedNotMultipleOfBlobGasPerBlob, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_validate_signer_unauthorized() {
+    fn test_fork_fields_requests_hash_gated_by_prague() {
+        let cancun = spec(|b| b.cancun_activated());
+        let early = fork_header();
+        assert!(matches!(
+            PoaConsensus::validate_fork_fields(&cancun, &early),
+            Err(ConsensusError::RequestsHashUnexpected)
+        ));
+
+        let prague = spec(|b| b.prague_activated());
+        let missing = Header { requests_hash: None, ..fork_header() };
+        assert!(matches!(
+            PoaConsensus::validate_fork_fields(&prague, &missing),
+            Err(ConsensusError::RequestsHashMissing)
+        ));
+        assert!(PoaConsensus::validate_fork_fields(&prague, &early).is_ok());
+    }
+
+    #[test]
+    fn test_validate_header_rejects_missing_fork_fields() {
         let consensus = production_consensus();
-        let fake_signer: Address = "0x0000000000000000000000000000000000000099"
-            .parse()
-            .unwrap();
-        let result = consensus.validate_signer(&fake_signer);
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            PoaConsensusError::UnauthorizedSigner { signer } => {
-                assert_eq!(signer, fake_signer);
-            }
-            other => panic!("Expected UnauthorizedSigner, got {:?}", other),
+        let header = Header {
+            gas_limit: 30_000_000,
+            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+            ..frontier_header()
+        };
+        let sealed = SealedHeader::seal_slow(header);
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(matches!(result, Err(ConsensusError::BaseFeeMissing)));
+    }
+
+    /// A transfer signed by dev account `key`.
+    fn signed_transfer(key: usize) -> reth_ethereum::TransactionSigned {
+        use alloy_consensus::{SignableTransaction, TxLegacy};
+        use alloy_signer::SignerSync;
+        use alloy_signer_local::PrivateKeySigner;
+
+        let signer: PrivateKeySigner = dev::DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let tx = TxLegacy {
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: alloy_primitives::TxKind::Call(Address::with_last_byte(0xbe)),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        reth_ethereum::TransactionSigned::Legacy(tx.into_signed(signature))
+    }
+
+    fn acl_result(
+        acl: AccessControlList,
+        block: &SealedBlock<reth_ethereum::Block>,
+    ) -> Result<(), ConsensusError> {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_acl(acl, 0);
+        let consensus = PoaConsensus::new_dev(Arc::new(chain));
+        let block = block.clone().try_recover().unwrap();
+        let result = BlockExecutionResult {
+            receipts: vec![],
+            requests: Default::default(),
+            gas_used: block.header().gas_used,
+            blob_gas_used: 0,
+        };
+        FullConsensus::<reth_ethereum::EthPrimitives>::validate_block_post_execution(
+            &consensus, &block, &result, None,
+        )
+    }
+
+    #[test]
+    fn test_acl_blacklist_rejects_sender() {
+        let accounts = crate::genesis::dev_accounts();
+        let block = block_with(vec![signed_transfer(0), signed_transfer(1)]);
+
+        assert!(acl_result(AccessControlList::blacklist([accounts[2]]), &block).is_ok());
+        let err = acl_result(AccessControlList::blacklist([accounts[1]]), &block).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::BlacklistedSender(sender) if *sender == accounts[1]
+        ));
+    }
+
+    #[test]
+    fn test_acl_whitelist_rejects_unlisted_sender() {
+        let accounts = crate::genesis::dev_accounts();
+        let block = block_with(vec![signed_transfer(0), signed_transfer(1)]);
+
+        let both = AccessControlList::whitelist([accounts[0], accounts[1]]);
+        assert!(acl_result(both.clone(), &block).is_ok());
+        let err = acl_result(AccessControlList::whitelist([accounts[0]]), &block).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::SenderNotWhitelisted(sender) if *sender == accounts[1]
+        ));
+
+        // The blacklist applies to whitelisted senders too.
+        let mut blocked = both;
+        blocked.blacklisted.insert(accounts[0]);
+        let err = acl_result(blocked, &block).unwrap_err();
+        assert!(matches!(poa_error(&err), PoaConsensusError::BlacklistedSender(_)));
+    }
+
+    #[test]
+    fn test_acl_applies_from_activation_block() {
+        let accounts = crate::genesis::dev_accounts();
+        let header = Header {
+            number: 10,
+            ..fork_header()
+        };
+        let transactions = vec![signed_transfer(0)];
+        let block = SealedBlock::seal_slow(reth_ethereum::Block {
+            header: Header {
+                transactions_root: alloy_consensus::proofs::calculate_transaction_root(
+                    &transactions,
+                ),
+                ..header
+            },
+            body: alloy_consensus::BlockBody {
+                transactions,
+                ..Default::default()
+            },
+        })
+        .try_recover()
+        .unwrap();
+        let result = BlockExecutionResult {
+            receipts: vec![],
+            requests: Default::default(),
+            gas_used: 0,
+            blob_gas_used: 0,
+        };
+        let validate = |activation| {
+            let acl = AccessControlList::blacklist([accounts[0]]);
+            let chain = crate::chainspec::PoaChainSpec::dev_chain().with_acl(acl, activation);
+            let consensus = PoaConsensus::new_dev(Arc::new(chain));
+            FullConsensus::<reth_ethereum::EthPrimitives>::validate_block_post_execution(
+                &consensus, &block, &result, None,
+            )
+        };
+        assert!(validate(11).is_ok());
+        assert!(validate(10).is_err());
+    }
+
+    fn legacy_gas_consensus() -> PoaConsensus {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_eip1559_disabled();
+        PoaConsensus::new_dev(Arc::new(chain))
+    }
+
+    fn block_with(
+        transactions: Vec<reth_ethereum::TransactionSigned>,
+    ) -> SealedBlock<reth_ethereum::Block> {
+        SealedBlock::seal_slow(reth_ethereum::Block {
+            header: Header {
+                base_fee_per_gas: None,
+                transactions_root: alloy_consensus::proofs::calculate_transaction_root(
+                    &transactions,
+                ),
+                ..fork_header()
+            },
+            body: alloy_consensus::BlockBody {
+                transactions,
+                ..Default::default()
+            },
+        })
+    }
+
+    #[test]
+    fn test_eip1559_disabled_header_without_base_fee() {
+        let consensus = legacy_gas_consensus();
+        let header = Header {
+            base_fee_per_gas: None,
+            gas_limit: 30_000_000,
+            ..fork_header()
+        };
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &SealedHeader::seal_slow(header));
+        assert!(result.is_ok());
+
+        let header = Header {
+            base_fee_per_gas: Some(0),
+            gas_limit: 30_000_000,
+            ..fork_header()
+        };
+        match HeaderValidator::validate_header(&consensus, &SealedHeader::seal_slow(header)) {
+            Err(ConsensusError::Custom(err)) => assert!(matches!(
+                err.downcast_ref::<PoaConsensusError>(),
+                Some(PoaConsensusError::BaseFeeBeforeLondon)
+            )),
+            other => panic!("Expected BaseFeeBeforeLondon, got {:?}", other),
         }
     }
 
+    fn fee_market_consensus(market: FeeMarket) -> PoaConsensus {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_fee_market(market);
+        PoaConsensus::new_dev(Arc::new(chain))
+    }
+
     #[test]
-    fn test_recover_signer_short_extra_data() {
-        let consensus = production_consensus();
-        let header = Header {
-            extra_data: vec![0u8; 10].into(), // Too short
+    fn test_fee_market_legacy_rejects_max_fee_per_gas() {
+        use alloy_consensus::{Signed, TxEip1559};
+        use alloy_primitives::Signature;
+
+        let consensus = fee_market_consensus(FeeMarket::Legacy);
+        let dynamic = reth_ethereum::TransactionSigned::Eip1559(Signed::new_unhashed(
+            TxEip1559 {
+                max_fee_per_gas: 1_000_000_000,
+                gas_limit: 21_000,
+                ..Default::default()
+            },
+            Signature::test_signature(),
+        ));
+        let err = Consensus::<reth_ethereum::Block>::validate_block_pre_execution(
+            &consensus,
+            &block_with(vec![dynamic]),
+        )
+        .unwrap_err();
+        assert_eq!(poa_error(&err).kind(), "dynamicFeeTransaction");
+
+        // Legacy headers carry no base fee.
+        let header = SealedHeader::seal_slow(fork_header());
+        let err = HeaderValidator::validate_header(&consensus, &header).unwrap_err();
+        assert_eq!(poa_error(&err).kind(), "baseFeeBeforeLondon");
+    }
+
+    #[test]
+    fn test_fee_market_eip1559_allows_no_blob_gas() {
+        use crate::chainspec::BaseFeeConfig;
+
+        let consensus = fee_market_consensus(FeeMarket::Eip1559(BaseFeeConfig::default()));
+        let no_blobs = SealedHeader::seal_slow(fork_header());
+        assert!(HeaderValidator::validate_header(&consensus, &no_blobs).is_ok());
+
+        let one_blob = Header { blob_gas_used: Some(DATA_GAS_PER_BLOB), ..fork_header() };
+        assert!(matches!(
+            HeaderValidator::validate_header(&consensus, &SealedHeader::seal_slow(one_blob)),
+            Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                max_blob_gas_per_block: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_fee_market_eip4844_blob_limit_and_excess() {
+        use crate::chainspec::BaseFeeConfig;
+
+        let blobs = BlobFeeParams {
+            target_gas_per_block: DATA_GAS_PER_BLOB,
+            max_gas_per_block: 2 * DATA_GAS_PER_BLOB,
             ..Default::default()
         };
-        let result = consensus.recover_signer(&header);
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            PoaConsensusError::ExtraDataTooShort { expected, got } => {
-                assert_eq!(expected, EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH);
-                assert_eq!(got, 10);
-            }
-            other => panic!("Expected ExtraDataTooShort, got {:?}", other),
+        let consensus = fee_market_consensus(FeeMarket::Eip4844(BaseFeeConfig::default(), blobs));
+        let two_blobs = Header { blob_gas_used: Some(2 * DATA_GAS_PER_BLOB), ..fork_header() };
+        let header = SealedHeader::seal_slow(two_blobs.clone());
+        assert!(HeaderValidator::validate_header(&consensus, &header).is_ok());
+        let three_blobs = Header { blob_gas_used: Some(3 * DATA_GAS_PER_BLOB), ..fork_header() };
+        let header = SealedHeader::seal_slow(three_blobs);
+        assert!(HeaderValidator::validate_header(&consensus, &header).is_err());
+
+        // Two blobs over a one-blob target leave excess blob gas for the child.
+        let parent = two_blobs;
+        let blob_params = consensus.chain_spec.blob_params_at_timestamp(parent.timestamp);
+        let expected = parent.maybe_next_block_excess_blob_gas(blob_params).unwrap();
+        assert!(expected > 0);
+        let child = Header { excess_blob_gas: Some(expected), ..fork_header() };
+        let chain = consensus.chain_spec.as_ref();
+        assert!(PoaConsensus::validate_blob_gas_fields(chain, &child, &parent).is_ok());
+        let child = Header { excess_blob_gas: Some(0), ..fork_header() };
+        assert!(matches!(
+            PoaConsensus::validate_blob_gas_fields(chain, &child, &parent),
+            Err(ConsensusError::ExcessBlobGasDiff { .. })
+        ));
+    }
+
+    fn blob_header(blob_gas_used: Option<u64>, excess_blob_gas: Option<u64>) -> Header {
+        Header {
+            blob_gas_used,
+            excess_blob_gas,
+            ..fork_header()
         }
     }
 
-    #[tokio::test]
-    async fn test_recover_signer_valid_signature() {
-        let consensus = production_consensus();
-        let manager = Arc::new(SignerManager::new());
-        let address = manager
-            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+    /// The dev chain with blob fee `params` installed.
+    fn blob_chain(params: BlobFeeParams) -> crate::chainspec::PoaChainSpec {
+        crate::chainspec::PoaChainSpec::dev_chain().with_blob_fee_params(params)
+    }
 
-        let sealer = BlockSealer::new(manager);
+    #[test]
+    fn test_blob_gas_fields_absent_before_cancun() {
+        let shanghai = spec(|b| b.shanghai_activated());
+        let parent = blob_header(None, None);
+        let empty = blob_header(None, None);
+        assert!(PoaConsensus::validate_blob_gas_fields(&shanghai, &empty, &parent).is_ok());
+
+        let used = blob_header(Some(0), None);
+        assert!(matches!(
+            PoaConsensus::validate_blob_gas_fields(&shanghai, &used, &parent),
+            Err(ConsensusError::BlobGasUsedUnexpected)
+        ));
+        let excess = blob_header(None, Some(0));
+        assert!(matches!(
+            PoaConsensus::validate_blob_gas_fields(&shanghai, &excess, &parent),
+            Err(ConsensusError::ExcessBlobGasUnexpected)
+        ));
+    }
 
-        // Create a header with space for vanity + seal
-        let header = Header {
-            number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 12345,
-            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
+    #[test]
+    fn test_blob_gas_fields_first_cancun_block() {
+        // The first Cancun block follows a parent without blob fields.
+        let chain = blob_chain(BlobFeeParams::default());
+        let fork_parent = blob_header(None, None);
+        let first = blob_header(Some(DATA_GAS_PER_BLOB), Some(0));
+        assert!(PoaConsensus::validate_blob_gas_fields(&chain, &first, &fork_parent).is_ok());
+    }
+
+    #[test]
+    fn test_blob_gas_used_above_max() {
+        let chain = blob_chain(BlobFeeParams {
+            target_gas_per_block: DATA_GAS_PER_BLOB,
+            max_gas_per_block: 2 * DATA_GAS_PER_BLOB,
             ..Default::default()
-        };
+        });
+        let parent = blob_header(Some(0), Some(0));
+        let at_max = blob_header(Some(2 * DATA_GAS_PER_BLOB), Some(0));
+        assert!(PoaConsensus::validate_blob_gas_fields(&chain, &at_max, &parent).is_ok());
+        let over = blob_header(Some(3 * DATA_GAS_PER_BLOB), Some(0));
+        assert!(matches!(
+            PoaConsensus::validate_blob_gas_fields(&chain, &over, &parent),
+            Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                blob_gas_used,
+                max_blob_gas_per_block,
+            }) if blob_gas_used == 3 * DATA_GAS_PER_BLOB
+                && max_blob_gas_per_block == 2 * DATA_GAS_PER_BLOB
+        ));
+    }
 
-        // Sign the header
-        let sealed_header = sealer.seal_header(header, &address).await.unwrap();
+    #[test]
+    fn test_excess_blob_gas_follows_parent() {
+        let chain = blob_chain(BlobFeeParams {
+            target_gas_per_block: DATA_GAS_PER_BLOB,
+            max_gas_per_block: 4 * DATA_GAS_PER_BLOB,
+            ..Default::default()
+        });
+        // Three blobs over a one-blob target on top of one blob of excess.
+        let parent = blob_header(Some(3 * DATA_GAS_PER_BLOB), Some(DATA_GAS_PER_BLOB));
+        let expected = 3 * DATA_GAS_PER_BLOB;
+        let child = blob_header(Some(0), Some(expected));
+        assert!(PoaConsensus::validate_blob_gas_fields(&chain, &child, &parent).is_ok());
+
+        let wrong = blob_header(Some(0), Some(expected - DATA_GAS_PER_BLOB));
+        match PoaConsensus::validate_blob_gas_fields(&chain, &wrong, &parent) {
+            Err(ConsensusError::ExcessBlobGasDiff { diff, .. }) => {
+                assert_eq!(diff.expected, expected);
+                assert_eq!(diff.got, 2 * DATA_GAS_PER_BLOB);
+            }
+            other => panic!("Expected ExcessBlobGasDiff, got {other:?}"),
+        }
 
-        // Recover the signer
-        let recovered = consensus.recover_signer(&sealed_header).unwrap();
-        assert_eq!(recovered, address);
+        // Usage below the target drains the excess, never below zero.
+        let parent = blob_header(Some(0), Some(DATA_GAS_PER_BLOB / 2));
+        let child = blob_header(Some(0), Some(0));
+        assert!(PoaConsensus::validate_blob_gas_fields(&chain, &child, &parent).is_ok());
     }
 
-    #[tokio::test]
-    async fn test_validate_header_with_valid_signature() {
-        let consensus = production_consensus();
-        let manager = Arc::new(SignerManager::new());
-        let address = manager
-            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+    #[test]
+    fn test_eip1559_disabled_rejects_dynamic_fee_transactions() {
+        use alloy_consensus::{Signed, TxEip1559, TxEip2930, TxLegacy};
+        use alloy_primitives::Signature;
+        use reth_ethereum::TransactionSigned;
+
+        let sig = Signature::test_signature();
+        let legacy = TransactionSigned::Legacy(Signed::new_unhashed(
+            TxLegacy {
+                gas_price: 1_000_000_000,
+                gas_limit: 21_000,
+                ..Default::default()
+            },
+            sig,
+        ));
+        let access_list = TransactionSigned::Eip2930(Signed::new_unhashed(
+            TxEip2930 {
+                gas_price: 1_000_000_000,
+                gas_limit: 21_000,
+                ..Default::default()
+            },
+            sig,
+        ));
+        let dynamic = TransactionSigned::Eip1559(Signed::new_unhashed(
+            TxEip1559 {
+                max_fee_per_gas: 1_000_000_000,
+                gas_limit: 21_000,
+                ..Default::default()
+            },
+            sig,
+        ));
 
-        // Verify the signer address is authorized
-        assert!(consensus.chain_spec.is_authorized_signer(&address));
+        let consensus = legacy_gas_consensus();
+        let ok = block_with(vec![legacy.clone(), access_list.clone()]);
+        let result: Result<(), ConsensusError> =
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(&consensus, &ok);
+        assert!(result.is_ok());
 
-        let sealer = BlockSealer::new(manager);
+        let bad = block_with(vec![legacy.clone(), access_list, dynamic.clone()]);
+        match Consensus::<reth_ethereum::Block>::validate_block_pre_execution(&consensus, &bad) {
+            Err(ConsensusError::Custom(err)) => assert!(matches!(
+                err.downcast_ref::<PoaConsensusError>(),
+                Some(PoaConsensusError::DynamicFeeTransaction {
+                    index: 2,
+                    tx_type: 2
+                })
+            )),
+            other => panic!("Expected DynamicFeeTransaction, got {:?}", other),
+        }
 
-        let header = Header {
-            number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 12345,
-            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
-        };
+        // With EIP-1559 enabled the same transactions are accepted.
+        let block = block_with(vec![legacy, dynamic]);
+        let result: Result<(), ConsensusError> =
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(
+                &dev_consensus(),
+                &block,
+            );
+        assert!(result.is_ok());
+    }
 
-        let signed_header = sealer.seal_header(header, &address).await.unwrap();
-        let sealed = SealedHeader::seal_slow(signed_header);
+    #[test]
+    fn test_blob_transaction_rejected_before_cancun() {
+        use alloy_consensus::{Signed, TxEip4844, TxLegacy};
+        use alloy_primitives::Signature;
+        use reth_chainspec::{Chain, ChainSpecBuilder};
+        use reth_ethereum::TransactionSigned;
+
+        let sig = Signature::test_signature();
+        let legacy = TransactionSigned::Legacy(Signed::new_unhashed(TxLegacy::default(), sig));
+        let blob = TransactionSigned::Eip4844(Signed::new_unhashed(
+            TxEip4844 {
+                max_fee_per_gas: 1_000_000_000,
+                gas_limit: 21_000,
+                blob_versioned_hashes: vec![B256::repeat_byte(1)],
+                max_fee_per_blob_gas: 1,
+                ..Default::default()
+            },
+            sig,
+        ));
+
+        let shanghai = ChainSpecBuilder::default()
+            .chain(Chain::dev())
+            .genesis(Default::default())
+            .shanghai_activated()
+            .build();
+        let allowed = PoaConsensus::fork_tx_types(&shanghai, 1, 1);
+        assert_eq!(allowed, TxTypes::from_iter([0, 1, 2]));
+        let err =
+            PoaConsensus::validate_transaction_types(&[legacy.clone(), blob.clone()], allowed)
+                .unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::DisallowedTransactionType {
+                index: 1,
+                tx_type: 3
+            }
+        ));
 
-        // Should pass validation in production mode
+        // The dev chain activates Cancun at genesis.
+        let consensus = dev_consensus();
+        assert!(consensus.allowed_tx_types_at(1, 1).contains(3));
+        let block = block_with(vec![legacy, blob]);
         let result: Result<(), ConsensusError> =
-            HeaderValidator::validate_header(&consensus, &sealed);
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(&consensus, &block);
         assert!(result.is_ok());
     }
 
     #[test]
-    fn test_validate_header_short_extra_data_production() {
-        let consensus = production_consensus();
+    fn test_allowed_tx_types_override_forks() {
+        use alloy_consensus::{Signed, TxEip1559, TxLegacy};
+        use alloy_primitives::Signature;
+        use reth_ethereum::TransactionSigned;
 
-        let header = Header {
-            number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 12345,
-            extra_data: vec![0u8; 10].into(), // Too short for POA
-            ..Default::default()
-        };
-        let sealed = SealedHeader::seal_slow(header);
+        let sig = Signature::test_signature();
+        let legacy = TransactionSigned::Legacy(Signed::new_unhashed(TxLegacy::default(), sig));
+        let dynamic = TransactionSigned::Eip1559(Signed::new_unhashed(TxEip1559::default(), sig));
 
-        // Production mode should reject
+        let consensus = dev_consensus().with_allowed_tx_types(TxTypes::from_iter([0, 1]));
+        assert_eq!(
+            consensus.allowed_tx_types_at(1, 1),
+            TxTypes::from_iter([0, 1])
+        );
+        let ok = block_with(vec![legacy.clone()]);
         let result: Result<(), ConsensusError> =
-            HeaderValidator::validate_header(&consensus, &sealed);
-        assert!(result.is_err());
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(&consensus, &ok);
+        assert!(result.is_ok());
+
+        let bad = block_with(vec![legacy, dynamic]);
+        let err = Consensus::<reth_ethereum::Block>::validate_block_pre_execution(&consensus, &bad)
+            .unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::DisallowedTransactionType {
+                index: 1,
+                tx_type: 2
+            }
+        ));
     }
 
-    #[test]
-    fn test_validate_header_short_extra_data_dev_mode() {
-        let consensus = dev_consensus();
+    /// Production consensus over the dev signers with a 4-block epoch.
+    fn checkpoint_consensus() -> PoaConsensus {
+        let chain = crate::chainspec::PoaChainSpec::new(
+            crate::genesis::create_dev_genesis(),
+            crate::chainspec::PoaConfig {
+                period: 2,
+                epoch: 4,
+                signers: crate::genesis::dev_signers(),
+            },
+        );
+        PoaConsensus::new(Arc::new(chain))
+    }
 
+    /// Epoch block `number` listing `signers`, sealed with dev key `key`.
+    async fn checkpoint(number: u64, signers: &[Address], key: usize) -> SealedHeader<Header> {
+        let manager = Arc::new(SignerManager::new());
+        let sealer_addr = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[key]).unwrap();
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LENGTH];
+        for signer in signers {
+            extra_data.extend_from_slice(signer.as_slice());
+        }
+        extra_data.extend_from_slice(&[0u8; EXTRA_SEAL_LENGTH]);
         let header = Header {
-            number: 1,
+            number,
             gas_limit: 30_000_000,
-            timestamp: 12345,
-            extra_data: vec![0u8; 10].into(), // Too short for POA
-            ..Default::default()
+            timestamp: 1_000 + number * 2,
+            extra_data: extra_data.into(),
+            ..fork_header()
         };
-        let sealed = SealedHeader::seal_slow(header);
+        let signed = BlockSealer::new(manager)
+            .seal_header(header, &sealer_addr)
+            .await
+            .unwrap();
+        SealedHeader::seal_slow(signed)
+    }
 
-        // Dev mode should pass (no signature checks)
-        let result: Result<(), ConsensusError> =
-            HeaderValidator::validate_header(&consensus, &sealed);
-        assert!(result.is_ok());
+    fn poa_error(err: &ConsensusError) -> &PoaConsensusError {
+        match err {
+            ConsensusError::Custom(err) => err
+                .downcast_ref::<PoaConsensusError>()
+                .expect("PoaConsensusError"),
+            other => panic!("Expected custom POA error, got {:?}", other),
+        }
     }
 
     #[tokio::test]
-    async fn test_validate_header_unauthorized_signer() {
-        // Use a key that produces an address NOT in the authorized signers list
-        let consensus = production_consensus();
-        let manager = Arc::new(SignerManager::new());
-        // DEV_PRIVATE_KEYS[5] corresponds to account index 5 which is NOT in dev_signers() (only first 3)
-        let address = manager
-            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[5]).unwrap();
+    async fn test_verify_checkpoint_chain_with_signer_change() {
+        let consensus = checkpoint_consensus();
+        let initial = crate::genesis::dev_signers();
+        let added = crate::genesis::dev_accounts()[3];
+        let mut grown = initial.clone();
+        grown.push(added);
+
+        // Epoch 1: unchanged. Epoch 2: signer 3 voted in. Epoch 3: sealed by the new signer.
+        let checkpoints = vec![
+            checkpoint(4, &initial, 0).await,
+            checkpoint(8, &grown, 1).await,
+            checkpoint(12, &grown, 3).await,
+        ];
+
+        let snapshot = consensus.verify_checkpoint_chain(&checkpoints).unwrap();
+        assert_eq!(snapshot.number, 12);
+        assert_eq!(snapshot.hash, checkpoints[2].hash());
+        assert_eq!(snapshot.signers, grown);
+        assert!(snapshot.is_signer(&added));
+    }
 
-        // Verify the address is NOT authorized
-        assert!(!consensus.chain_spec.is_authorized_signer(&address));
+    #[tokio::test]
+    async fn test_verify_checkpoint_chain_rejects_gap() {
+        let consensus = checkpoint_consensus();
+        let signers = crate::genesis::dev_signers();
+        let checkpoints = vec![
+            checkpoint(4, &signers, 0).await,
+            checkpoint(12, &signers, 1).await,
+        ];
+
+        let err = consensus.verify_checkpoint_chain(&checkpoints).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::CheckpointGap {
+                expected: 8,
+                got: 12
+            }
+        ));
+        assert!(matches!(
+            poa_error(&consensus.verify_checkpoint_chain(&[]).unwrap_err()),
+            PoaConsensusError::EmptyCheckpointChain
+        ));
+    }
 
-        let sealer = BlockSealer::new(manager);
+    #[tokio::test]
+    async fn test_verify_checkpoint_chain_rejects_sealer_outside_prior_set() {
+        let consensus = checkpoint_consensus();
+        let initial = crate::genesis::dev_signers();
+        let outsider = crate::genesis::dev_accounts()[3];
+        let mut grown = initial.clone();
+        grown.push(outsider);
+
+        // The outsider seals the very checkpoint that would add it.
+        let checkpoints = vec![checkpoint(4, &grown, 3).await];
+        let err = consensus.verify_checkpoint_chain(&checkpoints).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::UnauthorizedSigner { signer } if *signer == outsider
+        ));
+    }
 
-        let header = Header {
-            number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 12345,
-            extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
-        };
+    #[tokio::test]
+    async fn test_verify_checkpoint_chain_rejects_underivable_signer_list() {
+        let consensus = checkpoint_consensus();
+        let initial = crate::genesis::dev_signers();
+        let accounts = crate::genesis::dev_accounts();
+        let replaced = vec![initial[0], accounts[3], accounts[4]];
+
+        let checkpoints = vec![
+            checkpoint(4, &initial, 0).await,
+            checkpoint(8, &replaced, 1).await,
+        ];
+        let err = consensus.verify_checkpoint_chain(&checkpoints).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::UnderivableSignerSet {
+                number: 8,
+                retained: 1,
+                previous: 3
+            }
+        ));
+    }
 
-        let signed_header = sealer.seal_header(header, &address).await.unwrap();
-        let sealed = SealedHeader::seal_slow(signed_header);
+    #[tokio::test]
+    async fn test_epoch_snapshot_of_epoch_blocks_only() {
+        let consensus = checkpoint_consensus();
+        let signers = crate::genesis::dev_signers();
 
-        // Should fail - signer not authorized
-        let result: Result<(), ConsensusError> =
-            HeaderValidator::validate_header(&consensus, &sealed);
-        assert!(result.is_err());
+        let epoch_block = checkpoint(4, &signers, 0).await;
+        assert_eq!(
+            consensus.epoch_snapshot(&epoch_block).unwrap(),
+            Some(Snapshot {
+                number: 4,
+                hash: epoch_block.hash(),
+                signers,
+            })
+        );
+        let header = SealedHeader::seal_slow(Header {
+            number: 5,
+            ..epoch_block.header().clone()
+        });
+        assert_eq!(consensus.epoch_snapshot(&header).unwrap(), None);
     }
 
-    #[test]
-    fn test_validate_header_against_parent_valid() {
-        let consensus = dev_consensus();
+    /// Fixed balances; `None` stands for unavailable parent state.
+    struct Balances(Option<U256>);
 
-        let parent = Header {
-            number: 0,
-            gas_limit: 30_000_000,
-            timestamp: 0,
-            ..Default::default()
-        };
-        let sealed_parent = SealedHeader::seal_slow(parent);
+    impl SignerStakeReader for Balances {
+        fn stake_at(&self, _block_hash: B256, _signer: Address) -> Option<U256> {
+            self.0
+        }
+    }
 
-        let child = Header {
-            number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 2, // At least block_period (2s) after parent
-            parent_hash: sealed_parent.hash(),
-            ..Default::default()
+    #[tokio::test]
+    async fn test_understaked_signer_rejected() {
+        const ETH: u64 = 1_000_000_000_000_000_000;
+        let required = U256::from(32 * ETH);
+        let chain = crate::chainspec::PoaChainSpec::new(
+            crate::genesis::create_dev_genesis(),
+            crate::chainspec::PoaConfig {
+                period: 2,
+                epoch: 4,
+                signers: crate::genesis::dev_signers(),
+            },
+        )
+        .with_min_signer_stake(required);
+        let staked = |balance: Option<U256>| {
+            PoaConsensus::new(Arc::new(chain.clone()))
+                .with_stake_source(StakeSource::new(Arc::new(Balances(balance))))
+        };
+        let header = checkpoint(5, &[], 0).await;
+        let signer = staked(None).recover_signer(header.header()).unwrap();
+        let block = SealedBlock::seal_slow(reth_ethereum::Block {
+            header: header.header().clone(),
+            body: Default::default(),
+        });
+        let validate = |consensus: PoaConsensus| {
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(&consensus, &block)
         };
-        let sealed_child = SealedHeader::seal_slow(child);
 
-        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
-        assert!(result.is_ok());
+        // Header validation has no state to judge the stake by.
+        assert!(staked(Some(U256::ZERO)).validate_header(&header).is_ok());
+        let err = validate(staked(Some(U256::from(31 * ETH)))).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::SignerUnderstaked { signer: s, stake, required: r }
+                if *s == signer && *stake == U256::from(31 * ETH) && *r == required
+        ));
+
+        assert!(validate(staked(Some(required))).is_ok());
+        // Executing a block needs its parent state; without it the block is refused.
+        let err = validate(staked(None)).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::ParentStateUnavailable { parent_hash }
+                if *parent_hash == header.parent_hash
+        ));
+    }
+
+    /// Checkpoint headers held in memory, missing ones absent.
+    struct Partial(Vec<SealedHeader<Header>>);
+
+    impl CheckpointSource for Partial {
+        fn checkpoint_header(&self, number: u64) -> Option<SealedHeader<Header>> {
+            self.0.iter().find(|h| h.number == number).cloned()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_light_sync_snapshot_reports_missing_checkpoint() {
+        let consensus = checkpoint_consensus();
+        let signers = crate::genesis::dev_signers();
+        let source = Partial(vec![
+            checkpoint(4, &signers, 0).await,
+            checkpoint(8, &signers, 1).await,
+        ]);
+
+        let snapshot = consensus
+            .light_sync_snapshot(&source, 0, 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.number, 8);
+
+        let err = consensus.light_sync_snapshot(&source, 0, 13).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::CheckpointGap { expected: 12, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_light_sync_snapshot_skips_checkpoints_at_or_below_head() {
+        let consensus = checkpoint_consensus();
+        let signers = crate::genesis::dev_signers();
+        let source = Partial(vec![
+            checkpoint(4, &signers, 0).await,
+            checkpoint(8, &signers, 1).await,
+        ]);
+
+        // Executed up to the latest checkpoint (or past it): nothing newer to apply,
+        // even though the headers would verify.
+        for head in [8, 9, 11] {
+            assert_eq!(
+                consensus.light_sync_snapshot(&source, head, 11).unwrap(),
+                None
+            );
+        }
+        // A checkpoint above the executed head is verified from genesis and applied.
+        let snapshot = consensus
+            .light_sync_snapshot(&source, 7, 11)
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.number, 8);
     }
 
     #[test]
-    fn test_validate_header_against_parent_wrong_number() {
-        let consensus = dev_consensus();
+    fn test_validate_parent_hash_accepts_child() {
+        let parent = SealedHeader::seal_slow(Header { number: 7, ..fork_header() });
+        let child = Header { number: 8, parent_hash: parent.hash(), ..fork_header() };
+        assert!(PoaConsensus::validate_parent_hash(&child, &parent).is_ok());
+    }
 
-        let parent = Header {
-            number: 0,
-            gas_limit: 30_000_000,
-            ..Default::default()
-        };
-        let sealed_parent = SealedHeader::seal_slow(parent);
+    #[test]
+    fn test_validate_parent_hash_rejects_wrong_hash() {
+        let parent = SealedHeader::seal_slow(Header { number: 7, ..fork_header() });
+        let child = Header { number: 8, parent_hash: B256::repeat_byte(0xee), ..fork_header() };
+        match PoaConsensus::validate_parent_hash(&child, &parent).unwrap_err() {
+            ConsensusError::ParentHashMismatch(diff) => {
+                assert_eq!(diff.got, B256::repeat_byte(0xee));
+                assert_eq!(diff.expected, parent.hash());
+            }
+            other => panic!("Expected ParentHashMismatch, got {:?}", other),
+        }
+    }
 
-        let child = Header {
-            number: 5, // Wrong - should be 1
+    #[test]
+    fn test_validate_parent_hash_rejects_wrong_number() {
+        let parent = SealedHeader::seal_slow(Header { number: 7, ..fork_header() });
+        for number in [7, 9, 0] {
+            let child = Header { number, parent_hash: parent.hash(), ..fork_header() };
+            match PoaConsensus::validate_parent_hash(&child, &parent).unwrap_err() {
+                ConsensusError::ParentBlockNumberMismatch { parent_block_number, block_number } => {
+                    assert_eq!(parent_block_number, 7);
+                    assert_eq!(block_number, number);
+                }
+                other => panic!("Expected ParentBlockNumberMismatch, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_parent_hash_max_block_number() {
+        let parent = SealedHeader::seal_slow(Header { number: u64::MAX, ..fork_header() });
+        let child = Header { number: 0, parent_hash: parent.hash(), ..fork_header() };
+        assert!(PoaConsensus::validate_parent_hash(&child, &parent).is_err());
+    }
+
+    fn header_with_vanity(vanity: &[u8]) -> SealedHeader<Header> {
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH];
+        extra_data[..vanity.len()].copy_from_slice(vanity);
+        SealedHeader::seal_slow(Header {
             gas_limit: 30_000_000,
-            timestamp: 2,
-            parent_hash: sealed_parent.hash(),
-            ..Default::default()
-        };
-        let sealed_child = SealedHeader::seal_slow(child);
+            extra_data: extra_data.into(),
+            ..fork_header()
+        })
+    }
 
-        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
-        assert!(result.is_err());
+    #[test]
+    fn test_strict_vanity_rejects_control_characters() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_strict_vanity(true);
+        let consensus = PoaConsensus::new_dev(Arc::new(chain));
+        let sealed = header_with_vanity(b"meow[31mchain");
+
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        match result.unwrap_err() {
+            ConsensusError::Custom(err) => match err.downcast_ref::<PoaConsensusError>() {
+                Some(PoaConsensusError::InvalidVanity { offset, byte }) => {
+                    assert_eq!(*offset, 4);
+                    assert_eq!(*byte, 0x1b);
+                }
+                other => panic!("Expected InvalidVanity, got {:?}", other),
+            },
+            other => panic!("Expected Custom, got {:?}", other),
+        }
+
+        let clean = header_with_vanity(&vanity::client_vanity("meowchain/1.0"));
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &clean);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_vanity_unchecked_without_strict_mode() {
+        let consensus = PoaConsensus::new_dev(Arc::new(crate::chainspec::PoaChainSpec::dev_chain()));
+        let sealed = header_with_vanity(b"meow[31mchain");
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(result.is_ok());
     }
 
     #[test]
-    fn test_validate_header_against_parent_wrong_hash() {
+    fn test_validate_header_rejects_gas_limit_below_minimum() {
+        // Bounds are enforced even in dev mode
         let consensus = dev_consensus();
+        let header = Header {
+            number: 1,
+            gas_limit: 4999,
+            ..fork_header()
+        };
+        let sealed = SealedHeader::seal_slow(header);
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&consensus, &sealed);
+        assert!(result.is_err());
+    }
 
+    #[test]
+    fn test_validate_block_gas_target_within_drift() {
         let parent = Header {
-            number: 0,
             gas_limit: 30_000_000,
             ..Default::default()
         };
-        let sealed_parent = SealedHeader::seal_slow(parent);
-
         let child = Header {
-            number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 2,
-            parent_hash: B256::ZERO, // Wrong parent hash
+            gas_limit: 30_000_000 + 30_000_000 / 1024,
             ..Default::default()
         };
-        let sealed_child = SealedHeader::seal_slow(child);
+        assert!(PoaConsensus::validate_block_gas_target(&child, &parent).is_ok());
 
-        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
-        assert!(result.is_err());
+        let too_far = Header {
+            gas_limit: 30_000_000 + 30_000_000 / 1024 + 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            PoaConsensus::validate_block_gas_target(&too_far, &parent),
+            Err(ConsensusError::GasLimitInvalidIncrease { .. })
+        ));
     }
 
-    #[test]
-    fn test_validate_header_against_parent_timestamp_too_early() {
-        let consensus = dev_consensus();
+    /// Parent gas limit and ChainConfig target, the same for every block; None =
+    /// state unavailable.
+    struct GasLimits(Option<(u64, u64)>);
+
+    impl GasLimitReader for GasLimits {
+        fn ramp_gas_limits(&self, _block_hash: B256) -> Option<(u64, u64)> {
+            self.0
+        }
+    }
 
+    #[test]
+    fn test_validate_gas_limit_change_leaves_ramp_steps_to_pre_execution() {
+        let chain = PoaChainSpec::dev_chain().with_max_gas_per_block_ramp(10);
+        let consensus = PoaConsensus::new_dev(Arc::new(chain));
         let parent = Header {
-            number: 0,
             gas_limit: 30_000_000,
-            timestamp: 100,
-            ..Default::default()
+            ..fork_header()
         };
-        let sealed_parent = SealedHeader::seal_slow(parent);
-
-        let child = Header {
+        let ramped = Header {
             number: 1,
-            gas_limit: 30_000_000,
-            timestamp: 100, // Same as parent — too early (block_period is 1s, need > parent)
-            parent_hash: sealed_parent.hash(),
-            ..Default::default()
+            gas_limit: 127_000_000,
+            ..fork_header()
         };
-        let sealed_child = SealedHeader::seal_slow(child);
+        assert!(consensus.validate_gas_limit_change(&ramped, &parent).is_ok());
 
-        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
-        assert!(result.is_err());
+        // Past the ramp only the drift applies: no jump to the target.
+        let jump = Header {
+            number: 11,
+            gas_limit: 1_000_000_000,
+            ..fork_header()
+        };
+        assert!(matches!(
+            consensus.validate_gas_limit_change(&jump, &parent),
+            Err(ConsensusError::GasLimitInvalidIncrease { .. })
+        ));
+        // Without a ramp only the drift applies.
+        assert!(dev_consensus()
+            .validate_gas_limit_change(&ramped, &parent)
+            .is_err());
     }
 
     #[test]
-    fn test_validate_header_against_parent_gas_limit_increase_too_large() {
-        let consensus = dev_consensus();
+    fn test_validate_gas_limit_ramp_bounds_each_step() {
+        let (parent, target) = (30_000_000, 1_000_000_000);
+        // First of ten ramp blocks: a tenth of the 970M distance.
+        assert!(PoaConsensus::validate_gas_limit_ramp(127_000_000, parent, target, 10).is_ok());
+        assert!(matches!(
+            PoaConsensus::validate_gas_limit_ramp(127_000_001, parent, target, 10),
+            Err(ConsensusError::GasLimitInvalidIncrease { .. })
+        ));
+        // Moving slower than the ramp is fine, as is the plain drift either way.
+        assert!(PoaConsensus::validate_gas_limit_ramp(50_000_000, parent, target, 10).is_ok());
+        let drift = parent - parent / 1024;
+        assert!(PoaConsensus::validate_gas_limit_ramp(drift, parent, target, 10).is_ok());
+        // Only the last ramp block may land on the target.
+        assert!(PoaConsensus::validate_gas_limit_ramp(target, parent, target, 2).is_err());
+        assert!(PoaConsensus::validate_gas_limit_ramp(target, parent, target, 1).is_ok());
+    }
 
-        let parent = Header {
-            number: 0,
-            gas_limit: 30_000_000,
-            timestamp: 0,
-            ..Default::default()
+    #[test]
+    fn test_gas_limit_ramp_checked_against_parent_state() {
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_max_gas_per_block_ramp(10));
+        let consensus = |gas_limits: Option<(u64, u64)>| {
+            PoaConsensus::new_dev(chain.clone())
+                .with_gas_limit_source(GasLimitSource::new(Arc::new(GasLimits(gas_limits))))
         };
-        let sealed_parent = SealedHeader::seal_slow(parent);
-
-        // Max increase is parent_gas_limit / 1024 = ~29,296
-        let child = Header {
-            number: 1,
-            gas_limit: 31_000_000, // 1M increase, way over limit
-            timestamp: 2,
-            parent_hash: sealed_parent.hash(),
-            ..Default::default()
+        let block = |number: u64, gas_limit: u64| {
+            SealedBlock::seal_slow(reth_ethereum::Block {
+                header: Header {
+                    number,
+                    gas_limit,
+                    ..fork_header()
+                },
+                body: Default::default(),
+            })
+        };
+        let validate = |consensus: &PoaConsensus, block: &SealedBlock<reth_ethereum::Block>| {
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(consensus, block)
         };
-        let sealed_child = SealedHeader::seal_slow(child);
 
-        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
-        assert!(result.is_err());
+        let ramping = consensus(Some((30_000_000, 1_000_000_000)));
+        assert!(validate(&ramping, &block(1, 127_000_000)).is_ok());
+        assert!(validate(&ramping, &block(1, 127_000_001)).is_err());
+        // Past the ramp the step is header validation's drift check.
+        assert!(validate(&ramping, &block(11, 127_000_001)).is_ok());
+
+        let unavailable = validate(&consensus(None), &block(1, 127_000_000)).unwrap_err();
+        assert!(matches!(
+            poa_error(&unavailable),
+            PoaConsensusError::ParentStateUnavailable { .. }
+        ));
     }
 
     #[test]
-    fn test_validate_header_against_parent_gas_limit_decrease_too_large() {
-        let consensus = dev_consensus();
-
+    fn test_validate_block_gas_target_rejects_below_minimum_even_within_drift() {
+        // A 1-gas drop from the minimum is within the drift allowance (5000 / 1024 = 4),
+        // but the bounds check must still reject it.
         let parent = Header {
-            number: 0,
-            gas_limit: 30_000_000,
-            timestamp: 0,
+            gas_limit: MIN_GAS_LIMIT,
             ..Default::default()
         };
-        let sealed_parent = SealedHeader::seal_slow(parent);
-
         let child = Header {
-            number: 1,
-            gas_limit: 29_000_000, // 1M decrease, way over limit
-            timestamp: 2,
-            parent_hash: sealed_parent.hash(),
+            gas_limit: MIN_GAS_LIMIT - 1,
             ..Default::default()
         };
-        let sealed_child = SealedHeader::seal_slow(child);
-
-        let result = consensus.validate_header_against_parent(&sealed_child, &sealed_parent);
-        assert!(result.is_err());
+        let err = PoaConsensus::validate_block_gas_target(&child, &parent).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::GasLimitTooLow { got, min }
+                if *got == MIN_GAS_LIMIT - 1 && *min == MIN_GAS_LIMIT
+        ));
     }
 
     #[test]
@@ -846,6 +3719,124 @@ mod tests {
         assert_eq!(hash_with, hash_without);
     }
 
+    #[tokio::test]
+    async fn test_sign_and_seal_genesis_passes_validate_header() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let manager = SignerManager::new();
+        let primary = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+
+        let sealed = PoaConsensus::sign_and_seal_genesis(&chain, &manager, primary)
+            .await
+            .unwrap();
+        assert_eq!(sealed.header().number, 0);
+        assert_eq!(sealed.header().difficulty, U256::ZERO);
+        assert_eq!(sealed.header().ommers_hash, alloy_consensus::EMPTY_OMMER_ROOT_HASH);
+
+        let consensus = PoaConsensus::new(Arc::new(chain));
+        assert_eq!(consensus.recover_signer(sealed.header()).unwrap(), primary);
+        HeaderValidator::validate_header(&consensus, sealed.sealed_header()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_seal_genesis_rejects_unauthorized_signer() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let manager = SignerManager::new();
+        let outsider = manager
+            .add_signer_from_hex(
+                "0x1111111111111111111111111111111111111111111111111111111111111111",
+            )
+            .unwrap();
+
+        let err = PoaConsensus::sign_and_seal_genesis(&chain, &manager, outsider)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::UnauthorizedSigner { signer } if *signer == outsider
+        ));
+    }
+
+    #[test]
+    fn test_check_extra_data_format() {
+        let with_len = |len: usize| Header {
+            extra_data: vec![0u8; len].into(),
+            ..Default::default()
+        };
+        let one_signer = EXTRA_VANITY_LENGTH + ADDRESS_LENGTH + EXTRA_SEAL_LENGTH;
+        assert!(PoaConsensus::check_extra_data_format(&with_len(one_signer)).is_ok());
+        assert!(matches!(
+            PoaConsensus::check_extra_data_format(&with_len(one_signer + 1)),
+            Err(PoaConsensusError::InvalidSignerList)
+        ));
+        let no_signers = with_len(EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH);
+        assert!(matches!(
+            PoaConsensus::check_extra_data_format(&no_signers),
+            Err(PoaConsensusError::ExtraDataTooShort { .. })
+        ));
+        // A metadata commitment after the seal is not part of the signer list.
+        let committed = with_len(one_signer + metadata::METADATA_HASH_LENGTH);
+        assert!(PoaConsensus::check_extra_data_format(&committed).is_ok());
+    }
+
+    #[test]
+    fn test_check_extra_data_format_skips_metadata() {
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LENGTH + ADDRESS_LENGTH + EXTRA_SEAL_LENGTH];
+        let metadata = BlockMetadata::from([("batchSeq".to_string(), serde_json::json!(9))]);
+        metadata::append_metadata(&mut extra_data, &metadata).unwrap();
+        let header = Header {
+            extra_data: extra_data.into(),
+            ..Default::default()
+        };
+        assert!(PoaConsensus::check_extra_data_format(&header).is_ok());
+
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LENGTH + ADDRESS_LENGTH + EXTRA_SEAL_LENGTH];
+        extra_data.extend_from_slice(b"[1]");
+        extra_data.extend_from_slice(&3u32.to_be_bytes());
+        extra_data.extend_from_slice(&metadata::METADATA_MAGIC);
+        let header = Header {
+            extra_data: extra_data.into(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            PoaConsensus::check_extra_data_format(&header),
+            Err(PoaConsensusError::InvalidBlockMetadata(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_metadata_is_covered_by_seal() {
+        let consensus = production_consensus();
+        let manager = Arc::new(SignerManager::new());
+        let address = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH];
+        let metadata = BlockMetadata::from([("l1StateRoot".to_string(), "0xabc".into())]);
+        metadata::append_metadata(&mut extra_data, &metadata).unwrap();
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            extra_data: extra_data.into(),
+            ..fork_header()
+        };
+        let signed = BlockSealer::new(manager).seal_header(header, &address).await.unwrap();
+
+        assert_eq!(consensus.recover_signer(&signed).unwrap(), address);
+        assert_eq!(metadata::decode_metadata(&signed.extra_data).unwrap(), Some(metadata));
+        let sealed = SealedHeader::seal_slow(signed.clone());
+        assert!(HeaderValidator::validate_header(&consensus, &sealed).is_ok());
+
+        // Rewriting the metadata changes the seal hash, so the signer no longer matches.
+        let mut tampered = signed.extra_data.to_vec();
+        let json_start = EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH;
+        let position = tampered[json_start..].iter().position(|&b| b == b'a').unwrap();
+        tampered[json_start + position] = b'b';
+        let tampered = Header {
+            extra_data: tampered.into(),
+            ..signed
+        };
+        assert_ne!(consensus.recover_signer(&tampered).ok(), Some(address));
+    }
+
     #[test]
     fn test_extract_signers_from_epoch_block() {
         let consensus = production_consensus();
@@ -925,6 +3916,8 @@ mod tests {
     // FullConsensus trait: validate_block_post_execution tests
     // =========================================================================
 
+    use alloy_eips::eip6110::DEPOSIT_REQUEST_TYPE;
+    use alloy_eips::eip7685::Requests;
     use alloy_primitives::Bloom;
     use reth_ethereum::BlockBody;
     use reth_execution_types::BlockExecutionResult;
@@ -941,7 +3934,7 @@ mod tests {
             gas_limit,
             receipts_root,
             logs_bloom,
-            ..Default::default()
+            ..fork_header()
         };
         let body = BlockBody::default();
         let block = reth_ethereum::Block { header, body };
@@ -1031,6 +4024,46 @@ mod tests {
         assert!(validation.is_err());
     }
 
+    #[test]
+    fn test_validate_block_post_execution_requests_hash() {
+        let chain =
+            PoaChainSpec::dev_chain().with_eip6110_deposit_requests(Address::repeat_byte(6));
+        let consensus = PoaConsensus::new_dev(Arc::new(chain));
+        let mut request = vec![DEPOSIT_REQUEST_TYPE];
+        request.resize(193, 0);
+        let deposits = Requests::new(vec![request.into()]);
+        let mut result = make_execution_result(0);
+        result.requests = deposits.clone();
+
+        // The header commits to no requests while execution produced a deposit.
+        let block = make_recovered_block(0, 30_000_000, B256::ZERO, Bloom::ZERO);
+        let validation: Result<(), ConsensusError> =
+            FullConsensus::<reth_ethereum::EthPrimitives>::validate_block_post_execution(
+                &consensus, &block, &result, None,
+            );
+        assert!(matches!(
+            validation,
+            Err(ConsensusError::BodyRequestsHashDiff(_))
+        ));
+
+        let header = Header {
+            requests_hash: Some(deposits.requests_hash()),
+            ..block.header().clone()
+        };
+        let block = RecoveredBlock::new_sealed(
+            SealedBlock::seal_slow(reth_ethereum::Block {
+                header,
+                body: BlockBody::default(),
+            }),
+            vec![],
+        );
+        let validation: Result<(), ConsensusError> =
+            FullConsensus::<reth_ethereum::EthPrimitives>::validate_block_post_execution(
+                &consensus, &block, &result, None,
+            );
+        assert!(validation.is_ok());
+    }
+
     #[test]
     fn test_validate_block_post_execution_no_receipt_root() {
         let consensus = dev_consensus();
@@ -1058,7 +4091,7 @@ mod tests {
             gas_used,
             gas_limit,
             extra_data: vec![0u8; extra_data_len].into(),
-            ..Default::default()
+            ..fork_header()
         };
         let body = BlockBody::default();
         let block = reth_ethereum::Block { header, body };
@@ -1121,6 +4154,298 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    /// Three signed legacy transfers (nonces 0..3) with an independently computed
+    /// transactions root.
+    fn known_transactions() -> (Vec<reth_ethereum::TransactionSigned>, B256) {
+        use alloy_consensus::{Signed, TxLegacy};
+        use alloy_primitives::{address, b256, Signature, TxKind};
+
+        let r = b256!("840cfc572845f5786e702984c2a582528cad4b49b2a10b9db1be7fca90058565");
+        let s = b256!("25e7109ceb98168d95b09b18bbf6b685130e0562f233877d492b94eee0c5b6d1");
+        let sig = Signature::new(U256::from_be_bytes(r.0), U256::from_be_bytes(s.0), false);
+        let transactions = (0..3)
+            .map(|nonce| {
+                reth_ethereum::TransactionSigned::Legacy(Signed::new_unhashed(
+                    TxLegacy {
+                        nonce,
+                        gas_price: 1_000_000_000,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(address!("000000000000000000000000000000000000beef")),
+                        value: U256::from(1_000_000_000_000_000_000u128),
+                        ..Default::default()
+                    },
+                    sig,
+                ))
+            })
+            .collect();
+        let root = b256!("626e4a7769199c125e33440aab55dd8771989b5dc29b700fbd450c53e31f843f");
+        (transactions, root)
+    }
+
+    #[test]
+    fn test_validate_transactions_root_known_transactions() {
+        let (transactions, root) = known_transactions();
+        let header = Header { transactions_root: root, ..fork_header() };
+        assert!(PoaConsensus::validate_transactions_root(&header, &transactions).is_ok());
+
+        // Dropping a transaction no longer matches the header.
+        match PoaConsensus::validate_transactions_root(&header, &transactions[..2]) {
+            Err(ConsensusError::Custom(err)) => match err.downcast_ref::<PoaConsensusError>() {
+                Some(PoaConsensusError::TransactionsRootMismatch { expected, computed }) => {
+                    assert_eq!(*expected, root);
+                    assert_ne!(*computed, root);
+                }
+                other => panic!("Expected TransactionsRootMismatch, got {:?}", other),
+            },
+            other => panic!("Expected TransactionsRootMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_transactions_root_empty_body() {
+        let header = fork_header();
+        let none: &[reth_ethereum::TransactionSigned] = &[];
+        assert!(PoaConsensus::validate_transactions_root(&header, none).is_ok());
+    }
+
+    #[test]
+    fn test_validate_block_pre_execution_rejects_body_not_in_header() {
+        let (transactions, _) = known_transactions();
+        let block = reth_ethereum::Block {
+            header: fork_header(),
+            body: BlockBody { transactions, ..Default::default() },
+        };
+        let result: Result<(), ConsensusError> =
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(
+                &dev_consensus(),
+                &SealedBlock::seal_slow(block),
+            );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_block_pre_execution_block_size_limit() {
+        let (transactions, root) = known_transactions();
+        let size = alloy_rlp::list_length::<_, reth_ethereum::TransactionSigned>(&transactions);
+        let block = SealedBlock::seal_slow(reth_ethereum::Block {
+            header: Header { transactions_root: root, ..fork_header() },
+            body: BlockBody { transactions, ..Default::default() },
+        });
+        let validate = |max: usize| {
+            let chain = crate::chainspec::PoaChainSpec::dev_chain().with_max_block_size_bytes(max);
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(
+                &PoaConsensus::new_dev(Arc::new(chain)),
+                &block,
+            )
+        };
+
+        // Exactly at and just under the limit.
+        assert!(validate(size).is_ok());
+        assert!(validate(size + 1).is_ok());
+
+        // One byte over.
+        let err = validate(size - 1).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::BlockTooLarge { size: got, max } if *got == size && *max == size - 1
+        ));
+    }
+
+    #[test]
+    fn test_validate_parent_beacon_root() {
+        let stub = B256::repeat_byte(0x42);
+        let with_root = |root| Header { parent_beacon_block_root: root, ..fork_header() };
+        let check = |root, spec, stub_root| {
+            PoaConsensus::validate_parent_beacon_root(&with_root(root), spec, stub_root)
+        };
+
+        // Pre-Cancun: the field must be absent, stub or not.
+        assert!(check(None, SpecId::SHANGHAI, Some(stub)).is_ok());
+        let err = check(Some(stub), SpecId::SHANGHAI, Some(stub)).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::InvalidParentBeaconRoot { expected: None, got: Some(_) }
+        ));
+
+        // Post-Cancun with a stub: exactly the stub root.
+        assert!(check(Some(stub), SpecId::CANCUN, Some(stub)).is_ok());
+        assert!(check(Some(stub), SpecId::PRAGUE, Some(stub)).is_ok());
+        assert!(check(Some(B256::ZERO), SpecId::CANCUN, Some(stub)).is_err());
+        let err = check(None, SpecId::CANCUN, Some(stub)).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::InvalidParentBeaconRoot { expected: Some(root), got: None }
+                if *root == stub
+        ));
+
+        // Post-Cancun without a stub: anything goes.
+        assert!(check(Some(B256::repeat_byte(7)), SpecId::CANCUN, None).is_ok());
+        assert!(check(Some(B256::ZERO), SpecId::PRAGUE, None).is_ok());
+    }
+
+    /// Receipt as stored by an indexer.
+    struct IndexedReceipt(Option<B256>);
+
+    impl ReceiptBlockHash for IndexedReceipt {
+        fn block_hash(&self) -> Option<B256> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_validate_block_hash_consistency() {
+        let sealed = SealedBlock::seal_slow(reth_ethereum::Block {
+            header: fork_header(),
+            body: Default::default(),
+        });
+        let hash = sealed.hash();
+        let good = [IndexedReceipt(Some(hash)), IndexedReceipt(Some(hash))];
+        assert!(PoaConsensus::validate_block_hash_consistency(&sealed, &good).is_ok());
+        let none: &[IndexedReceipt] = &[];
+        assert!(PoaConsensus::validate_block_hash_consistency(&sealed, none).is_ok());
+
+        let other = B256::repeat_byte(0xaa);
+        let bad = [IndexedReceipt(Some(hash)), IndexedReceipt(Some(other))];
+        let err = PoaConsensus::validate_block_hash_consistency(&sealed, &bad).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::ReceiptBlockHashMismatch { receipt_index: 1, expected, got }
+                if *expected == hash && *got == Some(other)
+        ));
+
+        let missing = [IndexedReceipt(None)];
+        let err = PoaConsensus::validate_block_hash_consistency(&sealed, &missing).unwrap_err();
+        assert_eq!(poa_error(&err).kind(), "receiptBlockHashMismatch");
+    }
+
+    #[tokio::test]
+    async fn test_validate_epoch_extra_data() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let epoch = chain.epoch();
+        let signers = chain.signers().to_vec();
+
+        // A well-formed signer list passes, and so does the whole header.
+        let reversed: Vec<Address> = signers.iter().rev().copied().collect();
+        let header = checkpoint(epoch, &reversed, 0).await;
+        assert!(PoaConsensus::validate_epoch_extra_data(header.header(), &chain).is_ok());
+        let result: Result<(), ConsensusError> =
+            HeaderValidator::validate_header(&production_consensus(), &header);
+        assert!(result.is_ok(), "{result:?}");
+
+        // A vote on an epoch block is rejected.
+        let mix_hash = votes::encode_vote(Address::repeat_byte(0x42), VoteType::Add);
+        let voting = Header {
+            mix_hash,
+            ..header.header().clone()
+        };
+        let err = PoaConsensus::validate_epoch_extra_data(&voting, &chain).unwrap_err();
+        assert!(matches!(poa_error(&err), PoaConsensusError::VoteInEpochBlock { .. }));
+
+        // Non-epoch blocks are not checked.
+        let header = Header {
+            number: epoch + 1,
+            mix_hash,
+            ..fork_header()
+        };
+        assert!(PoaConsensus::validate_epoch_extra_data(&header, &chain).is_ok());
+    }
+
+    /// SignerRegistry signers, the same after every block; None = state unavailable.
+    struct SignerSets(Option<Vec<Address>>);
+
+    impl SignerSetReader for SignerSets {
+        fn registry_signers(&self, _block_hash: B256) -> Option<Vec<Address>> {
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_epoch_signers_checked_against_parent_state() {
+        let chain = Arc::new(crate::chainspec::PoaChainSpec::dev_chain());
+        let epoch = chain.epoch();
+        let signers = chain.signers().to_vec();
+        let consensus = |registry: Option<Vec<Address>>| {
+            PoaConsensus::new(chain.clone())
+                .with_signer_set_source(SignerSetSource::new(Arc::new(SignerSets(registry))))
+        };
+        let validate = |consensus: &PoaConsensus, header: &SealedHeader<Header>| {
+            let block = SealedBlock::seal_slow(reth_ethereum::Block {
+                header: header.header().clone(),
+                body: Default::default(),
+            });
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(consensus, &block)
+        };
+
+        // An empty registry: the genesis signers, in any order.
+        let reversed: Vec<Address> = signers.iter().rev().copied().collect();
+        let genesis_list = checkpoint(epoch, &reversed, 0).await;
+        assert!(validate(&consensus(Some(vec![])), &genesis_list).is_ok());
+
+        // Governance removed a signer in the parent state: the old list is a mismatch,
+        // whatever the node's live signer set says.
+        let governed = consensus(Some(signers[..2].to_vec()));
+        let err = validate(&governed, &genesis_list).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::EpochExtraDataMismatch { expected, got }
+                if expected.len() == 2 && got.len() == 3
+        ));
+        let reduced = checkpoint(epoch, &signers[..2], 0).await;
+        assert!(validate(&governed, &reduced).is_ok());
+        chain.update_live_signers(signers.clone());
+        assert!(validate(&governed, &reduced).is_ok());
+
+        // A foreign signer is a mismatch.
+        let mut foreign = signers.clone();
+        foreign[2] = Address::repeat_byte(0x99);
+        let foreign = checkpoint(epoch, &foreign, 0).await;
+        let err = validate(&consensus(Some(vec![])), &foreign).unwrap_err();
+        assert_eq!(poa_error(&err).kind(), "epochExtraDataMismatch");
+
+        // Without the parent state the block cannot be checked.
+        let err = validate(&consensus(None), &genesis_list).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::ParentStateUnavailable { .. }
+        ));
+    }
+
+    #[test]
+    fn test_validate_vote() {
+        use crate::consensus::votes::encode_vote;
+
+        let epoch = 30000;
+        let vote = encode_vote(Address::repeat_byte(0x42), VoteType::Add);
+        let header = Header { number: epoch + 1, mix_hash: vote, ..fork_header() };
+        assert!(PoaConsensus::validate_vote(&header, epoch).is_ok());
+        assert!(PoaConsensus::validate_vote(&fork_header(), epoch).is_ok());
+
+        // Epoch blocks carry no vote, and anything else in mix_hash is malformed.
+        let epoch_vote = Header { number: epoch, ..header.clone() };
+        let malformed = Header { mix_hash: B256::repeat_byte(1), ..header };
+        for header in [epoch_vote, malformed] {
+            assert!(matches!(
+                PoaConsensus::validate_vote(&header, epoch),
+                Err(PoaConsensusError::InvalidVote { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_ommers_hash() {
+        assert!(PoaConsensus::validate_ommers_hash(&fork_header()).is_ok());
+
+        let header = Header { ommers_hash: B256::repeat_byte(1), ..fork_header() };
+        assert!(PoaConsensus::validate_ommers_hash(&header).is_err());
+        let block = reth_ethereum::Block { header, body: BlockBody::default() };
+        let result: Result<(), ConsensusError> =
+            Consensus::<reth_ethereum::Block>::validate_block_pre_execution(
+                &dev_consensus(),
+                &SealedBlock::seal_slow(block),
+            );
+        assert!(result.is_err());
+    }
+
     // =========================================================================
     // Boundary tests
     // =========================================================================
@@ -1232,7 +4557,7 @@ mod tests {
             gas_used: 0,
             timestamp: 12345,
             extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
+            ..fork_header()
         };
 
         // Sign the header
@@ -1293,11 +4618,36 @@ mod tests {
             gas_limit: 30_000_000,
             timestamp: 12345 + block_number * 2,
             extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
+            ..fork_header()
         };
         sealer.seal_header(header, &address).await.unwrap()
     }
 
+    #[test]
+    fn test_estimate_next_signer_round_robin() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let snapshot = Snapshot::genesis(&chain);
+        assert_eq!(snapshot.signers.len(), 3);
+
+        let rotation: Vec<Address> = (1..=9u64)
+            .map(|block| PoaConsensus::estimate_next_signer(&snapshot, block).unwrap())
+            .collect();
+        let [a, b, c] = [snapshot.signers[0], snapshot.signers[1], snapshot.signers[2]];
+        assert_eq!(rotation, vec![b, c, a, b, c, a, b, c, a]);
+        for block in 1..=9u64 {
+            assert_eq!(
+                PoaConsensus::estimate_next_signer(&snapshot, block),
+                chain.expected_signer(block)
+            );
+        }
+
+        let empty = Snapshot {
+            signers: Vec::new(),
+            ..snapshot
+        };
+        assert_eq!(PoaConsensus::estimate_next_signer(&empty, 1), None);
+    }
+
     #[tokio::test]
     async fn test_is_in_turn_block1() {
         // Dev chain has 3 signers: accounts 0, 1, 2
@@ -1331,6 +4681,26 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_expected_signer_matches_is_in_turn() {
+        let consensus = production_consensus();
+        let signers = consensus.chain_spec().signers().to_vec();
+        for block_num in 0u64..6 {
+            let expected = PoaConsensus::expected_signer(block_num, &signers);
+            assert_eq!(expected, signers[(block_num % 3) as usize]);
+            let index = signers.iter().position(|s| *s == expected).unwrap();
+            let header = build_signed_header(block_num, index).await;
+            assert_eq!(consensus.is_in_turn(&header), Some(true));
+        }
+    }
+
+    #[test]
+    fn test_out_of_turn_delay_is_half_period_plus_one() {
+        assert_eq!(PoaConsensus::out_of_turn_delay(0), Duration::from_secs(1));
+        assert_eq!(PoaConsensus::out_of_turn_delay(2), Duration::from_secs(2));
+        assert_eq!(PoaConsensus::out_of_turn_delay(15), Duration::from_secs(8));
+    }
+
     #[tokio::test]
     async fn test_score_chain_all_in_turn() {
         let consensus = production_consensus();
@@ -1458,7 +4828,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + block_num * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
 
             let signed = sealer.seal_header(header, &address).await.unwrap();
@@ -1518,9 +4888,14 @@ mod tests {
         tampered_header.extra_data = extra.into();
 
         let sealed_tampered = SealedHeader::seal_slow(tampered_header);
-        let result: Result<(), ConsensusError> =
-            HeaderValidator::validate_header(&consensus, &sealed_tampered);
-        assert!(result.is_err(), "Tampered block should be rejected");
+        let err = HeaderValidator::validate_header(&consensus, &sealed_tampered).unwrap_err();
+        assert!(
+            matches!(
+                poa_error(&err),
+                PoaConsensusError::InvalidSignature(SealError::RecoveryFailed(_))
+            ),
+            "Tampered block should be rejected, got {err:?}"
+        );
     }
 
     #[tokio::test]
@@ -1566,7 +4941,7 @@ mod tests {
             gas_limit: 30_000_000,
             timestamp: 1002,
             extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
+            ..fork_header()
         };
         let signed = sealer.seal_header(header, &address).await.unwrap();
         let sealed = SealedHeader::seal_slow(signed);
@@ -1629,7 +5004,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + block_num * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
 
             let signed = sealers[signer_idx]
@@ -1669,6 +5044,30 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_backfill_missing_signers() {
+        // 50 headers sealed round-robin by the 3 dev signers.
+        let mut headers = Vec::new();
+        for number in 1..=50u64 {
+            headers.push(build_signed_header(number, (number % 3) as usize).await);
+        }
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let expected: Vec<Address> =
+            (1..=50u64).map(|number| chain.signers()[(number % 3) as usize]).collect();
+        assert_eq!(PoaConsensus::backfill_missing_signers(&headers).unwrap(), expected);
+
+        // Corrupt the seal of block 17.
+        let mut extra_data = headers[16].extra_data.to_vec();
+        let last = extra_data.len() - 1;
+        extra_data[last] = 0xff;
+        headers[16].extra_data = extra_data.into();
+        let err = PoaConsensus::backfill_missing_signers(&headers).unwrap_err();
+        assert!(matches!(
+            poa_error(&err),
+            PoaConsensusError::InvalidSignatureAtBlock(17)
+        ));
+    }
+
     #[tokio::test]
     async fn test_3_signer_out_of_turn_accepted() {
         // When a signer is offline, another signer produces the block out-of-turn
@@ -1706,7 +5105,7 @@ mod tests {
             gas_limit: 30_000_000,
             timestamp: 1002,
             extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
+            ..fork_header()
         };
         let signed = sealer.seal_header(header, &addr).await.unwrap();
         let sealed = SealedHeader::seal_slow(signed);
@@ -1740,7 +5139,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + block_num * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
 
             let mgr = Arc::new(SignerManager::new());
@@ -1812,7 +5211,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + block_num * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
             let signed = sealer.seal_header(header, &addr).await.unwrap();
             let sealed = SealedHeader::seal_slow(signed);
@@ -1950,7 +5349,7 @@ mod tests {
             gas_limit: 30_000_000,
             timestamp: 1002,
             extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
+            ..fork_header()
         };
 
         // Block B at height 1 by signer 0 (different state root)
@@ -1960,7 +5359,7 @@ mod tests {
             timestamp: 1002,
             state_root: B256::from([0x11; 32]),
             extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-            ..Default::default()
+            ..fork_header()
         };
 
         let mgr = Arc::new(SignerManager::new());
@@ -2016,7 +5415,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + i * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
             let signed = sealer.seal_header(header, &addr).await.unwrap();
             let sealed = SealedHeader::seal_slow(signed.clone());
@@ -2038,7 +5437,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + i * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
             let signed = sealer.seal_header(header, &addr).await.unwrap();
             let sealed = SealedHeader::seal_slow(signed.clone());
@@ -2061,7 +5460,7 @@ mod tests {
                 gas_limit: 30_000_000,
                 timestamp: 1000 + i * 2,
                 extra_data: vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH].into(),
-                ..Default::default()
+                ..fork_header()
             };
             let signed = sealer.seal_header(header, &addr).await.unwrap();
             let sealed = SealedHeader::seal_slow(signed.clone());
@@ -0,0 +1,67 @@
+//! Signer set of the parent state.
+//!
+//! An epoch block lists the signers that seal the next epoch. The block producer
+//! writes the SignerRegistry signers of the parent state (the genesis signers while
+//! the registry is empty, see [`epoch_signers`]), so consensus checks the list
+//! against that same state, read through a [`SignerSetReader`]: the node's provider
+//! in production, a map in tests. The node's live signer set is not consulted; it
+//! follows the local head and differs from the parent of a side-chain or historical
+//! block.
+
+use crate::chainspec::PoaChainSpec;
+use crate::onchain::{read_signer_list, StateProviderStorageReader};
+use alloy_primitives::{Address, B256};
+use reth_storage_api::StateProviderFactory;
+use std::fmt;
+use std::sync::Arc;
+
+/// Source of SignerRegistry signers in historical state.
+pub trait SignerSetReader: Send + Sync {
+    /// SignerRegistry signers in the state after block `block_hash` (empty while the
+    /// registry has none); None if that state is unavailable.
+    fn registry_signers(&self, block_hash: B256) -> Option<Vec<Address>>;
+}
+
+impl<P> SignerSetReader for P
+where
+    P: StateProviderFactory + Send + Sync,
+{
+    fn registry_signers(&self, block_hash: B256) -> Option<Vec<Address>> {
+        let state = self.state_by_block_hash(block_hash).ok()?;
+        let list = read_signer_list(&StateProviderStorageReader(state.as_ref()));
+        Some(list.map(|list| list.signers).unwrap_or_default())
+    }
+}
+
+/// Signers an epoch block lists after a parent state whose SignerRegistry holds
+/// `registry`: those, or the genesis signers while the registry is empty.
+pub fn epoch_signers(chain_spec: &PoaChainSpec, registry: Vec<Address>) -> Vec<Address> {
+    if registry.is_empty() {
+        chain_spec.signers().to_vec()
+    } else {
+        registry
+    }
+}
+
+/// Shared [`SignerSetReader`] held by consensus.
+#[derive(Clone)]
+pub struct SignerSetSource(Arc<dyn SignerSetReader>);
+
+impl SignerSetSource {
+    /// Wrap a signer set reader.
+    pub fn new(reader: Arc<dyn SignerSetReader>) -> Self {
+        Self(reader)
+    }
+
+    /// SignerRegistry signers after block `block_hash`.
+    #[inline]
+    pub fn registry_signers(&self, block_hash: B256) -> Option<Vec<Address>> {
+        self.0.registry_signers(block_hash)
+    }
+}
+
+impl fmt::Debug for SignerSetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SignerSetSource(..)")
+    }
+}
@@ -0,0 +1,151 @@
+//! Epoch snapshots persisted to disk.
+//!
+//! The signer set in effect is only known in memory: after a restart the node has
+//! to replay headers (or re-read the registry) before it knows who may seal. With
+//! [`PoaNode::with_automatic_epoch_snapshot`](crate::node::PoaNode::with_automatic_epoch_snapshot)
+//! the node writes a [`Snapshot`] of every canonical epoch block to
+//! `<dir>/<block_hash>.json`, from the canonical chain notifications rather than
+//! header validation: a validated block may still lose a fork choice. At startup the
+//! snapshot of the head's last epoch block is looked up by its canonical hash, so
+//! the file of a side chain or of an older epoch never seeds the live signer set.
+
+use super::{PoaConsensus, Snapshot};
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use reth_primitives_traits::SealedHeader;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Failure to write or read a persisted snapshot.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// The snapshot could not be encoded or decoded as JSON
+    #[error("Snapshot serialization failed: {0}")]
+    SerializationFailed(#[from] serde_json::Error),
+    /// The snapshot directory or file could not be accessed
+    #[error("Snapshot I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Directory of JSON epoch snapshots named by block hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Store snapshots in `dir`, created on the first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The snapshot directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File holding the snapshot of block `hash`.
+    pub fn path(&self, hash: B256) -> PathBuf {
+        self.dir.join(format!("{hash}.json"))
+    }
+
+    /// Write `snapshot` to `<dir>/<hash>.json`, replacing any previous file.
+    ///
+    /// Written to a temporary file first, so a crash never leaves a truncated
+    /// snapshot behind.
+    pub fn save(&self, snapshot: &Snapshot) -> Result<PathBuf, SnapshotError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(snapshot.hash);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(snapshot)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+
+    /// Write the snapshot of canonical block `header` if it is an epoch block.
+    ///
+    /// Called from the canonical chain listener, off the validation path; a failure
+    /// is logged and the next epoch block is written regardless.
+    pub fn record_canonical_block(&self, consensus: &PoaConsensus, header: &SealedHeader<Header>) {
+        let result = match consensus.epoch_snapshot(header) {
+            Ok(Some(snapshot)) => self.save(&snapshot).map_err(|err| err.to_string()),
+            Ok(None) => return,
+            Err(err) => Err(err.to_string()),
+        };
+        match result {
+            Ok(path) => debug!(
+                target: "poa::consensus",
+                path = %path.display(),
+                "Persisted epoch snapshot"
+            ),
+            Err(err) => warn!(target: "poa::consensus", %err, "Failed to persist epoch snapshot"),
+        }
+    }
+
+    /// Snapshot of block `hash`; None if none was written for it.
+    pub fn load(&self, hash: B256) -> Result<Option<Snapshot>, SnapshotError> {
+        match fs::read(self.path(hash)) {
+            Ok(json) => Ok(Some(serde_json::from_slice(&json)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    fn temp_store(name: &str) -> SnapshotStore {
+        let dir = std::env::temp_dir().join(format!("poa-snapshots-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        SnapshotStore::new(dir)
+    }
+
+    fn snapshot(number: u64) -> Snapshot {
+        Snapshot {
+            number,
+            hash: B256::with_last_byte(number as u8),
+            signers: vec![Address::repeat_byte(1), Address::repeat_byte(2)],
+        }
+    }
+
+    #[test]
+    fn test_snapshots_are_keyed_by_hash() {
+        let store = temp_store("by-hash");
+        assert_eq!(store.load(B256::with_last_byte(30)).unwrap(), None);
+
+        for number in [30, 60] {
+            store.save(&snapshot(number)).unwrap();
+        }
+        // Same number, another fork: a separate file.
+        let fork = Snapshot {
+            hash: B256::repeat_byte(0xfe),
+            ..snapshot(60)
+        };
+        store.save(&fork).unwrap();
+        assert_eq!(
+            store.load(B256::with_last_byte(60)).unwrap(),
+            Some(snapshot(60))
+        );
+        assert_eq!(store.load(fork.hash).unwrap(), Some(fork));
+        assert_eq!(store.load(B256::with_last_byte(90)).unwrap(), None);
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_snapshot_is_serialization_error() {
+        let store = temp_store("corrupt");
+        fs::create_dir_all(store.dir()).unwrap();
+        let hash = B256::with_last_byte(30);
+        fs::write(store.path(hash), "{").unwrap();
+        assert!(matches!(
+            store.load(hash),
+            Err(SnapshotError::SerializationFailed(_))
+        ));
+        fs::remove_dir_all(store.dir()).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+//! Minimum signer stake.
+//!
+//! With [`PoaChainSpec::with_min_signer_stake`](crate::chainspec::PoaChainSpec::with_min_signer_stake)
+//! a block is only valid if its sealer holds at least the stake in the parent
+//! state. Consensus has no state of its own, so balances come from a
+//! [`SignerStakeReader`]: the node's provider in production, a map in tests. The
+//! balance is read from committed state, never by executing anything.
+//!
+//! The check runs before execution, when the parent state must exist: a block whose
+//! parent state is unavailable is refused rather than let through. Headers synced
+//! ahead of execution are not checked.
+
+use alloy_primitives::{Address, B256, U256};
+use reth_storage_api::StateProviderFactory;
+use std::fmt;
+use std::sync::Arc;
+
+/// Source of signer balances in historical state.
+pub trait SignerStakeReader: Send + Sync {
+    /// Balance of `signer` in the state after block `block_hash`; None if that
+    /// state is unavailable (e.g. pruned or not yet synced).
+    fn stake_at(&self, block_hash: B256, signer: Address) -> Option<U256>;
+}
+
+impl<P> SignerStakeReader for P
+where
+    P: StateProviderFactory + Send + Sync,
+{
+    fn stake_at(&self, block_hash: B256, signer: Address) -> Option<U256> {
+        let state = self.state_by_block_hash(block_hash).ok()?;
+        Some(state.account_balance(&signer).ok()?.unwrap_or_default())
+    }
+}
+
+/// Shared [`SignerStakeReader`] held by consensus.
+#[derive(Clone)]
+pub struct StakeSource(Arc<dyn SignerStakeReader>);
+
+impl StakeSource {
+    /// Wrap a stake reader.
+    pub fn new(reader: Arc<dyn SignerStakeReader>) -> Self {
+        Self(reader)
+    }
+
+    /// Balance of `signer` after block `block_hash`.
+    #[inline]
+    pub fn stake_at(&self, block_hash: B256, signer: Address) -> Option<U256> {
+        self.0.stake_at(block_hash, signer)
+    }
+}
+
+impl fmt::Debug for StakeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StakeSource(..)")
+    }
+}
@@ -0,0 +1,52 @@
+//! Sets of EIP-2718 transaction types.
+//!
+//! Blocks are checked against the types allowed at their number and timestamp, so
+//! the set is built once per block; [`TxTypes`] keeps it in a single word instead of
+//! a heap-allocated set.
+
+/// Set of EIP-2718 transaction types (`0x00..=0x7f`), one bit per type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxTypes(u128);
+
+impl TxTypes {
+    /// Bit of type `ty`; None outside the EIP-2718 range.
+    #[inline]
+    fn bit(ty: u8) -> Option<u128> {
+        1u128.checked_shl(ty.into())
+    }
+
+    /// Add type `ty`; types outside the EIP-2718 range are ignored.
+    #[inline]
+    pub fn insert(&mut self, ty: u8) {
+        self.0 |= Self::bit(ty).unwrap_or_default();
+    }
+
+    /// Whether type `ty` is in the set.
+    #[inline]
+    pub fn contains(self, ty: u8) -> bool {
+        Self::bit(ty).is_some_and(|bit| self.0 & bit != 0)
+    }
+}
+
+impl FromIterator<u8> for TxTypes {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut types = Self::default();
+        for ty in iter {
+            types.insert(ty);
+        }
+        types
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tx_types_membership() {
+        let types = TxTypes::from_iter([0, 2, 0x7f, 0x80, 0xff]);
+        assert!(types.contains(0) && types.contains(2) && types.contains(0x7f));
+        assert!(!types.contains(1) && !types.contains(0x80) && !types.contains(0xff));
+        assert_eq!(types, TxTypes::from_iter([2, 0x7f, 0]));
+    }
+}
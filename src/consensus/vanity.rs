@@ -0,0 +1,143 @@
+//! Extra-data vanity policy.
+//!
+//! The first [`EXTRA_VANITY_LENGTH`] bytes of `extra_data` are free-form. Sealers
+//! conventionally put a client identifier there and block explorers render it
+//! verbatim, so a vanity carrying ANSI escapes or look-alike unicode is a display
+//! spoofing vector.
+//!
+//! - [`client_vanity`] builds the vanity our payload builder writes: an ASCII client
+//!   tag, truncated or zero-padded to the vanity length.
+//! - [`invalid_vanity_byte`] is the strict-mode check used by `PoaConsensus`: the
+//!   vanity must be printable ASCII (`0x20..=0x7e`) followed only by zero padding.
+//! - [`decode_vanity`] renders any vanity as a safe string for RPC.
+
+use super::EXTRA_VANITY_LENGTH;
+
+/// Client tag written into the vanity when none is configured.
+pub fn default_client_tag() -> String {
+    format!("meowchain/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Whether `byte` is printable ASCII (space through `~`).
+#[inline]
+pub fn is_printable_ascii(byte: u8) -> bool {
+    (0x20..=0x7e).contains(&byte)
+}
+
+/// Encode a client tag as a vanity.
+///
+/// Tags longer than [`EXTRA_VANITY_LENGTH`] are truncated, shorter ones are
+/// zero-padded. Bytes outside printable ASCII are replaced with `?` so the
+/// builder never emits a vanity that strict-mode peers would reject.
+pub fn client_vanity(tag: &str) -> [u8; EXTRA_VANITY_LENGTH] {
+    let mut vanity = [0u8; EXTRA_VANITY_LENGTH];
+    for (slot, byte) in vanity.iter_mut().zip(tag.bytes()) {
+        *slot = if is_printable_ascii(byte) { byte } else { b'?' };
+    }
+    vanity
+}
+
+/// First byte violating the strict vanity policy, as `(offset, byte)`.
+///
+/// Trailing zero bytes are padding; every byte before them must be printable
+/// ASCII (so an interior NUL is rejected too). Only the first
+/// [`EXTRA_VANITY_LENGTH`] bytes of `extra_data` are inspected.
+pub fn invalid_vanity_byte(extra_data: &[u8]) -> Option<(usize, u8)> {
+    let vanity = &extra_data[..extra_data.len().min(EXTRA_VANITY_LENGTH)];
+    let text_len = vanity.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    vanity[..text_len]
+        .iter()
+        .position(|&b| !is_printable_ascii(b))
+        .map(|i| (i, vanity[i]))
+}
+
+/// Render the vanity of `extra_data` for display.
+///
+/// Zero padding is stripped and any remaining non-printable byte is escaped
+/// as `\xNN`, so the result is always plain printable ASCII.
+pub fn decode_vanity(extra_data: &[u8]) -> String {
+    let vanity = &extra_data[..extra_data.len().min(EXTRA_VANITY_LENGTH)];
+    let text_len = vanity.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    let mut out = String::with_capacity(text_len);
+    for &byte in &vanity[..text_len] {
+        if is_printable_ascii(byte) && byte != b'\\' {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_tag_is_zero_padded() {
+        let vanity = client_vanity("meowchain/1.0");
+        assert_eq!(&vanity[..13], b"meowchain/1.0");
+        assert!(vanity[13..].iter().all(|&b| b == 0));
+        assert_eq!(decode_vanity(&vanity), "meowchain/1.0");
+    }
+
+    #[test]
+    fn test_long_tag_is_truncated() {
+        let tag = "meowchain/1.0.0-very-long-build-identifier+abcdef";
+        let vanity = client_vanity(tag);
+        assert_eq!(vanity.len(), EXTRA_VANITY_LENGTH);
+        assert_eq!(&vanity[..], &tag.as_bytes()[..EXTRA_VANITY_LENGTH]);
+        assert!(invalid_vanity_byte(&vanity).is_none());
+    }
+
+    #[test]
+    fn test_tag_control_characters_are_replaced() {
+        let vanity = client_vanity("evil\x1b[31mred");
+        assert_eq!(&vanity[..12], b"evil?[31mred");
+        assert!(invalid_vanity_byte(&vanity).is_none());
+
+        let unicode = client_vanity("caf\u{e9}");
+        assert_eq!(&unicode[..5], b"caf??");
+    }
+
+    #[test]
+    fn test_default_tag_is_valid_vanity() {
+        let tag = default_client_tag();
+        assert!(tag.starts_with("meowchain/"));
+        assert!(invalid_vanity_byte(&client_vanity(&tag)).is_none());
+    }
+
+    #[test]
+    fn test_invalid_vanity_byte_detection() {
+        assert_eq!(invalid_vanity_byte(&[0u8; 32]), None);
+
+        let mut ansi = [0u8; 32];
+        ansi[..9].copy_from_slice(b"ok\x1b[2Jbad");
+        assert_eq!(invalid_vanity_byte(&ansi), Some((2, 0x1b)));
+
+        let mut interior_nul = [0u8; 32];
+        interior_nul[..3].copy_from_slice(b"a\0b");
+        assert_eq!(invalid_vanity_byte(&interior_nul), Some((1, 0)));
+
+        let mut high = [0u8; 32];
+        high[..2].copy_from_slice(&[b'x', 0xc3]);
+        assert_eq!(invalid_vanity_byte(&high), Some((1, 0xc3)));
+    }
+
+    #[test]
+    fn test_only_vanity_prefix_is_inspected() {
+        // Signer list / seal bytes after the vanity are arbitrary binary.
+        let mut extra = client_vanity("meowchain").to_vec();
+        extra.extend_from_slice(&[0xff; 65]);
+        assert!(invalid_vanity_byte(&extra).is_none());
+        assert_eq!(decode_vanity(&extra), "meowchain");
+    }
+
+    #[test]
+    fn test_decode_escapes_non_printable() {
+        let mut vanity = [0u8; 32];
+        vanity[..6].copy_from_slice(b"a\x1bb\\c\x7f");
+        assert_eq!(decode_vanity(&vanity), "a\\x1bb\\x5cc\\x7f");
+        assert_eq!(decode_vanity(&[]), "");
+    }
+}
@@ -0,0 +1,470 @@
+//! Signer-set votes awaiting quorum.
+//!
+//! Authorized signers vote to add a candidate to the signer set or remove a member
+//! from it (Clique-style: one vote per signer per candidate, the latest vote wins).
+//! A vote travels in the header of a block its signer seals: the `mix_hash`
+//! (`prevRandao`, unused by proof of authority) holds the candidate and the vote
+//! direction, see [`encode_vote`]. Every node tallies the votes of its canonical
+//! blocks since the last epoch block, so all nodes report the same [`VoterSet`].
+//!
+//! A proposal is finalized once more than half of the current signers back it; it
+//! then leaves the tally and the change is carried out through the on-chain
+//! SignerRegistry. Everything below quorum is reported by
+//! [`PoaNode::list_pending_votes`](crate::node::PoaNode::list_pending_votes) and
+//! `poa_listPendingVotes`.
+//!
+//! `clique_propose` and `clique_discard` manage the proposals the local signers put
+//! into the blocks they seal; a proposal only counts once it is in a canonical block.
+
+use super::PoaConsensusError;
+use alloy_consensus::Header;
+use alloy_primitives::{Address, B256};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Direction of a signer-set vote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteType {
+    /// Authorize the candidate as a signer
+    Add,
+    /// Deauthorize an existing signer
+    Remove,
+}
+
+impl VoteType {
+    /// The only action that makes sense for `candidate` under `signers`.
+    #[inline]
+    pub fn for_candidate(candidate: &Address, signers: &[Address]) -> Self {
+        if signers.contains(candidate) {
+            Self::Remove
+        } else {
+            Self::Add
+        }
+    }
+}
+
+/// A proposal that has not reached quorum yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingVote {
+    /// Address being added or removed
+    pub candidate: Address,
+    /// Change proposed for the candidate
+    pub action: VoteType,
+    /// Signers voting for the change, sorted
+    pub votes_for: Vec<Address>,
+    /// Number of signers voting the opposite way
+    pub votes_against: usize,
+    /// Votes needed to finalize: more than half of the current signers
+    pub quorum: usize,
+}
+
+/// Last `mix_hash` byte of a vote to add the candidate.
+const VOTE_ADD: u8 = 0x01;
+/// Last `mix_hash` byte of a vote to remove the candidate.
+const VOTE_REMOVE: u8 = 0x02;
+
+/// Votes needed to change a signer set of `signer_count` members.
+#[inline]
+pub fn quorum(signer_count: usize) -> usize {
+    signer_count / 2 + 1
+}
+
+/// Header `mix_hash` casting a vote: the candidate, 11 zero bytes, then the direction.
+pub fn encode_vote(candidate: Address, action: VoteType) -> B256 {
+    let mut mix_hash = B256::ZERO;
+    mix_hash[..Address::len_bytes()].copy_from_slice(candidate.as_slice());
+    mix_hash[31] = match action {
+        VoteType::Add => VOTE_ADD,
+        VoteType::Remove => VOTE_REMOVE,
+    };
+    mix_hash
+}
+
+/// The vote carried by a header `mix_hash`; `None` for a zero `mix_hash` (no vote).
+pub fn decode_vote(mix_hash: &B256) -> Result<Option<(Address, VoteType)>, PoaConsensusError> {
+    if mix_hash.is_zero() {
+        return Ok(None);
+    }
+    let invalid = || PoaConsensusError::InvalidVote {
+        mix_hash: *mix_hash,
+    };
+    let action = match mix_hash[31] {
+        VOTE_ADD => VoteType::Add,
+        VOTE_REMOVE => VoteType::Remove,
+        _ => return Err(invalid()),
+    };
+    let candidate = Address::from_slice(&mix_hash[..Address::len_bytes()]);
+    if candidate.is_zero() || mix_hash[Address::len_bytes()..31].iter().any(|&b| b != 0) {
+        return Err(invalid());
+    }
+    Ok(Some((candidate, action)))
+}
+
+#[derive(Debug, Default)]
+struct Votes {
+    /// Canonical votes since the last epoch block: candidate → (voter → vote).
+    tally: HashMap<Address, HashMap<Address, VoteType>>,
+    /// Votes the local signers put into the blocks they seal.
+    proposals: HashMap<Address, VoteType>,
+}
+
+/// Signer-set votes of the canonical chain, and the local proposals.
+///
+/// Cheap to clone; all clones share the same state, so the payload builder, the
+/// canonical block tally and the RPC handlers observe the same votes.
+#[derive(Debug, Clone, Default)]
+pub struct VoterSet(Arc<RwLock<Votes>>);
+
+impl VoterSet {
+    /// Create an empty tally.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `voter`'s vote on `candidate` under the current `signers`.
+    ///
+    /// Votes from non-signers and votes that do not fit the candidate (adding an
+    /// existing signer, removing a non-signer) are ignored. Returns the action once
+    /// the vote reaches quorum; the proposal is then removed from the tally and from
+    /// the local proposals.
+    pub fn cast(
+        &self,
+        signers: &[Address],
+        voter: Address,
+        candidate: Address,
+        action: VoteType,
+    ) -> Option<VoteType> {
+        if !signers.contains(&voter) || VoteType::for_candidate(&candidate, signers) != action {
+            return None;
+        }
+        let mut votes = self.0.write().unwrap_or_else(|e| e.into_inner());
+        let ballots = votes.tally.entry(candidate).or_default();
+        ballots.insert(voter, action);
+        let support = ballots
+            .iter()
+            .filter(|(voter, vote)| **vote == action && signers.contains(voter))
+            .count();
+        if support >= quorum(signers.len()) {
+            votes.tally.remove(&candidate);
+            votes.proposals.remove(&candidate);
+            Some(action)
+        } else {
+            None
+        }
+    }
+
+    /// Tally canonical block `header` sealed by `signer`.
+    ///
+    /// Epoch blocks carry no vote and reset the tally, as in Clique. Returns the
+    /// action once the header's vote reaches quorum.
+    pub fn apply_header(
+        &self,
+        epoch: u64,
+        header: &Header,
+        signer: Address,
+        signers: &[Address],
+    ) -> Option<VoteType> {
+        if header.number.is_multiple_of(epoch) {
+            self.reset();
+            return None;
+        }
+        let (candidate, action) = decode_vote(&header.mix_hash).ok().flatten()?;
+        self.cast(signers, signer, candidate, action)
+    }
+
+    /// Forget every canonical vote, e.g. before re-tallying after a reorg.
+    pub fn reset(&self) {
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .tally
+            .clear();
+    }
+
+    /// Have the local signers vote `action` on `candidate` in the blocks they seal.
+    pub fn propose(&self, candidate: Address, action: VoteType) {
+        let mut votes = self.0.write().unwrap_or_else(|e| e.into_inner());
+        votes.proposals.insert(candidate, action);
+    }
+
+    /// Stop voting on `candidate`. Votes already sealed stay in the tally.
+    pub fn discard(&self, candidate: &Address) {
+        let mut votes = self.0.write().unwrap_or_else(|e| e.into_inner());
+        votes.proposals.remove(candidate);
+    }
+
+    /// The local proposals: candidate → action.
+    pub fn proposals(&self) -> HashMap<Address, VoteType> {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .proposals
+            .clone()
+    }
+
+    /// The vote `voter` seals next: the first proposal, by candidate, that fits
+    /// `signers` and that `voter` has not cast yet.
+    pub fn next_vote(&self, voter: &Address, signers: &[Address]) -> Option<(Address, VoteType)> {
+        let votes = self.0.read().unwrap_or_else(|e| e.into_inner());
+        votes
+            .proposals
+            .iter()
+            .filter(|(candidate, action)| {
+                VoteType::for_candidate(candidate, signers) == **action
+                    && votes.tally.get(*candidate).and_then(|b| b.get(voter)) != Some(*action)
+            })
+            .min_by_key(|(candidate, _)| **candidate)
+            .map(|(candidate, action)| (*candidate, *action))
+    }
+
+    /// Proposals below quorum under `signers`, sorted by candidate.
+    ///
+    /// Only votes from current signers count, so a signer removed since voting no
+    /// longer contributes.
+    pub fn pending(&self, signers: &[Address]) -> Vec<PendingVote> {
+        let votes = self.0.read().unwrap_or_else(|e| e.into_inner());
+        let quorum = quorum(signers.len());
+        let mut pending: Vec<PendingVote> = votes
+            .tally
+            .iter()
+            .filter_map(|(candidate, ballots)| {
+                let action = VoteType::for_candidate(candidate, signers);
+                let mut votes_for = Vec::new();
+                let mut votes_against = 0;
+                for (voter, vote) in ballots.iter().filter(|(v, _)| signers.contains(v)) {
+                    if *vote == action {
+                        votes_for.push(*voter);
+                    } else {
+                        votes_against += 1;
+                    }
+                }
+                if votes_for.is_empty() && votes_against == 0 {
+                    return None;
+                }
+                votes_for.sort();
+                Some(PendingVote {
+                    candidate: *candidate,
+                    action,
+                    votes_for,
+                    votes_against,
+                    quorum,
+                })
+            })
+            .collect();
+        pending.sort_by_key(|vote| vote.candidate);
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signers(n: u8) -> Vec<Address> {
+        (1..=n).map(Address::with_last_byte).collect()
+    }
+
+    #[test]
+    fn test_two_votes_below_quorum_are_pending() {
+        let signers = signers(5);
+        let candidate = Address::with_last_byte(0x42);
+        let votes = VoterSet::new();
+
+        assert_eq!(
+            votes.cast(&signers, signers[0], candidate, VoteType::Add),
+            None
+        );
+        assert_eq!(
+            votes.cast(&signers, signers[1], candidate, VoteType::Add),
+            None
+        );
+
+        let pending = votes.pending(&signers);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].candidate, candidate);
+        assert_eq!(pending[0].action, VoteType::Add);
+        assert_eq!(pending[0].votes_for, vec![signers[0], signers[1]]);
+        assert_eq!(pending[0].votes_against, 0);
+        assert_eq!(pending[0].quorum, 3);
+    }
+
+    #[test]
+    fn test_quorum_finalizes_and_clears() {
+        let signers = signers(5);
+        let candidate = Address::with_last_byte(0x42);
+        let votes = VoterSet::new();
+        for voter in &signers[..2] {
+            votes.cast(&signers, *voter, candidate, VoteType::Add);
+        }
+        assert_eq!(
+            votes.cast(&signers, signers[2], candidate, VoteType::Add),
+            Some(VoteType::Add)
+        );
+        assert!(votes.pending(&signers).is_empty());
+    }
+
+    #[test]
+    fn test_repeat_vote_counts_once() {
+        let signers = signers(3);
+        let candidate = Address::with_last_byte(0x42);
+        let votes = VoterSet::new();
+        votes.cast(&signers, signers[0], candidate, VoteType::Add);
+        assert_eq!(
+            votes.cast(&signers, signers[0], candidate, VoteType::Add),
+            None
+        );
+        assert_eq!(votes.pending(&signers)[0].votes_for.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_votes_are_ignored() {
+        let signers = signers(3);
+        let votes = VoterSet::new();
+        // Outsider voting, adding an existing signer, removing a non-signer.
+        votes.cast(
+            &signers,
+            Address::with_last_byte(0x99),
+            signers[0],
+            VoteType::Remove,
+        );
+        votes.cast(&signers, signers[0], signers[1], VoteType::Add);
+        votes.cast(
+            &signers,
+            signers[0],
+            Address::with_last_byte(0x42),
+            VoteType::Remove,
+        );
+        assert!(votes.pending(&signers).is_empty());
+    }
+
+    #[test]
+    fn test_votes_against_after_membership_change() {
+        let before = signers(3);
+        let candidate = Address::with_last_byte(0x42);
+        let votes = VoterSet::new();
+        votes.cast(&before, before[0], candidate, VoteType::Add);
+
+        // The candidate joined through governance; an Add ballot now opposes removal.
+        let mut after = before.clone();
+        after.push(candidate);
+        votes.cast(&after, after[1], candidate, VoteType::Remove);
+        let pending = votes.pending(&after);
+        assert_eq!(pending[0].action, VoteType::Remove);
+        assert_eq!(pending[0].votes_for, vec![after[1]]);
+        assert_eq!(pending[0].votes_against, 1);
+        assert_eq!(pending[0].quorum, 3);
+    }
+
+    #[test]
+    fn test_vote_round_trips_through_mix_hash() {
+        let candidate = Address::with_last_byte(0x42);
+        for action in [VoteType::Add, VoteType::Remove] {
+            let mix_hash = encode_vote(candidate, action);
+            assert_eq!(decode_vote(&mix_hash).unwrap(), Some((candidate, action)));
+        }
+        assert_eq!(decode_vote(&B256::ZERO).unwrap(), None);
+
+        let mut bad_kind = encode_vote(candidate, VoteType::Add);
+        bad_kind[31] = 3;
+        let mut bad_padding = encode_vote(candidate, VoteType::Add);
+        bad_padding[25] = 1;
+        for mix_hash in [
+            bad_kind,
+            bad_padding,
+            encode_vote(Address::ZERO, VoteType::Add),
+        ] {
+            assert!(matches!(
+                decode_vote(&mix_hash),
+                Err(PoaConsensusError::InvalidVote { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_apply_header_tallies_until_epoch() {
+        let signers = signers(5);
+        let candidate = Address::with_last_byte(0x42);
+        let votes = VoterSet::new();
+        let header = |number| Header {
+            number,
+            mix_hash: encode_vote(candidate, VoteType::Add),
+            ..Default::default()
+        };
+
+        votes.apply_header(10, &header(11), signers[0], &signers);
+        votes.apply_header(10, &header(12), signers[1], &signers);
+        assert_eq!(
+            votes.pending(&signers)[0].votes_for,
+            vec![signers[0], signers[1]]
+        );
+
+        // The epoch block starts a new tally.
+        let epoch = Header {
+            number: 20,
+            ..Default::default()
+        };
+        votes.apply_header(10, &epoch, signers[2], &signers);
+        assert!(votes.pending(&signers).is_empty());
+    }
+
+    #[test]
+    fn test_next_vote_skips_cast_and_stale_proposals() {
+        let signers = signers(5);
+        let (first, second) = (Address::with_last_byte(0x41), Address::with_last_byte(0x42));
+        let votes = VoterSet::new();
+        votes.propose(second, VoteType::Add);
+        votes.propose(first, VoteType::Add);
+        // Removing a non-signer can never pass.
+        votes.propose(Address::with_last_byte(0x40), VoteType::Remove);
+
+        assert_eq!(
+            votes.next_vote(&signers[0], &signers),
+            Some((first, VoteType::Add))
+        );
+        votes.cast(&signers, signers[0], first, VoteType::Add);
+        assert_eq!(
+            votes.next_vote(&signers[0], &signers),
+            Some((second, VoteType::Add))
+        );
+        assert_eq!(
+            votes.next_vote(&signers[1], &signers),
+            Some((first, VoteType::Add))
+        );
+
+        votes.discard(&second);
+        votes.cast(&signers, signers[0], second, VoteType::Add);
+        assert_eq!(votes.next_vote(&signers[0], &signers), None);
+    }
+
+    #[test]
+    fn test_quorum_drops_local_proposal() {
+        let signers = signers(3);
+        let candidate = Address::with_last_byte(0x42);
+        let votes = VoterSet::new();
+        votes.propose(candidate, VoteType::Add);
+        votes.cast(&signers, signers[0], candidate, VoteType::Add);
+        assert_eq!(
+            votes.cast(&signers, signers[1], candidate, VoteType::Add),
+            Some(VoteType::Add)
+        );
+        assert!(votes.proposals().is_empty());
+    }
+
+    #[test]
+    fn test_pending_vote_json() {
+        let vote = PendingVote {
+            candidate: Address::with_last_byte(0x42),
+            action: VoteType::Add,
+            votes_for: vec![Address::with_last_byte(1)],
+            votes_against: 0,
+            quorum: 2,
+        };
+        let json = serde_json::to_value(&vote).unwrap();
+        assert_eq!(json["action"], "add");
+        assert_eq!(json["votesFor"].as_array().unwrap().len(), 1);
+        assert_eq!(json["votesAgainst"], 0);
+    }
+}
@@ -9,3 +9,7 @@ pub const ADDRESS_LENGTH: usize = 20;
 pub const DEFAULT_CHAIN_ID: u64 = 9323310;
 /// Default epoch length (blocks between signer list snapshots)
 pub const DEFAULT_EPOCH: u64 = 30000;
+/// Minimum block gas limit accepted by consensus (matches geth's `MinGasLimit`)
+pub const MIN_GAS_LIMIT: u64 = 5000;
+/// Maximum block gas limit accepted by consensus (2^63 - 1, matches geth's `MaxGasLimit`)
+pub const MAX_GAS_LIMIT: u64 = i64::MAX as u64;
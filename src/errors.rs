@@ -1,2 +1,3 @@
 pub use crate::consensus::PoaConsensusError;
-pub use crate::signer::SignerError;
+pub use crate::node::NodeError;
+pub use crate::signer::{SealError, SignerError};
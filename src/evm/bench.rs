@@ -17,20 +17,26 @@
 //! | `test_evm_keccak_heavy` | SHA3 / KECCAK256 intensive workload |
 //! | `test_evm_calldata_discount` | Calldata gas reduction (4 vs 16 gas/byte) |
 //! | `test_evm_max_contract_size` | Configurable contract size limit |
+//! | `test_evm_warm_coinbase` | Gas saved by pre-warming the coinbase |
+//! | `test_evm_bls12_381_pairing` | BLS12-381 pairing (EIP-2537) vs ECPAIRING throughput |
+//! | `test_evm_staticcall_vs_call_overhead` | STATICCALL vs CALL gas + read-only enforcement |
 //! | `test_parallel_schedule_throughput` | ParallelSchedule batch scheduling perf |
-//! | `test_conflict_detection_performance` | ConflictDetector mixed access patterns |
+//! | `test_conflict_detection_performance` | ConflictDetector mixed access patterns + transfer fast path |
 
 #[cfg(test)]
 mod tests {
-    use crate::evm::parallel::{ConflictDetector, ParallelSchedule, TxAccessRecord};
+    use crate::evm::parallel::{AccessKey, ConflictDetector, ParallelSchedule, TxAccessRecord};
     use crate::evm::{CalldataDiscountInspector, PoaEvmFactory};
+    use alloy_evm::eth::EthEvmFactory;
     use alloy_evm::revm::bytecode::Bytecode;
     use alloy_evm::revm::context::{BlockEnv, TxEnv};
     use alloy_evm::revm::database_interface::DBErrorMarker;
     use alloy_evm::revm::inspector::NoOpInspector;
+    use alloy_evm::revm::interpreter::{CallInputs, CallOutcome, CallScheme, InstructionResult};
     use alloy_evm::revm::primitives::hardfork::SpecId;
     use alloy_evm::revm::primitives::TxKind;
-    use alloy_evm::revm::state::AccountInfo;
+    use alloy_evm::revm::state::{Account, AccountInfo};
+    use alloy_evm::revm::Inspector;
     use alloy_evm::{Evm, EvmEnv, EvmFactory};
     use alloy_primitives::{Address, Bytes, B256, U256};
     use std::time::Instant;
@@ -61,6 +67,9 @@ mod tests {
         sender_balance: U256,
         /// Address of the sender account.
         sender_addr: Address,
+        /// Accounts written by committed transactions; consulted before the
+        /// static sender/contract fixtures so later txs observe earlier writes.
+        accounts: std::collections::HashMap<Address, AccountInfo>,
     }
 
     impl BenchDb {
@@ -78,6 +87,17 @@ mod tests {
             self
         }
 
+        /// Commit the state returned by `transact` so subsequent transactions see it.
+        fn apply(&mut self, changes: impl IntoIterator<Item = (Address, Account)>) {
+            for (address, account) in changes {
+                if address == self.contract_addr {
+                    for (slot, value) in account.storage {
+                        self.storage.insert(slot, value.present_value);
+                    }
+                }
+                self.accounts.insert(address, account.info);
+            }
+        }
     }
 
     /// Minimal error type for the benchmark DB.
@@ -92,7 +112,9 @@ mod tests {
         type Error = BenchDbError;
 
         fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
-            if address == self.sender_addr {
+            if let Some(info) = self.accounts.get(&address) {
+                Ok(Some(info.clone()))
+            } else if address == self.sender_addr {
                 Ok(Some(AccountInfo {
                     balance: self.sender_balance,
                     nonce: 0,
@@ -433,6 +455,98 @@ mod tests {
         Bytes::from(code)
     }
 
+    /// Iterations of the STATICCALL / CALL loops.
+    const CALL_LOOP_ITERATIONS: u16 = 500;
+
+    const CALL: u8 = 0xF1;
+    const STATICCALL: u8 = 0xFA;
+
+    /// EVM bytecode: loop `iterations` times calling `target` with `opcode`
+    /// (CALL or STATICCALL, no calldata, all remaining gas).
+    ///
+    /// The callee's 32-byte return value lands at memory[0] and the call's
+    /// success flag is stored at memory[32]; both words of the last iteration
+    /// are returned.
+    fn call_loop_bytecode(opcode: u8, target: Address, iterations: u16) -> Bytes {
+        let iter_hi = (iterations >> 8) as u8;
+        let iter_lo = (iterations & 0xFF) as u8;
+        let mut code = vec![
+            0x60, 0x00, // PUSH1 0  (counter = 0)
+            0x5B, // JUMPDEST (offset 2)
+            0x60, 0x20, // PUSH1 32 (retSize)
+            0x60, 0x00, // PUSH1 0  (retOffset)
+            0x60, 0x00, // PUSH1 0  (argsSize)
+            0x60, 0x00, // PUSH1 0  (argsOffset)
+        ];
+        if opcode == CALL {
+            code.extend([0x60, 0x00]); // PUSH1 0  (value)
+        }
+        code.push(0x73); // PUSH20 target
+        code.extend_from_slice(target.as_slice());
+        code.extend([
+            0x5A,   // GAS
+            opcode, // CALL / STATICCALL
+            0x60, 0x20, // PUSH1 32
+            0x52, // MSTORE    mem[32] = success
+            0x60, 0x01, // PUSH1 1
+            0x01, // ADD       (counter++)
+            0x80, // DUP1
+            0x61, iter_hi, iter_lo, // PUSH2 iterations
+            0x10,    // LT
+            0x60, 0x02, // PUSH1 2
+            0x57, // JUMPI
+            0x60, 0x40, // PUSH1 64
+            0x60, 0x00, // PUSH1 0
+            0xF3, // RETURN    mem[0..64]
+        ]);
+        Bytes::from(code)
+    }
+
+    /// EVM bytecode: STATICCALL `target` (a read-only contract) in a loop,
+    /// keeping its return value.
+    fn make_staticcall_bytecode(target: Address) -> Bytes {
+        call_loop_bytecode(STATICCALL, target, CALL_LOOP_ITERATIONS)
+    }
+
+    /// Same loop as [`make_staticcall_bytecode`] with a plain CALL.
+    fn make_call_bytecode(target: Address) -> Bytes {
+        call_loop_bytecode(CALL, target, CALL_LOOP_ITERATIONS)
+    }
+
+    /// Read-only callee: returns 42 * 7 as a 32-byte word.
+    fn pure_target_bytecode() -> Bytes {
+        Bytes::from_static(&[
+            0x60, 0x2A, // PUSH1 42
+            0x60, 0x07, // PUSH1 7
+            0x02, // MUL
+            0x60, 0x00, // PUSH1 0
+            0x52, // MSTORE
+            0x60, 0x20, // PUSH1 32
+            0x60, 0x00, // PUSH1 0
+            0xF3, // RETURN
+        ])
+    }
+
+    /// State-changing callee: writes storage slot 0.
+    fn sstore_target_bytecode() -> Bytes {
+        Bytes::from_static(&[
+            0x60, 0x01, // PUSH1 1
+            0x60, 0x00, // PUSH1 0
+            0x55, // SSTORE
+            0x00, // STOP
+        ])
+    }
+
+    /// Records the scheme and result of every finished call frame, innermost first.
+    #[derive(Debug, Default)]
+    struct CallResults(Vec<(CallScheme, InstructionResult)>);
+
+    impl<CTX> Inspector<CTX> for CallResults {
+        fn call_end(&mut self, _: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+            self.0.push((inputs.scheme, *outcome.instruction_result()));
+        }
+    }
+
     // =====================================================================
     //  Benchmark Tests
     // =====================================================================
@@ -441,15 +555,17 @@ mod tests {
 
     #[test]
     fn test_evm_simple_transfer() {
-        // Measure the time to create an EVM and execute a simple value transfer.
+        // Measure the time to execute a simple value transfer, comparing the old
+        // clone-the-DB-and-rebuild-the-EVM-per-tx pattern with a single reused EVM.
         // Reference: ~0.5-5 us per simple transfer (in-memory, no disk).
         let factory = PoaEvmFactory::default();
-        let db = BenchDb::new();
+        let mut db = BenchDb::new();
         let env = bench_env();
 
         const ITERATIONS: u32 = 1_000;
-        let start = Instant::now();
 
+        // Baseline: fresh EVM over a cloned DB for every transaction.
+        let start = Instant::now();
         for _ in 0..ITERATIONS {
             let mut evm = factory.create_evm(db.clone(), env.clone());
             let tx = simple_transfer_tx(
@@ -458,22 +574,38 @@ mod tests {
             );
             let _result = evm.transact(tx);
         }
+        let clone_elapsed = start.elapsed();
+        let clone_per_tx_us = clone_elapsed.as_micros() as f64 / ITERATIONS as f64;
 
+        // Reused: one EVM borrowing the DB for the whole loop.
+        let start = Instant::now();
+        {
+            let mut evm = factory.create_evm_ref(&mut db, env.clone());
+            for _ in 0..ITERATIONS {
+                let tx = simple_transfer_tx(
+                    Address::from([0xCC; 20]),
+                    U256::from(1_000_000_000u64), // 1 gwei
+                );
+                let _result = evm.transact(tx);
+            }
+        }
         let elapsed = start.elapsed();
         let per_tx_us = elapsed.as_micros() as f64 / ITERATIONS as f64;
 
         println!("=== EVM Simple Transfer Benchmark ===");
         println!("  Iterations:   {ITERATIONS}");
-        println!("  Total time:   {elapsed:?}");
-        println!("  Per transfer: {per_tx_us:.2} us");
-        println!("  Throughput:   {:.0} tx/s", 1_000_000.0 / per_tx_us);
+        println!("  Clone-per-tx: {clone_elapsed:?} ({clone_per_tx_us:.2} us/transfer)");
+        println!("  Reused EVM:   {elapsed:?} ({per_tx_us:.2} us/transfer)");
+        println!(
+            "  Speedup:      {:.2}x",
+            clone_per_tx_us / per_tx_us.max(f64::EPSILON)
+        );
+        println!("  Throughput:   {:.0} tx/s", 1_000_000.0 / per_tx_us.max(f64::EPSILON));
         println!("  ---");
         println!("  Reference:  ~0.5-5 us/transfer (in-memory)");
         println!("  geth reference:  ~2-5 us/transfer");
         println!();
 
-        // Correctness: intrinsic gas for ETH transfer is exactly 21,000.
-        assert!(per_tx_us > 0.0, "timing must be positive");
         // A simple transfer should complete in well under 10ms each.
         assert!(
             per_tx_us < 10_000.0,
@@ -481,6 +613,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reused_evm_matches_clone_per_tx() {
+        // Sequential transfers must observe each other's state changes identically
+        // whether each tx gets a fresh EVM over a cloned DB or one EVM is reused.
+        let factory = PoaEvmFactory::default();
+        let env = bench_env();
+        let sender = Address::from([0xAA; 20]);
+        let recipient = Address::from([0xCC; 20]);
+        const TXS: u64 = 10;
+
+        let transfer = |nonce: u64| {
+            let mut tx = simple_transfer_tx(recipient, U256::from(1_000u64));
+            tx.nonce = nonce;
+            tx
+        };
+
+        // Clone-per-tx: fresh EVM each time, state committed back to the master DB.
+        let mut cloned_db = BenchDb::new();
+        let mut cloned_gas = Vec::new();
+        for nonce in 0..TXS {
+            let mut evm = factory.create_evm(cloned_db.clone(), env.clone());
+            let out = evm.transact(transfer(nonce)).expect("transfer should execute");
+            assert!(out.result.is_success(), "tx {nonce} failed: {:?}", out.result);
+            cloned_gas.push(out.result.gas_used());
+            drop(evm);
+            cloned_db.apply(out.state);
+        }
+
+        // Reused: a single EVM borrowing the DB, committing between transactions.
+        let mut reused_db = BenchDb::new();
+        let mut reused_gas = Vec::new();
+        {
+            let mut evm = factory.create_evm_ref(&mut reused_db, env.clone());
+            for nonce in 0..TXS {
+                let out = evm.transact(transfer(nonce)).expect("transfer should execute");
+                assert!(out.result.is_success(), "tx {nonce} failed: {:?}", out.result);
+                reused_gas.push(out.result.gas_used());
+                evm.db_mut().apply(out.state);
+            }
+        }
+
+        assert_eq!(cloned_gas, reused_gas);
+        for address in [sender, recipient] {
+            let cloned = alloy_evm::revm::Database::basic(&mut cloned_db, address).unwrap();
+            let reused = alloy_evm::revm::Database::basic(&mut reused_db, address).unwrap();
+            assert_eq!(cloned, reused, "state diverged for {address}");
+        }
+
+        // Every transfer was applied: nonce advanced and recipient credited once per tx.
+        let sender_info = reused_db.accounts.get(&sender).expect("sender touched");
+        assert_eq!(sender_info.nonce, TXS);
+        let recipient_info = reused_db.accounts.get(&recipient).expect("recipient touched");
+        assert_eq!(recipient_info.balance, U256::from(1_000u64 * TXS));
+    }
+
     // -- 2. Contract creation ---------------------------------------------
 
     #[test]
@@ -488,7 +675,7 @@ mod tests {
         // Deploy a small contract (64 bytes runtime code) repeatedly.
         // Reference: contract deployment ~10-50 us depending on size.
         let factory = PoaEvmFactory::default();
-        let db = BenchDb::new();
+        let mut db = BenchDb::new();
         let env = bench_env();
 
         // Runtime code: 64 bytes of STOP
@@ -498,9 +685,9 @@ mod tests {
 
         const ITERATIONS: u32 = 500;
         let start = Instant::now();
+        let mut evm = factory.create_evm_ref(&mut db, env);
 
         for _ in 0..ITERATIONS {
-            let mut evm = factory.create_evm(db.clone(), env.clone());
             let tx = create_tx(initcode.clone(), 200_000);
             let _result = evm.transact(tx);
         }
@@ -538,14 +725,14 @@ mod tests {
 
         let iterations: u16 = 200;
         let bytecode = storage_loop_bytecode(iterations);
-        let db = BenchDb::new().with_code(Bytecode::new_raw(bytecode.clone()));
+        let mut db = BenchDb::new().with_code(Bytecode::new_raw(bytecode.clone()));
         let env = bench_env();
 
         const RUNS: u32 = 100;
         let start = Instant::now();
+        let mut evm = factory.create_evm_ref(&mut db, env);
 
         for _ in 0..RUNS {
-            let mut evm = factory.create_evm(db.clone(), env.clone());
             let tx = contract_call_tx(contract_addr, Bytes::new(), 5_000_000);
             let _result = evm.transact(tx);
         }
@@ -585,14 +772,14 @@ mod tests {
 
         let iterations: u16 = 1_000;
         let bytecode = arithmetic_loop_bytecode(iterations);
-        let db = BenchDb::new().with_code(Bytecode::new_raw(bytecode));
+        let mut db = BenchDb::new().with_code(Bytecode::new_raw(bytecode));
         let env = bench_env();
 
         const RUNS: u32 = 500;
         let start = Instant::now();
+        let mut evm = factory.create_evm_ref(&mut db, env);
 
         for _ in 0..RUNS {
-            let mut evm = factory.create_evm(db.clone(), env.clone());
             let tx = contract_call_tx(contract_addr, Bytes::new(), 5_000_000);
             let _result = evm.transact(tx);
         }
@@ -633,14 +820,14 @@ mod tests {
 
         let iterations: u16 = 500;
         let bytecode = memory_loop_bytecode(iterations);
-        let db = BenchDb::new().with_code(Bytecode::new_raw(bytecode));
+        let mut db = BenchDb::new().with_code(Bytecode::new_raw(bytecode));
         let env = bench_env();
 
         const RUNS: u32 = 300;
         let start = Instant::now();
+        let mut evm = factory.create_evm_ref(&mut db, env);
 
         for _ in 0..RUNS {
-            let mut evm = factory.create_evm(db.clone(), env.clone());
             let tx = contract_call_tx(contract_addr, Bytes::new(), 10_000_000);
             let _result = evm.transact(tx);
         }
@@ -684,14 +871,14 @@ mod tests {
 
         let iterations: u16 = 500;
         let bytecode = keccak_loop_bytecode(iterations);
-        let db = BenchDb::new().with_code(Bytecode::new_raw(bytecode));
+        let mut db = BenchDb::new().with_code(Bytecode::new_raw(bytecode));
         let env = bench_env();
 
         const RUNS: u32 = 200;
         let start = Instant::now();
+        let mut evm = factory.create_evm_ref(&mut db, env);
 
         for _ in 0..RUNS {
-            let mut evm = factory.create_evm(db.clone(), env.clone());
             let tx = contract_call_tx(contract_addr, Bytes::new(), 10_000_000);
             let _result = evm.transact(tx);
         }
@@ -875,6 +1062,263 @@ mod tests {
 
     // -- 9. Parallel schedule throughput ----------------------------------
 
+    #[test]
+    fn test_evm_warm_coinbase() {
+        // A token transfer that pays a fee to the block producer reads the
+        // coinbase balance: modelled here as `BALANCE(COINBASE)`.
+        //   COINBASE BALANCE POP STOP
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x41, 0x31, 0x50, 0x00]));
+        let contract = Address::from([0xBBu8; 20]);
+
+        let gas_used_at = |factory: &PoaEvmFactory, spec: SpecId, block: u64| -> u64 {
+            let mut db = BenchDb::new().with_code(code.clone());
+            let mut env = bench_env();
+            env.cfg_env.spec = spec;
+            env.block_env.number = U256::from(block);
+            env.block_env.beneficiary = addr(0xC0);
+            let tx = contract_call_tx(contract, Bytes::new(), 100_000);
+            let result = factory.create_evm_ref(&mut db, env).transact(tx);
+            result.expect("call succeeds").result.gas_used()
+        };
+        let warming = PoaEvmFactory::default().with_coinbase_warming(Some(10));
+        let gas_used = |warmed: bool, spec: SpecId| -> u64 {
+            if warmed {
+                gas_used_at(&warming, spec, 10)
+            } else {
+                let mut db = BenchDb::new().with_code(code.clone());
+                let mut env = bench_env();
+                env.cfg_env.spec = spec;
+                env.block_env.beneficiary = addr(0xC0);
+                let tx = contract_call_tx(contract, Bytes::new(), 100_000);
+                let result = EthEvmFactory::default().create_evm(&mut db, env).transact(tx);
+                result.expect("call succeeds").result.gas_used()
+            }
+        };
+
+        let london_cold = gas_used(false, SpecId::LONDON);
+        let london_warm = gas_used(true, SpecId::LONDON);
+        let prague_cold = gas_used(false, SpecId::PRAGUE);
+        let prague_warm = gas_used(true, SpecId::PRAGUE);
+
+        println!("=== Coinbase Pre-warm Benchmark ===");
+        println!("  LONDON  not warmed: {london_cold} gas");
+        println!("  LONDON  warmed:     {london_warm} gas");
+        println!("  PRAGUE  not warmed: {prague_cold} gas (EIP-3651 warms coinbase)");
+        println!("  PRAGUE  warmed:     {prague_warm} gas");
+        println!("  Savings pre-Shanghai: {} gas", london_cold - london_warm);
+        println!();
+
+        // Cold (2600) → warm (100) account access.
+        assert_eq!(london_cold - london_warm, 2_500);
+        // Shanghai+ already warms the coinbase — pre-warming is a no-op.
+        assert_eq!(prague_cold, prague_warm);
+        // Gas accounting only changes from the activation block on.
+        assert_eq!(gas_used_at(&warming, SpecId::LONDON, 9), london_cold);
+        assert_eq!(gas_used_at(&PoaEvmFactory::default(), SpecId::LONDON, 10), london_cold);
+    }
+
+    // -- BLS12-381 pairing (EIP-2537) ------------------------------------
+
+    /// Concatenate hex-encoded field elements, each left-padded to `width` bytes.
+    fn field_elements(width: usize, elements: &[&str]) -> Vec<u8> {
+        elements
+            .iter()
+            .flat_map(|element| {
+                let bytes = alloy_primitives::hex::decode(element).unwrap();
+                let mut padded = vec![0u8; width - bytes.len()];
+                padded.extend(bytes);
+                padded
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_evm_bls12_381_pairing() {
+        // Both inputs check e(P, Q) · e(-P, Q) == 1 with the curves' generators,
+        // so the precompiles must return true (32-byte word ending in 0x01).
+        const BLS_G1_X: &str = concat!(
+            "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905",
+            "a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+        );
+        const BLS_G1_Y: &str = concat!(
+            "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af6",
+            "00db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+        );
+        const BLS_G1_NEG_Y: &str = concat!(
+            "114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c9",
+            "6655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca"
+        );
+        // G2 coordinates as (c0, c1).
+        const BLS_G2: [&str; 4] = [
+            concat!(
+                "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02",
+                "b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+            ),
+            concat!(
+                "13e02b6052719f607dacd3a088274f65596bd0d09920b61a",
+                "b5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+            ),
+            concat!(
+                "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a7",
+                "6d429a695160d12c923ac9cc3baca289e193548608b82801"
+            ),
+            concat!(
+                "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af",
+                "267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+            ),
+        ];
+        // BN254 G2 coordinates as (c1, c0), per EIP-197.
+        const BN_G2: [&str; 4] = [
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        ];
+        const BN_G1_NEG_Y: &str =
+            "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+
+        let mut bls_input = field_elements(64, &[BLS_G1_X, BLS_G1_Y]);
+        bls_input.extend(field_elements(64, &BLS_G2));
+        bls_input.extend(field_elements(64, &[BLS_G1_X, BLS_G1_NEG_Y]));
+        bls_input.extend(field_elements(64, &BLS_G2));
+        let mut bn_input = field_elements(32, &["01", "02"]);
+        bn_input.extend(field_elements(32, &BN_G2));
+        bn_input.extend(field_elements(32, &["01", BN_G1_NEG_Y]));
+        bn_input.extend(field_elements(32, &BN_G2));
+
+        let bls_pairing = Address::with_last_byte(0x0f);
+        let ec_pairing = Address::with_last_byte(0x08);
+        let mut env = bench_env();
+        env.cfg_env.spec = SpecId::CANCUN;
+        let factory = PoaEvmFactory::default().with_bls_precompiles(true);
+        let mut db = BenchDb::new();
+
+        const ITERATIONS: u32 = 50;
+        let mut run = |to: Address, input: &[u8]| {
+            let mut evm = factory.create_evm_ref(&mut db, env.clone());
+            let data = Bytes::copy_from_slice(input);
+            let start = Instant::now();
+            let mut output = Bytes::new();
+            for _ in 0..ITERATIONS {
+                let result = evm.transact(contract_call_tx(to, data.clone(), 1_000_000));
+                let result = result.expect("pairing call executes").result;
+                assert!(result.is_success(), "{result:?}");
+                output = result.output().cloned().unwrap_or_default();
+            }
+            (output, start.elapsed())
+        };
+
+        let (bls_output, bls_elapsed) = run(bls_pairing, &bls_input);
+        let (bn_output, bn_elapsed) = run(ec_pairing, &bn_input);
+        let bls_per_call_us = bls_elapsed.as_micros() as f64 / ITERATIONS as f64;
+        let bn_per_call_us = bn_elapsed.as_micros() as f64 / ITERATIONS as f64;
+
+        println!("=== BLS12-381 Pairing Benchmark (2 pairs, CANCUN + EIP-2537) ===");
+        println!("  Iterations:       {ITERATIONS}");
+        println!("  BLS12-381 (0x0f): {bls_elapsed:?} ({bls_per_call_us:.0} us/call)");
+        println!("  ECPAIRING (0x08): {bn_elapsed:?} ({bn_per_call_us:.0} us/call)");
+        println!(
+            "  BLS / BN254:      {:.2}x",
+            bls_per_call_us / bn_per_call_us.max(f64::EPSILON)
+        );
+        println!();
+
+        let mut pairing_true = [0u8; 32];
+        pairing_true[31] = 1;
+        assert_eq!(bls_output.as_ref(), pairing_true);
+        assert_eq!(bn_output.as_ref(), pairing_true);
+
+        // Without the override a Cancun EVM has no code at 0x0f: the call returns nothing.
+        let mut evm = PoaEvmFactory::default().create_evm_ref(&mut db, env);
+        let result = evm
+            .transact(contract_call_tx(bls_pairing, Bytes::from(bls_input), 1_000_000))
+            .expect("call executes")
+            .result;
+        assert!(result.output().is_none_or(|output| output.is_empty()));
+    }
+
+    // -- STATICCALL vs CALL (EIP-214) -------------------------------------
+
+    #[test]
+    fn test_evm_staticcall_vs_call_overhead() {
+        let pure_target = addr(0xC1);
+        let sstore_target = addr(0xC2);
+        let contract = Address::from([0xBBu8; 20]);
+        let db_with = |caller_code: Bytes| {
+            let mut db = BenchDb::new().with_code(Bytecode::new_raw(caller_code));
+            for (target, code) in [
+                (pure_target, pure_target_bytecode()),
+                (sstore_target, sstore_target_bytecode()),
+            ] {
+                let code = Bytecode::new_raw(code);
+                db.accounts.insert(
+                    target,
+                    AccountInfo {
+                        nonce: 1,
+                        code_hash: code.hash_slow(),
+                        code: Some(code),
+                        ..Default::default()
+                    },
+                );
+            }
+            db
+        };
+
+        let factory = PoaEvmFactory::default();
+        let run = |caller_code: Bytes| {
+            let mut db = db_with(caller_code);
+            let mut evm = factory.create_evm_ref(&mut db, bench_env());
+            let start = Instant::now();
+            let result = evm
+                .transact(contract_call_tx(contract, Bytes::new(), 10_000_000))
+                .expect("call loop executes")
+                .result;
+            let elapsed = start.elapsed();
+            assert!(result.is_success(), "{result:?}");
+            let output = result.output().cloned().unwrap_or_default();
+            (result.gas_used(), output, elapsed)
+        };
+
+        let (static_gas, static_output, static_elapsed) =
+            run(make_staticcall_bytecode(pure_target));
+        let (call_gas, call_output, call_elapsed) = run(make_call_bytecode(pure_target));
+        let diff_pct = static_gas.abs_diff(call_gas) as f64 / call_gas as f64 * 100.0;
+
+        println!("=== STATICCALL vs CALL Benchmark (read-only target) ===");
+        println!("  Iterations:  {CALL_LOOP_ITERATIONS}");
+        println!("  STATICCALL:  {static_gas} gas in {static_elapsed:?}");
+        println!("  CALL:        {call_gas} gas in {call_elapsed:?}");
+        println!("  Difference:  {diff_pct:.2}%");
+        println!();
+
+        // Both loops see the callee's return value (42 * 7) and a successful last call.
+        let mut expected = [0u8; 64];
+        expected[30] = 0x01;
+        expected[31] = 0x26;
+        expected[63] = 1;
+        assert_eq!(static_output.as_ref(), expected);
+        assert_eq!(call_output.as_ref(), expected);
+        // A read-only callee costs the same either way; CALL only pushes an extra
+        // zero value per iteration.
+        assert!(diff_pct < 10.0, "STATICCALL {static_gas} vs CALL {call_gas}");
+
+        // SSTORE inside a STATICCALL frame halts that frame; the caller only sees
+        // a zero success flag.
+        let mut db = db_with(call_loop_bytecode(STATICCALL, sstore_target, 1));
+        let mut evm =
+            factory.create_evm_with_inspector(&mut db, bench_env(), CallResults::default());
+        let result = evm
+            .transact(contract_call_tx(contract, Bytes::new(), 1_000_000))
+            .expect("call executes")
+            .result;
+        assert!(result.is_success(), "{result:?}");
+        assert_eq!(result.output().map(|output| output[63]), Some(0));
+        assert_eq!(
+            evm.inspector().0.first(),
+            Some(&(CallScheme::StaticCall, InstructionResult::StateChangeDuringStaticCall))
+        );
+    }
+
     #[test]
     fn test_parallel_schedule_throughput() {
         // Measure how fast ParallelSchedule::build processes large tx sets.
@@ -966,6 +1410,28 @@ mod tests {
             schedule_a.batches.len() < n_txs / 2,
             "independent txs should have far fewer batches than txs"
         );
+
+        // Records persisted as JSON lines replay to the same schedules.
+        let path =
+            std::env::temp_dir().join(format!("poa-access-records-{}.jsonl", std::process::id()));
+        for (records, schedule) in [
+            (&independent_records, &schedule_a),
+            (&chain_records, &schedule_b),
+            (&mixed_records, &schedule_c),
+        ] {
+            let lines: Vec<String> = records.iter().map(TxAccessRecord::to_json).collect();
+            std::fs::write(&path, lines.join("\n")).unwrap();
+            let replayed: Vec<TxAccessRecord> = std::fs::read_to_string(&path)
+                .unwrap()
+                .lines()
+                .map(|line| TxAccessRecord::from_json(line).unwrap())
+                .collect();
+            assert_eq!(&replayed, records);
+            let replayed_schedule = ParallelSchedule::build(&replayed);
+            assert_eq!(replayed_schedule.batches.len(), schedule.batches.len());
+            assert_eq!(replayed_schedule.tx_count(), schedule.tx_count());
+        }
+        std::fs::remove_file(&path).unwrap();
     }
 
     // -- 10. Conflict detection performance -------------------------------
@@ -1020,6 +1486,13 @@ mod tests {
         let elapsed_large = start_large.elapsed();
         let per_check_large_ns = elapsed_large.as_nanos() as f64 / LARGE_ITERS as f64;
 
+        // Same pair without the bloom pre-screen (full set intersection).
+        let start_exact = Instant::now();
+        for _ in 0..LARGE_ITERS {
+            assert!(!ConflictDetector::conflicts_exact(&tx_c, &tx_d));
+        }
+        let per_check_exact_ns = start_exact.elapsed().as_nanos() as f64 / LARGE_ITERS as f64;
+
         // -- Conflicting pair (worst case: must check all sets) --
         let mut tx_e = TxAccessRecord::default();
         let mut tx_f = TxAccessRecord::default();
@@ -1054,6 +1527,10 @@ mod tests {
         println!();
         println!("  Large sets (50R+20W vs 50R+20W, no conflict):");
         println!("    Per check: {per_check_large_ns:.1} ns");
+        println!(
+            "    Without bloom: {per_check_exact_ns:.1} ns ({:.1}x)",
+            per_check_exact_ns / per_check_large_ns.max(1e-9)
+        );
         println!("    Conflicts: {large_conflicts}/{LARGE_ITERS}");
         println!();
         println!("  WAW conflict (20R+20W with late WAW hit):");
@@ -1078,6 +1555,78 @@ mod tests {
             per_check_large_ns < 100_000.0,
             "large set check too slow: {per_check_large_ns} ns"
         );
+        // The bloom screen must beat the full set intersection it skips.
+        assert!(
+            per_check_large_ns < per_check_exact_ns,
+            "bloom screen no faster: {per_check_large_ns} ns vs {per_check_exact_ns} ns exact"
+        );
+
+        // -- Transfers only (sender + recipient account read/write) --
+        // Compares the inline TxAccessRecord against the previous HashSet-only
+        // representation, including record construction (the allocation cost).
+        #[derive(Default)]
+        struct HashRecord {
+            reads: std::collections::HashSet<AccessKey>,
+            writes: std::collections::HashSet<AccessKey>,
+        }
+
+        fn hash_conflicts(a: &HashRecord, b: &HashRecord) -> bool {
+            a.writes.iter().any(|k| b.writes.contains(k) || b.reads.contains(k))
+                || a.reads.intersection(&b.writes).next().is_some()
+        }
+
+        fn transfer_keys(i: u32) -> (AccessKey, AccessKey) {
+            let from = Address::from_word(B256::from(U256::from(2 * i)));
+            let to = Address::from_word(B256::from(U256::from(2 * i + 1)));
+            (AccessKey::account(from), AccessKey::account(to))
+        }
+
+        const TRANSFER_PAIRS: u32 = 50_000;
+
+        let start_hash = Instant::now();
+        let mut hash_hits = 0u32;
+        for i in 0..TRANSFER_PAIRS {
+            let mut pair = [HashRecord::default(), HashRecord::default()];
+            for (j, rec) in pair.iter_mut().enumerate() {
+                let (from, to) = transfer_keys(2 * i + j as u32);
+                rec.reads.insert(from);
+                rec.reads.insert(to);
+                rec.writes.insert(from);
+                rec.writes.insert(to);
+            }
+            hash_hits += hash_conflicts(&pair[0], &pair[1]) as u32;
+        }
+        let hash_ns = start_hash.elapsed().as_nanos() as f64 / TRANSFER_PAIRS as f64;
+
+        let start_inline = Instant::now();
+        let mut inline_hits = 0u32;
+        for i in 0..TRANSFER_PAIRS {
+            let mut pair = [TxAccessRecord::default(), TxAccessRecord::default()];
+            for (j, rec) in pair.iter_mut().enumerate() {
+                let (from, to) = transfer_keys(2 * i + j as u32);
+                rec.add_read(from.address, from.slot);
+                rec.add_read(to.address, to.slot);
+                rec.add_write(from.address, from.slot);
+                rec.add_write(to.address, to.slot);
+            }
+            debug_assert!(pair[0].is_inline() && pair[1].is_inline());
+            inline_hits += ConflictDetector::conflicts(&pair[0], &pair[1]) as u32;
+        }
+        let inline_ns = start_inline.elapsed().as_nanos() as f64 / TRANSFER_PAIRS as f64;
+        let transfer_speedup = hash_ns / inline_ns.max(f64::EPSILON);
+
+        println!("  Transfers only (2R+2W per tx, build + check):");
+        println!("    HashSet records: {hash_ns:.1} ns/pair");
+        println!("    Inline records:  {inline_ns:.1} ns/pair");
+        println!("    Speedup:         {transfer_speedup:.2}x");
+        println!();
+
+        assert_eq!(hash_hits, 0);
+        assert_eq!(inline_hits, 0);
+        assert!(
+            transfer_speedup >= 3.0,
+            "inline access records should be >=3x faster for transfers, got {transfer_speedup:.2}x"
+        );
     }
 
 }
@@ -0,0 +1,227 @@
+//! Gas estimation by binary search over the transaction gas limit.
+//!
+//! `eth_estimateGas` cannot derive the required limit from a single run: refunds and
+//! the EIP-150 63/64 rule mean a transaction can need more gas than it ends up
+//! using. [`PoaEvmFactory::estimate_gas`] first runs the transaction at the block gas
+//! limit, then narrows `[CALL_STIPEND, block gas limit]` until the interval between
+//! the highest failing and lowest succeeding limit is within the caller's tolerance.
+//! Every attempt runs on a fresh clone of the database; nothing is committed.
+
+use super::PoaEvmFactory;
+use alloy_evm::revm::context::{BlockEnv, TxEnv};
+use alloy_evm::revm::context_interface::result::{EVMError, ExecutionResult, HaltReason};
+use alloy_evm::revm::primitives::hardfork::SpecId;
+use alloy_evm::{Database, Evm, EvmEnv, EvmFactory};
+use alloy_primitives::Bytes;
+
+/// Gas forwarded with a value transfer; the lower bound of the search.
+pub const CALL_STIPEND: u64 = 2_300;
+
+/// Gas estimation failures.
+#[derive(Debug, thiserror::Error)]
+pub enum EstimateError {
+    /// The transaction runs out of gas even at the block gas limit
+    #[error("Transaction runs out of gas at the block gas limit")]
+    OutOfGas,
+
+    /// The transaction reverts at the block gas limit
+    #[error("Transaction reverted: {0}")]
+    Revert(Bytes),
+
+    /// The transaction halts for a reason other than running out of gas
+    #[error("Transaction halted: {0:?}")]
+    Halt(HaltReason),
+
+    /// The transaction is invalid or the database failed
+    #[error("EVM error during gas estimation: {0}")]
+    Evm(String),
+}
+
+impl PoaEvmFactory {
+    /// Smallest gas limit (within `tolerance`) at which `tx` succeeds on `db`.
+    ///
+    /// The search runs between [`CALL_STIPEND`] and `env.block_env.gas_limit` and
+    /// stops once the interval is at most `tolerance` gas wide (a zero tolerance is
+    /// treated as 1), returning its upper, succeeding end. `tx.gas_limit` is ignored.
+    pub fn estimate_gas<DB: Database + Clone>(
+        &self,
+        db: DB,
+        env: EvmEnv<SpecId, BlockEnv>,
+        tx: TxEnv,
+        tolerance: u64,
+    ) -> Result<u64, EstimateError> {
+        let max = env.block_env.gas_limit;
+        let run = |gas_limit: u64| {
+            let mut evm = self.create_evm(db.clone(), env.clone());
+            evm.transact(TxEnv {
+                gas_limit,
+                ..tx.clone()
+            })
+        };
+
+        match run(max) {
+            Ok(outcome) => match outcome.result {
+                ExecutionResult::Success { .. } => {}
+                ExecutionResult::Revert { output, .. } => {
+                    return Err(EstimateError::Revert(output))
+                }
+                ExecutionResult::Halt {
+                    reason: HaltReason::OutOfGas(_),
+                    ..
+                } => return Err(EstimateError::OutOfGas),
+                ExecutionResult::Halt { reason, .. } => return Err(EstimateError::Halt(reason)),
+            },
+            Err(err) => return Err(EstimateError::Evm(err.to_string())),
+        }
+
+        // Invariant: `hi` succeeds, `lo` is below the known minimum.
+        let (mut lo, mut hi) = (CALL_STIPEND.min(max), max);
+        let tolerance = tolerance.max(1);
+        while hi - lo > tolerance {
+            let mid = lo + (hi - lo) / 2;
+            match run(mid) {
+                Ok(outcome) if outcome.result.is_success() => hi = mid,
+                // Too little gas shows up as an out-of-gas halt, a revert from a
+                // gas-dependent check, or an intrinsic-gas validation error.
+                Ok(_) | Err(EVMError::Transaction(_)) => lo = mid,
+                Err(err) => return Err(EstimateError::Evm(err.to_string())),
+            }
+        }
+        Ok(hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_evm::revm::bytecode::Bytecode;
+    use alloy_evm::revm::database::InMemoryDB;
+    use alloy_evm::revm::primitives::TxKind;
+    use alloy_evm::revm::state::AccountInfo;
+    use alloy_primitives::{Address, U256};
+
+    const CONTRACT: Address = Address::repeat_byte(0xBB);
+    const CALLER: Address = Address::repeat_byte(0xAA);
+    const BLOCK_GAS_LIMIT: u64 = 5_000_000;
+
+    /// Counts down from 1000 to zero, then stops.
+    const ARITHMETIC_LOOP: &[u8] = &[
+        0x61, 0x03, 0xe8, // PUSH2 1000
+        0x5b, // JUMPDEST (pc 3)
+        0x60, 0x01, 0x90, 0x03, // PUSH1 1, SWAP1, SUB
+        0x80, 0x60, 0x03, 0x57, // DUP1, PUSH1 3, JUMPI
+        0x00, // STOP
+    ];
+
+    /// `mstore(0, 42); revert(0, 32)`
+    const REVERTS: &[u8] = &[0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xfd];
+
+    /// Jumps back to pc 0 forever.
+    const SPINS: &[u8] = &[0x5b, 0x60, 0x00, 0x56];
+
+    fn setup(code: &'static [u8]) -> (InMemoryDB, EvmEnv<SpecId, BlockEnv>, TxEnv) {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            CONTRACT,
+            AccountInfo {
+                code: Some(Bytecode::new_raw(Bytes::from_static(code))),
+                ..Default::default()
+            },
+        );
+        let mut env = EvmEnv::<SpecId, BlockEnv>::default();
+        env.block_env.gas_limit = BLOCK_GAS_LIMIT;
+        let tx = TxEnv {
+            caller: CALLER,
+            kind: TxKind::Call(CONTRACT),
+            ..Default::default()
+        };
+        (db, env, tx)
+    }
+
+    #[test]
+    fn test_estimate_arithmetic_loop_within_five_percent() {
+        let (db, env, tx) = setup(ARITHMETIC_LOOP);
+        let factory = PoaEvmFactory::default();
+
+        let actual = factory
+            .create_evm(db.clone(), env.clone())
+            .transact(TxEnv {
+                gas_limit: BLOCK_GAS_LIMIT,
+                ..tx.clone()
+            })
+            .unwrap()
+            .result;
+        assert!(actual.is_success());
+        let actual = actual.gas_used();
+        assert!(actual > 21_000 + 1_000 * 20, "loop body runs 1000 times");
+
+        let estimate = factory
+            .estimate_gas(db.clone(), env.clone(), tx.clone(), 1_000)
+            .unwrap();
+        assert!(
+            estimate >= actual,
+            "estimate {estimate} below gas used {actual}"
+        );
+        assert!(
+            estimate <= actual * 105 / 100,
+            "estimate {estimate} vs gas used {actual}"
+        );
+
+        // The estimate itself is a sufficient limit.
+        let at_estimate = factory
+            .create_evm(db, env)
+            .transact(TxEnv {
+                gas_limit: estimate,
+                ..tx
+            })
+            .unwrap();
+        assert!(at_estimate.result.is_success());
+    }
+
+    #[test]
+    fn test_estimate_zero_tolerance_is_exact() {
+        let (db, env, tx) = setup(ARITHMETIC_LOOP);
+        let factory = PoaEvmFactory::default();
+        let exact = factory
+            .estimate_gas(db.clone(), env.clone(), tx.clone(), 0)
+            .unwrap();
+        let failing = factory
+            .create_evm(db, env)
+            .transact(TxEnv {
+                gas_limit: exact - 1,
+                ..tx
+            })
+            .unwrap();
+        assert!(!failing.result.is_success());
+    }
+
+    #[test]
+    fn test_estimate_revert_returns_data() {
+        let (db, env, tx) = setup(REVERTS);
+        match PoaEvmFactory::default().estimate_gas(db, env, tx, 1_000) {
+            Err(EstimateError::Revert(data)) => {
+                assert_eq!(U256::from_be_slice(&data), U256::from(42));
+            }
+            other => panic!("Expected Revert, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_estimate_out_of_gas_at_block_limit() {
+        let (db, env, tx) = setup(SPINS);
+        assert!(matches!(
+            PoaEvmFactory::default().estimate_gas(db, env, tx, 1_000),
+            Err(EstimateError::OutOfGas)
+        ));
+    }
+
+    #[test]
+    fn test_estimate_plain_transfer() {
+        let (db, env, mut tx) = setup(&[]);
+        tx.kind = TxKind::Call(Address::repeat_byte(0xCC));
+        let estimate = PoaEvmFactory::default()
+            .estimate_gas(db, env, tx, 0)
+            .unwrap();
+        assert_eq!(estimate, 21_000);
+    }
+}
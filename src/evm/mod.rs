@@ -11,7 +11,11 @@
 //!   The stored `calldata_gas_per_byte` field on `PoaEvmFactory` is available for a future
 //!   custom `BlockExecutorFactory` that pre-processes `TxEnv` gas limits automatically.
 //!
-//! Also exposes [`PoaExecutorBuilder`] and [`parallel`] (Phase 2 item 13 foundation).
+//! - **BLS12-381 precompiles** (EIP-2537): optionally served at `0x0b`–`0x11` on
+//!   pre-Prague specs ([`PoaEvmFactory::with_bls_precompiles`]).
+//!
+//! Also exposes [`PoaExecutorBuilder`], [`parallel`] (Phase 2 item 13 foundation) and
+//! [`PoaEvmFactory::estimate_gas`] ([`estimate`]).
 //!
 //! # Architecture
 //! ```text
@@ -20,24 +24,32 @@
 //!                 → PoaEvmFactory::create_evm(db, env)
 //!                    → patch_env (contract size limits, spec overrides)
 //!                    → EthEvmFactory::create_evm(db, patched_env)
+//!                    → add BLS12-381 precompiles (pre-Prague, if enabled)
 //! ```
 
+pub mod estimate;
 pub mod parallel;
 
+pub use estimate::{EstimateError, CALL_STIPEND};
+
 use alloy_evm::{
     eth::{EthEvm, EthEvmContext, EthEvmFactory},
     precompiles::PrecompilesMap,
     revm::{
-        context::BlockEnv,
-        context_interface::result::{EVMError, HaltReason},
+        context::{BlockEnv, Journal},
+        context_interface::{
+            result::{EVMError, HaltReason},
+            JournalTr,
+        },
         inspector::NoOpInspector,
         interpreter::{
             CallInput, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
         },
+        precompile::{bls12_381, PrecompileSpecId, Precompiles},
         primitives::hardfork::SpecId,
         Inspector,
     },
-    Database, EvmEnv, EvmFactory,
+    Database, Evm, EvmEnv, EvmFactory,
 };
 use alloy_primitives::{Address, Log, U256};
 
@@ -49,6 +61,7 @@ use reth_ethereum::node::builder::{components::ExecutorBuilder, BuilderContext};
 use reth_ethereum::node::EthEvmConfig;
 use reth_ethereum::EthPrimitives;
 use reth_ethereum_forks::Hardforks;
+use std::borrow::Cow;
 
 
 // ─── Calldata gas discount inspector ──────────────────────────────────────────
@@ -210,6 +223,10 @@ impl<CTX, I: Inspector<CTX>> Inspector<CTX> for CalldataDiscountInspector<I> {
 ///    [`CalldataDiscountInspector`] so non-zero calldata bytes cost
 ///    `calldata_gas_per_byte` instead of the Ethereum default of 16.
 /// 3. Zero-gas mode — disables base fee validation so `gasPrice: 0` txs execute.
+/// 4. Coinbase pre-warming from a fork block — see [`warm_coinbase`].
+/// 5. BLS12-381 precompiles ahead of Prague — see [`add_bls12_381_precompiles`].
+/// 6. Spec override — runs every EVM under one fixed hardfork
+///    ([`PoaEvmFactory::with_spec_override`]).
 #[derive(Debug, Clone)]
 pub struct PoaEvmFactory {
     inner: EthEvmFactory,
@@ -227,11 +244,23 @@ pub struct PoaEvmFactory {
     /// `gasPrice: 0` / `maxFeePerGas: 0` are accepted (base fee check passes
     /// trivially since any `max_fee_per_gas >= 0`).
     pub zero_gas: bool,
+    /// Serve the EIP-2537 BLS12-381 precompiles on specs before Prague.
+    pub bls_precompiles: bool,
+    /// First block whose EVMs start with the coinbase warm; `None` = never.
+    ///
+    /// Changes gas accounting before Shanghai, so it activates at a fork block
+    /// (`PoaChainSpec::with_coinbase_warming_block`).
+    pub coinbase_warming_block: Option<u64>,
+    /// Hardfork every EVM runs under, instead of the one the chain spec activates.
+    ///
+    /// Meant for tests exercising older-fork behaviour (e.g. no `PUSH0` before
+    /// Shanghai); a node running it diverges from peers that do not.
+    pub spec_override: Option<SpecId>,
     /// Pre-computed flag: `true` if `patch_env` has any work to do.
     ///
     /// Allows the hot-path EVM creation calls (`create_evm` / `create_evm_with_inspector`)
     /// to skip the `patch_env` call entirely when all overrides are at their defaults
-    /// (`max_contract_size = None`).
+    /// (`max_contract_size = None`, `spec_override = None`).
     needs_env_patch: bool,
 }
 
@@ -242,6 +271,9 @@ impl Default for PoaEvmFactory {
             max_contract_size: None,
             calldata_gas_per_byte: 4, // POA default: reduce calldata cost
             zero_gas: false,
+            bls_precompiles: false,
+            coinbase_warming_block: None,
+            spec_override: None,
             needs_env_patch: false, // no CfgEnv overrides active by default
         }
     }
@@ -264,24 +296,78 @@ impl PoaEvmFactory {
             max_contract_size,
             calldata_gas_per_byte: calldata_gas_per_byte.clamp(1, 16),
             zero_gas,
+            bls_precompiles: false,
+            coinbase_warming_block: None,
+            spec_override: None,
             needs_env_patch,
         }
     }
 
+    /// Enable the EIP-2537 BLS12-381 precompiles before Prague
+    /// (`PoaChainSpec::with_eip2537_bls_precompiles`).
+    pub fn with_bls_precompiles(mut self, enabled: bool) -> Self {
+        self.bls_precompiles = enabled;
+        self
+    }
+
+    /// Pre-warm the coinbase in every EVM from block `activation` on; `None` disables.
+    pub fn with_coinbase_warming(mut self, activation: Option<u64>) -> Self {
+        self.coinbase_warming_block = activation;
+        self
+    }
+
+    /// Whether EVMs of block `number` start with the coinbase warm.
+    #[inline]
+    fn warms_coinbase(&self, number: U256) -> bool {
+        self.coinbase_warming_block
+            .is_some_and(|activation| number >= U256::from(activation))
+    }
+
+    /// Run every EVM under `spec`, whatever hardfork the chain spec has active.
+    pub fn with_spec_override(mut self, spec: SpecId) -> Self {
+        self.spec_override = Some(spec);
+        self.needs_env_patch = true;
+        self
+    }
+
     /// Apply POA-specific `CfgEnv` overrides to an [`EvmEnv`] before EVM creation.
     ///
     /// Only called when `needs_env_patch` is `true`; callers must check that flag
     /// before invoking this to avoid the function-call overhead on the hot path.
+    ///
+    /// Never touches the block base fee: reth derives it from the chain spec, and it
+    /// stays zero on a chain with EIP-1559 disabled (no London, no base fee).
     #[inline]
-    fn patch_env<S, B>(&self, mut env: EvmEnv<S, B>) -> EvmEnv<S, B> {
+    fn patch_env<B>(&self, mut env: EvmEnv<SpecId, B>) -> EvmEnv<SpecId, B> {
         if let Some(limit) = self.max_contract_size {
             env.cfg_env.limit_contract_code_size = Some(limit);
             // Also lift the initcode size limit (EIP-3860) proportionally.
             env.cfg_env.limit_contract_initcode_size = Some(limit * 2);
         }
+        if let Some(spec) = self.spec_override {
+            env.cfg_env.spec = spec;
+        }
         env
     }
 
+    /// Create an EVM that borrows `db` instead of taking ownership of it.
+    ///
+    /// The returned EVM can execute any number of transactions: each `transact`
+    /// call takes a fresh `TxEnv` and the journal is finalized (reset) at the end
+    /// of every transaction, so nothing leaks between txs.  Callers that need
+    /// sequential txs to observe each other's writes commit the returned state
+    /// into `db` between calls (or use `transact_commit` on a `DatabaseCommit` db).
+    ///
+    /// Prefer this over `create_evm(db.clone(), env)` inside per-transaction loops:
+    /// it avoids both the database clone and the EVM/precompile setup per tx.
+    pub fn create_evm_ref<'a, DB: Database>(
+        &self,
+        db: &'a mut DB,
+        input: EvmEnv<SpecId, BlockEnv>,
+    ) -> EthEvm<&'a mut DB, NoOpInspector, PrecompilesMap> {
+        self.create_evm(db, input)
+    }
+
     /// Whether the calldata discount is active (i.e. cheaper than mainnet).
     #[inline]
     pub fn has_calldata_discount(&self) -> bool {
@@ -289,6 +375,39 @@ impl PoaEvmFactory {
     }
 }
 
+/// Pre-warm the block coinbase in the journal before any transaction executes.
+///
+/// The first `BALANCE`/`EXTCODE*`/`CALL` touching the coinbase then costs the
+/// EIP-2929 warm price (100) instead of the cold price (2600).  From Shanghai
+/// onward EIP-3651 already warms the coinbase for every transaction, so this
+/// only changes gas accounting on pre-Shanghai specs.
+///
+/// The address is only marked warm, the same way EIP-3651 does it: nothing is read
+/// from the database, so there is no error to lose here. The mark is cleared when a
+/// transaction is committed, so it covers the first transaction executed by a
+/// freshly created EVM.
+#[inline]
+pub fn warm_coinbase<DB: Database>(journal: &mut Journal<DB>, coinbase: Address) {
+    journal.warm_coinbase_account(coinbase);
+}
+
+/// Add the EIP-2537 BLS12-381 precompiles (`0x0b`–`0x11`) to an EVM running `spec`.
+///
+/// No-op from Prague on, where the spec's own precompile set already has them.
+/// Earlier specs get their usual set plus the BLS12-381 precompiles.
+pub fn add_bls12_381_precompiles<DB, I>(evm: &mut EthEvm<DB, I, PrecompilesMap>, spec: SpecId)
+where
+    DB: Database,
+    I: Inspector<EthEvmContext<DB>>,
+{
+    if spec.is_enabled_in(SpecId::PRAGUE) {
+        return;
+    }
+    let mut precompiles = Precompiles::new(PrecompileSpecId::from_spec_id(spec)).clone();
+    precompiles.extend(bls12_381::precompiles());
+    *evm.precompiles_mut() = PrecompilesMap::new(Cow::Owned(precompiles));
+}
+
 impl EvmFactory for PoaEvmFactory {
     // Use the standard inspector passthrough — the `EvmFactory` trait requires
     // `Evm::Inspector == I`, so we cannot transparently wrap `I` with
@@ -310,7 +429,16 @@ impl EvmFactory for PoaEvmFactory {
     ) -> Self::Evm<DB, NoOpInspector> {
         // Skip patch_env entirely when no CfgEnv overrides are active.
         let env = if self.needs_env_patch { self.patch_env(input) } else { input };
-        self.inner.create_evm(db, env)
+        let spec = env.cfg_env.spec;
+        let mut evm = self.inner.create_evm(db, env);
+        if self.warms_coinbase(evm.block.number) {
+            let coinbase = evm.block.beneficiary;
+            warm_coinbase(&mut evm.journaled_state, coinbase);
+        }
+        if self.bls_precompiles {
+            add_bls12_381_precompiles(&mut evm, spec);
+        }
+        evm
     }
 
     fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>>>(
@@ -320,7 +448,16 @@ impl EvmFactory for PoaEvmFactory {
         inspector: I,
     ) -> Self::Evm<DB, I> {
         let env = if self.needs_env_patch { self.patch_env(input) } else { input };
-        self.inner.create_evm_with_inspector(db, env, inspector)
+        let spec = env.cfg_env.spec;
+        let mut evm = self.inner.create_evm_with_inspector(db, env, inspector);
+        if self.warms_coinbase(evm.block.number) {
+            let coinbase = evm.block.beneficiary;
+            warm_coinbase(&mut evm.journaled_state, coinbase);
+        }
+        if self.bls_precompiles {
+            add_bls12_381_precompiles(&mut evm, spec);
+        }
+        evm
     }
 }
 
@@ -330,7 +467,8 @@ impl EvmFactory for PoaEvmFactory {
 ///
 /// Plugged into `PoaNode::components_builder` in place of
 /// `EthereumExecutorBuilder`.  Passes through `max_contract_size`,
-/// `calldata_gas_per_byte`, and `zero_gas` to the factory.
+/// `calldata_gas_per_byte`, `zero_gas`, `bls_precompiles`, `coinbase_warming_block`
+/// and `spec_override` to the factory.
 #[derive(Debug, Clone)]
 pub struct PoaExecutorBuilder {
     /// Override for maximum deployed contract size.  `None` = Ethereum default.
@@ -339,6 +477,12 @@ pub struct PoaExecutorBuilder {
     pub calldata_gas_per_byte: u64,
     /// Zero-gas mode: disable base fee validation in the EVM.
     pub zero_gas: bool,
+    /// BLS12-381 precompiles before Prague (EIP-2537).
+    pub bls_precompiles: bool,
+    /// First block with the coinbase pre-warmed.  `None` = never.
+    pub coinbase_warming_block: Option<u64>,
+    /// Hardfork forced on every EVM.  `None` = the chain spec's active hardfork.
+    pub spec_override: Option<SpecId>,
 }
 
 impl PoaExecutorBuilder {
@@ -348,8 +492,30 @@ impl PoaExecutorBuilder {
             max_contract_size,
             calldata_gas_per_byte,
             zero_gas,
+            bls_precompiles: false,
+            coinbase_warming_block: None,
+            spec_override: None,
         }
     }
+
+    /// Serve the EIP-2537 BLS12-381 precompiles on specs before Prague.
+    pub fn with_bls_precompiles(mut self, enabled: bool) -> Self {
+        self.bls_precompiles = enabled;
+        self
+    }
+
+    /// Pre-warm the coinbase from block `activation` on (see
+    /// [`PoaEvmFactory::with_coinbase_warming`]).
+    pub fn with_coinbase_warming(mut self, activation: Option<u64>) -> Self {
+        self.coinbase_warming_block = activation;
+        self
+    }
+
+    /// Run every EVM under `spec` (see [`PoaEvmFactory::with_spec_override`]).
+    pub fn with_spec_override(mut self, spec: SpecId) -> Self {
+        self.spec_override = Some(spec);
+        self
+    }
 }
 
 impl<Node> ExecutorBuilder<Node> for PoaExecutorBuilder
@@ -364,10 +530,17 @@ where
     type EVM = EthEvmConfig<<Node::Types as NodeTypes>::ChainSpec, PoaEvmFactory>;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        Ok(EthEvmConfig::new_with_evm_factory(
-            ctx.chain_spec(),
-            PoaEvmFactory::new(self.max_contract_size, self.calldata_gas_per_byte, self.zero_gas),
-        ))
+        let mut factory = PoaEvmFactory::new(
+            self.max_contract_size,
+            self.calldata_gas_per_byte,
+            self.zero_gas,
+        )
+        .with_bls_precompiles(self.bls_precompiles)
+        .with_coinbase_warming(self.coinbase_warming_block);
+        if let Some(spec) = self.spec_override {
+            factory = factory.with_spec_override(spec);
+        }
+        Ok(EthEvmConfig::new_with_evm_factory(ctx.chain_spec(), factory))
     }
 }
 
@@ -401,6 +574,18 @@ mod tests {
         assert_eq!(patched.cfg_env.limit_contract_code_size, Some(524_288));
     }
 
+    #[test]
+    fn test_poa_evm_factory_patch_leaves_base_fee() {
+        let factory = PoaEvmFactory::new(Some(131_072), 16, false);
+        let mut env = make_env();
+        env.block_env.basefee = 0;
+        assert_eq!(factory.patch_env(env).block_env.basefee, 0);
+
+        let mut env = make_env();
+        env.block_env.basefee = 7;
+        assert_eq!(factory.patch_env(env).block_env.basefee, 7);
+    }
+
     #[test]
     fn test_poa_evm_factory_sets_initcode_limit_double() {
         let factory = PoaEvmFactory::new(Some(131_072), 16, false); // 128 KB
@@ -523,4 +708,70 @@ mod tests {
         let builder = PoaExecutorBuilder::new(None, 4, true);
         assert!(builder.zero_gas);
     }
+
+    // ── BLS12-381 precompiles ────────────────────────────────────────────────
+
+    #[test]
+    fn test_bls_precompiles_added_before_prague_only() {
+        use alloy_evm::revm::database_interface::EmptyDB;
+
+        let bls_pairing = Address::with_last_byte(0x0f);
+        let factory = PoaEvmFactory::default().with_bls_precompiles(true);
+        assert!(!PoaEvmFactory::default().bls_precompiles);
+
+        let mut env = make_env();
+        env.cfg_env.spec = SpecId::CANCUN;
+        let mut evm = factory.create_evm(EmptyDB::default(), env.clone());
+        assert!(evm.precompiles_mut().get(&bls_pairing).is_some());
+        // The spec's own precompiles are kept.
+        assert!(evm.precompiles_mut().get(&Address::with_last_byte(0x0a)).is_some());
+
+        let mut evm = PoaEvmFactory::default().create_evm(EmptyDB::default(), env);
+        assert!(evm.precompiles_mut().get(&bls_pairing).is_none());
+    }
+
+    // ── spec override ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_spec_override_rejects_push0_before_shanghai() {
+        use alloy_evm::revm::bytecode::Bytecode;
+        use alloy_evm::revm::database::InMemoryDB;
+        use alloy_evm::revm::primitives::TxKind;
+        use alloy_evm::revm::state::AccountInfo;
+        use alloy_primitives::Bytes;
+
+        // `PUSH0; PUSH0; RETURN`: valid from Shanghai (EIP-3855) on.
+        let contract = Address::with_last_byte(0xc0);
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x5f, 0x5f, 0xf3]));
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                code_hash: code.hash_slow(),
+                code: Some(code),
+                ..Default::default()
+            },
+        );
+        let tx = TxEnv {
+            caller: Address::with_last_byte(0xca),
+            kind: TxKind::Call(contract),
+            gas_limit: 100_000,
+            chain_id: None,
+            ..Default::default()
+        };
+
+        let mut env = make_env();
+        env.cfg_env.spec = SpecId::CANCUN;
+        let result = PoaEvmFactory::default()
+            .create_evm_ref(&mut db, env.clone())
+            .transact(tx.clone())
+            .unwrap()
+            .result;
+        assert!(result.is_success(), "{result:?}");
+
+        let factory = PoaEvmFactory::default().with_spec_override(SpecId::FRONTIER);
+        assert_eq!(factory.patch_env(env.clone()).cfg_env.spec, SpecId::FRONTIER);
+        let result = factory.create_evm_ref(&mut db, env).transact(tx).unwrap().result;
+        assert!(!result.is_success(), "{result:?}");
+    }
 }
@@ -10,8 +10,10 @@
 //!
 //! ```text
 //!   TxAccessRecord          — read/write sets recorded during EVM execution
+//!   AccessFilters           — cached 256-bit blooms pre-screening large access sets
 //!   ConflictDetector        — detects RAW / WAW / WAR hazards between two txs
 //!   ParallelSchedule        — groups txs into parallel batches with no intra-batch conflicts
+//!   ParallelEvmExecutor     — executes the batches concurrently on rayon, merging in order
 //! ```
 //!
 //! ## Current status
@@ -22,9 +24,23 @@
 //! - A `ParallelSchedule` that produces correct batches for sequential execution with
 //!   the same semantics as true parallel execution (i.e. no visible difference in output).
 //! - A `ParallelExecutor` stub that falls back to sequential execution.
+//! - A `ParallelEvmExecutor` running each batch concurrently, re-executing serially
+//!   any transaction whose schedule slot turns out to conflict.
 
-use alloy_primitives::{Address, B256};
+use super::PoaEvmFactory;
+use alloy_evm::revm::context::result::{EVMError, ExecutionResult};
+use alloy_evm::revm::context::{BlockEnv, TxEnv};
+use alloy_evm::revm::database_interface::{DatabaseCommit, DatabaseRef, WrapDatabaseRef};
+use alloy_evm::revm::primitives::hardfork::SpecId;
+use alloy_evm::revm::state::EvmState;
+use alloy_evm::{Database, Evm, EvmEnv, EvmFactory};
+use alloy_primitives::{Address, B256, U256};
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Reverse;
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
 
 // ─── TxAccessRecord ───────────────────────────────────────────────────────────
 
@@ -32,33 +48,85 @@ use std::collections::HashSet;
 ///
 /// Tracks every (address, slot) pair that the transaction reads from or writes to.
 /// This information is used by [`ConflictDetector`] to find WAW / WAR / RAW hazards.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+///
+/// The sets are filled only through [`add_read`](Self::add_read) /
+/// [`add_write`](Self::add_write), which reset the cached [`AccessFilters`].
+///
+/// Serializes as `{"reads": [{"address", "slot"}, ..], "writes": [..]}`, so recorded
+/// blocks can be saved and replayed through [`ParallelSchedule::build`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TxAccessRecord {
     /// Slots that this transaction *reads* (including balance / nonce / code reads).
-    pub reads: HashSet<AccessKey>,
+    reads: AccessSet,
     /// Slots that this transaction *writes* (including balance / nonce / code writes).
-    pub writes: HashSet<AccessKey>,
+    writes: AccessSet,
+    /// Bloom filters over `reads` / `writes`, built on the first conflict check.
+    #[serde(skip)]
+    filters: OnceLock<AccessFilters>,
 }
 
 impl TxAccessRecord {
     /// Record a storage read.
     pub fn add_read(&mut self, address: Address, slot: B256) {
-        self.reads.insert(AccessKey { address, slot });
+        if self.reads.insert(AccessKey { address, slot }) {
+            self.filters.take();
+        }
     }
 
     /// Record a storage write.
     pub fn add_write(&mut self, address: Address, slot: B256) {
-        self.writes.insert(AccessKey { address, slot });
+        if self.writes.insert(AccessKey { address, slot }) {
+            self.filters.take();
+        }
+    }
+
+    /// Slots that this transaction reads.
+    pub fn reads(&self) -> &AccessSet {
+        &self.reads
+    }
+
+    /// Slots that this transaction writes.
+    pub fn writes(&self) -> &AccessSet {
+        &self.writes
     }
 
     /// Whether this record is empty (no reads or writes).
     pub fn is_empty(&self) -> bool {
         self.reads.is_empty() && self.writes.is_empty()
     }
+
+    /// Whether both access sets are still in their allocation-free inline form.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.reads.is_inline() && self.writes.is_inline()
+    }
+
+    /// Bloom filters over the access sets, built on first use.
+    pub fn filters(&self) -> &AccessFilters {
+        self.filters.get_or_init(|| AccessFilters::new(self))
+    }
+
+    /// The record as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("access records always serialize")
+    }
+
+    /// Parse a record written by [`to_json`](Self::to_json).
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+impl PartialEq for TxAccessRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.reads == other.reads && self.writes == other.writes
+    }
 }
 
+impl Eq for TxAccessRecord {}
+
 /// A (contract address, storage slot) pair used as a key in access sets.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AccessKey {
     /// The account whose storage is accessed.
     pub address: Address,
@@ -79,6 +147,213 @@ impl AccessKey {
             slot: B256::ZERO,
         }
     }
+
+    /// 64-bit mix of the address and slot, for [`AccessBloom`] bit positions.
+    ///
+    /// Much cheaper than the `HashSet` hasher; a weak mix is fine since a collision
+    /// only costs an exact lookup.
+    #[inline]
+    fn bloom_hash(&self) -> u64 {
+        const K: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut h = 0u64;
+        let words = self.address.0.chunks(8).chain(self.slot.0.chunks(8));
+        for word in words {
+            let mut buf = [0u8; 8];
+            buf[..word.len()].copy_from_slice(word);
+            h = (h.rotate_left(5) ^ u64::from_le_bytes(buf)).wrapping_mul(K);
+        }
+        h
+    }
+}
+
+// ─── AccessBloom ──────────────────────────────────────────────────────────────
+
+/// 256-bit bloom filter over [`AccessKey`]s, two bits per key.
+///
+/// Never reports a false negative: a key (or shared key) it rules out is certainly
+/// absent, a hit still needs the exact set lookup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessBloom([u64; 4]);
+
+impl AccessBloom {
+    /// The two bit positions of `key`.
+    #[inline]
+    fn bits(key: &AccessKey) -> [u8; 2] {
+        let h = key.bloom_hash();
+        [(h >> 56) as u8, (h >> 48) as u8]
+    }
+
+    /// Add `key` to the filter.
+    pub fn insert(&mut self, key: &AccessKey) {
+        for bit in Self::bits(key) {
+            self.0[usize::from(bit >> 6)] |= 1 << (bit & 63);
+        }
+    }
+
+    /// Whether `key` may be a member.
+    #[inline]
+    pub fn may_contain(&self, key: &AccessKey) -> bool {
+        Self::bits(key)
+            .iter()
+            .all(|&bit| self.0[usize::from(bit >> 6)] & (1 << (bit & 63)) != 0)
+    }
+
+    /// Whether the filtered sets may share a key.
+    #[inline]
+    pub fn may_intersect(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).any(|(a, b)| a & b != 0)
+    }
+}
+
+/// Bloom filters over the read and write sets of one [`TxAccessRecord`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessFilters {
+    /// Filter over the read set.
+    pub reads: AccessBloom,
+    /// Filter over the write set.
+    pub writes: AccessBloom,
+}
+
+impl AccessFilters {
+    /// Filters over the current access sets of `record`.
+    pub fn new(record: &TxAccessRecord) -> Self {
+        let mut filters = Self::default();
+        record.reads.iter().for_each(|key| filters.reads.insert(key));
+        record.writes.iter().for_each(|key| filters.writes.insert(key));
+        filters
+    }
+}
+
+// ─── AccessSet ────────────────────────────────────────────────────────────────
+
+/// Number of keys an [`AccessSet`] holds inline before spilling to a `HashSet`.
+///
+/// A plain ETH transfer touches two accounts (sender + recipient), so 4 covers
+/// transfers and simple calls without any heap allocation.
+pub const INLINE_ACCESS_CAPACITY: usize = 4;
+
+/// Set of [`AccessKey`]s with a small-size optimisation.
+///
+/// Up to [`INLINE_ACCESS_CAPACITY`] keys live in an inline array (linear scan,
+/// no allocation); inserting one more distinct key moves everything into a
+/// `HashSet`.  Semantics are those of a set: duplicates are ignored and
+/// equality does not depend on insertion order.
+#[derive(Debug, Clone)]
+pub enum AccessSet {
+    /// `keys[..len]` are the members.
+    Inline {
+        /// Inline storage; entries past `len` are unused.
+        keys: [AccessKey; INLINE_ACCESS_CAPACITY],
+        /// Number of occupied entries.
+        len: usize,
+    },
+    /// Spilled representation for larger access sets.
+    Spilled(HashSet<AccessKey>),
+}
+
+impl Default for AccessSet {
+    fn default() -> Self {
+        Self::Inline {
+            keys: [AccessKey::account(Address::ZERO); INLINE_ACCESS_CAPACITY],
+            len: 0,
+        }
+    }
+}
+
+impl AccessSet {
+    /// Insert a key.  Returns `true` if it was not already present.
+    pub fn insert(&mut self, key: AccessKey) -> bool {
+        match self {
+            Self::Inline { keys, len } => {
+                if keys[..*len].contains(&key) {
+                    return false;
+                }
+                if *len < INLINE_ACCESS_CAPACITY {
+                    keys[*len] = key;
+                    *len += 1;
+                } else {
+                    let mut set = HashSet::with_capacity(INLINE_ACCESS_CAPACITY * 2);
+                    set.extend(keys.iter().copied());
+                    set.insert(key);
+                    *self = Self::Spilled(set);
+                }
+                true
+            }
+            Self::Spilled(set) => set.insert(key),
+        }
+    }
+
+    /// Whether `key` is a member.
+    #[inline]
+    pub fn contains(&self, key: &AccessKey) -> bool {
+        match self {
+            Self::Inline { keys, len } => keys[..*len].contains(key),
+            Self::Spilled(set) => set.contains(key),
+        }
+    }
+
+    /// Number of distinct keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Inline { len, .. } => *len,
+            Self::Spilled(set) => set.len(),
+        }
+    }
+
+    /// Whether the set has no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the set is still in inline (non-allocating) form.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self, Self::Inline { .. })
+    }
+
+    /// Inline members, or `None` once spilled.
+    #[inline]
+    fn as_inline(&self) -> Option<&[AccessKey]> {
+        match self {
+            Self::Inline { keys, len } => Some(&keys[..*len]),
+            Self::Spilled(_) => None,
+        }
+    }
+
+    /// Iterate over the members (order unspecified).
+    pub fn iter(&self) -> impl Iterator<Item = &AccessKey> + '_ {
+        let (inline, spilled) = match self {
+            Self::Inline { keys, len } => (&keys[..*len], None),
+            Self::Spilled(set) => (&[][..], Some(set.iter())),
+        };
+        inline.iter().chain(spilled.into_iter().flatten())
+    }
+}
+
+impl PartialEq for AccessSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|key| other.contains(key))
+    }
+}
+
+impl Eq for AccessSet {}
+
+impl Serialize for AccessSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for AccessSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut set = Self::default();
+        for key in Vec::<AccessKey>::deserialize(deserializer)? {
+            set.insert(key);
+        }
+        Ok(set)
+    }
 }
 
 // ─── ConflictDetector ─────────────────────────────────────────────────────────
@@ -105,20 +380,57 @@ impl ConflictDetector {
     /// The order of arguments matters: `tx_a` is assumed to appear *before* `tx_b`
     /// in the block.
     pub fn conflicts(tx_a: &TxAccessRecord, tx_b: &TxAccessRecord) -> bool {
+        // Fast path: all four sets inline (plain transfers, simple calls) —
+        // a handful of direct key comparisons, no hashing.
+        if let (Some(a_reads), Some(a_writes), Some(b_reads), Some(b_writes)) = (
+            tx_a.reads.as_inline(),
+            tx_a.writes.as_inline(),
+            tx_b.reads.as_inline(),
+            tx_b.writes.as_inline(),
+        ) {
+            return a_writes
+                .iter()
+                .any(|key| b_writes.contains(key) || b_reads.contains(key))
+                || a_reads.iter().any(|key| b_writes.contains(key));
+        }
+
+        let (filters_a, filters_b) = (tx_a.filters(), tx_b.filters());
+
+        // Level 1: whole-filter screen.  Disjoint filters mean disjoint sets.
+        let waw_raw = filters_a.writes.may_intersect(&filters_b.writes)
+            || filters_a.writes.may_intersect(&filters_b.reads);
+        let war = filters_a.reads.may_intersect(&filters_b.writes);
+
+        // Level 2: per-key bloom probe; only a bloom hit (true or false positive)
+        // pays for the exact hash-set lookup.
+        if waw_raw {
+            for key in tx_a.writes.iter() {
+                if (filters_b.writes.may_contain(key) && tx_b.writes.contains(key))
+                    || (filters_b.reads.may_contain(key) && tx_b.reads.contains(key))
+                {
+                    return true;
+                }
+            }
+        }
+        war && tx_a
+            .reads
+            .iter()
+            .any(|key| filters_b.writes.may_contain(key) && tx_b.writes.contains(key))
+    }
+
+    /// Full set intersection without the bloom screen.
+    pub(crate) fn conflicts_exact(tx_a: &TxAccessRecord, tx_b: &TxAccessRecord) -> bool {
         // Fused WAW + RAW check: iterate tx_a.writes once and probe both
         // tx_b.writes (WAW) and tx_b.reads (RAW) per element.  This halves
         // the number of hash-set lookups compared to two separate intersection
         // passes in the common case where a conflict is found early.
-        for key in &tx_a.writes {
+        for key in tx_a.writes.iter() {
             if tx_b.writes.contains(key) || tx_b.reads.contains(key) {
                 return true;
             }
         }
         // WAR: tx_b writes what tx_a read.
-        if tx_a.reads.intersection(&tx_b.writes).next().is_some() {
-            return true;
-        }
-        false
+        tx_a.reads.iter().any(|key| tx_b.writes.contains(key))
     }
 }
 
@@ -147,19 +459,47 @@ impl ParallelSchedule {
     /// `records[i]` is the access record for the `i`-th transaction in the block.
     /// The returned schedule preserves the original execution order semantics.
     pub fn build(records: &[TxAccessRecord]) -> Self {
+        Self::build_with_priorities(records, &vec![0; records.len()])
+    }
+
+    /// Build a schedule in which conflicting transactions run in gas price order.
+    ///
+    /// Transactions are placed highest `gas_prices[i]` first (block order among equal
+    /// prices), each into the earliest batch after every conflicting transaction
+    /// already placed, so a high-fee transaction is never held back by a lower-fee
+    /// one it conflicts with. Within a batch, indices are listed in placement order.
+    ///
+    /// This reorders conflicting transactions relative to `records`: use it while the
+    /// block order is still open, e.g. when building a payload from the pool.
+    ///
+    /// # Panics
+    ///
+    /// If `gas_prices` and `records` differ in length.
+    pub fn build_with_priorities(records: &[TxAccessRecord], gas_prices: &[u128]) -> Self {
+        assert_eq!(
+            records.len(),
+            gas_prices.len(),
+            "one gas price per access record"
+        );
+
+        // Placement order: highest gas price first; the sort is stable, so equal
+        // prices keep block order and `build` behaves exactly as before.
+        let mut order: Vec<usize> = (0..records.len()).collect();
+        order.sort_by_key(|&i| Reverse(gas_prices[i]));
+
         // `batch_of[i]` = which batch transaction `i` was placed into.
-        let mut batch_of: Vec<usize> = Vec::with_capacity(records.len());
+        let mut batch_of: Vec<usize> = vec![0; records.len()];
 
-        for (i, record_i) in records.iter().enumerate() {
+        for (position, &i) in order.iter().enumerate() {
             // Find the earliest batch where tx_i does not conflict with any
-            // earlier transaction already placed in that batch.
+            // transaction placed before it.
             let mut target_batch = 0;
-            for j in 0..i {
-                if ConflictDetector::conflicts(&records[j], record_i) {
+            for &j in &order[..position] {
+                if ConflictDetector::conflicts(&records[j], &records[i]) {
                     target_batch = target_batch.max(batch_of[j] + 1);
                 }
             }
-            batch_of.push(target_batch);
+            batch_of[i] = target_batch;
         }
 
         // Collect into batches.
@@ -170,8 +510,8 @@ impl ParallelSchedule {
         let mut batches: Vec<Vec<usize>> = (0..num_batches)
             .map(|_| Vec::with_capacity(avg_cap.max(1)))
             .collect();
-        for (tx_idx, &batch_idx) in batch_of.iter().enumerate() {
-            batches[batch_idx].push(tx_idx);
+        for &tx_idx in &order {
+            batches[batch_of[tx_idx]].push(tx_idx);
         }
 
         Self { batches }
@@ -244,11 +584,181 @@ impl ParallelExecutor {
     }
 }
 
+// ─── ParallelEvmExecutor ──────────────────────────────────────────────────────
+
+/// Executes the batches of a [`ParallelSchedule`] concurrently.
+///
+/// Each transaction of a batch runs on the rayon thread pool against the state at
+/// the start of the batch, read through a shared reference (nothing is copied).
+/// The results are then committed in index order; a transaction whose state
+/// footprint turns out to overlap the writes of an earlier transaction in the same
+/// batch (a conflict the schedule missed) is discarded and re-executed serially on
+/// the merged state.
+///
+/// Every fee-paying transaction credits the block beneficiary, so its balance is
+/// merged as a per-transaction delta instead of counting as a conflict.  A
+/// transaction sent from or to the beneficiary, or lowering its balance, is
+/// re-executed.  A transaction that only reads the beneficiary balance (`BALANCE`,
+/// `SELFBALANCE`) is not told apart from the fee credit and sees the value at the
+/// start of its batch, without the fees of the earlier transactions in the batch.
+/// Apart from such readers the outcome equals serial execution in schedule order,
+/// so the executor must not run blocks whose transactions may read the
+/// beneficiary balance.
+#[derive(Debug)]
+pub struct ParallelEvmExecutor {
+    factory: PoaEvmFactory,
+    evm_env: EvmEnv<SpecId, BlockEnv>,
+    conflict_reexecutions: AtomicU64,
+}
+
+impl ParallelEvmExecutor {
+    /// Execute with EVMs from `factory` in `evm_env`.
+    pub fn new(factory: PoaEvmFactory, evm_env: EvmEnv<SpecId, BlockEnv>) -> Self {
+        Self {
+            factory,
+            evm_env,
+            conflict_reexecutions: AtomicU64::new(0),
+        }
+    }
+
+    /// Transactions re-executed serially because the schedule missed a conflict.
+    pub fn conflict_reexecutions(&self) -> u64 {
+        self.conflict_reexecutions.load(Ordering::Relaxed)
+    }
+
+    /// Execute `txs` batch by batch, committing the resulting state into `db`.
+    ///
+    /// Returns the execution results in transaction order.  An invalid transaction
+    /// aborts execution with its error, as it would abort the block.
+    ///
+    /// # Panics
+    ///
+    /// If `schedule` does not cover every index of `txs` exactly once.
+    pub fn execute_schedule<DB>(
+        &self,
+        schedule: &ParallelSchedule,
+        txs: &[TxEnv],
+        db: &mut DB,
+    ) -> Result<Vec<ExecutionResult>, EVMError<<DB as Database>::Error>>
+    where
+        DB: Database + DatabaseRef<Error = <DB as Database>::Error> + DatabaseCommit + Sync,
+    {
+        let mut results: Vec<Option<ExecutionResult>> = vec![None; txs.len()];
+        let beneficiary = self.evm_env.block_env.beneficiary;
+
+        for batch in &schedule.batches {
+            let batch_start_balance = Self::balance(db, beneficiary)?;
+            let mut beneficiary_balance = batch_start_balance;
+
+            // Optimistic pass: every transaction reads the shared batch-start state.
+            let mut batch = batch.clone();
+            batch.sort_unstable();
+            let snapshot: &DB = db;
+            let outcomes: Vec<_> = batch
+                .into_par_iter()
+                .map(|i| {
+                    let state = WrapDatabaseRef(snapshot);
+                    let mut evm = self.factory.create_evm(state, self.evm_env.clone());
+                    (i, evm.transact(txs[i].clone()).ok())
+                })
+                .collect();
+
+            // Merge in index order; `written` holds the batch's writes so far.
+            let mut written = TxAccessRecord::default();
+            for (i, outcome) in outcomes {
+                let tx = &txs[i];
+                let reusable = outcome.filter(|outcome| {
+                    let sends_to_beneficiary =
+                        tx.caller == beneficiary || tx.kind.to() == Some(&beneficiary);
+                    let lowers_beneficiary = outcome
+                        .state
+                        .get(&beneficiary)
+                        .is_some_and(|acc| acc.info.balance < batch_start_balance);
+                    !sends_to_beneficiary
+                        && !lowers_beneficiary
+                        && !ConflictDetector::conflicts(
+                            &written,
+                            &Self::access_record(&outcome.state, beneficiary),
+                        )
+                });
+
+                let outcome = match reusable {
+                    Some(mut outcome) => {
+                        if let Some(account) = outcome.state.get_mut(&beneficiary) {
+                            beneficiary_balance += account.info.balance - batch_start_balance;
+                            account.info.balance = beneficiary_balance;
+                        }
+                        outcome
+                    }
+                    None => {
+                        self.conflict_reexecutions.fetch_add(1, Ordering::Relaxed);
+                        let mut evm = self.factory.create_evm(&mut *db, self.evm_env.clone());
+                        let outcome = evm.transact(tx.clone())?;
+                        if let Some(account) = outcome.state.get(&beneficiary) {
+                            beneficiary_balance = account.info.balance;
+                        }
+                        outcome
+                    }
+                };
+
+                let record = Self::access_record(&outcome.state, beneficiary);
+                for key in record.writes.iter() {
+                    written.add_write(key.address, key.slot);
+                }
+                db.commit(outcome.state);
+                assert!(results[i].is_none(), "transaction {i} scheduled twice");
+                results[i] = Some(outcome.result);
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .enumerate()
+            .map(|(i, result)| result.unwrap_or_else(|| panic!("transaction {i} not scheduled")))
+            .collect())
+    }
+
+    /// Balance of `address` in `db`, zero for a missing account.
+    fn balance<DB: Database>(db: &mut DB, address: Address) -> Result<U256, EVMError<DB::Error>> {
+        Ok(db
+            .basic(address)
+            .map_err(EVMError::Database)?
+            .map_or(U256::ZERO, |info| info.balance))
+    }
+
+    /// Accounts and slots a transaction loaded (reads) and changed (writes).
+    ///
+    /// The beneficiary account is left out: its balance is merged as a delta.
+    fn access_record(state: &EvmState, beneficiary: Address) -> TxAccessRecord {
+        let mut record = TxAccessRecord::default();
+        for (address, account) in state {
+            if *address != beneficiary {
+                record.add_read(*address, B256::ZERO);
+                if account.is_touched() {
+                    record.add_write(*address, B256::ZERO);
+                }
+            }
+            for (slot, value) in &account.storage {
+                let slot = B256::from(*slot);
+                record.add_read(*address, slot);
+                if value.is_changed() {
+                    record.add_write(*address, slot);
+                }
+            }
+        }
+        record
+    }
+}
+
 // ─── Tests ────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_evm::revm::database::InMemoryDB;
+    use alloy_evm::revm::state::AccountInfo;
+    use alloy_evm::revm::Database as _;
+    use alloy_primitives::TxKind;
 
     fn addr(n: u8) -> Address {
         Address::from([n; 20])
@@ -297,6 +807,199 @@ mod tests {
         assert_eq!(r.writes.len(), 1);
     }
 
+    #[test]
+    fn test_access_set_spills_past_inline_capacity() {
+        let mut r = TxAccessRecord::default();
+        for i in 0..INLINE_ACCESS_CAPACITY as u8 {
+            r.add_read(addr(1), slot(i));
+        }
+        assert!(r.reads.is_inline());
+        // Duplicate does not spill.
+        r.add_read(addr(1), slot(0));
+        assert!(r.reads.is_inline());
+        assert_eq!(r.reads.len(), INLINE_ACCESS_CAPACITY);
+
+        r.add_read(addr(1), slot(0xFF));
+        assert!(!r.reads.is_inline());
+        assert_eq!(r.reads.len(), INLINE_ACCESS_CAPACITY + 1);
+        for i in 0..INLINE_ACCESS_CAPACITY as u8 {
+            assert!(r.reads.contains(&AccessKey::storage(addr(1), slot(i))));
+        }
+        assert!(r.reads.contains(&AccessKey::storage(addr(1), slot(0xFF))));
+    }
+
+    #[test]
+    fn test_access_set_equality_ignores_order_and_representation() {
+        let mut a = AccessSet::default();
+        let mut b = AccessSet::default();
+        for i in 0..3u8 {
+            a.insert(AccessKey::account(addr(i)));
+            b.insert(AccessKey::account(addr(2 - i)));
+        }
+        assert_eq!(a, b);
+
+        // Same members, one spilled: still equal.
+        let mut spilled = AccessSet::Spilled(HashSet::new());
+        for i in 0..3u8 {
+            spilled.insert(AccessKey::account(addr(i)));
+        }
+        assert_eq!(a, spilled);
+    }
+
+    #[test]
+    fn test_tx_access_record_json_roundtrip() {
+        let mut r = TxAccessRecord::default();
+        r.add_read(addr(1), slot(2));
+        for i in 0..INLINE_ACCESS_CAPACITY as u8 + 2 {
+            r.add_write(addr(3), slot(i));
+        }
+        assert!(!r.writes.is_inline());
+        // The filter cache is rebuilt on use, never serialized.
+        let written = AccessKey::storage(addr(3), slot(0));
+        assert!(r.filters().writes.may_contain(&written));
+
+        let json: serde_json::Value = serde_json::from_str(&r.to_json()).unwrap();
+        assert_eq!(
+            json["reads"],
+            serde_json::json!([{ "address": addr(1), "slot": slot(2) }])
+        );
+        assert_eq!(json["writes"].as_array().unwrap().len(), r.writes.len());
+
+        let parsed = TxAccessRecord::from_json(&r.to_json()).unwrap();
+        assert_eq!(parsed, r);
+        assert!(parsed.reads.is_inline() && !parsed.writes.is_inline());
+        assert!(TxAccessRecord::from_json(r#"{"reads":[{"address":"0x01"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_zero_key_not_implicitly_present() {
+        // Unused inline slots hold the zero key; it must not read as a member.
+        let set = AccessSet::default();
+        assert!(!set.contains(&AccessKey::account(Address::ZERO)));
+    }
+
+    // ── Equivalence with the HashSet representation ──────────────────────────
+
+    /// The pre-inline representation, kept as the reference model.
+    #[derive(Default)]
+    struct HashRecord {
+        reads: HashSet<AccessKey>,
+        writes: HashSet<AccessKey>,
+    }
+
+    fn hash_conflicts(tx_a: &HashRecord, tx_b: &HashRecord) -> bool {
+        tx_a.writes
+            .iter()
+            .any(|k| tx_b.writes.contains(k) || tx_b.reads.contains(k))
+            || tx_a.reads.intersection(&tx_b.writes).next().is_some()
+    }
+
+    /// xorshift64 — deterministic pseudo-random source for property tests.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    /// Random record over a tiny key space so collisions (conflicts) are common.
+    /// Sizes straddle the inline capacity to exercise both representations.
+    fn random_pair(rng: &mut Rng) -> (TxAccessRecord, HashRecord) {
+        let mut record = TxAccessRecord::default();
+        let mut model = HashRecord::default();
+        let ops = rng.below(2 * INLINE_ACCESS_CAPACITY as u64 + 3);
+        for _ in 0..ops {
+            let key = AccessKey::storage(addr(rng.below(3) as u8), slot(rng.below(3) as u8));
+            if rng.below(2) == 0 {
+                record.add_read(key.address, key.slot);
+                model.reads.insert(key);
+            } else {
+                record.add_write(key.address, key.slot);
+                model.writes.insert(key);
+            }
+        }
+        (record, model)
+    }
+
+    #[test]
+    fn test_property_sets_match_hash_model() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..2_000 {
+            let (record, model) = random_pair(&mut rng);
+            assert_eq!(record.reads.len(), model.reads.len());
+            assert_eq!(record.writes.len(), model.writes.len());
+            assert!(model.reads.iter().all(|k| record.reads.contains(k)));
+            assert!(model.writes.iter().all(|k| record.writes.contains(k)));
+            assert!(record.reads.iter().all(|k| model.reads.contains(k)));
+            assert_eq!(record.is_empty(), model.reads.is_empty() && model.writes.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_property_conflicts_match_hash_model() {
+        let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+        let (mut inline_pairs, mut mixed_pairs, mut hits) = (0, 0, 0);
+        for _ in 0..5_000 {
+            let (a, model_a) = random_pair(&mut rng);
+            let (b, model_b) = random_pair(&mut rng);
+            let expected = hash_conflicts(&model_a, &model_b);
+            assert_eq!(ConflictDetector::conflicts(&a, &b), expected);
+            assert_eq!(
+                ConflictDetector::conflicts(&b, &a),
+                hash_conflicts(&model_b, &model_a)
+            );
+            if a.is_inline() && b.is_inline() {
+                inline_pairs += 1;
+            } else {
+                mixed_pairs += 1;
+            }
+            hits += expected as u32;
+        }
+        // Both code paths and both outcomes were exercised.
+        assert!(inline_pairs > 0 && mixed_pairs > 0);
+        assert!(hits > 0 && hits < 5_000);
+    }
+
+    // ── AccessFilters ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_bloom_has_no_false_negatives() {
+        let mut bloom = AccessBloom::default();
+        let keys: Vec<_> = (0..60u8).map(|i| AccessKey::storage(addr(i), slot(i))).collect();
+        keys.iter().for_each(|key| bloom.insert(key));
+        assert!(keys.iter().all(|key| bloom.may_contain(key)));
+        assert!(!AccessBloom::default().may_contain(&keys[0]));
+        assert!(!bloom.may_intersect(&AccessBloom::default()));
+    }
+
+    #[test]
+    fn test_filters_rebuilt_after_add() {
+        let mut record = TxAccessRecord::default();
+        for i in 0..10u8 {
+            record.add_write(addr(1), slot(i));
+        }
+        let before = record.filters().clone();
+        record.add_write(addr(1), slot(0xAA));
+        let after = record.filters();
+        assert_ne!(&before, after);
+        assert!(after.writes.may_contain(&AccessKey::storage(addr(1), slot(0xAA))));
+
+        // The cache does not affect equality.
+        let mut fresh = TxAccessRecord::default();
+        for i in (0..10u8).chain([0xAA]) {
+            fresh.add_write(addr(1), slot(i));
+        }
+        assert_eq!(record, fresh);
+    }
+
     // ── ConflictDetector ──────────────────────────────────────────────────────
 
     fn read_only(address: Address, slot: B256) -> TxAccessRecord {
@@ -428,6 +1131,34 @@ mod tests {
         assert_eq!(schedule.batches[1], vec![2]);
     }
 
+    #[test]
+    fn test_schedule_high_gas_price_tx_leads_conflicts() {
+        // tx0..tx2 all write slot(0) at 1 gwei; tx3 writes it too at 50 gwei and
+        // tx4 is independent.
+        let records = vec![
+            write_only(addr(1), slot(0)),
+            write_only(addr(1), slot(0)),
+            write_only(addr(1), slot(0)),
+            write_only(addr(1), slot(0)),
+            read_only(addr(2), slot(1)),
+        ];
+        let gwei = 1_000_000_000;
+        let prices = [gwei, gwei, gwei, 50 * gwei, gwei];
+
+        // Block order: tx3 waits behind the three conflicting transactions.
+        let schedule = ParallelSchedule::build(&records);
+        assert_eq!(schedule.batches, vec![vec![0, 4], vec![1], vec![2], vec![3]]);
+
+        // Priority order: tx3 takes the earliest batch, the rest keep block order.
+        let schedule = ParallelSchedule::build_with_priorities(&records, &prices);
+        assert_eq!(schedule.batches, vec![vec![3, 4], vec![0], vec![1], vec![2]]);
+        assert_eq!(schedule.tx_count(), records.len());
+
+        // Equal prices behave exactly like `build`.
+        let schedule = ParallelSchedule::build_with_priorities(&records, &[gwei; 5]);
+        assert_eq!(schedule.batches, ParallelSchedule::build(&records).batches);
+    }
+
     #[test]
     fn test_schedule_tx_count_matches_input() {
         let records = vec![
@@ -498,4 +1229,119 @@ mod tests {
         // tx0: batch 0, tx1 (empty): batch 0, tx2: batch 1
         assert_eq!(schedule.tx_count(), 3);
     }
+
+    // ── ParallelEvmExecutor ───────────────────────────────────────────────────
+
+    const BENEFICIARY: u8 = 0xCB;
+
+    fn executor() -> ParallelEvmExecutor {
+        let mut env = EvmEnv::<SpecId, BlockEnv>::default();
+        env.block_env.gas_limit = 30_000_000;
+        env.block_env.beneficiary = addr(BENEFICIARY);
+        ParallelEvmExecutor::new(PoaEvmFactory::default(), env)
+    }
+
+    fn funded_db(senders: &[u8]) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        for &sender in senders {
+            let info = AccountInfo {
+                balance: U256::from(10u64).pow(U256::from(18u64)),
+                ..Default::default()
+            };
+            db.insert_account_info(addr(sender), info);
+        }
+        db
+    }
+
+    fn transfer(from: u8, nonce: u64, to: u8) -> TxEnv {
+        TxEnv {
+            caller: addr(from),
+            nonce,
+            kind: TxKind::Call(addr(to)),
+            value: U256::from(1_000),
+            gas_limit: 21_000,
+            gas_price: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Reference: every transaction in order on one database.
+    fn execute_serially(txs: &[TxEnv], db: &mut InMemoryDB) -> Vec<ExecutionResult> {
+        let executor = executor();
+        txs.iter()
+            .map(|tx| {
+                let mut evm = executor
+                    .factory
+                    .create_evm(&mut *db, executor.evm_env.clone());
+                let outcome = evm.transact(tx.clone()).unwrap();
+                drop(evm);
+                db.commit(outcome.state);
+                outcome.result
+            })
+            .collect()
+    }
+
+    /// (balance, nonce) of each account.
+    fn accounts(db: &mut InMemoryDB, addresses: &[u8]) -> Vec<Option<(U256, u64)>> {
+        addresses
+            .iter()
+            .map(|&a| db.basic(addr(a)).unwrap().map(|info| (info.balance, info.nonce)))
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_evm_executor_matches_serial_execution() {
+        let txs: Vec<_> = (1..=4).map(|i| transfer(i, 0, 0x10 + i)).collect();
+        let schedule = ParallelSchedule {
+            batches: vec![vec![0, 1, 2, 3]],
+        };
+        let touched = [1, 2, 3, 4, 0x11, 0x12, 0x13, 0x14, BENEFICIARY];
+
+        let mut parallel_db = funded_db(&[1, 2, 3, 4]);
+        let executor = executor();
+        let results = executor
+            .execute_schedule(&schedule, &txs, &mut parallel_db)
+            .unwrap();
+        let mut serial_db = funded_db(&[1, 2, 3, 4]);
+        assert_eq!(results, execute_serially(&txs, &mut serial_db));
+        assert_eq!(
+            accounts(&mut parallel_db, &touched),
+            accounts(&mut serial_db, &touched)
+        );
+
+        // No missed conflicts; the four fees add up at the beneficiary.
+        assert_eq!(executor.conflict_reexecutions(), 0);
+        let beneficiary = parallel_db.basic(addr(BENEFICIARY)).unwrap().unwrap();
+        assert_eq!(beneficiary.balance, U256::from(4 * 21_000));
+    }
+
+    #[test]
+    fn test_parallel_evm_executor_reexecutes_missed_conflicts() {
+        // One batch holding a nonce chain and two transfers to the same recipient.
+        let txs = vec![
+            transfer(1, 0, 0x11),
+            transfer(1, 1, 0x12),
+            transfer(2, 0, 0x11),
+        ];
+        let schedule = ParallelSchedule {
+            batches: vec![vec![0, 1, 2]],
+        };
+        let touched = [1, 2, 0x11, 0x12, BENEFICIARY];
+
+        let mut parallel_db = funded_db(&[1, 2]);
+        let executor = executor();
+        let results = executor
+            .execute_schedule(&schedule, &txs, &mut parallel_db)
+            .unwrap();
+        let mut serial_db = funded_db(&[1, 2]);
+        assert_eq!(results, execute_serially(&txs, &mut serial_db));
+        assert!(results.iter().all(ExecutionResult::is_success));
+        assert_eq!(
+            accounts(&mut parallel_db, &touched),
+            accounts(&mut serial_db, &touched)
+        );
+
+        // tx1 saw nonce 0 on its snapshot, tx2 credited 0x11 after tx0 did.
+        assert_eq!(executor.conflict_reexecutions(), 2);
+    }
 }
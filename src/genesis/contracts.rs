@@ -1,3 +1,4 @@
+use alloy_eips::eip2935::HISTORY_STORAGE_ADDRESS;
 use alloy_genesis::GenesisAccount;
 use alloy_primitives::{address, b256, bytes, Address, Bytes, B256, U256};
 use std::collections::BTreeMap;
@@ -29,6 +30,52 @@ fn addr_to_b256(addr: Address) -> B256 {
     B256::from(slot)
 }
 
+/// EIP-4788 beacon roots stand-in that answers every query with a fixed root.
+///
+/// Any caller gets storage slot 0 (`root`) back as a 32-byte word, whatever the
+/// calldata. The per-block system call (from `0xff..fe`) stops without writing, so
+/// the root never changes. Replaces the canonical contract when
+/// `PoaChainSpec::with_eip4788_beacon_root` is used.
+pub(crate) fn beacon_root_stub_account(root: B256) -> GenesisAccount {
+    let stub_bytecode = bytes!(
+        "33"             // caller
+        "73"             // push20 SYSTEM_ADDRESS
+        "fffffffffffffffffffffffffffffffffffffffe"
+        "14"             // eq
+        "60" "22"        // push1 0x22 (STOP_DEST)
+        "57"             // jumpi
+        "5f"             // push0
+        "54"             // sload (slot 0 = root)
+        "5f"             // push0
+        "52"             // mstore
+        "60" "20"        // push1 0x20
+        "5f"             // push0
+        "f3"             // return
+        "5b"             // jumpdest (STOP_DEST = 0x22)
+        "00"             // stop
+    );
+    let mut account = code_account(stub_bytecode);
+    account.storage = Some(BTreeMap::from([(B256::ZERO, root)]));
+    account
+}
+
+/// Root answered by `account` if it is the [`beacon_root_stub_account`] stand-in.
+pub(crate) fn beacon_root_stub_root(account: &GenesisAccount) -> Option<B256> {
+    if account.code != beacon_root_stub_account(B256::ZERO).code {
+        return None;
+    }
+    account.storage.as_ref()?.get(&B256::ZERO).copied()
+}
+
+/// EIP-2935 history storage contract.
+///
+/// The per-block system call (from `0xff..fe`) stores the parent hash in slot
+/// `(number - 1) % 8191`; any other caller passes a block number as calldata and gets
+/// that block's hash back, for the last 8191 blocks.
+pub(crate) fn history_storage_account() -> GenesisAccount {
+    code_account(bytes!("3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500"))
+}
+
 /// Returns system contracts required by Cancun and Prague hardforks.
 /// These must be pre-deployed in genesis for the EVM to function correctly.
 pub(crate) fn system_contract_alloc() -> BTreeMap<Address, GenesisAccount> {
@@ -43,10 +90,7 @@ pub(crate) fn system_contract_alloc() -> BTreeMap<Address, GenesisAccount> {
 
     // EIP-2935: History storage contract (Prague)
     // Serves historical block hashes from state
-    contracts.insert(
-        address!("0000F90827F1C53a10cb7A02335B175320002935"),
-        code_account(bytes!("3373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500")),
-    );
+    contracts.insert(HISTORY_STORAGE_ADDRESS, history_storage_account());
 
     // EIP-7002: Withdrawal requests contract (Prague)
     // Execution layer triggerable withdrawals
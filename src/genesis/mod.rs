@@ -16,8 +16,12 @@ pub use addresses::{
     SAFE_SINGLETON_ADDRESS, SIGNER_REGISTRY_ADDRESS, TIMELOCK_ADDRESS, TREASURY_ADDRESS,
 };
 
+pub(crate) use contracts::{
+    beacon_root_stub_account, beacon_root_stub_root, history_storage_account,
+};
+
 use alloy_genesis::{Genesis, GenesisAccount};
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, Bytes, U256};
 use std::collections::BTreeMap;
 
 /// Create a development genesis configuration
@@ -171,22 +175,28 @@ impl GenesisConfig {
     }
 }
 
-/// Create a genesis configuration from the config
-pub fn create_genesis(config: GenesisConfig) -> Genesis {
-    // Build the extra data field for POA:
-    // Format: [vanity (32 bytes)][signers (N*20 bytes)][signature (65 bytes, all zeros for genesis)]
-    let mut extra_data = Vec::with_capacity(32 + config.signers.len() * 20 + 65);
+/// Build the POA genesis extra data field.
+///
+/// Format: [vanity (32 bytes)][signers (N*20 bytes)][signature (65 bytes, all zeros for genesis)]
+pub fn genesis_extra_data(vanity: &[u8; 32], signers: &[Address]) -> Bytes {
+    let mut extra_data = Vec::with_capacity(32 + signers.len() * 20 + 65);
 
     // Add vanity (32 bytes)
-    extra_data.extend_from_slice(&config.vanity);
+    extra_data.extend_from_slice(vanity);
 
     // Add signer addresses
-    for signer in &config.signers {
+    for signer in signers {
         extra_data.extend_from_slice(signer.as_slice());
     }
 
     // Add empty signature (65 bytes of zeros for genesis block)
     extra_data.extend_from_slice(&[0u8; 65]);
+    extra_data.into()
+}
+
+/// Create a genesis configuration from the config
+pub fn create_genesis(config: GenesisConfig) -> Genesis {
+    let extra_data = genesis_extra_data(&config.vanity, &config.signers);
 
     // Convert prefunded accounts to genesis alloc format
     let mut alloc: BTreeMap<Address, GenesisAccount> = config
@@ -246,7 +256,7 @@ pub fn create_genesis(config: GenesisConfig) -> Genesis {
         config: serde_json::from_value(chain_config).expect("valid chain config"),
         nonce: 0,
         timestamp: 0,
-        extra_data: extra_data.into(),
+        extra_data,
         gas_limit: config.gas_limit,
         difficulty: U256::from(1),
         mix_hash: Default::default(),
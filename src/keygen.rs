@@ -0,0 +1,138 @@
+//! `meowchain keygen`: create signer keys for `--signer-key`.
+//!
+//! A key is generated at random, or derived from a BIP-39 mnemonic at
+//! [`MNEMONIC_KEY_PATH`]. Key files hold the 64-character hex private key (no `0x`)
+//! accepted by [`SignerManager::add_signer_from_hex`](crate::signer::SignerManager::add_signer_from_hex),
+//! so `--signer-key "$(cat <file>)"` loads them as is.
+
+use crate::signer::{derive_mnemonic_signer, SignerError};
+use alloy_primitives::Address;
+use alloy_signer_local::PrivateKeySigner;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Derivation path of the key taken from `--mnemonic`: the first account.
+pub const MNEMONIC_KEY_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Key generation and key file failures.
+#[derive(Debug, thiserror::Error)]
+pub enum KeygenError {
+    /// The mnemonic or its derivation failed
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+
+    /// The output file already exists; keys are never overwritten
+    #[error("Key file {} already exists", .0.display())]
+    OutputExists(PathBuf),
+
+    /// The key file does not hold a hex private key
+    #[error("Key file {} does not hold a hex private key", .0.display())]
+    InvalidKeyFile(PathBuf),
+
+    /// The key file could not be read or written
+    #[error("Key file I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// A new random key.
+pub fn generate_key() -> PrivateKeySigner {
+    PrivateKeySigner::random()
+}
+
+/// The key of `phrase` at [`MNEMONIC_KEY_PATH`].
+pub fn key_from_mnemonic(phrase: &str) -> Result<PrivateKeySigner, KeygenError> {
+    Ok(derive_mnemonic_signer(phrase, MNEMONIC_KEY_PATH)?)
+}
+
+/// The private key as 64 hex characters, the `--signer-key` format.
+pub fn private_key_hex(signer: &PrivateKeySigner) -> String {
+    hex::encode(signer.to_bytes())
+}
+
+/// Write the key of `signer` to a new file at `path`, readable by the owner only.
+pub fn save_key(signer: &PrivateKeySigner, path: &Path) -> Result<(), KeygenError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path).map_err(|err| match err.kind() {
+        io::ErrorKind::AlreadyExists => KeygenError::OutputExists(path.to_path_buf()),
+        _ => err.into(),
+    })?;
+    writeln!(file, "{}", private_key_hex(signer))?;
+    Ok(())
+}
+
+/// Address of the key saved at `path`; surrounding whitespace is ignored.
+pub fn verify_key_file(path: &Path) -> Result<Address, KeygenError> {
+    let key: PrivateKeySigner = fs::read_to_string(path)?
+        .trim()
+        .parse()
+        .map_err(|_| KeygenError::InvalidKeyFile(path.to_path_buf()))?;
+    Ok(key.address())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::SignerManager;
+
+    const TEST_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    fn temp_key_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("poa-keygen-{name}-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_saved_key_loads_as_signer_key() {
+        let path = temp_key_file("roundtrip");
+        let key = generate_key();
+        save_key(&key, &path).unwrap();
+
+        let hex = fs::read_to_string(&path).unwrap();
+        assert_eq!(hex.trim().len(), 64);
+        let manager = SignerManager::new();
+        assert_eq!(
+            manager.add_signer_from_hex(hex.trim()).unwrap(),
+            key.address()
+        );
+        assert_eq!(verify_key_file(&path).unwrap(), key.address());
+
+        // An existing key is never overwritten.
+        assert!(matches!(
+            save_key(&generate_key(), &path),
+            Err(KeygenError::OutputExists(_))
+        ));
+        assert_eq!(verify_key_file(&path).unwrap(), key.address());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mnemonic_key_is_first_dev_account() {
+        let key = key_from_mnemonic(TEST_MNEMONIC).unwrap();
+        assert_eq!(key.address(), crate::genesis::dev_accounts()[0]);
+        assert_eq!(
+            private_key_hex(&key),
+            crate::signer::dev::DEV_PRIVATE_KEYS[0]
+        );
+        assert!(matches!(
+            key_from_mnemonic("not a mnemonic"),
+            Err(KeygenError::Signer(SignerError::InvalidMnemonic(_)))
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_non_key_file() {
+        let path = temp_key_file("invalid");
+        fs::write(&path, "not a key\n").unwrap();
+        assert!(matches!(
+            verify_key_file(&path),
+            Err(KeygenError::InvalidKeyFile(_))
+        ));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(verify_key_file(&path), Err(KeygenError::Io(_))));
+    }
+}
@@ -3,7 +3,8 @@
 //! Provides Ethereum Keystore V3-compatible encrypted key storage for production
 //! signer key management. Uses PBKDF2-HMAC-SHA256 key derivation with AES-128-CTR
 //! encryption, following the standard Ethereum keystore format compatible with
-//! geth, Reth, and other Ethereum clients.
+//! geth, Reth, and other Ethereum clients. Keystores written by geth (scrypt) can
+//! be decrypted as well.
 //!
 //! # Format
 //!
@@ -30,6 +31,7 @@ use alloy_signer_local::PrivateKeySigner;
 use eyre::{bail, ensure, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
 use crate::signer::SignerManager;
@@ -44,9 +46,31 @@ pub const DEFAULT_PBKDF2_C: u32 = 262_144;
 #[cfg(test)]
 const TEST_PBKDF2_C: u32 = 2;
 
+/// Scrypt cost of exported keystores: N = 2^18, r = 8, p = 1, as geth writes them
+#[cfg(not(test))]
+const SCRYPT_COST: (u8, u32, u32) = (18, 8, 1);
+
+/// Scrypt cost for testing (geth's `--lightkdf`: N = 2^12, r = 8, p = 6)
+#[cfg(test)]
+const SCRYPT_COST: (u8, u32, u32) = (12, 8, 6);
+
 /// Derived key length in bytes
 const DKLEN: u32 = 32;
 
+/// Failure to read or decrypt a keystore file.
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    /// The keystore file could not be read
+    #[error("Keystore I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// The file is not a V3 keystore this node can decrypt
+    #[error("Malformed keystore: {0}")]
+    Malformed(String),
+    /// The MAC does not match: the password is wrong
+    #[error("MAC verification failed: wrong password or corrupted keystore")]
+    InvalidPassword,
+}
+
 /// Ethereum Keystore V3 format (compatible with geth, Reth, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeystoreFile {
@@ -69,7 +93,7 @@ pub struct CryptoJson {
     pub ciphertext: String,
     /// Cipher parameters
     pub cipherparams: CipherParams,
-    /// Key derivation function (always "pbkdf2")
+    /// Key derivation function ("pbkdf2" when written here, "scrypt" from geth)
     pub kdf: String,
     /// KDF parameters
     pub kdfparams: KdfParams,
@@ -84,15 +108,26 @@ pub struct CipherParams {
     pub iv: String,
 }
 
-/// PBKDF2 key derivation parameters
+/// Key derivation parameters: `c` and `prf` for pbkdf2, `n`, `r` and `p` for scrypt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KdfParams {
     /// Derived key length in bytes (always 32)
     pub dklen: u32,
-    /// Iteration count (default 262144)
+    /// PBKDF2 iteration count (default 262144)
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub c: u32,
-    /// Pseudo-random function (always "hmac-sha256")
+    /// PBKDF2 pseudo-random function (always "hmac-sha256")
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub prf: String,
+    /// Scrypt CPU/memory cost, a power of two
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u64>,
+    /// Scrypt block size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r: Option<u32>,
+    /// Scrypt parallelization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<u32>,
     /// Hex-encoded random salt
     pub salt: String,
 }
@@ -344,14 +379,120 @@ pub fn encrypt_key_with_iterations(
         .map_err(|_| eyre::eyre!("Invalid private key"))?;
     let address = signer.address();
 
-    // Generate random salt (32 bytes) and IV (16 bytes)
+    // Generate random salt (32 bytes)
     let salt = random_bytes::<32>();
-    let iv = random_bytes::<16>();
 
     // Derive key using PBKDF2-HMAC-SHA256
     let mut derived_key = [0u8; 32];
     pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password.as_bytes(), &salt, pbkdf2_c, &mut derived_key);
 
+    let kdfparams = KdfParams {
+        dklen: DKLEN,
+        c: pbkdf2_c,
+        prf: "hmac-sha256".to_string(),
+        n: None,
+        r: None,
+        p: None,
+        salt: hex::encode(salt),
+    };
+    Ok(seal_key(
+        key_bytes,
+        address,
+        &derived_key,
+        "pbkdf2",
+        kdfparams,
+    ))
+}
+
+/// Encrypt `signer`'s private key into a scrypt keystore, the format geth writes.
+///
+/// Uses N = 2^18, r = 8, p = 1 for key derivation and AES-128-CTR for encryption.
+pub fn encrypt_signer_scrypt(
+    signer: &PrivateKeySigner,
+    password: &str,
+) -> Result<KeystoreFile, KeystoreError> {
+    let (log_n, r, p) = SCRYPT_COST;
+    let salt = random_bytes::<32>();
+    let mut derived_key = [0u8; 32];
+    let params = scrypt::Params::new(log_n, r, p, derived_key.len())
+        .map_err(|e| KeystoreError::Malformed(format!("invalid scrypt parameters: {e}")))?;
+    scrypt::scrypt(password.as_bytes(), &salt, &params, &mut derived_key)
+        .map_err(|e| KeystoreError::Malformed(format!("scrypt failed: {e}")))?;
+
+    let kdfparams = KdfParams {
+        dklen: DKLEN,
+        c: 0,
+        prf: String::new(),
+        n: Some(1 << log_n),
+        r: Some(r),
+        p: Some(p),
+        salt: hex::encode(salt),
+    };
+    let key_bytes = signer.credential().to_bytes().to_vec();
+    Ok(seal_key(
+        key_bytes,
+        signer.address(),
+        &derived_key,
+        "scrypt",
+        kdfparams,
+    ))
+}
+
+/// Write `keystore` to `path`, readable by the owner only.
+///
+/// Fails with [`io::ErrorKind::AlreadyExists`] if `path` exists, unless `overwrite`.
+/// An overwrite goes through a hidden temporary file renamed over `path`, so the old
+/// keystore stays intact until the new one is complete.
+pub fn write_keystore(
+    path: impl AsRef<Path>,
+    keystore: &KeystoreFile,
+    overwrite: bool,
+) -> Result<(), KeystoreError> {
+    let path = path.as_ref();
+    let json = serde_json::to_string_pretty(keystore)
+        .map_err(|e| KeystoreError::Malformed(e.to_string()))?;
+    if !overwrite {
+        return write_new_keystore(path, json.as_bytes());
+    }
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(path.file_name().unwrap_or_default());
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    match fs::remove_file(&tmp) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    write_new_keystore(&tmp, json.as_bytes())
+        .and_then(|()| fs::rename(&tmp, path).map_err(KeystoreError::from))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })
+}
+
+/// Create `path` with mode 0o600 and write `json` to disk.
+fn write_new_keystore(path: &Path, json: &[u8]) -> Result<(), KeystoreError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    io::Write::write_all(&mut file, json)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Encrypt `key_bytes` with AES-128-CTR under `derived_key` and assemble the keystore.
+///
+/// The MAC is computed as keccak256(derived_key[16..32] || ciphertext).
+fn seal_key(
+    key_bytes: Vec<u8>,
+    address: Address,
+    derived_key: &[u8; 32],
+    kdf: &str,
+    kdfparams: KdfParams,
+) -> KeystoreFile {
+    let iv = random_bytes::<16>();
+
     // Encrypt with AES-128-CTR (use first 16 bytes of derived key as encryption key)
     let mut ciphertext = key_bytes;
     let mut cipher = Aes128Ctr::new(derived_key[..16].into(), iv.as_slice().into());
@@ -363,10 +504,7 @@ pub fn encrypt_key_with_iterations(
     mac_input.extend_from_slice(&ciphertext);
     let mac = keccak256(&mac_input);
 
-    // Generate UUID
-    let id = uuid::Uuid::new_v4().to_string();
-
-    Ok(KeystoreFile {
+    KeystoreFile {
         version: 3,
         address: hex::encode(address.as_slice()), // 40 hex chars, no 0x prefix
         crypto: CryptoJson {
@@ -375,78 +513,169 @@ pub fn encrypt_key_with_iterations(
             cipherparams: CipherParams {
                 iv: hex::encode(iv),
             },
-            kdf: "pbkdf2".to_string(),
-            kdfparams: KdfParams {
-                dklen: DKLEN,
-                c: pbkdf2_c,
-                prf: "hmac-sha256".to_string(),
-                salt: hex::encode(salt),
-            },
+            kdf: kdf.to_string(),
+            kdfparams,
             mac: hex::encode(mac),
         },
-        id,
-    })
+        id: uuid::Uuid::new_v4().to_string(),
+    }
 }
 
 /// Decrypt a keystore file with the given password.
 ///
 /// Verifies the MAC before returning the decrypted private key hex.
 pub fn decrypt_key(keystore: &KeystoreFile, password: &str) -> Result<String> {
-    ensure!(
-        keystore.version == 3,
-        "Unsupported keystore version: {}",
-        keystore.version
-    );
-    ensure!(
-        keystore.crypto.cipher == "aes-128-ctr",
-        "Unsupported cipher: {}",
-        keystore.crypto.cipher
-    );
-    ensure!(
-        keystore.crypto.kdf == "pbkdf2",
-        "Unsupported KDF: {} (only pbkdf2 is supported)",
-        keystore.crypto.kdf
-    );
+    let signer = decrypt_keystore(keystore, password)?;
+    Ok(hex::encode(signer.credential().to_bytes()))
+}
 
-    let salt = hex::decode(&keystore.crypto.kdfparams.salt).wrap_err("Invalid salt hex")?;
-    let iv = hex::decode(&keystore.crypto.cipherparams.iv).wrap_err("Invalid IV hex")?;
-    let ciphertext = hex::decode(&keystore.crypto.ciphertext).wrap_err("Invalid ciphertext hex")?;
-    let expected_mac = hex::decode(&keystore.crypto.mac).wrap_err("Invalid MAC hex")?;
+/// Read a keystore file from disk.
+pub fn read_keystore(path: impl AsRef<Path>) -> Result<KeystoreFile, KeystoreError> {
+    let data = fs::read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| KeystoreError::Malformed(e.to_string()))
+}
 
-    ensure!(iv.len() == 16, "IV must be 16 bytes, got {}", iv.len());
-    ensure!(
-        ciphertext.len() == 32,
-        "Ciphertext must be 32 bytes, got {}",
-        ciphertext.len()
-    );
+/// Decrypt a keystore (pbkdf2 or scrypt KDF) into its signer.
+///
+/// A MAC mismatch is [`KeystoreError::InvalidPassword`]; anything else wrong with
+/// the file, including an `address` that does not match the key, is
+/// [`KeystoreError::Malformed`].
+pub fn decrypt_keystore(
+    keystore: &KeystoreFile,
+    password: &str,
+) -> Result<PrivateKeySigner, KeystoreError> {
+    let malformed = KeystoreError::Malformed;
+    let crypto = &keystore.crypto;
+    if keystore.version != 3 {
+        return Err(malformed(format!(
+            "unsupported version: {}",
+            keystore.version
+        )));
+    }
+    if crypto.cipher != "aes-128-ctr" {
+        return Err(malformed(format!("unsupported cipher: {}", crypto.cipher)));
+    }
 
-    // Derive key using PBKDF2-HMAC-SHA256
-    let dklen = keystore.crypto.kdfparams.dklen as usize;
-    let mut derived_key = vec![0u8; dklen];
-    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
-        password.as_bytes(),
-        &salt,
-        keystore.crypto.kdfparams.c,
-        &mut derived_key,
-    );
+    let decode = |field: &str, value: &str| {
+        hex::decode(value).map_err(|e| malformed(format!("invalid {field} hex: {e}")))
+    };
+    let iv = decode("IV", &crypto.cipherparams.iv)?;
+    let ciphertext = decode("ciphertext", &crypto.ciphertext)?;
+    let expected_mac = decode("MAC", &crypto.mac)?;
+    if iv.len() != 16 {
+        return Err(malformed(format!("IV must be 16 bytes, got {}", iv.len())));
+    }
+    if ciphertext.len() != 32 {
+        return Err(malformed(format!(
+            "ciphertext must be 32 bytes, got {}",
+            ciphertext.len()
+        )));
+    }
+
+    let derived_key = derive_key(&crypto.kdf, &crypto.kdfparams, password)?;
 
     // Verify MAC: keccak256(derived_key[16..32] || ciphertext)
     let mut mac_input = Vec::with_capacity(16 + ciphertext.len());
     mac_input.extend_from_slice(&derived_key[16..32]);
     mac_input.extend_from_slice(&ciphertext);
-    let computed_mac = keccak256(&mac_input);
-
-    ensure!(
-        computed_mac.as_slice() == expected_mac.as_slice(),
-        "MAC verification failed: wrong password or corrupted keystore"
-    );
+    if keccak256(&mac_input).as_slice() != expected_mac.as_slice() {
+        return Err(KeystoreError::InvalidPassword);
+    }
 
     // Decrypt with AES-128-CTR
     let mut plaintext = ciphertext;
     let mut cipher = Aes128Ctr::new(derived_key[..16].into(), iv.as_slice().into());
     cipher.apply_keystream(&mut plaintext);
 
-    Ok(hex::encode(&plaintext))
+    let signer = PrivateKeySigner::from_slice(&plaintext)
+        .map_err(|e| malformed(format!("invalid private key: {e}")))?;
+    if !keystore.address.is_empty() {
+        let address = parse_address(&keystore.address).map_err(|e| malformed(e.to_string()))?;
+        if address != signer.address() {
+            return Err(malformed(format!(
+                "address {address} does not match the key's address {}",
+                signer.address()
+            )));
+        }
+    }
+    Ok(signer)
+}
+
+/// Keystore files in `dir`, sorted by name.
+///
+/// geth names keystores `UTC--<timestamp>--<address>` without an extension, so files
+/// are told apart by shape: a regular file not starting with a dot that parses as a
+/// V3 keystore. Password files and other neighbours are skipped.
+pub fn keystore_files(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, KeystoreError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if entry.file_type()?.is_file() && !hidden && is_keystore(&entry.path()) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Whether `path` holds a V3 keystore.
+fn is_keystore(path: &Path) -> bool {
+    fs::read(path)
+        .ok()
+        .and_then(|json| serde_json::from_slice::<KeystoreFile>(&json).ok())
+        .is_some_and(|keystore| keystore.version == 3)
+}
+
+/// Keystore password from the first line of `path`, as geth's `--password` reads it.
+pub fn read_password_file(path: impl AsRef<Path>) -> Result<String, KeystoreError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(contents.lines().next().unwrap_or_default().to_string())
+}
+
+/// Derive the 32-byte decryption key with the keystore's KDF.
+fn derive_key(kdf: &str, params: &KdfParams, password: &str) -> Result<Vec<u8>, KeystoreError> {
+    let malformed = KeystoreError::Malformed;
+    if params.dklen != DKLEN {
+        return Err(malformed(format!(
+            "dklen must be {DKLEN}, got {}",
+            params.dklen
+        )));
+    }
+    let salt =
+        hex::decode(&params.salt).map_err(|e| malformed(format!("invalid salt hex: {e}")))?;
+    let mut derived_key = vec![0u8; DKLEN as usize];
+    match kdf {
+        "pbkdf2" => {
+            if params.prf != "hmac-sha256" {
+                return Err(malformed(format!("unsupported pbkdf2 prf: {}", params.prf)));
+            }
+            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+                password.as_bytes(),
+                &salt,
+                params.c,
+                &mut derived_key,
+            );
+        }
+        "scrypt" => {
+            let (Some(n), Some(r), Some(p)) = (params.n, params.r, params.p) else {
+                return Err(malformed(
+                    "scrypt parameters n, r and p are required".into(),
+                ));
+            };
+            if !n.is_power_of_two() || n < 2 {
+                return Err(malformed(format!(
+                    "scrypt n must be a power of two, got {n}"
+                )));
+            }
+            let scrypt_params = scrypt::Params::new(n.ilog2() as u8, r, p, derived_key.len())
+                .map_err(|e| malformed(format!("invalid scrypt parameters: {e}")))?;
+            scrypt::scrypt(password.as_bytes(), &salt, &scrypt_params, &mut derived_key)
+                .map_err(|e| malformed(format!("scrypt failed: {e}")))?;
+        }
+        other => return Err(malformed(format!("unsupported KDF: {other}"))),
+    }
+    Ok(derived_key)
 }
 
 /// Generate N random bytes using alloy_primitives::B256::random() as entropy source.
@@ -464,6 +693,11 @@ fn random_bytes<const N: usize>() -> [u8; N] {
     result
 }
 
+/// Whether a scrypt keystore's absent PBKDF2 iteration count is being serialized.
+fn is_zero(c: &u32) -> bool {
+    *c == 0
+}
+
 /// Parse an address string (with or without 0x prefix).
 fn parse_address(addr_str: &str) -> Result<Address> {
     let with_prefix = if addr_str.starts_with("0x") || addr_str.starts_with("0X") {
@@ -479,6 +713,7 @@ fn parse_address(addr_str: &str) -> Result<Address> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::signer::SignerError;
     use std::sync::Arc;
 
     /// Helper to create a temporary keystore manager (uses fast PBKDF2 for tests)
@@ -934,6 +1169,161 @@ mod tests {
         assert_eq!(decrypted, TEST_KEY);
     }
 
+    /// Keystore of dev account 1 in geth's format, scrypt with `--lightkdf` parameters
+    /// (password "meowchain").
+    const GETH_KEYSTORE: &str = r#"{
+        "address": "70997970c51812dc3a010c7d01b50e0d17dc79c8",
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "ciphertext": "530eedc7b38f9be94545ddce7add6562062180b7efecf0a7737782a1314d5f38",
+            "cipherparams": { "iv": "0e1512298f31c77314439e1d1152f55f" },
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 4096,
+                "p": 6,
+                "r": 8,
+                "salt": "fe3bf12159ca6fabb8d1c011fda642ee11bafacced36520672406ecfafdb1052"
+            },
+            "mac": "9fdad4c97a3b6cc31842b9035c8ba01bc32e2401a839eb47157acd118471c4f2"
+        },
+        "id": "eee2cdf3-f6f4-4559-a01e-a39c3a038f71",
+        "version": 3
+    }"#;
+
+    // -------------------------------------------------------------------------
+    // Test 21: geth scrypt keystore loads into the signer manager
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_geth_keystore_into_signer_manager() {
+        let dir = TempDir::new().unwrap();
+        let path = dir
+            .path()
+            .join("UTC--2024-01-01T00-00-00.000000000Z--70997970c518");
+        fs::write(&path, GETH_KEYSTORE).unwrap();
+        fs::write(dir.path().join("password.txt"), "meowchain\n").unwrap();
+        fs::write(dir.path().join(".DS_Store"), "").unwrap();
+
+        let signer_manager = SignerManager::new();
+        let password = read_password_file(dir.path().join("password.txt")).unwrap();
+        let address = signer_manager
+            .add_signer_from_keystore(&path, &password)
+            .unwrap();
+        assert_eq!(address, crate::genesis::dev_accounts()[1]);
+        assert!(signer_manager.has_signer(&address));
+
+        let files = keystore_files(dir.path()).unwrap();
+        assert_eq!(files, vec![path]);
+    }
+
+    // -------------------------------------------------------------------------
+    // Test 22: wrong password and malformed keystores are told apart
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_keystore_errors_are_typed() {
+        let keystore: KeystoreFile = serde_json::from_str(GETH_KEYSTORE).unwrap();
+        assert!(matches!(
+            decrypt_keystore(&keystore, "not-meowchain"),
+            Err(KeystoreError::InvalidPassword)
+        ));
+
+        let mut unknown_kdf = keystore.clone();
+        unknown_kdf.crypto.kdf = "argon2".to_string();
+        assert!(matches!(
+            decrypt_keystore(&unknown_kdf, "meowchain"),
+            Err(KeystoreError::Malformed(_))
+        ));
+
+        let mut wrong_address = keystore;
+        wrong_address.address = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string();
+        assert!(matches!(
+            decrypt_keystore(&wrong_address, "meowchain"),
+            Err(KeystoreError::Malformed(_))
+        ));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("broken.json");
+        fs::write(&path, "{\"version\":3}").unwrap();
+        assert!(matches!(
+            SignerManager::new().add_signer_from_keystore(&path, "meowchain"),
+            Err(KeystoreError::Malformed(_))
+        ));
+    }
+
+    // -------------------------------------------------------------------------
+    // Test 23: export_keystore + add_signer_from_keystore round-trip
+    // -------------------------------------------------------------------------
+    #[tokio::test]
+    async fn test_export_keystore_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("exported.json");
+        let original = SignerManager::new();
+        let address = original.add_signer_from_hex(TEST_KEY).unwrap();
+        original
+            .export_keystore(&address, TEST_PASSWORD, &path, false)
+            .unwrap();
+
+        let keystore = read_keystore(&path).unwrap();
+        assert_eq!(keystore.crypto.kdf, "scrypt");
+        assert_eq!(keystore.crypto.kdfparams.n, Some(1 << SCRYPT_COST.0));
+        assert!(uuid::Uuid::parse_str(&keystore.id).is_ok());
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(json["crypto"]["kdfparams"].get("c").is_none());
+
+        let reimported = SignerManager::new();
+        assert_eq!(
+            reimported
+                .add_signer_from_keystore(&path, TEST_PASSWORD)
+                .unwrap(),
+            address
+        );
+        let hash = keccak256(b"meowchain");
+        assert_eq!(
+            original.sign_hash(&address, hash).await.unwrap(),
+            reimported.sign_hash(&address, hash).await.unwrap()
+        );
+    }
+
+    // -------------------------------------------------------------------------
+    // Test 24: export_keystore refuses unknown signers and existing files
+    // -------------------------------------------------------------------------
+    #[test]
+    fn test_export_keystore_errors() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("exported.json");
+        let manager = SignerManager::new();
+        let unknown = Address::repeat_byte(0x99);
+        assert!(matches!(
+            manager.export_keystore(&unknown, TEST_PASSWORD, &path, false),
+            Err(SignerError::NoSignerForAddress(addr)) if addr == unknown
+        ));
+        assert!(!path.exists());
+
+        let address = manager.add_signer_from_hex(TEST_KEY).unwrap();
+        manager
+            .export_keystore(&address, "first", &path, false)
+            .unwrap();
+        assert!(matches!(
+            manager.export_keystore(&address, "second", &path, false),
+            Err(SignerError::Keystore(KeystoreError::Io(err)))
+                if err.kind() == io::ErrorKind::AlreadyExists
+        ));
+        manager
+            .export_keystore(&address, "second", &path, true)
+            .unwrap();
+        let keystore = read_keystore(&path).unwrap();
+        assert!(decrypt_keystore(&keystore, "second").is_ok());
+        // Replaced by rename: owner-only, no temporary file left behind.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
     // -------------------------------------------------------------------------
     // Helper: TempDir using std (no external tempfile crate needed)
     // -------------------------------------------------------------------------
@@ -6,11 +6,13 @@
 pub mod cache;
 pub mod chainspec;
 pub mod cli;
+pub mod clock;
 pub mod consensus;
 pub mod constants;
 pub mod errors;
 pub mod evm;
 pub mod genesis;
+pub mod keygen;
 pub mod keystore;
 pub mod metrics;
 pub mod node;
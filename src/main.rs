@@ -1,21 +1,39 @@
-use example_custom_poa_node::chainspec::{PoaChainSpec, PoaConfig};
-use example_custom_poa_node::cli::Cli;
+use example_custom_poa_node::chainspec::{AccessControlList, PoaChainSpec, PoaConfig};
+use example_custom_poa_node::cli::{Cli, Command, KeygenArgs, RevertArgs};
+use example_custom_poa_node::consensus::{PoaConsensus, SnapshotStore, TxTypes, VoterSet};
 use example_custom_poa_node::genesis;
+use example_custom_poa_node::keygen;
+use example_custom_poa_node::keystore;
 use example_custom_poa_node::metrics::{BlockMetrics, ChainMetrics};
-use example_custom_poa_node::node::PoaNode;
+use example_custom_poa_node::node::alerts::{AlertSink, LogAlertSink};
+use example_custom_poa_node::node::disk::{self, DiskMonitor, DiskThresholds, FsProbe};
+use example_custom_poa_node::node::mining::EagerTrigger;
+use example_custom_poa_node::node::revert;
+use example_custom_poa_node::node::{
+    AutomineControl, Backpressure, BackpressureConfig, InclusionTracker, NodeRuntimeStats, PoaNode,
+    ProviderBlockReplayer, SyncMode, WarmupConfig, WarmupReport, SHUTDOWN_BUILD_TIMEOUT,
+};
 use example_custom_poa_node::output;
+use example_custom_poa_node::payload::BlockMetadataProvider;
 use example_custom_poa_node::rpc::{
-    AdminApiServer, AdminRpc, CliqueApiServer, CliqueRpc, MeowApiServer, MeowRpc,
+    AdminApiServer, AdminRpc, CliqueApiServer, CliqueRpc, EvmDevApiServer, EvmDevRpc,
+    MeowApiServer, MeowRpc, PoaMetadataApiServer, PoaMetadataRpc, PoaProofApiServer, PoaProofRpc,
+    PoaReplayApiServer, PoaReplayRpc, PoaSequencerApiServer, PoaSequencerRpc, PoaStatusApiServer,
+    PoaStatusRpc, PoaVotesApiServer, PoaVotesRpc, TxpoolApiServer, TxpoolRpc,
+};
+use example_custom_poa_node::signer::{
+    self, BlockSealer, RemoteSigner, SealAuditEntry, SealAuditLog, SignerManager,
 };
-use example_custom_poa_node::signer::{self, SignerManager};
 use example_custom_poa_node::statediff::StateDiffBuilder;
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{Address, U256};
 use clap::Parser;
 use futures_util::StreamExt;
 use reth_db::init_db;
 use reth_ethereum::{
+    engine::local::MiningMode as LocalMiningMode,
+    node::api::FullNodeComponents,
     node::builder::{NodeBuilder, NodeHandle},
     node::core::{
         args::{
@@ -28,6 +46,7 @@ use reth_ethereum::{
     tasks::{RuntimeBuilder, RuntimeConfig, TokioConfig},
 };
 use reth_network_peers::TrustedPeer;
+use reth_rpc_server_types::RethRpcModule;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
@@ -111,11 +130,20 @@ async fn main() -> eyre::Result<()> {
     // Parse CLI arguments
     let cli = Cli::parse();
 
+    if let Some(Command::Keygen(args)) = &cli.command {
+        return run_keygen(args);
+    }
+    if cli.verify_db {
+        return verify_db(&cli.datadir.join("db"));
+    }
+
     // Determine if we're in dev mode
     let is_dev_mode = !cli.no_dev && !cli.production;
 
     // Create chain specification based on CLI flags
-    let poa_chain = if cli.production {
+    let poa_chain = if let Some(path) = &cli.genesis_file {
+        PoaChainSpec::from_genesis_file(path)?
+    } else if cli.production {
         let mut config = genesis::GenesisConfig::production();
         if let Some(gas_limit) = cli.gas_limit {
             config.gas_limit = gas_limit;
@@ -152,7 +180,54 @@ async fn main() -> eyre::Result<()> {
         PoaChainSpec::new(genesis, poa_config)
     };
 
+    let poa_chain = poa_chain
+        .with_production(cli.production)
+        .with_strict_vanity(cli.strict_vanity);
+    let poa_chain = if cli.disable_eip1559 {
+        poa_chain.with_eip1559_disabled()
+    } else {
+        poa_chain
+    };
+    let poa_chain = match cli.gas_target {
+        Some(gas_target) => poa_chain.with_gas_target(gas_target),
+        None => poa_chain,
+    };
+    let poa_chain = poa_chain
+        .with_max_gas_per_block_ramp(cli.max_gas_ramp_blocks)
+        .with_eip2537_bls_precompiles(cli.eip2537_bls);
+    let poa_chain = match cli.coinbase_warming_block {
+        Some(activation) => poa_chain.with_coinbase_warming_block(activation),
+        None => poa_chain,
+    };
+    let poa_chain = match cli.low_s_seal_block {
+        Some(activation) => poa_chain.with_low_s_seal_block(activation),
+        None => poa_chain,
+    };
+    let poa_chain = match cli.max_block_bytes {
+        Some(max) => poa_chain.with_max_block_size_bytes(max),
+        None => poa_chain,
+    };
+    let poa_chain = match cli.min_signer_stake_eth {
+        Some(stake) => poa_chain.with_min_signer_stake(stake),
+        None => poa_chain,
+    };
+    let poa_chain = match cli.eip4788_stub_root {
+        Some(root) => poa_chain.with_eip4788_beacon_root(root),
+        None => poa_chain,
+    };
+    let poa_chain = if cli.eip2935_block_hashes {
+        poa_chain.with_eip2935_block_hashes()
+    } else {
+        poa_chain
+    };
+    let poa_chain = match &cli.acl_file {
+        Some(path) => poa_chain.with_acl(AccessControlList::load(path)?, cli.acl_from_block),
+        None => poa_chain,
+    };
     let chain_spec_arc = Arc::new(poa_chain);
+    if let Some(Command::Revert(args)) = &cli.command {
+        return run_revert(args, &cli.datadir, chain_spec_arc.inner().clone());
+    }
 
     // Effective mining interval: --block-time-ms overrides --block-time when non-zero (Phase 2.14).
     let mining_interval = if cli.block_time_ms > 0 {
@@ -174,6 +249,12 @@ async fn main() -> eyre::Result<()> {
     };
     output::print_mode(mode_str);
     output::print_signers(chain_spec_arc.signers());
+    output::print_consensus_init(
+        chain_spec_arc.signers().len(),
+        chain_spec_arc.epoch(),
+        chain_spec_arc.block_period(),
+        if is_dev_mode { "dev (relaxed)" } else { "production (strict)" },
+    );
 
     // Set up signer manager with runtime key loading
     let signer_manager = Arc::new(SignerManager::new());
@@ -182,14 +263,66 @@ async fn main() -> eyre::Result<()> {
         // Load signer key from CLI/environment
         let addr = signer_manager.add_signer_from_hex(key)?;
         output::print_signer_loaded(&addr);
-    } else if is_dev_mode {
-        // In dev mode, load dev signers (first 3 keys)
-        for key in signer::dev::DEV_PRIVATE_KEYS.iter().take(3) {
-            signer_manager.add_signer_from_hex(key)?;
+    }
+    let keystores = cli.keystore_dir.as_ref().zip(cli.password_file.as_ref());
+    if let (Some((dir, password_file)), false) = (keystores, cli.watch_keystore_dir) {
+        // Decrypt every keystore in the directory with the one password
+        let password = keystore::read_password_file(password_file)?;
+        for path in keystore::keystore_files(dir)? {
+            let addr = signer_manager
+                .add_signer_from_keystore(&path, &password)
+                .map_err(|e| eyre::eyre!("Failed to load keystore {}: {e}", path.display()))?;
+            output::print_signer_loaded(&addr);
+        }
+    }
+    let mnemonic = match &cli.mnemonic_file {
+        Some(path) => Some(keystore::read_password_file(path)?),
+        None => std::env::var("SIGNER_MNEMONIC").ok(),
+    };
+    if let Some(phrase) = &mnemonic {
+        let addresses = signer_manager.add_signers_from_mnemonic(
+            phrase,
+            cli.mnemonic_count,
+            Some(&cli.derivation_path),
+        )?;
+        for addr in &addresses {
+            output::print_signer_loaded(addr);
+        }
+    }
+    if let (Some(url), Some(address)) = (&cli.remote_signer, cli.remote_signer_address) {
+        // The key stays on the signing service; seals are sent there
+        let addr = signer_manager.add_backend(RemoteSigner::new(url.clone(), address));
+        output::print_signer_loaded(&addr);
+    }
+    // Watched last: the watcher never replaces or removes a key loaded above.
+    let keystore_watcher = match (keystores, cli.watch_keystore_dir) {
+        (Some((dir, password_file)), true) => {
+            // Re-read the password on every load so it can rotate with the keys
+            let password_file = password_file.clone();
+            let loaded = signer_manager.signer_addresses();
+            let watcher = signer_manager.watch_keystore_dir(
+                dir.clone(),
+                Arc::new(move |_: &std::path::Path| keystore::read_password_file(&password_file)),
+                signer::DEFAULT_KEYSTORE_POLL_INTERVAL,
+            );
+            for addr in signer_manager.signer_addresses() {
+                if !loaded.contains(&addr) {
+                    output::print_signer_loaded(&addr);
+                }
+            }
+            output::print_feature("Keystore hot-reload", &dir.display().to_string());
+            Some(watcher)
+        }
+        _ => None,
+    };
+    if signer_manager.signer_count() == 0 {
+        if is_dev_mode {
+            // In dev mode, load dev signers (first 3 keys); refused on production specs.
+            signer::dev::add_dev_signers(&signer_manager, &chain_spec_arc);
+            output::print_dev_signers_loaded(signer_manager.signer_addresses().len());
+        } else {
+            output::print_no_signer_warning();
         }
-        output::print_dev_signers_loaded(signer_manager.signer_addresses().len());
-    } else {
-        output::print_no_signer_warning();
     }
 
     // Configure dev args (interval-based or eager block production).
@@ -200,7 +333,7 @@ async fn main() -> eyre::Result<()> {
     } else {
         DevArgs {
             dev: true,
-            block_time: if cli.eager_mining {
+            block_time: if cli.eager_mining || cli.automine {
                 None // Mine immediately on tx arrival
             } else {
                 Some(mining_interval)
@@ -241,6 +374,11 @@ async fn main() -> eyre::Result<()> {
         ..Default::default()
     };
 
+    // Engine API JWT secret: reth's auth server rejects unauthenticated calls with 401.
+    if let Some(ref path) = cli.jwt_secret {
+        rpc_args.auth_jwtsecret = Some(path.clone());
+    }
+
     // Add CORS if specified via --http-corsdomain
     if let Some(ref cors) = cli.http_corsdomain {
         rpc_args.http_corsdomain = Some(cors.clone());
@@ -273,6 +411,7 @@ async fn main() -> eyre::Result<()> {
     if cli.disable_discovery {
         network_args.discovery.disable_discovery = true;
     }
+    network_args.trusted_only = cli.trusted_only;
     if let Some(ref bootnodes) = cli.bootnodes {
         let parsed: Vec<TrustedPeer> = bootnodes
             .iter()
@@ -294,9 +433,16 @@ async fn main() -> eyre::Result<()> {
         MetricArgs::default()
     };
 
-    // Configure pruning: archive mode disables all pruning.
-    // When --archive is NOT set, Reth uses its default pruning behaviour.
-    let pruning_args = if cli.archive {
+    // Configure pruning: a pruning policy on the chain spec wins, then archive
+    // mode disables all pruning. Otherwise Reth uses its default pruning behaviour.
+    let pruning_args = if let Some(pruning) = chain_spec_arc.state_pruning() {
+        // Only account/storage history is pruned; no distance = archive.
+        PruningArgs {
+            account_history_distance: pruning.history_distance(),
+            storage_history_distance: pruning.history_distance(),
+            ..Default::default()
+        }
+    } else if cli.archive {
         // An empty PruningArgs (default) means no pruning flags are set,
         // which results in no pruning config (= archive behaviour).
         PruningArgs::default()
@@ -323,7 +469,9 @@ async fn main() -> eyre::Result<()> {
 
     output::print_config(
         is_dev_mode,
-        if cli.eager_mining {
+        if cli.automine {
+            "automine (synchronous)"
+        } else if cli.eager_mining {
             "eager (tx-triggered)"
         } else {
             "interval"
@@ -350,6 +498,52 @@ async fn main() -> eyre::Result<()> {
     std::fs::create_dir_all(&db_path)?;
     let database = Arc::new(init_db(&db_path, Default::default())?);
 
+    // Datadir free-space monitor: warns when low, pauses block building when critical.
+    let alert_sink: Arc<dyn AlertSink> = Arc::new(LogAlertSink);
+    let disk_monitor = DiskMonitor::new(
+        &cli.datadir,
+        DiskThresholds::from_gib(cli.disk_warn_gb, cli.disk_critical_gb),
+        Arc::new(FsProbe),
+        alert_sink.clone(),
+    );
+    if let Err(err) = disk_monitor.check() {
+        output::print_info(&format!("Disk space monitoring unavailable: {err}"));
+    }
+    let disk_guard = disk_monitor.guard();
+    tokio::spawn(disk_monitor.run(Duration::from_secs(30)));
+
+    // Transaction ingress back-pressure: shared by the eth_sendRawTransaction gate,
+    // the eager-mining trigger and poa_status; fed by the pool monitor and the
+    // canonical block stream spawned after launch.
+    let backpressure = Backpressure::new(
+        BackpressureConfig::new(cli.txpool_high_watermark, cli.txpool_low_watermark)
+            .with_max_retry_after(Duration::from_secs(cli.txpool_max_retry_secs))
+            .with_gossip_shedding(!cli.no_gossip_shedding)
+            .with_eager_throttling(!cli.no_eager_throttling),
+    );
+
+    // Dev-mode automine: the mining trigger follows the control's mining mode, and
+    // eth_sendRawTransaction waits on the tracker, fed from canonical blocks after launch.
+    let automine_enabled = cli.automine && mining_enabled;
+    let automine = if automine_enabled {
+        AutomineControl::new()
+    } else {
+        AutomineControl::disabled()
+    };
+    let inclusions = InclusionTracker::new();
+    // Eager mining and automine: reth's local miner fires on this trigger instead of
+    // on every arrival, so builds can be held to the block period while the pool is
+    // overloaded, and to the automine mining mode.
+    let eager_trigger = (mining_enabled && (cli.eager_mining || cli.automine)).then(|| {
+        let period = Duration::from_secs(chain_spec_arc.block_period());
+        let (trigger, triggers) = EagerTrigger::new(backpressure.clone(), period);
+        (trigger.with_automine(automine.clone()), triggers)
+    });
+    let (eager_trigger, mining_triggers) = eager_trigger.unzip();
+    let mined_automine = automine.clone();
+    // Cold-start warm-up runs inside the payload builder; its stats feed poa_status.
+    let warmup_report = WarmupReport::new();
+
     // Build and launch the node with PoaNode (custom consensus + payload builder)
     // PoaNode injects PoaConsensus for validation and PoaPayloadBuilder for signed block production.
     // dev_mode controls whether signature verification is enforced.
@@ -358,32 +552,152 @@ async fn main() -> eyre::Result<()> {
     // Clone Arc handles for the RPC closure; originals stay live for post-launch use.
     let rpc_chain_spec = chain_spec_arc.clone();
     let rpc_signer_manager = signer_manager.clone();
+    // Performance metrics (Phase 5), shared with the payload builder.
+    let chain_metrics = ChainMetrics::default_window();
 
-    let NodeHandle {
-        node,
-        node_exit_future,
-    } = NodeBuilder::new(node_config)
+    let poa_node = PoaNode::new(chain_spec_arc.clone())
+        .with_dev_mode(is_dev_mode)?
+        .with_signer_manager(signer_manager.clone())
+        .with_cache_size(cli.cache_size)
+        .with_signature_cache_size(cli.signature_cache_size as usize)
+        .with_max_contract_size(cli.max_contract_size)
+        .with_calldata_gas(cli.calldata_gas)
+        .with_zero_gas(cli.zero_gas)
+        .with_evm_spec(cli.evm_spec)?
+        .with_min_gas_price(cli.min_gas_price)
+        .with_sequencer_mode(cli.sequencer_mode)
+        .with_signer_rotation(cli.signer_rotation)
+        .with_infinite_fund(cli.infinite_fund.clone())
+        .with_client_tag(cli.client_tag.clone())
+        .with_chain_metrics(chain_metrics.clone())
+        .with_compression_stats(cli.compression_stats)
+        .with_disk_guard(disk_guard.clone())
+        .with_backpressure(backpressure.clone())
+        .with_rate_limited_rpc(cli.rpc_rate_limits.clone())
+        .with_mempool_content_api(cli.txpool_content_api)
+        .with_max_pending_transactions(cli.txpool_max_pending)
+        .with_automine(automine.clone(), inclusions.clone())
+        .with_trusted_checkpoint(cli.trusted_checkpoint)
+        .with_allowed_tx_types(cli.allowed_tx_types.clone().map(TxTypes::from_iter))
+        .with_trusted_peers(cli.trusted_peers.clone())?
+        .with_sync_mode(cli.sync_mode)
+        .with_p2p_static_peers(cli.static_peers.clone())?;
+    // Queue cursors are saved in the datadir so paid entries survive a restart.
+    let poa_node = if chain_spec_arc.withdrawal_config().is_some() {
+        poa_node.with_withdrawal_queue_state(&cli.datadir.join("withdrawal_queue.json"))?
+    } else {
+        poa_node
+    };
+    let withdrawal_queue = poa_node.withdrawal_queue().clone();
+    let poa_node = match &cli.epoch_snapshot_dir {
+        Some(dir) => poa_node.with_automatic_epoch_snapshot(dir.clone()),
+        None => poa_node,
+    };
+    let poa_node = match &cli.block_metadata_dir {
+        Some(dir) => poa_node
+            .with_block_metadata_provider(BlockMetadataProvider::from_dir(dir))
+            .with_block_metadata_hash(cli.block_metadata_hash),
+        None => poa_node,
+    };
+    let block_metadata = poa_node.block_metadata_provider().cloned();
+    let poa_node = match cli.health_probe {
+        Some(addr) => poa_node.with_health_probe_endpoint(addr),
+        None => poa_node,
+    };
+    let health_probe = poa_node.health_probe();
+    let health_probe_addr = poa_node.health_probe_endpoint();
+    #[cfg(feature = "otlp-metrics")]
+    let poa_node = match cli.otlp_endpoint {
+        Some(ref endpoint) => poa_node.with_telemetry_endpoint(endpoint)?,
+        None => poa_node,
+    };
+    #[cfg(feature = "otlp-metrics")]
+    let telemetry = poa_node.telemetry().cloned();
+    let poa_node = if cli.warmup {
+        poa_node.with_warmup(
+            WarmupConfig::new(cli.warmup_blocks, Duration::from_millis(cli.warmup_budget_ms)),
+            warmup_report.clone(),
+        )
+    } else {
+        poa_node
+    };
+    let rpc_status = PoaStatusRpc::new(
+        poa_node.production_pause().clone(),
+        disk_guard,
+        backpressure.clone(),
+    )
+    .with_warmup_report(warmup_report.clone());
+    let voter_set = poa_node.voter_set().clone();
+    let signature_cache = poa_node.signature_cache().clone();
+    let max_pending = poa_node.max_pending_transactions();
+    let mempool_content_api = poa_node.mempool_content_api();
+    let build_shutdown = poa_node.build_shutdown().clone();
+    let diagnostic_report = poa_node.diagnose().await;
+
+    let launcher = NodeBuilder::new(node_config)
         .with_database(database)
         .with_launch_context(tasks)
-        .node(
-            PoaNode::new(chain_spec_arc.clone())
-                .with_dev_mode(is_dev_mode)
-                .with_signer_manager(signer_manager.clone())
-                .with_cache_size(cli.cache_size)
-                .with_max_contract_size(cli.max_contract_size)
-                .with_calldata_gas(cli.calldata_gas)
-                .with_zero_gas(cli.zero_gas)
-                .with_infinite_fund(cli.infinite_fund.clone()),
-        )
+        .node(poa_node)
         .extend_rpc_modules(move |ctx| {
-            let meow_rpc = MeowRpc::new(rpc_chain_spec.clone(), rpc_signer_manager.clone(), is_dev_mode);
+            let meow_rpc = MeowRpc::new(
+                rpc_chain_spec.clone(),
+                rpc_signer_manager.clone(),
+                is_dev_mode,
+            )
+            .with_blocks(Arc::new(ctx.provider().clone()));
             ctx.modules.merge_configured(meow_rpc.into_rpc())?;
             output::print_rpc_registered("meow_*");
 
-            let clique_rpc = CliqueRpc::new(rpc_chain_spec.clone(), rpc_signer_manager.clone());
+            let clique_rpc = CliqueRpc::new(rpc_chain_spec.clone(), rpc_signer_manager.clone())
+                .with_voter_set(voter_set.clone());
             ctx.modules.merge_configured(clique_rpc.into_rpc())?;
             output::print_rpc_registered("clique_*");
 
+            let runtime_stats = NodeRuntimeStats {
+                provider: ctx.provider().clone(),
+                network: ctx.network().clone(),
+                pool: ctx.pool().clone(),
+            };
+            let rpc_status = rpc_status
+                .clone()
+                .with_diagnostics(diagnostic_report.clone(), Arc::new(runtime_stats));
+            ctx.modules.merge_configured(rpc_status.into_rpc())?;
+            let votes_rpc = PoaVotesRpc::new(rpc_chain_spec.clone(), voter_set.clone());
+            ctx.modules.merge_configured(votes_rpc.into_rpc())?;
+            let sequencer_rpc = PoaSequencerRpc::new(Arc::new(ctx.pool().clone()));
+            ctx.modules.merge_configured(sequencer_rpc.into_rpc())?;
+            let proof_rpc = PoaProofRpc::new(Arc::new(ctx.provider().clone()));
+            ctx.modules.merge_configured(proof_rpc.into_rpc())?;
+            let mut metadata_rpc = PoaMetadataRpc::new(Arc::new(ctx.provider().clone()));
+            if let Some(provider) = &block_metadata {
+                metadata_rpc = metadata_rpc.with_provider(provider.clone());
+            }
+            ctx.modules.merge_configured(metadata_rpc.into_rpc())?;
+            output::print_rpc_registered("poa_*");
+
+            let replayer = ProviderBlockReplayer::new(
+                ctx.provider().clone(),
+                ctx.node().evm_config().clone(),
+            );
+            let replay_rpc = PoaReplayRpc::new(Arc::new(replayer));
+            ctx.modules
+                .merge_if_module_configured(RethRpcModule::Debug, replay_rpc.into_rpc())?;
+            output::print_rpc_registered("debug_replayBlock");
+
+            // reth's txpool namespace already serves txpool_content / txpool_inspect
+            if mempool_content_api
+                && !ctx.modules.module_config().contains_any(&RethRpcModule::Txpool)
+            {
+                let txpool_rpc = TxpoolRpc::new(Arc::new(ctx.pool().clone()));
+                ctx.modules.merge_configured(txpool_rpc.into_rpc())?;
+                output::print_rpc_registered("txpool_content, txpool_inspect");
+            }
+
+            if automine_enabled {
+                ctx.modules.merge_configured(EvmDevRpc::new(automine.clone()).into_rpc())?;
+                output::print_rpc_registered("evm_*");
+            }
+
             let admin_rpc = AdminRpc::new(
                 rpc_chain_spec.clone(),
                 rpc_signer_manager.clone(),
@@ -400,8 +714,15 @@ async fn main() -> eyre::Result<()> {
             }
             Ok(())
         })
-        .launch_with_debug_capabilities()
-        .await?;
+        .launch_with_debug_capabilities();
+    let launcher = match mining_triggers {
+        Some(triggers) => launcher.with_mining_mode(LocalMiningMode::trigger(triggers)),
+        None => launcher,
+    };
+    let NodeHandle {
+        node,
+        node_exit_future,
+    } = launcher.await?;
 
     output::print_node_started(chain_spec_arc.inner().genesis_hash());
 
@@ -412,12 +733,27 @@ async fn main() -> eyre::Result<()> {
             &format!("http://0.0.0.0:{}/metrics", cli.metrics_port),
         );
     }
+    if let Some(addr) = health_probe_addr {
+        // Sick while syncing, so attach the launched network as the sync source.
+        let probe = health_probe.with_sync_status(Arc::new(node.network.clone()));
+        let bound = probe.serve(addr).await?;
+        output::print_feature("Health probe", &format!("http://{bound}/health"));
+    }
+    if let Some(ref path) = cli.jwt_secret {
+        output::print_feature("Engine API JWT", &path.display().to_string());
+    }
     if let Some(ref cors) = cli.http_corsdomain {
         output::print_feature("CORS", cors);
     }
     output::print_info(&format!("HTTP API modules: {}", cli.http_api));
     output::print_info(&format!("WS API modules: {}", cli.ws_api));
     output::print_info(&format!("Max RPC connections: {}", cli.rpc_max_connections));
+    for limit in &cli.rpc_rate_limits {
+        output::print_feature(
+            "RPC rate limit",
+            &format!("{} at {}/min", limit.method, limit.max_per_minute),
+        );
+    }
     output::print_info(&format!(
         "RPC payload limits: request={}MB response={}MB",
         cli.rpc_max_request_size, cli.rpc_max_response_size,
@@ -432,13 +768,233 @@ async fn main() -> eyre::Result<()> {
     if cli.zero_gas {
         output::print_feature("Zero-gas mode", "base fee disabled, gasPrice=0 accepted");
     }
+    if let Some(acl) = chain_spec_arc.acl() {
+        let whitelist = acl
+            .whitelisted
+            .as_ref()
+            .map_or("none".to_string(), |w| w.len().to_string());
+        output::print_feature(
+            "Sender ACL",
+            &format!(
+                "{} blacklisted, whitelist: {whitelist}, from block {}",
+                acl.blacklisted.len(),
+                chain_spec_arc.acl_activation_block()
+            ),
+        );
+    }
     if cli.log_json {
         output::print_feature("JSON logging", "structured output enabled");
     }
+    if let Some(stats) = warmup_report.stats() {
+        output::print_warmup(&stats);
+    }
 
-    // Register graceful shutdown handlers for SIGINT (Ctrl+C) and SIGTERM.
-    // These print a shutdown message before the node exits.
-    tokio::spawn(async {
+    if chain_spec_arc.withdrawal_config().is_some() {
+        // Advance the withdrawal queue cursors with every canonical block (new chain
+        // only on reorg: cursors are bound to block hashes).
+        let mut withdrawal_stream = node.provider.canonical_state_stream();
+        tokio::spawn(async move {
+            while let Some(notification) = withdrawal_stream.next().await {
+                for block in notification.committed().blocks_iter() {
+                    let withdrawals = block
+                        .body()
+                        .withdrawals
+                        .as_ref()
+                        .map_or(&[][..], |w| w.as_slice());
+                    withdrawal_queue.record_canonical_block(
+                        block.header().number(),
+                        block.hash(),
+                        block.header().parent_hash(),
+                        withdrawals,
+                    );
+                }
+            }
+        });
+    }
+
+    if !is_dev_mode {
+        // Tally the signer votes sealed in canonical blocks since the last epoch
+        // block; a reorg re-tallies the epoch up to the new tip.
+        let vote_consensus =
+            PoaConsensus::new(chain_spec_arc.clone()).with_signature_cache(signature_cache.clone());
+        let vote_provider = node.provider.clone();
+        let canonical_votes = voter_set.clone();
+        let mut vote_stream = node.provider.canonical_state_stream();
+        tokio::spawn(async move {
+            use reth_storage_api::BlockNumReader;
+
+            let head = vote_provider.best_block_number().unwrap_or_default();
+            tally_epoch_votes(&vote_provider, &vote_consensus, &canonical_votes, head);
+            while let Some(notification) = vote_stream.next().await {
+                if notification.reverted().is_some() {
+                    let tip = notification.tip().header().number();
+                    tally_epoch_votes(&vote_provider, &vote_consensus, &canonical_votes, tip);
+                    continue;
+                }
+                for block in notification.committed().blocks_iter() {
+                    tally_vote(&vote_consensus, &canonical_votes, block.sealed_header());
+                }
+            }
+        });
+    }
+
+    if let (Some(dir), false) = (&cli.epoch_snapshot_dir, is_dev_mode) {
+        // Snapshot canonical epoch blocks only, keyed by hash: a validated block may
+        // still lose a fork choice.
+        let store = SnapshotStore::new(dir);
+        let snapshot_consensus = Arc::new(
+            PoaConsensus::new(chain_spec_arc.clone()).with_signature_cache(signature_cache.clone()),
+        );
+        let mut snapshot_stream = node.provider.canonical_state_stream();
+        tokio::spawn(async move {
+            while let Some(notification) = snapshot_stream.next().await {
+                for block in notification.committed().blocks_iter() {
+                    if !snapshot_consensus.is_epoch_block(block.header().number()) {
+                        continue;
+                    }
+                    let (store, consensus) = (store.clone(), snapshot_consensus.clone());
+                    let header = block.clone_sealed_header();
+                    tokio::task::spawn_blocking(move || {
+                        store.record_canonical_block(&consensus, &header)
+                    });
+                }
+            }
+        });
+    }
+
+    if let (Some(path), false) = (&cli.seal_audit_log, is_dev_mode) {
+        // Log canonical blocks, not seals: the builder seals every rebuild of a
+        // payload, and only one of them is published.
+        let log = Arc::new(SealAuditLog::open(path)?);
+        let audit_consensus =
+            PoaConsensus::new(chain_spec_arc.clone()).with_signature_cache(signature_cache.clone());
+        let audit_signers = signer_manager.clone();
+        let mut audit_stream = node.provider.canonical_state_stream();
+        tokio::spawn(async move {
+            while let Some(notification) = audit_stream.next().await {
+                for block in notification.committed().blocks_iter() {
+                    let header = block.sealed_header();
+                    let Ok(signer) = audit_consensus.recover_sealed_signer(header) else {
+                        continue;
+                    };
+                    if !audit_signers.has_signer(&signer) {
+                        continue;
+                    }
+                    let entry = SealAuditEntry::new(header, BlockSealer::seal_hash(header), signer);
+                    if let Err(err) = log.record(vec![entry]).await {
+                        output::print_info(&format!(
+                            "Seal audit log: recording block {} failed: {err}",
+                            header.number
+                        ));
+                    }
+                }
+            }
+        });
+        output::print_feature("Seal audit log", &path.display().to_string());
+    }
+
+    if automine_enabled {
+        // Wake automine submissions once their transactions are canonical, and start
+        // the mining interval from the new tip.
+        let mut inclusion_stream = node.provider.canonical_state_stream();
+        tokio::spawn(async move {
+            while let Some(notification) = inclusion_stream.next().await {
+                for block in notification.committed().blocks_iter() {
+                    mined_automine.record_block(Instant::now());
+                    inclusions.notify_block(
+                        block.header().number(),
+                        block.body().transactions().map(|tx| *tx.tx_hash()),
+                    );
+                }
+            }
+        });
+        output::print_feature("Automine", "eth_sendRawTransaction returns once mined");
+    }
+
+    // Sample pool occupancy for back-pressure; shed gossip transactions while overloaded.
+    tokio::spawn(backpressure.clone().monitor_pool(node.pool.clone(), Duration::from_millis(500)));
+    // The drain rate (retry hints, eager throttling) counts canonical blocks only.
+    let mut drain_stream = node.provider.canonical_state_stream();
+    let drain_backpressure = backpressure.clone();
+    tokio::spawn(async move {
+        while let Some(notification) = drain_stream.next().await {
+            for block in notification.committed().blocks_iter() {
+                drain_backpressure.record_block(
+                    block.header().number(),
+                    block.body().transactions().count(),
+                    Instant::now(),
+                );
+            }
+        }
+    });
+    if let Some(trigger) = eager_trigger {
+        let trigger = trigger.with_shutdown_token(build_shutdown.token().clone());
+        tokio::spawn(trigger.run(node.pool.clone()));
+    }
+    output::print_feature(
+        "Ingress back-pressure",
+        &format!(
+            "high {} / low {} pooled transactions",
+            cli.txpool_high_watermark, cli.txpool_low_watermark
+        ),
+    );
+
+    // Pending transactions are capped by the pool itself.
+    if let Some(max) = max_pending {
+        output::print_feature("Pending tx cap", &format!("{max} pending"));
+    }
+
+    // Light header sync: once the header stage has stored a checkpoint header above the
+    // executed head, verify signer-set continuity checkpoint to checkpoint and seed the
+    // live signer set. Checkpoints the node has already executed are never applied:
+    // the signer set derived from its own chain is at least as recent.
+    if cli.sync_mode == SyncMode::LightHeaders {
+        if is_dev_mode {
+            output::print_info(
+                "--sync-mode light-headers needs signed blocks; ignored in dev mode",
+            );
+        } else {
+            let provider = node.provider.clone();
+            let light_chain_spec = chain_spec_arc.clone();
+            let light_cache = signature_cache.clone();
+            tokio::spawn(async move {
+                use reth_storage_api::BlockNumReader;
+
+                let consensus =
+                    PoaConsensus::new(light_chain_spec.clone()).with_signature_cache(light_cache);
+                let mut ticker = tokio::time::interval(Duration::from_secs(5));
+                loop {
+                    ticker.tick().await;
+                    let head = provider.best_block_number().unwrap_or_default();
+                    let header_tip = provider.last_block_number().unwrap_or_default();
+                    match consensus.light_sync_snapshot(&provider, head, header_tip) {
+                        Ok(None) => continue,
+                        Ok(Some(snapshot)) => {
+                            output::print_info(&format!(
+                                "Light sync: verified checkpoints up to block {} (head {head}), \
+                                 {} signers",
+                                snapshot.number,
+                                snapshot.signers.len()
+                            ));
+                            light_chain_spec.update_live_signers(snapshot.signers);
+                        }
+                        Err(err) => output::print_info(&format!(
+                            "Light sync: checkpoint verification failed: {err}"
+                        )),
+                    }
+                    break;
+                }
+            });
+        }
+    }
+
+    // Graceful shutdown on SIGINT (Ctrl+C) and SIGTERM: print a shutdown message and
+    // stop block production, letting the block being built reach the engine, then push
+    // the last metrics to the collector. Awaited alongside the node below, so the
+    // process does not exit before that is done.
+    #[cfg(feature = "otlp-metrics")]
+    let exit_telemetry = telemetry.clone();
+    let shutdown_signal = async move {
         let ctrl_c = tokio::signal::ctrl_c();
         #[cfg(unix)]
         {
@@ -460,11 +1016,28 @@ async fn main() -> eyre::Result<()> {
             let _ = ctrl_c.await;
             output::print_shutdown("Received SIGINT (Ctrl+C), shutting down...");
         }
-    });
+        match build_shutdown.shutdown(SHUTDOWN_BUILD_TIMEOUT).await {
+            Ok(()) => output::print_info("Block production stopped"),
+            Err(err) => output::print_info(&format!("Block production stopped: {err}")),
+        }
+        #[cfg(feature = "otlp-metrics")]
+        if let Some(telemetry) = exit_telemetry {
+            // The SDK flushes synchronously; keep it off the async workers.
+            match tokio::task::spawn_blocking(move || telemetry.shutdown()).await {
+                Ok(Ok(())) => output::print_info("OTLP metrics flushed"),
+                Ok(Err(err)) => output::print_info(&format!("OTLP metrics not flushed: {err}")),
+                Err(err) => output::print_info(&format!("OTLP metrics not flushed: {err}")),
+            }
+        }
+    };
 
-    // Set up performance metrics (Phase 5)
-    let chain_metrics = ChainMetrics::default_window();
+    // Set up performance metrics output
     let metrics_interval = cli.metrics_interval;
+    #[cfg(feature = "otlp-metrics")]
+    if let Some(telemetry) = &telemetry {
+        telemetry.export_chain_metrics(chain_metrics.clone());
+        output::print_feature("OTLP metrics", telemetry.endpoint());
+    }
 
     // Spawn block monitoring task (single subscription)
     let monitoring_chain_spec = chain_spec_arc.clone();
@@ -517,32 +1090,7 @@ async fn main() -> eyre::Result<()> {
                     .with_gas_used(gas_used)
                     .with_tx_count(tx_count);
                 for (addr, account) in outcome.bundle_accounts_iter() {
-                    // Account-level changes: balance, nonce, code
-                    match (&account.original_info, &account.info) {
-                        (Some(old), Some(new)) => {
-                            if old.balance != new.balance {
-                                diff_builder.record_balance_change(addr, old.balance, new.balance);
-                            }
-                            if old.nonce != new.nonce {
-                                diff_builder.record_nonce_change(addr, old.nonce, new.nonce);
-                            }
-                            if old.code_hash != new.code_hash {
-                                diff_builder.record_code_change(addr);
-                            }
-                        }
-                        (None, Some(_)) => diff_builder.record_code_change(addr), // created
-                        (Some(_), None) => diff_builder.record_code_change(addr), // destroyed
-                        (None, None) => {}
-                    }
-                    // Storage-slot changes
-                    for (slot_key, slot) in &account.storage {
-                        if slot.is_changed() {
-                            let old =
-                                B256::from(slot.previous_or_original_value.to_be_bytes::<32>());
-                            let new = B256::from(slot.present_value.to_be_bytes::<32>());
-                            diff_builder.record_storage_change(addr, *slot_key, old, new);
-                        }
-                    }
+                    diff_builder.record_bundle_account(addr, account);
                 }
                 let state_diff = diff_builder.build();
                 (state_diff.touched_account_count(), state_diff.total_storage_changes())
@@ -597,11 +1145,21 @@ async fn main() -> eyre::Result<()> {
             // Print metrics report at configured interval
             if metrics_interval > 0 && block_num > 0 && block_num.is_multiple_of(metrics_interval) {
                 let snap = monitoring_metrics.snapshot();
+                let sig_cache = signature_cache.stats();
+                let compression = if snap.block_compressed_bytes > 0 {
+                    format!(" compression_ratio={:.2}", snap.compression_ratio())
+                } else {
+                    String::new()
+                };
                 println!(
-                    "  [metrics] block={} total_txs={} in_turn_rate={:.1}%",
+                    "  [metrics] block={} total_txs={} in_turn_rate={:.1}% \
+                     sig_cache_hits={} sig_cache_misses={}{}",
                     block_num,
                     snap.total_txs,
                     snap.in_turn_rate() * 100.0,
+                    sig_cache.hits,
+                    sig_cache.misses,
+                    compression,
                 );
             }
         }
@@ -613,6 +1171,107 @@ async fn main() -> eyre::Result<()> {
     output::print_chain_data(&cli.datadir, mining_interval);
     output::print_running(&cli.http_addr, cli.http_port, &cli.ws_addr, cli.ws_port);
 
-    // Keep the node running
-    node_exit_future.await
+    // Keep the node running until it exits or a shutdown signal has been handled; an
+    // out-of-space exit is reported with recovery steps.
+    let exit = tokio::select! {
+        exit = node_exit_future => exit,
+        () = shutdown_signal => Ok(()),
+    };
+    if let Some(watcher) = keystore_watcher {
+        watcher.abort();
+    }
+    exit.map_err(|err| disk::explain_exit_error(err, &cli.datadir, alert_sink.as_ref()))
+}
+
+/// `revert`: remove every block above `args.to` from the stopped node's datadir.
+/// Re-tally the votes of the canonical blocks from the epoch block at or below `tip`.
+fn tally_epoch_votes<P>(provider: &P, consensus: &PoaConsensus, votes: &VoterSet, tip: u64)
+where
+    P: reth_storage_api::HeaderProvider<Header = alloy_consensus::Header>,
+{
+    votes.reset();
+    let start = tip - tip % consensus.chain_spec().epoch();
+    match provider.sealed_headers_range(start..=tip) {
+        Ok(headers) => headers
+            .iter()
+            .for_each(|header| tally_vote(consensus, votes, header)),
+        Err(err) => output::print_info(&format!("Signer votes: reading headers failed: {err}")),
+    }
+}
+
+/// Count the signer vote sealed in canonical `header`, if it carries one.
+fn tally_vote(
+    consensus: &PoaConsensus,
+    votes: &VoterSet,
+    header: &reth_primitives_traits::SealedHeader,
+) {
+    let Ok(signer) = consensus.recover_sealed_signer(header) else {
+        return;
+    };
+    let chain_spec = consensus.chain_spec();
+    let finalized = chain_spec.with_effective_signers(|signers| {
+        votes.apply_header(chain_spec.epoch(), header, signer, signers)
+    });
+    if let Some(action) = finalized {
+        output::print_info(&format!(
+            "Signer vote {:?} for {} reached quorum at block {}",
+            action,
+            Address::from_slice(&header.mix_hash[..20]),
+            header.number
+        ));
+    }
+}
+
+fn run_revert(
+    args: &RevertArgs,
+    datadir: &std::path::Path,
+    chain_spec: Arc<reth_ethereum::chainspec::ChainSpec>,
+) -> eyre::Result<()> {
+    let store = revert::open_chain_store(datadir, chain_spec)?;
+    let removed = revert::revert_chain(&store, args.to, &args.reason)?;
+    output::print_info(&format!(
+        "Reverted {removed} blocks; block {} is the new tip",
+        args.to
+    ));
+    Ok(())
+}
+
+/// `keygen`: generate a signer key (random or from a mnemonic), or check a saved one.
+fn run_keygen(args: &KeygenArgs) -> eyre::Result<()> {
+    if let Some(path) = &args.verify {
+        let address = keygen::verify_key_file(path)?;
+        output::print_info(&format!("Key file {}: address {address}", path.display()));
+        return Ok(());
+    }
+    let key = match &args.mnemonic {
+        Some(phrase) => keygen::key_from_mnemonic(phrase)?,
+        None => keygen::generate_key(),
+    };
+    output::print_info(&format!("Address:     {}", key.address()));
+    output::print_info(&format!("Private key: {}", keygen::private_key_hex(&key)));
+    if let Some(path) = &args.output {
+        keygen::save_key(&key, path)?;
+        output::print_info(&format!("Saved to {} (use with --signer-key)", path.display()));
+    }
+    Ok(())
+}
+
+/// `--verify-db`: read-only consistency check of the node database.
+fn verify_db(db_path: &std::path::Path) -> eyre::Result<()> {
+    let report = disk::verify_database(db_path)?;
+    match report.tip {
+        Some((number, hash)) => output::print_info(&format!(
+            "Database {}: tip block {number} ({hash}), {} canonical / {} headers",
+            db_path.display(),
+            report.canonical_headers,
+            report.headers
+        )),
+        None => output::print_info(&format!("Database {}: empty", db_path.display())),
+    }
+    if report.is_ok() {
+        output::print_info("Database is consistent");
+        Ok(())
+    } else {
+        Err(eyre::eyre!("Database is inconsistent:\n  {}", report.issues.join("\n  ")))
+    }
 }
@@ -10,10 +10,16 @@
 //! - Gas throughput (gas/second, rolling window)
 //! - Cache hit/miss rates (from `cache::CacheStats`)
 //! - Signer turn statistics (in-turn vs out-of-turn blocks)
+//! - Searcher bundles included and rejected by the payload builder
 //!
 //! # Design
 //! Uses `std::sync::atomic` counters for thread-safe updates without locking.
 //! Heavy operations (window computation) acquire a `Mutex` only on read.
+//! With the `otlp-metrics` feature, [`otlp`] pushes the counters to an
+//! OpenTelemetry collector.
+
+#[cfg(feature = "otlp-metrics")]
+pub mod otlp;
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
@@ -144,6 +150,14 @@ pub struct MetricsSnapshot {
     pub in_turn_blocks: u64,
     /// Blocks produced out-of-turn.
     pub out_of_turn_blocks: u64,
+    /// Bundles placed in built payloads.
+    pub bundles_included: u64,
+    /// Bundles skipped because a transaction would fail.
+    pub bundles_rejected: u64,
+    /// RLP size of the block bodies measured for compression.
+    pub block_raw_bytes: u64,
+    /// zstd-compressed size of those block bodies.
+    pub block_compressed_bytes: u64,
     /// Rolling average TPS over the last N blocks.
     pub rolling_tps: f64,
     /// Rolling average gas/second over the last N blocks.
@@ -155,6 +169,16 @@ pub struct MetricsSnapshot {
 }
 
 impl MetricsSnapshot {
+    /// Raw / compressed bytes over all measured block bodies (0.0 before any).
+    #[inline]
+    pub fn compression_ratio(&self) -> f64 {
+        if self.block_compressed_bytes == 0 {
+            0.0
+        } else {
+            self.block_raw_bytes as f64 / self.block_compressed_bytes as f64
+        }
+    }
+
     /// Fraction of blocks produced in-turn.
     #[inline]
     pub fn in_turn_rate(&self) -> f64 {
@@ -193,6 +217,7 @@ impl MetricsSnapshot {
 ///
 /// Uses atomics for hot-path counters and a `Mutex<SlidingWindow>` only
 /// for the rolling-window computations read infrequently.
+#[derive(Debug)]
 pub struct ChainMetrics {
     // Atomic counters (written on every block)
     total_blocks: AtomicU64,
@@ -200,6 +225,12 @@ pub struct ChainMetrics {
     total_gas: AtomicU64,
     in_turn_blocks: AtomicU64,
     out_of_turn_blocks: AtomicU64,
+    // Written by the payload builder once per sealed block with bundle support
+    bundles_included: AtomicU64,
+    bundles_rejected: AtomicU64,
+    // Written by the payload builder when compression stats are enabled
+    block_raw_bytes: AtomicU64,
+    block_compressed_bytes: AtomicU64,
 
     // Rolling windows (guarded by mutex, written on every block, read on demand)
     window: Mutex<BlockWindow>,
@@ -208,6 +239,7 @@ pub struct ChainMetrics {
     window_size: usize,
 }
 
+#[derive(Debug)]
 struct BlockWindow {
     build_ms: SlidingWindow<u64>,
     sign_ms: SlidingWindow<u64>,
@@ -238,6 +270,10 @@ impl ChainMetrics {
             total_gas: AtomicU64::new(0),
             in_turn_blocks: AtomicU64::new(0),
             out_of_turn_blocks: AtomicU64::new(0),
+            bundles_included: AtomicU64::new(0),
+            bundles_rejected: AtomicU64::new(0),
+            block_raw_bytes: AtomicU64::new(0),
+            block_compressed_bytes: AtomicU64::new(0),
             window: Mutex::new(BlockWindow::new(window_size)),
             window_size,
         }
@@ -272,6 +308,31 @@ impl ChainMetrics {
         }
     }
 
+    /// Record the bundle outcome of one sealed block.
+    ///
+    /// Counted once per sealed block, when the next build starts from it; the
+    /// payload's unpublished rebuilds are not counted.
+    pub fn record_bundles(&self, included: u64, rejected: u64) {
+        self.bundles_included.fetch_add(included, Ordering::Relaxed);
+        self.bundles_rejected.fetch_add(rejected, Ordering::Relaxed);
+    }
+
+    /// Record the RLP size of a built block body and its zstd-compressed size.
+    pub fn record_compression(&self, raw_bytes: u64, compressed_bytes: u64) {
+        self.block_raw_bytes.fetch_add(raw_bytes, Ordering::Relaxed);
+        self.block_compressed_bytes
+            .fetch_add(compressed_bytes, Ordering::Relaxed);
+    }
+
+    /// Raw / compressed bytes over all recorded block bodies (0.0 before any).
+    pub fn compression_ratio(&self) -> f64 {
+        let compressed = self.block_compressed_bytes.load(Ordering::Relaxed);
+        if compressed == 0 {
+            return 0.0;
+        }
+        self.block_raw_bytes.load(Ordering::Relaxed) as f64 / compressed as f64
+    }
+
     /// Take a snapshot of all metrics (momentary read — values may change concurrently).
     pub fn snapshot(&self) -> MetricsSnapshot {
         let total_blocks = self.total_blocks.load(Ordering::Relaxed);
@@ -279,6 +340,10 @@ impl ChainMetrics {
         let total_gas = self.total_gas.load(Ordering::Relaxed);
         let in_turn_blocks = self.in_turn_blocks.load(Ordering::Relaxed);
         let out_of_turn_blocks = self.out_of_turn_blocks.load(Ordering::Relaxed);
+        let bundles_included = self.bundles_included.load(Ordering::Relaxed);
+        let bundles_rejected = self.bundles_rejected.load(Ordering::Relaxed);
+        let block_raw_bytes = self.block_raw_bytes.load(Ordering::Relaxed);
+        let block_compressed_bytes = self.block_compressed_bytes.load(Ordering::Relaxed);
 
         let (rolling_tps, rolling_gas_per_second, rolling_build_ms, rolling_sign_ms) =
             if let Ok(w) = self.window.lock() {
@@ -310,6 +375,10 @@ impl ChainMetrics {
             total_gas,
             in_turn_blocks,
             out_of_turn_blocks,
+            bundles_included,
+            bundles_rejected,
+            block_raw_bytes,
+            block_compressed_bytes,
             rolling_tps,
             rolling_gas_per_second,
             rolling_build_ms,
@@ -329,6 +398,18 @@ impl ChainMetrics {
         self.total_txs.load(Ordering::Relaxed)
     }
 
+    /// Total RLP-encoded bytes of recorded block bodies.
+    #[inline]
+    pub fn block_raw_bytes(&self) -> u64 {
+        self.block_raw_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Total zstd-compressed bytes of recorded block bodies.
+    #[inline]
+    pub fn block_compressed_bytes(&self) -> u64 {
+        self.block_compressed_bytes.load(Ordering::Relaxed)
+    }
+
     /// Configured rolling window size.
     #[inline]
     pub fn window_size(&self) -> usize {
@@ -542,6 +623,30 @@ mod tests {
         assert!((snap.in_turn_rate() - 2.0 / 3.0).abs() < 1e-9);
     }
 
+    #[test]
+    fn test_chain_metrics_records_bundles() {
+        let metrics = ChainMetrics::new(10);
+        metrics.record_bundles(2, 1);
+        metrics.record_bundles(0, 3);
+
+        let snap = metrics.snapshot();
+        assert_eq!(snap.bundles_included, 2);
+        assert_eq!(snap.bundles_rejected, 4);
+    }
+
+    #[test]
+    fn test_chain_metrics_compression_ratio() {
+        let metrics = ChainMetrics::new(10);
+        assert_eq!(metrics.compression_ratio(), 0.0);
+
+        metrics.record_compression(3_000, 1_000);
+        metrics.record_compression(1_000, 1_000);
+        assert_eq!(metrics.block_raw_bytes(), 4_000);
+        assert_eq!(metrics.block_compressed_bytes(), 2_000);
+        assert!((metrics.compression_ratio() - 2.0).abs() < f64::EPSILON);
+        assert!((metrics.snapshot().compression_ratio() - 2.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_chain_metrics_default_window_arc() {
         let m = ChainMetrics::default_window();
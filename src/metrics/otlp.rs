@@ -0,0 +1,235 @@
+//! OpenTelemetry metrics export (feature `otlp-metrics`).
+//!
+//! Production deployments collect metrics centrally rather than scraping every
+//! node. [`OtlpTelemetry`] pushes the [`ChainMetrics`] counters to an OTLP collector
+//! over gRPC every [`EXPORT_INTERVAL`]; it is created by
+//! [`PoaNode::with_telemetry_endpoint`](crate::node::PoaNode::with_telemetry_endpoint)
+//! (`--otlp-endpoint`) and attached to the node's metrics after launch.
+//!
+//! Instruments are observable: the SDK reads the atomic counters at each export, so
+//! the block path pays nothing for telemetry.
+
+use super::{ChainMetrics, MetricsSnapshot};
+use opentelemetry::metrics::{Meter, MeterProvider};
+use opentelemetry_otlp::{MetricExporter, WithExportConfig};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::Resource;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often metrics are pushed to the collector.
+pub const EXPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// `service.name` reported to the collector.
+pub const SERVICE_NAME: &str = "meowchain";
+
+/// OTLP exporter setup failure.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TelemetryError {
+    /// The endpoint is not an `http(s)://host:port` URL
+    #[error("Invalid OTLP endpoint: {0}")]
+    InvalidEndpoint(String),
+    /// The gRPC exporter could not be created
+    #[error("OTLP exporter failed: {0}")]
+    ExporterFailed(String),
+}
+
+/// Check that `endpoint` is an `http://` or `https://` URL with a host.
+pub fn validate_endpoint(endpoint: &str) -> Result<(), TelemetryError> {
+    let invalid = || TelemetryError::InvalidEndpoint(endpoint.to_string());
+    let authority = endpoint
+        .strip_prefix("http://")
+        .or_else(|| endpoint.strip_prefix("https://"))
+        .ok_or_else(invalid)?;
+    let host = authority.split('/').next().unwrap_or_default();
+    if host.is_empty() || host.starts_with(':') || host.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// Meter provider pushing to an OTLP collector.
+#[derive(Debug, Clone)]
+pub struct OtlpTelemetry {
+    endpoint: String,
+    provider: SdkMeterProvider,
+}
+
+impl OtlpTelemetry {
+    /// Create a gRPC exporter for `endpoint` (e.g. `http://collector:4317`).
+    ///
+    /// The connection is established lazily; an unreachable collector only logs
+    /// export errors. Must be called within a tokio runtime.
+    pub fn new(endpoint: &str) -> Result<Self, TelemetryError> {
+        validate_endpoint(endpoint)?;
+        let exporter = MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|err| TelemetryError::ExporterFailed(err.to_string()))?;
+        let reader = PeriodicReader::builder(exporter)
+            .with_interval(EXPORT_INTERVAL)
+            .build();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(reader)
+            .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+            .build();
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            provider,
+        })
+    }
+
+    /// Collector endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Export `metrics` from now on.
+    pub fn export_chain_metrics(&self, metrics: Arc<ChainMetrics>) {
+        register_chain_metrics(&self.provider.meter(SERVICE_NAME), metrics);
+    }
+
+    /// Push pending metrics and stop exporting.
+    pub fn shutdown(&self) -> Result<(), TelemetryError> {
+        self.provider
+            .shutdown()
+            .map_err(|err| TelemetryError::ExporterFailed(err.to_string()))
+    }
+}
+
+/// Exported counters: name, description and the snapshot field read.
+const COUNTERS: [(&str, &str, fn(&MetricsSnapshot) -> u64); 9] = [
+    ("blocks_produced_total", "Blocks produced", |s| {
+        s.total_blocks
+    }),
+    ("transactions_total", "Transactions included", |s| {
+        s.total_txs
+    }),
+    ("gas_used_total", "Gas used", |s| s.total_gas),
+    ("in_turn_blocks_total", "Blocks sealed in turn", |s| {
+        s.in_turn_blocks
+    }),
+    (
+        "out_of_turn_blocks_total",
+        "Blocks sealed out of turn",
+        |s| s.out_of_turn_blocks,
+    ),
+    ("bundles_included_total", "Searcher bundles included", |s| {
+        s.bundles_included
+    }),
+    ("bundles_rejected_total", "Searcher bundles rejected", |s| {
+        s.bundles_rejected
+    }),
+    (
+        "block_body_raw_bytes_total",
+        "RLP size of the block bodies measured for compression",
+        |s| s.block_raw_bytes,
+    ),
+    (
+        "block_body_compressed_bytes_total",
+        "zstd-compressed size of the measured block bodies",
+        |s| s.block_compressed_bytes,
+    ),
+];
+
+/// Register observable counters reading `metrics` on `meter`.
+pub fn register_chain_metrics(meter: &Meter, metrics: Arc<ChainMetrics>) {
+    for (name, description, read) in COUNTERS {
+        let metrics = metrics.clone();
+        meter
+            .u64_observable_counter(name)
+            .with_description(description)
+            .with_callback(move |observer| observer.observe(read(&metrics.snapshot()), &[]))
+            .build();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "otlp-metrics-testing")]
+    use crate::metrics::BlockMetrics;
+    #[cfg(feature = "otlp-metrics-testing")]
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+    #[cfg(feature = "otlp-metrics-testing")]
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+
+    /// Last exported value of the counter `name`.
+    #[cfg(feature = "otlp-metrics-testing")]
+    fn exported_counter(exporter: &InMemoryMetricExporter, name: &str) -> Option<u64> {
+        let exported = exporter.get_finished_metrics().unwrap();
+        let resource = exported.last()?;
+        let metric = resource
+            .scope_metrics()
+            .flat_map(|scope| scope.metrics())
+            .find(|metric| metric.name() == name)?;
+        match metric.data() {
+            AggregatedMetrics::U64(MetricData::Sum(sum)) => {
+                sum.data_points().next().map(|point| point.value())
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "otlp-metrics-testing")]
+    fn test_blocks_produced_total_exported() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let metrics = ChainMetrics::default_window();
+        register_chain_metrics(&provider.meter(SERVICE_NAME), metrics.clone());
+
+        for block_number in 1..=3 {
+            metrics.record_block(&BlockMetrics {
+                block_number,
+                tx_count: 2,
+                gas_used: 42_000,
+                build_duration: Duration::ZERO,
+                sign_duration: Duration::ZERO,
+                in_turn: block_number != 2,
+            });
+        }
+        provider.force_flush().unwrap();
+
+        assert_eq!(
+            exported_counter(&exporter, "blocks_produced_total"),
+            Some(3)
+        );
+        assert_eq!(exported_counter(&exporter, "transactions_total"), Some(6));
+        assert_eq!(exported_counter(&exporter, "gas_used_total"), Some(126_000));
+        assert_eq!(
+            exported_counter(&exporter, "out_of_turn_blocks_total"),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_validate_endpoint() {
+        assert!(validate_endpoint("http://localhost:4317").is_ok());
+        assert!(validate_endpoint("https://collector.example.com").is_ok());
+        for bad in [
+            "localhost:4317",
+            "grpc://localhost:4317",
+            "http://",
+            "http://:4317",
+        ] {
+            assert_eq!(
+                validate_endpoint(bad),
+                Err(TelemetryError::InvalidEndpoint(bad.to_string()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_exporter() {
+        let telemetry = OtlpTelemetry::new("http://127.0.0.1:4317").unwrap();
+        assert_eq!(telemetry.endpoint(), "http://127.0.0.1:4317");
+        assert!(matches!(
+            OtlpTelemetry::new("127.0.0.1:4317"),
+            Err(TelemetryError::InvalidEndpoint(_))
+        ));
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! Thread safety: all fields are [`AtomicU64`] — no locks required on write.
 
+use crate::clock::{ClockSource, SystemClock};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -61,13 +62,9 @@ impl MetricsRegistry {
     pub fn new(chain_id: u64) -> Self {
         let registry = Self::default();
         registry.chain_id.store(chain_id, Ordering::Relaxed);
-        registry.start_time.store(
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            Ordering::Relaxed,
-        );
+        registry
+            .start_time
+            .store(SystemClock.unix_secs(), Ordering::Relaxed);
         registry
     }
 
@@ -0,0 +1,89 @@
+//! Operator alerts.
+//!
+//! Conditions that need a human (disk nearly full, storage failures) are raised
+//! through an [`AlertSink`] rather than by panicking. The default
+//! [`LogAlertSink`] emits them as `poa::alerts` tracing events; deployments can
+//! plug in a pager or webhook by implementing the trait.
+
+use reth_tracing::tracing::{error, warn};
+use std::fmt::Debug;
+
+/// How urgent an alert is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertSeverity {
+    /// Degraded but operating normally (e.g. disk space low).
+    Warning,
+    /// Functionality has been withheld or the node is stopping.
+    Critical,
+}
+
+/// A single operator alert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    pub severity: AlertSeverity,
+    /// Component raising the alert (e.g. `"disk"`).
+    pub source: &'static str,
+    pub message: String,
+}
+
+impl Alert {
+    /// Create a warning alert.
+    pub fn warning(source: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity: AlertSeverity::Warning,
+            source,
+            message: message.into(),
+        }
+    }
+
+    /// Create a critical alert.
+    pub fn critical(source: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity: AlertSeverity::Critical,
+            source,
+            message: message.into(),
+        }
+    }
+}
+
+/// Destination for operator alerts.
+pub trait AlertSink: Send + Sync + Debug {
+    /// Deliver an alert. Must not block for long or panic.
+    fn alert(&self, alert: &Alert);
+}
+
+/// Alert sink that writes alerts to the tracing log.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogAlertSink;
+
+impl AlertSink for LogAlertSink {
+    fn alert(&self, alert: &Alert) {
+        match alert.severity {
+            AlertSeverity::Warning => {
+                warn!(target: "poa::alerts", source = alert.source, "{}", alert.message)
+            }
+            AlertSeverity::Critical => {
+                error!(target: "poa::alerts", source = alert.source, "{}", alert.message)
+            }
+        }
+    }
+}
+
+/// Alert sink that keeps every alert in memory (tests).
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct RecordingAlertSink(pub(crate) std::sync::Mutex<Vec<Alert>>);
+
+#[cfg(test)]
+impl RecordingAlertSink {
+    pub(crate) fn alerts(&self) -> Vec<Alert> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl AlertSink for RecordingAlertSink {
+    fn alert(&self, alert: &Alert) {
+        self.0.lock().unwrap().push(alert.clone());
+    }
+}
@@ -0,0 +1,102 @@
+//! Payload attributes of locally mined blocks.
+//!
+//! In dev mode reth's local miner asks [`LocalPayloadAttributesBuilder`] for the
+//! attributes of every block, which sets a zero `parent_beacon_block_root` from
+//! Cancun on. With a stubbed beacon roots contract
+//! ([`PoaChainSpec::with_eip4788_beacon_root`](crate::chainspec::PoaChainSpec::with_eip4788_beacon_root))
+//! consensus requires the stub's root instead, so [`PoaPayloadAttributesBuilder`]
+//! replaces it. The stub root is read back from the genesis alloc, as the local miner
+//! only sees reth's chain spec.
+
+use alloy_eips::eip4788::BEACON_ROOTS_ADDRESS;
+use alloy_primitives::B256;
+use reth_chainspec::ChainSpec;
+use reth_ethereum::engine::local::LocalPayloadAttributesBuilder;
+use reth_ethereum::node::api::PayloadAttributesBuilder;
+use reth_ethereum_engine_primitives::EthPayloadAttributes;
+use std::sync::Arc;
+
+/// [`LocalPayloadAttributesBuilder`] that carries the EIP-4788 stub root.
+#[derive(Debug)]
+pub struct PoaPayloadAttributesBuilder {
+    inner: LocalPayloadAttributesBuilder<ChainSpec>,
+    stub_root: Option<B256>,
+}
+
+impl PoaPayloadAttributesBuilder {
+    /// Attributes for blocks of `chain_spec`.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        let stub_root = chain_spec
+            .genesis
+            .alloc
+            .get(&BEACON_ROOTS_ADDRESS)
+            .and_then(crate::genesis::beacon_root_stub_root);
+        Self {
+            inner: LocalPayloadAttributesBuilder::new(chain_spec),
+            stub_root,
+        }
+    }
+}
+
+impl PayloadAttributesBuilder<EthPayloadAttributes> for PoaPayloadAttributesBuilder {
+    fn build(&self, timestamp: u64) -> EthPayloadAttributes {
+        let mut attributes = self.inner.build(timestamp);
+        // Pre-Cancun attributes carry no root and stay without one.
+        if let (Some(_), Some(root)) = (attributes.parent_beacon_block_root, self.stub_root) {
+            attributes.parent_beacon_block_root = Some(root);
+        }
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::PoaChainSpec;
+    use crate::consensus::PoaConsensus;
+    use alloy_consensus::Header;
+    use reth_consensus::HeaderValidator;
+    use reth_primitives_traits::SealedHeader;
+
+    /// Header of a block mined locally from `attributes`, with the fork fields of
+    /// the dev chain (Prague at genesis).
+    fn local_block(attributes: &EthPayloadAttributes) -> SealedHeader<Header> {
+        SealedHeader::seal_slow(Header {
+            number: 1,
+            timestamp: attributes.timestamp,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(0),
+            withdrawals_root: Some(alloy_consensus::constants::EMPTY_WITHDRAWALS),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: attributes.parent_beacon_block_root,
+            requests_hash: Some(alloy_eips::eip7685::EMPTY_REQUESTS_HASH),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_local_block_with_stub_root_passes_validation() {
+        let root = B256::repeat_byte(0x42);
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_eip4788_beacon_root(root));
+        let builder = PoaPayloadAttributesBuilder::new(chain.inner().clone());
+
+        let attributes = builder.build(12);
+        assert_eq!(attributes.parent_beacon_block_root, Some(root));
+        let consensus = PoaConsensus::new_dev(chain.clone());
+        assert!(HeaderValidator::validate_header(&consensus, &local_block(&attributes)).is_ok());
+
+        // reth's builder alone produces a block the stubbed chain rejects.
+        let reth_attributes = LocalPayloadAttributesBuilder::new(chain.inner().clone()).build(12);
+        let rejected = local_block(&reth_attributes);
+        assert!(HeaderValidator::validate_header(&consensus, &rejected).is_err());
+    }
+
+    #[test]
+    fn test_no_stub_keeps_reth_attributes() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let builder = PoaPayloadAttributesBuilder::new(chain.inner().clone());
+        assert_eq!(builder.stub_root, None);
+        assert_eq!(builder.build(12).parent_beacon_block_root, Some(B256::ZERO));
+    }
+}
@@ -0,0 +1,390 @@
+//! Deterministic dev-mode instant sealing ("automine").
+//!
+//! Test frameworks such as Hardhat and Foundry expect every submitted transaction
+//! to be mined immediately, with `eth_sendRawTransaction` returning only once the
+//! containing block is canonical, so a receipt query that follows is answered
+//! without polling. With `--automine` the node runs reth's local miner in
+//! tx-triggered mode, and:
+//!
+//! - the RPC middleware ([`AutomineLayer`](crate::rpc::AutomineLayer)) holds each
+//!   submission until [`InclusionTracker`] sees the transaction in a canonical
+//!   block, or the timeout elapses (the hash is returned either way);
+//! - the mining trigger ([`EagerTrigger`](super::mining::EagerTrigger)) waits on
+//!   [`AutomineControl::wait_for_mining_slot`] before firing reth's local miner. In
+//!   automine mode it fires at once, so each transaction gets its own block unless
+//!   several arrive within the same tick.
+//!
+//! `evm_setAutomine` / `evm_setIntervalMining` switch modes at runtime like Anvil:
+//! with automine off, builds are held to the mining interval since the last
+//! canonical block, or not triggered at all when no interval is set. Only the
+//! trigger task waits; no thread is held.
+
+use alloy_primitives::B256;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, Notify};
+
+/// Default time `eth_sendRawTransaction` waits for inclusion in automine mode.
+pub const DEFAULT_AUTOMINE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of recently mined transaction hashes remembered for late waiters.
+const RECENT_INCLUSIONS: usize = 4_096;
+
+/// How blocks are triggered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningMode {
+    /// Mine as soon as a transaction arrives
+    Auto,
+    /// Mine at most once per interval
+    Interval(Duration),
+    /// Do not mine until automine or interval mining is re-enabled
+    Manual,
+}
+
+/// Decision for a pending block build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningGate {
+    /// Build now
+    Now,
+    /// Build after the given delay
+    After(Duration),
+    /// Wait for a mode change
+    Hold,
+}
+
+#[derive(Debug)]
+struct ControlState {
+    automine: bool,
+    interval: Option<Duration>,
+    last_block: Option<Instant>,
+}
+
+/// Shared automine switch (cheap to clone).
+///
+/// A control created with [`disabled`](Self::disabled) never delays a build, so the
+/// mining trigger can consult it unconditionally.
+#[derive(Debug, Clone)]
+pub struct AutomineControl {
+    enabled: bool,
+    state: Arc<Mutex<ControlState>>,
+    changed: Arc<Notify>,
+}
+
+impl Default for AutomineControl {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl AutomineControl {
+    /// Control for a node started with `--automine`.
+    pub fn new() -> Self {
+        Self::with_state(true)
+    }
+
+    /// Control for a node without `--automine`: every build proceeds immediately.
+    pub fn disabled() -> Self {
+        Self::with_state(false)
+    }
+
+    fn with_state(enabled: bool) -> Self {
+        Self {
+            enabled,
+            state: Arc::new(Mutex::new(ControlState {
+                automine: enabled,
+                interval: None,
+                last_block: None,
+            })),
+            changed: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Whether the node was started in automine mode.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether submissions are currently mined immediately.
+    pub fn is_automine(&self) -> bool {
+        self.enabled && self.state.lock().unwrap().automine
+    }
+
+    /// Current mining mode.
+    pub fn mode(&self) -> MiningMode {
+        self.mode_of(&self.state.lock().unwrap())
+    }
+
+    fn mode_of(&self, state: &ControlState) -> MiningMode {
+        if !self.enabled || state.automine {
+            MiningMode::Auto
+        } else if let Some(interval) = state.interval {
+            MiningMode::Interval(interval)
+        } else {
+            MiningMode::Manual
+        }
+    }
+
+    /// `evm_setAutomine`: mine every submission immediately, or fall back to
+    /// interval mining (manual when no interval is set).
+    pub fn set_automine(&self, enabled: bool) {
+        self.update(|state| state.automine = enabled);
+    }
+
+    /// `evm_setIntervalMining`: mine every `interval_ms`; `0` disables interval
+    /// mining. A non-zero interval turns automine off, as in Anvil.
+    pub fn set_interval_mining(&self, interval_ms: u64) {
+        self.update(|state| {
+            if interval_ms == 0 {
+                state.interval = None;
+            } else {
+                state.interval = Some(Duration::from_millis(interval_ms));
+                state.automine = false;
+            }
+        });
+    }
+
+    fn update(&self, f: impl FnOnce(&mut ControlState)) {
+        f(&mut self.state.lock().unwrap());
+        self.changed.notify_waiters();
+    }
+
+    /// Record that a canonical block was imported at `now`.
+    pub fn record_block(&self, now: Instant) {
+        self.state.lock().unwrap().last_block = Some(now);
+    }
+
+    /// Whether a build may start at `now`.
+    pub fn mining_gate(&self, now: Instant) -> MiningGate {
+        self.gate_of(&self.state.lock().unwrap(), now)
+    }
+
+    fn gate_of(&self, state: &ControlState, now: Instant) -> MiningGate {
+        match self.mode_of(state) {
+            MiningMode::Auto => MiningGate::Now,
+            MiningMode::Manual => MiningGate::Hold,
+            MiningMode::Interval(interval) => {
+                let since_last = state.last_block.map(|at| now.saturating_duration_since(at));
+                match since_last.and_then(|elapsed| interval.checked_sub(elapsed)) {
+                    Some(delay) if !delay.is_zero() => MiningGate::After(delay),
+                    _ => MiningGate::Now,
+                }
+            }
+        }
+    }
+
+    /// Wait until the gate opens.
+    ///
+    /// Re-evaluates on every mode change, so toggling automine back on releases a
+    /// held build immediately.
+    pub async fn wait_for_mining_slot(&self) {
+        loop {
+            let mut changed = std::pin::pin!(self.changed.notified());
+            // Register before reading the gate, so a change in between is not missed.
+            changed.as_mut().enable();
+            match self.mining_gate(tokio::time::Instant::now().into_std()) {
+                MiningGate::Now => return,
+                MiningGate::After(delay) => {
+                    let _ = tokio::time::timeout(delay, changed).await;
+                }
+                MiningGate::Hold => changed.await,
+            }
+        }
+    }
+}
+
+/// Automine submission failures.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AutomineError {
+    /// The transaction was not seen in a canonical block within the timeout
+    #[error("Transaction {hash} not mined within {}ms", .timeout.as_millis())]
+    InclusionTimeout {
+        /// Submitted transaction hash
+        hash: B256,
+        /// Time waited
+        timeout: Duration,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Inclusions {
+    waiters: HashMap<B256, Vec<oneshot::Sender<u64>>>,
+    recent: HashMap<B256, u64>,
+    order: VecDeque<B256>,
+}
+
+/// Hand-off from canonical block import to waiting RPC submissions.
+///
+/// Recently mined hashes are remembered, so a waiter registering after its block
+/// was imported still resolves immediately.
+#[derive(Debug, Clone, Default)]
+pub struct InclusionTracker(Arc<Mutex<Inclusions>>);
+
+impl InclusionTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record canonical block `number` containing `hashes`, waking their waiters.
+    pub fn notify_block(&self, number: u64, hashes: impl IntoIterator<Item = B256>) {
+        let mut inner = self.0.lock().unwrap();
+        for hash in hashes {
+            for waiter in inner.waiters.remove(&hash).unwrap_or_default() {
+                let _ = waiter.send(number);
+            }
+            if inner.recent.insert(hash, number).is_none() {
+                inner.order.push_back(hash);
+            }
+        }
+        while inner.order.len() > RECENT_INCLUSIONS {
+            if let Some(old) = inner.order.pop_front() {
+                inner.recent.remove(&old);
+            }
+        }
+    }
+
+    /// Wait until `hash` is in a canonical block; returns the block number.
+    pub async fn wait_for(&self, hash: B256, timeout: Duration) -> Result<u64, AutomineError> {
+        let rx = {
+            let mut inner = self.0.lock().unwrap();
+            if let Some(&number) = inner.recent.get(&hash) {
+                return Ok(number);
+            }
+            let (tx, rx) = oneshot::channel();
+            inner.waiters.entry(hash).or_default().push(tx);
+            rx
+        };
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(number)) => Ok(number),
+            _ => {
+                self.prune_closed_waiters();
+                Err(AutomineError::InclusionTimeout { hash, timeout })
+            }
+        }
+    }
+
+    /// Number of hashes with at least one waiter.
+    pub fn pending_waiters(&self) -> usize {
+        self.0.lock().unwrap().waiters.len()
+    }
+
+    fn prune_closed_waiters(&self) {
+        let mut inner = self.0.lock().unwrap();
+        inner.waiters.retain(|_, senders| {
+            senders.retain(|s| !s.is_closed());
+            !senders.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(n: u8) -> B256 {
+        B256::repeat_byte(n)
+    }
+
+    #[test]
+    fn test_disabled_control_never_delays() {
+        let control = AutomineControl::disabled();
+        control.set_automine(false);
+        control.set_interval_mining(1_000);
+        assert!(!control.is_automine());
+        assert_eq!(control.mode(), MiningMode::Auto);
+        assert_eq!(control.mining_gate(Instant::now()), MiningGate::Now);
+    }
+
+    #[test]
+    fn test_toggle_automine_off_resumes_interval_mining() {
+        let control = AutomineControl::new();
+        let t0 = Instant::now();
+        control.record_block(t0);
+        assert_eq!(control.mining_gate(t0), MiningGate::Now);
+
+        control.set_interval_mining(2_000);
+        assert!(!control.is_automine());
+        assert_eq!(control.mode(), MiningMode::Interval(Duration::from_secs(2)));
+        assert_eq!(
+            control.mining_gate(t0 + Duration::from_millis(500)),
+            MiningGate::After(Duration::from_millis(1_500))
+        );
+        assert_eq!(
+            control.mining_gate(t0 + Duration::from_secs(2)),
+            MiningGate::Now
+        );
+
+        control.set_automine(true);
+        assert_eq!(control.mining_gate(t0), MiningGate::Now);
+        // Automine off keeps the configured interval.
+        control.set_automine(false);
+        assert_eq!(control.mode(), MiningMode::Interval(Duration::from_secs(2)));
+        control.set_interval_mining(0);
+        assert_eq!(control.mode(), MiningMode::Manual);
+        assert_eq!(control.mining_gate(t0), MiningGate::Hold);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_held_build_released_by_toggle() {
+        let control = AutomineControl::new();
+        control.set_automine(false);
+        let held = {
+            let control = control.clone();
+            tokio::spawn(async move { control.wait_for_mining_slot().await })
+        };
+        // Manual mode holds indefinitely.
+        tokio::time::sleep(Duration::from_secs(3_600)).await;
+        assert!(!held.is_finished());
+        control.set_automine(true);
+        held.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_interval_wait_counts_from_last_block() {
+        let control = AutomineControl::new();
+        control.set_interval_mining(2_000);
+        let start = tokio::time::Instant::now();
+        control.record_block(start.into_std());
+        control.wait_for_mining_slot().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_resolves_on_inclusion() {
+        let tracker = InclusionTracker::new();
+        let waiter = {
+            let tracker = tracker.clone();
+            tokio::spawn(async move { tracker.wait_for(hash(1), DEFAULT_AUTOMINE_TIMEOUT).await })
+        };
+        tokio::task::yield_now().await;
+        tracker.notify_block(7, [hash(2), hash(1)]);
+        assert_eq!(waiter.await.unwrap(), Ok(7));
+        assert_eq!(tracker.pending_waiters(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_after_inclusion_uses_recent_blocks() {
+        let tracker = InclusionTracker::new();
+        tracker.notify_block(3, [hash(9)]);
+        assert_eq!(
+            tracker.wait_for(hash(9), Duration::from_millis(1)).await,
+            Ok(3)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_times_out() {
+        let tracker = InclusionTracker::new();
+        let timeout = Duration::from_millis(10);
+        assert_eq!(
+            tracker.wait_for(hash(4), timeout).await,
+            Err(AutomineError::InclusionTimeout {
+                hash: hash(4),
+                timeout
+            })
+        );
+        assert_eq!(tracker.pending_waiters(), 0);
+    }
+}
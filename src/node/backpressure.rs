@@ -0,0 +1,497 @@
+//! Back-pressure between transaction ingress and block production.
+//!
+//! Under spam, `eth_sendRawTransaction` can accept transactions faster than blocks
+//! drain them. [`Backpressure`] tracks pool occupancy with hysteresis:
+//!
+//! - occupancy reaches `high_watermark` → **overloaded**:
+//!   - RPC submissions are rejected with `-32005` and a retry hint derived from the
+//!     observed drain rate ([`Backpressure::check_rpc_submission`]),
+//!   - gossip-originated transactions above the high watermark are shed, lowest fee
+//!     first ([`shed_gossip_transactions`]), so local submissions keep priority,
+//!   - eager (tx-triggered) block production is throttled to one block per block
+//!     period ([`Backpressure::eager_build_delay`], applied by
+//!     [`EagerTrigger`](super::mining::EagerTrigger)), so blocks fill up instead of
+//!     thrashing on every arrival;
+//! - occupancy falls to `low_watermark` → back to **normal**.
+//!
+//! Transitions are logged under `poa::backpressure` and counted; the current state
+//! is served by `poa_status`.
+
+use alloy_consensus::Transaction;
+use reth_tracing::tracing::{info, warn};
+use reth_transaction_pool::TransactionPool;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Weight of the newest block in the drain-rate moving average.
+const DRAIN_RATE_ALPHA: f64 = 0.3;
+
+/// Watermarks and behavior of the back-pressure controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackpressureConfig {
+    /// Pool occupancy (transactions) at which ingress is throttled
+    pub high_watermark: usize,
+    /// Pool occupancy at which normal operation resumes
+    pub low_watermark: usize,
+    /// Upper bound for the retry hint returned to RPC clients
+    pub max_retry_after: Duration,
+    /// Shed gossip-originated transactions above the high watermark while overloaded
+    pub shed_gossip: bool,
+    /// Hold eager block production to one block per block period while overloaded
+    pub throttle_eager_mining: bool,
+}
+
+impl BackpressureConfig {
+    /// Config with the given watermarks; `low` is clamped below `high`.
+    pub fn new(high_watermark: usize, low_watermark: usize) -> Self {
+        let high_watermark = high_watermark.max(1);
+        Self {
+            high_watermark,
+            low_watermark: low_watermark.min(high_watermark - 1),
+            ..Self::default()
+        }
+    }
+
+    /// Cap the retry hint returned to rejected RPC clients.
+    pub fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max.max(Duration::from_secs(1));
+        self
+    }
+
+    /// Enable or disable shedding of gossip-originated transactions.
+    pub fn with_gossip_shedding(mut self, enabled: bool) -> Self {
+        self.shed_gossip = enabled;
+        self
+    }
+
+    /// Enable or disable eager-mining throttling.
+    pub fn with_eager_throttling(mut self, enabled: bool) -> Self {
+        self.throttle_eager_mining = enabled;
+        self
+    }
+}
+
+impl Default for BackpressureConfig {
+    /// 8,000 / 4,000 transactions (reth's pending sub-pool holds 10,000), 30 s retry cap.
+    fn default() -> Self {
+        Self {
+            high_watermark: 8_000,
+            low_watermark: 4_000,
+            max_retry_after: Duration::from_secs(30),
+            shed_gossip: true,
+            throttle_eager_mining: true,
+        }
+    }
+}
+
+/// Ingress state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverloadState {
+    /// Occupancy below the high watermark (or recovered to the low watermark)
+    Normal,
+    /// Occupancy reached the high watermark and has not yet fallen to the low one
+    Overloaded,
+}
+
+/// RPC submission refused while overloaded.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum OverloadError {
+    /// The pool is above its high watermark
+    #[error("Server overloaded, retry after {}s", .retry_after.as_secs())]
+    Overloaded {
+        /// Pool occupancy when the submission was refused
+        occupancy: usize,
+        /// Estimated time until occupancy falls to the low watermark
+        retry_after: Duration,
+    },
+}
+
+/// Snapshot of the controller, served by `poa_status`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackpressureStatus {
+    pub state: OverloadState,
+    pub occupancy: usize,
+    pub high_watermark: usize,
+    pub low_watermark: usize,
+    /// Transactions included per second (moving average over recent blocks)
+    pub drain_rate: f64,
+    /// Retry hint currently given to rejected submissions, in seconds
+    pub retry_after_secs: u64,
+    /// Times the controller entered the overloaded state
+    pub overload_entered: u64,
+    /// Times it returned to normal
+    pub overload_cleared: u64,
+    /// RPC submissions rejected while overloaded
+    pub rpc_rejected: u64,
+    /// Gossip-originated transactions shed while overloaded
+    pub gossip_shed: u64,
+    /// Eager block builds held back to the block period
+    pub eager_suppressed: u64,
+}
+
+#[derive(Debug, Default)]
+struct DrainTracker {
+    /// Number and arrival time of the last recorded canonical block
+    last_block: Option<(u64, Instant)>,
+    /// Transactions per second
+    rate: f64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    overload_entered: AtomicU64,
+    overload_cleared: AtomicU64,
+    rpc_rejected: AtomicU64,
+    gossip_shed: AtomicU64,
+    eager_suppressed: AtomicU64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: BackpressureConfig,
+    overloaded: AtomicBool,
+    occupancy: AtomicUsize,
+    drain: Mutex<DrainTracker>,
+    counters: Counters,
+}
+
+/// Shared back-pressure controller.
+///
+/// Cheap to clone; the pool monitor, the eager-mining trigger and the RPC middleware
+/// all observe the same state.
+#[derive(Debug, Clone)]
+pub struct Backpressure(Arc<Inner>);
+
+impl Default for Backpressure {
+    fn default() -> Self {
+        Self::new(BackpressureConfig::default())
+    }
+}
+
+impl Backpressure {
+    /// Create a controller in the normal state.
+    pub fn new(config: BackpressureConfig) -> Self {
+        Self(Arc::new(Inner {
+            config,
+            overloaded: AtomicBool::new(false),
+            occupancy: AtomicUsize::new(0),
+            drain: Mutex::new(DrainTracker::default()),
+            counters: Counters::default(),
+        }))
+    }
+
+    /// The controller's configuration.
+    pub fn config(&self) -> &BackpressureConfig {
+        &self.0.config
+    }
+
+    /// Current ingress state.
+    #[inline]
+    pub fn state(&self) -> OverloadState {
+        if self.0.overloaded.load(Ordering::Acquire) {
+            OverloadState::Overloaded
+        } else {
+            OverloadState::Normal
+        }
+    }
+
+    /// Record the current pool occupancy. Returns the new state on a transition.
+    pub fn observe(&self, occupancy: usize) -> Option<OverloadState> {
+        let config = &self.0.config;
+        self.0.occupancy.store(occupancy, Ordering::Release);
+
+        if occupancy >= config.high_watermark {
+            if !self.0.overloaded.swap(true, Ordering::AcqRel) {
+                self.0
+                    .counters
+                    .overload_entered
+                    .fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    target: "poa::backpressure",
+                    occupancy,
+                    high_watermark = config.high_watermark,
+                    drain_rate = self.drain_rate(),
+                    "Transaction pool overloaded: throttling ingress"
+                );
+                return Some(OverloadState::Overloaded);
+            }
+        } else if occupancy <= config.low_watermark
+            && self.0.overloaded.swap(false, Ordering::AcqRel)
+        {
+            self.0
+                .counters
+                .overload_cleared
+                .fetch_add(1, Ordering::Relaxed);
+            info!(
+                target: "poa::backpressure",
+                occupancy,
+                low_watermark = config.low_watermark,
+                "Transaction pool recovered: ingress resumed"
+            );
+            return Some(OverloadState::Normal);
+        }
+        None
+    }
+
+    /// Record a canonical block containing `tx_count` transactions.
+    ///
+    /// Heights at or below the last recorded one (reorgs) are ignored.
+    pub fn record_block(&self, number: u64, tx_count: usize, now: Instant) {
+        let mut drain = self.0.drain.lock().unwrap();
+        match drain.last_block {
+            Some((last, _)) if number <= last => return,
+            Some((_, at)) => {
+                let elapsed = now.saturating_duration_since(at).as_secs_f64().max(1e-3);
+                let sample = tx_count as f64 / elapsed;
+                drain.rate = if drain.rate == 0.0 {
+                    sample
+                } else {
+                    DRAIN_RATE_ALPHA * sample + (1.0 - DRAIN_RATE_ALPHA) * drain.rate
+                };
+            }
+            None => {}
+        }
+        drain.last_block = Some((number, now));
+    }
+
+    /// Transactions included per second over recent blocks (0 until two blocks are seen).
+    pub fn drain_rate(&self) -> f64 {
+        self.0.drain.lock().unwrap().rate
+    }
+
+    /// Estimated time for the pool to drain to the low watermark.
+    ///
+    /// Clamped to `[1s, max_retry_after]`; the maximum while nothing has drained yet.
+    pub fn retry_after(&self) -> Duration {
+        let config = &self.0.config;
+        let excess = self
+            .0
+            .occupancy
+            .load(Ordering::Acquire)
+            .saturating_sub(config.low_watermark);
+        let rate = self.drain_rate();
+        if rate <= 0.0 {
+            return config.max_retry_after;
+        }
+        Duration::from_secs_f64((excess as f64 / rate).ceil())
+            .clamp(Duration::from_secs(1), config.max_retry_after)
+    }
+
+    /// Admit or refuse an RPC transaction submission.
+    pub fn check_rpc_submission(&self) -> Result<(), OverloadError> {
+        if self.state() == OverloadState::Normal {
+            return Ok(());
+        }
+        self.0.counters.rpc_rejected.fetch_add(1, Ordering::Relaxed);
+        Err(OverloadError::Overloaded {
+            occupancy: self.0.occupancy.load(Ordering::Acquire),
+            retry_after: self.retry_after(),
+        })
+    }
+
+    /// How long an eager build should wait so that, while overloaded, blocks are
+    /// at least `block_period` apart. `None` when the build may proceed now.
+    pub fn eager_build_delay(&self, now: Instant, block_period: Duration) -> Option<Duration> {
+        if !self.0.config.throttle_eager_mining || self.state() == OverloadState::Normal {
+            return None;
+        }
+        let (_, last) = self.0.drain.lock().unwrap().last_block?;
+        let delay = block_period.checked_sub(now.saturating_duration_since(last))?;
+        if delay.is_zero() {
+            return None;
+        }
+        self.0
+            .counters
+            .eager_suppressed
+            .fetch_add(1, Ordering::Relaxed);
+        Some(delay)
+    }
+
+    /// Number of gossip transactions to shed now (0 unless overloaded with shedding on).
+    pub fn gossip_excess(&self) -> usize {
+        if !self.0.config.shed_gossip || self.state() == OverloadState::Normal {
+            return 0;
+        }
+        self.0
+            .occupancy
+            .load(Ordering::Acquire)
+            .saturating_sub(self.0.config.high_watermark)
+    }
+
+    /// Count gossip transactions removed by [`shed_gossip_transactions`].
+    pub fn record_gossip_shed(&self, count: usize) {
+        self.0
+            .counters
+            .gossip_shed
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    /// Snapshot for `poa_status`.
+    pub fn status(&self) -> BackpressureStatus {
+        let counters = &self.0.counters;
+        BackpressureStatus {
+            state: self.state(),
+            occupancy: self.0.occupancy.load(Ordering::Acquire),
+            high_watermark: self.0.config.high_watermark,
+            low_watermark: self.0.config.low_watermark,
+            drain_rate: self.drain_rate(),
+            retry_after_secs: self.retry_after().as_secs(),
+            overload_entered: counters.overload_entered.load(Ordering::Relaxed),
+            overload_cleared: counters.overload_cleared.load(Ordering::Relaxed),
+            rpc_rejected: counters.rpc_rejected.load(Ordering::Relaxed),
+            gossip_shed: counters.gossip_shed.load(Ordering::Relaxed),
+            eager_suppressed: counters.eager_suppressed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sample `pool` every `interval`, updating the state and shedding gossip
+    /// transactions while overloaded. Runs until the task is dropped.
+    pub async fn monitor_pool<Pool: TransactionPool>(self, pool: Pool, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            self.observe(pool.pool_size().total);
+            let excess = self.gossip_excess();
+            if excess > 0 {
+                let shed = shed_gossip_transactions(&pool, excess);
+                self.record_gossip_shed(shed);
+            }
+        }
+    }
+}
+
+/// Remove up to `count` gossip-originated (external) transactions, lowest fee first.
+///
+/// Locally submitted transactions are never removed. Returns the number removed.
+pub fn shed_gossip_transactions<Pool: TransactionPool>(pool: &Pool, count: usize) -> usize {
+    let mut external: Vec<_> = pool
+        .pooled_transactions()
+        .into_iter()
+        .filter(|tx| tx.origin.is_external())
+        .collect();
+    external.sort_by_key(|tx| tx.transaction.max_fee_per_gas());
+    let victims: Vec<_> = external.iter().take(count).map(|tx| *tx.hash()).collect();
+    if victims.is_empty() {
+        return 0;
+    }
+    let shed = pool.remove_transactions(victims).len();
+    warn!(target: "poa::backpressure", shed, "Shed gossip transactions while overloaded");
+    shed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller(high: usize, low: usize) -> Backpressure {
+        Backpressure::new(BackpressureConfig::new(high, low))
+    }
+
+    #[test]
+    fn test_config_clamps_low_below_high() {
+        let config = BackpressureConfig::new(100, 500);
+        assert_eq!(config.high_watermark, 100);
+        assert_eq!(config.low_watermark, 99);
+    }
+
+    #[test]
+    fn test_hysteresis_between_watermarks() {
+        let bp = controller(100, 40);
+        assert_eq!(bp.observe(99), None);
+        assert_eq!(bp.observe(100), Some(OverloadState::Overloaded));
+        assert_eq!(bp.observe(150), None);
+        // Falling below high is not enough; the low watermark must be reached.
+        assert_eq!(bp.observe(60), None);
+        assert_eq!(bp.state(), OverloadState::Overloaded);
+        assert_eq!(bp.observe(40), Some(OverloadState::Normal));
+        assert_eq!(bp.observe(99), None);
+
+        let status = bp.status();
+        assert_eq!(status.overload_entered, 1);
+        assert_eq!(status.overload_cleared, 1);
+    }
+
+    #[test]
+    fn test_rpc_rejection_retry_hint_from_drain_rate() {
+        let bp = controller(1_000, 200);
+        let t0 = Instant::now();
+        bp.record_block(1, 0, t0);
+        bp.record_block(2, 100, t0 + Duration::from_secs(2)); // 50 tx/s
+        assert!(bp.check_rpc_submission().is_ok());
+
+        bp.observe(1_200);
+        match bp.check_rpc_submission() {
+            Err(OverloadError::Overloaded {
+                occupancy,
+                retry_after,
+            }) => {
+                assert_eq!(occupancy, 1_200);
+                // (1200 - 200) / 50 tx/s
+                assert_eq!(retry_after, Duration::from_secs(20));
+            }
+            other => panic!("Expected Overloaded, got {other:?}"),
+        }
+        assert_eq!(bp.status().rpc_rejected, 1);
+    }
+
+    #[test]
+    fn test_retry_hint_capped_without_drain() {
+        let bp = Backpressure::new(
+            BackpressureConfig::new(10, 5).with_max_retry_after(Duration::from_secs(7)),
+        );
+        bp.observe(10);
+        assert_eq!(bp.retry_after(), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn test_reorged_height_does_not_skew_drain_rate() {
+        let bp = controller(10, 5);
+        let t0 = Instant::now();
+        bp.record_block(1, 0, t0);
+        bp.record_block(2, 20, t0 + Duration::from_secs(2));
+        bp.record_block(2, 5_000, t0 + Duration::from_secs(2));
+        assert_eq!(bp.drain_rate(), 10.0);
+    }
+
+    #[test]
+    fn test_eager_builds_held_to_block_period_while_overloaded() {
+        let bp = controller(10, 5);
+        let t0 = Instant::now();
+        let period = Duration::from_secs(2);
+        bp.record_block(1, 3, t0);
+
+        let soon = t0 + Duration::from_millis(500);
+        assert_eq!(bp.eager_build_delay(soon, period), None);
+
+        bp.observe(10);
+        assert_eq!(
+            bp.eager_build_delay(soon, period),
+            Some(Duration::from_millis(1_500))
+        );
+        assert_eq!(bp.eager_build_delay(t0 + period, period), None);
+        assert_eq!(bp.status().eager_suppressed, 1);
+
+        let relaxed =
+            Backpressure::new(BackpressureConfig::new(10, 5).with_eager_throttling(false));
+        relaxed.record_block(1, 3, t0);
+        relaxed.observe(10);
+        assert_eq!(relaxed.eager_build_delay(soon, period), None);
+    }
+
+    #[test]
+    fn test_gossip_excess_only_while_overloaded() {
+        let bp = controller(100, 50);
+        bp.observe(90);
+        assert_eq!(bp.gossip_excess(), 0);
+        bp.observe(130);
+        assert_eq!(bp.gossip_excess(), 30);
+
+        let keep = Backpressure::new(BackpressureConfig::new(100, 50).with_gossip_shedding(false));
+        keep.observe(130);
+        assert_eq!(keep.gossip_excess(), 0);
+    }
+}
@@ -1,12 +1,18 @@
 use crate::chainspec::PoaChainSpec;
-use crate::consensus::PoaConsensus;
-use crate::output;
+use crate::consensus::{
+    Checkpoint, GasLimitSource, PoaConsensus, SignerSetSource, SnapshotStore, StakeSource, TxTypes,
+};
+use crate::signer::SignatureCache;
+use alloy_primitives::B256;
 use reth_ethereum::node::builder::{
     components::ConsensusBuilder,
     node::{FullNodeTypes, NodeTypes},
     BuilderContext,
 };
 use reth_ethereum::EthPrimitives;
+use reth_storage_api::BlockHashReader;
+use reth_tracing::tracing::{info, warn};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Custom consensus builder that provides `PoaConsensus` instead of `EthBeaconConsensus`.
@@ -20,6 +26,22 @@ pub struct PoaConsensusBuilder {
     chain_spec: Arc<PoaChainSpec>,
     /// Whether to create consensus in dev mode (relaxed validation)
     pub dev_mode: bool,
+    /// Trusted checkpoint below which header validation is skipped.
+    checkpoint: Option<Checkpoint>,
+    /// Transaction types allowed in blocks; None = those of the active forks.
+    allowed_tx_types: Option<TxTypes>,
+    /// Directory of persisted epoch snapshots the signer set is seeded from.
+    snapshot_dir: Option<PathBuf>,
+    /// Canonical hash of the head's last epoch block, whose snapshot is the seed.
+    head_epoch_hash: Option<B256>,
+    /// Balance source for the minimum signer stake.
+    stakes: Option<StakeSource>,
+    /// Parent-state gas limits for the gas limit ramp.
+    gas_limits: Option<GasLimitSource>,
+    /// Parent-state signer sets for epoch blocks.
+    signer_sets: Option<SignerSetSource>,
+    /// Recovered-sealer cache shared with the node; None = a default-sized one.
+    signature_cache: Option<Arc<SignatureCache>>,
 }
 
 impl PoaConsensusBuilder {
@@ -28,6 +50,14 @@ impl PoaConsensusBuilder {
         Self {
             chain_spec,
             dev_mode: false,
+            checkpoint: None,
+            allowed_tx_types: None,
+            snapshot_dir: None,
+            head_epoch_hash: None,
+            stakes: None,
+            gas_limits: None,
+            signer_sets: None,
+            signature_cache: None,
         }
     }
 
@@ -36,6 +66,127 @@ impl PoaConsensusBuilder {
         self.dev_mode = dev_mode;
         self
     }
+
+    /// Skip header validation below a trusted checkpoint
+    pub fn with_checkpoint(mut self, checkpoint: Option<Checkpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Restrict blocks to these EIP-2718 transaction types
+    pub fn with_allowed_tx_types(mut self, types: Option<TxTypes>) -> Self {
+        self.allowed_tx_types = types;
+        self
+    }
+
+    /// Seed the signer set from the epoch snapshots persisted in `dir`
+    pub fn with_snapshot_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.snapshot_dir = dir;
+        self
+    }
+
+    /// Seed only from the snapshot of this canonical epoch block, the head's last one
+    pub fn with_head_epoch_hash(mut self, hash: Option<B256>) -> Self {
+        self.head_epoch_hash = hash;
+        self
+    }
+
+    /// Memoize recovered sealers in `cache`
+    pub fn with_signature_cache(mut self, cache: Arc<SignatureCache>) -> Self {
+        self.signature_cache = Some(cache);
+        self
+    }
+
+    /// Read signer balances from `stakes` for the minimum signer stake
+    pub fn with_stake_source(mut self, stakes: StakeSource) -> Self {
+        self.stakes = Some(stakes);
+        self
+    }
+
+    /// Read parent gas limits from `gas_limits` to check gas limit ramp steps
+    pub fn with_gas_limit_source(mut self, gas_limits: GasLimitSource) -> Self {
+        self.gas_limits = Some(gas_limits);
+        self
+    }
+
+    /// Read the parent's signer set from `signer_sets` to check epoch signer lists
+    pub fn with_signer_set_source(mut self, signer_sets: SignerSetSource) -> Self {
+        self.signer_sets = Some(signer_sets);
+        self
+    }
+
+    /// Construct the consensus engine and emit the `POA consensus initialized` event.
+    ///
+    /// Library code never prints; the console summary is main's job.
+    pub fn build(self) -> Arc<PoaConsensus> {
+        let mode = if self.dev_mode {
+            "dev (relaxed)"
+        } else {
+            "production (strict)"
+        };
+        info!(
+            target: "poa::consensus",
+            signer_count = self.chain_spec.signers().len(),
+            epoch = self.chain_spec.epoch(),
+            period = self.chain_spec.block_period(),
+            mode,
+            "POA consensus initialized"
+        );
+        if let (Some(dir), Some(hash)) = (self.snapshot_dir, self.head_epoch_hash) {
+            let store = SnapshotStore::new(dir);
+            match store.load(hash) {
+                Ok(Some(snapshot)) => {
+                    info!(
+                        target: "poa::consensus",
+                        number = snapshot.number,
+                        signers = snapshot.signers.len(),
+                        "Seeding signer set from epoch snapshot"
+                    );
+                    self.chain_spec.update_live_signers(snapshot.signers);
+                }
+                Ok(None) => {}
+                Err(err) => warn!(
+                    target: "poa::consensus",
+                    dir = %store.dir().display(),
+                    %err,
+                    "Failed to load epoch snapshot"
+                ),
+            }
+        }
+        let consensus = PoaConsensus::new(self.chain_spec).with_dev_mode(self.dev_mode);
+        let consensus = match self.allowed_tx_types {
+            Some(types) => consensus.with_allowed_tx_types(types),
+            None => consensus,
+        };
+        let consensus = match self.stakes {
+            Some(stakes) => consensus.with_stake_source(stakes),
+            None => consensus,
+        };
+        let consensus = match self.gas_limits {
+            Some(gas_limits) => consensus.with_gas_limit_source(gas_limits),
+            None => consensus,
+        };
+        let consensus = match self.signer_sets {
+            Some(signer_sets) => consensus.with_signer_set_source(signer_sets),
+            None => consensus,
+        };
+        let consensus = match self.signature_cache {
+            Some(cache) => consensus.with_signature_cache(cache),
+            None => consensus,
+        };
+        Arc::new(match self.checkpoint {
+            Some(checkpoint) => {
+                info!(
+                    target: "poa::consensus",
+                    number = checkpoint.block_number,
+                    hash = %checkpoint.block_hash,
+                    "Trusting headers below checkpoint"
+                );
+                consensus.with_checkpoint(checkpoint)
+            }
+            None => consensus,
+        })
+    }
 }
 
 impl<N> ConsensusBuilder<N> for PoaConsensusBuilder
@@ -44,20 +195,166 @@ where
 {
     type Consensus = Arc<PoaConsensus>;
 
-    async fn build_consensus(self, _ctx: &BuilderContext<N>) -> eyre::Result<Self::Consensus> {
-        let mode = if self.dev_mode {
-            "dev (relaxed)"
+    async fn build_consensus(self, ctx: &BuilderContext<N>) -> eyre::Result<Self::Consensus> {
+        // The minimum signer stake is read from the node's state.
+        let builder = match self.chain_spec.min_signer_stake() {
+            Some(_) => self.with_stake_source(StakeSource::new(Arc::new(ctx.provider().clone()))),
+            None => self,
+        };
+        // So is the ChainConfig gas limit the ramp moves toward.
+        let builder = if builder.chain_spec.max_gas_ramp_blocks() > 0 {
+            builder.with_gas_limit_source(GasLimitSource::new(Arc::new(ctx.provider().clone())))
         } else {
-            "production (strict)"
+            builder
         };
-        output::print_consensus_init(
-            self.chain_spec.signers().len(),
-            self.chain_spec.epoch(),
-            self.chain_spec.block_period(),
-            mode,
+        // And the signer set epoch blocks list, wherever seals are checked.
+        let builder = if builder.dev_mode {
+            builder
+        } else {
+            builder.with_signer_set_source(SignerSetSource::new(Arc::new(ctx.provider().clone())))
+        };
+        // Only the snapshot of the head's last canonical epoch block seeds the signer
+        // set: a newer epoch may have changed it, and a side chain never applied.
+        let head = ctx.head().number;
+        let epoch_block = head - head % builder.chain_spec.epoch();
+        let head_epoch_hash = match epoch_block {
+            0 => None,
+            number => ctx.provider().block_hash(number)?,
+        };
+        let builder = builder.with_head_epoch_hash(head_epoch_hash);
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_tracing::tracing::field::{Field, Visit};
+    use reth_tracing::tracing::{Event, Subscriber};
+    use reth_tracing::tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    type Captured = Arc<Mutex<Vec<(String, BTreeMap<String, String>)>>>;
+
+    /// Layer that records every event's target and fields.
+    struct CaptureLayer(Captured);
+
+    struct FieldVisitor(BTreeMap<String, String>);
+
+    impl Visit for FieldVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut visitor = FieldVisitor(BTreeMap::new());
+            event.record(&mut visitor);
+            self.0
+                .lock()
+                .unwrap()
+                .push((event.metadata().target().to_string(), visitor.0));
+        }
+    }
+
+    fn capture(f: impl FnOnce()) -> Vec<(String, BTreeMap<String, String>)> {
+        let captured: Captured = Arc::default();
+        let subscriber =
+            reth_tracing::tracing_subscriber::registry().with(CaptureLayer(captured.clone()));
+        reth_tracing::tracing::subscriber::with_default(subscriber, f);
+        let events = captured.lock().unwrap().clone();
+        events
+    }
+
+    #[test]
+    fn test_build_emits_consensus_initialized_event() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let events = capture(|| {
+            let consensus = PoaConsensusBuilder::new(chain.clone()).with_dev_mode(true).build();
+            assert!(consensus.is_dev_mode());
+        });
+
+        let (target, fields) = events
+            .iter()
+            .find(|(_, f)| f.get("message").map(String::as_str) == Some("POA consensus initialized"))
+            .expect("consensus-initialized event");
+        assert_eq!(target, "poa::consensus");
+        assert_eq!(fields["signer_count"], chain.signers().len().to_string());
+        assert_eq!(fields["epoch"], chain.epoch().to_string());
+        assert_eq!(fields["period"], chain.block_period().to_string());
+        assert_eq!(fields["mode"], "dev (relaxed)");
+    }
+
+    #[test]
+    fn test_build_production_mode_field() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let events = capture(|| {
+            PoaConsensusBuilder::new(chain).build();
+        });
+        assert!(events.iter().any(|(_, f)| f.get("mode").map(String::as_str) == Some("production (strict)")));
+    }
+
+    #[test]
+    fn test_build_seeds_signers_from_head_epoch_snapshot() {
+        use crate::consensus::Snapshot;
+
+        let dir = std::env::temp_dir().join(format!("poa-seed-snapshots-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let snapshot = |byte: u8| Snapshot {
+            number: 60,
+            hash: B256::repeat_byte(byte),
+            signers: vec![alloy_primitives::Address::repeat_byte(byte)],
+        };
+        let store = SnapshotStore::new(&dir);
+        store.save(&snapshot(7)).unwrap();
+        store.save(&snapshot(8)).unwrap();
+
+        // A snapshot of another block (a side chain, an older epoch) is not used.
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        PoaConsensusBuilder::new(chain.clone())
+            .with_snapshot_dir(Some(dir.clone()))
+            .with_head_epoch_hash(Some(B256::repeat_byte(9)))
+            .build();
+        assert_eq!(chain.effective_signers(), chain.signers());
+
+        PoaConsensusBuilder::new(chain.clone())
+            .with_snapshot_dir(Some(dir.clone()))
+            .with_head_epoch_hash(Some(B256::repeat_byte(8)))
+            .build();
+        assert_eq!(chain.effective_signers(), snapshot(8).signers);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// `build()` reports through `tracing` only: every event is under a `poa::` target
+    /// and the startup report is a single consensus-initialized event.
+    #[test]
+    fn test_build_reports_only_through_tracing() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let checkpoint = Checkpoint {
+            block_number: 100,
+            block_hash: B256::repeat_byte(1),
+        };
+        let events = capture(|| {
+            PoaConsensusBuilder::new(chain)
+                .with_checkpoint(Some(checkpoint))
+                .build();
+        });
+        assert!(
+            events.iter().all(|(target, _)| target.starts_with("poa::")),
+            "{events:?}"
         );
-        Ok(Arc::new(
-            PoaConsensus::new(self.chain_spec).with_dev_mode(self.dev_mode),
-        ))
+        let messages: Vec<&str> = events
+            .iter()
+            .filter_map(|(_, f)| f.get("message").map(String::as_str))
+            .collect();
+        let initialized = "POA consensus initialized";
+        assert_eq!(messages.iter().filter(|m| **m == initialized).count(), 1);
+        assert!(messages.contains(&"Trusting headers below checkpoint"));
     }
 }
@@ -0,0 +1,234 @@
+use crate::rpc::NODE_VERSION;
+use alloy_evm::revm::primitives::hardfork::SpecId;
+use alloy_primitives::Address;
+use reth_chainspec::EthereumHardforks;
+use reth_ethereum::network::api::PeersInfo;
+use reth_storage_api::BlockNumReader;
+use reth_transaction_pool::TransactionPool;
+use serde::Serialize;
+
+/// Live statistics of a launched node, for [`DiagnosticReport::with_stats_from`].
+pub trait RuntimeStats: Send + Sync {
+    /// Number of the canonical head, if readable.
+    fn latest_block_number(&self) -> Option<u64>;
+    /// Connected peers.
+    fn peer_count(&self) -> usize;
+    /// Transactions in the pool, pending and queued.
+    fn mempool_size(&self) -> usize;
+}
+
+/// [`RuntimeStats`] read from a launched node's provider, network and pool.
+#[derive(Debug, Clone)]
+pub struct NodeRuntimeStats<Provider, Network, Pool> {
+    /// Canonical chain provider
+    pub provider: Provider,
+    /// Network handle
+    pub network: Network,
+    /// Transaction pool
+    pub pool: Pool,
+}
+
+impl<Provider, Network, Pool> RuntimeStats for NodeRuntimeStats<Provider, Network, Pool>
+where
+    Provider: BlockNumReader + Send + Sync,
+    Network: PeersInfo + Send + Sync,
+    Pool: TransactionPool,
+{
+    fn latest_block_number(&self) -> Option<u64> {
+        self.provider.best_block_number().ok()
+    }
+
+    fn peer_count(&self) -> usize {
+        self.network.num_connected_peers()
+    }
+
+    fn mempool_size(&self) -> usize {
+        self.pool.pool_size().total
+    }
+}
+
+/// Structured snapshot of node configuration for bug reports.
+///
+/// Produced by [`PoaNode::diagnose`](super::PoaNode::diagnose). Contains only public
+/// configuration — signer *addresses*, never keys. Runtime statistics (head block,
+/// peers, mempool) are unknown to `PoaNode` itself and are `None` until filled in
+/// from the launched node via [`DiagnosticReport::with_stats_from`]; `poa_diagnostics`
+/// serves the report that way.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticReport {
+    pub chain_id: u64,
+    pub block_period: u64,
+    pub epoch: u64,
+    /// Effective signer addresses (live on-chain list if synced, else genesis).
+    pub signer_addresses: Vec<Address>,
+    pub latest_block_number: Option<u64>,
+    pub peer_count: Option<usize>,
+    pub mempool_size: Option<usize>,
+    /// Latest EVM spec active at genesis (e.g. `"PRAGUE"`).
+    pub evm_spec_id: String,
+    pub calldata_gas_per_byte: u64,
+    /// `None` = Ethereum default (24,576 bytes).
+    pub max_contract_size: Option<usize>,
+    pub version: String,
+    /// Enabled node features (`dev_mode`, `zero_gas`, ...).
+    pub features: Vec<String>,
+}
+
+impl DiagnosticReport {
+    /// Attach runtime statistics gathered from a launched node.
+    pub fn with_runtime_stats(
+        mut self,
+        latest_block_number: u64,
+        peer_count: usize,
+        mempool_size: usize,
+    ) -> Self {
+        self.latest_block_number = Some(latest_block_number);
+        self.peer_count = Some(peer_count);
+        self.mempool_size = Some(mempool_size);
+        self
+    }
+
+    /// Attach the current statistics of `stats`.
+    pub fn with_stats_from(mut self, stats: &dyn RuntimeStats) -> Self {
+        self.latest_block_number = stats.latest_block_number();
+        self.peer_count = Some(stats.peer_count());
+        self.mempool_size = Some(stats.mempool_size());
+        self
+    }
+
+    /// Render the report as JSON safe to paste into a public issue.
+    ///
+    /// Fields are listed explicitly rather than derived from `Serialize`, so a field
+    /// added to the struct later is not exported until it is reviewed here. Nothing
+    /// key-shaped (signer keys, keystore passwords, `SIGNER_KEY`) is ever included.
+    pub fn to_redacted_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "chainId": self.chain_id,
+            "blockPeriod": self.block_period,
+            "epoch": self.epoch,
+            "signerAddresses": self.signer_addresses,
+            "latestBlockNumber": self.latest_block_number,
+            "peerCount": self.peer_count,
+            "mempoolSize": self.mempool_size,
+            "evmSpecId": self.evm_spec_id,
+            "calldataGasPerByte": self.calldata_gas_per_byte,
+            "maxContractSize": self.max_contract_size,
+            "version": self.version,
+            "features": self.features,
+        })
+    }
+}
+
+/// Build version string reported in diagnostics.
+pub(crate) fn build_version() -> String {
+    format!(
+        "{NODE_VERSION} ({} {})",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Latest EVM spec active at genesis for the given chain spec.
+pub(crate) fn genesis_spec_id(chain_spec: &impl EthereumHardforks) -> SpecId {
+    crate::chainspec::hardforks::spec_id_at(chain_spec, 0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::PoaChainSpec;
+    use crate::node::PoaNode;
+    use crate::signer::dev;
+    use std::sync::Arc;
+
+    /// Collect every string leaf in a JSON value.
+    fn string_leaves(value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(s) => out.push(s.clone()),
+            serde_json::Value::Array(items) => items.iter().for_each(|v| string_leaves(v, out)),
+            serde_json::Value::Object(map) => map.values().for_each(|v| string_leaves(v, out)),
+            _ => {}
+        }
+    }
+
+    fn looks_like_private_key(s: &str) -> bool {
+        let hex = s.strip_prefix("0x").unwrap_or(s);
+        hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_reports_config() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let node = PoaNode::new(chain.clone())
+            .with_dev_mode(true)
+            .unwrap()
+            .with_calldata_gas(8)
+            .with_max_contract_size(65_536);
+        let report = node.diagnose().await;
+
+        assert_eq!(report.chain_id, 9323310);
+        assert_eq!(report.block_period, 1);
+        assert_eq!(report.epoch, 30000);
+        assert_eq!(report.signer_addresses, chain.signers().to_vec());
+        assert_eq!(report.calldata_gas_per_byte, 8);
+        assert_eq!(report.max_contract_size, Some(65_536));
+        assert_eq!(report.evm_spec_id, format!("{:?}", SpecId::PRAGUE));
+        assert!(report.features.contains(&"dev_mode".to_string()));
+        assert!(report.latest_block_number.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_with_runtime_stats() {
+        let node = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()));
+        let report = node.diagnose().await.with_runtime_stats(42, 3, 17);
+        let json = report.to_redacted_json();
+        assert_eq!(json["latestBlockNumber"], 42);
+        assert_eq!(json["peerCount"], 3);
+        assert_eq!(json["mempoolSize"], 17);
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_with_stats_from_source() {
+        struct Stats;
+        impl RuntimeStats for Stats {
+            fn latest_block_number(&self) -> Option<u64> {
+                Some(7)
+            }
+            fn peer_count(&self) -> usize {
+                2
+            }
+            fn mempool_size(&self) -> usize {
+                5
+            }
+        }
+        let node = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()));
+        let report = node.diagnose().await.with_stats_from(&Stats);
+        assert_eq!(report.latest_block_number, Some(7));
+        assert_eq!(report.peer_count, Some(2));
+        assert_eq!(report.mempool_size, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_redacted_json_contains_no_private_keys() {
+        // Load real signer keys so a leak would be visible in the output.
+        let node = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()))
+            .with_signer_manager(dev::setup_dev_signers().await)
+            .with_zero_gas(true);
+        let json = node.diagnose().await.to_redacted_json();
+
+        let mut leaves = Vec::new();
+        string_leaves(&json, &mut leaves);
+        assert!(!leaves.is_empty());
+        for leaf in &leaves {
+            assert!(
+                !looks_like_private_key(leaf),
+                "key-shaped value in report: {leaf}"
+            );
+        }
+        let rendered = json.to_string();
+        for key in dev::DEV_PRIVATE_KEYS {
+            assert!(!rendered.contains(key), "private key leaked into report");
+        }
+    }
+}
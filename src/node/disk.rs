@@ -0,0 +1,557 @@
+//! Datadir disk-space monitoring and out-of-space error handling.
+//!
+//! A node whose datadir fills up mid-commit used to die with an opaque MDBX error.
+//! This module degrades instead:
+//!
+//! - [`DiskMonitor`] polls free space on the datadir filesystem. Below
+//!   [`DiskThresholds::warn_bytes`] it raises a warning alert; below
+//!   [`DiskThresholds::critical_bytes`] it flips the shared [`DiskGuard`], which
+//!   makes the payload builder refuse to build new blocks. RPC reads keep working.
+//!   Building resumes automatically once space is freed.
+//! - [`is_out_of_space`] recognises ENOSPC / `MDBX_MAP_FULL` anywhere in an error
+//!   chain, and [`explain_exit_error`] turns such a node exit into an actionable
+//!   message plus a critical alert. MDBX commits are atomic, so a failed commit
+//!   leaves the database at the previous block; [`verify_database`] (`--verify-db`)
+//!   confirms that after the event.
+
+use super::alerts::{Alert, AlertSink};
+use alloy_primitives::B256;
+use reth_tracing::tracing::info;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+/// `ENOSPC` — "No space left on device" (same value on Linux and macOS).
+const ENOSPC: i32 = 28;
+
+/// Source of free-space readings for a path.
+pub trait DiskSpaceProbe: Send + Sync + std::fmt::Debug {
+    /// Bytes available to unprivileged writers on the filesystem holding `path`.
+    fn available_bytes(&self, path: &Path) -> io::Result<u64>;
+}
+
+/// Probe backed by `statvfs(3)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsProbe;
+
+impl DiskSpaceProbe for FsProbe {
+    #[cfg(unix)]
+    fn available_bytes(&self, path: &Path) -> io::Result<u64> {
+        let stat = rustix::fs::statvfs(path)?;
+        Ok(stat.f_bavail.saturating_mul(stat.f_frsize))
+    }
+
+    #[cfg(not(unix))]
+    fn available_bytes(&self, _path: &Path) -> io::Result<u64> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "disk space probe requires unix",
+        ))
+    }
+}
+
+/// Free-space thresholds for the datadir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskThresholds {
+    /// Warn when available space drops below this many bytes.
+    pub warn_bytes: u64,
+    /// Stop building payloads when available space drops below this many bytes.
+    pub critical_bytes: u64,
+}
+
+impl DiskThresholds {
+    /// Thresholds from whole gibibytes (the CLI unit).
+    pub fn from_gib(warn_gib: u64, critical_gib: u64) -> Self {
+        Self {
+            warn_bytes: warn_gib.saturating_mul(GIB),
+            critical_bytes: critical_gib.saturating_mul(GIB),
+        }
+    }
+
+    /// Classify an available-space reading.
+    pub fn classify(&self, available: u64) -> DiskStatus {
+        if available < self.critical_bytes {
+            DiskStatus::Critical
+        } else if available < self.warn_bytes {
+            DiskStatus::Low
+        } else {
+            DiskStatus::Ok
+        }
+    }
+}
+
+impl Default for DiskThresholds {
+    fn default() -> Self {
+        Self::from_gib(10, 1)
+    }
+}
+
+/// Disk space state of the datadir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DiskStatus {
+    Ok = 0,
+    /// Below the warning threshold; everything still runs.
+    Low = 1,
+    /// Below the critical threshold; block building is paused.
+    Critical = 2,
+}
+
+impl DiskStatus {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Low,
+            2 => Self::Critical,
+            _ => Self::Ok,
+        }
+    }
+}
+
+/// Shared view of the latest disk status, consulted by the payload builder.
+///
+/// Cheap to clone; all clones observe the same state. A fresh guard reports
+/// [`DiskStatus::Ok`] so nodes without a monitor are unaffected.
+#[derive(Debug, Clone, Default)]
+pub struct DiskGuard {
+    status: Arc<AtomicU8>,
+    available: Arc<AtomicU64>,
+}
+
+impl DiskGuard {
+    /// Create a guard in the `Ok` state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest classified status.
+    pub fn status(&self) -> DiskStatus {
+        DiskStatus::from_u8(self.status.load(Ordering::Acquire))
+    }
+
+    /// Latest available-space reading in bytes (0 until the first check).
+    pub fn available_bytes(&self) -> u64 {
+        self.available.load(Ordering::Relaxed)
+    }
+
+    /// Whether new payloads may be built.
+    #[inline]
+    pub fn allows_block_building(&self) -> bool {
+        self.status() != DiskStatus::Critical
+    }
+
+    /// Error to return from the payload builder while building is paused.
+    pub fn check_block_building(&self) -> Result<(), DiskError> {
+        if self.allows_block_building() {
+            Ok(())
+        } else {
+            Err(DiskError::BuildingPaused {
+                available: self.available_bytes(),
+            })
+        }
+    }
+
+    fn set(&self, status: DiskStatus, available: u64) -> DiskStatus {
+        self.available.store(available, Ordering::Relaxed);
+        DiskStatus::from_u8(self.status.swap(status as u8, Ordering::AcqRel))
+    }
+}
+
+/// Disk-related failures.
+#[derive(Debug, thiserror::Error)]
+pub enum DiskError {
+    /// Free space is below the critical threshold
+    #[error("Block building paused: only {available} bytes free in datadir")]
+    BuildingPaused {
+        /// Available bytes at the last check
+        available: u64,
+    },
+
+    /// A storage write failed because the datadir filesystem is full
+    #[error(
+        "Datadir {} ran out of disk space during a database write ({reason}). \
+         The failed commit was not applied; the database is at the last committed block. \
+         Free space or move the datadir to a larger volume, run with --verify-db, then restart.",
+        datadir.display()
+    )]
+    OutOfSpace {
+        /// Node datadir
+        datadir: PathBuf,
+        /// Underlying storage error
+        reason: String,
+    },
+}
+
+/// Periodically checks datadir free space, updating a [`DiskGuard`] and raising alerts.
+#[derive(Debug, Clone)]
+pub struct DiskMonitor {
+    path: PathBuf,
+    thresholds: DiskThresholds,
+    probe: Arc<dyn DiskSpaceProbe>,
+    sink: Arc<dyn AlertSink>,
+    guard: DiskGuard,
+}
+
+impl DiskMonitor {
+    /// Monitor `path` with the given thresholds, probe and alert sink.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        thresholds: DiskThresholds,
+        probe: Arc<dyn DiskSpaceProbe>,
+        sink: Arc<dyn AlertSink>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            thresholds,
+            probe,
+            sink,
+            guard: DiskGuard::new(),
+        }
+    }
+
+    /// Guard shared with the payload builder.
+    pub fn guard(&self) -> DiskGuard {
+        self.guard.clone()
+    }
+
+    /// Take one reading, update the guard and alert on status transitions.
+    pub fn check(&self) -> io::Result<DiskStatus> {
+        let available = self.probe.available_bytes(&self.path)?;
+        let status = self.thresholds.classify(available);
+        let previous = self.guard.set(status, available);
+        if status != previous {
+            self.on_transition(previous, status, available);
+        }
+        Ok(status)
+    }
+
+    /// Run [`check`](Self::check) every `interval` until the task is dropped.
+    pub async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = self.check() {
+                self.sink.alert(&Alert::warning(
+                    "disk",
+                    format!("Cannot read free space for {}: {err}", self.path.display()),
+                ));
+            }
+        }
+    }
+
+    fn on_transition(&self, previous: DiskStatus, status: DiskStatus, available: u64) {
+        let path = self.path.display();
+        let free_mib = available / (1024 * 1024);
+        match status {
+            DiskStatus::Critical => self.sink.alert(&Alert::critical(
+                "disk",
+                format!(
+                    "Datadir {path} has {free_mib} MiB free (critical threshold {} MiB): \
+                     block building paused, RPC reads continue",
+                    self.thresholds.critical_bytes / (1024 * 1024)
+                ),
+            )),
+            DiskStatus::Low => self.sink.alert(&Alert::warning(
+                "disk",
+                format!(
+                    "Datadir {path} is low on space: {free_mib} MiB free (warning threshold {} MiB)",
+                    self.thresholds.warn_bytes / (1024 * 1024)
+                ),
+            )),
+            DiskStatus::Ok => {}
+        }
+        if previous == DiskStatus::Critical {
+            info!(target: "poa::disk", free_mib, "Disk space recovered, block building resumed");
+        }
+    }
+}
+
+/// Whether any error in the chain is an out-of-space failure.
+///
+/// Matches `ENOSPC` / [`io::ErrorKind::StorageFull`] I/O errors and MDBX's
+/// `MDBX_MAP_FULL` / `MDBX_ENOSPC` conditions (surfaced by reth as text).
+pub fn is_out_of_space(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(err);
+    while let Some(err) = current {
+        if let Some(io_err) = err.downcast_ref::<io::Error>() {
+            if io_err.raw_os_error() == Some(ENOSPC) || io_err.kind() == io::ErrorKind::StorageFull
+            {
+                return true;
+            }
+        }
+        let text = err.to_string();
+        if text.contains("MDBX_MAP_FULL")
+            || text.contains("MDBX_ENOSPC")
+            || text.contains("No space left on device")
+        {
+            return true;
+        }
+        current = err.source();
+    }
+    false
+}
+
+/// Translate a node exit error: out-of-space failures become [`DiskError::OutOfSpace`]
+/// with operator instructions and raise a critical alert; anything else is returned as is.
+pub fn explain_exit_error(err: eyre::Report, datadir: &Path, sink: &dyn AlertSink) -> eyre::Report {
+    let chain_is_oos = err.chain().any(|cause| is_out_of_space(cause));
+    if !chain_is_oos {
+        return err;
+    }
+    let wrapped = DiskError::OutOfSpace {
+        datadir: datadir.to_path_buf(),
+        reason: format!("{err:#}"),
+    };
+    sink.alert(&Alert::critical("disk", wrapped.to_string()));
+    eyre::Report::new(wrapped)
+}
+
+/// Summary of a read-only database integrity pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbVerifyReport {
+    /// Entries in the canonical `number → hash` table.
+    pub canonical_headers: usize,
+    /// Entries in the headers table.
+    pub headers: usize,
+    /// Highest canonical block, if any.
+    pub tip: Option<(u64, B256)>,
+    /// Inconsistencies found (empty = healthy).
+    pub issues: Vec<String>,
+}
+
+impl DbVerifyReport {
+    /// Whether no inconsistencies were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Open the database read-only and check that the canonical chain is intact.
+///
+/// Verifies that every canonical block number up to the tip is present, that the
+/// tip header exists, and that the hash index points back to the tip.
+pub fn verify_database(db_path: &Path) -> eyre::Result<DbVerifyReport> {
+    use reth_db::cursor::DbCursorRO;
+    use reth_db::transaction::DbTx;
+    use reth_db::{open_db_read_only, tables, Database};
+
+    let db = open_db_read_only(db_path, Default::default())?;
+    let tx = db.tx()?;
+
+    let canonical_headers = tx.entries::<tables::CanonicalHeaders>()?;
+    let headers = tx.entries::<tables::Headers>()?;
+    let tip = tx.cursor_read::<tables::CanonicalHeaders>()?.last()?;
+
+    let mut issues = Vec::new();
+    if let Some((number, hash)) = tip {
+        if canonical_headers as u64 != number + 1 {
+            issues.push(format!(
+                "canonical table has {canonical_headers} entries but tip is block {number} (gap)"
+            ));
+        }
+        if tx.get::<tables::Headers>(number)?.is_none() {
+            issues.push(format!("tip header {number} missing from headers table"));
+        }
+        if tx.get::<tables::HeaderNumbers>(hash)? != Some(number) {
+            issues.push(format!(
+                "hash index does not map tip hash {hash} to block {number}"
+            ));
+        }
+    }
+
+    Ok(DbVerifyReport {
+        canonical_headers,
+        headers,
+        tip,
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::alerts::{AlertSeverity, RecordingAlertSink};
+    use std::sync::Mutex;
+
+    /// Probe returning scripted readings, simulating a disk filling up.
+    #[derive(Debug)]
+    struct ScriptedProbe(Mutex<u64>);
+
+    impl ScriptedProbe {
+        fn set(&self, available: u64) {
+            *self.0.lock().unwrap() = available;
+        }
+    }
+
+    impl DiskSpaceProbe for ScriptedProbe {
+        fn available_bytes(&self, _path: &Path) -> io::Result<u64> {
+            Ok(*self.0.lock().unwrap())
+        }
+    }
+
+    fn monitor(available: u64) -> (DiskMonitor, Arc<ScriptedProbe>, Arc<RecordingAlertSink>) {
+        let probe = Arc::new(ScriptedProbe(Mutex::new(available)));
+        let sink = Arc::new(RecordingAlertSink::default());
+        let monitor = DiskMonitor::new(
+            "/data",
+            DiskThresholds {
+                warn_bytes: 1_000,
+                critical_bytes: 100,
+            },
+            probe.clone(),
+            sink.clone(),
+        );
+        (monitor, probe, sink)
+    }
+
+    #[test]
+    fn test_thresholds_classify() {
+        let t = DiskThresholds {
+            warn_bytes: 1_000,
+            critical_bytes: 100,
+        };
+        assert_eq!(t.classify(5_000), DiskStatus::Ok);
+        assert_eq!(t.classify(1_000), DiskStatus::Ok);
+        assert_eq!(t.classify(999), DiskStatus::Low);
+        assert_eq!(t.classify(100), DiskStatus::Low);
+        assert_eq!(t.classify(99), DiskStatus::Critical);
+        assert_eq!(DiskThresholds::default(), DiskThresholds::from_gib(10, 1));
+    }
+
+    #[test]
+    fn test_filling_disk_degrades_then_recovers() {
+        let (monitor, probe, sink) = monitor(10_000);
+        let guard = monitor.guard();
+
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Ok);
+        assert!(guard.allows_block_building());
+        assert!(sink.alerts().is_empty());
+
+        // Disk fills past the warning threshold: alert, keep building.
+        probe.set(500);
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Low);
+        assert!(guard.allows_block_building());
+        assert_eq!(sink.alerts().len(), 1);
+        assert_eq!(sink.alerts()[0].severity, AlertSeverity::Warning);
+
+        // Repeated readings at the same level don't re-alert.
+        probe.set(400);
+        monitor.check().unwrap();
+        assert_eq!(sink.alerts().len(), 1);
+
+        // Past the critical threshold: building paused with a clear error.
+        probe.set(10);
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Critical);
+        assert!(!guard.allows_block_building());
+        match guard.check_block_building() {
+            Err(DiskError::BuildingPaused { available }) => assert_eq!(available, 10),
+            other => panic!("Expected BuildingPaused, got {other:?}"),
+        }
+        assert_eq!(sink.alerts()[1].severity, AlertSeverity::Critical);
+
+        // Space freed: building resumes.
+        probe.set(50_000);
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Ok);
+        assert!(guard.check_block_building().is_ok());
+        assert_eq!(sink.alerts().len(), 2);
+    }
+
+    #[test]
+    fn test_default_guard_allows_building() {
+        assert!(DiskGuard::new().allows_block_building());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fs_probe_reads_temp_dir() {
+        let available = FsProbe.available_bytes(&std::env::temp_dir()).unwrap();
+        assert!(available > 0);
+        assert!(FsProbe
+            .available_bytes(Path::new("/definitely/not/a/path"))
+            .is_err());
+    }
+
+    /// Fills a real tmpfs: `mount -t tmpfs -o size=8m tmpfs <dir>` and run with
+    /// `POA_TMPFS_DIR=<dir> cargo test -- --ignored test_fs_probe_on_full_tmpfs`.
+    #[cfg(unix)]
+    #[test]
+    #[ignore = "needs a small tmpfs mounted at POA_TMPFS_DIR"]
+    fn test_fs_probe_on_full_tmpfs() {
+        use std::io::Write;
+
+        let dir = PathBuf::from(std::env::var("POA_TMPFS_DIR").expect("POA_TMPFS_DIR"));
+        let sink = Arc::new(RecordingAlertSink::default());
+        let thresholds = DiskThresholds {
+            warn_bytes: 4 * 1024 * 1024,
+            critical_bytes: 1024 * 1024,
+        };
+        let monitor = DiskMonitor::new(&dir, thresholds, Arc::new(FsProbe), sink.clone());
+        let guard = monitor.guard();
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Ok);
+
+        // Write until the filesystem refuses: the error is recognised as out of space.
+        let fill = dir.join("fill");
+        let mut file = std::fs::File::create(&fill).unwrap();
+        let chunk = vec![0xa5u8; 64 * 1024];
+        let err = loop {
+            if let Err(err) = file.write_all(&chunk).and_then(|()| file.sync_all()) {
+                break err;
+            }
+        };
+        assert!(is_out_of_space(&err), "{err}");
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Critical);
+        assert!(!guard.allows_block_building());
+
+        drop(file);
+        std::fs::remove_file(&fill).unwrap();
+        assert_eq!(monitor.check().unwrap(), DiskStatus::Ok);
+        assert!(guard.allows_block_building());
+        assert_eq!(sink.alerts()[0].severity, AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_is_out_of_space_detects_enospc() {
+        let err = io::Error::from_raw_os_error(ENOSPC);
+        assert!(is_out_of_space(&err));
+        assert!(!is_out_of_space(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+    }
+
+    #[test]
+    fn test_explain_exit_error_wraps_nested_enospc() {
+        let sink = RecordingAlertSink::default();
+        let err = eyre::Report::new(io::Error::from_raw_os_error(ENOSPC))
+            .wrap_err("failed to commit block 42");
+        let explained = explain_exit_error(err, Path::new("/data"), &sink);
+
+        assert!(matches!(
+            explained.downcast_ref::<DiskError>(),
+            Some(DiskError::OutOfSpace { .. })
+        ));
+        assert!(explained.to_string().contains("--verify-db"));
+        assert_eq!(sink.alerts().len(), 1);
+        assert_eq!(sink.alerts()[0].severity, AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_explain_exit_error_detects_mdbx_map_full() {
+        let sink = RecordingAlertSink::default();
+        let err =
+            eyre::eyre!("Database write error: MDBX_MAP_FULL: Environment mapsize limit reached");
+        let explained = explain_exit_error(err, Path::new("/data"), &sink);
+        assert!(explained.downcast_ref::<DiskError>().is_some());
+    }
+
+    #[test]
+    fn test_explain_exit_error_passes_through_other_errors() {
+        let sink = RecordingAlertSink::default();
+        let explained =
+            explain_exit_error(eyre::eyre!("peer disconnected"), Path::new("/data"), &sink);
+        assert_eq!(explained.to_string(), "peer disconnected");
+        assert!(sink.alerts().is_empty());
+    }
+}
@@ -0,0 +1,327 @@
+//! End-to-end tests against a launched node.
+//!
+//! [`TestNode::launch`] boots a [`PoaNode`] on the dev chain the way `main.rs` does
+//! (real MDBX datadir, HTTP + WS RPC, JWT-authenticated auth server) but without dev
+//! mining, so each test drives block production itself through the Engine API. All
+//! ports are picked by the OS, so the tests can run in parallel.
+
+use super::PoaNode;
+use crate::chainspec::PoaChainSpec;
+use crate::signer::{dev::DEV_PRIVATE_KEYS, SignerManager};
+use alloy_consensus::{SignableTransaction, TxLegacy};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Bytes, TxKind, B256};
+use alloy_rpc_types_engine::{Claims, JwtSecret};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use jsonrpsee::core::client::{ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use reth_db::init_db;
+use reth_ethereum::{
+    node::builder::{NodeBuilder, NodeHandle},
+    node::core::{
+        args::{DatadirArgs, RpcServerArgs},
+        node_config::NodeConfig,
+    },
+    tasks::{RuntimeBuilder, RuntimeConfig, TokioConfig},
+    TransactionSigned,
+};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a test waits for a subscription notification before failing.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Init code that emits one `LOG0` with empty data and deploys nothing
+/// (`PUSH1 0 PUSH1 0 LOG0 STOP`).
+const LOG0_INIT_CODE: [u8; 6] = [0x60, 0x00, 0x60, 0x00, 0xa0, 0x00];
+
+/// A launched dev-chain node and the endpoints a test talks to.
+struct TestNode {
+    chain: Arc<PoaChainSpec>,
+    ws_url: String,
+    auth_addr: SocketAddr,
+    jwt: JwtSecret,
+    http: reqwest::Client,
+    datadir: PathBuf,
+}
+
+impl TestNode {
+    async fn launch(name: &str) -> eyre::Result<Self> {
+        let datadir = std::env::temp_dir().join(format!("poa-e2e-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&datadir);
+        std::fs::create_dir_all(datadir.join("db"))?;
+
+        let jwt = JwtSecret::random();
+        let jwt_path = datadir.join("jwt.hex");
+        std::fs::write(&jwt_path, hex::encode(jwt.as_bytes()))?;
+
+        let rpc_args = RpcServerArgs {
+            http: true,
+            ws: true,
+            ipcdisable: true,
+            auth_jwtsecret: Some(jwt_path),
+            ..Default::default()
+        };
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let mut node_config = NodeConfig::default()
+            .with_rpc(rpc_args)
+            .with_chain(chain.inner().clone())
+            .with_datadir_args(DatadirArgs {
+                datadir: datadir.clone().into(),
+                ..Default::default()
+            })
+            .with_unused_ports();
+        node_config.network.discovery.disable_discovery = true;
+
+        let signer_manager = Arc::new(SignerManager::new());
+        signer_manager.add_signer_from_hex(DEV_PRIVATE_KEYS[0])?;
+        let poa_node = PoaNode::new(chain.clone())
+            .with_dev_mode(true)?
+            .with_signer_manager(signer_manager);
+
+        let tasks = RuntimeBuilder::new(RuntimeConfig::default().with_tokio(
+            TokioConfig::existing_handle(tokio::runtime::Handle::current()),
+        ))
+        .build()
+        .map_err(|e| eyre::eyre!("{e}"))?;
+        let database = Arc::new(init_db(datadir.join("db"), Default::default())?);
+        let NodeHandle { node, .. } = NodeBuilder::new(node_config)
+            .with_database(database)
+            .with_launch_context(tasks)
+            .node(poa_node)
+            .launch()
+            .await?;
+
+        let ws_url = node
+            .rpc_server_handle()
+            .ws_url()
+            .ok_or_else(|| eyre::eyre!("WS RPC server not started"))?;
+        Ok(Self {
+            chain,
+            ws_url,
+            auth_addr: node.auth_server_handle().local_addr(),
+            jwt,
+            http: reqwest::Client::new(),
+            datadir,
+        })
+    }
+
+    fn auth_url(&self) -> String {
+        format!("http://{}", self.auth_addr)
+    }
+
+    /// POST a JSON-RPC call to the auth server, optionally with a bearer token.
+    async fn post_engine(
+        &self,
+        authorization: Option<String>,
+        method: &str,
+        params: Value,
+    ) -> eyre::Result<reqwest::Response> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut request = self
+            .http
+            .post(self.auth_url())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&body)?);
+        if let Some(authorization) = authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Authenticated Engine API call; returns `result` or fails on a JSON-RPC error.
+    async fn engine(&self, method: &str, params: Value) -> eyre::Result<Value> {
+        let response = self
+            .post_engine(Some(bearer(&self.jwt)?), method, params)
+            .await?;
+        let mut reply: Value = serde_json::from_slice(&response.bytes().await?)?;
+        if let Some(error) = reply.get("error") {
+            eyre::bail!("{method} failed: {error}");
+        }
+        Ok(reply["result"].take())
+    }
+
+    async fn ws(&self) -> eyre::Result<WsClient> {
+        Ok(WsClientBuilder::default().build(&self.ws_url).await?)
+    }
+
+    /// Build a block on top of `parent` and return its `engine_getPayloadV4` envelope.
+    ///
+    /// The timestamp is the only attribute that differs between siblings, so two
+    /// calls with different timestamps yield two competing blocks at the same height.
+    async fn build_payload(&self, parent: B256, timestamp: u64) -> eyre::Result<Value> {
+        let state = json!({
+            "headBlockHash": parent,
+            "safeBlockHash": B256::ZERO,
+            "finalizedBlockHash": B256::ZERO,
+        });
+        let attributes = json!({
+            "timestamp": format!("{timestamp:#x}"),
+            "prevRandao": B256::ZERO,
+            "suggestedFeeRecipient": self.chain.inner().genesis().coinbase,
+            "withdrawals": [],
+            "parentBeaconBlockRoot": B256::ZERO,
+        });
+        let updated = self
+            .engine("engine_forkchoiceUpdatedV3", json!([state, attributes]))
+            .await?;
+        let payload_id = updated["payloadId"].clone();
+        // Give the payload job a moment to pick the pending transaction up.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        self.engine("engine_getPayloadV4", json!([payload_id]))
+            .await
+    }
+
+    /// Import `envelope` with `engine_newPayloadV4` and make it the canonical head.
+    async fn import_and_select(&self, envelope: &Value) -> eyre::Result<B256> {
+        let payload = &envelope["executionPayload"];
+        let status = self
+            .engine(
+                "engine_newPayloadV4",
+                json!([payload, [], B256::ZERO, envelope["executionRequests"]]),
+            )
+            .await?;
+        eyre::ensure!(status["status"] == "VALID", "newPayload: {status}");
+        let hash: B256 = serde_json::from_value(payload["blockHash"].clone())?;
+        let state = json!({
+            "headBlockHash": hash,
+            "safeBlockHash": B256::ZERO,
+            "finalizedBlockHash": B256::ZERO,
+        });
+        let updated = self
+            .engine("engine_forkchoiceUpdatedV3", json!([state, null]))
+            .await?;
+        eyre::ensure!(
+            updated["payloadStatus"]["status"] == "VALID",
+            "forkchoiceUpdated: {updated}"
+        );
+        Ok(hash)
+    }
+}
+
+impl Drop for TestNode {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.datadir);
+    }
+}
+
+/// A fresh `Authorization` header value signed with `secret`.
+fn bearer(secret: &JwtSecret) -> eyre::Result<String> {
+    let iat = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    Ok(format!(
+        "Bearer {}",
+        secret.encode(&Claims { iat, exp: None })?
+    ))
+}
+
+/// A signed contract creation from dev account 0 whose init code emits one log.
+fn log_emitting_tx(chain: &PoaChainSpec) -> Bytes {
+    let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[0].parse().unwrap();
+    let tx = TxLegacy {
+        chain_id: Some(chain.inner().chain.id()),
+        nonce: 0,
+        gas_price: 2_000_000_000,
+        gas_limit: 100_000,
+        to: TxKind::Create,
+        input: Bytes::from_static(&LOG0_INIT_CODE),
+        ..Default::default()
+    };
+    let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+    TransactionSigned::from(tx.into_signed(signature))
+        .encoded_2718()
+        .into()
+}
+
+async fn next(subscription: &mut Subscription<Value>) -> Value {
+    tokio::time::timeout(NOTIFICATION_TIMEOUT, subscription.next())
+        .await
+        .expect("subscription notification timed out")
+        .expect("subscription closed")
+        .expect("malformed notification")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reorg_reemits_logs_and_new_heads() {
+    let node = TestNode::launch("reorg").await.unwrap();
+    let ws = node.ws().await.unwrap();
+    let mut logs: Subscription<Value> = ws
+        .subscribe(
+            "eth_subscribe",
+            rpc_params!["logs", json!({})],
+            "eth_unsubscribe",
+        )
+        .await
+        .unwrap();
+    let mut heads: Subscription<Value> = ws
+        .subscribe("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+        .await
+        .unwrap();
+
+    let raw = log_emitting_tx(&node.chain);
+    let _: B256 = ws
+        .request("eth_sendRawTransaction", rpc_params![raw])
+        .await
+        .unwrap();
+
+    // Two siblings of genesis, both carrying the log-emitting transaction.
+    let genesis = node.chain.inner().genesis_hash();
+    let first = node.build_payload(genesis, 1).await.unwrap();
+    let second = node.build_payload(genesis, 2).await.unwrap();
+    for envelope in [&first, &second] {
+        let txs = envelope["executionPayload"]["transactions"]
+            .as_array()
+            .unwrap();
+        assert_eq!(txs.len(), 1, "payload must include the pending transaction");
+    }
+
+    let a = node.import_and_select(&first).await.unwrap();
+    let b = node.import_and_select(&second).await.unwrap();
+    assert_ne!(a, b);
+
+    // Canonical A, then the reorg: A's log retracted before B's log is emitted.
+    let expected = [(a, false), (a, true), (b, false)];
+    for (block_hash, removed) in expected {
+        let log = next(&mut logs).await;
+        assert_eq!(log["blockHash"], json!(block_hash), "{log}");
+        assert_eq!(log["blockNumber"], json!("0x1"), "{log}");
+        assert_eq!(log["removed"], json!(removed), "{log}");
+    }
+    for block_hash in [a, b] {
+        let head = next(&mut heads).await;
+        assert_eq!(head["hash"], json!(block_hash), "{head}");
+        assert_eq!(head["number"], json!("0x1"), "{head}");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auth_server_requires_jwt() {
+    let node = TestNode::launch("jwt").await.unwrap();
+    let params = json!([["engine_forkchoiceUpdatedV3"]]);
+
+    let anonymous = node
+        .post_engine(None, "engine_exchangeCapabilities", params.clone())
+        .await
+        .unwrap();
+    assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let wrong_key = node
+        .post_engine(
+            Some(bearer(&JwtSecret::random()).unwrap()),
+            "engine_exchangeCapabilities",
+            params.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(wrong_key.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let capabilities = node
+        .engine("engine_exchangeCapabilities", params)
+        .await
+        .unwrap();
+    assert!(capabilities.as_array().is_some_and(|c| !c.is_empty()));
+}
@@ -6,6 +6,7 @@ use reth_ethereum::node::builder::rpc::PayloadValidatorBuilder;
 use reth_ethereum::node::EthereumEngineValidator;
 use reth_ethereum::EthPrimitives;
 use reth_ethereum_engine_primitives::EthPayloadAttributes;
+use reth_ethereum_forks::EthereumHardforks;
 use reth_payload_primitives::{
     EngineApiMessageVersion, EngineObjectValidationError, NewPayloadError, PayloadOrAttributes,
     PayloadTypes,
@@ -39,16 +40,21 @@ pub fn strip_extra_data(payload: ExecutionPayload) -> (ExecutionPayload, alloy_p
 ///
 /// Wraps [`EthereumEngineValidator`] and overrides only [`PayloadValidator::convert_payload_to_block`]
 /// to strip/restore POA extra_data around alloy's strict 32-byte check.
+///
+/// Execution payloads always carry a base fee, so on a chain without London
+/// (EIP-1559 disabled) the converted header's `base_fee_per_gas` is cleared again.
 #[derive(Debug, Clone)]
 pub struct PoaEngineValidator<ChainSpec = reth_chainspec::ChainSpec> {
     inner: EthereumEngineValidator<ChainSpec>,
+    chain_spec: Arc<ChainSpec>,
 }
 
 impl<ChainSpec> PoaEngineValidator<ChainSpec> {
     /// Creates a new validator with the given chain spec.
-    pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
         Self {
-            inner: EthereumEngineValidator::new(chain_spec),
+            inner: EthereumEngineValidator::new(chain_spec.clone()),
+            chain_spec,
         }
     }
 }
@@ -79,6 +85,11 @@ where
         // Restore the original extra_data.
         block.header.extra_data = orig_extra;
 
+        // Payloads encode a missing base fee as zero; pre-London headers have none.
+        if !self.chain_spec.is_london_active_at_block(block.header.number) {
+            block.header.base_fee_per_gas = None;
+        }
+
         // Reseal: recompute the block hash with the restored extra_data.
         let sealed = SealedBlock::seal_slow(block);
 
@@ -0,0 +1,21 @@
+//! Node configuration errors.
+
+/// Invalid `PoaNode` configuration.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NodeError {
+    /// Dev mode (relaxed signature checks) requested on a production chain spec
+    #[error("Dev mode cannot be enabled on production chain {chain_id}")]
+    DevModeOnProduction {
+        /// Chain ID of the production spec
+        chain_id: u64,
+    },
+    /// An EVM spec override requested outside dev mode
+    #[error("The EVM spec override ({spec}) is only allowed in dev mode")]
+    EvmSpecOutsideDevMode {
+        /// The requested hardfork
+        spec: String,
+    },
+    /// A configured peer is not a valid `enode://<id>@<ip>:<port>` URL
+    #[error("Invalid peer URL: {0}")]
+    InvalidPeerUrl(String),
+}
@@ -0,0 +1,375 @@
+//! Read-only state snapshots for Foundry-style forking.
+//!
+//! [`PoaNode::create_fork`](super::PoaNode::create_fork) pins the state of a historical
+//! block and returns it as a [`ForkDb`], a revm database that tools can execute
+//! against (`forge test --fork-url`, `anvil --fork-block-number`) without touching the
+//! live chain. Every account, slot, contract and block hash read is cached, so the
+//! provider is queried at most once per key for the lifetime of the fork.
+
+use alloy_evm::revm::bytecode::Bytecode;
+use alloy_evm::revm::database_interface::{DBErrorMarker, Database, DatabaseRef};
+use alloy_evm::revm::state::AccountInfo;
+use alloy_primitives::{Address, B256, U256};
+use reth_ethereum::evm::revm::database::StateProviderDatabase;
+use reth_storage_api::{BlockNumReader, StateProviderFactory};
+use reth_tracing::tracing::info;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+/// Fork creation and fork read failures.
+#[derive(Debug, thiserror::Error)]
+pub enum ForkError {
+    /// The block is beyond the canonical tip
+    #[error("Block {0} not found in the canonical chain")]
+    BlockNotFound(u64),
+
+    /// The node was built without a fork source
+    #[error("Fork source not attached; call PoaNode::with_fork_source before forking")]
+    ForkSourceUnavailable,
+
+    /// Underlying storage read failed
+    #[error("Storage error in fork: {0}")]
+    Storage(String),
+}
+
+impl DBErrorMarker for ForkError {}
+
+/// Opens state snapshots at historical blocks.
+pub trait ForkSource: Send + Sync + Debug {
+    /// Snapshot of the state after block `block_number`.
+    fn fork_at(&self, block_number: u64) -> Result<ForkDb, ForkError>;
+}
+
+/// [`ForkSource`] backed by a reth provider's historical state.
+#[derive(Debug, Clone)]
+pub struct ProviderForkSource<P> {
+    provider: P,
+}
+
+impl<P> ProviderForkSource<P> {
+    /// Fork from `provider`.
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+}
+
+impl<P> ForkSource for ProviderForkSource<P>
+where
+    P: StateProviderFactory + BlockNumReader + Send + Sync + Debug,
+{
+    fn fork_at(&self, block_number: u64) -> Result<ForkDb, ForkError> {
+        let tip = self
+            .provider
+            .best_block_number()
+            .map_err(|e| ForkError::Storage(e.to_string()))?;
+        if block_number > tip {
+            return Err(ForkError::BlockNotFound(block_number));
+        }
+        let state = self
+            .provider
+            .history_by_block_number(block_number)
+            .map_err(|e| ForkError::Storage(e.to_string()))?;
+        Ok(ForkDb::new(block_number, StateProviderDatabase::new(state)))
+    }
+}
+
+/// Read-only, caching view of the state at one block.
+///
+/// Implements both [`Database`] and [`DatabaseRef`]; share it as `Arc<ForkDb>`.
+/// Writes are never accepted: execute on a `CacheDB` layered on top to keep changes.
+pub struct ForkDb {
+    block_number: u64,
+    state: Mutex<ForkState>,
+}
+
+/// Source database plus everything already read from it.
+struct ForkState {
+    source: Box<dyn DatabaseRef<Error = ForkError> + Send>,
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+    contracts: HashMap<B256, Bytecode>,
+    block_hashes: HashMap<u64, B256>,
+}
+
+/// Adapts a source database's errors to [`ForkError`].
+struct StorageErrors<DB>(DB);
+
+impl<DB: DatabaseRef> DatabaseRef for StorageErrors<DB> {
+    type Error = ForkError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, ForkError> {
+        self.0
+            .basic_ref(address)
+            .map_err(|e| ForkError::Storage(e.to_string()))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, ForkError> {
+        self.0
+            .code_by_hash_ref(code_hash)
+            .map_err(|e| ForkError::Storage(e.to_string()))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, ForkError> {
+        self.0
+            .storage_ref(address, index)
+            .map_err(|e| ForkError::Storage(e.to_string()))
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, ForkError> {
+        self.0
+            .block_hash_ref(number)
+            .map_err(|e| ForkError::Storage(e.to_string()))
+    }
+}
+
+/// `map[key]`, loading and remembering it on first access.
+fn cached<K: Eq + Hash, V: Clone>(
+    map: &mut HashMap<K, V>,
+    key: K,
+    load: impl FnOnce() -> Result<V, ForkError>,
+) -> Result<V, ForkError> {
+    if let Some(value) = map.get(&key) {
+        return Ok(value.clone());
+    }
+    let value = load()?;
+    map.insert(key, value.clone());
+    Ok(value)
+}
+
+impl ForkDb {
+    /// Fork the state `source` holds for block `block_number`.
+    pub fn new<DB>(block_number: u64, source: DB) -> Self
+    where
+        DB: DatabaseRef + Send + 'static,
+    {
+        Self {
+            block_number,
+            state: Mutex::new(ForkState {
+                source: Box::new(StorageErrors(source)),
+                accounts: HashMap::new(),
+                storage: HashMap::new(),
+                contracts: HashMap::new(),
+                block_hashes: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Block the snapshot was taken at.
+    #[inline]
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Number of cached entries (accounts, slots, contracts and block hashes).
+    pub fn cached_entries(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.accounts.len()
+            + state.storage.len()
+            + state.contracts.len()
+            + state.block_hashes.len()
+    }
+}
+
+impl Debug for ForkDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForkDb")
+            .field("block_number", &self.block_number)
+            .field("cached_entries", &self.cached_entries())
+            .finish_non_exhaustive()
+    }
+}
+
+impl DatabaseRef for ForkDb {
+    type Error = ForkError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, ForkError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let ForkState {
+            source, accounts, ..
+        } = &mut *state;
+        cached(accounts, address, || source.basic_ref(address))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, ForkError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let ForkState {
+            source, contracts, ..
+        } = &mut *state;
+        cached(contracts, code_hash, || source.code_by_hash_ref(code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, ForkError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let ForkState {
+            source, storage, ..
+        } = &mut *state;
+        cached(storage, (address, index), || {
+            source.storage_ref(address, index)
+        })
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, ForkError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let ForkState {
+            source,
+            block_hashes,
+            ..
+        } = &mut *state;
+        cached(block_hashes, number, || source.block_hash_ref(number))
+    }
+}
+
+impl Database for ForkDb {
+    type Error = ForkError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, ForkError> {
+        self.basic_ref(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, ForkError> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, ForkError> {
+        self.storage_ref(address, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, ForkError> {
+        self.block_hash_ref(number)
+    }
+}
+
+/// Open a fork at `block_number` through `source` on a blocking thread.
+pub async fn create_fork(
+    source: Arc<dyn ForkSource>,
+    block_number: u64,
+) -> Result<Arc<ForkDb>, ForkError> {
+    let fork = tokio::task::spawn_blocking(move || source.fork_at(block_number))
+        .await
+        .map_err(|e| ForkError::Storage(e.to_string()))??;
+    info!(target: "poa::fork", block_number, "Fork created");
+    Ok(Arc::new(fork))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::PoaChainSpec;
+    use alloy_evm::revm::database::InMemoryDB;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves the dev genesis state as block 0; later blocks do not exist.
+    #[derive(Debug)]
+    struct GenesisSource(PoaChainSpec);
+
+    impl ForkSource for GenesisSource {
+        fn fork_at(&self, block_number: u64) -> Result<ForkDb, ForkError> {
+            if block_number > 0 {
+                return Err(ForkError::BlockNotFound(block_number));
+            }
+            Ok(ForkDb::new(0, self.0.genesis_alloc_as_revm_state()))
+        }
+    }
+
+    /// Counts account lookups reaching the wrapped database.
+    #[derive(Clone)]
+    struct CountingDb(InMemoryDB, Arc<AtomicUsize>);
+
+    impl DatabaseRef for CountingDb {
+        type Error = <InMemoryDB as DatabaseRef>::Error;
+
+        fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            self.0.basic_ref(address)
+        }
+
+        fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.0.code_by_hash_ref(code_hash)
+        }
+
+        fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.0.storage_ref(address, index)
+        }
+
+        fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+            self.0.block_hash_ref(number)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fork_at_genesis_reads_coinbase_balance() {
+        let chain = PoaChainSpec::dev_chain();
+        let genesis = &chain.inner().genesis;
+        let coinbase = genesis.coinbase;
+        let expected = genesis
+            .alloc
+            .get(&coinbase)
+            .map(|a| a.balance)
+            .unwrap_or_default();
+
+        let fork = create_fork(Arc::new(GenesisSource(chain.clone())), 0)
+            .await
+            .unwrap();
+        assert_eq!(fork.block_number(), 0);
+        let balance = fork
+            .basic_ref(coinbase)
+            .unwrap()
+            .map(|info| info.balance)
+            .unwrap_or_default();
+        assert_eq!(balance, expected);
+    }
+
+    #[tokio::test]
+    async fn test_fork_beyond_tip_is_not_found() {
+        let source = Arc::new(GenesisSource(PoaChainSpec::dev_chain()));
+        assert!(matches!(
+            create_fork(source, 5).await,
+            Err(ForkError::BlockNotFound(5))
+        ));
+    }
+
+    #[test]
+    fn test_fork_caches_reads() {
+        let chain = PoaChainSpec::dev_chain();
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let source = CountingDb(chain.genesis_alloc_as_revm_state(), lookups.clone());
+        let mut fork = ForkDb::new(0, source);
+
+        let signer = chain.signers()[0];
+        let first = fork.basic(signer).unwrap();
+        let again = fork.basic(signer).unwrap();
+        assert_eq!(first, again);
+        assert_eq!(lookups.load(Ordering::Relaxed), 1);
+
+        // Unknown accounts are cached too.
+        let missing = Address::repeat_byte(0x77);
+        assert_eq!(fork.basic_ref(missing).unwrap(), None);
+        assert_eq!(fork.basic_ref(missing).unwrap(), None);
+        assert_eq!(lookups.load(Ordering::Relaxed), 2);
+        assert_eq!(fork.cached_entries(), 2);
+    }
+
+    #[test]
+    fn test_fork_storage_matches_genesis() {
+        let chain = PoaChainSpec::dev_chain();
+        let (address, slot, value) = chain
+            .inner()
+            .genesis
+            .alloc
+            .iter()
+            .find_map(|(address, account)| {
+                let (slot, value) = account.storage.as_ref()?.iter().next()?;
+                Some((
+                    *address,
+                    U256::from_be_bytes(slot.0),
+                    U256::from_be_bytes(value.0),
+                ))
+            })
+            .expect("dev genesis has contract storage");
+
+        let fork = Arc::new(ForkDb::new(0, chain.genesis_alloc_as_revm_state()));
+        assert_eq!(fork.storage_ref(address, slot).unwrap(), value);
+    }
+}
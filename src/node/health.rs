@@ -0,0 +1,245 @@
+//! Load balancer health probe.
+//!
+//! Kubernetes and cloud load balancers want a plain HTTP check, not a JSON-RPC
+//! call. [`HealthProbe::serve`] starts a separate TCP endpoint (raw tokio, no web
+//! framework) that answers `GET /health` with `200 OK` and body `OK`, or with
+//! `503 Service Unavailable` and body `SICK: <reason>` when [`HealthProbe::check`]
+//! fails. Every other request gets `404 Not Found`, and a client that does not send
+//! its request head within five seconds gets `408 Request Timeout`.
+//!
+//! The node is sick while it is still syncing, when the chain has no authorized
+//! signers, while block production is paused for a revert, or while the datadir is
+//! below the critical free-space threshold.
+
+use super::disk::DiskGuard;
+use super::revert::ProductionPause;
+use crate::chainspec::PoaChainSpec;
+use reth_ethereum::network::api::NetworkInfo;
+use reth_tracing::tracing::{info, warn};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head read before answering.
+const MAX_REQUEST_BYTES: usize = 1024;
+
+/// How long a client may take to send its request head.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sync state consulted by the probe.
+///
+/// Implemented for every reth [`NetworkInfo`], so the launched node's network
+/// handle can be passed directly.
+pub trait SyncStatus: Send + Sync {
+    /// Whether the node is still catching up with the chain.
+    fn is_syncing(&self) -> bool;
+}
+
+impl<N: NetworkInfo> SyncStatus for N {
+    fn is_syncing(&self) -> bool {
+        NetworkInfo::is_syncing(self)
+    }
+}
+
+/// Health check served on the probe endpoint.
+#[derive(Clone)]
+pub struct HealthProbe {
+    chain_spec: Arc<PoaChainSpec>,
+    production_pause: ProductionPause,
+    disk_guard: DiskGuard,
+    sync: Option<Arc<dyn SyncStatus>>,
+}
+
+impl HealthProbe {
+    /// Probe for a node running `chain_spec`, sharing its pause switch and disk guard.
+    pub fn new(
+        chain_spec: Arc<PoaChainSpec>,
+        production_pause: ProductionPause,
+        disk_guard: DiskGuard,
+    ) -> Self {
+        Self {
+            chain_spec,
+            production_pause,
+            disk_guard,
+            sync: None,
+        }
+    }
+
+    /// Report the node as sick while `sync` says it is syncing.
+    pub fn with_sync_status(mut self, sync: Arc<dyn SyncStatus>) -> Self {
+        self.sync = Some(sync);
+        self
+    }
+
+    /// `Ok` if the node is healthy, otherwise the reason it is not.
+    pub fn check(&self) -> Result<(), String> {
+        if self.sync.as_ref().is_some_and(|sync| sync.is_syncing()) {
+            return Err("not synced".to_string());
+        }
+        if self.chain_spec.with_effective_signers(<[_]>::is_empty) {
+            return Err("no signers".to_string());
+        }
+        if self.production_pause.is_paused() {
+            return Err("block production paused".to_string());
+        }
+        self.disk_guard
+            .check_block_building()
+            .map_err(|err| err.to_string())
+    }
+
+    /// Listen on `addr` and answer health checks until the runtime shuts down.
+    ///
+    /// Returns the bound address (useful with port 0).
+    pub async fn serve(self, addr: SocketAddr) -> std::io::Result<SocketAddr> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        info!(target: "poa::health", %local_addr, "Health probe listening");
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let probe = self.clone();
+                        tokio::spawn(async move { probe.respond(stream).await });
+                    }
+                    Err(err) => warn!(target: "poa::health", %err, "Health probe accept failed"),
+                }
+            }
+        });
+        Ok(local_addr)
+    }
+
+    async fn respond(&self, mut stream: TcpStream) {
+        let mut buf = [0u8; MAX_REQUEST_BYTES];
+        let head = tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream, &mut buf)).await;
+        let response = match head {
+            Ok(len) => self.response(&buf[..len]),
+            Err(_) => http_response("408 Request Timeout", "Request Timeout"),
+        };
+        let _ = stream.write_all(response.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+
+    /// Full HTTP response to the request head `request`.
+    fn response(&self, request: &[u8]) -> String {
+        let request_line = request
+            .split(|&b| b == b'\r' || b == b'\n')
+            .next()
+            .unwrap_or(&[]);
+        let mut parts = request_line.split(|&b| b == b' ');
+        let (status, body) = match (parts.next(), parts.next()) {
+            (Some(b"GET"), Some(b"/health")) => match self.check() {
+                Ok(()) => ("200 OK", "OK".to_string()),
+                Err(reason) => ("503 Service Unavailable", format!("SICK: {reason}")),
+            },
+            _ => ("404 Not Found", "Not Found".to_string()),
+        };
+        http_response(status, &body)
+    }
+}
+
+/// Read from `stream` into `buf` until the end of the request head, EOF or a full
+/// buffer; returns the number of bytes read.
+async fn read_head(stream: &mut TcpStream, buf: &mut [u8]) -> usize {
+    let mut len = 0;
+    while len < buf.len() && !buf[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+        match stream.read(&mut buf[len..]).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => len += n,
+        }
+    }
+    len
+}
+
+/// Full HTTP response with `status` and a plain-text `body`; the connection closes.
+fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Syncing;
+
+    impl SyncStatus for Syncing {
+        fn is_syncing(&self) -> bool {
+            true
+        }
+    }
+
+    fn probe() -> HealthProbe {
+        HealthProbe::new(
+            Arc::new(PoaChainSpec::dev_chain()),
+            ProductionPause::new(),
+            DiskGuard::new(),
+        )
+    }
+
+    /// Send `request` over a real TCP connection and return the raw response.
+    async fn get(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    fn status_and_body(response: &str) -> (&str, &str) {
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap(), body)
+    }
+
+    #[tokio::test]
+    async fn test_health_probe_over_tcp() {
+        let probe = probe();
+        let pause = probe.production_pause.clone();
+        let addr = probe.serve("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        let request = "GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let response = get(addr, request).await;
+        assert_eq!(status_and_body(&response), ("HTTP/1.1 200 OK", "OK"));
+        assert!(response.contains("Content-Length: 2\r\n"));
+
+        pause.pause();
+        let response = get(addr, request).await;
+        assert_eq!(
+            status_and_body(&response),
+            (
+                "HTTP/1.1 503 Service Unavailable",
+                "SICK: block production paused"
+            )
+        );
+
+        let response = get(addr, "GET /metrics HTTP/1.1\r\n\r\n").await;
+        assert_eq!(status_and_body(&response).0, "HTTP/1.1 404 Not Found");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_request_times_out() {
+        let addr = probe().serve("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        // The request head never ends; the probe gives up after REQUEST_TIMEOUT.
+        let response = get(addr, "GET /health HTTP/1.1\r\n").await;
+        assert_eq!(
+            status_and_body(&response),
+            ("HTTP/1.1 408 Request Timeout", "Request Timeout")
+        );
+    }
+
+    #[test]
+    fn test_health_check_reasons() {
+        assert_eq!(probe().check(), Ok(()));
+        let syncing = probe().with_sync_status(Arc::new(Syncing));
+        assert_eq!(syncing.check(), Err("not synced".to_string()));
+    }
+}
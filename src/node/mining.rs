@@ -0,0 +1,199 @@
+//! Triggers for reth's local miner.
+//!
+//! With `--eager-mining` or `--automine` the node mines as soon as transactions
+//! arrive. Instead of reth's instant mode, the local miner runs on the trigger stream
+//! returned by [`EagerTrigger::new`], and [`EagerTrigger::run`] decides when to fire it:
+//!
+//! - normally on every arrival, coalescing arrivals that come in while a build is
+//!   already queued;
+//! - while [`Backpressure`] reports the pool overloaded, at most once per block
+//!   period ([`Backpressure::eager_build_delay`]), so blocks fill up instead of
+//!   thrashing on every arrival;
+//! - with automine, only when the [`AutomineControl`] mining mode allows it: held to
+//!   the mining interval, or not at all in manual mode.
+//!
+//! All waits are async on the trigger task; the payload builder never blocks. With
+//! [`EagerTrigger::with_shutdown_token`] the task stops when the node shuts down,
+//! even while a build is held, and the local miner's trigger stream ends.
+
+use crate::node::automine::AutomineControl;
+use crate::node::backpressure::Backpressure;
+use alloy_primitives::TxHash;
+use reth_transaction_pool::TransactionPool;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+/// Fires the local miner when transactions arrive, throttled under back-pressure.
+#[derive(Debug)]
+pub struct EagerTrigger {
+    backpressure: Backpressure,
+    block_period: Duration,
+    automine: AutomineControl,
+    shutdown: CancellationToken,
+    trigger: mpsc::Sender<()>,
+}
+
+impl EagerTrigger {
+    /// Trigger held to `block_period` while `backpressure` is overloaded, and the
+    /// stream to hand to the local miner (`MiningMode::trigger`).
+    pub fn new(backpressure: Backpressure, block_period: Duration) -> (Self, ReceiverStream<()>) {
+        // One queued build covers every arrival before it starts.
+        let (trigger, triggers) = mpsc::channel(1);
+        let this = Self {
+            backpressure,
+            block_period,
+            automine: AutomineControl::disabled(),
+            shutdown: CancellationToken::new(),
+            trigger,
+        };
+        (this, ReceiverStream::new(triggers))
+    }
+
+    /// Follow the mining mode of `automine` (`evm_setAutomine`, `evm_setIntervalMining`).
+    pub fn with_automine(mut self, automine: AutomineControl) -> Self {
+        self.automine = automine;
+        self
+    }
+
+    /// Stop firing once `token` is cancelled, abandoning any wait in progress.
+    pub fn with_shutdown_token(mut self, token: CancellationToken) -> Self {
+        self.shutdown = token;
+        self
+    }
+
+    /// Fire on new pending transactions of `pool` until the miner or pool goes away or
+    /// the shutdown token is cancelled.
+    pub async fn run<Pool: TransactionPool>(self, pool: Pool) {
+        self.run_with(pool.pending_transactions_listener()).await
+    }
+
+    /// Fire on each hash received from `arrivals`.
+    pub async fn run_with(self, arrivals: mpsc::Receiver<TxHash>) {
+        // Dropping `self` afterwards closes the trigger stream.
+        self.shutdown
+            .run_until_cancelled(self.fire_on(arrivals))
+            .await;
+    }
+
+    /// Loop of [`Self::run_with`], ended early by the shutdown token.
+    async fn fire_on(&self, mut arrivals: mpsc::Receiver<TxHash>) {
+        while arrivals.recv().await.is_some() {
+            let now = tokio::time::Instant::now().into_std();
+            if let Some(delay) = self.backpressure.eager_build_delay(now, self.block_period) {
+                tokio::time::sleep(delay).await;
+            }
+            self.automine.wait_for_mining_slot().await;
+            // Arrivals during the wait are included in the build fired below.
+            while arrivals.try_recv().is_ok() {}
+            if let Err(TrySendError::Closed(())) = self.trigger.try_send(()) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::backpressure::BackpressureConfig;
+    use futures_util::StreamExt;
+
+    fn arrival(arrivals: &mpsc::Sender<TxHash>) {
+        arrivals.try_send(TxHash::random()).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fires_on_arrival_and_coalesces() {
+        let (trigger, mut triggers) = EagerTrigger::new(Backpressure::default(), Duration::ZERO);
+        let (arrivals, rx) = mpsc::channel(16);
+        tokio::spawn(trigger.run_with(rx));
+
+        arrival(&arrivals);
+        arrival(&arrivals);
+        arrival(&arrivals);
+        assert_eq!(triggers.next().await, Some(()));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        // The three arrivals were coalesced into one build.
+        assert!(futures_util::FutureExt::now_or_never(triggers.next()).is_none());
+
+        drop(arrivals);
+        assert_eq!(triggers.next().await, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_held_to_block_period_while_overloaded() {
+        let period = Duration::from_secs(2);
+        let backpressure = Backpressure::new(BackpressureConfig::new(10, 5));
+        let (trigger, mut triggers) = EagerTrigger::new(backpressure.clone(), period);
+        let (arrivals, rx) = mpsc::channel(16);
+        tokio::spawn(trigger.run_with(rx));
+
+        let start = tokio::time::Instant::now();
+        backpressure.record_block(1, 3, start.into_std());
+        backpressure.observe(10);
+
+        arrival(&arrivals);
+        assert_eq!(triggers.next().await, Some(()));
+        assert_eq!(start.elapsed(), period);
+        assert_eq!(backpressure.status().eager_suppressed, 1);
+
+        // Back to normal: the next arrival fires without waiting.
+        backpressure.observe(5);
+        let fired = tokio::time::Instant::now();
+        arrival(&arrivals);
+        assert_eq!(triggers.next().await, Some(()));
+        assert_eq!(fired.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_follows_automine_mining_mode() {
+        let automine = AutomineControl::new();
+        let (trigger, mut triggers) = EagerTrigger::new(Backpressure::default(), Duration::ZERO);
+        let (arrivals, rx) = mpsc::channel(16);
+        tokio::spawn(trigger.with_automine(automine.clone()).run_with(rx));
+
+        // Automine: every arrival is mined at once.
+        arrival(&arrivals);
+        assert_eq!(triggers.next().await, Some(()));
+
+        // Manual: nothing is mined, however long the transaction waits.
+        automine.set_automine(false);
+        arrival(&arrivals);
+        tokio::time::sleep(Duration::from_secs(3_600)).await;
+        assert!(futures_util::FutureExt::now_or_never(triggers.next()).is_none());
+
+        // Interval: the held arrival is mined one interval after the last block.
+        let start = tokio::time::Instant::now();
+        automine.record_block(start.into_std());
+        automine.set_interval_mining(2_000);
+        assert_eq!(triggers.next().await, Some(()));
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_ends_held_wait() {
+        let automine = AutomineControl::new();
+        automine.set_automine(false);
+        let token = CancellationToken::new();
+        let (trigger, mut triggers) = EagerTrigger::new(Backpressure::default(), Duration::ZERO);
+        let (arrivals, rx) = mpsc::channel(16);
+        let task = tokio::spawn(
+            trigger
+                .with_automine(automine)
+                .with_shutdown_token(token.clone())
+                .run_with(rx),
+        );
+
+        // Manual mode holds the arrival until the node shuts down.
+        arrival(&arrivals);
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert!(!task.is_finished());
+
+        token.cancel();
+        task.await.unwrap();
+        assert_eq!(triggers.next().await, None);
+    }
+}
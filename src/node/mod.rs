@@ -4,17 +4,70 @@
 //! This is the core architectural change that makes the node actually use POA consensus
 //! instead of being a vanilla Ethereum dev-mode node with unused POA code.
 
+pub mod alerts;
+pub mod attributes;
+pub mod automine;
+pub mod backpressure;
 pub mod builder;
+pub mod diagnostics;
+pub mod disk;
 pub mod engine;
+pub mod errors;
+pub mod fork;
+pub mod health;
+pub mod mining;
+pub mod peers;
+pub mod pool;
+pub mod replay;
+pub mod revert;
+pub mod shutdown;
+pub mod sync;
+pub mod warmup;
 
+#[cfg(test)]
+mod e2e;
+
+pub use attributes::PoaPayloadAttributesBuilder;
+pub use automine::{AutomineControl, InclusionTracker};
 pub use builder::PoaConsensusBuilder;
+pub use diagnostics::{DiagnosticReport, NodeRuntimeStats, RuntimeStats};
 pub use engine::{strip_extra_data, PoaEngineValidator, PoaEngineValidatorBuilder};
+pub use errors::NodeError;
+pub use fork::{ForkDb, ForkError, ForkSource, ProviderForkSource};
+pub use health::{HealthProbe, SyncStatus};
+pub use peers::{validate_bootnode_url, PoaNetworkBuilder};
+pub use pool::{check_admission, AdmissionError, PoaPoolBuilder, PoaTransactionValidator};
+pub use backpressure::{Backpressure, BackpressureConfig};
+pub use replay::{
+    AccountOverride, BlockReplayer, ProviderBlockReplayer, ReplayError, ReplayResult,
+};
+pub use revert::{
+    open_chain_store, revert_chain, ProductionPause, ProviderChainStore, RevertError,
+    RevertableChain,
+};
+pub use shutdown::{BuildShutdown, ShutdownError, SHUTDOWN_BUILD_TIMEOUT};
+pub use sync::SyncMode;
+pub use warmup::{WarmupConfig, WarmupReport, WarmupStats};
 
 use crate::chainspec::PoaChainSpec;
+use crate::consensus::{Checkpoint, PendingVote, TxTypes, VoterSet};
 use crate::evm::PoaExecutorBuilder;
-use crate::payload::PoaPayloadBuilderBuilder;
-use crate::signer::SignerManager;
+#[cfg(feature = "otlp-metrics")]
+use crate::metrics::otlp::{OtlpTelemetry, TelemetryError};
+use crate::metrics::ChainMetrics;
+use crate::payload::{
+    BlockMetadataProvider, CrossChainMessageExtractor, DepositSource, DepositStateError,
+    DepositTracker, FeePolicy, PoaPayloadBuilderBuilder, SignerRotation, TrackerStateError,
+    WithdrawalQueueTracker,
+};
+use crate::rpc::{AutomineLayer, BackpressureLayer, MethodRateLimit, RateLimitLayer};
+use crate::signer::{SignatureCache, SignerManager};
+use alloy_evm::revm::primitives::hardfork::SpecId;
+use reth_network_peers::NodeRecord;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tower::layer::util::Stack;
 
 // Node builder types
 use reth_ethereum::node::builder::{
@@ -27,17 +80,12 @@ use reth_ethereum::node::builder::{
 use reth_ethereum::node::api::{FullNodeComponents, PayloadAttributesBuilder};
 
 // Ethereum component builders (pool, network, executor, payload)
-use reth_ethereum::node::{
-    EthEngineTypes, EthereumAddOns, EthereumEthApiBuilder, EthereumNetworkBuilder,
-    EthereumPoolBuilder,
-};
+use reth_ethereum::node::{EthEngineTypes, EthereumAddOns, EthereumEthApiBuilder};
 
 // Primitive and storage types
+use reth_ethereum::storage::StorageChangeSetReader;
 use reth_ethereum::{provider::EthStorage, EthPrimitives};
 
-// Engine types for payload attributes
-use reth_ethereum::engine::local::LocalPayloadAttributesBuilder;
-
 // Payload types
 use reth_payload_primitives::PayloadTypes;
 
@@ -46,7 +94,7 @@ use reth_chainspec::ChainSpec;
 
 // RPC add-ons
 use reth_ethereum::node::builder::rpc::{
-    BasicEngineApiBuilder, BasicEngineValidatorBuilder, Identity, RpcAddOns,
+    BasicEngineApiBuilder, BasicEngineValidatorBuilder, RpcAddOns,
 };
 
 /// Custom POA Node type.
@@ -65,6 +113,8 @@ pub struct PoaNode {
     dev_mode: bool,
     /// Hot state cache capacity for governance reads (Phase 5.31).
     cache_size: usize,
+    /// Recovered sealers of validated headers, shared with consensus.
+    signature_cache: Arc<SignatureCache>,
     /// Maximum deployed contract code size override (Phase 2.11).
     /// `None` = Ethereum default (24,576 bytes).
     max_contract_size: Option<usize>,
@@ -73,10 +123,82 @@ pub struct PoaNode {
     calldata_gas_per_byte: u64,
     /// Zero-gas mode: disable base fee validation, accept gasPrice=0 txs.
     zero_gas: bool,
+    /// Hardfork forced on the EVM; `None` = the chain spec's active hardfork.
+    evm_spec: Option<SpecId>,
+    /// Fee floor (wei) on pool transactions selected for blocks; 0 = none.
+    min_gas_price: u128,
+    /// Include every valid pending transaction, ignoring the fee floor.
+    sequencer_mode: bool,
+    /// Which local key seals each produced block.
+    signer_rotation: SignerRotation,
     /// Addresses to credit with a withdrawal on every built block.
     infinite_fund: Vec<alloy_primitives::Address>,
+    /// Governance withdrawal queue cursors, fed from canonical blocks.
+    withdrawal_queue: Arc<WithdrawalQueueTracker>,
+    /// L1 bridge deposits relayed at the top of every block (L2 deployments).
+    deposit_source: Option<DepositSource>,
+    /// L1 deposit cursors of recent canonical blocks.
+    deposit_tracker: Arc<DepositTracker>,
+    /// Client identifier written into the vanity of produced blocks.
+    client_tag: String,
+    /// Datadir free-space state; block building pauses while it is critical.
+    disk_guard: disk::DiskGuard,
+    /// Stops block production while set.
+    production_pause: ProductionPause,
+    /// Shutdown token and the block builds in progress, shared with the payload builder.
+    shutdown: BuildShutdown,
+    /// Historical state source for [`create_fork`](Self::create_fork), attached after launch.
+    forks: Option<Arc<dyn ForkSource>>,
+    /// Canonical signer-set votes and local proposals, shared with the payload builder
+    /// and the RPC.
+    votes: VoterSet,
+    /// Trusted checkpoint handed to consensus; None = validate every header.
+    checkpoint: Option<Checkpoint>,
+    /// EIP-2718 transaction types consensus and the pool accept; None = those of the
+    /// active forks.
+    allowed_tx_types: Option<TxTypes>,
+    /// Directory of canonical epoch snapshots seeding the signer set; None = not used.
+    epoch_snapshot_dir: Option<PathBuf>,
+    /// Peer addresses that stay connected regardless of the peer limit.
+    trusted_peers: Vec<NodeRecord>,
+    /// How the node catches up with the chain.
+    sync_mode: SyncMode,
+    /// Peers dialed and kept connected regardless of discovery.
+    static_peers: Vec<NodeRecord>,
+    /// Address of the load balancer health probe; None = not served.
+    health_probe: Option<SocketAddr>,
+    /// Transaction ingress back-pressure shared by the RPC middleware and payload builder.
+    backpressure: Backpressure,
+    /// Per-method RPC rate limits; empty = unlimited.
+    rpc_rate_limits: RateLimitLayer,
+    /// Serve geth's `txpool_content` / `txpool_inspect` without reth's txpool namespace.
+    mempool_content_api: bool,
+    /// Cap on pending pool transactions; None = reth's pool limits only.
+    max_pending_transactions: Option<usize>,
+    /// Dev-mode automine switch shared by the payload builder and the `evm_*` RPCs.
+    automine: AutomineControl,
+    /// Canonical inclusions awaited by automine `eth_sendRawTransaction` calls.
+    inclusions: InclusionTracker,
+    /// Cold-start state preloading run by the payload builder; None = disabled.
+    warmup: Option<WarmupConfig>,
+    /// Stats of the startup warm-up, shared with `poa_status`.
+    warmup_report: WarmupReport,
+    /// Chain metrics the payload builder records bundle and compression stats in.
+    chain_metrics: Option<Arc<ChainMetrics>>,
+    /// Measure how well the body of every block sealed here compresses.
+    compression_stats: bool,
+    /// Metadata attached to every signed block; None = none.
+    block_metadata: Option<BlockMetadataProvider>,
+    /// Commit to the block metadata instead of embedding it.
+    block_metadata_hash: bool,
+    /// OTLP exporter for the chain metrics; None = no push export.
+    #[cfg(feature = "otlp-metrics")]
+    telemetry: Option<OtlpTelemetry>,
 }
 
+/// Largest chain ID whose EIP-155 `v` value (`chain_id * 2 + 36`) fits in a `u64`.
+pub const MAX_EIP155_CHAIN_ID: u64 = (u64::MAX - 36) / 2;
+
 impl PoaNode {
     /// Create a new PoaNode with the given chain specification.
     pub fn new(chain_spec: Arc<PoaChainSpec>) -> Self {
@@ -85,16 +207,78 @@ impl PoaNode {
             signer_manager: Arc::new(SignerManager::new()),
             dev_mode: false,
             cache_size: 1024,
+            signature_cache: Arc::default(),
             max_contract_size: None,
             calldata_gas_per_byte: 4, // POA default: cheap calldata
             zero_gas: false,
+            evm_spec: None,
+            min_gas_price: 0,
+            sequencer_mode: false,
+            signer_rotation: SignerRotation::default(),
             infinite_fund: Vec::new(),
+            withdrawal_queue: Arc::new(WithdrawalQueueTracker::new()),
+            deposit_source: None,
+            deposit_tracker: Arc::new(DepositTracker::new()),
+            client_tag: crate::consensus::vanity::default_client_tag(),
+            disk_guard: disk::DiskGuard::new(),
+            production_pause: ProductionPause::new(),
+            shutdown: BuildShutdown::default(),
+            forks: None,
+            votes: VoterSet::new(),
+            checkpoint: None,
+            allowed_tx_types: None,
+            epoch_snapshot_dir: None,
+            trusted_peers: Vec::new(),
+            sync_mode: SyncMode::default(),
+            static_peers: Vec::new(),
+            health_probe: None,
+            backpressure: Backpressure::default(),
+            rpc_rate_limits: RateLimitLayer::default(),
+            mempool_content_api: false,
+            max_pending_transactions: None,
+            automine: AutomineControl::disabled(),
+            inclusions: InclusionTracker::new(),
+            warmup: None,
+            warmup_report: WarmupReport::new(),
+            chain_metrics: None,
+            compression_stats: false,
+            block_metadata: None,
+            block_metadata_hash: false,
+            #[cfg(feature = "otlp-metrics")]
+            telemetry: None,
         }
     }
 
     /// Set dev mode on the node
-    pub fn with_dev_mode(mut self, dev_mode: bool) -> Self {
+    ///
+    /// Dev mode skips signature verification, so it is refused on a production chain
+    /// spec ([`PoaChainSpec::is_production`]).
+    pub fn with_dev_mode(mut self, dev_mode: bool) -> Result<Self, NodeError> {
+        if dev_mode && self.chain_spec.is_production() {
+            return Err(NodeError::DevModeOnProduction {
+                chain_id: self.chain_spec.inner().chain.id(),
+            });
+        }
         self.dev_mode = dev_mode;
+        Ok(self)
+    }
+
+    /// Run with `chain_id` instead of the spec's chain ID, keeping every other parameter.
+    ///
+    /// The chain ID reaches transaction signing and EVM `TxEnv` validation through the
+    /// chain spec.
+    ///
+    /// # Panics
+    ///
+    /// If `chain_id` is 0 or 1 (reserved), or too large for an EIP-155 legacy
+    /// signature `v` (`chain_id * 2 + 36`) to fit in 64 bits.
+    pub fn with_custom_chain_id(mut self, chain_id: u64) -> Self {
+        assert!(chain_id > 1, "chain ID {chain_id} is reserved");
+        assert!(
+            chain_id <= MAX_EIP155_CHAIN_ID,
+            "chain ID {chain_id} exceeds the EIP-155 maximum {MAX_EIP155_CHAIN_ID}"
+        );
+        self.chain_spec = Arc::new((*self.chain_spec).clone().with_chain_id(chain_id));
         self
     }
 
@@ -110,6 +294,19 @@ impl PoaNode {
         self
     }
 
+    /// Remember the sealers of up to `size` validated headers
+    /// ([`DEFAULT_SIGNATURE_CACHE_SIZE`](crate::signer::DEFAULT_SIGNATURE_CACHE_SIZE) by
+    /// default).
+    pub fn with_signature_cache_size(mut self, size: usize) -> Self {
+        self.signature_cache = Arc::new(SignatureCache::new(size));
+        self
+    }
+
+    /// Cache of the sealers recovered by consensus; its stats feed the metrics output.
+    pub fn signature_cache(&self) -> &Arc<SignatureCache> {
+        &self.signature_cache
+    }
+
     /// Override the maximum deployed contract code size (Phase 2.11).
     ///
     /// `0` → no override (use Ethereum's 24,576-byte default).
@@ -133,11 +330,423 @@ impl PoaNode {
         self
     }
 
+    /// Run the EVM under `spec` instead of the chain spec's active hardfork; see
+    /// [`PoaEvmFactory::with_spec_override`](crate::evm::PoaEvmFactory::with_spec_override).
+    ///
+    /// Blocks executed under another hardfork are invalid to every other node, so the
+    /// override is refused unless [`with_dev_mode`](Self::with_dev_mode) enabled dev
+    /// mode first.
+    pub fn with_evm_spec(mut self, spec: Option<SpecId>) -> Result<Self, NodeError> {
+        if let Some(spec) = spec.filter(|_| !self.dev_mode) {
+            return Err(NodeError::EvmSpecOutsideDevMode {
+                spec: format!("{spec:?}"),
+            });
+        }
+        self.evm_spec = spec;
+        Ok(self)
+    }
+
+    /// Leave pool transactions paying less than `min_gas_price` wei per gas out of
+    /// produced blocks, and refuse those that can never pay it at admission (0
+    /// disables the floor).
+    pub fn with_min_gas_price(mut self, min_gas_price: u128) -> Self {
+        self.min_gas_price = min_gas_price;
+        self
+    }
+
+    /// Produce blocks as a single sequencer: every valid pending transaction is
+    /// included, limited only by the block gas limit (the fee floor is lifted).
+    pub fn with_sequencer_mode(mut self, sequencer_mode: bool) -> Self {
+        self.sequencer_mode = sequencer_mode;
+        self
+    }
+
+    /// Seal produced blocks with the local key chosen by `rotation`: in turn only,
+    /// also out of turn (the default), or always one fixed key.
+    pub fn with_signer_rotation(mut self, rotation: SignerRotation) -> Self {
+        self.signer_rotation = rotation;
+        self
+    }
+
     /// Set the list of addresses to credit with a per-block withdrawal.
     pub fn with_infinite_fund(mut self, addrs: Vec<alloy_primitives::Address>) -> Self {
         self.infinite_fund = addrs;
         self
     }
+
+    /// Persist the governance withdrawal queue cursors at `path`, resuming from the
+    /// cursors saved there, so entries already paid are not paid again after a restart.
+    pub fn with_withdrawal_queue_state(mut self, path: &Path) -> Result<Self, TrackerStateError> {
+        self.withdrawal_queue = Arc::new(WithdrawalQueueTracker::open(path)?);
+        Ok(self)
+    }
+
+    /// Withdrawal queue cursors; feed it every canonical block after launch
+    /// ([`WithdrawalQueueTracker::record_canonical_block`]).
+    pub fn withdrawal_queue(&self) -> &Arc<WithdrawalQueueTracker> {
+        &self.withdrawal_queue
+    }
+
+    /// Relay the L1 bridge deposits found by `extractor` at the top of every block
+    /// (see `payload::deposits`).
+    pub fn with_cross_chain_message_extractor(
+        mut self,
+        extractor: Arc<dyn CrossChainMessageExtractor>,
+    ) -> Self {
+        self.deposit_source = Some(DepositSource::new(extractor));
+        self
+    }
+
+    /// Persist the L1 deposit cursors at `path`, resuming from the cursors saved
+    /// there, so deposits already relayed are not relayed again after a restart.
+    pub fn with_deposit_state(mut self, path: &Path) -> Result<Self, DepositStateError> {
+        self.deposit_tracker = Arc::new(DepositTracker::open(path)?);
+        Ok(self)
+    }
+
+    /// Set the client tag written into the vanity of produced blocks.
+    pub fn with_client_tag(mut self, tag: impl Into<String>) -> Self {
+        self.client_tag = tag.into();
+        self
+    }
+
+    /// Share the [`DiskGuard`](disk::DiskGuard) updated by a running
+    /// [`DiskMonitor`](disk::DiskMonitor).
+    pub fn with_disk_guard(mut self, guard: disk::DiskGuard) -> Self {
+        self.disk_guard = guard;
+        self
+    }
+
+    /// Share the transaction ingress [`Backpressure`] controller.
+    ///
+    /// The same handle gates `eth_sendRawTransaction` and throttles the eager-mining
+    /// [`EagerTrigger`](mining::EagerTrigger). After launch it must be fed pool
+    /// occupancy by [`Backpressure::monitor_pool`] and canonical blocks by
+    /// [`Backpressure::record_block`].
+    pub fn with_backpressure(mut self, backpressure: Backpressure) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// Refuse RPC calls to each limited method beyond its `max_per_minute`, counted
+    /// per client connection and per call inside batches.
+    ///
+    /// Rejected calls get the `-32005` "limit exceeded" error with a retry hint; see
+    /// [`rate_limit`](crate::rpc::rate_limit).
+    pub fn with_rate_limited_rpc(mut self, limits: Vec<MethodRateLimit>) -> Self {
+        self.rpc_rate_limits = RateLimitLayer::new(&limits);
+        self
+    }
+
+    /// Serve geth-compatible `txpool_content` and `txpool_inspect`.
+    ///
+    /// Pending and queued transactions are grouped by sender and nonce, as monitoring
+    /// tools built for geth expect; see [`txpool`](crate::rpc::txpool). The methods are
+    /// registered after launch from [`mempool_content_api`](Self::mempool_content_api)
+    /// when no transport serves reth's `txpool` namespace, which already has them.
+    pub fn with_mempool_content_api(mut self, enabled: bool) -> Self {
+        self.mempool_content_api = enabled;
+        self
+    }
+
+    /// Whether [`with_mempool_content_api`](Self::with_mempool_content_api) is enabled.
+    pub fn mempool_content_api(&self) -> bool {
+        self.mempool_content_api
+    }
+
+    /// Keep at most `max` pending transactions in the pool. `0` → reth's limit.
+    ///
+    /// Sets the pool's pending sub-pool limit (reth's `pending_max_count`), so the
+    /// pool discards the surplus itself, lowest priority first. See `node::pool`.
+    pub fn with_max_pending_transactions(mut self, max: usize) -> Self {
+        self.max_pending_transactions = (max > 0).then_some(max);
+        self
+    }
+
+    /// Pending transaction cap set with
+    /// [`with_max_pending_transactions`](Self::with_max_pending_transactions).
+    pub fn max_pending_transactions(&self) -> Option<usize> {
+        self.max_pending_transactions
+    }
+
+    /// Enable dev-mode automine with shared `control` and `inclusions` handles.
+    ///
+    /// `eth_sendRawTransaction` waits until the transaction is canonical. The same
+    /// control must gate the local miner through
+    /// [`EagerTrigger::with_automine`](mining::EagerTrigger::with_automine), and both
+    /// handles must be fed canonical blocks after launch.
+    pub fn with_automine(mut self, control: AutomineControl, inclusions: InclusionTracker) -> Self {
+        self.automine = control;
+        self.inclusions = inclusions;
+        self
+    }
+
+    /// Preload recently touched state before the first block is built.
+    ///
+    /// The payload builder runs [`warmup::warm_up`] once on creation and records the
+    /// result in `report`. Startup is delayed by at most `config.budget`.
+    pub fn with_warmup(mut self, config: WarmupConfig, report: WarmupReport) -> Self {
+        self.warmup = Some(config);
+        self.warmup_report = report;
+        self
+    }
+
+    /// Attach the historical state source used by [`create_fork`](Self::create_fork),
+    /// e.g. `ProviderForkSource::new(node.provider.clone())`.
+    pub fn with_fork_source(mut self, source: Arc<dyn ForkSource>) -> Self {
+        self.forks = Some(source);
+        self
+    }
+
+    /// Serve a plain HTTP health check for load balancers on `addr`.
+    ///
+    /// `GET /health` answers `200 OK` / `OK` or `503 Service Unavailable` /
+    /// `SICK: <reason>`; see [`health`]. The endpoint is started after launch with
+    /// [`health_probe`](Self::health_probe) and [`HealthProbe::serve`].
+    pub fn with_health_probe_endpoint(mut self, addr: SocketAddr) -> Self {
+        self.health_probe = Some(addr);
+        self
+    }
+
+    /// Address configured with [`with_health_probe_endpoint`](Self::with_health_probe_endpoint).
+    pub fn health_probe_endpoint(&self) -> Option<SocketAddr> {
+        self.health_probe
+    }
+
+    /// Health check sharing this node's chain spec, production switch and disk guard.
+    pub fn health_probe(&self) -> HealthProbe {
+        HealthProbe::new(
+            self.chain_spec.clone(),
+            self.production_pause.clone(),
+            self.disk_guard.clone(),
+        )
+    }
+
+    /// Record the payload builder's stats (bundles, compression) in `metrics`.
+    pub fn with_chain_metrics(mut self, metrics: Arc<ChainMetrics>) -> Self {
+        self.chain_metrics = Some(metrics);
+        self
+    }
+
+    /// Measure how well the body of every block sealed here compresses, recorded in
+    /// the chain metrics (see `payload::compression`).
+    pub fn with_compression_stats(mut self, enabled: bool) -> Self {
+        self.compression_stats = enabled;
+        self
+    }
+
+    /// Attach the metadata of `provider` to every signed block (see
+    /// `consensus::metadata`).
+    pub fn with_block_metadata_provider(mut self, provider: BlockMetadataProvider) -> Self {
+        self.block_metadata = Some(provider);
+        self
+    }
+
+    /// Commit to the block metadata (32-byte hash after the seal) instead of
+    /// embedding it; `poa_getBlockMetadata` serves it from the provider.
+    pub fn with_block_metadata_hash(mut self, enabled: bool) -> Self {
+        self.block_metadata_hash = enabled;
+        self
+    }
+
+    /// Metadata provider set with
+    /// [`with_block_metadata_provider`](Self::with_block_metadata_provider), for the
+    /// metadata RPC.
+    pub fn block_metadata_provider(&self) -> Option<&BlockMetadataProvider> {
+        self.block_metadata.as_ref()
+    }
+
+    /// Push chain metrics to the OpenTelemetry collector at `endpoint` over gRPC.
+    ///
+    /// The exporter is created here (connecting lazily) and exports every
+    /// [`EXPORT_INTERVAL`](crate::metrics::otlp::EXPORT_INTERVAL) once the node's
+    /// `ChainMetrics` are attached with [`OtlpTelemetry::export_chain_metrics`]; see
+    /// [`otlp`](crate::metrics::otlp). Must be called within a tokio runtime.
+    #[cfg(feature = "otlp-metrics")]
+    pub fn with_telemetry_endpoint(mut self, endpoint: &str) -> Result<Self, TelemetryError> {
+        self.telemetry = Some(OtlpTelemetry::new(endpoint)?);
+        Ok(self)
+    }
+
+    /// Exporter created by [`with_telemetry_endpoint`](Self::with_telemetry_endpoint).
+    #[cfg(feature = "otlp-metrics")]
+    pub fn telemetry(&self) -> Option<&OtlpTelemetry> {
+        self.telemetry.as_ref()
+    }
+
+    /// Switch shared with the payload builder that pauses block production.
+    pub fn production_pause(&self) -> &ProductionPause {
+        &self.production_pause
+    }
+
+    /// Stop block production when `token` is cancelled, finishing the block in progress
+    pub fn with_shutdown_token(mut self, token: tokio_util::sync::CancellationToken) -> Self {
+        self.shutdown = BuildShutdown::new(token);
+        self
+    }
+
+    /// Shutdown state shared with the payload builder.
+    pub fn build_shutdown(&self) -> &BuildShutdown {
+        &self.shutdown
+    }
+
+    /// Cancel the shutdown token and wait for the block being built, if any.
+    ///
+    /// The block is sealed and handed to the engine; a build still running after
+    /// [`SHUTDOWN_BUILD_TIMEOUT`] is abandoned with [`ShutdownError::BuildTimedOut`].
+    /// See [`shutdown`] for details.
+    pub async fn shutdown(&self) -> Result<(), ShutdownError> {
+        self.shutdown.shutdown(SHUTDOWN_BUILD_TIMEOUT).await
+    }
+
+    /// Read-only snapshot of the state at `block_number`, for Foundry-style forking.
+    ///
+    /// The returned [`ForkDb`] is a revm database that caches every read. See [`fork`].
+    pub async fn create_fork(&self, block_number: u64) -> Result<Arc<ForkDb>, ForkError> {
+        let source = self
+            .forks
+            .clone()
+            .ok_or(ForkError::ForkSourceUnavailable)?;
+        fork::create_fork(source, block_number).await
+    }
+
+    /// Trust headers below `checkpoint` instead of validating them during sync.
+    ///
+    /// The header at the checkpoint height must carry the trusted hash; see
+    /// [`PoaConsensus::with_checkpoint`](crate::consensus::PoaConsensus::with_checkpoint).
+    pub fn with_trusted_checkpoint(mut self, checkpoint: Option<Checkpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// Only accept blocks and pool transactions with one of these EIP-2718 types; see
+    /// [`PoaConsensus::with_allowed_tx_types`](crate::consensus::PoaConsensus::with_allowed_tx_types).
+    pub fn with_allowed_tx_types(mut self, types: Option<TxTypes>) -> Self {
+        self.allowed_tx_types = types;
+        self
+    }
+
+    /// Seed the live signer set at startup from the snapshot in `snapshot_dir` of the
+    /// head's last canonical epoch block, so a restart does not need a header replay.
+    /// The node writes one per canonical epoch block, as `<block_hash>.json`; see
+    /// [`snapshots`](crate::consensus::snapshots).
+    pub fn with_automatic_epoch_snapshot(mut self, snapshot_dir: PathBuf) -> Self {
+        self.epoch_snapshot_dir = Some(snapshot_dir);
+        self
+    }
+
+    /// Keep the `peers` enode URLs connected even when the peer limit is reached:
+    /// reth's peer manager treats them as trusted peers. See
+    /// [`peers`](crate::node::peers). Every URL must parse (see
+    /// [`validate_bootnode_url`]); the first invalid one is returned as an error.
+    pub fn with_trusted_peers(mut self, peers: Vec<String>) -> Result<Self, NodeError> {
+        self.trusted_peers = peers
+            .iter()
+            .map(|url| validate_bootnode_url(url))
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    /// Select how the node catches up with the chain; see [`sync`](crate::node::sync).
+    pub fn with_sync_mode(mut self, mode: SyncMode) -> Self {
+        self.sync_mode = mode;
+        self
+    }
+
+    /// Sync strategy configured with [`with_sync_mode`](Self::with_sync_mode).
+    pub fn sync_mode(&self) -> SyncMode {
+        self.sync_mode
+    }
+
+    /// Maintain connections to the `peers` enode URLs whatever discovery finds.
+    ///
+    /// Unlike bootnodes, which only seed discovery, static peers are redialed by
+    /// reth's peer manager whenever they drop. Every URL must parse (see
+    /// [`validate_bootnode_url`]); the first invalid one is returned as an error.
+    pub fn with_p2p_static_peers(mut self, peers: Vec<String>) -> Result<Self, NodeError> {
+        self.static_peers = peers
+            .iter()
+            .map(|url| validate_bootnode_url(url))
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    /// Static peers configured with [`with_p2p_static_peers`](Self::with_p2p_static_peers).
+    pub fn static_peers(&self) -> &[NodeRecord] {
+        &self.static_peers
+    }
+
+    /// Signer-set votes shared with the payload builder, `clique_propose` and the RPC.
+    ///
+    /// The node tallies the votes of canonical blocks into it; see
+    /// [`consensus::votes`](crate::consensus::votes).
+    pub fn voter_set(&self) -> &VoterSet {
+        &self.votes
+    }
+
+    /// Signer additions and removals that have votes but have not reached quorum.
+    ///
+    /// Votes sealed into canonical blocks since the last epoch block, tallied against
+    /// the live signer set.
+    pub async fn list_pending_votes(&self) -> Vec<PendingVote> {
+        self.chain_spec
+            .with_effective_signers(|signers| self.votes.pending(signers))
+    }
+
+    /// Collect a configuration snapshot for bug reports.
+    ///
+    /// Runtime fields (head block, peers, mempool) are left `None`; a launched node
+    /// fills them in with [`DiagnosticReport::with_stats_from`].
+    pub async fn diagnose(&self) -> DiagnosticReport {
+        use reth_chainspec::EthChainSpec;
+
+        let mut features = Vec::new();
+        if self.dev_mode {
+            features.push("dev_mode".to_string());
+        }
+        if self.zero_gas {
+            features.push("zero_gas".to_string());
+        }
+        if self.sequencer_mode {
+            features.push("sequencer_mode".to_string());
+        }
+        if self.signer_rotation != SignerRotation::default() {
+            features.push(format!("signer_rotation={}", self.signer_rotation));
+        }
+        if self.calldata_gas_per_byte < 16 {
+            features.push("calldata_discount".to_string());
+        }
+        if self.max_contract_size.is_some() {
+            features.push("max_contract_size_override".to_string());
+        }
+        if self.evm_spec.is_some() {
+            features.push("evm_spec_override".to_string());
+        }
+        if !self.infinite_fund.is_empty() {
+            features.push("infinite_fund".to_string());
+        }
+        if self.chain_spec.eip1559_disabled() {
+            features.push("eip1559_disabled".to_string());
+        }
+        if self.chain_spec.acl().is_some() {
+            features.push("sender_acl".to_string());
+        }
+
+        DiagnosticReport {
+            chain_id: self.chain_spec.chain().id(),
+            block_period: self.chain_spec.block_period(),
+            epoch: self.chain_spec.epoch(),
+            signer_addresses: self.chain_spec.effective_signers(),
+            latest_block_number: None,
+            peer_count: None,
+            mempool_size: None,
+            evm_spec_id: format!("{:?}", diagnostics::genesis_spec_id(self.chain_spec.as_ref())),
+            calldata_gas_per_byte: self.calldata_gas_per_byte,
+            max_contract_size: self.max_contract_size,
+            version: diagnostics::build_version(),
+            features,
+        }
+    }
 }
 
 // PoaNode uses the same type configuration as EthereumNode
@@ -152,13 +761,14 @@ impl NodeTypes for PoaNode {
 // The only difference from EthereumNode is the consensus builder and the engine validator.
 impl<N> Node<N> for PoaNode
 where
-    N: FullNodeTypes<Types = Self>,
+    // Storage changesets feed the payload builder's cold-start warm-up.
+    N: FullNodeTypes<Types = Self, Provider: StorageChangeSetReader>,
 {
     type ComponentsBuilder = ComponentsBuilder<
         N,
-        EthereumPoolBuilder,
+        PoaPoolBuilder,
         BasicPayloadServiceBuilder<PoaPayloadBuilderBuilder>,
-        EthereumNetworkBuilder,
+        PoaNetworkBuilder,
         PoaExecutorBuilder,
         PoaConsensusBuilder,
     >;
@@ -169,30 +779,74 @@ where
         PoaEngineValidatorBuilder,
         BasicEngineApiBuilder<PoaEngineValidatorBuilder>,
         BasicEngineValidatorBuilder<PoaEngineValidatorBuilder>,
-        Identity,
+        Stack<Stack<AutomineLayer, BackpressureLayer>, RateLimitLayer>,
     >;
 
     fn components_builder(&self) -> Self::ComponentsBuilder {
+        let mut executor = PoaExecutorBuilder::new(
+            self.max_contract_size,
+            self.calldata_gas_per_byte,
+            self.zero_gas,
+        )
+        .with_bls_precompiles(self.chain_spec.eip2537_enabled())
+        .with_coinbase_warming(self.chain_spec.coinbase_warming_block());
+        if let Some(spec) = self.evm_spec {
+            executor = executor.with_spec_override(spec);
+        }
+        let mut payload = PoaPayloadBuilderBuilder::new(
+            self.chain_spec.clone(),
+            self.signer_manager.clone(),
+            self.dev_mode,
+        )
+        .with_cache_size(self.cache_size)
+        .with_infinite_fund(self.infinite_fund.clone())
+        .with_withdrawal_queue(self.withdrawal_queue.clone())
+        .with_deposit_tracker(self.deposit_tracker.clone())
+        .with_client_tag(self.client_tag.clone())
+        .with_disk_guard(self.disk_guard.clone())
+        .with_production_pause(self.production_pause.clone())
+        .with_shutdown(self.shutdown.clone())
+        .with_warmup(self.warmup, self.warmup_report.clone())
+        .with_min_gas_price(self.min_gas_price)
+        .with_sequencer_mode(self.sequencer_mode)
+        .with_signer_rotation(self.signer_rotation)
+        .with_voter_set(self.votes.clone())
+        .with_compression_stats(self.compression_stats)
+        .with_block_metadata_hash(self.block_metadata_hash);
+        if let Some(provider) = &self.block_metadata {
+            payload = payload.with_block_metadata_provider(provider.clone());
+        }
+        if let Some(metrics) = &self.chain_metrics {
+            payload = payload.with_chain_metrics(metrics.clone());
+        }
+        if let Some(source) = &self.deposit_source {
+            payload = payload.with_deposit_source(source.clone());
+        }
         ComponentsBuilder::default()
             .node_types::<N>()
-            .pool(EthereumPoolBuilder::default())
-            .executor(PoaExecutorBuilder::new(
-                self.max_contract_size,
-                self.calldata_gas_per_byte,
-                self.zero_gas,
-            ))
-            .payload(BasicPayloadServiceBuilder::new(
-                PoaPayloadBuilderBuilder::new(
-                    self.chain_spec.clone(),
-                    self.signer_manager.clone(),
-                    self.dev_mode,
-                )
-                .with_cache_size(self.cache_size)
-                .with_infinite_fund(self.infinite_fund.clone()),
-            ))
-            .network(EthereumNetworkBuilder::default())
+            .pool(
+                PoaPoolBuilder::new(self.chain_spec.clone())
+                    .with_fee_policy(FeePolicy {
+                        min_gas_price: self.min_gas_price,
+                        sequencer_mode: self.sequencer_mode,
+                    })
+                    .with_allowed_tx_types(self.allowed_tx_types)
+                    .with_max_pending_transactions(self.max_pending_transactions),
+            )
+            .executor(executor)
+            .payload(BasicPayloadServiceBuilder::new(payload))
+            .network(
+                PoaNetworkBuilder::default()
+                    .with_trusted_peers(self.trusted_peers.clone())
+                    .with_static_peers(self.static_peers.clone()),
+            )
             .consensus(
-                PoaConsensusBuilder::new(self.chain_spec.clone()).with_dev_mode(self.dev_mode),
+                PoaConsensusBuilder::new(self.chain_spec.clone())
+                    .with_dev_mode(self.dev_mode)
+                    .with_checkpoint(self.checkpoint)
+                    .with_allowed_tx_types(self.allowed_tx_types)
+                    .with_snapshot_dir(self.epoch_snapshot_dir.clone())
+                    .with_signature_cache(self.signature_cache.clone()),
             )
     }
 
@@ -202,13 +856,24 @@ where
             PoaEngineValidatorBuilder,
             BasicEngineApiBuilder::<PoaEngineValidatorBuilder>::default(),
             BasicEngineValidatorBuilder::new(PoaEngineValidatorBuilder),
-            Identity::default(),
+            // Rate limits reject first, then back-pressure; accepted submissions then
+            // wait for automine.
+            Stack::new(
+                Stack::new(
+                    AutomineLayer::new(self.automine.clone(), self.inclusions.clone()),
+                    BackpressureLayer::new(self.backpressure.clone()),
+                ),
+                self.rpc_rate_limits.clone(),
+            ),
         ))
     }
 }
 
 // DebugNode enables launch_with_debug_capabilities(), which properly sets up dev mining.
-impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for PoaNode {
+impl<N> DebugNode<N> for PoaNode
+where
+    N: FullNodeComponents<Types = Self, Provider: StorageChangeSetReader>,
+{
     type RpcBlock = reth_ethereum::rpc::eth::primitives::Block;
 
     fn rpc_to_primitive_block(rpc_block: Self::RpcBlock) -> reth_ethereum::Block {
@@ -218,7 +883,7 @@ impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for PoaNode {
     fn local_payload_attributes_builder(
         chain_spec: &Self::ChainSpec,
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
-        LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone()))
+        PoaPayloadAttributesBuilder::new(Arc::new(chain_spec.clone()))
     }
 }
 
@@ -231,15 +896,131 @@ mod tests {
         let chain = Arc::new(PoaChainSpec::dev_chain());
         let node = PoaNode::new(chain.clone());
         assert_eq!(node.chain_spec.signers().len(), 3);
+
+        let first_dev_account = crate::signer::dev::first_dev_signer().address();
+        let signers = node.chain_spec.signers();
+        assert_eq!(
+            crate::consensus::PoaConsensus::expected_signer(0, signers),
+            first_dev_account
+        );
     }
 
     #[test]
     fn test_poa_node_with_dev_mode() {
         let chain = Arc::new(PoaChainSpec::dev_chain());
-        let node = PoaNode::new(chain).with_dev_mode(true);
+        let node = PoaNode::new(chain).with_dev_mode(true).unwrap();
         assert!(node.dev_mode);
     }
 
+    #[test]
+    fn test_poa_node_dev_mode_refused_on_production() {
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_production(true));
+        let err = PoaNode::new(chain.clone()).with_dev_mode(true).unwrap_err();
+        assert_eq!(err, NodeError::DevModeOnProduction { chain_id: 9323310 });
+        assert!(!PoaNode::new(chain).with_dev_mode(false).unwrap().dev_mode);
+    }
+
+    #[test]
+    fn test_poa_node_evm_spec_only_in_dev_mode() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let err = PoaNode::new(chain.clone())
+            .with_evm_spec(Some(SpecId::SHANGHAI))
+            .unwrap_err();
+        assert!(matches!(err, NodeError::EvmSpecOutsideDevMode { .. }));
+        assert!(PoaNode::new(chain.clone()).with_evm_spec(None).is_ok());
+
+        let node = PoaNode::new(chain)
+            .with_dev_mode(true)
+            .unwrap()
+            .with_evm_spec(Some(SpecId::SHANGHAI))
+            .unwrap();
+        assert_eq!(node.evm_spec, Some(SpecId::SHANGHAI));
+    }
+
+    #[test]
+    fn test_poa_node_static_peers_reject_invalid_url() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let valid = concat!(
+            "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec0129",
+            "37307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303"
+        );
+        let node = PoaNode::new(chain.clone())
+            .with_p2p_static_peers(vec![valid.to_string()])
+            .unwrap();
+        assert_eq!(node.static_peers().len(), 1);
+
+        let err = PoaNode::new(chain)
+            .with_p2p_static_peers(vec![valid.to_string(), "10.0.0.1:30303".to_string()])
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidPeerUrl(url) if url.contains("10.0.0.1:30303")));
+    }
+
+    #[test]
+    fn test_poa_node_rate_limited_rpc() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        assert!(PoaNode::new(chain.clone()).rpc_rate_limits.buckets().is_empty());
+
+        let node = PoaNode::new(chain)
+            .with_rate_limited_rpc(vec![MethodRateLimit::new("eth_getLogs", 1)]);
+        let buckets = node.rpc_rate_limits.buckets();
+        assert!(buckets.try_acquire(None, "eth_getLogs").is_ok());
+        assert!(buckets.try_acquire(None, "eth_getLogs").is_err());
+        assert!(buckets.try_acquire(None, "eth_call").is_ok());
+    }
+
+    #[test]
+    fn test_poa_node_trusted_peers() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let url = concat!(
+            "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec0129",
+            "37307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.0.0.2:30303"
+        );
+        let validator = validate_bootnode_url(url).unwrap();
+
+        let node = PoaNode::new(chain.clone())
+            .with_trusted_peers(vec![url.to_string()])
+            .unwrap();
+        assert_eq!(node.sync_mode(), SyncMode::Full);
+        assert_eq!(node.trusted_peers, [validator]);
+        let node = node.with_sync_mode(SyncMode::LightHeaders);
+        assert_eq!(node.sync_mode(), SyncMode::LightHeaders);
+
+        let err = PoaNode::new(chain)
+            .with_trusted_peers(vec!["10.0.0.2:30303".to_string()])
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidPeerUrl(_)));
+    }
+
+    #[cfg(feature = "otlp-metrics")]
+    #[tokio::test]
+    async fn test_poa_node_telemetry_endpoint() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        assert!(PoaNode::new(chain.clone()).telemetry().is_none());
+
+        let node = PoaNode::new(chain.clone())
+            .with_telemetry_endpoint("http://127.0.0.1:4317")
+            .unwrap();
+        assert_eq!(node.telemetry().unwrap().endpoint(), "http://127.0.0.1:4317");
+
+        let err = PoaNode::new(chain).with_telemetry_endpoint("collector:4317").unwrap_err();
+        assert_eq!(err, TelemetryError::InvalidEndpoint("collector:4317".to_string()));
+    }
+
+    #[test]
+    fn test_poa_node_max_pending_transactions() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        assert!(PoaNode::new(chain.clone())
+            .max_pending_transactions()
+            .is_none());
+        assert!(PoaNode::new(chain.clone())
+            .with_max_pending_transactions(0)
+            .max_pending_transactions()
+            .is_none());
+
+        let node = PoaNode::new(chain).with_max_pending_transactions(50_000);
+        assert_eq!(node.max_pending_transactions(), Some(50_000));
+    }
+
     #[test]
     fn test_poa_node_with_signer_manager() {
         let chain = Arc::new(PoaChainSpec::dev_chain());
@@ -255,12 +1036,154 @@ mod tests {
         let manager = Arc::new(SignerManager::new());
         let node = PoaNode::new(chain)
             .with_dev_mode(true)
+            .unwrap()
             .with_signer_manager(manager.clone());
         assert!(node.dev_mode);
         assert!(Arc::ptr_eq(&node.signer_manager, &manager));
         assert_eq!(node.chain_spec.signers().len(), 3);
     }
 
+    #[test]
+    fn test_with_custom_chain_id_reaches_evm_tx_validation() {
+        use crate::evm::PoaEvmFactory;
+        use alloy_evm::revm::context::result::{EVMError, InvalidTransaction};
+        use alloy_evm::revm::context::TxEnv;
+        use alloy_evm::revm::database_interface::EmptyDB;
+        use alloy_evm::{Evm, EvmFactory};
+        use reth_chainspec::EthChainSpec;
+        use reth_ethereum::node::EthEvmConfig;
+        use reth_evm::ConfigureEvm;
+
+        let dev = PoaChainSpec::dev_chain();
+        let genesis_hash = dev.genesis_hash();
+        let node = PoaNode::new(Arc::new(dev)).with_custom_chain_id(12345);
+        assert_eq!(node.chain_spec.chain().id(), 12345);
+        assert_eq!(node.chain_spec.genesis_hash(), genesis_hash);
+        assert_eq!(node.chain_spec.signers().len(), 3);
+
+        let evm_config = EthEvmConfig::new_with_evm_factory(
+            node.chain_spec.inner().clone(),
+            PoaEvmFactory::default(),
+        );
+        let env = evm_config.evm_env(node.chain_spec.genesis_header()).unwrap();
+        assert_eq!(env.cfg_env.chain_id, 12345);
+
+        let mut evm = PoaEvmFactory::default().create_evm(EmptyDB::default(), env);
+        let foreign = TxEnv {
+            chain_id: Some(1),
+            ..Default::default()
+        };
+        assert!(matches!(
+            evm.transact(foreign),
+            Err(EVMError::Transaction(InvalidTransaction::InvalidChainId))
+        ));
+        let local = TxEnv {
+            chain_id: Some(12345),
+            ..Default::default()
+        };
+        assert!(!matches!(
+            evm.transact(local),
+            Err(EVMError::Transaction(InvalidTransaction::InvalidChainId))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "reserved")]
+    fn test_with_custom_chain_id_rejects_reserved() {
+        let _ = PoaNode::new(Arc::new(PoaChainSpec::dev_chain())).with_custom_chain_id(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "EIP-155")]
+    fn test_with_custom_chain_id_rejects_oversized() {
+        let _ = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()))
+            .with_custom_chain_id(MAX_EIP155_CHAIN_ID + 1);
+    }
+
+    #[tokio::test]
+    async fn test_poa_node_fork_requires_source() {
+        let node = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()));
+        assert!(matches!(
+            node.create_fork(0).await,
+            Err(ForkError::ForkSourceUnavailable)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_poa_node_lists_pending_votes() {
+        use crate::consensus::votes::encode_vote;
+        use crate::consensus::VoteType;
+
+        // Five signers: quorum is three.
+        let genesis = crate::genesis::create_dev_genesis();
+        let signers: Vec<_> = crate::genesis::dev_accounts().into_iter().take(5).collect();
+        let poa_config = crate::chainspec::PoaConfig {
+            period: 1,
+            epoch: 30000,
+            signers: signers.clone(),
+        };
+        let chain = PoaChainSpec::new(genesis, poa_config);
+        let node = PoaNode::new(Arc::new(chain));
+        let candidate = alloy_primitives::Address::with_last_byte(0x42);
+        // Two canonical blocks, sealed by different signers, vote for the candidate.
+        for (number, voter) in (1..).zip(&signers[..2]) {
+            let header = alloy_consensus::Header {
+                number,
+                mix_hash: encode_vote(candidate, VoteType::Add),
+                ..Default::default()
+            };
+            node.voter_set().apply_header(30000, &header, *voter, &signers);
+        }
+
+        let pending = node.list_pending_votes().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].candidate, candidate);
+        assert_eq!(pending[0].action, VoteType::Add);
+        assert_eq!(pending[0].votes_for.len(), 2);
+        assert_eq!(pending[0].quorum, 3);
+    }
+
+    #[tokio::test]
+    async fn test_poa_node_shutdown_completes_in_flight_block() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let token = tokio_util::sync::CancellationToken::new();
+        let node = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()))
+            .with_shutdown_token(token.clone());
+
+        // Mock builder: registers a build like the payload builder, then seals slowly.
+        let sealed = Arc::new(AtomicBool::new(false));
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let builder = {
+            let shutdown = node.build_shutdown().clone();
+            let sealed = sealed.clone();
+            tokio::task::spawn_blocking(move || {
+                let _build = shutdown.begin_build().unwrap();
+                started_tx.send(()).unwrap();
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                sealed.store(true, Ordering::SeqCst);
+            })
+        };
+        started_rx.await.unwrap();
+
+        node.shutdown().await.unwrap();
+        assert!(sealed.load(Ordering::SeqCst));
+        assert!(token.is_cancelled());
+        assert_eq!(
+            node.build_shutdown().begin_build().unwrap_err(),
+            ShutdownError::ShuttingDown
+        );
+        builder.await.unwrap();
+    }
+
+    #[test]
+    fn test_poa_node_clones_share_production_pause() {
+        let node = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()));
+        let launched = node.clone();
+        node.production_pause().pause();
+        assert!(launched.production_pause().is_paused());
+    }
+
     #[test]
     fn test_poa_consensus_builder_creation() {
         let chain = Arc::new(PoaChainSpec::dev_chain());
@@ -0,0 +1,102 @@
+//! Trusted peers that bypass connection limits.
+//!
+//! Multi-datacenter deployments need some peers (fellow validators, monitoring
+//! nodes) connected at all times, even when the peer limit is reached. Their enode
+//! URLs are configured with
+//! [`PoaNode::with_trusted_peers`](super::PoaNode::with_trusted_peers) (`--trusted-peer`)
+//! and handed to reth's peer manager as [`PeerKind::Trusted`] once the network is up:
+//! reth admits them past its inbound limits, never bans them for reputation and
+//! redials them when they drop. With `--trusted-only` reth's `trusted_nodes_only`
+//! additionally refuses every other peer.
+//!
+//! Static peers ([`PoaNode::with_p2p_static_peers`](super::PoaNode::with_p2p_static_peers),
+//! `--static-peer`) are enode URLs added to reth's peer set as [`PeerKind::Static`]
+//! once the network is up: reth keeps dialing them whatever discovery finds, unlike
+//! bootnodes, which only seed discovery.
+
+use reth_ethereum::network::api::{PeerKind, Peers};
+use reth_ethereum::node::api::FullNodeTypes;
+use reth_ethereum::node::builder::{components::NetworkBuilder, BuilderContext};
+use reth_ethereum::node::EthereumNetworkBuilder;
+use super::NodeError;
+use reth_network_peers::NodeRecord;
+use reth_tracing::tracing::info;
+use reth_transaction_pool::TransactionPool;
+
+/// Parse an `enode://<id>@<ip>:<port>` URL, as given for bootnodes and static peers.
+pub fn validate_bootnode_url(url: &str) -> Result<NodeRecord, NodeError> {
+    url.trim()
+        .parse::<NodeRecord>()
+        .map_err(|err| NodeError::InvalidPeerUrl(format!("{url}: {err}")))
+}
+
+/// Ethereum network with trusted peers that bypass the peer limit and
+/// always-dialed static peers.
+#[derive(Debug, Clone, Default)]
+pub struct PoaNetworkBuilder {
+    inner: EthereumNetworkBuilder,
+    trusted_peers: Vec<NodeRecord>,
+    static_peers: Vec<NodeRecord>,
+}
+
+impl PoaNetworkBuilder {
+    /// Keep `peers` connected at all times, whatever the peer limit.
+    pub fn with_trusted_peers(mut self, peers: Vec<NodeRecord>) -> Self {
+        self.trusted_peers = peers;
+        self
+    }
+
+    /// Maintain connections to `peers` regardless of discovery.
+    pub fn with_static_peers(mut self, peers: Vec<NodeRecord>) -> Self {
+        self.static_peers = peers;
+        self
+    }
+}
+
+impl<Node, Pool> NetworkBuilder<Node, Pool> for PoaNetworkBuilder
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + Unpin + 'static,
+    EthereumNetworkBuilder: NetworkBuilder<Node, Pool>,
+{
+    type Network = <EthereumNetworkBuilder as NetworkBuilder<Node, Pool>>::Network;
+
+    async fn build_network(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<Self::Network> {
+        let network = self.inner.build_network(ctx, pool).await?;
+        for peer in &self.trusted_peers {
+            let (tcp, udp) = (peer.tcp_addr(), peer.udp_addr());
+            info!(target: "poa::network", peer_id = %peer.id, %tcp, "Trusting peer");
+            network.add_peer_kind(peer.id, PeerKind::Trusted, tcp, Some(udp));
+        }
+        for peer in &self.static_peers {
+            let (tcp, udp) = (peer.tcp_addr(), peer.udp_addr());
+            info!(target: "poa::network", peer_id = %peer.id, %tcp, "Maintaining static peer");
+            network.add_peer_kind(peer.id, PeerKind::Static, tcp, Some(udp));
+        }
+        Ok(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn test_validate_bootnode_url() {
+        let url = concat!(
+            "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec0129",
+            "37307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303"
+        );
+        let record = validate_bootnode_url(url).unwrap();
+        assert_eq!(record.tcp_addr(), SocketAddr::from(([10, 3, 58, 6], 30303)));
+        assert!(matches!(
+            validate_bootnode_url("enode://not-a-key@10.0.0.1:30303"),
+            Err(NodeError::InvalidPeerUrl(_))
+        ));
+    }
+}
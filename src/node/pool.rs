@@ -0,0 +1,396 @@
+//! Transaction pool admission rules.
+//!
+//! [`PoaPoolBuilder`] builds reth's Ethereum transaction pool with its validator
+//! wrapped in a [`PoaTransactionValidator`]. Transactions that no block of this chain
+//! may include are refused on entry, before the Ethereum checks run, so they are
+//! never gossiped, never counted against pool limits and never offered to the
+//! payload builder. RPC submissions, gossip and the transactions reloaded from disk
+//! on restart all pass through the same validator.
+//!
+//! The rules mirror what [`PoaConsensus`](crate::consensus::PoaConsensus) rejects in
+//! blocks, plus the `--min-gas-price` floor of the payload builder; see
+//! [`check_admission`]. Rules with an activation block apply from the block after the
+//! pool's head.
+//!
+//! The pending sub-pool can be capped below reth's default
+//! ([`PoaPoolBuilder::with_max_pending_transactions`]); reth's pool then discards
+//! the surplus itself, lowest priority first, whenever it grows past the cap.
+
+use crate::chainspec::PoaChainSpec;
+use crate::consensus::TxTypes;
+use crate::payload::FeePolicy;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::Address;
+use reth_ethereum::node::api::FullNodeTypes;
+use reth_ethereum::node::builder::components::{create_blob_store, PoolBuilder, TxPoolBuilder};
+use reth_ethereum::node::builder::BuilderContext;
+use reth_ethereum::primitives::SealedBlock;
+use reth_primitives_traits::transaction::error::InvalidTransactionError;
+use reth_tracing::tracing::debug;
+use reth_transaction_pool::blobstore::DiskFileBlobStore;
+use reth_transaction_pool::error::{InvalidPoolTransactionError, PoolTransactionError};
+use reth_transaction_pool::{
+    CoinbaseTipOrdering, EthPooledTransaction, EthTransactionValidator, Pool, PoolTransaction,
+    TransactionOrigin, TransactionValidationOutcome, TransactionValidationTaskExecutor,
+    TransactionValidator,
+};
+use std::any::Any;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::PoaNode;
+
+/// Admission refusal without a reth counterpart.
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionError {
+    /// The access control list in force refuses the sender
+    #[error("sender {0} refused by the access control list")]
+    SenderRefused(Address),
+}
+
+impl PoolTransactionError for AdmissionError {
+    fn is_bad_transaction(&self) -> bool {
+        // A list change, not the peer, decides this.
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Refuse `tx` if block `block_number` of this chain could not include it.
+///
+/// * with EIP-1559 disabled, dynamic-fee transactions (see
+///   [`PoaConsensus`](crate::consensus::PoaConsensus)`::validate_legacy_fee_transactions`);
+/// * transactions whose type is not in `allowed_tx_types` (see
+///   `PoaConsensus::with_allowed_tx_types`); without it reth's validator already
+///   refuses the types of inactive forks;
+/// * transactions from senders refused by the access control list in force at
+///   `block_number` (see `PoaConsensus::validate_sender_access`);
+/// * transactions whose maximum fee per gas is below the floor of `fee_policy`, which
+///   the payload builder would never select.
+pub fn check_admission<T: PoolTransaction>(
+    chain_spec: &PoaChainSpec,
+    fee_policy: &FeePolicy,
+    allowed_tx_types: Option<TxTypes>,
+    block_number: u64,
+    tx: &T,
+) -> Result<(), InvalidPoolTransactionError> {
+    if chain_spec.eip1559_disabled() && tx.is_dynamic_fee() {
+        return Err(InvalidTransactionError::Eip1559Disabled.into());
+    }
+    if allowed_tx_types.is_some_and(|types| !types.contains(tx.ty())) {
+        return Err(InvalidTransactionError::TxTypeNotSupported.into());
+    }
+    if let Some(acl) = chain_spec.acl_at(block_number) {
+        let sender = tx.sender();
+        if !acl.allows(&sender) {
+            let err = AdmissionError::SenderRefused(sender);
+            return Err(InvalidPoolTransactionError::Other(Box::new(err)));
+        }
+    }
+    if !fee_policy.admits(tx.max_fee_per_gas()) {
+        return Err(InvalidPoolTransactionError::Underpriced);
+    }
+    Ok(())
+}
+
+/// Transaction validator applying [`check_admission`] before the wrapped validator.
+#[derive(Debug, Clone)]
+pub struct PoaTransactionValidator<V> {
+    inner: V,
+    chain_spec: Arc<PoaChainSpec>,
+    fee_policy: FeePolicy,
+    /// Transaction types admitted; None = those reth's validator accepts.
+    allowed_tx_types: Option<TxTypes>,
+    /// Number of the pool's head block, advanced by `on_new_head_block`.
+    head_number: Arc<AtomicU64>,
+}
+
+impl<V> PoaTransactionValidator<V> {
+    /// Wrap `inner` with the admission rules of `chain_spec`.
+    pub fn new(inner: V, chain_spec: Arc<PoaChainSpec>) -> Self {
+        Self {
+            inner,
+            chain_spec,
+            fee_policy: FeePolicy::default(),
+            allowed_tx_types: None,
+            head_number: Arc::default(),
+        }
+    }
+
+    /// Start from head block `number` (the node's head at startup).
+    pub fn with_head_number(self, number: u64) -> Self {
+        self.head_number.store(number, Ordering::Relaxed);
+        self
+    }
+
+    /// Refuse transactions that can never pay the fee floor of `fee_policy`.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.fee_policy = fee_policy;
+        self
+    }
+
+    /// Refuse transactions whose EIP-2718 type is not in `types`.
+    pub fn with_allowed_tx_types(mut self, types: Option<TxTypes>) -> Self {
+        self.allowed_tx_types = types;
+        self
+    }
+
+    /// The wrapped validator.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+}
+
+impl<V> TransactionValidator for PoaTransactionValidator<V>
+where
+    V: TransactionValidator,
+{
+    type Transaction = V::Transaction;
+    type Block = V::Block;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        let block_number = self.head_number.load(Ordering::Relaxed) + 1;
+        let admission = check_admission(
+            &self.chain_spec,
+            &self.fee_policy,
+            self.allowed_tx_types,
+            block_number,
+            &transaction,
+        );
+        if let Err(err) = admission {
+            debug!(
+                target: "poa::txpool",
+                hash = %transaction.hash(),
+                ?origin,
+                %err,
+                "Refused transaction at admission"
+            );
+            return TransactionValidationOutcome::Invalid(transaction, err);
+        }
+        self.inner.validate_transaction(origin, transaction).await
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock<Self::Block>) {
+        self.head_number
+            .store(new_tip_block.header().number(), Ordering::Relaxed);
+        self.inner.on_new_head_block(new_tip_block)
+    }
+}
+
+/// Validator of the pool built by [`PoaPoolBuilder`].
+pub type PoaPoolValidator<Provider> = TransactionValidationTaskExecutor<
+    PoaTransactionValidator<EthTransactionValidator<Provider, EthPooledTransaction>>,
+>;
+
+/// Transaction pool built by [`PoaPoolBuilder`].
+pub type PoaTransactionPool<Provider> =
+    Pool<PoaPoolValidator<Provider>, CoinbaseTipOrdering<EthPooledTransaction>, DiskFileBlobStore>;
+
+/// Builds the Ethereum transaction pool with [`PoaTransactionValidator`] admission.
+#[derive(Debug, Clone)]
+pub struct PoaPoolBuilder {
+    chain_spec: Arc<PoaChainSpec>,
+    fee_policy: FeePolicy,
+    /// Transaction types admitted; None = those of the active forks.
+    allowed_tx_types: Option<TxTypes>,
+    /// Pending sub-pool transaction limit; None = the node's `--txpool` settings.
+    max_pending: Option<usize>,
+}
+
+impl PoaPoolBuilder {
+    /// Pool builder enforcing the admission rules of `chain_spec`.
+    pub fn new(chain_spec: Arc<PoaChainSpec>) -> Self {
+        Self {
+            chain_spec,
+            fee_policy: FeePolicy::default(),
+            allowed_tx_types: None,
+            max_pending: None,
+        }
+    }
+
+    /// Apply the payload builder's fee floor at admission too.
+    pub fn with_fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+        self.fee_policy = fee_policy;
+        self
+    }
+
+    /// Refuse the transaction types consensus would reject in blocks.
+    pub fn with_allowed_tx_types(mut self, types: Option<TxTypes>) -> Self {
+        self.allowed_tx_types = types;
+        self
+    }
+
+    /// Keep at most `max` transactions in the pending sub-pool (reth's
+    /// `pending_max_count`); None keeps the node's configured limit.
+    pub fn with_max_pending_transactions(mut self, max: Option<usize>) -> Self {
+        self.max_pending = max;
+        self
+    }
+}
+
+impl<Node> PoolBuilder<Node> for PoaPoolBuilder
+where
+    Node: FullNodeTypes<Types = PoaNode>,
+{
+    type Pool = PoaTransactionPool<Node::Provider>;
+
+    async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
+        let mut pool_config = ctx.pool_config();
+        if let Some(max) = self.max_pending {
+            pool_config.pending_limit.max_txs = max;
+        }
+        let blob_store = create_blob_store(ctx)?;
+        let txpool = &ctx.config().txpool;
+        let (chain_spec, fee_policy) = (self.chain_spec, self.fee_policy);
+        let allowed_tx_types = self.allowed_tx_types;
+        let head_number = ctx.head().number;
+        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
+            .with_head_timestamp(ctx.head().timestamp)
+            .with_max_tx_input_bytes(txpool.max_tx_input_bytes)
+            .kzg_settings(ctx.kzg_settings()?)
+            .with_local_transactions_config(pool_config.local_transactions_config.clone())
+            .set_tx_fee_cap(ctx.config().rpc.rpc_tx_fee_cap)
+            .with_max_tx_gas_limit(txpool.max_tx_gas_limit)
+            .with_minimum_priority_fee(txpool.minimum_priority_fee)
+            .with_additional_tasks(txpool.additional_validation_tasks)
+            .build_with_tasks(ctx.task_executor().clone(), blob_store.clone())
+            .map(|validator| {
+                PoaTransactionValidator::new(validator, chain_spec.clone())
+                    .with_fee_policy(fee_policy)
+                    .with_allowed_tx_types(allowed_tx_types)
+                    .with_head_number(head_number)
+            });
+
+        TxPoolBuilder::new(ctx)
+            .with_validator(validator)
+            .build_and_spawn_maintenance_task(blob_store, pool_config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxEip1559, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_ethereum::TransactionSigned;
+    use reth_primitives_traits::SignerRecoverable;
+
+    fn pooled<T>(tx: T) -> EthPooledTransaction
+    where
+        T: SignableTransaction<alloy_primitives::Signature>,
+        TransactionSigned: From<alloy_consensus::Signed<T>>,
+    {
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[0].parse().unwrap();
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        let recovered = TransactionSigned::from(tx.into_signed(signature))
+            .try_into_recovered()
+            .unwrap();
+        EthPooledTransaction::try_from_consensus(recovered).unwrap()
+    }
+
+    fn legacy() -> EthPooledTransaction {
+        pooled(TxLegacy {
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::with_last_byte(0xbe)),
+            value: U256::from(1),
+            ..Default::default()
+        })
+    }
+
+    fn dynamic_fee() -> EthPooledTransaction {
+        pooled(TxEip1559 {
+            chain_id: 9323310,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::with_last_byte(0xbe)),
+            value: U256::from(1),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_admission_refuses_dynamic_fee_without_eip1559() {
+        let chain = PoaChainSpec::dev_chain().with_eip1559_disabled();
+        let fees = FeePolicy::default();
+        assert!(check_admission(&chain, &fees, None, 1, &legacy()).is_ok());
+        assert!(matches!(
+            check_admission(&chain, &fees, None, 1, &dynamic_fee()),
+            Err(InvalidPoolTransactionError::Consensus(
+                InvalidTransactionError::Eip1559Disabled
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_admission_accepts_dynamic_fee_with_eip1559() {
+        let chain = PoaChainSpec::dev_chain();
+        let fees = FeePolicy::default();
+        assert!(check_admission(&chain, &fees, None, 1, &legacy()).is_ok());
+        assert!(check_admission(&chain, &fees, None, 1, &dynamic_fee()).is_ok());
+    }
+
+    #[test]
+    fn test_admission_refuses_disallowed_tx_types() {
+        let chain = PoaChainSpec::dev_chain();
+        let fees = FeePolicy::default();
+        let legacy_only = Some(TxTypes::from_iter([0]));
+        assert!(check_admission(&chain, &fees, legacy_only, 1, &legacy()).is_ok());
+        assert!(matches!(
+            check_admission(&chain, &fees, legacy_only, 1, &dynamic_fee()),
+            Err(InvalidPoolTransactionError::Consensus(
+                InvalidTransactionError::TxTypeNotSupported
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_admission_applies_fee_floor_outside_sequencer_mode() {
+        let chain = PoaChainSpec::dev_chain();
+        // legacy() pays 1 gwei, dynamic_fee() up to 2 gwei.
+        let floor = FeePolicy {
+            min_gas_price: 2_000_000_000,
+            sequencer_mode: false,
+        };
+        assert!(matches!(
+            check_admission(&chain, &floor, None, 1, &legacy()),
+            Err(InvalidPoolTransactionError::Underpriced)
+        ));
+        assert!(check_admission(&chain, &floor, None, 1, &dynamic_fee()).is_ok());
+
+        let sequencer = FeePolicy {
+            sequencer_mode: true,
+            ..floor
+        };
+        assert!(check_admission(&chain, &sequencer, None, 1, &legacy()).is_ok());
+    }
+
+    #[test]
+    fn test_admission_applies_acl_from_activation_block() {
+        let sender = crate::genesis::dev_accounts()[0];
+        let acl = crate::chainspec::AccessControlList::blacklist([sender]);
+        let chain = PoaChainSpec::dev_chain().with_acl(acl, 10);
+        let fees = FeePolicy::default();
+        assert!(check_admission(&chain, &fees, None, 9, &legacy()).is_ok());
+        let err = check_admission(&chain, &fees, None, 10, &legacy()).unwrap_err();
+        let InvalidPoolTransactionError::Other(err) = err else {
+            panic!("expected an admission error, got {err:?}");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref(),
+            Some(AdmissionError::SenderRefused(refused)) if *refused == sender
+        ));
+        assert!(!err.is_bad_transaction());
+    }
+}
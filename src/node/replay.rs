@@ -0,0 +1,446 @@
+//! Debug replay of a stored block.
+//!
+//! `debug_replayBlock` (see [`rpc::replay`](crate::rpc::replay)) re-executes block `N`
+//! on the state left by block `N - 1`, optionally with per-account overrides ("what if
+//! this address had no balance?"), to investigate exploits and failed transactions:
+//!
+//! 1. the block and its recovered senders are read from storage,
+//! 2. the overrides are written into a [`CacheDB`] layered over the parent state,
+//! 3. the block runs through the node's block executor (system calls, withdrawals
+//!    and fee accounting included),
+//! 4. receipts, gas used and per-account changes are returned.
+//!
+//! Nothing is written back: the database and the canonical chain are untouched.
+//! [`ReplayResult::state_changes`] is relative to the overridden parent state.
+
+use crate::statediff::{AccountDiff, StateDiffBuilder};
+use alloy_evm::revm::bytecode::Bytecode;
+use alloy_evm::revm::database::CacheDB;
+use alloy_evm::revm::database_interface::DatabaseRef;
+use alloy_primitives::{keccak256, Address, Bytes, U256};
+use reth_ethereum::evm::revm::database::StateProviderDatabase;
+use reth_ethereum::{Block, EthPrimitives, Receipt};
+use reth_evm::execute::Executor;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::RecoveredBlock;
+use reth_storage_api::{BlockReader, StateProviderFactory, TransactionVariant};
+use reth_tracing::tracing::info;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Block replay failures.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// The block is not in the canonical chain
+    #[error("Block {0} not found in the canonical chain")]
+    BlockNotFound(u64),
+
+    /// The genesis block has no parent state to replay on
+    #[error("The genesis block cannot be replayed")]
+    GenesisBlock,
+
+    /// Executing the block failed, e.g. a transaction became invalid under the overrides
+    #[error("Block execution failed during replay: {0}")]
+    EvmError(String),
+
+    /// Underlying storage read failed
+    #[error("Storage error during replay: {0}")]
+    Storage(String),
+}
+
+/// Replacement state for one account during a replay.
+///
+/// Unset fields keep the parent block's value. Deserializes from the state override
+/// object of `eth_call` (`balance`, `nonce`, `code`, `state`, `stateDiff`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AccountOverride {
+    /// Balance to use instead of the stored one
+    pub balance: Option<U256>,
+    /// Nonce to use instead of the stored one
+    #[serde(with = "alloy_serde::quantity::opt")]
+    pub nonce: Option<u64>,
+    /// Runtime bytecode to install at the address
+    pub code: Option<Bytes>,
+    /// Full storage replacement; slots not listed read as zero
+    pub state: Option<HashMap<U256, U256>>,
+    /// Individual slots written on top of the (possibly replaced) storage
+    pub state_diff: HashMap<U256, U256>,
+}
+
+/// Outcome of a replayed block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayResult {
+    /// One receipt per transaction, in block order
+    pub receipts: Vec<Receipt>,
+    /// Total gas used by the block
+    pub gas_used: u64,
+    /// Accounts changed by the block, relative to the overridden parent state
+    pub state_changes: HashMap<Address, AccountDiff>,
+}
+
+/// Re-executes stored blocks.
+pub trait BlockReplayer: Send + Sync + Debug {
+    /// Replay block `block_number` on its parent state with `overrides` applied.
+    fn replay(
+        &self,
+        block_number: u64,
+        overrides: &HashMap<Address, AccountOverride>,
+    ) -> Result<ReplayResult, ReplayError>;
+}
+
+/// [`BlockReplayer`] backed by a reth provider and the node's EVM configuration.
+#[derive(Debug, Clone)]
+pub struct ProviderBlockReplayer<P, E> {
+    provider: P,
+    evm_config: E,
+}
+
+impl<P, E> ProviderBlockReplayer<P, E> {
+    /// Replay blocks from `provider` with `evm_config`.
+    pub fn new(provider: P, evm_config: E) -> Self {
+        Self {
+            provider,
+            evm_config,
+        }
+    }
+}
+
+impl<P, E> BlockReplayer for ProviderBlockReplayer<P, E>
+where
+    P: BlockReader<Block = Block> + StateProviderFactory + Send + Sync + Debug,
+    E: ConfigureEvm<Primitives = EthPrimitives> + Debug,
+{
+    fn replay(
+        &self,
+        block_number: u64,
+        overrides: &HashMap<Address, AccountOverride>,
+    ) -> Result<ReplayResult, ReplayError> {
+        if block_number == 0 {
+            return Err(ReplayError::GenesisBlock);
+        }
+        let block = self
+            .provider
+            .recovered_block(block_number.into(), TransactionVariant::WithHash)
+            .map_err(|e| ReplayError::Storage(e.to_string()))?
+            .ok_or(ReplayError::BlockNotFound(block_number))?;
+        let parent_state = self
+            .provider
+            .history_by_block_number(block_number - 1)
+            .map_err(|e| ReplayError::Storage(e.to_string()))?;
+        replay_on(
+            &self.evm_config,
+            StateProviderDatabase::new(parent_state),
+            &block,
+            overrides,
+        )
+    }
+}
+
+/// Write `overrides` into `db` ahead of execution.
+pub fn apply_overrides<DB: DatabaseRef>(
+    db: &mut CacheDB<DB>,
+    overrides: &HashMap<Address, AccountOverride>,
+) -> Result<(), ReplayError> {
+    for (&address, account) in overrides {
+        let mut info = db
+            .load_account(address)
+            .map_err(|e| ReplayError::Storage(e.to_string()))?
+            .info
+            .clone();
+        if let Some(balance) = account.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = &account.code {
+            info.code_hash = keccak256(code);
+            info.code = Some(Bytecode::new_raw(code.clone()));
+        }
+        db.insert_account_info(address, info);
+
+        if let Some(state) = &account.state {
+            db.replace_account_storage(address, state.iter().map(|(k, v)| (*k, *v)).collect())
+                .map_err(|e| ReplayError::Storage(e.to_string()))?;
+        }
+        for (&slot, &value) in &account.state_diff {
+            db.insert_account_storage(address, slot, value)
+                .map_err(|e| ReplayError::Storage(e.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Execute `block` on `parent_state` with `overrides` applied, without persisting.
+pub fn replay_on<E, DB>(
+    evm_config: &E,
+    parent_state: DB,
+    block: &RecoveredBlock<Block>,
+    overrides: &HashMap<Address, AccountOverride>,
+) -> Result<ReplayResult, ReplayError>
+where
+    E: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef,
+{
+    let mut db = CacheDB::new(parent_state);
+    apply_overrides(&mut db, overrides)?;
+
+    let mut executor = evm_config.executor(db);
+    let output = executor
+        .execute_one(block)
+        .map_err(|e| ReplayError::EvmError(e.to_string()))?;
+    let bundle = executor.into_state().take_bundle();
+
+    let mut diff = StateDiffBuilder::new(block.header().number, block.hash());
+    for (address, account) in bundle.state() {
+        diff.record_bundle_account(*address, account);
+    }
+    Ok(ReplayResult {
+        receipts: output.receipts,
+        gas_used: output.gas_used,
+        state_changes: diff.build().changes,
+    })
+}
+
+/// Replay `block_number` through `replayer` on a blocking thread.
+///
+/// See the [module docs](self) for what is executed and returned.
+pub async fn replay_block(
+    replayer: Arc<dyn BlockReplayer>,
+    block_number: u64,
+    overrides: HashMap<Address, AccountOverride>,
+) -> Result<ReplayResult, ReplayError> {
+    let overridden = overrides.len();
+    let result = tokio::task::spawn_blocking(move || replayer.replay(block_number, &overrides))
+        .await
+        .map_err(|e| ReplayError::EvmError(e.to_string()))??;
+    info!(
+        target: "poa::replay",
+        block_number,
+        overridden,
+        txs = result.receipts.len(),
+        gas_used = result.gas_used,
+        accounts_changed = result.state_changes.len(),
+        "Block replayed"
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::PoaChainSpec;
+    use crate::evm::PoaEvmFactory;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{BlockBody, Header, SignableTransaction, TxLegacy, TxType};
+    use alloy_evm::revm::database::InMemoryDB;
+    use alloy_primitives::{address, TxKind, B256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::EthChainSpec;
+    use reth_ethereum::node::EthEvmConfig;
+    use reth_ethereum::TransactionSigned;
+
+    const GAS_PRICE: u128 = 10_000_000_000;
+    const VALUE: u128 = 1_000_000_000_000_000_000;
+
+    /// Prefunded dev account that is not a signer, so it earns no fees in block 1.
+    fn recipient() -> Address {
+        crate::genesis::dev_accounts()[5]
+    }
+
+    fn evm_config(chain: &PoaChainSpec) -> EthEvmConfig<reth_chainspec::ChainSpec, PoaEvmFactory> {
+        EthEvmConfig::new_with_evm_factory(chain.inner().clone(), PoaEvmFactory::default())
+    }
+
+    /// Block 1 of the dev chain: one 1 ETH transfer from the first dev account.
+    fn block_one(chain: &PoaChainSpec) -> (RecoveredBlock<Block>, Address) {
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[0].parse().unwrap();
+        let tx = TxLegacy {
+            chain_id: Some(chain.chain().id()),
+            nonce: 0,
+            gas_price: GAS_PRICE,
+            gas_limit: 21_000,
+            to: TxKind::Call(recipient()),
+            value: U256::from(VALUE),
+            ..Default::default()
+        };
+        let sig = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        let tx = TransactionSigned::Legacy(tx.into_signed(sig));
+
+        let genesis = chain.genesis_header();
+        let header = Header {
+            parent_hash: chain.genesis_hash(),
+            number: 1,
+            timestamp: genesis.timestamp + 1,
+            gas_limit: genesis.gas_limit,
+            beneficiary: chain.signers()[0],
+            base_fee_per_gas: genesis.base_fee_per_gas,
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        let block = Block {
+            header,
+            body: BlockBody {
+                transactions: vec![tx],
+                ommers: vec![],
+                withdrawals: Some(Default::default()),
+            },
+        };
+        (
+            RecoveredBlock::new_unhashed(block, vec![signer.address()]),
+            signer.address(),
+        )
+    }
+
+    #[test]
+    fn test_replay_block_one_reproduces_transfer() {
+        let chain = PoaChainSpec::dev_chain();
+        let (block, sender) = block_one(&chain);
+
+        let replay = replay_on(
+            &evm_config(&chain),
+            chain.genesis_alloc_as_revm_state(),
+            &block,
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            replay.receipts,
+            vec![Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21_000,
+                logs: vec![],
+            }]
+        );
+        assert_eq!(replay.gas_used, 21_000);
+        assert_eq!(replay.state_changes[&sender].nonce, Some((0, 1)));
+        let (before, after) = replay.state_changes[&recipient()].balance.unwrap();
+        assert_eq!(after - before, U256::from(VALUE));
+    }
+
+    #[test]
+    fn test_replay_with_drained_sender_fails() {
+        let chain = PoaChainSpec::dev_chain();
+        let (block, sender) = block_one(&chain);
+        let overrides = HashMap::from([(
+            sender,
+            AccountOverride {
+                balance: Some(U256::ZERO),
+                ..Default::default()
+            },
+        )]);
+
+        let result = replay_on(
+            &evm_config(&chain),
+            chain.genesis_alloc_as_revm_state(),
+            &block,
+            &overrides,
+        );
+        assert!(
+            matches!(result, Err(ReplayError::EvmError(_))),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_replay_diff_is_relative_to_overrides() {
+        let chain = PoaChainSpec::dev_chain();
+        let (block, _) = block_one(&chain);
+        let start = U256::from(5u64);
+        let overrides = HashMap::from([(
+            recipient(),
+            AccountOverride {
+                balance: Some(start),
+                ..Default::default()
+            },
+        )]);
+
+        let replay = replay_on(
+            &evm_config(&chain),
+            chain.genesis_alloc_as_revm_state(),
+            &block,
+            &overrides,
+        )
+        .unwrap();
+        assert_eq!(
+            replay.state_changes[&recipient()].balance,
+            Some((start, start + U256::from(VALUE)))
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides_storage() {
+        let contract = address!("00000000000000000000000000000000000c0de0");
+        let mut base = InMemoryDB::default();
+        base.insert_account_storage(contract, U256::from(1), U256::from(10))
+            .unwrap();
+        base.insert_account_storage(contract, U256::from(2), U256::from(20))
+            .unwrap();
+
+        let overrides = HashMap::from([(
+            contract,
+            AccountOverride {
+                nonce: Some(9),
+                code: Some(Bytes::from_static(&[0x60, 0x00])),
+                state: Some(HashMap::from([(U256::from(1), U256::from(11))])),
+                state_diff: HashMap::from([(U256::from(3), U256::from(33))]),
+                ..Default::default()
+            },
+        )]);
+        let mut db = CacheDB::new(base);
+        apply_overrides(&mut db, &overrides).unwrap();
+
+        let info = db.basic_ref(contract).unwrap().unwrap();
+        assert_eq!(info.nonce, 9);
+        assert_eq!(info.code_hash, keccak256([0x60, 0x00]));
+        assert_eq!(
+            db.storage_ref(contract, U256::from(1)).unwrap(),
+            U256::from(11)
+        );
+        assert_eq!(db.storage_ref(contract, U256::from(2)).unwrap(), U256::ZERO);
+        assert_eq!(
+            db.storage_ref(contract, U256::from(3)).unwrap(),
+            U256::from(33)
+        );
+    }
+
+    #[test]
+    fn test_account_override_from_json() {
+        let json = r#"{ "balance": "0x0", "nonce": "0x9", "stateDiff": { "0x3": "0x21" } }"#;
+        let parsed: AccountOverride = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed,
+            AccountOverride {
+                balance: Some(U256::ZERO),
+                nonce: Some(9),
+                state_diff: HashMap::from([(U256::from(3), U256::from(33))]),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[derive(Debug)]
+    struct MissingBlocks;
+
+    impl BlockReplayer for MissingBlocks {
+        fn replay(
+            &self,
+            block_number: u64,
+            _: &HashMap<Address, AccountOverride>,
+        ) -> Result<ReplayResult, ReplayError> {
+            Err(ReplayError::BlockNotFound(block_number))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_block_reports_missing_block() {
+        let result = replay_block(Arc::new(MissingBlocks), 42, HashMap::new()).await;
+        assert!(matches!(result, Err(ReplayError::BlockNotFound(42))));
+    }
+}
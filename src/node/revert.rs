@@ -0,0 +1,338 @@
+//! Administrative chain rollback (`meowchain revert`).
+//!
+//! When a consensus bug has produced bad blocks, an operator stops the node and
+//! unwinds its database to a known-good block with
+//! `meowchain revert --to <BLOCK> --reason <TEXT>`:
+//!
+//! 1. the database and static files in the datadir are opened with
+//!    [`open_chain_store`] (the node must not be running),
+//! 2. the target is checked against the canonical tip (`target < tip`, target present),
+//! 3. blocks `target + 1 ..= tip` and their state changes are removed and the
+//!    canonical tip moves to `target` (reth's `remove_block_and_execution_above`),
+//! 4. the reason and the OS user that requested it are logged.
+//!
+//! Running offline means no engine holds the removed blocks in memory and no payload
+//! is built on top of them mid-revert; the next start resumes from the new tip.
+
+use super::PoaNode;
+use alloy_primitives::B256;
+use reth_chainspec::ChainSpec;
+use reth_db::{open_db, DatabaseEnv};
+use reth_ethereum::node::api::NodeTypesWithDBAdapter;
+use reth_ethereum::provider::providers::StaticFileProvider;
+use reth_ethereum::provider::ProviderFactory;
+use reth_storage_api::{
+    BlockExecutionWriter, BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
+};
+use reth_tracing::tracing::{info, warn};
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Chain rollback failures.
+#[derive(Debug, thiserror::Error)]
+pub enum RevertError {
+    /// The target block is not in the canonical chain
+    #[error("Revert target block {target} not found in the canonical chain")]
+    TargetBlockNotFound {
+        /// Requested target block
+        target: u64,
+    },
+
+    /// The target is at or above the current tip, so there is nothing to remove
+    #[error("Revert target block {target} is not below the current tip {tip}")]
+    TargetAboveTip {
+        /// Requested target block
+        target: u64,
+        /// Current canonical tip
+        tip: u64,
+    },
+
+    /// Block production was stopped through a [`ProductionPause`]
+    #[error("Block production is paused")]
+    ProductionPaused,
+
+    /// Underlying storage read or write failed
+    #[error("Storage error during revert: {0}")]
+    Storage(String),
+}
+
+/// Storage operations needed to revert the canonical chain.
+pub trait RevertableChain: Send + Sync + Debug {
+    /// Number of the current canonical tip.
+    fn tip_number(&self) -> Result<u64, RevertError>;
+
+    /// Canonical hash of block `number`, if present.
+    fn canonical_hash(&self, number: u64) -> Result<Option<B256>, RevertError>;
+
+    /// Remove every block above `number`, including its state changes, and commit.
+    fn remove_blocks_above(&self, number: u64) -> Result<(), RevertError>;
+}
+
+/// [`RevertableChain`] backed by a reth provider factory.
+#[derive(Debug, Clone)]
+pub struct ProviderChainStore<F>(pub F);
+
+/// Provider factory over a datadir opened by [`open_chain_store`].
+pub type OfflineProviderFactory =
+    ProviderFactory<NodeTypesWithDBAdapter<PoaNode, Arc<DatabaseEnv>>>;
+
+/// Open the database and static files under `datadir` read-write for a revert.
+///
+/// The node must be stopped: a running node keeps recent blocks in memory and would
+/// write them back over the reverted tip.
+pub fn open_chain_store(
+    datadir: &Path,
+    chain_spec: Arc<ChainSpec>,
+) -> eyre::Result<ProviderChainStore<OfflineProviderFactory>> {
+    let db = Arc::new(open_db(&datadir.join("db"), Default::default())?);
+    let static_files = StaticFileProvider::read_write(datadir.join("static_files"))?;
+    Ok(ProviderChainStore(ProviderFactory::new(
+        db,
+        chain_spec,
+        static_files,
+    )))
+}
+
+impl<F> RevertableChain for ProviderChainStore<F>
+where
+    F: DatabaseProviderFactory<ProviderRW: BlockExecutionWriter>
+        + BlockNumReader
+        + BlockHashReader
+        + Send
+        + Sync
+        + Debug,
+{
+    fn tip_number(&self) -> Result<u64, RevertError> {
+        self.0
+            .best_block_number()
+            .map_err(|e| RevertError::Storage(e.to_string()))
+    }
+
+    fn canonical_hash(&self, number: u64) -> Result<Option<B256>, RevertError> {
+        self.0
+            .block_hash(number)
+            .map_err(|e| RevertError::Storage(e.to_string()))
+    }
+
+    fn remove_blocks_above(&self, number: u64) -> Result<(), RevertError> {
+        let provider = self
+            .0
+            .database_provider_rw()
+            .map_err(|e| RevertError::Storage(e.to_string()))?;
+        provider
+            .remove_block_and_execution_above(number)
+            .map_err(|e| RevertError::Storage(e.to_string()))?;
+        provider
+            .commit()
+            .map_err(|e| RevertError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Shared switch that stops the payload builder from producing blocks.
+///
+/// Cheap to clone; all clones observe the same state. Reported by `poa_status` and
+/// the health probe.
+#[derive(Debug, Clone, Default)]
+pub struct ProductionPause(Arc<AtomicBool>);
+
+impl ProductionPause {
+    /// Create a switch with production enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop block production. Returns `false` if it was already paused.
+    pub fn pause(&self) -> bool {
+        !self.0.swap(true, Ordering::AcqRel)
+    }
+
+    /// Resume block production.
+    pub fn resume(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+
+    /// Whether block production is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Error to return from the payload builder while paused.
+    pub fn check_block_building(&self) -> Result<(), RevertError> {
+        if self.is_paused() {
+            Err(RevertError::ProductionPaused)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// OS user that owns the node process, recorded as the principal of a revert.
+pub fn calling_principal() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("LOGNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Revert `store` to `target_block`, returning the number of blocks removed.
+///
+/// See the [module docs](self) for the sequence and post-conditions. A failed
+/// removal leaves the database untouched.
+pub fn revert_chain(
+    store: &dyn RevertableChain,
+    target_block: u64,
+    reason: &str,
+) -> Result<u64, RevertError> {
+    let tip = store.tip_number()?;
+    if target_block >= tip {
+        return Err(RevertError::TargetAboveTip {
+            target: target_block,
+            tip,
+        });
+    }
+    let Some(target_hash) = store.canonical_hash(target_block)? else {
+        return Err(RevertError::TargetBlockNotFound {
+            target: target_block,
+        });
+    };
+
+    let principal = calling_principal();
+    warn!(
+        target: "poa::revert",
+        tip,
+        target_block,
+        %target_hash,
+        principal = %principal,
+        reason,
+        "Reverting chain"
+    );
+    store.remove_blocks_above(target_block)?;
+    let removed = tip - target_block;
+    info!(
+        target: "poa::revert",
+        removed,
+        new_tip = target_block,
+        %target_hash,
+        principal = %principal,
+        reason,
+        "Chain reverted; the next start resumes from the new tip"
+    );
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// In-memory canonical chain; `None` entries are missing blocks.
+    #[derive(Debug)]
+    struct MockChainStore {
+        blocks: Mutex<Vec<Option<B256>>>,
+        fail_removal: bool,
+    }
+
+    impl MockChainStore {
+        fn with_tip(tip: u64) -> Self {
+            let blocks = (0..=tip)
+                .map(|n| Some(B256::left_padding_from(&n.to_be_bytes())))
+                .collect();
+            Self {
+                blocks: Mutex::new(blocks),
+                fail_removal: false,
+            }
+        }
+    }
+
+    impl RevertableChain for MockChainStore {
+        fn tip_number(&self) -> Result<u64, RevertError> {
+            Ok(self.blocks.lock().unwrap().len() as u64 - 1)
+        }
+
+        fn canonical_hash(&self, number: u64) -> Result<Option<B256>, RevertError> {
+            Ok(self
+                .blocks
+                .lock()
+                .unwrap()
+                .get(number as usize)
+                .copied()
+                .flatten())
+        }
+
+        fn remove_blocks_above(&self, number: u64) -> Result<(), RevertError> {
+            if self.fail_removal {
+                return Err(RevertError::Storage("disk I/O error".to_string()));
+            }
+            self.blocks.lock().unwrap().truncate(number as usize + 1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_revert_removes_blocks_above_target() {
+        let store = MockChainStore::with_tip(10);
+
+        assert_eq!(revert_chain(&store, 7, "bad state root at 8").unwrap(), 3);
+        assert_eq!(store.tip_number().unwrap(), 7);
+        assert_eq!(store.canonical_hash(8).unwrap(), None);
+    }
+
+    #[test]
+    fn test_revert_to_tip_or_above_is_rejected() {
+        let store = MockChainStore::with_tip(5);
+
+        for target in [5, 9] {
+            match revert_chain(&store, target, "noop") {
+                Err(RevertError::TargetAboveTip { target: t, tip }) => {
+                    assert_eq!(t, target);
+                    assert_eq!(tip, 5);
+                }
+                other => panic!("Expected TargetAboveTip, got {other:?}"),
+            }
+        }
+        assert_eq!(store.tip_number().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_missing_target_is_not_found() {
+        let store = MockChainStore::with_tip(5);
+        // Simulate a missing canonical entry below the tip.
+        store.blocks.lock().unwrap()[2] = None;
+
+        assert!(matches!(
+            revert_chain(&store, 2, "test"),
+            Err(RevertError::TargetBlockNotFound { target: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_failed_removal_keeps_chain() {
+        let store = MockChainStore {
+            fail_removal: true,
+            ..MockChainStore::with_tip(4)
+        };
+
+        assert!(matches!(
+            revert_chain(&store, 1, "test"),
+            Err(RevertError::Storage(_))
+        ));
+        assert_eq!(store.tip_number().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_production_pause_blocks_building() {
+        let pause = ProductionPause::new();
+        assert!(pause.check_block_building().is_ok());
+        assert!(pause.pause());
+        assert!(!pause.pause());
+        assert!(matches!(
+            pause.check_block_building(),
+            Err(RevertError::ProductionPaused)
+        ));
+        pause.resume();
+        assert!(!pause.is_paused());
+    }
+}
@@ -0,0 +1,144 @@
+//! Graceful shutdown of block production.
+//!
+//! Stopping the process while the payload builder is mid-block loses that block: its
+//! transactions are executed and its header may already be sealed, but it never
+//! reaches the engine. With
+//! [`PoaNode::with_shutdown_token`](super::PoaNode::with_shutdown_token) the payload
+//! builder registers every build with a [`BuildShutdown`]. Cancelling the token stops
+//! new builds; [`PoaNode::shutdown`](super::PoaNode::shutdown) also waits up to
+//! [`SHUTDOWN_BUILD_TIMEOUT`] for the build in progress to be sealed and handed to the
+//! engine. Writing it to the database is left to reth's engine persistence; the binary
+//! only delays its exit until the handover.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// How long [`PoaNode::shutdown`](super::PoaNode::shutdown) waits for an in-flight build.
+pub const SHUTDOWN_BUILD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Graceful shutdown failures.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ShutdownError {
+    /// The shutdown token is cancelled; no new block is built
+    #[error("Node is shutting down, block production stopped")]
+    ShuttingDown,
+
+    /// The block being built was still in progress when the timeout expired
+    #[error("In-flight block build did not finish within {0:?}")]
+    BuildTimedOut(Duration),
+}
+
+/// Shutdown token plus the number of payload builds in progress.
+///
+/// Cheap to clone; all clones observe the same state.
+#[derive(Debug, Clone)]
+pub struct BuildShutdown {
+    token: CancellationToken,
+    in_flight: Arc<watch::Sender<usize>>,
+}
+
+impl Default for BuildShutdown {
+    fn default() -> Self {
+        Self::new(CancellationToken::new())
+    }
+}
+
+impl BuildShutdown {
+    /// Stop block production when `token` is cancelled.
+    pub fn new(token: CancellationToken) -> Self {
+        Self {
+            token,
+            in_flight: Arc::new(watch::Sender::new(0)),
+        }
+    }
+
+    /// The shutdown token.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Whether the token is cancelled.
+    #[inline]
+    pub fn is_shutting_down(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Number of builds in progress.
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight.borrow()
+    }
+
+    /// Register a build, refused once the token is cancelled.
+    ///
+    /// The build counts as in flight until the returned guard is dropped.
+    pub fn begin_build(&self) -> Result<BuildGuard, ShutdownError> {
+        // Counted before the check: a shutdown that cancels in between still waits.
+        self.in_flight.send_modify(|n| *n += 1);
+        let guard = BuildGuard {
+            in_flight: self.in_flight.clone(),
+        };
+        if self.is_shutting_down() {
+            return Err(ShutdownError::ShuttingDown);
+        }
+        Ok(guard)
+    }
+
+    /// Cancel the token, then wait up to `timeout` for the builds in progress.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), ShutdownError> {
+        self.token.cancel();
+        let mut in_flight = self.in_flight.subscribe();
+        // The sender lives in `self`, so waiting cannot fail with a closed channel.
+        tokio::time::timeout(timeout, in_flight.wait_for(|n| *n == 0))
+            .await
+            .map_err(|_| ShutdownError::BuildTimedOut(timeout))?
+            .map(drop)
+            .map_err(|_| ShutdownError::ShuttingDown)
+    }
+}
+
+/// A payload build in progress; dropping it ends the build.
+#[must_use = "the build ends when the guard is dropped"]
+#[derive(Debug)]
+pub struct BuildGuard {
+    in_flight: Arc<watch::Sender<usize>>,
+}
+
+impl Drop for BuildGuard {
+    fn drop(&mut self) {
+        self.in_flight.send_modify(|n| *n -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_refuses_new_builds() {
+        let shutdown = BuildShutdown::default();
+        drop(shutdown.begin_build().unwrap());
+        assert_eq!(shutdown.in_flight(), 0);
+
+        shutdown.shutdown(SHUTDOWN_BUILD_TIMEOUT).await.unwrap();
+        assert!(shutdown.token().is_cancelled());
+        assert_eq!(
+            shutdown.begin_build().unwrap_err(),
+            ShutdownError::ShuttingDown
+        );
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_times_out_on_stuck_build() {
+        let shutdown = BuildShutdown::default();
+        let _stuck = shutdown.begin_build().unwrap();
+        let timeout = Duration::from_millis(50);
+        assert_eq!(
+            shutdown.shutdown(timeout).await,
+            Err(ShutdownError::BuildTimedOut(timeout))
+        );
+        assert_eq!(shutdown.in_flight(), 1);
+    }
+}
@@ -0,0 +1,38 @@
+//! How a new node catches up with the chain (`--sync-mode`).
+//!
+//! `Full` executes every block from genesis, validating every header. `LightHeaders`
+//! additionally verifies the epoch checkpoint headers first (signer-set continuity
+//! checkpoint to checkpoint) to learn the signer set of downloaded headers ahead of
+//! the executed head.
+//!
+//! State retention is separate: `--archive` keeps all historical state. reth does not
+//! ship a `snap/1` state-download client, so there is no snap mode; pair a new node
+//! with `--trusted-checkpoint` to skip header validation below a known block.
+
+use clap::ValueEnum;
+
+/// Sync strategy of a [`PoaNode`](super::PoaNode), selected with `--sync-mode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SyncMode {
+    /// Validate every header.
+    #[default]
+    Full,
+    /// Verify epoch checkpoints first to establish the head signer set.
+    LightHeaders,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_sync_mode_is_full() {
+        assert_eq!(SyncMode::default(), SyncMode::Full);
+        assert_eq!(
+            SyncMode::from_str("light-headers", false),
+            Ok(SyncMode::LightHeaders)
+        );
+        assert!(SyncMode::from_str("snap", false).is_err());
+        assert!(SyncMode::from_str("archive", false).is_err());
+    }
+}
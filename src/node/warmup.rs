@@ -0,0 +1,427 @@
+//! Cold-start state preloading (`--warmup`).
+//!
+//! The first block after a restart is typically 5–10x slower than steady state:
+//! every account and storage read misses the OS page cache and goes to MDBX on
+//! disk. Warm-up runs once while the payload builder is created, before any block
+//! is built. It collects the accounts and storage slots changed in the last N
+//! blocks (from the changesets), newest block first, and reads each of them once.
+//! The reads pull their pages into the OS page cache, and storage values land in
+//! the builder's hot state cache.
+//!
+//! A hard time budget bounds the delay: collection and loading both stop once it
+//! is spent, and the node starts with whatever was warmed. [`WarmupStats`] are
+//! shown in the startup summary and served by `poa_status`.
+
+use crate::onchain::StorageReader;
+use alloy_primitives::{Address, B256, U256};
+use reth_storage_api::{AccountReader, ChangeSetReader, StateProvider, StorageChangeSetReader};
+use reth_tracing::tracing::{info, warn};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Blocks whose changesets are scanned by default.
+pub const DEFAULT_WARMUP_BLOCKS: u64 = 64;
+
+/// Default upper bound on the time warm-up may add to startup.
+pub const DEFAULT_WARMUP_BUDGET: Duration = Duration::from_secs(10);
+
+/// Entries loaded between progress log lines.
+const PROGRESS_INTERVAL: u64 = 10_000;
+
+/// How much history to preload and how long startup may wait for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmupConfig {
+    /// Number of most recent blocks whose touched state is preloaded.
+    pub blocks: u64,
+    /// Time after which warm-up stops, finished or not.
+    pub budget: Duration,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_WARMUP_BLOCKS, DEFAULT_WARMUP_BUDGET)
+    }
+}
+
+impl WarmupConfig {
+    /// Preload the last `blocks` blocks within `budget`.
+    pub fn new(blocks: u64, budget: Duration) -> Self {
+        Self { blocks, budget }
+    }
+}
+
+/// Outcome of a warm-up run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarmupStats {
+    /// Blocks whose changesets were read
+    pub blocks_scanned: u64,
+    /// Distinct accounts read
+    pub accounts: u64,
+    /// Distinct storage slots read
+    pub slots: u64,
+    /// Wall-clock time spent, in milliseconds
+    pub duration_ms: u64,
+    /// Warm-up stopped at the time budget before loading everything
+    pub budget_exhausted: bool,
+}
+
+impl WarmupStats {
+    /// Total accounts and slots read.
+    #[inline]
+    pub fn entries(&self) -> u64 {
+        self.accounts + self.slots
+    }
+}
+
+/// Shared slot for the stats of the startup warm-up.
+///
+/// Cheap to clone; the payload builder records into it and `poa_status` and the
+/// startup summary read it. Empty when warm-up is disabled or has not run yet.
+#[derive(Debug, Clone, Default)]
+pub struct WarmupReport(Arc<Mutex<Option<WarmupStats>>>);
+
+impl WarmupReport {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the result of a warm-up run.
+    pub fn record(&self, stats: WarmupStats) {
+        *self.0.lock().expect("warmup report lock") = Some(stats);
+    }
+
+    /// Stats of the last warm-up run, if any.
+    pub fn stats(&self) -> Option<WarmupStats> {
+        *self.0.lock().expect("warmup report lock")
+    }
+}
+
+/// Accounts and storage slots changed in one block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TouchedState {
+    /// Accounts whose info changed
+    pub accounts: Vec<Address>,
+    /// `(account, slot)` pairs whose value changed
+    pub slots: Vec<(Address, B256)>,
+}
+
+/// Where warm-up learns what recent blocks touched and loads accounts from.
+pub trait WarmupSource {
+    /// State changed in block `number`. Read errors yield an empty set.
+    fn touched(&self, number: u64) -> TouchedState;
+
+    /// Read `address`'s account info, discarding the result.
+    fn load_account(&self, address: Address);
+}
+
+/// [`WarmupSource`] over the node's changesets and latest state.
+pub struct ProviderWarmupSource<'a, P> {
+    provider: &'a P,
+    state: &'a dyn StateProvider,
+}
+
+impl<'a, P> ProviderWarmupSource<'a, P> {
+    /// Read changesets from `provider` and accounts from `state`.
+    pub fn new(provider: &'a P, state: &'a dyn StateProvider) -> Self {
+        Self { provider, state }
+    }
+}
+
+impl<P: ChangeSetReader + StorageChangeSetReader> WarmupSource for ProviderWarmupSource<'_, P> {
+    fn touched(&self, number: u64) -> TouchedState {
+        let accounts = match self.provider.account_block_changeset(number) {
+            Ok(changes) => changes.into_iter().map(|change| change.address).collect(),
+            Err(err) => {
+                warn!(target: "poa::warmup", number, %err, "Failed to read account changeset");
+                Vec::new()
+            }
+        };
+        let slots = match self.provider.storage_changeset(number) {
+            Ok(changes) => changes
+                .into_iter()
+                .map(|(key, entry)| (key.address(), entry.key))
+                .collect(),
+            Err(err) => {
+                warn!(target: "poa::warmup", number, %err, "Failed to read storage changeset");
+                Vec::new()
+            }
+        };
+        TouchedState { accounts, slots }
+    }
+
+    fn load_account(&self, address: Address) {
+        let _ = self.state.basic_account(&address);
+    }
+}
+
+/// Preload state touched in the `config.blocks` blocks up to `tip`.
+///
+/// Storage is read through `storage`; pass a `CachedStorageReader` sharing the
+/// payload builder's cache to keep the values hot there as well. Stops when
+/// `config.budget` is spent.
+pub fn warm_up(
+    source: &impl WarmupSource,
+    storage: &impl StorageReader,
+    tip: u64,
+    config: WarmupConfig,
+) -> WarmupStats {
+    let started = Instant::now();
+    // An unrepresentable deadline (e.g. `Duration::MAX`) means no limit.
+    let deadline = started.checked_add(config.budget);
+    let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+    let mut stats = WarmupStats::default();
+    info!(
+        target: "poa::warmup",
+        tip,
+        blocks = config.blocks,
+        budget_ms = config.budget.as_millis() as u64,
+        "Warming state caches"
+    );
+
+    // Newest first, so a tight budget still covers the blocks most likely to be hot.
+    let mut accounts = Vec::new();
+    let mut slots = Vec::new();
+    let (mut seen_accounts, mut seen_slots) = (HashSet::new(), HashSet::new());
+    let oldest = tip.saturating_sub(config.blocks.saturating_sub(1));
+    for number in (oldest..=tip).rev().take(config.blocks as usize) {
+        if expired() {
+            stats.budget_exhausted = true;
+            break;
+        }
+        let touched = source.touched(number);
+        accounts.extend(
+            touched
+                .accounts
+                .into_iter()
+                .filter(|a| seen_accounts.insert(*a)),
+        );
+        slots.extend(touched.slots.into_iter().filter(|s| seen_slots.insert(*s)));
+        stats.blocks_scanned += 1;
+    }
+    info!(
+        target: "poa::warmup",
+        blocks = stats.blocks_scanned,
+        accounts = accounts.len(),
+        slots = slots.len(),
+        "Collected recently touched state"
+    );
+
+    if !stats.budget_exhausted {
+        for address in accounts {
+            if expired() {
+                stats.budget_exhausted = true;
+                break;
+            }
+            source.load_account(address);
+            stats.accounts += 1;
+            log_progress(&stats);
+        }
+    }
+    if !stats.budget_exhausted {
+        for (address, slot) in slots {
+            if expired() {
+                stats.budget_exhausted = true;
+                break;
+            }
+            let _ = storage.read_storage(address, U256::from_be_bytes(slot.0));
+            stats.slots += 1;
+            log_progress(&stats);
+        }
+    }
+
+    stats.duration_ms = started.elapsed().as_millis() as u64;
+    if stats.budget_exhausted {
+        warn!(
+            target: "poa::warmup",
+            entries = stats.entries(),
+            duration_ms = stats.duration_ms,
+            "Warm-up stopped at its time budget"
+        );
+    } else {
+        info!(
+            target: "poa::warmup",
+            accounts = stats.accounts,
+            slots = stats.slots,
+            duration_ms = stats.duration_ms,
+            "Warm-up complete"
+        );
+    }
+    stats
+}
+
+fn log_progress(stats: &WarmupStats) {
+    if stats.entries().is_multiple_of(PROGRESS_INTERVAL) {
+        info!(target: "poa::warmup", entries = stats.entries(), "Warm-up progress");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CachedStorageReader, HotStateCache, SharedCache};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MockSource {
+        blocks: HashMap<u64, TouchedState>,
+        scanned: RefCell<Vec<u64>>,
+        loaded: RefCell<Vec<Address>>,
+        delay: Duration,
+    }
+
+    impl WarmupSource for MockSource {
+        fn touched(&self, number: u64) -> TouchedState {
+            self.scanned.borrow_mut().push(number);
+            self.blocks.get(&number).cloned().unwrap_or_default()
+        }
+
+        fn load_account(&self, address: Address) {
+            std::thread::sleep(self.delay);
+            self.loaded.borrow_mut().push(address);
+        }
+    }
+
+    #[derive(Default)]
+    struct MockStorage(RefCell<Vec<(Address, U256)>>);
+
+    impl StorageReader for MockStorage {
+        fn read_storage(&self, address: Address, slot: U256) -> Option<B256> {
+            self.0.borrow_mut().push((address, slot));
+            Some(B256::with_last_byte(7))
+        }
+    }
+
+    fn touched(accounts: &[u8], slots: &[(u8, u8)]) -> TouchedState {
+        TouchedState {
+            accounts: accounts.iter().map(|&a| Address::repeat_byte(a)).collect(),
+            slots: slots
+                .iter()
+                .map(|&(a, s)| (Address::repeat_byte(a), B256::with_last_byte(s)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_warm_up_loads_each_touched_entry_once() {
+        let source = MockSource {
+            blocks: HashMap::from([
+                (10, touched(&[1, 2], &[(1, 1)])),
+                (9, touched(&[2, 3], &[(1, 1), (1, 2)])),
+                (8, touched(&[4], &[])),
+            ]),
+            ..Default::default()
+        };
+        let storage = MockStorage::default();
+
+        let stats = warm_up(
+            &source,
+            &storage,
+            10,
+            WarmupConfig::new(2, Duration::from_secs(5)),
+        );
+
+        assert_eq!(
+            *source.scanned.borrow(),
+            vec![10, 9],
+            "newest first, last 2 blocks"
+        );
+        assert_eq!(
+            *source.loaded.borrow(),
+            vec![
+                Address::repeat_byte(1),
+                Address::repeat_byte(2),
+                Address::repeat_byte(3)
+            ]
+        );
+        assert_eq!(storage.0.borrow().len(), 2);
+        assert_eq!(stats.blocks_scanned, 2);
+        assert_eq!((stats.accounts, stats.slots, stats.entries()), (3, 2, 5));
+        assert!(!stats.budget_exhausted);
+    }
+
+    #[test]
+    fn test_warm_up_near_genesis() {
+        let source = MockSource::default();
+        let stats = warm_up(
+            &source,
+            &MockStorage::default(),
+            2,
+            WarmupConfig::new(64, Duration::MAX),
+        );
+        assert_eq!(*source.scanned.borrow(), vec![2, 1, 0]);
+        assert_eq!(stats.blocks_scanned, 3);
+
+        let stats = warm_up(
+            &source,
+            &MockStorage::default(),
+            2,
+            WarmupConfig::new(0, Duration::MAX),
+        );
+        assert_eq!(stats.blocks_scanned, 0);
+    }
+
+    #[test]
+    fn test_warm_up_stops_at_budget() {
+        let accounts: Vec<u8> = (1..=50).collect();
+        let source = MockSource {
+            blocks: HashMap::from([(5, touched(&accounts, &[(1, 1)]))]),
+            delay: Duration::from_millis(2),
+            ..Default::default()
+        };
+        let storage = MockStorage::default();
+
+        let stats = warm_up(
+            &source,
+            &storage,
+            5,
+            WarmupConfig::new(1, Duration::from_millis(20)),
+        );
+
+        assert!(stats.budget_exhausted);
+        assert!(stats.accounts < 50, "loaded {} accounts", stats.accounts);
+        assert_eq!(stats.slots, 0);
+        assert!(storage.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_warm_up_fills_hot_state_cache() {
+        let source = MockSource {
+            blocks: HashMap::from([(1, touched(&[], &[(9, 1), (9, 2)]))]),
+            ..Default::default()
+        };
+        let cache: SharedCache = Arc::new(Mutex::new(HotStateCache::new(16)));
+        let storage = CachedStorageReader::new_shared(MockStorage::default(), Arc::clone(&cache));
+
+        warm_up(&source, &storage, 1, WarmupConfig::default());
+
+        let mut cache = cache.lock().unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.get(Address::repeat_byte(9), U256::from(2)),
+            Some(B256::with_last_byte(7))
+        );
+    }
+
+    #[test]
+    fn test_report_is_shared() {
+        let report = WarmupReport::new();
+        assert_eq!(report.stats(), None);
+
+        let stats = WarmupStats {
+            accounts: 3,
+            slots: 4,
+            ..Default::default()
+        };
+        report.clone().record(stats);
+        assert_eq!(report.stats(), Some(stats));
+
+        let json = serde_json::to_value(stats).unwrap();
+        assert_eq!(json["accounts"], 3);
+        assert_eq!(json["budgetExhausted"], false);
+    }
+}
@@ -31,6 +31,18 @@ pub fn mapping_address_bool_slot(key: Address, mapping_slot: U256) -> B256 {
     hasher.finalize()
 }
 
+/// Compute the storage slot for a Solidity `mapping(uint256 => T)` entry.
+///
+/// For `entries[id]` at mapping slot 3:
+///   slot = keccak256(abi.encode(id, 3))
+#[inline]
+pub fn mapping_u256_slot(key: U256, mapping_slot: U256) -> B256 {
+    let mut hasher = Keccak256::new();
+    hasher.update(key.to_be_bytes::<32>());
+    hasher.update(mapping_slot.to_be_bytes::<32>());
+    hasher.finalize()
+}
+
 /// Decode an address from a B256 storage value (left-padded with zeros).
 #[inline]
 pub fn decode_address(value: B256) -> Address {
@@ -24,16 +24,19 @@ pub mod slots;
 // Re-export the StorageReader trait and key types at module level
 pub use helpers::{
     decode_address, decode_bool, decode_u64, dynamic_array_base_slot, encode_address, encode_u64,
-    mapping_address_bool_slot,
+    mapping_address_bool_slot, mapping_u256_slot,
 };
 pub use providers::{GenesisStorageReader, StateProviderStorageReader};
 pub use readers::{
-    is_signer_on_chain, is_timelock_paused, read_block_time, read_chain_config, read_gas_limit,
-    read_signer_list, read_timelock_delay, read_timelock_proposer, DynamicChainConfig,
-    DynamicSignerList,
+    dequeue_withdrawals, is_signer_on_chain, is_timelock_paused, read_block_time,
+    read_chain_config, read_gas_limit, read_signer_list, read_timelock_delay,
+    read_timelock_proposer, read_withdrawal_queue_bounds, DynamicChainConfig, DynamicSignerList,
+    QueuedWithdrawal,
 };
 pub use selectors::function_selector;
-pub use slots::{chain_config_slots, signer_registry_slots, timelock_slots};
+pub use slots::{
+    chain_config_slots, signer_registry_slots, timelock_slots, withdrawal_queue_slots,
+};
 
 use alloy_primitives::{Address, B256, U256};
 
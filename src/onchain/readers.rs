@@ -1,5 +1,9 @@
-use super::helpers::{decode_address, decode_bool, decode_u64, mapping_address_bool_slot};
-use super::slots::{chain_config_slots, signer_registry_slots, timelock_slots};
+use super::helpers::{
+    decode_address, decode_bool, decode_u64, mapping_address_bool_slot, mapping_u256_slot,
+};
+use super::slots::{
+    chain_config_slots, signer_registry_slots, timelock_slots, withdrawal_queue_slots,
+};
 use super::StorageReader;
 use crate::genesis::{CHAIN_CONFIG_ADDRESS, SIGNER_REGISTRY_ADDRESS, TIMELOCK_ADDRESS};
 use alloy_primitives::{Address, B256, U256};
@@ -148,3 +152,65 @@ pub fn is_timelock_paused(reader: &impl StorageReader) -> bool {
         .map(decode_bool)
         .unwrap_or(false)
 }
+
+/// A pending entry in the WithdrawalQueue contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuedWithdrawal {
+    /// Queue id (used as the EIP-4895 withdrawal index)
+    pub id: u64,
+    /// Address credited by the withdrawal
+    pub recipient: Address,
+    /// Amount in gwei
+    pub amount_gwei: u64,
+}
+
+/// Read `(head, tail)` from a WithdrawalQueue contract.
+///
+/// Returns None if the contract has no storage (not deployed).
+pub fn read_withdrawal_queue_bounds(reader: &impl StorageReader, queue: Address) -> Option<(u64, u64)> {
+    let head = reader.read_storage(queue, withdrawal_queue_slots::HEAD).map(decode_u64);
+    let tail = reader.read_storage(queue, withdrawal_queue_slots::TAIL).map(decode_u64);
+    match (head, tail) {
+        (None, None) => None,
+        (head, tail) => Some((head.unwrap_or(0), tail.unwrap_or(0))),
+    }
+}
+
+/// Storage-level equivalent of `WithdrawalQueue.dequeue(max)`.
+///
+/// Returns up to `max` pending entries with id in `[from.max(head), tail)`. `from`
+/// lets the caller skip ids it has already included but the contract has not yet
+/// finalized. Entries with no recipient (deleted or never written) end the run.
+pub fn dequeue_withdrawals(
+    reader: &impl StorageReader,
+    queue: Address,
+    from: u64,
+    max: usize,
+) -> Vec<QueuedWithdrawal> {
+    let Some((head, tail)) = read_withdrawal_queue_bounds(reader, queue) else {
+        return Vec::new();
+    };
+    let start = from.max(head);
+    let count = tail.saturating_sub(start).min(max as u64);
+
+    let mut out = Vec::with_capacity(count as usize);
+    for id in start..start + count {
+        let slot_hash = mapping_u256_slot(U256::from(id), withdrawal_queue_slots::ENTRIES_MAPPING);
+        let Some(value) = reader.read_storage(queue, U256::from_be_bytes(slot_hash.0)) else {
+            break;
+        };
+        let recipient = decode_address(value);
+        if recipient == Address::ZERO {
+            break;
+        }
+        // Entry packs `uint64 amountGwei` directly above the 20-byte address.
+        let mut amount = [0u8; 8];
+        amount.copy_from_slice(&value[4..12]);
+        out.push(QueuedWithdrawal {
+            id,
+            recipient,
+            amount_gwei: u64::from_be_bytes(amount),
+        });
+    }
+    out
+}
@@ -72,3 +72,21 @@ pub mod timelock_slots {
     /// slot 4: paused (bool)
     pub const PAUSED: U256 = U256::from_limbs([4, 0, 0, 0]);
 }
+
+/// WithdrawalQueue contract storage layout.
+///
+/// Matches `genesis-contracts/WithdrawalQueue.sol`. Unlike the other governance
+/// contracts it is not deployed at a fixed genesis address — the address comes
+/// from [`WithdrawalConfig`](crate::chainspec::WithdrawalConfig).
+pub mod withdrawal_queue_slots {
+    use alloy_primitives::U256;
+
+    /// slot 0: governance (address)
+    pub const GOVERNANCE: U256 = U256::from_limbs([0, 0, 0, 0]);
+    /// slot 1: head (uint64) — next unprocessed id
+    pub const HEAD: U256 = U256::from_limbs([1, 0, 0, 0]);
+    /// slot 2: tail (uint64) — next id to assign
+    pub const TAIL: U256 = U256::from_limbs([2, 0, 0, 0]);
+    /// slot 3: entries mapping base (mapping(uint256 => Entry))
+    pub const ENTRIES_MAPPING: U256 = U256::from_limbs([3, 0, 0, 0]);
+}
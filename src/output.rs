@@ -4,6 +4,7 @@
 //! Color scheme: blue+bold headers, cyan values, green success,
 //! yellow warnings, dimmed secondary text.
 
+use crate::node::warmup::WarmupStats;
 use alloy_primitives::Address;
 use colored::Colorize;
 use std::fmt;
@@ -250,66 +251,6 @@ pub fn print_consensus_init(signer_count: usize, epoch: u64, period: u64, mode:
     );
 }
 
-// ── On-Chain Reads ─────────────────────────────────────────────────
-
-/// Print when on-chain gas limit differs from default.
-pub fn print_onchain_gas_limit(onchain: u64, default: u64) {
-    println!(
-        "  {} OnChain gas limit: {} (default was {})",
-        "OK".green().bold(),
-        onchain.to_string().cyan(),
-        default.to_string().dimmed(),
-    );
-}
-
-/// Print when on-chain signers are loaded at startup.
-pub fn print_onchain_signers(count: usize) {
-    println!(
-        "  {} OnChain signers: {} loaded from SignerRegistry",
-        "OK".green().bold(),
-        count.to_string().cyan(),
-    );
-}
-
-// ── Payload / Block Production ─────────────────────────────────────
-
-/// Print when signers are refreshed at an epoch block.
-pub fn print_epoch_refresh(block_number: u64, signer_count: usize) {
-    println!(
-        "  {} Epoch #{}: refreshed {} signers from SignerRegistry",
-        "OK".green().bold(),
-        block_number.to_string().cyan(),
-        signer_count.to_string().cyan(),
-    );
-}
-
-/// Print when a block is signed by a POA signer.
-///
-/// `build_ms` is the wall-clock time spent building the block (Phase 2.17 timing).
-/// `sign_ms` is the wall-clock time spent on ECDSA signing (Phase 5 timing).
-pub fn print_block_signed(
-    block_number: u64,
-    signer: &Address,
-    in_turn: bool,
-    build_ms: u64,
-    sign_ms: u64,
-) {
-    let turn = if in_turn {
-        "in-turn".green()
-    } else {
-        "out-of-turn".yellow()
-    };
-    println!(
-        "  {} POA block #{} signed by {} ({}, build={}ms sign={}ms)",
-        "OK".green().bold(),
-        block_number.to_string().cyan(),
-        signer.to_string().cyan(),
-        turn,
-        build_ms.to_string().dimmed(),
-        sign_ms.to_string().dimmed(),
-    );
-}
-
 // ── Block Monitor ──────────────────────────────────────────────────
 
 /// Print block info when no signers are configured (dev/monitoring).
@@ -387,6 +328,24 @@ pub fn print_block_time_budget_warning(block_num: u64, elapsed_ms: u64, interval
 
 // ── Shutdown & Info ──────────────────────────────────────────────────
 
+/// Print the result of the startup state warm-up.
+pub fn print_warmup(stats: &WarmupStats) {
+    let detail = format!(
+        "{} accounts, {} slots from {} blocks in {}ms",
+        stats.accounts, stats.slots, stats.blocks_scanned, stats.duration_ms,
+    );
+    if stats.budget_exhausted {
+        println!(
+            "  {} {}: {} (stopped at time budget)",
+            "WARN".yellow().bold(),
+            "State warm-up".cyan(),
+            detail,
+        );
+    } else {
+        print_feature("State warm-up", &detail);
+    }
+}
+
 /// Print a shutdown message with the reason.
 pub fn print_shutdown(reason: &str) {
     println!();
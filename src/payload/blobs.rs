@@ -0,0 +1,230 @@
+//! EIP-4844 blob sidecar validation (feature `blob-transactions`).
+//!
+//! The transaction pool checks sidecars when a blob transaction is submitted, but
+//! a sealer signs whatever the builder hands it. With
+//! [`PoaPayloadBuilderBuilder::with_blob_transaction_support`](super::PoaPayloadBuilderBuilder::with_blob_transaction_support)
+//! enabled, the stored sidecar of every blob transaction is re-checked when the
+//! builder selects it:
+//!
+//! 1. each commitment must hash (`kzg_to_versioned_hash`) to the versioned hash at
+//!    the same index in the transaction, and
+//! 2. the KZG proofs must verify the blobs against those commitments.
+//!
+//! A transaction failing either check is left out of the block, with its sender's
+//! later transactions; the rest of the block is built as usual.
+
+use super::sequencer::BoxedBest;
+use alloy_consensus::Transaction;
+use alloy_eips::eip4844::env_settings::EnvKzgSettings;
+use alloy_eips::eip4844::{kzg_to_versioned_hash, BlobTransactionSidecar};
+use alloy_primitives::B256;
+use reth_ethereum::TransactionSigned;
+use reth_primitives_traits::SignedTransaction;
+use reth_tracing::tracing::warn;
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
+use std::sync::Arc;
+
+/// Blob sidecar validation failures.
+#[derive(Debug, thiserror::Error)]
+pub enum BlobError {
+    /// Sidecar supplied for a transaction that carries no blob hashes
+    #[error("Transaction {tx_hash} is not a blob transaction")]
+    NotBlobTransaction {
+        /// Transaction hash
+        tx_hash: B256,
+    },
+
+    /// Number of sidecar commitments differs from the transaction's blob hashes
+    #[error("Transaction {tx_hash} has {expected} blob hashes but sidecar has {got} commitments")]
+    BlobCountMismatch {
+        /// Transaction hash
+        tx_hash: B256,
+        /// Versioned hashes in the transaction
+        expected: usize,
+        /// Commitments in the sidecar
+        got: usize,
+    },
+
+    /// Commitment does not match the transaction's versioned hash
+    #[error("Blob commitment {index} does not match versioned hash in transaction {tx_hash}")]
+    InvalidCommitment {
+        /// Transaction hash
+        tx_hash: B256,
+        /// Index of the mismatching blob
+        index: usize,
+    },
+
+    /// KZG proof verification failed
+    #[error("KZG proof verification failed for transaction {tx_hash}: {reason}")]
+    InvalidProof {
+        /// Transaction hash
+        tx_hash: B256,
+        /// Underlying verifier error
+        reason: String,
+    },
+}
+
+/// Validate a blob transaction's sidecar before it is sealed into a block.
+pub fn validate_blob_sidecar(
+    tx: &TransactionSigned,
+    sidecar: &BlobTransactionSidecar,
+) -> Result<(), BlobError> {
+    let tx_hash = *tx.tx_hash();
+    let versioned_hashes = match tx.blob_versioned_hashes() {
+        Some(hashes) if !hashes.is_empty() => hashes,
+        _ => return Err(BlobError::NotBlobTransaction { tx_hash }),
+    };
+
+    if versioned_hashes.len() != sidecar.commitments.len() {
+        return Err(BlobError::BlobCountMismatch {
+            tx_hash,
+            expected: versioned_hashes.len(),
+            got: sidecar.commitments.len(),
+        });
+    }
+
+    for (index, (commitment, expected)) in
+        sidecar.commitments.iter().zip(versioned_hashes).enumerate()
+    {
+        if kzg_to_versioned_hash(commitment.as_slice()) != *expected {
+            return Err(BlobError::InvalidCommitment { tx_hash, index });
+        }
+    }
+
+    sidecar
+        .validate(versioned_hashes, EnvKzgSettings::Default.get())
+        .map_err(|err| BlobError::InvalidProof {
+            tx_hash,
+            reason: err.to_string(),
+        })
+}
+
+/// Whether the sidecar the pool stores for `tx` matches it; true for non-blob
+/// transactions.
+///
+/// A missing sidecar is left to the inner builder, which drops the transaction;
+/// EIP-7594 (cell proof) sidecars are left to the pool's validation.
+pub(crate) fn has_valid_sidecar<P>(pool: &P, tx: &ValidPoolTransaction<P::Transaction>) -> bool
+where
+    P: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    if !tx.is_eip4844() {
+        return true;
+    }
+    let Ok(Some(sidecar)) = pool.get_blob(*tx.hash()) else {
+        return true;
+    };
+    let Some(sidecar) = sidecar.as_eip4844() else {
+        return true;
+    };
+    let transaction = tx.transaction.clone_into_consensus();
+    match validate_blob_sidecar(transaction.inner(), sidecar) {
+        Ok(()) => true,
+        Err(err) => {
+            warn!(target: "poa::payload", %err, "Invalid blob sidecar, skipping transaction");
+            false
+        }
+    }
+}
+
+/// `best` without the blob transactions whose sidecar fails [`has_valid_sidecar`].
+pub(crate) fn filter_invalid_sidecars<P>(
+    pool: P,
+    best: BoxedBest<P::Transaction>,
+) -> BoxedBest<P::Transaction>
+where
+    P: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>> + 'static,
+{
+    Box::new(
+        best.filter_transactions(move |tx: &Arc<ValidPoolTransaction<P::Transaction>>| {
+            has_valid_sidecar(&pool, tx)
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{SidecarBuilder, Signed, SimpleCoder, TxEip4844};
+    use alloy_primitives::{Address, Signature};
+
+    fn sidecar_for(data: &[u8]) -> BlobTransactionSidecar {
+        SidecarBuilder::<SimpleCoder>::from_slice(data)
+            .build()
+            .unwrap()
+    }
+
+    fn blob_tx(versioned_hashes: Vec<B256>) -> TransactionSigned {
+        let tx = TxEip4844 {
+            chain_id: 9323310,
+            to: Address::repeat_byte(0x42),
+            gas_limit: 21_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_fee_per_blob_gas: 1,
+            blob_versioned_hashes: versioned_hashes,
+            ..Default::default()
+        };
+        TransactionSigned::Eip4844(Signed::new_unhashed(tx, Signature::test_signature()))
+    }
+
+    #[test]
+    fn test_correctly_committed_blob_is_accepted() {
+        let sidecar = sidecar_for(b"meowchain blob payload");
+        let tx = blob_tx(sidecar.versioned_hashes().collect());
+        assert!(validate_blob_sidecar(&tx, &sidecar).is_ok());
+    }
+
+    #[test]
+    fn test_wrong_versioned_hash_is_invalid_commitment() {
+        let sidecar = sidecar_for(b"meowchain blob payload");
+        let tx = blob_tx(
+            sidecar_for(b"a different blob")
+                .versioned_hashes()
+                .collect(),
+        );
+        match validate_blob_sidecar(&tx, &sidecar) {
+            Err(BlobError::InvalidCommitment { tx_hash, index }) => {
+                assert_eq!(tx_hash, *tx.tx_hash());
+                assert_eq!(index, 0);
+            }
+            other => panic!("Expected InvalidCommitment, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_tampered_blob_fails_proof() {
+        let mut sidecar = sidecar_for(b"meowchain blob payload");
+        let tx = blob_tx(sidecar.versioned_hashes().collect());
+        sidecar.blobs[0][0] ^= 0x01;
+        assert!(matches!(
+            validate_blob_sidecar(&tx, &sidecar),
+            Err(BlobError::InvalidProof { .. })
+        ));
+    }
+
+    #[test]
+    fn test_blob_count_mismatch() {
+        let sidecar = sidecar_for(b"meowchain blob payload");
+        let mut hashes: Vec<B256> = sidecar.versioned_hashes().collect();
+        hashes.push(hashes[0]);
+        let tx = blob_tx(hashes);
+        assert!(matches!(
+            validate_blob_sidecar(&tx, &sidecar),
+            Err(BlobError::BlobCountMismatch {
+                expected: 2,
+                got: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_non_blob_transaction_rejected() {
+        let sidecar = sidecar_for(b"meowchain blob payload");
+        let tx = blob_tx(Vec::new());
+        assert!(matches!(
+            validate_blob_sidecar(&tx, &sidecar),
+            Err(BlobError::NotBlobTransaction { .. })
+        ));
+    }
+}
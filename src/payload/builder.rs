@@ -1,24 +1,98 @@
 use crate::cache::{CacheConfig, CachedStorageReader, HotStateCache, SharedCache};
 use crate::chainspec::PoaChainSpec;
-use crate::consensus::{EXTRA_SEAL_LENGTH, EXTRA_VANITY_LENGTH};
-use crate::onchain::{read_gas_limit, read_signer_list, StateProviderStorageReader};
-use crate::output;
+use crate::clock::TimestampScheduler;
+use crate::consensus::metadata::BlockMetadata;
+use crate::consensus::vanity::{client_vanity, default_client_tag};
+use crate::consensus::{GasLimitSource, VoterSet};
+use crate::metrics::ChainMetrics;
+use crate::node::disk::DiskGuard;
+use crate::node::revert::ProductionPause;
+use crate::node::shutdown::BuildShutdown;
+use crate::node::warmup::{warm_up, ProviderWarmupSource, WarmupConfig, WarmupReport};
+use crate::consensus::EXTRA_SEAL_LENGTH;
+use crate::onchain::{
+    read_gas_limit, read_signer_list, StateProviderStorageReader,
+};
 use crate::signer::SignerManager;
 use alloy_primitives::{Address, Bytes};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum::node::api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_ethereum::node::builder::{components::PayloadBuilderBuilder, BuilderContext};
 use reth_ethereum::node::core::cli::config::PayloadBuilderConfig;
-use reth_ethereum::storage::StateProviderFactory;
+use reth_ethereum::storage::{BlockNumReader, StateProviderFactory, StorageChangeSetReader};
 use reth_ethereum::EthPrimitives;
 use reth_ethereum_engine_primitives::{EthBuiltPayload, EthPayloadAttributes};
 use reth_ethereum_payload_builder::EthereumBuilderConfig;
 use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
 use reth_payload_primitives::PayloadTypes;
+use reth_tracing::tracing::{info, warn};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
-use std::sync::{Arc, Mutex};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
 
-use super::PoaPayloadBuilder;
+use super::{
+    CrossChainMessageExtractor, DepositSource, DepositTracker, FeePolicy, GasLimitRamp,
+    PoaPayloadBuilder, PrioritySenders, SignerRotation, TxBundle, WithdrawalQueueTracker,
+};
+
+/// Source of per-block metadata, called with the number of the block being signed.
+#[derive(Clone)]
+pub struct BlockMetadataProvider(Arc<dyn Fn(u64) -> BlockMetadata + Send + Sync>);
+
+impl BlockMetadataProvider {
+    /// Wrap a metadata function.
+    pub fn new(provider: Arc<dyn Fn(u64) -> BlockMetadata + Send + Sync>) -> Self {
+        Self(provider)
+    }
+
+    /// Metadata read from `dir/<number>.json`, a JSON object written by an external
+    /// process (e.g. a batcher) before block `number` is sealed and never changed
+    /// afterwards: the RPC serves committed metadata from the same file. A block
+    /// without a file gets empty metadata; an unreadable file is logged and treated
+    /// the same.
+    pub fn from_dir(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        Self(Arc::new(move |number| {
+            let path = dir.join(format!("{number}.json"));
+            let bytes = match std::fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    return BlockMetadata::new()
+                }
+                Err(err) => {
+                    warn!(
+                        target: "poa::payload",
+                        path = %path.display(),
+                        %err,
+                        "Block metadata unreadable"
+                    );
+                    return BlockMetadata::new();
+                }
+            };
+            serde_json::from_slice(&bytes).unwrap_or_else(|err| {
+                warn!(
+                    target: "poa::payload",
+                    path = %path.display(),
+                    %err,
+                    "Invalid block metadata"
+                );
+                BlockMetadata::new()
+            })
+        }))
+    }
+
+    /// Metadata for block `number`.
+    pub fn metadata(&self, number: u64) -> BlockMetadata {
+        (self.0)(number)
+    }
+}
+
+impl std::fmt::Debug for BlockMetadataProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BlockMetadataProvider(..)")
+    }
+}
 
 /// Component-level builder that creates `PoaPayloadBuilder` instances.
 /// Plugs into `BasicPayloadServiceBuilder` in the node's `ComponentsBuilder`.
@@ -31,6 +105,45 @@ pub struct PoaPayloadBuilderBuilder {
     pub(crate) cache_size: usize,
     /// Addresses to credit with a withdrawal on every built block (infinite fund).
     pub(crate) infinite_fund: Vec<Address>,
+    /// Queue cursors of recent canonical blocks, shared with the node's chain follower.
+    pub(crate) withdrawal_queue: Arc<WithdrawalQueueTracker>,
+    /// Client identifier written into every block's vanity.
+    pub(crate) client_tag: String,
+    /// Datadir free-space state shared with the disk monitor.
+    pub(crate) disk_guard: DiskGuard,
+    /// Stop switch shared with `PoaNode::production_pause`.
+    pub(crate) production_pause: ProductionPause,
+    /// Shutdown token and in-flight builds shared with `PoaNode::shutdown`.
+    pub(crate) shutdown: BuildShutdown,
+    /// Metadata appended after the seal of every signed block.
+    pub(crate) block_metadata: Option<BlockMetadataProvider>,
+    /// Append a 32-byte commitment to the metadata instead of the metadata itself.
+    pub(crate) block_metadata_hash: bool,
+    /// Cold-start state preloading run once in `build_payload_builder`.
+    pub(crate) warmup: Option<WarmupConfig>,
+    /// Where the warm-up result is recorded for `poa_status`.
+    pub(crate) warmup_report: WarmupReport,
+    /// Searcher bundles placed atomically at the top of every block.
+    pub(crate) bundle_pool: Option<Arc<RwLock<Vec<TxBundle>>>>,
+    /// Chain metrics receiving the bundle inclusion counters.
+    pub(crate) metrics: Option<Arc<ChainMetrics>>,
+    /// Measure the compressed size of every built block body.
+    pub(crate) compression_stats: bool,
+    /// Fee floor and sequencer mode for pool transaction selection.
+    pub(crate) fee_policy: FeePolicy,
+    /// Which local key seals each block.
+    pub(crate) signer_rotation: SignerRotation,
+    /// Signer-set proposals voted on in sealed blocks, and the canonical tally.
+    pub(crate) votes: VoterSet,
+    /// L1 bridge deposits placed at the top of every block.
+    pub(crate) deposit_source: Option<DepositSource>,
+    /// L1 cursors of recent canonical blocks, used by the deposit source.
+    pub(crate) deposit_tracker: Arc<DepositTracker>,
+    /// Senders whose pending transactions lead every block, bypassing fee ordering.
+    pub(crate) priority_senders: HashSet<Address>,
+    /// Re-validate blob sidecars before sealing (feature `blob-transactions`).
+    #[cfg(feature = "blob-transactions")]
+    pub(crate) blob_transactions: bool,
 }
 
 impl PoaPayloadBuilderBuilder {
@@ -46,6 +159,26 @@ impl PoaPayloadBuilderBuilder {
             dev_mode,
             cache_size: CacheConfig::default().max_entries,
             infinite_fund: Vec::new(),
+            withdrawal_queue: Arc::new(WithdrawalQueueTracker::new()),
+            client_tag: default_client_tag(),
+            disk_guard: DiskGuard::new(),
+            production_pause: ProductionPause::new(),
+            shutdown: BuildShutdown::default(),
+            block_metadata: None,
+            block_metadata_hash: false,
+            warmup: None,
+            warmup_report: WarmupReport::new(),
+            bundle_pool: None,
+            metrics: None,
+            compression_stats: false,
+            fee_policy: FeePolicy::default(),
+            signer_rotation: SignerRotation::default(),
+            votes: VoterSet::new(),
+            deposit_source: None,
+            deposit_tracker: Arc::new(DepositTracker::new()),
+            priority_senders: HashSet::new(),
+            #[cfg(feature = "blob-transactions")]
+            blob_transactions: false,
         }
     }
 
@@ -60,12 +193,186 @@ impl PoaPayloadBuilderBuilder {
         self.infinite_fund = addrs;
         self
     }
+
+    /// Share the governance withdrawal queue cursors.
+    ///
+    /// The tracker must be fed with canonical blocks
+    /// ([`record_canonical_block`](WithdrawalQueueTracker::record_canonical_block));
+    /// the builder only catches up on blocks it finds missing. Open it with a state
+    /// file so paid entries are not paid again after a restart.
+    pub fn with_withdrawal_queue(mut self, tracker: Arc<WithdrawalQueueTracker>) -> Self {
+        self.withdrawal_queue = tracker;
+        self
+    }
+
+    /// Set the client tag written into the block vanity.
+    ///
+    /// Truncated to 32 bytes; non-printable bytes become `?` (see `consensus::vanity`).
+    pub fn with_client_tag(mut self, tag: impl Into<String>) -> Self {
+        self.client_tag = tag.into();
+        self
+    }
+
+    /// Share the datadir [`DiskGuard`]: payloads are refused while free space is critical.
+    pub fn with_disk_guard(mut self, guard: DiskGuard) -> Self {
+        self.disk_guard = guard;
+        self
+    }
+
+    /// Share the [`ProductionPause`] switch: no payloads are built while it is set.
+    pub fn with_production_pause(mut self, pause: ProductionPause) -> Self {
+        self.production_pause = pause;
+        self
+    }
+
+    /// Share the [`BuildShutdown`]: no payloads are built once its token is cancelled,
+    /// and a build in progress is tracked until it is handed to the engine.
+    pub fn with_shutdown(mut self, shutdown: BuildShutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Attach chain-specific metadata (e.g. batch sequence number, L1 state root) to
+    /// every signed block.
+    ///
+    /// `provider` is called with the block number; its result is JSON-encoded into a
+    /// framed suffix after the seal (see `consensus::metadata`) and covered by the
+    /// signature. Dev-mode blocks are unsigned and carry no metadata.
+    pub fn with_block_metadata_provider(mut self, provider: BlockMetadataProvider) -> Self {
+        self.block_metadata = Some(provider);
+        self
+    }
+
+    /// Commit to the block metadata instead of embedding it.
+    ///
+    /// With a [`with_block_metadata_provider`](Self::with_block_metadata_provider)
+    /// set, signed blocks carry `keccak256` of the metadata JSON (32 bytes) after the
+    /// seal rather than the JSON frame; the metadata itself is served by
+    /// `poa_getBlockMetadata` and checked against the commitment.
+    pub fn with_block_metadata_hash(mut self, enabled: bool) -> Self {
+        self.block_metadata_hash = enabled;
+        self
+    }
+
+    /// Preload state touched by recent blocks before the builder is handed to the
+    /// payload service, recording the stats in `report` (`None` disables warm-up).
+    ///
+    /// Storage values also land in the hot state cache. See `node::warmup`.
+    pub fn with_warmup(mut self, config: Option<WarmupConfig>, report: WarmupReport) -> Self {
+        self.warmup = config;
+        self.warmup_report = report;
+        self
+    }
+
+    /// Include searcher bundles from `bundle_pool` at the top of every block.
+    ///
+    /// Each bundle is included whole or not at all: it is simulated on the parent
+    /// state first and skipped if any transaction not listed in its
+    /// `reverting_tx_hashes` fails. A bundle tried in a block leaves `bundle_pool`
+    /// once the next block is built on it. See `payload::bundles`.
+    pub fn with_bundle_support(mut self, bundle_pool: Arc<RwLock<Vec<TxBundle>>>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+
+    /// Count included and rejected bundles in `metrics`.
+    pub fn with_chain_metrics(mut self, metrics: Arc<ChainMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Skip pool transactions whose effective gas price is below `min_gas_price` wei
+    /// (0 disables the floor). Ignored in sequencer mode.
+    pub fn with_min_gas_price(mut self, min_gas_price: u128) -> Self {
+        self.fee_policy.min_gas_price = min_gas_price;
+        self
+    }
+
+    /// Include every valid pending transaction, limited only by the block gas limit.
+    ///
+    /// For chains run as a single sequencer with a commitment to censorship
+    /// resistance: the min gas price floor is lifted, and a warning is logged when a
+    /// valid transaction is left out of a block that had gas for it. See
+    /// `payload::sequencer`.
+    pub fn with_sequencer_mode(mut self, sequencer_mode: bool) -> Self {
+        self.fee_policy.sequencer_mode = sequencer_mode;
+        self
+    }
+
+    /// Choose the local key sealing each block; blocks `rotation` assigns none of our
+    /// keys are not built. See `payload::rotation`.
+    pub fn with_signer_rotation(mut self, rotation: SignerRotation) -> Self {
+        self.signer_rotation = rotation;
+        self
+    }
+
+    /// Seal votes for the proposals in `votes` (`clique_propose`) into built blocks.
+    /// See `consensus::votes`.
+    pub fn with_voter_set(mut self, votes: VoterSet) -> Self {
+        self.votes = votes;
+        self
+    }
+
+    /// Always include the pending transactions of `senders`, whatever they pay.
+    ///
+    /// For system operators (oracle updates, governance executions): their
+    /// transactions follow deposits and bundles, ahead of the fee-ordered pool and
+    /// regardless of the min gas price floor. See `payload::priority`.
+    pub fn with_priority_transaction_whitelist(mut self, senders: HashSet<Address>) -> Self {
+        self.priority_senders = senders;
+        self
+    }
+
+    /// Relay L1 bridge deposits found by `extractor` at the top of every block.
+    ///
+    /// Each block processes the next L1 block: its deposit transactions lead the
+    /// block, ahead of bundles and pool transactions. See `payload::deposits`.
+    pub fn with_cross_chain_message_extractor(
+        mut self,
+        extractor: Arc<dyn CrossChainMessageExtractor>,
+    ) -> Self {
+        self.with_deposit_source(DepositSource::new(extractor))
+    }
+
+    /// Relay L1 bridge deposits from `source`; its tracker is replaced by the one set
+    /// with [`with_deposit_tracker`](Self::with_deposit_tracker).
+    pub fn with_deposit_source(mut self, source: DepositSource) -> Self {
+        self.deposit_source = Some(source);
+        self
+    }
+
+    /// Track the L1 deposit cursor in `tracker`, e.g. one persisted in the datadir.
+    pub fn with_deposit_tracker(mut self, tracker: Arc<DepositTracker>) -> Self {
+        self.deposit_tracker = tracker;
+        self
+    }
+
+    /// Log how well the body of every block sealed here compresses (RLP, zstd
+    /// level 3), once per block.
+    ///
+    /// Raw and compressed sizes also accumulate in the chain metrics set with
+    /// [`with_chain_metrics`](Self::with_chain_metrics). See `payload::compression`.
+    pub fn with_compression_stats(mut self, enabled: bool) -> Self {
+        self.compression_stats = enabled;
+        self
+    }
+
+    /// Validate EIP-4844 blob sidecars against their transactions when selecting them.
+    ///
+    /// A blob transaction whose stored sidecar does not match its versioned hashes or
+    /// fails KZG proof verification is skipped, and the block is built without it.
+    /// See [`validate_blob_sidecar`](super::validate_blob_sidecar).
+    #[cfg(feature = "blob-transactions")]
+    pub fn with_blob_transaction_support(mut self, enabled: bool) -> Self {
+        self.blob_transactions = enabled;
+        self
+    }
 }
 
 impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for PoaPayloadBuilderBuilder
 where
     Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
-    Node: FullNodeTypes<Types = Types>,
+    Node: FullNodeTypes<Types = Types, Provider: StorageChangeSetReader>,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
         + Unpin
         + 'static,
@@ -76,7 +383,7 @@ where
         PayloadAttributes = EthPayloadAttributes,
     >,
 {
-    type PayloadBuilder = PoaPayloadBuilder<Pool, Node::Provider, Evm>;
+    type PayloadBuilder = PoaPayloadBuilder<Pool, Node::Provider, GasLimitRamp<Evm>>;
 
     async fn build_payload_builder(
         self,
@@ -103,7 +410,12 @@ where
                 let onchain_gas = read_gas_limit(&cached).filter(|&gl| gl > 0);
                 let gl = if let Some(gl) = onchain_gas {
                     if gl != default_gas_limit {
-                        output::print_onchain_gas_limit(gl, default_gas_limit);
+                        info!(
+                            target: "poa::payload",
+                            onchain = gl,
+                            default = default_gas_limit,
+                            "Using on-chain gas limit from ChainConfig"
+                        );
                     }
                     gl
                 } else {
@@ -113,7 +425,11 @@ where
                 // Seed live signer cache from SignerRegistry
                 if let Some(list) = read_signer_list(&cached) {
                     if !list.signers.is_empty() {
-                        output::print_onchain_signers(list.signers.len());
+                        info!(
+                            target: "poa::payload",
+                            signer_count = list.signers.len(),
+                            "Loaded on-chain signers from SignerRegistry"
+                        );
                         self.chain_spec.update_live_signers(list.signers);
                     }
                 }
@@ -123,33 +439,116 @@ where
             Err(_) => default_gas_limit,
         };
 
+        // Cold-start warm-up: runs before the payload service exists, so before any
+        // block is built, and is bounded by its own time budget.
+        if let Some(config) = self.warmup {
+            match (ctx.provider().best_block_number(), ctx.provider().latest()) {
+                (Ok(tip), Ok(state)) => {
+                    let source = ProviderWarmupSource::new(ctx.provider(), state.as_ref());
+                    let reader = StateProviderStorageReader(state.as_ref());
+                    let storage = CachedStorageReader::new_shared(reader, Arc::clone(&cache));
+                    self.warmup_report.record(warm_up(&source, &storage, tip, config));
+                }
+                _ => warn!(target: "poa::warmup", "State unavailable, skipping warm-up"),
+            }
+        }
+
+        // Blocks of the gas limit ramp step toward the parent state's ChainConfig value.
+        let gas_limits = (self.chain_spec.max_gas_ramp_blocks() > 0)
+            .then(|| GasLimitSource::new(Arc::new(ctx.provider().clone())));
+        let builder_config = EthereumBuilderConfig::new()
+            .with_gas_limit(gas_limit)
+            .with_max_blobs_per_block(conf.max_blobs_per_block());
+        Ok(self.into_payload_builder(
+            ctx.provider().clone(),
+            pool,
+            evm_config,
+            builder_config,
+            cache,
+            gas_limits,
+        ))
+    }
+}
+
+impl PoaPayloadBuilderBuilder {
+    /// Payload builder over `client` and `pool`, once the startup state reads are
+    /// done: `builder_config` carries the gas limit and `cache` the state read so far.
+    pub(crate) fn into_payload_builder<Pool, Client, Evm>(
+        self,
+        client: Client,
+        pool: Pool,
+        evm_config: Evm,
+        builder_config: EthereumBuilderConfig,
+        cache: SharedCache,
+        gas_limits: Option<GasLimitSource>,
+    ) -> PoaPayloadBuilder<Pool, Client, GasLimitRamp<Evm>>
+    where
+        Client: Clone,
+        Pool: Clone,
+        Evm: Clone,
+    {
         // In production mode, pre-allocate POA extra_data (vanity + seal placeholder).
-        // In dev mode, leave extra_data empty — blocks are unsigned and Reth's engine
+        // In dev mode, write the vanity alone — blocks are unsigned and Reth's engine
         // rejects extra_data > 32 bytes (Ethereum mainnet limit).
+        let vanity = client_vanity(&self.client_tag);
         let extra_data = if self.dev_mode {
-            Bytes::new()
+            Bytes::copy_from_slice(&vanity)
         } else {
-            Bytes::from(vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH])
+            let mut buf = Vec::with_capacity(vanity.len() + EXTRA_SEAL_LENGTH);
+            buf.extend_from_slice(&vanity);
+            buf.extend_from_slice(&[0u8; EXTRA_SEAL_LENGTH]);
+            Bytes::from(buf)
+        };
+
+        let timestamps = TimestampScheduler::new(self.chain_spec.block_period());
+
+        let evm_config = GasLimitRamp::new(evm_config, self.chain_spec.clone());
+        let evm_config = match gas_limits {
+            Some(gas_limits) => evm_config.with_gas_limit_source(gas_limits),
+            None => evm_config,
         };
 
+        let builder_config = builder_config.with_extra_data(extra_data);
         let inner = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
-            ctx.provider().clone(),
-            pool,
-            evm_config,
-            EthereumBuilderConfig::new()
-                .with_gas_limit(gas_limit)
-                .with_max_blobs_per_block(conf.max_blobs_per_block())
-                .with_extra_data(extra_data),
+            client.clone(),
+            pool.clone(),
+            evm_config.clone(),
+            builder_config.clone(),
         );
 
-        Ok(PoaPayloadBuilder {
+        PoaPayloadBuilder {
             inner,
+            pool,
             chain_spec: self.chain_spec,
             signer_manager: self.signer_manager,
             dev_mode: self.dev_mode,
-            client: ctx.provider().clone(),
+            client,
             cache,
             infinite_fund: Arc::new(self.infinite_fund),
-        })
+            withdrawal_queue: self.withdrawal_queue,
+            timestamps,
+            vanity,
+            disk_guard: self.disk_guard,
+            production_pause: self.production_pause,
+            shutdown: self.shutdown,
+            block_metadata: self.block_metadata,
+            block_metadata_hash: self.block_metadata_hash,
+            evm_config,
+            builder_config,
+            bundle_pool: self.bundle_pool,
+            sealed_bundles: Arc::default(),
+            metrics: self.metrics,
+            compression_stats: self.compression_stats,
+            fee_policy: self.fee_policy,
+            sequenced_blocks: Arc::default(),
+            signer_rotation: self.signer_rotation,
+            votes: self.votes,
+            deposit_source: self
+                .deposit_source
+                .map(|source| source.with_tracker(self.deposit_tracker)),
+            priority_senders: PrioritySenders::new(self.priority_senders),
+            #[cfg(feature = "blob-transactions")]
+            blob_transactions: self.blob_transactions,
+        }
     }
 }
@@ -0,0 +1,568 @@
+//! Flashbots-style transaction bundles.
+//!
+//! A searcher submits a [`TxBundle`]: transactions that must land in a block
+//! together, in order, or not at all. With
+//! [`PoaPayloadBuilderBuilder::with_bundle_support`](super::PoaPayloadBuilderBuilder::with_bundle_support)
+//! the payload builder places bundles at the top of every block:
+//!
+//! 1. bundles whose `[min_timestamp, max_timestamp]` window excludes the block are
+//!    left for a later block (expired ones are dropped from the pool),
+//! 2. every other bundle is simulated on the parent state plus the bundles accepted
+//!    before it; a transaction that is invalid, or that reverts without being listed
+//!    in `reverting_tx_hashes`, rejects the whole bundle and its state is discarded,
+//! 3. the accepted transactions are handed to the inner builder ahead of the pool's
+//!    best transactions, so they execute exactly as simulated.
+//!
+//! A payload is rebuilt many times for the same parent and at most one version is
+//! published, so the bundles tried in a block are only settled once a later build
+//! starts from that block ([`SealedBundles`]): they leave the pool, included or
+//! rejected, and reach the metrics once. A bundle without `max_timestamp` is thus
+//! tried in one sealed block, not forever. Blob transactions are refused, as a bundle
+//! carries no sidecars.
+
+use alloy_consensus::Transaction;
+use alloy_eips::Typed2718;
+use alloy_evm::revm::database::CacheDB;
+use alloy_evm::revm::database_interface::{DatabaseCommit, DatabaseRef};
+use alloy_evm::Evm;
+use alloy_primitives::B256;
+use reth_ethereum::{EthPrimitives, TransactionSigned};
+use reth_evm::{ConfigureEvm, EvmEnvFor};
+use reth_primitives_traits::{Recovered, SignerRecoverable};
+use reth_transaction_pool::error::InvalidPoolTransactionError;
+use reth_transaction_pool::identifier::{SenderId, TransactionId};
+use reth_transaction_pool::{
+    BestTransactions, PoolTransaction, TransactionOrigin, ValidPoolTransaction,
+};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Transactions to include atomically, in order, at the top of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxBundle {
+    /// Bundle transactions, executed in this order
+    pub transactions: Vec<TransactionSigned>,
+    /// Earliest block timestamp the bundle may be included at
+    pub min_timestamp: Option<u64>,
+    /// Latest block timestamp the bundle may be included at
+    pub max_timestamp: Option<u64>,
+    /// Transactions allowed to revert without invalidating the bundle
+    pub reverting_tx_hashes: Vec<B256>,
+}
+
+impl TxBundle {
+    /// Whether a block with `timestamp` falls inside the bundle's window.
+    #[inline]
+    pub fn is_eligible_at(&self, timestamp: u64) -> bool {
+        self.min_timestamp.is_none_or(|min| timestamp >= min)
+            && self.max_timestamp.is_none_or(|max| timestamp <= max)
+    }
+
+    /// Whether the bundle can no longer be included at or after `timestamp`.
+    #[inline]
+    pub fn is_expired_at(&self, timestamp: u64) -> bool {
+        self.max_timestamp.is_some_and(|max| timestamp > max)
+    }
+}
+
+/// Why a bundle was rejected during simulation.
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    /// The bundle has no transactions
+    #[error("Bundle is empty")]
+    Empty,
+
+    /// A transaction signature does not recover to a sender
+    #[error("Bundle transaction {0} has an invalid signature")]
+    InvalidSignature(B256),
+
+    /// Bundles carry no sidecars, so blob transactions cannot be included
+    #[error("Bundle transaction {0} is a blob transaction")]
+    BlobTransaction(B256),
+
+    /// The transaction does not fit in the block's remaining gas
+    #[error("Bundle transaction {0} exceeds the block gas limit")]
+    GasLimit(B256),
+
+    /// The EVM refused the transaction (nonce, balance, fee, ...)
+    #[error("Bundle transaction {hash} is invalid: {reason}")]
+    Invalid {
+        /// Transaction hash
+        hash: B256,
+        /// EVM error
+        reason: String,
+    },
+
+    /// The transaction reverted or halted and is not allowed to
+    #[error("Bundle transaction {0} reverted")]
+    Reverted(B256),
+}
+
+/// Outcome of simulating a block's bundles.
+#[derive(Debug, Default)]
+pub struct BundleSelection {
+    /// Transactions of the accepted bundles, in block order
+    pub transactions: Vec<Recovered<TransactionSigned>>,
+    /// Indices of the accepted bundles in the simulated slice
+    pub included: Vec<usize>,
+    /// Number of eligible bundles that were rejected
+    pub rejected: usize,
+}
+
+/// Simulate `bundles`, in order, for a block at `timestamp` with `gas_limit`.
+///
+/// Each eligible bundle runs on `parent_state` plus the state of the bundles accepted
+/// before it; a rejected bundle leaves no trace. Bundles outside their timestamp
+/// window are neither included nor counted as rejected.
+pub fn select_bundles<E, DB>(
+    evm_config: &E,
+    parent_state: DB,
+    evm_env: EvmEnvFor<E>,
+    bundles: &[TxBundle],
+    timestamp: u64,
+    gas_limit: u64,
+) -> (BundleSelection, Vec<(usize, BundleError)>)
+where
+    E: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef<Error: std::error::Error + Send + Sync + 'static> + Debug,
+{
+    let mut accepted = CacheDB::new(parent_state);
+    let mut selection = BundleSelection::default();
+    let mut errors = Vec::new();
+    let mut gas_used = 0;
+
+    for (index, bundle) in bundles.iter().enumerate() {
+        if !bundle.is_eligible_at(timestamp) {
+            continue;
+        }
+        match simulate_bundle(
+            evm_config,
+            &accepted,
+            evm_env.clone(),
+            bundle,
+            gas_limit - gas_used,
+        ) {
+            Ok(simulated) => {
+                for state in simulated.states {
+                    accepted.commit(state);
+                }
+                gas_used += simulated.gas_used;
+                selection.transactions.extend(simulated.transactions);
+                selection.included.push(index);
+            }
+            Err(err) => {
+                selection.rejected += 1;
+                errors.push((index, err));
+            }
+        }
+    }
+    (selection, errors)
+}
+
+/// Bundles tried in one sealed block.
+#[derive(Debug, Clone)]
+pub(crate) struct BundleAttempt {
+    /// Parent of the sealed block
+    parent_hash: B256,
+    /// Bundles eligible at the block's timestamp, included or rejected
+    bundles: Vec<TxBundle>,
+    included: usize,
+    rejected: usize,
+}
+
+impl BundleAttempt {
+    /// The bundles of `bundles` that `selection` tried in a block at `timestamp` on
+    /// top of `parent_hash`.
+    pub(crate) fn new(
+        parent_hash: B256,
+        mut bundles: Vec<TxBundle>,
+        timestamp: u64,
+        selection: &BundleSelection,
+    ) -> Self {
+        bundles.retain(|bundle| bundle.is_eligible_at(timestamp));
+        Self {
+            parent_hash,
+            bundles,
+            included: selection.included.len(),
+            rejected: selection.rejected,
+        }
+    }
+}
+
+/// Bundle attempts of the blocks this node sealed, keyed by block hash.
+#[derive(Debug, Default)]
+pub(crate) struct SealedBundles(Mutex<HashMap<B256, BundleAttempt>>);
+
+impl SealedBundles {
+    /// Record the bundles tried in the sealed block `block_hash`.
+    pub(crate) fn record(&self, block_hash: B256, attempt: BundleAttempt) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(block_hash, attempt);
+    }
+
+    /// Settle the block `parent_hash` a new build starts from.
+    ///
+    /// If this node sealed it, its bundles leave `pool` and the included and rejected
+    /// counts are returned. Attempts built on any other parent were not published
+    /// and are forgotten; rebuilds on `parent_hash` settle nothing more.
+    pub(crate) fn settle(
+        &self,
+        parent_hash: B256,
+        pool: &mut Vec<TxBundle>,
+    ) -> Option<(usize, usize)> {
+        let mut sealed = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let attempt = sealed.remove(&parent_hash);
+        sealed.retain(|_, attempt| attempt.parent_hash == parent_hash);
+        let attempt = attempt?;
+        pool.retain(|bundle| !attempt.bundles.contains(bundle));
+        Some((attempt.included, attempt.rejected))
+    }
+}
+
+/// A bundle that executed cleanly, with the state each transaction left behind.
+struct SimulatedBundle {
+    transactions: Vec<Recovered<TransactionSigned>>,
+    states: Vec<alloy_evm::revm::state::EvmState>,
+    gas_used: u64,
+}
+
+/// Execute `bundle` on a throwaway layer over `base`.
+///
+/// Mirrors the inner builder's per-transaction gas check: a transaction is only
+/// included while its gas limit fits in the gas the block has left.
+fn simulate_bundle<E, DB>(
+    evm_config: &E,
+    base: &CacheDB<DB>,
+    evm_env: EvmEnvFor<E>,
+    bundle: &TxBundle,
+    gas_available: u64,
+) -> Result<SimulatedBundle, BundleError>
+where
+    E: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef<Error: std::error::Error + Send + Sync + 'static> + Debug,
+{
+    if bundle.transactions.is_empty() {
+        return Err(BundleError::Empty);
+    }
+    let mut db = CacheDB::new(base);
+    let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+    let mut simulated = SimulatedBundle {
+        transactions: Vec::with_capacity(bundle.transactions.len()),
+        states: Vec::with_capacity(bundle.transactions.len()),
+        gas_used: 0,
+    };
+
+    for tx in &bundle.transactions {
+        let hash = *tx.tx_hash();
+        if tx.is_eip4844() {
+            return Err(BundleError::BlobTransaction(hash));
+        }
+        if simulated.gas_used + tx.gas_limit() > gas_available {
+            return Err(BundleError::GasLimit(hash));
+        }
+        let tx = tx
+            .clone()
+            .try_into_recovered()
+            .map_err(|_| BundleError::InvalidSignature(hash))?;
+        let outcome = evm
+            .transact(tx.as_recovered_ref())
+            .map_err(|e| BundleError::Invalid {
+                hash,
+                reason: e.to_string(),
+            })?;
+        if !outcome.result.is_success() && !bundle.reverting_tx_hashes.contains(&hash) {
+            return Err(BundleError::Reverted(hash));
+        }
+        simulated.gas_used += outcome.result.gas_used();
+        evm.db_mut().commit(outcome.state.clone());
+        simulated.states.push(outcome.state);
+        simulated.transactions.push(tx);
+    }
+    Ok(simulated)
+}
+
+/// Best-transactions iterator yielding the accepted bundle transactions before the
+/// pool's own best transactions.
+pub(crate) struct BundlesFirst<T: PoolTransaction> {
+    bundles: std::vec::IntoIter<Arc<ValidPoolTransaction<T>>>,
+    pool: Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+}
+
+impl<T: PoolTransaction> BundlesFirst<T> {
+    pub(crate) fn new(
+        bundles: Vec<Arc<ValidPoolTransaction<T>>>,
+        pool: Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+    ) -> Self {
+        Self {
+            bundles: bundles.into_iter(),
+            pool,
+        }
+    }
+}
+
+impl<T: PoolTransaction> Iterator for BundlesFirst<T> {
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.bundles.next().or_else(|| self.pool.next())
+    }
+}
+
+impl<T: PoolTransaction> BestTransactions for BundlesFirst<T> {
+    fn mark_invalid(&mut self, transaction: &Self::Item, kind: &InvalidPoolTransactionError) {
+        self.pool.mark_invalid(transaction, kind)
+    }
+
+    fn no_updates(&mut self) {
+        self.pool.no_updates()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.pool.set_skip_blobs(skip_blobs)
+    }
+}
+
+/// Wrap accepted bundle transactions as pool transactions for the inner builder.
+///
+/// Each gets a sender id from the top of the id space, so marking one invalid never
+/// touches a real pool sender. Returns `None` if a transaction has no pool form.
+pub(crate) fn into_pool_transactions<T>(
+    transactions: Vec<Recovered<TransactionSigned>>,
+) -> Option<Vec<Arc<ValidPoolTransaction<T>>>>
+where
+    T: PoolTransaction<Consensus = TransactionSigned>,
+{
+    let now = Instant::now();
+    transactions
+        .into_iter()
+        .enumerate()
+        .map(|(i, tx)| {
+            let transaction_id =
+                TransactionId::new(SenderId::from(u64::MAX - i as u64), tx.nonce());
+            let transaction = T::try_from_consensus(tx).ok()?;
+            Some(Arc::new(ValidPoolTransaction {
+                transaction,
+                transaction_id,
+                propagate: false,
+                timestamp: now,
+                origin: TransactionOrigin::Private,
+                authority_ids: None,
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::PoaChainSpec;
+    use crate::evm::PoaEvmFactory;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_evm::revm::bytecode::Bytecode;
+    use alloy_evm::revm::database::InMemoryDB;
+    use alloy_evm::revm::state::AccountInfo;
+    use alloy_primitives::{address, Address, Bytes, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_chainspec::EthChainSpec;
+    use reth_ethereum::node::EthEvmConfig;
+
+    const RECIPIENT: Address = address!("000000000000000000000000000000000000beef");
+    const REVERTER: Address = address!("000000000000000000000000000000000000dead");
+    const GAS_PRICE: u128 = 10_000_000_000;
+    const GAS_LIMIT: u64 = 30_000_000;
+
+    /// `revert(0, 0)`
+    const REVERTS: &[u8] = &[0x60, 0x00, 0x60, 0x00, 0xfd];
+
+    fn evm_config(chain: &PoaChainSpec) -> EthEvmConfig<reth_chainspec::ChainSpec, PoaEvmFactory> {
+        EthEvmConfig::new_with_evm_factory(chain.inner().clone(), PoaEvmFactory::default())
+    }
+
+    fn parent_state(chain: &PoaChainSpec) -> InMemoryDB {
+        let mut db = chain.genesis_alloc_as_revm_state();
+        db.insert_account_info(
+            REVERTER,
+            AccountInfo {
+                code: Some(Bytecode::new_raw(Bytes::from_static(REVERTS))),
+                ..Default::default()
+            },
+        );
+        db
+    }
+
+    fn tx(chain: &PoaChainSpec, key: usize, nonce: u64, to: Address) -> TransactionSigned {
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let tx = TxLegacy {
+            chain_id: Some(chain.chain().id()),
+            nonce,
+            gas_price: GAS_PRICE,
+            gas_limit: 50_000,
+            to: TxKind::Call(to),
+            value: U256::from(1_000),
+            ..Default::default()
+        };
+        let sig = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TransactionSigned::Legacy(tx.into_signed(sig))
+    }
+
+    fn bundle(transactions: Vec<TransactionSigned>) -> TxBundle {
+        TxBundle {
+            transactions,
+            ..Default::default()
+        }
+    }
+
+    fn select(chain: &PoaChainSpec, bundles: &[TxBundle]) -> BundleSelection {
+        select_with_gas(chain, bundles, GAS_LIMIT)
+    }
+
+    fn select_with_gas(chain: &PoaChainSpec, bundles: &[TxBundle], gas: u64) -> BundleSelection {
+        let config = evm_config(chain);
+        let env = config.evm_env(chain.genesis_header()).unwrap();
+        let timestamp = chain.genesis_header().timestamp;
+        select_bundles(&config, parent_state(chain), env, bundles, timestamp, gas).0
+    }
+
+    fn hashes(selection: &BundleSelection) -> Vec<B256> {
+        selection
+            .transactions
+            .iter()
+            .map(|tx| *tx.tx_hash())
+            .collect()
+    }
+
+    #[test]
+    fn test_bundle_included_in_order() {
+        let chain = PoaChainSpec::dev_chain();
+        let txs = vec![tx(&chain, 0, 0, RECIPIENT), tx(&chain, 0, 1, RECIPIENT)];
+        let selection = select(&chain, &[bundle(txs.clone())]);
+
+        assert_eq!(selection.included, vec![0]);
+        assert_eq!(selection.rejected, 0);
+        assert_eq!(
+            hashes(&selection),
+            vec![*txs[0].tx_hash(), *txs[1].tx_hash()]
+        );
+    }
+
+    #[test]
+    fn test_failing_bundle_is_skipped_entirely() {
+        let chain = PoaChainSpec::dev_chain();
+        // Nonce 5 is invalid after nonce 0: the valid first transaction must go too.
+        let broken = bundle(vec![
+            tx(&chain, 0, 0, RECIPIENT),
+            tx(&chain, 0, 5, RECIPIENT),
+        ]);
+        // Its discarded state must not leak: nonce 0 is still the next nonce.
+        let retry = bundle(vec![tx(&chain, 0, 0, RECIPIENT)]);
+        let selection = select(&chain, &[broken, retry.clone()]);
+
+        assert_eq!(selection.included, vec![1]);
+        assert_eq!(selection.rejected, 1);
+        assert_eq!(hashes(&selection), vec![*retry.transactions[0].tx_hash()]);
+    }
+
+    #[test]
+    fn test_later_bundles_see_accepted_state() {
+        let chain = PoaChainSpec::dev_chain();
+        let first = bundle(vec![tx(&chain, 0, 0, RECIPIENT)]);
+        let replayed = bundle(vec![tx(&chain, 0, 0, RECIPIENT)]);
+        let next = bundle(vec![tx(&chain, 0, 1, RECIPIENT)]);
+        let selection = select(&chain, &[first, replayed, next]);
+
+        assert_eq!(selection.included, vec![0, 2]);
+        assert_eq!(selection.rejected, 1);
+    }
+
+    #[test]
+    fn test_revert_rejects_unless_allowed() {
+        let chain = PoaChainSpec::dev_chain();
+        let reverting = tx(&chain, 1, 0, REVERTER);
+        let txs = vec![tx(&chain, 0, 0, RECIPIENT), reverting.clone()];
+
+        let selection = select(&chain, &[bundle(txs.clone())]);
+        assert!(selection.included.is_empty());
+        assert_eq!(selection.rejected, 1);
+
+        let allowed = TxBundle {
+            reverting_tx_hashes: vec![*reverting.tx_hash()],
+            ..bundle(txs)
+        };
+        let selection = select(&chain, &[allowed]);
+        assert_eq!(selection.included, vec![0]);
+        assert_eq!(selection.transactions.len(), 2);
+    }
+
+    #[test]
+    fn test_bundle_over_gas_limit_rejected() {
+        let chain = PoaChainSpec::dev_chain();
+        let txs = vec![tx(&chain, 0, 0, RECIPIENT), tx(&chain, 0, 1, RECIPIENT)];
+        // Room for the first transaction's 50k gas limit, not the second's.
+        let selection = select_with_gas(&chain, &[bundle(txs)], 80_000);
+        assert!(selection.transactions.is_empty());
+        assert_eq!(selection.rejected, 1);
+    }
+
+    #[test]
+    fn test_sealed_bundles_settle_once_per_parent() {
+        let chain = PoaChainSpec::dev_chain();
+        let now = chain.genesis_header().timestamp;
+        let parent = B256::repeat_byte(1);
+        let included = bundle(vec![tx(&chain, 0, 0, RECIPIENT)]);
+        let failing = bundle(vec![tx(&chain, 0, 9, RECIPIENT)]);
+        let deferred = TxBundle {
+            min_timestamp: Some(now + 1),
+            ..bundle(vec![tx(&chain, 1, 0, RECIPIENT)])
+        };
+        let mut pool = vec![included.clone(), failing, deferred.clone()];
+        let selection = select(&chain, &pool);
+        let sealed = SealedBundles::default();
+
+        // Two versions of the same block; the second is the one built upon.
+        let attempt = BundleAttempt::new(parent, pool.clone(), now, &selection);
+        sealed.record(B256::repeat_byte(2), attempt.clone());
+        sealed.record(B256::repeat_byte(3), attempt);
+
+        // Rebuilding on the same parent settles nothing.
+        assert_eq!(sealed.settle(parent, &mut pool), None);
+        assert_eq!(pool.len(), 3);
+
+        // Building on the published version drops the bundles it tried, included or
+        // not, and counts them once. The deferred bundle was not tried and stays.
+        assert_eq!(sealed.settle(B256::repeat_byte(3), &mut pool), Some((1, 1)));
+        assert_eq!(pool, vec![deferred]);
+        assert_eq!(sealed.settle(B256::repeat_byte(3), &mut pool), None);
+        // The unpublished version is forgotten.
+        assert_eq!(sealed.settle(B256::repeat_byte(2), &mut pool), None);
+        assert!(!pool.contains(&included));
+    }
+
+    #[test]
+    fn test_timestamp_window() {
+        let chain = PoaChainSpec::dev_chain();
+        let now = chain.genesis_header().timestamp;
+        let future = TxBundle {
+            min_timestamp: Some(now + 1),
+            ..bundle(vec![tx(&chain, 0, 0, RECIPIENT)])
+        };
+        let selection = select(&chain, &[future.clone(), TxBundle::default()]);
+        // Out-of-window bundles are deferred, not rejected; empty bundles are rejected.
+        assert!(selection.included.is_empty());
+        assert_eq!(selection.rejected, 1);
+
+        assert!(future.is_eligible_at(now + 1));
+        assert!(!future.is_expired_at(now + 1));
+        let past = TxBundle {
+            max_timestamp: Some(now),
+            ..future
+        };
+        assert!(!past.is_eligible_at(now + 1));
+        assert!(past.is_expired_at(now + 1));
+    }
+}
@@ -0,0 +1,110 @@
+//! Block compressibility statistics.
+//!
+//! Block bodies cross the P2P network on every block, so their size bounds how
+//! fast blocks propagate. With
+//! [`PoaPayloadBuilderBuilder::with_compression_stats`](super::PoaPayloadBuilderBuilder::with_compression_stats)
+//! the payload builder RLP-encodes the body of every block it sealed, compresses it
+//! with zstd and records both sizes in `ChainMetrics`, showing how much a
+//! compressing transport (or archive) would save for the chain's actual workload.
+//! A block is measured once, when the next build starts from it; payloads rebuilt
+//! but never published are not. The totals are part of the metrics snapshot.
+
+use reth_ethereum::BlockBody;
+
+/// zstd level used for the estimate (zstd's default trade-off).
+pub const COMPRESSION_LEVEL: i32 = 3;
+
+/// Raw and compressed size of one block body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// RLP-encoded body size
+    pub raw_bytes: u64,
+    /// zstd-compressed size of the encoding
+    pub compressed_bytes: u64,
+}
+
+impl CompressionStats {
+    /// Raw size / compressed size; above 1.0 the body compresses.
+    #[inline]
+    pub fn ratio(&self) -> f64 {
+        if self.compressed_bytes == 0 {
+            return 0.0;
+        }
+        self.raw_bytes as f64 / self.compressed_bytes as f64
+    }
+}
+
+/// RLP-encode `body` and compress it at [`COMPRESSION_LEVEL`].
+pub fn measure_body_compression(body: &BlockBody) -> std::io::Result<CompressionStats> {
+    let raw = alloy_rlp::encode(body);
+    let compressed = zstd::bulk::compress(&raw, COMPRESSION_LEVEL)?;
+    Ok(CompressionStats {
+        raw_bytes: raw.len() as u64,
+        compressed_bytes: compressed.len() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_primitives::{keccak256, Address, Bytes, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_ethereum::TransactionSigned;
+
+    /// Body of `count` contract calls with calldata `data(nonce)`.
+    fn body(count: u64, data: impl Fn(u64) -> Bytes) -> BlockBody {
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[0].parse().unwrap();
+        let transactions = (0..count)
+            .map(|nonce| {
+                let tx = TxLegacy {
+                    chain_id: Some(9323310),
+                    nonce,
+                    gas_price: 1_000_000_000,
+                    gas_limit: 200_000,
+                    to: TxKind::Call(Address::with_last_byte(0xcc)),
+                    value: U256::ZERO,
+                    input: data(nonce),
+                };
+                let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                TransactionSigned::Legacy(tx.into_signed(signature))
+            })
+            .collect();
+        BlockBody {
+            transactions,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compression_ratio_by_workload() {
+        let empty = measure_body_compression(&BlockBody::default()).unwrap();
+
+        // `compute(uint256 iterations)`: selector plus a mostly-zero word.
+        let arithmetic = body(100, |_| {
+            let mut data = vec![0xa4, 0x44, 0x2f, 0x1b];
+            data.extend(U256::from(1_000).to_be_bytes::<32>());
+            data.into()
+        });
+        let arithmetic = measure_body_compression(&arithmetic).unwrap();
+
+        // 512 bytes of pseudo-random calldata per transaction.
+        let random = body(100, |nonce| {
+            (0..16u64)
+                .flat_map(|chunk| keccak256((nonce << 8 | chunk).to_be_bytes()).0)
+                .collect::<Vec<u8>>()
+                .into()
+        });
+        let random = measure_body_compression(&random).unwrap();
+
+        // An empty body is a few bytes: the zstd frame header outweighs it.
+        assert!(empty.ratio() < 1.0, "{empty:?}");
+        // Repeated call targets and calldata compress; only the signatures remain.
+        assert!(arithmetic.ratio() > 1.5, "{arithmetic:?}");
+        // Random calldata barely compresses.
+        assert!(random.ratio() < 1.1, "{random:?}");
+        assert!(random.raw_bytes > arithmetic.raw_bytes);
+    }
+}
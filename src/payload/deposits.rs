@@ -0,0 +1,522 @@
+//! L1→L2 deposits for L2 deployments.
+//!
+//! When the chain runs as an L2, a bridge contract on L1 emits deposit events that
+//! must be relayed as transactions on L2. A [`CrossChainMessageExtractor`] reads
+//! them (from an L1 RPC, an indexer, ...) and turns each into a [`DepositTx`]: the
+//! L2 transaction crediting the deposit, signed by the bridge relayer. With
+//! [`PoaPayloadBuilderBuilder::with_cross_chain_message_extractor`](super::PoaPayloadBuilderBuilder::with_cross_chain_message_extractor)
+//! the payload builder processes one L1 block per L2 block and places its deposits
+//! at the top of the block, ahead of searcher bundles and pool transactions.
+//!
+//! Which L1 block comes next is tracked from canonical blocks only, like the
+//! withdrawal queue's cursors (see `payload::withdrawals`). A sealed block records
+//! the L1 block it processed; the cursor moves past it once a build starts on that
+//! block, i.e. once it became the chain head ([`DepositTracker::settle`]). Payloads
+//! that were built but never published consume nothing. Blocks sealed elsewhere
+//! carry the cursor of their parent: an L2 chain has one sequencer relaying the
+//! deposits. With a state file the cursors survive restarts.
+//!
+//! The extractor is queried once per parent: rebuilding a payload for the same
+//! parent reuses the deposits read for the first build. Deposits the block leaves
+//! out (invalid nonce, insufficient relayer balance, ...) are reported with their
+//! L1 position and not retried; the cursor still moves past their L1 block.
+
+use alloy_primitives::B256;
+use reth_ethereum::TransactionSigned;
+use reth_primitives_traits::Recovered;
+use reth_tracing::tracing::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::{fs, io};
+
+/// How many recent blocks' L1 cursors to retain (reorg depth headroom).
+const TRACKER_RETAINED_BLOCKS: usize = 256;
+
+/// Failure loading the persisted L1 deposit cursors.
+#[derive(Debug, thiserror::Error)]
+pub enum DepositStateError {
+    /// Reading the state file failed
+    #[error("Deposit state I/O: {0}")]
+    Io(#[from] io::Error),
+    /// The state file is not a list of cursors
+    #[error("Invalid deposit state: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// An L1 bridge deposit relayed as an L2 transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositTx {
+    /// L1 block the deposit event was emitted in
+    pub l1_block: u64,
+    /// Index of the deposit event among that block's logs
+    pub log_index: u64,
+    /// L2 transaction crediting the deposit, signed by the bridge relayer
+    pub transaction: Recovered<TransactionSigned>,
+}
+
+impl DepositTx {
+    /// Hash of the L2 transaction.
+    #[inline]
+    pub fn tx_hash(&self) -> B256 {
+        *self.transaction.tx_hash()
+    }
+}
+
+/// Source of the deposits emitted by the L1 bridge contract.
+pub trait CrossChainMessageExtractor: Send + Sync {
+    /// Deposits emitted in L1 block `l1_block`, in log order.
+    fn extract_deposits(&self, l1_block: u64) -> Vec<DepositTx>;
+
+    /// First L1 block to process, e.g. the bridge deployment block.
+    fn first_l1_block(&self) -> u64 {
+        0
+    }
+
+    /// Latest L1 block safe to process (final or deep enough); `None` = no limit.
+    ///
+    /// The builder waits for later L1 blocks instead of skipping them.
+    fn l1_head(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// L1 cursor after one canonical block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct DepositCursor {
+    number: u64,
+    hash: B256,
+    /// Next L1 block to process in the child of this block.
+    next: u64,
+}
+
+/// L1 block processed by a block sealed here, until it becomes a parent.
+#[derive(Debug, Clone, Copy)]
+struct SealedDeposits {
+    parent_hash: B256,
+    l1_block: u64,
+}
+
+/// Tracks the L1 cursor after each recent canonical block.
+#[derive(Debug, Default)]
+pub struct DepositTracker {
+    /// block number → cursor after that block, with the block's hash.
+    cursors: Mutex<BTreeMap<u64, DepositCursor>>,
+    /// Blocks sealed here → L1 block they processed.
+    sealed: Mutex<HashMap<B256, SealedDeposits>>,
+    /// File the cursors are saved to after every update; None = memory only.
+    path: Option<PathBuf>,
+}
+
+impl DepositTracker {
+    /// Create an empty, in-memory tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracker persisted at `path`, resuming from the cursors saved there.
+    ///
+    /// A missing file starts empty; it is written on the first settled block.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, DepositStateError> {
+        let path = path.into();
+        let cursors = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Vec<DepositCursor>>(&bytes)?
+                .into_iter()
+                .map(|cursor| (cursor.number, cursor))
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self {
+            cursors: Mutex::new(cursors),
+            sealed: Mutex::default(),
+            path: Some(path),
+        })
+    }
+
+    /// Next L1 block to process in the child of canonical block `block_hash`, or None
+    /// if that block has not been settled.
+    pub fn next_l1_block_after(&self, block_number: u64, block_hash: B256) -> Option<u64> {
+        self.cursors
+            .lock()
+            .expect("deposit tracker lock")
+            .get(&block_number)
+            .filter(|cursor| cursor.hash == block_hash)
+            .map(|cursor| cursor.next)
+    }
+
+    /// Record that block `block_hash`, sealed here on `parent_hash`, processed
+    /// `l1_block`. It only counts once a build starts on it.
+    pub fn record_sealed(&self, block_hash: B256, parent_hash: B256, l1_block: u64) {
+        self.sealed.lock().expect("deposit tracker lock").insert(
+            block_hash,
+            SealedDeposits {
+                parent_hash,
+                l1_block,
+            },
+        );
+    }
+
+    /// Record the cursor after the canonical block `parent_hash` a new build starts
+    /// from; its own parent is `grandparent_hash`.
+    ///
+    /// The cursor moves past the L1 block the parent processed if it was sealed
+    /// here, and otherwise continues from the grandparent (the latest recorded
+    /// cursor below if the grandparent was never settled). Blocks sealed on any
+    /// other parent were not published and are forgotten. Settling the same parent
+    /// again changes nothing.
+    pub fn settle(&self, parent_number: u64, parent_hash: B256, grandparent_hash: B256) {
+        let processed = {
+            let mut sealed = self.sealed.lock().expect("deposit tracker lock");
+            let processed = sealed.remove(&parent_hash).map(|block| block.l1_block);
+            sealed.retain(|_, block| block.parent_hash == parent_hash);
+            processed
+        };
+        let mut cursors = self.cursors.lock().expect("deposit tracker lock");
+        if cursors
+            .get(&parent_number)
+            .is_some_and(|cursor| cursor.hash == parent_hash)
+        {
+            return;
+        }
+        let previous = parent_number.checked_sub(1).and_then(|number| {
+            cursors
+                .get(&number)
+                .filter(|cursor| cursor.hash == grandparent_hash)
+                .or_else(|| {
+                    cursors
+                        .range(..number)
+                        .next_back()
+                        .map(|(_, cursor)| cursor)
+                })
+                .map(|cursor| cursor.next)
+        });
+        let next = match (processed, previous) {
+            (Some(l1_block), previous) => (l1_block + 1).max(previous.unwrap_or(0)),
+            (None, Some(previous)) => previous,
+            (None, None) => return,
+        };
+        cursors.insert(
+            parent_number,
+            DepositCursor {
+                number: parent_number,
+                hash: parent_hash,
+                next,
+            },
+        );
+        cursors.retain(|&number, _| number <= parent_number);
+        while cursors.len() > TRACKER_RETAINED_BLOCKS {
+            cursors.pop_first();
+        }
+        self.persist(&cursors);
+    }
+
+    /// Save `cursors` to the state file, through a temporary file so a crash never
+    /// leaves a truncated one behind. A failed write is logged; the in-memory cursors
+    /// stay authoritative until the next save.
+    fn persist(&self, cursors: &BTreeMap<u64, DepositCursor>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(err) = write_cursors(path, cursors) {
+            warn!(
+                target: "poa::payload",
+                path = %path.display(),
+                %err,
+                "Failed to save the deposit state"
+            );
+        }
+    }
+}
+
+fn write_cursors(
+    path: &Path,
+    cursors: &BTreeMap<u64, DepositCursor>,
+) -> Result<(), DepositStateError> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(
+        &tmp,
+        serde_json::to_vec(&cursors.values().collect::<Vec<_>>())?,
+    )?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Deposits read for the children of one parent.
+#[derive(Debug)]
+struct PendingDeposits {
+    parent_hash: B256,
+    /// L1 block processed and its deposits; None while L1 has not reached it.
+    deposits: Option<(u64, Vec<DepositTx>)>,
+}
+
+/// Deposit extractor plus the L1 cursors of recent canonical blocks.
+#[derive(Clone)]
+pub struct DepositSource {
+    extractor: Arc<dyn CrossChainMessageExtractor>,
+    tracker: Arc<DepositTracker>,
+    /// Deposits of the latest parent built on, reused by its rebuilds.
+    pending: Arc<Mutex<Option<PendingDeposits>>>,
+}
+
+impl DepositSource {
+    /// Relay the deposits found by `extractor`, tracking the cursor in memory.
+    pub fn new(extractor: Arc<dyn CrossChainMessageExtractor>) -> Self {
+        Self {
+            extractor,
+            tracker: Arc::new(DepositTracker::new()),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Track the L1 cursor in `tracker`, e.g. one persisted with
+    /// [`DepositTracker::open`].
+    pub fn with_tracker(mut self, tracker: Arc<DepositTracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
+
+    /// The L1 cursors.
+    pub fn tracker(&self) -> &Arc<DepositTracker> {
+        &self.tracker
+    }
+
+    /// Next L1 block to process in the child of the settled block `parent_hash`.
+    pub fn next_l1_block_after(&self, parent_number: u64, parent_hash: B256) -> u64 {
+        self.tracker
+            .next_l1_block_after(parent_number, parent_hash)
+            .unwrap_or_else(|| self.extractor.first_l1_block())
+    }
+
+    /// L1 block processed by the child of the canonical block `parent_hash` (child of
+    /// `grandparent_hash`) and its deposits, after settling the parent.
+    ///
+    /// The extractor is queried on the first call for a parent only. `None` while
+    /// that L1 block is past the extractor's
+    /// [`l1_head`](CrossChainMessageExtractor::l1_head).
+    pub fn deposits_after(
+        &self,
+        parent_number: u64,
+        parent_hash: B256,
+        grandparent_hash: B256,
+    ) -> Option<(u64, Vec<DepositTx>)> {
+        let mut pending = self.pending.lock().expect("deposit source lock");
+        if let Some(pending) = pending.as_ref().filter(|p| p.parent_hash == parent_hash) {
+            return pending.deposits.clone();
+        }
+        self.tracker
+            .settle(parent_number, parent_hash, grandparent_hash);
+        let l1_block = self.next_l1_block_after(parent_number, parent_hash);
+        let deposits = match self.extractor.l1_head() {
+            Some(head) if l1_block > head => None,
+            _ => Some((l1_block, self.extractor.extract_deposits(l1_block))),
+        };
+        *pending = Some(PendingDeposits {
+            parent_hash,
+            deposits: deposits.clone(),
+        });
+        deposits
+    }
+
+    /// Record that block `block_hash`, sealed here on `parent_hash`, processed
+    /// `l1_block`.
+    pub fn record_sealed(&self, block_hash: B256, parent_hash: B256, l1_block: u64) {
+        self.tracker
+            .record_sealed(block_hash, parent_hash, l1_block);
+    }
+}
+
+impl std::fmt::Debug for DepositSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DepositSource")
+            .field("tracker", &self.tracker)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Deposits missing from a block whose transaction hashes are `included`.
+pub(crate) fn left_out<'a>(
+    deposits: &'a [DepositTx],
+    included: &'a HashSet<B256>,
+) -> impl Iterator<Item = &'a DepositTx> + 'a {
+    deposits
+        .iter()
+        .filter(|deposit| !included.contains(&deposit.tx_hash()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_primitives_traits::SignerRecoverable;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// Transfer signed with dev key `key`.
+    fn transfer(key: usize, nonce: u64) -> Recovered<TransactionSigned> {
+        let tx = TxLegacy {
+            chain_id: None,
+            nonce,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::with_last_byte(0xbe)),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TransactionSigned::Legacy(tx.into_signed(signature))
+            .try_into_recovered()
+            .unwrap()
+    }
+
+    /// Two deposits in every L1 block from 100 on, up to L1 block `head`; counts the
+    /// extractor queries.
+    #[derive(Default)]
+    struct MockBridge {
+        head: AtomicU64,
+        queries: AtomicUsize,
+    }
+
+    impl MockBridge {
+        fn with_head(head: u64) -> Arc<Self> {
+            let bridge = Self::default();
+            bridge.head.store(head, Ordering::Relaxed);
+            Arc::new(bridge)
+        }
+    }
+
+    impl CrossChainMessageExtractor for MockBridge {
+        fn extract_deposits(&self, l1_block: u64) -> Vec<DepositTx> {
+            self.queries.fetch_add(1, Ordering::Relaxed);
+            (0..2)
+                .map(|log_index| DepositTx {
+                    l1_block,
+                    log_index,
+                    transaction: transfer(0, l1_block * 2 + log_index),
+                })
+                .collect()
+        }
+
+        fn first_l1_block(&self) -> u64 {
+            100
+        }
+
+        fn l1_head(&self) -> Option<u64> {
+            Some(self.head.load(Ordering::Relaxed))
+        }
+    }
+
+    fn hash(n: u8) -> B256 {
+        B256::repeat_byte(n)
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("poa-dep-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("l1_deposits.json")
+    }
+
+    #[test]
+    fn test_rebuilds_reuse_the_parent_deposits() {
+        let bridge = MockBridge::with_head(200);
+        let source = DepositSource::new(bridge.clone());
+
+        let (l1_block, deposits) = source.deposits_after(0, hash(0), B256::ZERO).unwrap();
+        assert_eq!((l1_block, deposits.len()), (100, 2));
+        // Rebuilds of block 1 do not query L1 again.
+        for _ in 0..3 {
+            assert_eq!(
+                source.deposits_after(0, hash(0), B256::ZERO).unwrap().0,
+                100
+            );
+        }
+        assert_eq!(bridge.queries.load(Ordering::Relaxed), 1);
+
+        // A new parent does.
+        source.record_sealed(hash(1), hash(0), 100);
+        assert_eq!(source.deposits_after(1, hash(1), hash(0)).unwrap().0, 101);
+        assert_eq!(bridge.queries.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_cursor_advances_on_canonical_blocks_only() {
+        let source = DepositSource::new(MockBridge::with_head(200));
+        assert_eq!(
+            source.deposits_after(0, hash(0), B256::ZERO).unwrap().0,
+            100
+        );
+
+        // Two payloads were sealed for block 1; only 0x1b was published.
+        source.record_sealed(hash(0x1a), hash(0), 100);
+        source.record_sealed(hash(0x1b), hash(0), 100);
+        assert_eq!(
+            source.deposits_after(1, hash(0x1b), hash(0)).unwrap().0,
+            101
+        );
+        assert_eq!(source.tracker().sealed.lock().unwrap().len(), 0);
+
+        // Block 2 was sealed here but lost to a foreign block 2': L1 block 101 is
+        // processed again on top of it.
+        source.record_sealed(hash(0x2a), hash(0x1b), 101);
+        assert_eq!(
+            source.deposits_after(2, hash(0x2b), hash(0x1b)).unwrap().0,
+            101
+        );
+        source.record_sealed(hash(0x3a), hash(0x2b), 101);
+        assert_eq!(
+            source.deposits_after(3, hash(0x3a), hash(0x2b)).unwrap().0,
+            102
+        );
+    }
+
+    #[test]
+    fn test_cursor_waits_for_l1_head() {
+        let bridge = MockBridge::with_head(100);
+        let source = DepositSource::new(bridge.clone());
+        source.deposits_after(0, hash(0), B256::ZERO).unwrap();
+        source.record_sealed(hash(1), hash(0), 100);
+
+        // L1 block 101 is past the head: no deposits, and the cursor waits.
+        assert!(source.deposits_after(1, hash(1), hash(0)).is_none());
+        bridge.head.store(101, Ordering::Relaxed);
+        assert_eq!(source.deposits_after(2, hash(2), hash(1)).unwrap().0, 101);
+    }
+
+    #[test]
+    fn test_cursors_survive_restart() {
+        let path = temp_file("restart");
+        let tracker = Arc::new(DepositTracker::open(&path).unwrap());
+        let source = DepositSource::new(MockBridge::with_head(200)).with_tracker(tracker);
+        source.deposits_after(0, hash(0), B256::ZERO).unwrap();
+        source.record_sealed(hash(1), hash(0), 100);
+        source.deposits_after(1, hash(1), hash(0)).unwrap();
+
+        let reopened = DepositTracker::open(&path).unwrap();
+        assert_eq!(reopened.next_l1_block_after(1, hash(1)), Some(101));
+        // Unknown blocks continue from the latest recorded cursor below them.
+        reopened.settle(5, hash(5), hash(4));
+        assert_eq!(reopened.next_l1_block_after(5, hash(5)), Some(101));
+
+        fs::write(&path, b"not json").unwrap();
+        assert!(matches!(
+            DepositTracker::open(&path),
+            Err(DepositStateError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_left_out_deposits_are_reported() {
+        let bridge = MockBridge::with_head(200);
+        let deposits = bridge.extract_deposits(100);
+        let included: HashSet<B256> = [deposits[1].tx_hash(), *transfer(1, 0).tx_hash()].into();
+
+        let missing: Vec<_> = left_out(&deposits, &included).collect();
+        assert_eq!(missing, [&deposits[0]]);
+    }
+}
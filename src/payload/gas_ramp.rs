@@ -0,0 +1,219 @@
+//! Building blocks on the gas limit ramp.
+//!
+//! Inside the ramp (see
+//! [`PoaChainSpec::with_max_gas_per_block_ramp`](crate::chainspec::PoaChainSpec::with_max_gas_per_block_ramp))
+//! consensus accepts gas limit steps up to
+//! [`ramp_target`](crate::chainspec::gas_ramp::ramp_target) toward the ChainConfig gas
+//! limit of the parent state. Reth's Ethereum builder only moves the gas limit by the
+//! EIP-1559 drift, so [`GasLimitRamp`] wraps the EVM configuration and sets the gas
+//! limit of the next block's environment to the ramp step instead. Every block built
+//! from that environment, bundle simulations included, then carries that gas limit.
+//!
+//! The wrapped configuration derives the base fee from reth's chain spec, which knows
+//! neither the chain's gas target nor a
+//! [`base_fee_override`](crate::chainspec::PoaChainSpec::with_base_fee_override), so
+//! the environment takes it from [`PoaChainSpec`]'s `next_block_base_fee`, the value
+//! consensus expects. Transactions are charged that fee and the assembled header
+//! carries it; nothing is rewritten after execution.
+
+use crate::chainspec::PoaChainSpec;
+use crate::consensus::GasLimitSource;
+use alloy_consensus::Header;
+use reth_chainspec::EthChainSpec;
+use reth_ethereum::{Block, EthPrimitives};
+use reth_evm::{ConfigureEvm, EvmEnvFor, ExecutionCtxFor, NextBlockEnvAttributes};
+use reth_primitives_traits::{SealedBlock, SealedHeader};
+use reth_tracing::tracing::{debug, warn};
+use std::sync::Arc;
+
+/// EVM configuration whose next-block environment follows the gas limit ramp and the
+/// chain spec's base fee.
+///
+/// Without a [`GasLimitSource`] the gas limit is the wrapped configuration's.
+#[derive(Debug, Clone)]
+pub struct GasLimitRamp<E> {
+    inner: E,
+    chain_spec: Arc<PoaChainSpec>,
+    gas_limits: Option<GasLimitSource>,
+}
+
+impl<E> GasLimitRamp<E> {
+    /// Wrap `inner` for the ramp configured in `chain_spec`.
+    pub fn new(inner: E, chain_spec: Arc<PoaChainSpec>) -> Self {
+        Self {
+            inner,
+            chain_spec,
+            gas_limits: None,
+        }
+    }
+
+    /// Read the ramp target from the parent state through `gas_limits`.
+    pub fn with_gas_limit_source(mut self, gas_limits: GasLimitSource) -> Self {
+        self.gas_limits = Some(gas_limits);
+        self
+    }
+
+    /// The wrapped EVM configuration.
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Gas limit of the block built on `parent` while it lies inside the ramp.
+    fn ramp_gas_limit(&self, parent: &Header) -> Option<u64> {
+        let gas_limits = self.gas_limits.as_ref()?;
+        let number = parent.number + 1;
+        let remaining = self.chain_spec.gas_limit_ramp_remaining(number)?;
+        let Some((_, target)) = gas_limits.ramp_gas_limits(parent.hash_slow()) else {
+            warn!(
+                target: "poa::payload",
+                number,
+                "Parent state unavailable, building without the gas limit ramp"
+            );
+            return None;
+        };
+        self.chain_spec.gas_limit_ramp_target(parent.gas_limit, number, target).inspect(
+            |&gas_limit| debug!(target: "poa::payload", number, gas_limit, remaining, "Ramp step"),
+        )
+    }
+}
+
+impl<E> ConfigureEvm for GasLimitRamp<E>
+where
+    E: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+{
+    type Primitives = EthPrimitives;
+    type Error = E::Error;
+    type NextBlockEnvCtx = NextBlockEnvAttributes;
+    type BlockExecutorFactory = E::BlockExecutorFactory;
+    type BlockAssembler = E::BlockAssembler;
+
+    fn block_executor_factory(&self) -> &Self::BlockExecutorFactory {
+        self.inner.block_executor_factory()
+    }
+
+    fn block_assembler(&self) -> &Self::BlockAssembler {
+        self.inner.block_assembler()
+    }
+
+    fn evm_env(&self, header: &Header) -> Result<EvmEnvFor<Self>, Self::Error> {
+        self.inner.evm_env(header)
+    }
+
+    fn next_evm_env(
+        &self,
+        parent: &Header,
+        attributes: &NextBlockEnvAttributes,
+    ) -> Result<EvmEnvFor<Self>, Self::Error> {
+        let mut env = match self.ramp_gas_limit(parent) {
+            Some(gas_limit) => self.inner.next_evm_env(
+                parent,
+                &NextBlockEnvAttributes {
+                    gas_limit,
+                    ..attributes.clone()
+                },
+            ),
+            None => self.inner.next_evm_env(parent, attributes),
+        }?;
+        let basefee = self
+            .chain_spec
+            .next_block_base_fee(parent, attributes.timestamp);
+        if let Some(basefee) = basefee.filter(|_| !self.chain_spec.eip1559_disabled()) {
+            env.block_env.basefee = basefee;
+        }
+        Ok(env)
+    }
+
+    fn context_for_block<'a>(
+        &self,
+        block: &'a SealedBlock<Block>,
+    ) -> Result<ExecutionCtxFor<'a, Self>, Self::Error> {
+        self.inner.context_for_block(block)
+    }
+
+    fn context_for_next_block(
+        &self,
+        parent: &SealedHeader,
+        attributes: NextBlockEnvAttributes,
+    ) -> Result<ExecutionCtxFor<'_, Self>, Self::Error> {
+        self.inner.context_for_next_block(parent, attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::GasLimitReader;
+    use crate::evm::PoaEvmFactory;
+    use alloy_primitives::{Address, B256};
+    use reth_ethereum::node::EthEvmConfig;
+
+    struct Target(u64);
+
+    impl GasLimitReader for Target {
+        fn ramp_gas_limits(&self, _block_hash: B256) -> Option<(u64, u64)> {
+            Some((30_000_000, self.0))
+        }
+    }
+
+    fn attributes(gas_limit: u64) -> NextBlockEnvAttributes {
+        NextBlockEnvAttributes {
+            timestamp: 12,
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            gas_limit,
+            parent_beacon_block_root: Some(B256::ZERO),
+            withdrawals: None,
+        }
+    }
+
+    #[test]
+    fn test_next_evm_env_follows_ramp() {
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_max_gas_per_block_ramp(10));
+        let evm_config =
+            EthEvmConfig::new_with_evm_factory(chain.inner().clone(), PoaEvmFactory::default());
+        let ramp = GasLimitRamp::new(evm_config, chain.clone())
+            .with_gas_limit_source(GasLimitSource::new(Arc::new(Target(1_000_000_000))));
+        let parent = chain.genesis_header().clone();
+        let drift = parent.gas_limit + parent.gas_limit / 1024;
+
+        // First ramp block: a tenth of the way to the target, not the drift.
+        let env = ramp.next_evm_env(&parent, &attributes(drift)).unwrap();
+        let expected = chain.gas_limit_ramp_target(parent.gas_limit, 1, 1_000_000_000);
+        assert_eq!(Some(env.block_env.gas_limit), expected);
+
+        // Past the ramp the requested gas limit is kept.
+        let late = Header {
+            number: 20,
+            ..parent.clone()
+        };
+        let env = ramp.next_evm_env(&late, &attributes(drift)).unwrap();
+        assert_eq!(env.block_env.gas_limit, drift);
+    }
+
+    #[test]
+    fn test_next_evm_env_charges_pinned_base_fee() {
+        const FEE: u64 = 7_000_000_000;
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_base_fee_override(FEE, 5));
+        let evm_config =
+            EthEvmConfig::new_with_evm_factory(chain.inner().clone(), PoaEvmFactory::default());
+        let ramp = GasLimitRamp::new(evm_config, chain.clone());
+        let parent = |number, gas_used| Header {
+            number,
+            gas_used,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..chain.genesis_header().clone()
+        };
+
+        // Whatever the parent's gas used, blocks from the activation block pay the fee.
+        for gas_used in [0, 15_000_000, 30_000_000] {
+            let env = ramp
+                .next_evm_env(&parent(4, gas_used), &attributes(30_000_000))
+                .unwrap();
+            assert_eq!(env.block_env.basefee, FEE);
+        }
+        let env = ramp
+            .next_evm_env(&parent(3, 0), &attributes(30_000_000))
+            .unwrap();
+        assert_eq!(env.block_env.basefee, 875_000_000);
+    }
+}
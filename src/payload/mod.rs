@@ -7,34 +7,77 @@
 //! 2. Build extra_data with POA format (vanity + [signers at epoch] + signature)
 //! 3. Sign the block header with the appropriate signer key
 
+#[cfg(feature = "blob-transactions")]
+pub mod blobs;
 pub mod builder;
+pub mod bundles;
+pub mod compression;
+pub mod deposits;
+pub mod gas_ramp;
+pub mod priority;
+pub mod rotation;
+pub mod sequencer;
+pub mod size;
+pub mod withdrawals;
+
+pub use crate::consensus::metadata::BlockMetadata;
+#[cfg(feature = "blob-transactions")]
+pub use blobs::{validate_blob_sidecar, BlobError};
+pub use builder::{BlockMetadataProvider, PoaPayloadBuilderBuilder};
+pub use bundles::{select_bundles, BundleError, BundleSelection, TxBundle};
+pub use compression::{measure_body_compression, CompressionStats};
+pub use deposits::{
+    CrossChainMessageExtractor, DepositSource, DepositStateError, DepositTracker, DepositTx,
+};
+pub use gas_ramp::GasLimitRamp;
+pub use priority::PrioritySenders;
+pub use rotation::SignerRotation;
+pub use sequencer::{excluded_for_non_gas, FeePolicy};
+pub use withdrawals::{TrackerStateError, WithdrawalHistory, WithdrawalQueueTracker};
 
-pub use builder::PoaPayloadBuilderBuilder;
+use bundles::{BundleAttempt, BundlesFirst, SealedBundles};
+use sequencer::{BoxedBest, SealedSequencerBlocks, SequencedBlock};
+use size::BlockSizeBudget;
 
 use crate::cache::{CachedStorageReader, SharedCache};
+use crate::chainspec::rewards::{credit_index, FUND_VALIDATOR_INDEX};
 use crate::chainspec::PoaChainSpec;
-use crate::consensus::{EXTRA_SEAL_LENGTH, EXTRA_VANITY_LENGTH};
-use crate::genesis::addresses::SIGNER_REGISTRY_ADDRESS;
-use crate::metrics::PhaseTimer;
+use crate::clock::TimestampScheduler;
+use crate::consensus::metadata::{append_metadata, append_metadata_hash};
+use crate::consensus::votes::encode_vote;
+use crate::consensus::{
+    epoch_signers, PoaConsensus, VoterSet, EXTRA_SEAL_LENGTH, EXTRA_VANITY_LENGTH,
+};
+use crate::genesis::addresses::{CHAIN_CONFIG_ADDRESS, SIGNER_REGISTRY_ADDRESS};
+use crate::metrics::{ChainMetrics, PhaseTimer};
+use crate::node::disk::DiskGuard;
+use crate::node::revert::ProductionPause;
+use crate::node::shutdown::BuildShutdown;
 use crate::onchain::{read_signer_list, StateProviderStorageReader};
-use crate::output;
 use crate::signer::{BlockSealer, SignerManager};
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{Address, Bytes, B256, U256};
 use reth_basic_payload_builder::{
     BuildArguments, BuildOutcome, HeaderForPayload, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
 };
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_ethereum::evm::revm::database::StateProviderDatabase;
 use reth_ethereum::storage::StateProviderFactory;
-use reth_ethereum::EthPrimitives;
+use reth_ethereum::{Block, EthPrimitives, TransactionSigned};
 use reth_ethereum_engine_primitives::EthBuiltPayload;
 use reth_ethereum_engine_primitives::EthPayloadAttributes;
-use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
+use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_evm::{ConfigureEvm, EvmEnvFor, NextBlockEnvAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::BuiltPayload;
 use reth_primitives_traits::block::SealedBlock;
-use reth_transaction_pool::{PoolTransaction, TransactionPool};
-use std::sync::Arc;
+use reth_primitives_traits::Recovered;
+use reth_tracing::tracing::{debug, info, warn};
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 /// POA payload builder that wraps `EthereumPayloadBuilder`.
 ///
@@ -50,6 +93,8 @@ pub struct PoaPayloadBuilder<Pool, Client, EvmConfig> {
     /// The inner Ethereum payload builder that does the actual block construction.
     pub(crate) inner:
         reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Client, EvmConfig>,
+    /// Transaction pool, used to evict transactions the chain cannot include.
+    pub(crate) pool: Pool,
     /// POA chain specification with signer list, epoch, period.
     pub(crate) chain_spec: Arc<PoaChainSpec>,
     /// Signer manager with signing keys.
@@ -62,12 +107,58 @@ pub struct PoaPayloadBuilder<Pool, Client, EvmConfig> {
     pub(crate) cache: SharedCache,
     /// Addresses to credit with a withdrawal every block (infinite fund).
     pub(crate) infinite_fund: Arc<Vec<Address>>,
+    /// Queue cursors of recent canonical blocks (governance withdrawal queue).
+    pub(crate) withdrawal_queue: Arc<WithdrawalQueueTracker>,
+    /// Clamps requested header timestamps past the parent (survives clock regressions).
+    pub(crate) timestamps: TimestampScheduler,
+    /// Encoded client tag written as the vanity of every signed block.
+    pub(crate) vanity: [u8; EXTRA_VANITY_LENGTH],
+    /// Datadir free-space state; building is refused while disk space is critical.
+    pub(crate) disk_guard: DiskGuard,
+    /// Administrative stop switch, set while the chain is being reverted.
+    pub(crate) production_pause: ProductionPause,
+    /// Graceful shutdown: refuses new builds once cancelled, tracks the one in progress.
+    pub(crate) shutdown: BuildShutdown,
+    /// Which local key seals each block; blocks it assigns no key are not built.
+    pub(crate) signer_rotation: SignerRotation,
+    /// Local signer-set proposals and the canonical vote tally.
+    pub(crate) votes: VoterSet,
+    /// Metadata appended after the seal of every signed block.
+    pub(crate) block_metadata: Option<BlockMetadataProvider>,
+    /// Append only the metadata's 32-byte commitment.
+    pub(crate) block_metadata_hash: bool,
+    /// EVM configuration, used to simulate bundles before the inner build.
+    pub(crate) evm_config: EvmConfig,
+    /// Configuration of the inner builder, reused when building with bundles.
+    pub(crate) builder_config: EthereumBuilderConfig,
+    /// Searcher bundles placed atomically at the top of every block.
+    pub(crate) bundle_pool: Option<Arc<RwLock<Vec<TxBundle>>>>,
+    /// Bundles tried in the blocks sealed here, settled when one becomes a parent.
+    pub(crate) sealed_bundles: Arc<SealedBundles>,
+    /// Chain metrics receiving the bundle inclusion counters.
+    pub(crate) metrics: Option<Arc<ChainMetrics>>,
+    /// Measure and log how well every built block body compresses.
+    pub(crate) compression_stats: bool,
+    /// Fee floor on pool transactions, lifted in sequencer mode.
+    pub(crate) fee_policy: FeePolicy,
+    /// Blocks sealed here, checked once they become a parent (inclusion, compression).
+    pub(crate) sequenced_blocks: Arc<SealedSequencerBlocks>,
+    /// L1 bridge deposits placed at the very top of every block (L2 deployments).
+    pub(crate) deposit_source: Option<DepositSource>,
+    /// Senders whose pending transactions follow the leading ones, ahead of the pool.
+    pub(crate) priority_senders: PrioritySenders,
+    /// Re-validate blob sidecars against their transactions when selecting them.
+    #[cfg(feature = "blob-transactions")]
+    pub(crate) blob_transactions: bool,
 }
 
 impl<Pool, Client, EvmConfig> PayloadBuilder for PoaPayloadBuilder<Pool, Client, EvmConfig>
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
-    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
+    Client: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + WithdrawalHistory
+        + Clone,
     Pool:
         TransactionPool<Transaction: PoolTransaction<Consensus = reth_ethereum::TransactionSigned>>,
 {
@@ -78,40 +169,61 @@ where
         &self,
         mut args: BuildArguments<EthPayloadAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        // 0. Infinite-fund: inject one EIP-4895 withdrawal per whitelisted address.
-        //    Withdrawals are protocol-level balance credits — the executor's
-        //    post-execution hook adds their amount (in gwei) to each recipient's
-        //    balance. This creates value out of thin air, mirroring how mainnet
-        //    beacon-chain validator rewards work. Works on a live datadir, no
-        //    wipe required. Amount per block per address is the EIP-4895 max
-        //    (u64 gwei / 2 ≈ 9.2B ETH) so balances grow extremely fast.
-        //
-        //    Withdrawal indices are derived deterministically from the block
-        //    number (`block_number * fund_count + addr_index`) so they survive
-        //    node restarts without collision and remain monotonic across blocks.
-        if !self.infinite_fund.is_empty() {
-            const PER_BLOCK_GWEI: u64 = u64::MAX / 2;
-            let child_number = args.config.parent_header.number + 1;
-            let fund_count = self.infinite_fund.len() as u64;
-            let base_index = child_number.saturating_mul(fund_count);
-            let withdrawals = args
-                .config
-                .attributes
-                .withdrawals
-                .get_or_insert_with(Vec::new);
-            for (i, addr) in self.infinite_fund.iter().enumerate() {
-                withdrawals.push(alloy_eips::eip4895::Withdrawal {
-                    index: base_index + i as u64,
-                    validator_index: 0,
-                    address: *addr,
-                    amount: PER_BLOCK_GWEI,
-                });
-            }
+        self.check_can_build()?;
+        // Held until the sealed payload is returned, so a shutdown waits for it.
+        let _build = self
+            .shutdown
+            .begin_build()
+            .map_err(|err| PayloadBuilderError::Other(Box::new(err)))?;
+        let block_number = args.config.parent_header.number + 1;
+        if self.skipped_by_rotation(block_number) {
+            debug!(
+                target: "poa::payload",
+                block_number,
+                rotation = %self.signer_rotation,
+                "No local key selected by the signer rotation, skipping build"
+            );
+            return Ok(BuildOutcome::Cancelled);
+        }
+        let parent_hash = args.config.parent_header.hash();
+        if let Some((signer, stake)) = self.understaked_signer(block_number, parent_hash) {
+            warn!(
+                target: "poa::payload",
+                block_number,
+                %signer,
+                %stake,
+                "Signer below the minimum stake, skipping build"
+            );
+            return Ok(BuildOutcome::Cancelled);
         }
 
-        // 1. Let the inner builder construct the block (transactions, state, etc.)
+        // 0. Header timestamp (the requested one, raised past the parent if the
+        //    clock stepped backwards), then protocol withdrawals: infinite fund +
+        //    governance withdrawal queue.
+        let build_start = Instant::now();
+        let parent_number = args.config.parent_header.number;
+        args.config.attributes.timestamp = self.timestamps.next_timestamp(
+            args.config.parent_header.timestamp,
+            args.config.attributes.timestamp,
+        );
+        self.settle_sealed_block(parent_hash);
+        self.inject_withdrawals(parent_number, parent_hash, &mut args.config.attributes);
+        self.cast_vote(block_number, &mut args.config.attributes);
+
+        // 1. Let the inner builder construct the block (transactions, state, etc.),
+        //    with the next L1 block's deposits first.
+        let grandparent_hash = args.config.parent_header.parent_hash;
+        let deposits = self.pending_deposits(parent_number, parent_hash, grandparent_hash);
+        let leading = deposits
+            .iter()
+            .flat_map(|(_, deposits)| deposits)
+            .map(|deposit| deposit.transaction.clone())
+            .collect();
         let build_timer = PhaseTimer::start();
-        let outcome = self.inner.try_build(args)?;
+        let (outcome, bundle_attempt) = match &self.bundle_pool {
+            Some(bundle_pool) => self.build_with_bundles(bundle_pool, leading, args)?,
+            None => (self.build_from_pool(leading, args)?, None),
+        };
         let build_ms = build_timer.elapsed_ms();
 
         // 2. Post-process: sign the block if we have a signer
@@ -121,6 +233,9 @@ where
                 cached_reads,
             } => {
                 let signed_payload = self.sign_payload(payload, build_ms)?;
+                self.record_deposits(&signed_payload, deposits.as_ref());
+                self.record_bundle_attempt(&signed_payload, bundle_attempt);
+                self.record_sealed_block(&signed_payload, build_start);
                 Ok(BuildOutcome::Better {
                     payload: signed_payload,
                     cached_reads,
@@ -128,6 +243,9 @@ where
             }
             BuildOutcome::Freeze(payload) => {
                 let signed_payload = self.sign_payload(payload, build_ms)?;
+                self.record_deposits(&signed_payload, deposits.as_ref());
+                self.record_bundle_attempt(&signed_payload, bundle_attempt);
+                self.record_sealed_block(&signed_payload, build_start);
                 Ok(BuildOutcome::Freeze(signed_payload))
             }
             other => Ok(other),
@@ -145,27 +263,17 @@ where
         &self,
         mut config: PayloadConfig<Self::Attributes, HeaderForPayload<Self::BuiltPayload>>,
     ) -> Result<EthBuiltPayload, PayloadBuilderError> {
-        // Mirror the infinite-fund withdrawal injection from try_build so that
-        // empty payloads also credit the whitelisted addresses. Indices are
-        // deterministic from block number — same scheme as try_build.
-        if !self.infinite_fund.is_empty() {
-            const PER_BLOCK_GWEI: u64 = u64::MAX / 2;
-            let child_number = config.parent_header.number + 1;
-            let fund_count = self.infinite_fund.len() as u64;
-            let base_index = child_number.saturating_mul(fund_count);
-            let withdrawals = config
-                .attributes
-                .withdrawals
-                .get_or_insert_with(Vec::new);
-            for (i, addr) in self.infinite_fund.iter().enumerate() {
-                withdrawals.push(alloy_eips::eip4895::Withdrawal {
-                    index: base_index + i as u64,
-                    validator_index: 0,
-                    address: *addr,
-                    amount: PER_BLOCK_GWEI,
-                });
-            }
-        }
+        self.check_can_build()?;
+
+        // Mirror the timestamp clamp and withdrawal injection from try_build so that
+        // empty payloads also credit the infinite-fund addresses and drain the queue.
+        let parent_number = config.parent_header.number;
+        let parent_hash = config.parent_header.hash();
+        config.attributes.timestamp = self
+            .timestamps
+            .next_timestamp(config.parent_header.timestamp, config.attributes.timestamp);
+        self.inject_withdrawals(parent_number, parent_hash, &mut config.attributes);
+        self.cast_vote(parent_number + 1, &mut config.attributes);
         let build_timer = PhaseTimer::start();
         let payload = self.inner.build_empty_payload(config)?;
         let build_ms = build_timer.elapsed_ms();
@@ -173,10 +281,498 @@ where
     }
 }
 
+impl<Pool, Client, EvmConfig> PoaPayloadBuilder<Pool, Client, EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + WithdrawalHistory
+        + Clone,
+    Pool:
+        TransactionPool<Transaction: PoolTransaction<Consensus = reth_ethereum::TransactionSigned>>,
+{
+    /// The L1 block processed by the child of canonical block `parent_hash` and its
+    /// deposits; None without a deposit source or while L1 has not advanced.
+    fn pending_deposits(
+        &self,
+        parent_number: u64,
+        parent_hash: B256,
+        grandparent_hash: B256,
+    ) -> Option<(u64, Vec<DepositTx>)> {
+        let (l1_block, deposits) = self.deposit_source.as_ref()?.deposits_after(
+            parent_number,
+            parent_hash,
+            grandparent_hash,
+        )?;
+        if !deposits.is_empty() {
+            info!(
+                target: "poa::payload",
+                l1_block,
+                deposits = deposits.len(),
+                "Placing L1 deposits at the top of the block"
+            );
+        }
+        Some((l1_block, deposits))
+    }
+
+    /// Record the L1 block processed by the sealed `payload` and report the deposits
+    /// it left out: they were invalid on the parent state and are not retried.
+    fn record_deposits(&self, payload: &EthBuiltPayload, deposits: Option<&(u64, Vec<DepositTx>)>) {
+        let (Some(source), Some((l1_block, deposits))) = (&self.deposit_source, deposits) else {
+            return;
+        };
+        let block = payload.block();
+        let included: HashSet<B256> = block
+            .body()
+            .transactions
+            .iter()
+            .map(|tx| *tx.tx_hash())
+            .collect();
+        for deposit in deposits::left_out(deposits, &included) {
+            warn!(
+                target: "poa::payload",
+                block = block.number,
+                l1_block = deposit.l1_block,
+                log_index = deposit.log_index,
+                tx = %deposit.tx_hash(),
+                "L1 deposit left out of the block as invalid"
+            );
+        }
+        source.record_sealed(block.hash(), block.parent_hash, *l1_block);
+    }
+
+    /// Build with the pool's eligible bundles at the top of the block, after `deposits`.
+    ///
+    /// The bundles tried in the parent, if this node sealed it, are settled first:
+    /// they leave the pool and reach the metrics (see [`SealedBundles`]). Expired
+    /// bundles are dropped. The rest are simulated on the parent state (see
+    /// [`bundles`]); the accepted transactions lead the block and the pool's best
+    /// transactions follow. Without an accepted bundle this is a plain pool build.
+    /// The simulation does not see the deposits' state changes.
+    ///
+    /// Returns the bundles tried, to record once the block is sealed.
+    fn build_with_bundles(
+        &self,
+        bundle_pool: &RwLock<Vec<TxBundle>>,
+        mut deposits: Vec<Recovered<TransactionSigned>>,
+        args: BuildArguments<EthPayloadAttributes, EthBuiltPayload>,
+    ) -> Result<(BuildOutcome<EthBuiltPayload>, Option<BundleAttempt>), PayloadBuilderError> {
+        let parent = &args.config.parent_header;
+        let attributes = &args.config.attributes;
+        let timestamp = attributes.timestamp;
+        let bundles = {
+            let mut pool = bundle_pool.write().unwrap_or_else(|e| e.into_inner());
+            let settled = self.sealed_bundles.settle(parent.hash(), &mut pool);
+            if let Some(((included, rejected), metrics)) = settled.zip(self.metrics.as_ref()) {
+                metrics.record_bundles(included as u64, rejected as u64);
+            }
+            pool.retain(|bundle| !bundle.is_expired_at(timestamp));
+            pool.clone()
+        };
+        if bundles.is_empty() {
+            return Ok((self.build_from_pool(deposits, args)?, None));
+        }
+
+        let evm_env = self.next_evm_env(&args)?;
+        let gas_limit = evm_env.block_env.gas_limit;
+        let parent_state = self.client.state_by_block_hash(parent.hash())?;
+        let (selection, rejections) = select_bundles(
+            &self.evm_config,
+            StateProviderDatabase::new(parent_state),
+            evm_env,
+            &bundles,
+            timestamp,
+            gas_limit,
+        );
+        for (bundle, err) in &rejections {
+            warn!(target: "poa::payload", bundle, %err, "Bundle rejected");
+        }
+        let attempt = BundleAttempt::new(parent.hash(), bundles, timestamp, &selection);
+        if selection.transactions.is_empty() {
+            return Ok((self.build_from_pool(deposits, args)?, Some(attempt)));
+        }
+
+        info!(
+            target: "poa::payload",
+            included = selection.included.len(),
+            rejected = selection.rejected,
+            txs = selection.transactions.len(),
+            "Placing bundles at the top of the block"
+        );
+        deposits.extend(selection.transactions);
+        Ok((self.build_from_pool(deposits, args)?, Some(attempt)))
+    }
+
+    /// Remember the bundles tried in the sealed `payload`.
+    fn record_bundle_attempt(&self, payload: &EthBuiltPayload, attempt: Option<BundleAttempt>) {
+        if let Some(attempt) = attempt {
+            self.sealed_bundles.record(payload.block().hash(), attempt);
+        }
+    }
+
+    /// Build from the pool's best transactions after `leading` ones and the priority
+    /// senders' pending transactions, applying the fee policy.
+    ///
+    /// Without leading transactions, priority senders, a fee floor, a block size
+    /// limit or an access control list this is a plain inner build.
+    fn build_from_pool(
+        &self,
+        leading: Vec<Recovered<TransactionSigned>>,
+        args: BuildArguments<EthPayloadAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        if leading.is_empty()
+            && self.priority_senders.is_empty()
+            && !self.fee_policy.is_active()
+            && self.chain_spec.max_block_size_bytes().is_none()
+            && self
+                .chain_spec
+                .acl_at(args.config.parent_header.number + 1)
+                .is_none()
+            && !self.screens_blob_sidecars()
+        {
+            return self.inner.try_build(args);
+        }
+        let leading = bundles::into_pool_transactions(leading).unwrap_or_else(|| {
+            warn!(target: "poa::payload", "Leading transaction not representable in the pool");
+            Vec::new()
+        });
+        self.build_with_leading(leading, args)
+    }
+
+    /// Build with `leading` transactions, then the priority senders' pending ones,
+    /// ahead of the pool's fee-filtered best transactions.
+    ///
+    /// With a block size limit, pool transactions stop being selected once the block
+    /// is full (see [`size`]). With an access control list in force, transactions
+    /// from refused senders are left out: consensus would reject the block.
+    fn build_with_leading(
+        &self,
+        mut leading: Vec<Arc<ValidPoolTransaction<Pool::Transaction>>>,
+        args: BuildArguments<EthPayloadAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        leading.extend(self.priority_senders.pending(&self.pool));
+        let acl = self
+            .chain_spec
+            .acl_at(args.config.parent_header.number + 1)
+            .cloned();
+        if let Some(acl) = &acl {
+            leading.retain(|tx| acl.allows(&tx.sender()));
+        }
+        #[cfg(feature = "blob-transactions")]
+        if self.blob_transactions {
+            leading.retain(|tx| blobs::has_valid_sidecar(&self.pool, tx));
+        }
+        let pool = self.pool.clone();
+        #[cfg(feature = "blob-transactions")]
+        let blob_screen = self.blob_transactions.then(|| self.pool.clone());
+        let fee_policy = self.fee_policy;
+        let priority_senders = self.priority_senders.clone();
+        let size_budget = self
+            .chain_spec
+            .max_block_size_bytes()
+            .map(BlockSizeBudget::new);
+        reth_ethereum_payload_builder::default_ethereum_payload(
+            self.evm_config.clone(),
+            self.client.clone(),
+            self.pool.clone(),
+            self.builder_config.clone(),
+            args,
+            |attributes| {
+                let base_fee = attributes.basefee;
+                let best = pool.best_transactions_with_attributes(attributes);
+                let best = fee_policy.filter(priority_senders.exclude(best), base_fee);
+                let best: BoxedBest<_> = match acl {
+                    Some(acl) => Box::new(best.filter_transactions(
+                        move |tx: &Arc<ValidPoolTransaction<_>>| acl.allows(&tx.sender()),
+                    )),
+                    None => best,
+                };
+                #[cfg(feature = "blob-transactions")]
+                let best = match blob_screen {
+                    Some(pool) => blobs::filter_invalid_sidecars(pool, best),
+                    None => best,
+                };
+                let best = match size_budget {
+                    Some(budget) => budget.filter(&leading, best),
+                    None => best,
+                };
+                Box::new(BundlesFirst::new(leading, best))
+            },
+        )
+    }
+
+    /// Whether blob sidecars are re-checked during transaction selection.
+    #[inline]
+    fn screens_blob_sidecars(&self) -> bool {
+        #[cfg(feature = "blob-transactions")]
+        {
+            self.blob_transactions
+        }
+        #[cfg(not(feature = "blob-transactions"))]
+        {
+            false
+        }
+    }
+
+    /// EVM environment of the block built on `args`' parent.
+    fn next_evm_env(
+        &self,
+        args: &BuildArguments<EthPayloadAttributes, EthBuiltPayload>,
+    ) -> Result<EvmEnvFor<EvmConfig>, PayloadBuilderError> {
+        let parent = &args.config.parent_header;
+        let attributes = &args.config.attributes;
+        self.evm_config
+            .next_evm_env(
+                parent,
+                &NextBlockEnvAttributes {
+                    timestamp: attributes.timestamp,
+                    suggested_fee_recipient: attributes.suggested_fee_recipient,
+                    prev_randao: attributes.prev_randao,
+                    gas_limit: self.builder_config.gas_limit(parent.gas_limit),
+                    parent_beacon_block_root: attributes.parent_beacon_block_root,
+                    withdrawals: attributes.withdrawals.clone().map(Into::into),
+                },
+            )
+            .map_err(|err| PayloadBuilderError::Other(Box::new(err)))
+    }
+
+    /// Sequencer mode and compression stats: remember the sealed `payload` until a
+    /// build starts from it.
+    fn record_sealed_block(&self, payload: &EthBuiltPayload, built_at: Instant) {
+        if self.fee_policy.sequencer_mode || self.compression_stats {
+            self.sequenced_blocks.record(SequencedBlock {
+                block: payload.block().clone(),
+                built_at,
+            });
+        }
+    }
+
+    /// If this node sealed the parent `parent_hash`, measure how well its body
+    /// compresses and, in sequencer mode, check it left no valid transaction out.
+    ///
+    /// Runs once per sealed block, so unpublished rebuilds are never measured.
+    fn settle_sealed_block(&self, parent_hash: B256) {
+        let Some(sealed) = self.sequenced_blocks.settle(parent_hash) else {
+            return;
+        };
+        if self.compression_stats {
+            self.record_compression(&sealed.block);
+        }
+        if self.fee_policy.sequencer_mode {
+            self.check_sequencer_inclusion(sealed);
+        }
+    }
+
+    /// Sequencer mode: warn about valid pending transactions the sealed parent left
+    /// out although it had gas left for them.
+    ///
+    /// Transactions that arrived after its build started are not counted.
+    fn check_sequencer_inclusion(&self, sealed: SequencedBlock) {
+        use alloy_consensus::Transaction;
+
+        let SequencedBlock { block, built_at } = sealed;
+        let included: HashSet<B256> = block
+            .body()
+            .transactions
+            .iter()
+            .map(|tx| *tx.tx_hash())
+            .collect();
+        let gas_remaining = block
+            .header()
+            .gas_limit
+            .saturating_sub(block.header().gas_used);
+        let pending = self.pool.pending_transactions();
+        let excluded = excluded_for_non_gas(
+            pending
+                .iter()
+                .filter(|tx| tx.timestamp < built_at)
+                .map(|tx| (*tx.hash(), tx.transaction.gas_limit())),
+            &included,
+            gas_remaining,
+        );
+        if excluded > 0 {
+            warn!(
+                target: "poa::payload",
+                block_number = block.header().number,
+                excluded,
+                gas_remaining,
+                "Sequencer mode: valid transactions excluded for non-gas reasons"
+            );
+        }
+    }
+}
+
 impl<Pool, Client, EvmConfig> PoaPayloadBuilder<Pool, Client, EvmConfig>
 where
     Client: StateProviderFactory + Clone,
 {
+    /// Refuse to build while production is paused by an administrative revert, or
+    /// while the datadir is below the critical free-space threshold.
+    ///
+    /// A block committed to a full disk would fail mid-write; pausing here keeps the
+    /// node serving RPC reads until space is freed.
+    fn check_can_build(&self) -> Result<(), PayloadBuilderError> {
+        self.production_pause
+            .check_block_building()
+            .map_err(|err| PayloadBuilderError::Other(Box::new(err)))?;
+        self.disk_guard
+            .check_block_building()
+            .map_err(|err| PayloadBuilderError::Other(Box::new(err)))
+    }
+
+    /// Whether block `block_number` is left to another node: signers are configured
+    /// but the signer rotation selects none of our keys. Never in dev mode.
+    fn skipped_by_rotation(&self, block_number: u64) -> bool {
+        !self.dev_mode
+            && self.chain_spec.with_effective_signers(|signers| {
+                !signers.is_empty()
+                    && self
+                        .signer_rotation
+                        .select(block_number, signers, &self.signer_manager)
+                        .is_none()
+            })
+    }
+
+    /// The key the signer rotation selects for block `block_number` and its balance in
+    /// the parent state, if that is below [`PoaChainSpec::min_signer_stake`]:
+    /// consensus would refuse the block it seals. Never in dev mode.
+    fn understaked_signer(&self, block_number: u64, parent_hash: B256) -> Option<(Address, U256)> {
+        if self.dev_mode {
+            return None;
+        }
+        let required = self.chain_spec.min_signer_stake()?;
+        let (signer, _) = self.chain_spec.with_effective_signers(|signers| {
+            self.signer_rotation
+                .select(block_number, signers, &self.signer_manager)
+        })?;
+        let state = self.client.state_by_block_hash(parent_hash).ok()?;
+        let stake = state.account_balance(&signer).ok()?.unwrap_or_default();
+        (stake < required).then_some((signer, stake))
+    }
+
+    /// Put the sealing signer's next vote (see [`VoterSet::next_vote`]) into the
+    /// block's `mix_hash`. Epoch blocks and unsigned dev-mode blocks carry none.
+    fn cast_vote(&self, block_number: u64, attributes: &mut EthPayloadAttributes) {
+        if self.dev_mode || block_number.is_multiple_of(self.chain_spec.epoch()) {
+            return;
+        }
+        let vote = self.chain_spec.with_effective_signers(|signers| {
+            let (voter, _) =
+                self.signer_rotation
+                    .select(block_number, signers, &self.signer_manager)?;
+            self.votes
+                .next_vote(&voter, signers)
+                .map(|vote| (voter, vote))
+        });
+        if let Some((voter, (candidate, action))) = vote {
+            debug!(
+                target: "poa::payload",
+                block_number,
+                %voter,
+                %candidate,
+                ?action,
+                "Casting signer vote"
+            );
+            attributes.prev_randao = encode_vote(candidate, action);
+        }
+    }
+
+    /// Log and record the raw vs zstd-compressed size of a sealed block body.
+    fn record_compression(&self, block: &SealedBlock<Block>) {
+        match measure_body_compression(block.body()) {
+            Ok(stats) => {
+                info!(
+                    target: "poa::payload",
+                    block_number = block.header().number,
+                    raw_bytes = stats.raw_bytes,
+                    compressed_bytes = stats.compressed_bytes,
+                    ratio = %format_args!("{:.2}", stats.ratio()),
+                    "Block body compression"
+                );
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_compression(stats.raw_bytes, stats.compressed_bytes);
+                }
+            }
+            Err(err) => {
+                warn!(target: "poa::payload", %err, "Failed to compress block body");
+            }
+        }
+    }
+
+    /// Append this block's protocol withdrawals to the payload attributes.
+    ///
+    /// 1. Infinite fund: one EIP-4895 withdrawal per whitelisted address.
+    ///    Withdrawals are protocol-level balance credits — the executor's
+    ///    post-execution hook adds their amount (in gwei) to each recipient's
+    ///    balance. This creates value out of thin air, mirroring how mainnet
+    ///    beacon-chain validator rewards work. Works on a live datadir, no
+    ///    wipe required. Amount per block per address is the EIP-4895 max
+    ///    (u64 gwei / 2 ≈ 9.2B ETH) so balances grow extremely fast.
+    ///
+    /// 2. Signer reward: one withdrawal to the key that will seal the block, scaled
+    ///    by whether it signs in turn (see [`rewards`](crate::chainspec::rewards)).
+    ///
+    ///    Fund and reward indices are derived deterministically from the block
+    ///    number (`block_number * slots + slot`, see [`credit_index`]) so they
+    ///    survive node restarts without collision and remain monotonic across
+    ///    blocks.
+    ///
+    /// 3. Withdrawal queue: up to `max_per_block` pending entries from the
+    ///    configured `WithdrawalQueue` contract, read from the parent state and
+    ///    continuing after the ids paid by the canonical chain up to the parent.
+    fn inject_withdrawals(
+        &self,
+        parent_number: u64,
+        parent_hash: B256,
+        attributes: &mut EthPayloadAttributes,
+    ) {
+        let child_number = parent_number + 1;
+
+        let reward = self
+            .chain_spec
+            .uncle_reward()
+            .filter(|_| !self.dev_mode)
+            .and_then(|reward| {
+                let (signer, in_turn) = self.chain_spec.with_effective_signers(|signers| {
+                    self.signer_rotation
+                        .select(child_number, signers, &self.signer_manager)
+                })?;
+                reward.reward_withdrawal(0, signer, in_turn)
+            });
+        let credits = protocol_credits(child_number, &self.infinite_fund, reward);
+        if !credits.is_empty() {
+            attributes
+                .withdrawals
+                .get_or_insert_with(Vec::new)
+                .extend(credits);
+        }
+
+        let Some(config) = self.chain_spec.withdrawal_config() else {
+            return;
+        };
+        let Ok(state) = self.client.state_by_block_hash(parent_hash) else {
+            return;
+        };
+        // The parent is canonical (the engine builds on its head); the node may not
+        // have recorded it yet.
+        self.withdrawal_queue
+            .catch_up(&self.client, parent_number, parent_hash);
+        let reader = StateProviderStorageReader(state.as_ref());
+        let queued = withdrawals::queue_withdrawals(
+            &reader,
+            config,
+            &self.withdrawal_queue,
+            parent_number,
+            parent_hash,
+        );
+        if !queued.is_empty() {
+            attributes
+                .withdrawals
+                .get_or_insert_with(Vec::new)
+                .extend(queued);
+        }
+    }
+
     /// Sign a built payload with POA signature.
     ///
     /// `build_ms` is the wall-clock time spent building the block (Phase 2.17 timing).
@@ -186,7 +782,8 @@ where
     /// 1. At epoch blocks — refreshes live signer list from on-chain SignerRegistry
     /// 2. Determines which signer should sign (round-robin using effective_signers)
     /// 3. Sets difficulty = 0 (Engine API compatibility)
-    /// 4. Builds extra_data with POA format (vanity + [signers at epoch] + signature)
+    /// 4. Builds extra_data with POA format (vanity + [signers at epoch] + signature
+    ///    [+ metadata suffix])
     /// 5. Signs the header via BlockSealer
     /// 6. Reconstructs the sealed block
     fn sign_payload(
@@ -194,6 +791,13 @@ where
         payload: EthBuiltPayload,
         build_ms: u64,
     ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        let payload = if self.chain_spec.eip1559_disabled() {
+            strip_base_fee(payload)
+        } else {
+            payload
+        };
+        audit_fork_fields(&self.chain_spec, &payload)?;
+
         if self.dev_mode {
             return Ok(payload);
         }
@@ -203,24 +807,31 @@ where
         let epoch = self.chain_spec.epoch();
         let is_epoch = block_number > 0 && block_number.is_multiple_of(epoch);
 
-        // At epoch blocks, refresh live signer list from SignerRegistry.
-        // Invalidate the cached SignerRegistry slots first so we get the latest governance
-        // state, then re-populate the cache with the fresh read.
+        // At epoch blocks, list the signers of the parent state (see
+        // `consensus::signer_set`), which is what consensus checks the list against.
+        // Invalidate the cached slots first so we get the parent's governance state,
+        // then re-populate the cache with the fresh read.
         if is_epoch {
-            if let Ok(state) = self.client.latest() {
-                // Invalidate stale signer registry entries before refreshing
-                self.cache
-                    .lock()
-                    .expect("cache lock")
-                    .invalidate_address(SIGNER_REGISTRY_ADDRESS);
+            if let Ok(state) = self.client.state_by_block_hash(block.header().parent_hash) {
+                // Invalidate stale governance entries before refreshing
+                {
+                    let mut cache = self.cache.lock().expect("cache lock");
+                    cache.invalidate_address(SIGNER_REGISTRY_ADDRESS);
+                    cache.invalidate_address(CHAIN_CONFIG_ADDRESS);
+                }
                 let reader = StateProviderStorageReader(state.as_ref());
                 let cached = CachedStorageReader::new_shared(reader, Arc::clone(&self.cache));
-                if let Some(list) = read_signer_list(&cached) {
-                    if !list.signers.is_empty() {
-                        output::print_epoch_refresh(block_number, list.signers.len());
-                        self.chain_spec.update_live_signers(list.signers);
-                    }
-                }
+                let registry = read_signer_list(&cached)
+                    .map(|list| list.signers)
+                    .unwrap_or_default();
+                let signers = epoch_signers(&self.chain_spec, registry);
+                info!(
+                    target: "poa::payload",
+                    block_number,
+                    signer_count = signers.len(),
+                    "Refreshed signers from SignerRegistry at epoch block"
+                );
+                self.chain_spec.update_live_signers(signers);
             }
         }
 
@@ -228,7 +839,8 @@ where
         // acquisition.  `with_effective_signers` passes `&[Address]` to the
         // closure without cloning the Vec.
         struct SignerPrep {
-            in_turn_signer: Address,
+            /// Sealing key and whether it is in turn; None = no key selected.
+            selected: Option<(Address, bool)>,
             extra_data_bytes: Bytes,
         }
 
@@ -236,7 +848,10 @@ where
             if signers.is_empty() {
                 return None;
             }
-            let in_turn_signer = signers[(block_number as usize) % signers.len()];
+            // Signer lookup is sync (std::sync::RwLock in SignerManager).
+            let selected = self
+                .signer_rotation
+                .select(block_number, signers, &self.signer_manager);
 
             // Build extra_data buffer while we still hold the lock so we read
             // the signer list only once.
@@ -244,7 +859,7 @@ where
                 + if is_epoch { signers.len() * 20 } else { 0 }
                 + EXTRA_SEAL_LENGTH;
             let mut extra_data = Vec::with_capacity(cap);
-            extra_data.extend_from_slice(&[0u8; EXTRA_VANITY_LENGTH]);
+            extra_data.extend_from_slice(&self.vanity);
             if is_epoch {
                 for s in signers.iter() {
                     extra_data.extend_from_slice(s.as_slice());
@@ -253,19 +868,35 @@ where
             extra_data.extend_from_slice(&[0u8; EXTRA_SEAL_LENGTH]);
 
             Some(SignerPrep {
-                in_turn_signer,
+                selected,
                 extra_data_bytes: Bytes::from(extra_data),
             })
         });
 
         let SignerPrep {
-            in_turn_signer,
+            selected,
             extra_data_bytes,
         } = match prep {
             Some(p) => p,
             None => return Ok(payload), // No signers configured, return unsigned
         };
 
+        // Optional metadata suffix after the seal placeholder (covered by the seal hash).
+        let extra_data_bytes = match &self.block_metadata {
+            Some(provider) => {
+                let mut extra_data = extra_data_bytes.to_vec();
+                let metadata = provider.metadata(block_number);
+                if self.block_metadata_hash {
+                    append_metadata_hash(&mut extra_data, &metadata)
+                } else {
+                    append_metadata(&mut extra_data, &metadata)
+                }
+                .map_err(|e| PayloadBuilderError::Other(Box::new(e)))?;
+                Bytes::from(extra_data)
+            }
+            None => extra_data_bytes,
+        };
+
         // Clone header and body from the built block
         let mut header = block.header().clone();
         let body = block.body().clone();
@@ -276,18 +907,8 @@ where
         // Apply pre-built extra_data (vanity + [epoch signers] + sig placeholder)
         header.extra_data = extra_data_bytes;
 
-        // Signer lookup is sync (std::sync::RwLock in SignerManager): fast path
-        // checks the in-turn key, slow path scans the full authorized list.
-        let (signer_addr, is_in_turn) = if self.signer_manager.has_signer(&in_turn_signer) {
-            (in_turn_signer, true)
-        } else {
-            // Slow path: clone the signer list once to scan for any key we hold.
-            // This branch runs rarely — normal operation is in-turn.
-            let all_signers = self.chain_spec.with_effective_signers(<[Address]>::to_vec);
-            match self.signer_manager.first_signer_in(&all_signers) {
-                Some(addr) => (addr, false),
-                None => return Ok(payload), // No authorized signer key available
-            }
+        let Some((signer_addr, is_in_turn)) = selected else {
+            return Ok(payload); // No authorized signer key available
         };
 
         // Only the ECDSA signing itself requires block_in_place: seal_header calls
@@ -308,7 +929,15 @@ where
 
         let sign_ms = sign_timer.elapsed_ms();
 
-        output::print_block_signed(block_number, &signer_addr, is_in_turn, build_ms, sign_ms);
+        info!(
+            target: "poa::payload",
+            block_number,
+            signer = %signer_addr,
+            in_turn = is_in_turn,
+            build_ms,
+            sign_ms,
+            "POA block signed"
+        );
 
         // Reconstruct the sealed block with the signed header
         let new_block = alloy_consensus::Block {
@@ -321,14 +950,95 @@ where
             Arc::new(sealed),
             payload.fees(),
             payload.requests(),
-        ))
+        )
+        .with_sidecars(payload.sidecars().clone()))
     }
 }
 
+/// Local key that seals block `block_number` and whether it is in turn, under the
+/// default [`SignerRotation::Any`].
+///
+/// `None` without signers or without a key for any of them.
+pub fn select_signer(
+    chain_spec: &PoaChainSpec,
+    signer_manager: &SignerManager,
+    block_number: u64,
+) -> Option<(Address, bool)> {
+    chain_spec.with_effective_signers(|signers| {
+        SignerRotation::Any.select(block_number, signers, signer_manager)
+    })
+}
+
+/// Protocol credits of block `block_number`: one per `infinite_fund` address, then
+/// the signer `reward`, indexed together by [`credit_index`].
+pub(crate) fn protocol_credits(
+    block_number: u64,
+    infinite_fund: &[Address],
+    reward: Option<Withdrawal>,
+) -> Vec<Withdrawal> {
+    const PER_BLOCK_GWEI: u64 = u64::MAX / 2;
+    let fund_count = infinite_fund.len() as u64;
+    let slots = fund_count + u64::from(reward.is_some());
+    let fund = infinite_fund
+        .iter()
+        .enumerate()
+        .map(|(i, addr)| Withdrawal {
+            index: credit_index(block_number, slots, i as u64),
+            validator_index: FUND_VALIDATOR_INDEX,
+            address: *addr,
+            amount: PER_BLOCK_GWEI,
+        });
+    let reward = reward.map(|reward| Withdrawal {
+        index: credit_index(block_number, slots, fund_count),
+        ..reward
+    });
+    fund.chain(reward).collect()
+}
+
+/// Clear the header base fee of a built payload.
+///
+/// Reth's block assembler always writes a base fee (zero before London); a chain
+/// with EIP-1559 disabled must not carry one, so the block is resealed without it.
+fn strip_base_fee(payload: EthBuiltPayload) -> EthBuiltPayload {
+    if payload.block().header().base_fee_per_gas.is_none() {
+        return payload;
+    }
+    let block = payload.block();
+    let mut header = block.header().clone();
+    header.base_fee_per_gas = None;
+    let sealed = SealedBlock::seal_slow(alloy_consensus::Block {
+        header,
+        body: block.body().clone(),
+    });
+    EthBuiltPayload::new(Arc::new(sealed), payload.fees(), payload.requests())
+        .with_sidecars(payload.sidecars().clone())
+}
+
+/// Refuse a payload whose header carries a fork-gated field consensus rejects.
+///
+/// Reth's block assembler sets the London to Prague fields from the chain spec; the
+/// POA post-processing above (base fee strip) must leave them consistent with the
+/// schedule, or every node, this one included, rejects the sealed block.
+fn audit_fork_fields(
+    chain_spec: &PoaChainSpec,
+    payload: &EthBuiltPayload,
+) -> Result<(), PayloadBuilderError> {
+    PoaConsensus::validate_fork_fields(chain_spec, payload.block().header()).map_err(|err| {
+        warn!(
+            target: "poa::payload",
+            block_number = payload.block().header().number,
+            %err,
+            "Built header does not match the fork schedule"
+        );
+        PayloadBuilderError::Other(Box::new(err))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::chainspec::PoaChainSpec;
+    use crate::chainspec::rewards::REWARD_VALIDATOR_INDEX;
+    use crate::chainspec::{PoaChainSpec, UncleRewardConfig};
     use crate::signer::{dev, BlockSealer};
     use alloy_consensus::Header;
 
@@ -342,6 +1052,61 @@ mod tests {
         assert_eq!(builder.chain_spec.signers().len(), 3);
     }
 
+    #[test]
+    fn test_strip_base_fee_reseals_block() {
+        let block = alloy_consensus::Block::<reth_ethereum::TransactionSigned> {
+            header: Header {
+                number: 1,
+                base_fee_per_gas: Some(0),
+                ..Default::default()
+            },
+            body: Default::default(),
+        };
+        let sealed = SealedBlock::seal_slow(block);
+        let original_hash = sealed.hash();
+        let payload = EthBuiltPayload::new(Arc::new(sealed), U256::from(7), None);
+
+        let stripped = strip_base_fee(payload);
+        assert_eq!(stripped.block().header().base_fee_per_gas, None);
+        assert_ne!(stripped.block().hash(), original_hash);
+        assert_eq!(stripped.fees(), U256::from(7));
+
+        // Already stripped payloads are returned as is.
+        let hash = stripped.block().hash();
+        assert_eq!(strip_base_fee(stripped).block().hash(), hash);
+    }
+
+    #[test]
+    fn test_audit_fork_fields_follows_schedule() {
+        let payload_with = |base_fee_per_gas| {
+            let block = alloy_consensus::Block::<reth_ethereum::TransactionSigned> {
+                header: Header {
+                    number: 1,
+                    base_fee_per_gas,
+                    withdrawals_root: Some(alloy_consensus::constants::EMPTY_WITHDRAWALS),
+                    blob_gas_used: Some(0),
+                    excess_blob_gas: Some(0),
+                    parent_beacon_block_root: Some(B256::ZERO),
+                    requests_hash: Some(alloy_eips::eip7685::EMPTY_REQUESTS_HASH),
+                    ..Default::default()
+                },
+                body: Default::default(),
+            };
+            EthBuiltPayload::new(Arc::new(SealedBlock::seal_slow(block)), U256::ZERO, None)
+        };
+
+        // Prague at genesis: every field up to requests_hash, base fee included.
+        let chain = PoaChainSpec::dev_chain();
+        assert!(audit_fork_fields(&chain, &payload_with(Some(0))).is_ok());
+        assert!(audit_fork_fields(&chain, &payload_with(Some(9))).is_ok());
+        assert!(audit_fork_fields(&chain, &payload_with(None)).is_err());
+
+        // EIP-1559 disabled: the assembler's base fee must be stripped before sealing.
+        let legacy = PoaChainSpec::dev_chain().with_eip1559_disabled();
+        assert!(audit_fork_fields(&legacy, &payload_with(Some(0))).is_err());
+        assert!(audit_fork_fields(&legacy, &strip_base_fee(payload_with(Some(0)))).is_ok());
+    }
+
     #[tokio::test]
     async fn test_sign_payload_components() {
         // Test the signing logic components work together
@@ -412,6 +1177,163 @@ mod tests {
         assert!(builder.dev_mode);
     }
 
+    #[tokio::test]
+    async fn test_payload_builder_builder_client_tag() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false);
+        assert_eq!(builder.client_tag, crate::consensus::vanity::default_client_tag());
+
+        let builder = builder.with_client_tag("acme-sealer/2.1");
+        assert_eq!(builder.client_tag, "acme-sealer/2.1");
+    }
+
+    #[cfg(feature = "blob-transactions")]
+    #[tokio::test]
+    async fn test_payload_builder_builder_blob_transaction_support() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false);
+        assert!(!builder.blob_transactions);
+        assert!(builder.with_blob_transaction_support(true).blob_transactions);
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_disk_guard() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain.clone(), manager.clone(), false);
+        assert!(builder.disk_guard.allows_block_building());
+
+        let guard = DiskGuard::new();
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false)
+            .with_disk_guard(guard.clone());
+        assert!(builder.disk_guard.check_block_building().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_bundle_support() {
+        use crate::metrics::ChainMetrics;
+
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain.clone(), manager.clone(), false);
+        assert!(builder.bundle_pool.is_none());
+
+        let bundle_pool = Arc::new(RwLock::new(Vec::new()));
+        let metrics = ChainMetrics::default_window();
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false)
+            .with_bundle_support(bundle_pool.clone())
+            .with_chain_metrics(metrics.clone());
+        bundle_pool.write().unwrap().push(TxBundle::default());
+        assert_eq!(builder.bundle_pool.unwrap().read().unwrap().len(), 1);
+        assert!(Arc::ptr_eq(&builder.metrics.unwrap(), &metrics));
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_deposit_tracker() {
+        struct NoDeposits;
+
+        impl CrossChainMessageExtractor for NoDeposits {
+            fn extract_deposits(&self, _l1_block: u64) -> Vec<DepositTx> {
+                Vec::new()
+            }
+        }
+
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let tracker = Arc::new(DepositTracker::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false)
+            .with_cross_chain_message_extractor(Arc::new(NoDeposits))
+            .with_deposit_tracker(tracker.clone());
+        let source = builder
+            .deposit_source
+            .unwrap()
+            .with_tracker(builder.deposit_tracker);
+        assert!(Arc::ptr_eq(source.tracker(), &tracker));
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_compression_stats() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false);
+        assert!(!builder.compression_stats);
+        assert!(builder.with_compression_stats(true).compression_stats);
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_block_metadata_hash() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false);
+        assert!(!builder.block_metadata_hash);
+        let builder = builder
+            .with_block_metadata_provider(BlockMetadataProvider::new(Arc::new(|_| {
+                BlockMetadata::new()
+            })))
+            .with_block_metadata_hash(true);
+        assert!(builder.block_metadata_hash);
+        assert!(builder.block_metadata.is_some());
+    }
+
+    #[test]
+    fn test_block_metadata_from_dir() {
+        let dir = std::env::temp_dir().join(format!("poa-metadata-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("7.json"), br#"{"batchSeq": 70}"#).unwrap();
+        std::fs::write(dir.join("8.json"), b"not json").unwrap();
+
+        let provider = BlockMetadataProvider::from_dir(&dir);
+        assert_eq!(provider.metadata(7)["batchSeq"], 70);
+        assert!(provider.metadata(8).is_empty());
+        assert!(provider.metadata(9).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_sequencer_mode() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false);
+        assert_eq!(builder.fee_policy, FeePolicy::default());
+
+        let builder = builder.with_min_gas_price(1_000_000_000);
+        assert!(builder.fee_policy.is_active());
+        let builder = builder.with_sequencer_mode(true);
+        assert!(builder.fee_policy.sequencer_mode);
+        assert!(!builder.fee_policy.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_priority_whitelist() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false);
+        assert!(builder.priority_senders.is_empty());
+
+        let operator = Address::repeat_byte(0x0a);
+        let builder = builder.with_priority_transaction_whitelist(HashSet::from([operator]));
+        assert!(builder.priority_senders.contains(&operator));
+    }
+
+    #[tokio::test]
+    async fn test_payload_builder_builder_shares_warmup_report() {
+        use crate::node::warmup::{WarmupConfig, WarmupReport, WarmupStats};
+
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let manager = Arc::new(SignerManager::new());
+        let builder = PoaPayloadBuilderBuilder::new(chain.clone(), manager.clone(), false);
+        assert!(builder.warmup.is_none());
+
+        let report = WarmupReport::new();
+        let builder = PoaPayloadBuilderBuilder::new(chain, manager, false)
+            .with_warmup(Some(WarmupConfig::default()), report.clone());
+        assert_eq!(builder.warmup, Some(WarmupConfig::default()));
+        builder.warmup_report.record(WarmupStats { accounts: 1, ..Default::default() });
+        assert_eq!(report.stats().map(|s| s.accounts), Some(1));
+    }
+
     #[tokio::test]
     async fn test_payload_builder_builder_production_mode() {
         let chain = Arc::new(PoaChainSpec::dev_chain());
@@ -466,6 +1388,93 @@ mod tests {
         assert_eq!(difficulty, U256::from(2));
     }
 
+    /// Block `number` carrying `withdrawals`, sealed by `signer`.
+    async fn rewarded_block(
+        manager: Arc<SignerManager>,
+        signer: Address,
+        number: u64,
+        withdrawals: Vec<Withdrawal>,
+    ) -> SealedBlock<Block> {
+        let header = Header {
+            number,
+            gas_limit: 30_000_000,
+            timestamp: 12 * number,
+            extra_data: Bytes::from(vec![0u8; EXTRA_VANITY_LENGTH + EXTRA_SEAL_LENGTH]),
+            base_fee_per_gas: Some(0),
+            withdrawals_root: Some(alloy_consensus::proofs::calculate_withdrawals_root(
+                &withdrawals,
+            )),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            requests_hash: Some(alloy_eips::eip7685::EMPTY_REQUESTS_HASH),
+            ..Default::default()
+        };
+        let header = BlockSealer::new(manager)
+            .seal_header(header, &signer)
+            .await
+            .unwrap();
+        SealedBlock::seal_slow(Block {
+            header,
+            body: alloy_consensus::BlockBody {
+                withdrawals: Some(withdrawals.into()),
+                ..Default::default()
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_out_of_turn_signer_receives_fractional_reward() {
+        use reth_consensus::Consensus;
+
+        let reward = UncleRewardConfig {
+            per_block_wei: 2_000_000_000_000_000_000,
+            out_of_turn_reward_pct: 50,
+        };
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_uncle_block_reward(reward));
+        let signers = chain.signers().to_vec();
+        let fund = [Address::with_last_byte(0xf0)];
+
+        // Only signer[1]'s key is held: block 1 is its turn, block 2 is not.
+        let manager = Arc::new(SignerManager::new());
+        manager
+            .add_signer_from_hex(crate::signer::dev::DEV_PRIVATE_KEYS[1])
+            .unwrap();
+        assert_eq!(select_signer(&chain, &manager, 1), Some((signers[1], true)));
+        let (signer, in_turn) = select_signer(&chain, &manager, 2).unwrap();
+        assert_eq!((signer, in_turn), (signers[1], false));
+        assert_eq!(select_signer(&chain, &SignerManager::new(), 2), None);
+
+        let credits = protocol_credits(2, &fund, reward.reward_withdrawal(0, signer, in_turn));
+        let paid = &credits[1];
+        assert_eq!(paid.address, signers[1]);
+        assert_eq!(paid.amount, 1_000_000_000); // 1 ETH in gwei, half the reward
+        assert_eq!(paid.validator_index, REWARD_VALIDATOR_INDEX);
+        assert_eq!(
+            credits.iter().map(|w| w.index).collect::<Vec<_>>(),
+            [credit_index(2, 2, 0), credit_index(2, 2, 1)]
+        );
+
+        // Consensus accepts the credits the builder produced for the sealing signer.
+        let consensus = PoaConsensus::new(chain.clone());
+        let block = rewarded_block(manager.clone(), signer, 2, credits.clone()).await;
+        assert!(Consensus::<Block>::validate_block_pre_execution(&consensus, &block).is_ok());
+
+        // The in-turn reward claimed out of turn is rejected.
+        let mut overpaid = credits.clone();
+        overpaid[1].amount *= 2;
+        let block = rewarded_block(manager.clone(), signer, 2, overpaid).await;
+        let err = Consensus::<Block>::validate_block_pre_execution(&consensus, &block).unwrap_err();
+        assert!(err.to_string().contains("signer rewards"), "{err}");
+
+        // So is a block leaving the reward out, and a reward on a chain paying none.
+        let block = rewarded_block(manager.clone(), signer, 2, credits[..1].to_vec()).await;
+        assert!(Consensus::<Block>::validate_block_pre_execution(&consensus, &block).is_err());
+        let unrewarded = PoaConsensus::new(Arc::new(PoaChainSpec::dev_chain()));
+        let block = rewarded_block(manager, signer, 2, credits).await;
+        assert!(Consensus::<Block>::validate_block_pre_execution(&unrewarded, &block).is_err());
+    }
+
     #[tokio::test]
     async fn test_epoch_block_includes_all_signers_in_extra_data() {
         let chain = Arc::new(PoaChainSpec::dev_chain());
@@ -0,0 +1,253 @@
+//! Priority senders: operator transactions that lead every block.
+//!
+//! Governance-controlled chains depend on a few system operators (oracle updaters,
+//! governance executors) whose transactions must not wait behind fee-paying traffic.
+//! With
+//! [`PoaPayloadBuilderBuilder::with_priority_transaction_whitelist`](super::PoaPayloadBuilderBuilder::with_priority_transaction_whitelist)
+//! the pending transactions of the whitelisted senders are placed right after L1
+//! deposits and bundles, in nonce order, ahead of the pool's fee-ordered best
+//! transactions and regardless of the builder's `--min-gas-price` floor (the node's
+//! pool applies the floor at admission to every sender).
+//!
+//! The base fee still applies: a transaction paying less than the block's base fee
+//! is not pending and cannot execute (zero-fee operator transactions need a
+//! zero base fee, e.g. `--zero-gas`).
+
+use super::sequencer::BoxedBest;
+use alloy_primitives::Address;
+use reth_transaction_pool::{
+    BestTransactions, PoolTransaction, TransactionPool, ValidPoolTransaction,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Senders whose pending transactions are included first, bypassing fee ordering.
+#[derive(Debug, Clone, Default)]
+pub struct PrioritySenders(Arc<HashSet<Address>>);
+
+impl PrioritySenders {
+    /// Whitelist `senders`.
+    pub fn new(senders: HashSet<Address>) -> Self {
+        Self(Arc::new(senders))
+    }
+
+    /// Whether no sender is whitelisted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `sender` is whitelisted.
+    #[inline]
+    pub fn contains(&self, sender: &Address) -> bool {
+        self.0.contains(sender)
+    }
+
+    /// Pending transactions of the whitelisted senders, each sender's in nonce order.
+    pub(crate) fn pending<P: TransactionPool>(
+        &self,
+        pool: &P,
+    ) -> Vec<Arc<ValidPoolTransaction<P::Transaction>>> {
+        let mut senders: Vec<_> = self.0.iter().copied().collect();
+        senders.sort();
+        senders
+            .into_iter()
+            .flat_map(|sender| {
+                let mut transactions = pool.get_pending_transactions_by_sender(sender);
+                transactions.sort_by_key(|tx| tx.nonce());
+                transactions
+            })
+            .collect()
+    }
+
+    /// `best` without the whitelisted senders' transactions, which already lead.
+    pub(crate) fn exclude<T: PoolTransaction>(&self, best: BoxedBest<T>) -> BoxedBest<T> {
+        if self.is_empty() {
+            return best;
+        }
+        let senders = self.clone();
+        Box::new(
+            best.filter_transactions(move |tx: &Arc<ValidPoolTransaction<T>>| {
+                !senders.contains(&tx.sender())
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::HotStateCache;
+    use crate::chainspec::PoaChainSpec;
+    use crate::evm::PoaEvmFactory;
+    use crate::payload::bundles::into_pool_transactions;
+    use crate::payload::PoaPayloadBuilderBuilder;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{Header, SignableTransaction, TxLegacy};
+    use alloy_primitives::{TxKind, B256, U256};
+    use alloy_rpc_types_engine::PayloadId;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_basic_payload_builder::{BuildArguments, BuildOutcome, PayloadBuilder, PayloadConfig};
+    use reth_chainspec::EthChainSpec;
+    use reth_ethereum::evm::revm::cached::CachedReads;
+    use reth_ethereum::node::EthEvmConfig;
+    use reth_ethereum::TransactionSigned;
+    use reth_ethereum_engine_primitives::EthPayloadAttributes;
+    use reth_ethereum_payload_builder::EthereumBuilderConfig;
+    use reth_primitives_traits::{SealedHeader, SignerRecoverable};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_transaction_pool::blobstore::InMemoryBlobStore;
+    use reth_transaction_pool::error::InvalidPoolTransactionError;
+    use reth_transaction_pool::test_utils::MockTransactionValidator;
+    use reth_transaction_pool::{
+        BlockInfo, CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolConfig, TransactionOrigin,
+        TransactionPoolExt,
+    };
+    use std::sync::Mutex;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    /// Best transactions served from a fixed list, in order.
+    struct ListBest(std::vec::IntoIter<Arc<ValidPoolTransaction<EthPooledTransaction>>>);
+
+    impl Iterator for ListBest {
+        type Item = Arc<ValidPoolTransaction<EthPooledTransaction>>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl BestTransactions for ListBest {
+        fn mark_invalid(&mut self, _: &Self::Item, _: &InvalidPoolTransactionError) {}
+
+        fn no_updates(&mut self) {}
+
+        fn set_skip_blobs(&mut self, _: bool) {}
+    }
+
+    /// Legacy transfer from dev key `key` paying `gas_price`.
+    fn transfer(key: usize, gas_price: u128) -> Arc<ValidPoolTransaction<EthPooledTransaction>> {
+        let tx = TxLegacy {
+            chain_id: None,
+            nonce: 0,
+            gas_price,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::with_last_byte(0xbe)),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        let recovered = TransactionSigned::Legacy(tx.into_signed(signature))
+            .try_into_recovered()
+            .unwrap();
+        into_pool_transactions(vec![recovered]).unwrap().remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_zero_fee_priority_sender_leads_full_block() {
+        let operator = transfer(0, 0);
+        let normal = [transfer(1, 50 * GWEI), transfer(2, 20 * GWEI)];
+        // Zero base fee so the operator's transaction can execute.
+        let chain = Arc::new(PoaChainSpec::dev_chain().with_base_fee_override(0, 0));
+        // A parent with room for two transfers.
+        let parent = SealedHeader::seal_slow(Header {
+            gas_limit: 50_000,
+            ..chain.genesis_header().clone()
+        });
+
+        let provider = MockEthProvider::default().with_chain_spec((**chain.inner()).clone());
+        provider.add_header(parent.hash(), parent.header().clone());
+        provider.extend_accounts(
+            chain
+                .inner()
+                .genesis
+                .alloc
+                .iter()
+                .map(|(address, account)| {
+                    let storage = account.storage.clone().unwrap_or_default();
+                    let mut state =
+                        ExtendedAccount::new(account.nonce.unwrap_or_default(), account.balance)
+                            .extend_storage(
+                                storage
+                                    .into_iter()
+                                    .map(|(k, v)| (k, U256::from_be_bytes(v.0))),
+                            );
+                    if let Some(code) = &account.code {
+                        state = state.with_bytecode(code.clone());
+                    }
+                    (*address, state)
+                }),
+        );
+
+        let pool = Pool::new(
+            MockTransactionValidator::<EthPooledTransaction>::default(),
+            CoinbaseTipOrdering::default(),
+            InMemoryBlobStore::default(),
+            PoolConfig {
+                minimal_protocol_basefee: 0,
+                ..Default::default()
+            },
+        );
+        pool.set_block_info(BlockInfo {
+            block_gas_limit: parent.gas_limit,
+            last_seen_block_hash: parent.hash(),
+            last_seen_block_number: parent.number,
+            pending_basefee: 0,
+            pending_blob_fee: None,
+        });
+        // The pool orders by fee, so the operator's transaction would come last.
+        for tx in [&normal[0], &normal[1], &operator] {
+            pool.add_transaction(TransactionOrigin::External, tx.transaction.clone())
+                .await
+                .unwrap();
+        }
+
+        let evm_config =
+            EthEvmConfig::new_with_evm_factory(chain.inner().clone(), PoaEvmFactory::default());
+        let builder = PoaPayloadBuilderBuilder::new(chain.clone(), Arc::default(), true)
+            .with_min_gas_price(GWEI)
+            .with_priority_transaction_whitelist(HashSet::from([operator.sender()]))
+            .into_payload_builder(
+                provider,
+                pool,
+                evm_config,
+                EthereumBuilderConfig::new().with_gas_limit(parent.gas_limit),
+                Arc::new(Mutex::new(HotStateCache::new(16))),
+                None,
+            );
+        let attributes = EthPayloadAttributes {
+            timestamp: parent.timestamp + chain.block_period(),
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: Address::ZERO,
+            withdrawals: Some(Vec::new()),
+            parent_beacon_block_root: Some(B256::ZERO),
+        };
+        let config = PayloadConfig::new(Arc::new(parent), attributes, PayloadId::new([0; 8]));
+        let args = BuildArguments::new(CachedReads::default(), config, Default::default(), None);
+        let BuildOutcome::Better { payload, .. } = builder.try_build(args).unwrap() else {
+            panic!("no payload built");
+        };
+
+        // The operator's transaction leads; the cheaper transfer no longer fits.
+        let block: Vec<_> = payload
+            .block()
+            .body()
+            .transactions
+            .iter()
+            .map(|tx| *tx.tx_hash())
+            .collect();
+        assert_eq!(block, vec![*operator.hash(), *normal[0].hash()]);
+    }
+
+    #[test]
+    fn test_exclude_without_senders_keeps_best() {
+        let senders = PrioritySenders::default();
+        assert!(senders.is_empty());
+        let txs = vec![transfer(0, GWEI), transfer(1, GWEI)];
+        let best: BoxedBest<EthPooledTransaction> = Box::new(ListBest(txs.clone().into_iter()));
+        assert_eq!(senders.exclude(best).count(), 2);
+    }
+}
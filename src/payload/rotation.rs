@@ -0,0 +1,190 @@
+//! Which local key seals each block.
+//!
+//! A node's [`SignerManager`] may hold several of the chain's signer keys. Block
+//! `N`'s in-turn signer is `signers[N % signers.len()]`; the [`SignerRotation`]
+//! given to
+//! [`PoaPayloadBuilderBuilder::with_signer_rotation`](super::PoaPayloadBuilderBuilder::with_signer_rotation)
+//! decides whether the node seals only in turn, also out of turn, or always with one
+//! fixed key. When the policy selects no key the block is not built.
+
+use crate::consensus::PoaConsensus;
+use crate::signer::SignerManager;
+use alloy_primitives::Address;
+use std::fmt;
+use std::str::FromStr;
+
+/// Policy choosing the local key that seals a block (`--signer-rotation`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignerRotation {
+    /// Seal only blocks whose in-turn signer is a local key.
+    InTurn,
+    /// Seal with the in-turn key when held, otherwise out of turn with the first
+    /// local key in the signer list.
+    #[default]
+    Any,
+    /// Always seal with this key, in turn or not.
+    Fixed(Address),
+}
+
+impl SignerRotation {
+    /// Round-robin rotation, sealing out of turn too when `allow_out_of_turn` is set.
+    pub fn round_robin(allow_out_of_turn: bool) -> Self {
+        if allow_out_of_turn {
+            Self::Any
+        } else {
+            Self::InTurn
+        }
+    }
+
+    /// Whether blocks may be sealed by a key that is not in turn.
+    pub fn allows_out_of_turn(&self) -> bool {
+        !matches!(self, Self::InTurn)
+    }
+
+    /// Local key sealing block `block_number` among the ordered `signers`, and
+    /// whether it is in turn.
+    ///
+    /// `None` when no signers are configured or the policy selects no key we hold;
+    /// a fixed key must also be in `signers`.
+    pub fn select(
+        &self,
+        block_number: u64,
+        signers: &[Address],
+        signer_manager: &SignerManager,
+    ) -> Option<(Address, bool)> {
+        if signers.is_empty() {
+            return None;
+        }
+        let in_turn = PoaConsensus::expected_signer(block_number, signers);
+        match self {
+            Self::InTurn => signer_manager
+                .has_signer(&in_turn)
+                .then_some((in_turn, true)),
+            Self::Any if signer_manager.has_signer(&in_turn) => Some((in_turn, true)),
+            // Rare: normal operation is in turn.
+            Self::Any => signer_manager
+                .first_signer_in(signers)
+                .map(|address| (address, false)),
+            Self::Fixed(address) => (signers.contains(address)
+                && signer_manager.has_signer(address))
+            .then_some((*address, *address == in_turn)),
+        }
+    }
+}
+
+impl FromStr for SignerRotation {
+    type Err = String;
+
+    /// `in-turn`, `any` or `fixed:<address>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "in-turn" => Ok(Self::InTurn),
+            "any" => Ok(Self::Any),
+            _ => match s.strip_prefix("fixed:") {
+                Some(address) => address
+                    .parse()
+                    .map(Self::Fixed)
+                    .map_err(|err| format!("invalid fixed signer address `{address}`: {err}")),
+                None => Err(format!(
+                    "unknown signer rotation `{s}`; expected in-turn, any or fixed:<address>"
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for SignerRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InTurn => f.write_str("in-turn"),
+            Self::Any => f.write_str("any"),
+            Self::Fixed(address) => write!(f, "fixed:{address}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::PoaChainSpec;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+
+    /// Five chain signers, of which the node holds the keys of 0, 2 and 3.
+    fn three_local_signers() -> (Vec<Address>, SignerManager) {
+        let signers = crate::genesis::dev_accounts()[..5].to_vec();
+        let manager = SignerManager::new();
+        for key in [0, 2, 3] {
+            manager.add_signer_from_hex(DEV_PRIVATE_KEYS[key]).unwrap();
+        }
+        (signers, manager)
+    }
+
+    #[test]
+    fn test_in_turn_rotation_across_consecutive_blocks() {
+        let (signers, manager) = three_local_signers();
+        let rotation = SignerRotation::round_robin(false);
+        let chosen: Vec<_> = (10..20)
+            .map(|number| rotation.select(number, &signers, &manager))
+            .collect();
+        let expected: Vec<_> = (10..20)
+            .map(|number: u64| {
+                let in_turn = signers[(number % 5) as usize];
+                manager.has_signer(&in_turn).then_some((in_turn, true))
+            })
+            .collect();
+        assert_eq!(chosen, expected);
+        // Blocks 10, 12, 13, 15, ...: our keys rotate; 11, 14, ...: skipped.
+        assert_eq!(chosen[0], Some((signers[0], true)));
+        assert_eq!(chosen[1], None);
+        assert_eq!(chosen[2], Some((signers[2], true)));
+        assert_eq!(chosen[3], Some((signers[3], true)));
+        assert_eq!(chosen[4], None);
+    }
+
+    #[test]
+    fn test_any_and_fixed_rotation_seal_out_of_turn() {
+        let (signers, manager) = three_local_signers();
+        let any = SignerRotation::round_robin(true);
+        assert!(any.allows_out_of_turn());
+        assert_eq!(any.select(2, &signers, &manager), Some((signers[2], true)));
+        assert_eq!(any.select(1, &signers, &manager), Some((signers[0], false)));
+
+        let fixed = SignerRotation::Fixed(signers[3]);
+        assert_eq!(
+            fixed.select(3, &signers, &manager),
+            Some((signers[3], true))
+        );
+        assert_eq!(
+            fixed.select(4, &signers, &manager),
+            Some((signers[3], false))
+        );
+        // A key we do not hold, or one outside the signer list, seals nothing.
+        assert_eq!(
+            SignerRotation::Fixed(signers[1]).select(1, &signers, &manager),
+            None
+        );
+        assert_eq!(fixed.select(0, &signers[..3], &manager), None);
+        assert_eq!(any.select(0, &[], &manager), None);
+
+        let chain = PoaChainSpec::dev_chain();
+        assert_eq!(SignerRotation::default(), SignerRotation::Any);
+        assert_eq!(
+            SignerRotation::Any.select(0, chain.signers(), &SignerManager::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_signer_rotation() {
+        let address = Address::with_last_byte(0x42);
+        for rotation in [
+            SignerRotation::InTurn,
+            SignerRotation::Any,
+            SignerRotation::Fixed(address),
+        ] {
+            assert_eq!(rotation.to_string().parse::<SignerRotation>(), Ok(rotation));
+        }
+        assert!("fixed:0x42".parse::<SignerRotation>().is_err());
+        assert!("round-robin".parse::<SignerRotation>().is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Sequencer mode: include every valid transaction.
+//!
+//! A POA chain acting as a single sequencer (e.g. for an L2) commits to censorship
+//! resistance: every valid pending transaction goes into the next block that has
+//! room for it. With
+//! [`PoaPayloadBuilderBuilder::with_sequencer_mode`](super::PoaPayloadBuilderBuilder::with_sequencer_mode)
+//! the payload builder therefore ignores the `--min-gas-price` floor and is limited
+//! only by the block gas limit. Once a block it built becomes the parent of the next
+//! build, it compares that block with the pool's pending set and warns about valid
+//! transactions that were left out even though they would have fit
+//! ([`excluded_for_non_gas`]). Rebuilds of a block that was never sealed are not
+//! checked, and transactions that arrived after the build started are not counted.
+//!
+//! The base fee is a validity rule, not a floor: a transaction paying less than the
+//! block's base fee is not pending and is never counted.
+
+use alloy_consensus::Transaction;
+use alloy_primitives::B256;
+use reth_ethereum::primitives::SealedBlock;
+use reth_ethereum::Block;
+use reth_transaction_pool::{BestTransactions, PoolTransaction, ValidPoolTransaction};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Boxed best-transactions iterator over the pool's transactions.
+pub(crate) type BoxedBest<T> = Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>;
+
+/// Which pool transactions the payload builder may select.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeePolicy {
+    /// Minimum effective gas price (wei) outside sequencer mode; 0 = no floor.
+    pub min_gas_price: u128,
+    /// Include every valid transaction, whatever it pays.
+    pub sequencer_mode: bool,
+}
+
+impl FeePolicy {
+    /// Whether the policy filters anything out of the pool's best transactions.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        !self.sequencer_mode && self.min_gas_price > 0
+    }
+
+    /// Whether a transaction paying `effective_gas_price` per gas may be included.
+    #[inline]
+    pub fn admits(&self, effective_gas_price: u128) -> bool {
+        !self.is_active() || effective_gas_price >= self.min_gas_price
+    }
+
+    /// Apply the fee floor to `best`, pricing transactions at `base_fee`.
+    ///
+    /// Skipped transactions stay in the pool for a block where they pay enough.
+    pub(crate) fn filter<T: PoolTransaction>(
+        self,
+        best: BoxedBest<T>,
+        base_fee: u64,
+    ) -> BoxedBest<T> {
+        if !self.is_active() {
+            return best;
+        }
+        Box::new(
+            best.filter_transactions(move |tx: &Arc<ValidPoolTransaction<T>>| {
+                self.admits(tx.transaction.effective_gas_price(Some(base_fee)))
+            }),
+        )
+    }
+}
+
+/// A block sealed by this node and when its build started.
+#[derive(Debug)]
+pub(crate) struct SequencedBlock {
+    pub(crate) block: Arc<SealedBlock<Block>>,
+    pub(crate) built_at: Instant,
+}
+
+/// Blocks sealed by this node, by hash, until one becomes the next parent.
+#[derive(Debug, Default)]
+pub(crate) struct SealedSequencerBlocks(Mutex<HashMap<B256, SequencedBlock>>);
+
+impl SealedSequencerBlocks {
+    /// Record a block sealed by this node.
+    pub(crate) fn record(&self, block: SequencedBlock) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(block.block.hash(), block);
+    }
+
+    /// Take the block `parent_hash` a new build starts from, if this node sealed it.
+    ///
+    /// Blocks built on any other parent were not published and are forgotten;
+    /// rebuilds on `parent_hash` take nothing more.
+    pub(crate) fn settle(&self, parent_hash: B256) -> Option<SequencedBlock> {
+        let mut sealed = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let block = sealed.remove(&parent_hash);
+        sealed.retain(|_, built| built.block.parent_hash == parent_hash);
+        block
+    }
+}
+
+/// Number of pending transactions missing from a block that still had room for them.
+///
+/// `pending` yields `(hash, gas_limit)` for every valid pending transaction,
+/// `included` holds the block's transaction hashes and `gas_remaining` is the block
+/// gas limit minus the gas used. A transaction whose gas limit fits the remaining
+/// gas was left out for a reason other than gas.
+pub fn excluded_for_non_gas(
+    pending: impl IntoIterator<Item = (B256, u64)>,
+    included: &HashSet<B256>,
+    gas_remaining: u64,
+) -> usize {
+    pending
+        .into_iter()
+        .filter(|(hash, gas_limit)| !included.contains(hash) && *gas_limit <= gas_remaining)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_ethereum::TransactionSigned;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_transaction_pool::error::InvalidPoolTransactionError;
+    use reth_transaction_pool::EthPooledTransaction;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    /// Best transactions served from a fixed list, in order.
+    struct ListBest(std::vec::IntoIter<Arc<ValidPoolTransaction<EthPooledTransaction>>>);
+
+    impl Iterator for ListBest {
+        type Item = Arc<ValidPoolTransaction<EthPooledTransaction>>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl BestTransactions for ListBest {
+        fn mark_invalid(&mut self, _: &Self::Item, _: &InvalidPoolTransactionError) {}
+
+        fn no_updates(&mut self) {}
+
+        fn set_skip_blobs(&mut self, _: bool) {}
+    }
+
+    /// Legacy transfers from distinct dev keys, one per gas price.
+    fn best(gas_prices: &[u128]) -> BoxedBest<EthPooledTransaction> {
+        let transactions = gas_prices
+            .iter()
+            .enumerate()
+            .map(|(key, &gas_price)| {
+                let tx = TxLegacy {
+                    chain_id: None,
+                    nonce: 0,
+                    gas_price,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::with_last_byte(0xbe)),
+                    value: U256::from(1),
+                    ..Default::default()
+                };
+                let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[key].parse().unwrap();
+                let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                TransactionSigned::Legacy(tx.into_signed(signature))
+                    .try_into_recovered()
+                    .unwrap()
+            })
+            .collect();
+        let pool_txs = crate::payload::bundles::into_pool_transactions(transactions).unwrap();
+        Box::new(ListBest(pool_txs.into_iter()))
+    }
+
+    fn selected_prices(policy: FeePolicy, gas_prices: &[u128]) -> Vec<u128> {
+        policy
+            .filter(best(gas_prices), 0)
+            .map(|tx| tx.transaction.max_fee_per_gas())
+            .collect()
+    }
+
+    #[test]
+    fn test_zero_fee_transaction_included_only_in_sequencer_mode() {
+        let normal = FeePolicy {
+            min_gas_price: GWEI,
+            sequencer_mode: false,
+        };
+        assert_eq!(selected_prices(normal, &[0, 10 * GWEI]), vec![10 * GWEI]);
+
+        let sequencer = FeePolicy {
+            sequencer_mode: true,
+            ..normal
+        };
+        assert_eq!(
+            selected_prices(sequencer, &[0, 10 * GWEI]),
+            vec![0, 10 * GWEI]
+        );
+    }
+
+    #[test]
+    fn test_fee_policy_admits() {
+        let policy = FeePolicy::default();
+        assert!(!policy.is_active());
+        assert!(policy.admits(0));
+
+        let floor = FeePolicy {
+            min_gas_price: GWEI,
+            sequencer_mode: false,
+        };
+        assert!(floor.is_active());
+        assert!(!floor.admits(GWEI - 1));
+        assert!(floor.admits(GWEI));
+    }
+
+    #[test]
+    fn test_sealed_sequencer_blocks_settle_once_per_parent() {
+        let sealed = SealedSequencerBlocks::default();
+        let block = |parent_hash: B256, number: u64| {
+            let block = SealedBlock::seal_slow(Block {
+                header: alloy_consensus::Header {
+                    parent_hash,
+                    number,
+                    ..Default::default()
+                },
+                body: Default::default(),
+            });
+            SequencedBlock {
+                block: Arc::new(block),
+                built_at: Instant::now(),
+            }
+        };
+        let parent = B256::repeat_byte(1);
+        let (first, rebuilt) = (block(parent, 1), block(parent, 2));
+        let (first_hash, rebuilt_hash) = (first.block.hash(), rebuilt.block.hash());
+        sealed.record(first);
+        sealed.record(rebuilt);
+
+        // The rebuild that was sealed is checked once; the other one is dropped.
+        assert!(sealed.settle(rebuilt_hash).is_some());
+        assert!(sealed.settle(rebuilt_hash).is_none());
+        assert!(sealed.settle(first_hash).is_none());
+    }
+
+    #[test]
+    fn test_excluded_for_non_gas() {
+        let (a, b, c) = (
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        );
+        let included = HashSet::from([a]);
+        // b would have fit, c needs more gas than was left.
+        let pending = [(a, 21_000), (b, 21_000), (c, 1_000_000)];
+        assert_eq!(excluded_for_non_gas(pending, &included, 50_000), 1);
+        assert_eq!(excluded_for_non_gas(pending, &included, 10_000), 0);
+    }
+}
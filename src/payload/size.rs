@@ -0,0 +1,187 @@
+//! Block size limit during transaction selection.
+//!
+//! With [`PoaChainSpec::with_max_block_size_bytes`](crate::chainspec::PoaChainSpec::with_max_block_size_bytes)
+//! consensus rejects blocks whose RLP-encoded transaction list is larger than the
+//! limit. The payload builder therefore stops offering pool transactions that would
+//! not fit in what is left of it ([`BlockSizeBudget`]); they stay in the pool for a
+//! later block. Leading transactions (deposits, bundles, priority senders) are
+//! charged first.
+//!
+//! A transaction is charged when it is offered to the inner builder, so one the
+//! builder then skips (out of gas, failed execution) still uses up its share: a
+//! block may come out smaller than the limit, never larger.
+
+use super::sequencer::BoxedBest;
+use reth_transaction_pool::{BestTransactions, PoolTransaction, ValidPoolTransaction};
+use std::sync::Arc;
+
+/// Bytes `tx` adds to the RLP-encoded transaction list of a block body.
+///
+/// Legacy transactions are RLP lists of their own; typed ones are their EIP-2718
+/// encoding wrapped in an RLP string.
+pub(crate) fn body_length<T: PoolTransaction>(tx: &T) -> usize {
+    let len = tx.encoded_length();
+    if tx.is_legacy() {
+        len
+    } else {
+        len + alloy_rlp::length_of_length(len)
+    }
+}
+
+/// Bytes left for transactions in a block limited to `max` bytes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BlockSizeBudget {
+    max: usize,
+    /// RLP payload length of the transactions charged so far.
+    used: usize,
+}
+
+impl BlockSizeBudget {
+    /// An empty block limited to `max` bytes.
+    pub(crate) fn new(max: usize) -> Self {
+        Self { max, used: 0 }
+    }
+
+    /// Charge `tx` if the transaction list still fits with it.
+    pub(crate) fn charge<T: PoolTransaction>(&mut self, tx: &T) -> bool {
+        let used = self.used + body_length(tx);
+        if used + alloy_rlp::length_of_length(used) > self.max {
+            return false;
+        }
+        self.used = used;
+        true
+    }
+
+    /// Charge `leading`, which is placed whatever its size, then leave out of `best`
+    /// every transaction that does not fit in the rest.
+    pub(crate) fn filter<T: PoolTransaction>(
+        mut self,
+        leading: &[Arc<ValidPoolTransaction<T>>],
+        best: BoxedBest<T>,
+    ) -> BoxedBest<T> {
+        for tx in leading {
+            self.used += body_length(&tx.transaction);
+        }
+        Box::new(
+            best.filter_transactions(move |tx: &Arc<ValidPoolTransaction<T>>| {
+                self.charge(&tx.transaction)
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload::bundles::into_pool_transactions;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxEip1559, TxLegacy};
+    use alloy_primitives::{Address, Bytes, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_ethereum::TransactionSigned;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_transaction_pool::error::InvalidPoolTransactionError;
+    use reth_transaction_pool::EthPooledTransaction;
+
+    /// Best transactions served from a fixed list, in order.
+    struct ListBest(std::vec::IntoIter<Arc<ValidPoolTransaction<EthPooledTransaction>>>);
+
+    impl Iterator for ListBest {
+        type Item = Arc<ValidPoolTransaction<EthPooledTransaction>>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl BestTransactions for ListBest {
+        fn mark_invalid(&mut self, _: &Self::Item, _: &InvalidPoolTransactionError) {}
+
+        fn no_updates(&mut self) {}
+
+        fn set_skip_blobs(&mut self, _: bool) {}
+    }
+
+    /// Transactions from dev key `key` carrying `calldata` bytes each, alternating
+    /// legacy and EIP-1559.
+    fn transactions(key: usize, calldata: &[usize]) -> Vec<TransactionSigned> {
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let to = TxKind::Call(Address::with_last_byte(0xbe));
+        calldata
+            .iter()
+            .enumerate()
+            .map(|(nonce, &len)| {
+                let input = Bytes::from(vec![1u8; len]);
+                if nonce % 2 == 0 {
+                    let tx = TxLegacy {
+                        nonce: nonce as u64,
+                        gas_price: 1_000_000_000,
+                        gas_limit: 1_000_000,
+                        to,
+                        value: U256::from(1),
+                        input,
+                        ..Default::default()
+                    };
+                    let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                    TransactionSigned::Legacy(tx.into_signed(signature))
+                } else {
+                    let tx = TxEip1559 {
+                        chain_id: 9323310,
+                        nonce: nonce as u64,
+                        max_fee_per_gas: 2_000_000_000,
+                        gas_limit: 1_000_000,
+                        to,
+                        input,
+                        ..Default::default()
+                    };
+                    let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                    TransactionSigned::Eip1559(tx.into_signed(signature))
+                }
+            })
+            .collect()
+    }
+
+    fn pool(
+        transactions: &[TransactionSigned],
+    ) -> Vec<Arc<ValidPoolTransaction<EthPooledTransaction>>> {
+        let recovered = transactions
+            .iter()
+            .map(|tx| tx.clone().try_into_recovered().unwrap())
+            .collect();
+        into_pool_transactions(recovered).unwrap()
+    }
+
+    #[test]
+    fn test_body_length_matches_block_encoding() {
+        let signed = transactions(0, &[0, 0, 100, 100]);
+        for (tx, pooled) in signed.iter().zip(pool(&signed)) {
+            assert_eq!(
+                body_length(&pooled.transaction),
+                alloy_rlp::Encodable::length(tx)
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_stops_at_block_size() {
+        let leading = transactions(0, &[1_000]);
+        let rest = transactions(1, &[1_000, 1_000, 1_000, 10]);
+        let best: BoxedBest<EthPooledTransaction> = Box::new(ListBest(pool(&rest).into_iter()));
+
+        // Room for the leading transaction and two more of the same size.
+        let max =
+            alloy_rlp::list_length::<_, TransactionSigned>(&[&leading[0], &rest[0], &rest[1]]);
+        let selected: Vec<_> = BlockSizeBudget::new(max)
+            .filter(&pool(&leading), best)
+            .map(|tx| *tx.hash())
+            .collect();
+        // The third large transaction does not fit; the small one after it does not
+        // fit either, as the limit is already reached to the byte.
+        assert_eq!(selected, [*rest[0].tx_hash(), *rest[1].tx_hash()]);
+
+        let mut block = leading.clone();
+        block.extend(rest.iter().take(2).cloned());
+        assert!(alloy_rlp::list_length::<_, TransactionSigned>(&block) <= max);
+    }
+}
@@ -0,0 +1,586 @@
+//! Governance withdrawal queue (EIP-4895 credits from a contract).
+//!
+//! When [`WithdrawalConfig`] is set on the chain spec, every built block includes
+//! up to `max_per_block` pending entries from the `WithdrawalQueue` contract.
+//! The queue id becomes the withdrawal index.
+//!
+//! `dequeue` is performed as a storage read against the parent state (same
+//! approach as the ChainConfig/SignerRegistry readers). Which ids the chain has
+//! already paid is tracked by [`WithdrawalQueueTracker`] from canonical blocks only:
+//! the node records every committed block, and the builder catches up on its parent
+//! before reading the queue, so payloads that were built but never published consume
+//! nothing, and rebuilding a payload for the same parent is idempotent. With a state
+//! file the cursors survive restarts. Advancing `head` on-chain is done by calling
+//! `WithdrawalQueue.finalize(ids)` (governance or system caller); the builder always
+//! resumes from `max(head, last included + 1)`.
+
+use crate::chainspec::WithdrawalConfig;
+use crate::onchain::{dequeue_withdrawals, StorageReader};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::B256;
+use reth_ethereum::Block;
+use reth_storage_api::BlockReader;
+use reth_tracing::tracing::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{fs, io};
+
+/// Validator index stamped on queue withdrawals, distinguishing them from
+/// infinite-fund credits (validator index 0).
+pub const QUEUE_VALIDATOR_INDEX: u64 = u64::MAX;
+
+/// How many recent blocks' queue cursors to retain (reorg depth headroom).
+const TRACKER_RETAINED_BLOCKS: usize = 256;
+
+/// Failure loading the persisted withdrawal queue cursors.
+#[derive(Debug, thiserror::Error)]
+pub enum TrackerStateError {
+    /// Reading the state file failed
+    #[error("Withdrawal queue state I/O: {0}")]
+    Io(#[from] io::Error),
+    /// The state file is not a list of cursors
+    #[error("Invalid withdrawal queue state: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Queue cursor after one canonical block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct QueueCursor {
+    number: u64,
+    hash: B256,
+    /// First queue id not yet paid once this block is applied.
+    next: u64,
+}
+
+/// Canonical block withdrawals, for catching the tracker up on missed blocks.
+///
+/// Implemented for every reth [`BlockReader`], so the node's provider can be passed
+/// directly.
+pub trait WithdrawalHistory {
+    /// Parent hash and withdrawals of the stored block `hash`.
+    fn block_withdrawals(&self, hash: B256) -> Option<(B256, Vec<Withdrawal>)>;
+}
+
+impl<P> WithdrawalHistory for P
+where
+    P: BlockReader<Block = Block>,
+{
+    fn block_withdrawals(&self, hash: B256) -> Option<(B256, Vec<Withdrawal>)> {
+        let block = self.block_by_hash(hash).ok().flatten()?;
+        let withdrawals = block
+            .body
+            .withdrawals
+            .map(|w| w.into_inner())
+            .unwrap_or_default();
+        Some((block.header.parent_hash, withdrawals))
+    }
+}
+
+/// Tracks the queue cursor after each recent canonical block.
+#[derive(Debug, Default)]
+pub struct WithdrawalQueueTracker {
+    /// block number → cursor after that block, with the block's hash.
+    cursors: Mutex<BTreeMap<u64, QueueCursor>>,
+    /// File the cursors are saved to after every update; None = memory only.
+    path: Option<PathBuf>,
+}
+
+impl WithdrawalQueueTracker {
+    /// Create an empty, in-memory tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracker persisted at `path`, resuming from the cursors saved there.
+    ///
+    /// A missing file starts empty; it is written on the first recorded block.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, TrackerStateError> {
+        let path = path.into();
+        let cursors = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Vec<QueueCursor>>(&bytes)?
+                .into_iter()
+                .map(|cursor| (cursor.number, cursor))
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self {
+            cursors: Mutex::new(cursors),
+            path: Some(path),
+        })
+    }
+
+    /// First queue id not yet paid as of canonical block `block_hash`, or None if that
+    /// block has not been recorded.
+    pub fn next_id_after(&self, block_number: u64, block_hash: B256) -> Option<u64> {
+        self.cursors
+            .lock()
+            .expect("withdrawal tracker lock")
+            .get(&block_number)
+            .filter(|cursor| cursor.hash == block_hash)
+            .map(|cursor| cursor.next)
+    }
+
+    /// Record the queue withdrawals paid by a canonical block.
+    ///
+    /// The cursor continues from the recorded parent; a block recorded again (same
+    /// height after a reorg) replaces the previous entry.
+    pub fn record_canonical_block(
+        &self,
+        block_number: u64,
+        block_hash: B256,
+        parent_hash: B256,
+        withdrawals: &[Withdrawal],
+    ) {
+        let mut cursors = self.cursors.lock().expect("withdrawal tracker lock");
+        insert_cursor(
+            &mut cursors,
+            block_number,
+            block_hash,
+            parent_hash,
+            withdrawals,
+        );
+        self.persist(&cursors);
+    }
+
+    /// Record every ancestor of `block_hash` (inclusive) missing from the tracker,
+    /// reading them from `history`, so the next cursor after the block is known.
+    ///
+    /// Walks back at most [`TRACKER_RETAINED_BLOCKS`]; an older gap resumes from the
+    /// contract's `head`.
+    pub fn catch_up(&self, history: &impl WithdrawalHistory, block_number: u64, block_hash: B256) {
+        let mut missing = Vec::new();
+        let (mut number, mut hash) = (block_number, block_hash);
+        while missing.len() < TRACKER_RETAINED_BLOCKS && self.next_id_after(number, hash).is_none()
+        {
+            let Some((parent_hash, withdrawals)) = history.block_withdrawals(hash) else {
+                break;
+            };
+            missing.push((number, hash, parent_hash, withdrawals));
+            let Some(parent_number) = number.checked_sub(1) else {
+                break;
+            };
+            (number, hash) = (parent_number, parent_hash);
+        }
+        if missing.is_empty() {
+            return;
+        }
+        let mut cursors = self.cursors.lock().expect("withdrawal tracker lock");
+        for (number, hash, parent_hash, withdrawals) in missing.into_iter().rev() {
+            insert_cursor(&mut cursors, number, hash, parent_hash, &withdrawals);
+        }
+        self.persist(&cursors);
+    }
+
+    /// Save `cursors` to the state file, through a temporary file so a crash never
+    /// leaves a truncated one behind. A failed write is logged; the in-memory cursors
+    /// stay authoritative until the next save.
+    fn persist(&self, cursors: &BTreeMap<u64, QueueCursor>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(err) = write_cursors(path, cursors) {
+            warn!(
+                target: "poa::payload",
+                path = %path.display(),
+                %err,
+                "Failed to save the withdrawal queue state"
+            );
+        }
+    }
+}
+
+fn insert_cursor(
+    cursors: &mut BTreeMap<u64, QueueCursor>,
+    number: u64,
+    hash: B256,
+    parent_hash: B256,
+    withdrawals: &[Withdrawal],
+) {
+    let parent_next = number
+        .checked_sub(1)
+        .and_then(|parent| cursors.get(&parent))
+        .filter(|parent| parent.hash == parent_hash)
+        .map_or(0, |parent| parent.next);
+    let next = withdrawals
+        .iter()
+        .filter(|w| w.validator_index == QUEUE_VALIDATOR_INDEX)
+        .map(|w| w.index + 1)
+        .fold(parent_next, u64::max);
+    cursors.insert(number, QueueCursor { number, hash, next });
+    while cursors.len() > TRACKER_RETAINED_BLOCKS {
+        cursors.pop_first();
+    }
+}
+
+fn write_cursors(
+    path: &Path,
+    cursors: &BTreeMap<u64, QueueCursor>,
+) -> Result<(), TrackerStateError> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(
+        &tmp,
+        serde_json::to_vec(&cursors.values().collect::<Vec<_>>())?,
+    )?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Pending queue entries for the child of canonical block `parent_hash`, as
+/// withdrawals.
+///
+/// `reader` must be the parent block's state; `tracker` must have the parent
+/// recorded (see [`WithdrawalQueueTracker::catch_up`]), otherwise the queue is read
+/// from the contract's `head`.
+pub fn queue_withdrawals(
+    reader: &impl StorageReader,
+    config: &WithdrawalConfig,
+    tracker: &WithdrawalQueueTracker,
+    parent_number: u64,
+    parent_hash: B256,
+) -> Vec<Withdrawal> {
+    if config.max_per_block == 0 {
+        return Vec::new();
+    }
+    let from = tracker
+        .next_id_after(parent_number, parent_hash)
+        .unwrap_or(0);
+    dequeue_withdrawals(reader, config.queue_address, from, config.max_per_block)
+        .into_iter()
+        .map(|entry| Withdrawal {
+            index: entry.id,
+            validator_index: QUEUE_VALIDATOR_INDEX,
+            address: entry.recipient,
+            amount: entry.amount_gwei,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onchain::{encode_address, encode_u64, mapping_u256_slot, withdrawal_queue_slots};
+    use alloy_primitives::{Address, U256};
+    use std::collections::HashMap;
+
+    /// In-memory model of `WithdrawalQueue.sol` storage.
+    struct MockQueue {
+        address: Address,
+        storage: BTreeMap<U256, B256>,
+    }
+
+    impl MockQueue {
+        fn new(address: Address) -> Self {
+            let mut storage = BTreeMap::new();
+            storage.insert(
+                withdrawal_queue_slots::GOVERNANCE,
+                encode_address(Address::repeat_byte(0x99)),
+            );
+            storage.insert(withdrawal_queue_slots::HEAD, encode_u64(0));
+            storage.insert(withdrawal_queue_slots::TAIL, encode_u64(0));
+            Self { address, storage }
+        }
+
+        fn get_u64(&self, slot: U256) -> u64 {
+            crate::onchain::decode_u64(self.storage[&slot])
+        }
+
+        fn entry_slot(id: u64) -> U256 {
+            let hash = mapping_u256_slot(U256::from(id), withdrawal_queue_slots::ENTRIES_MAPPING);
+            U256::from_be_bytes(hash.0)
+        }
+
+        /// `enqueue(recipient, amountGwei)`
+        fn enqueue(&mut self, recipient: Address, amount_gwei: u64) -> u64 {
+            let id = self.get_u64(withdrawal_queue_slots::TAIL);
+            let mut packed = [0u8; 32];
+            packed[4..12].copy_from_slice(&amount_gwei.to_be_bytes());
+            packed[12..32].copy_from_slice(recipient.as_slice());
+            self.storage
+                .insert(Self::entry_slot(id), B256::from(packed));
+            self.storage
+                .insert(withdrawal_queue_slots::TAIL, encode_u64(id + 1));
+            id
+        }
+
+        /// `finalize(ids)`
+        fn finalize(&mut self, ids: &[u64]) {
+            let mut head = self.get_u64(withdrawal_queue_slots::HEAD);
+            for &id in ids {
+                assert_eq!(id, head, "out of order");
+                self.storage.remove(&Self::entry_slot(id));
+                head += 1;
+            }
+            self.storage
+                .insert(withdrawal_queue_slots::HEAD, encode_u64(head));
+        }
+    }
+
+    impl StorageReader for MockQueue {
+        fn read_storage(&self, address: Address, slot: U256) -> Option<B256> {
+            if address != self.address {
+                return None;
+            }
+            self.storage.get(&slot).copied()
+        }
+    }
+
+    fn config(queue: Address, max_per_block: usize) -> WithdrawalConfig {
+        WithdrawalConfig {
+            queue_address: queue,
+            max_per_block,
+        }
+    }
+
+    fn ids(withdrawals: &[Withdrawal]) -> Vec<u64> {
+        withdrawals.iter().map(|w| w.index).collect()
+    }
+
+    fn hash(number: u64) -> B256 {
+        B256::from(U256::from(number + 1))
+    }
+
+    /// Record canonical block `number` of the test chain (hashes from [`hash`]).
+    fn record(tracker: &WithdrawalQueueTracker, number: u64, withdrawals: &[Withdrawal]) {
+        tracker.record_canonical_block(number, hash(number), hash(number - 1), withdrawals);
+    }
+
+    fn queue_withdrawal(index: u64) -> Withdrawal {
+        Withdrawal {
+            index,
+            validator_index: QUEUE_VALIDATOR_INDEX,
+            address: Address::repeat_byte(1),
+            amount: 1,
+        }
+    }
+
+    /// Stored blocks by hash: parent hash and withdrawals.
+    struct MockHistory(HashMap<B256, (B256, Vec<Withdrawal>)>);
+
+    impl WithdrawalHistory for MockHistory {
+        fn block_withdrawals(&self, hash: B256) -> Option<(B256, Vec<Withdrawal>)> {
+            self.0.get(&hash).cloned()
+        }
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("poa-wq-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("withdrawal_queue.json")
+    }
+
+    #[test]
+    fn test_empty_queue_yields_no_withdrawals() {
+        let queue = MockQueue::new(Address::repeat_byte(0x10));
+        let tracker = WithdrawalQueueTracker::new();
+        let cfg = config(queue.address, 8);
+        assert!(queue_withdrawals(&queue, &cfg, &tracker, 0, hash(0)).is_empty());
+    }
+
+    #[test]
+    fn test_undeployed_queue_yields_no_withdrawals() {
+        let queue = MockQueue::new(Address::repeat_byte(0x10));
+        let tracker = WithdrawalQueueTracker::new();
+        let cfg = config(Address::repeat_byte(0x11), 8);
+        assert!(queue_withdrawals(&queue, &cfg, &tracker, 0, hash(0)).is_empty());
+    }
+
+    #[test]
+    fn test_dequeue_maps_entries_to_withdrawals() {
+        let mut queue = MockQueue::new(Address::repeat_byte(0x10));
+        let alice = Address::repeat_byte(0xa1);
+        let bob = Address::repeat_byte(0xb0);
+        queue.enqueue(alice, 1_000_000_000);
+        queue.enqueue(bob, 42);
+
+        let tracker = WithdrawalQueueTracker::new();
+        let out = queue_withdrawals(&queue, &config(queue.address, 8), &tracker, 0, hash(0));
+        assert_eq!(
+            out,
+            vec![
+                Withdrawal {
+                    index: 0,
+                    validator_index: QUEUE_VALIDATOR_INDEX,
+                    address: alice,
+                    amount: 1_000_000_000
+                },
+                Withdrawal {
+                    index: 1,
+                    validator_index: QUEUE_VALIDATOR_INDEX,
+                    address: bob,
+                    amount: 42
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_per_block_limits_and_tracker_advances() {
+        let mut queue = MockQueue::new(Address::repeat_byte(0x10));
+        for i in 0..5u8 {
+            queue.enqueue(Address::repeat_byte(i + 1), 100 + i as u64);
+        }
+        let cfg = config(queue.address, 2);
+        let tracker = WithdrawalQueueTracker::new();
+
+        let block1 = queue_withdrawals(&queue, &cfg, &tracker, 0, hash(0));
+        assert_eq!(ids(&block1), vec![0, 1]);
+        record(&tracker, 1, &block1);
+
+        let block2 = queue_withdrawals(&queue, &cfg, &tracker, 1, hash(1));
+        assert_eq!(ids(&block2), vec![2, 3]);
+        record(&tracker, 2, &block2);
+
+        let block3 = queue_withdrawals(&queue, &cfg, &tracker, 2, hash(2));
+        assert_eq!(ids(&block3), vec![4]);
+        record(&tracker, 3, &block3);
+
+        assert!(queue_withdrawals(&queue, &cfg, &tracker, 3, hash(3)).is_empty());
+    }
+
+    #[test]
+    fn test_unpublished_builds_consume_nothing() {
+        let mut queue = MockQueue::new(Address::repeat_byte(0x10));
+        queue.enqueue(Address::repeat_byte(1), 1);
+        queue.enqueue(Address::repeat_byte(2), 2);
+        let cfg = config(queue.address, 1);
+        let tracker = WithdrawalQueueTracker::new();
+        record(&tracker, 1, &[]);
+
+        // Payloads rebuilt for the same parent, none of them canonical yet.
+        let first = queue_withdrawals(&queue, &cfg, &tracker, 1, hash(1));
+        let again = queue_withdrawals(&queue, &cfg, &tracker, 1, hash(1));
+        assert_eq!(ids(&first), vec![0]);
+        assert_eq!(ids(&first), ids(&again));
+
+        record(&tracker, 2, &first);
+        assert_eq!(
+            ids(&queue_withdrawals(&queue, &cfg, &tracker, 2, hash(2))),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_on_chain_finalize_moves_head() {
+        let mut queue = MockQueue::new(Address::repeat_byte(0x10));
+        for i in 0..3u8 {
+            queue.enqueue(Address::repeat_byte(i + 1), 7);
+        }
+        queue.finalize(&[0, 1]);
+
+        // Fresh tracker (e.g. after restart) resumes from the contract's head.
+        let tracker = WithdrawalQueueTracker::new();
+        let out = queue_withdrawals(&queue, &config(queue.address, 8), &tracker, 10, hash(10));
+        assert_eq!(ids(&out), vec![2]);
+    }
+
+    #[test]
+    fn test_empty_block_carries_cursor_forward() {
+        let tracker = WithdrawalQueueTracker::new();
+        record(
+            &tracker,
+            1,
+            &[
+                queue_withdrawal(0),
+                queue_withdrawal(1),
+                queue_withdrawal(2),
+            ],
+        );
+        record(&tracker, 2, &[]);
+        assert_eq!(tracker.next_id_after(2, hash(2)), Some(3));
+    }
+
+    #[test]
+    fn test_infinite_fund_withdrawals_are_not_queue_ids() {
+        let tracker = WithdrawalQueueTracker::new();
+        let fund = Withdrawal {
+            index: 40,
+            validator_index: 0,
+            ..queue_withdrawal(0)
+        };
+        record(&tracker, 1, &[fund, queue_withdrawal(5)]);
+        assert_eq!(tracker.next_id_after(1, hash(1)), Some(6));
+    }
+
+    #[test]
+    fn test_cursor_is_bound_to_block_hash() {
+        let tracker = WithdrawalQueueTracker::new();
+        record(&tracker, 1, &[queue_withdrawal(0)]);
+        assert_eq!(tracker.next_id_after(1, B256::repeat_byte(0xee)), None);
+
+        // A sibling of block 2 does not continue from the orphaned block 1.
+        let orphan_parent = B256::repeat_byte(0xee);
+        tracker.record_canonical_block(2, hash(2), orphan_parent, &[]);
+        assert_eq!(tracker.next_id_after(2, hash(2)), Some(0));
+
+        // Block 1 replaced by a reorg.
+        tracker.record_canonical_block(1, B256::repeat_byte(0xaa), hash(0), &[]);
+        assert_eq!(tracker.next_id_after(1, hash(1)), None);
+        assert_eq!(tracker.next_id_after(1, B256::repeat_byte(0xaa)), Some(0));
+    }
+
+    #[test]
+    fn test_catch_up_records_missed_ancestors() {
+        let tracker = WithdrawalQueueTracker::new();
+        record(&tracker, 1, &[queue_withdrawal(0)]);
+        // Blocks 2 and 3 were sealed elsewhere and not yet recorded here.
+        let history = MockHistory(HashMap::from([
+            (
+                hash(2),
+                (hash(1), vec![queue_withdrawal(1), queue_withdrawal(2)]),
+            ),
+            (hash(3), (hash(2), vec![])),
+        ]));
+        tracker.catch_up(&history, 3, hash(3));
+        assert_eq!(tracker.next_id_after(2, hash(2)), Some(3));
+        assert_eq!(tracker.next_id_after(3, hash(3)), Some(3));
+
+        // Unknown blocks are left unrecorded.
+        tracker.catch_up(&history, 9, hash(9));
+        assert_eq!(tracker.next_id_after(9, hash(9)), None);
+    }
+
+    #[test]
+    fn test_tracker_prunes_old_blocks() {
+        let tracker = WithdrawalQueueTracker::new();
+        let last = TRACKER_RETAINED_BLOCKS as u64 + 10;
+        for block in 1..=last {
+            record(&tracker, block, &[queue_withdrawal(block)]);
+        }
+        assert_eq!(tracker.next_id_after(1, hash(1)), None);
+        assert_eq!(tracker.next_id_after(last, hash(last)), Some(last + 1));
+    }
+
+    #[test]
+    fn test_tracker_state_survives_restart() {
+        let path = temp_file("restart");
+        let tracker = WithdrawalQueueTracker::open(&path).unwrap();
+        record(&tracker, 1, &[queue_withdrawal(0), queue_withdrawal(1)]);
+        record(&tracker, 2, &[]);
+        drop(tracker);
+
+        let reopened = WithdrawalQueueTracker::open(&path).unwrap();
+        assert_eq!(reopened.next_id_after(2, hash(2)), Some(2));
+        record(&reopened, 3, &[queue_withdrawal(2)]);
+        assert_eq!(reopened.next_id_after(3, hash(3)), Some(3));
+
+        fs::write(&path, b"not json").unwrap();
+        assert!(matches!(
+            WithdrawalQueueTracker::open(&path),
+            Err(TrackerStateError::Json(_))
+        ));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_zero_max_per_block_disables_queue() {
+        let mut queue = MockQueue::new(Address::repeat_byte(0x10));
+        queue.enqueue(Address::repeat_byte(1), 1);
+        let tracker = WithdrawalQueueTracker::new();
+        let cfg = config(queue.address, 0);
+        assert!(queue_withdrawals(&queue, &cfg, &tracker, 0, hash(0)).is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use alloy_primitives::Address;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
-use super::types::{ChainConfigResponse, NodeInfoResponse};
+use super::types::{BlockVanityResponse, ChainConfigResponse, NodeInfoResponse};
 
 /// The `meow_*` RPC namespace definition.
 #[rpc(server, namespace = "meow")]
@@ -17,4 +17,8 @@ pub trait MeowApi {
     /// Returns node information including local signer status.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfoResponse>;
+
+    /// Returns the decoded extra_data vanity of a block, or `null` if the block is unknown.
+    #[method(name = "blockVanity")]
+    async fn block_vanity(&self, number: u64) -> RpcResult<Option<BlockVanityResponse>>;
 }
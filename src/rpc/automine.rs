@@ -0,0 +1,144 @@
+//! RPC middleware that makes transaction submission synchronous in automine mode.
+//!
+//! [`AutomineLayer`] wraps reth's RPC service. While automine is on,
+//! `eth_sendRawTransaction` responds only once the submitted transaction is in a
+//! canonical block (see [`InclusionTracker`]), so a receipt query issued right after
+//! it succeeds. If the transaction is not mined within the timeout, the hash is
+//! returned as usual and the caller falls back to polling.
+
+use super::backpressure::SEND_RAW_TRANSACTION;
+use crate::node::automine::{AutomineControl, InclusionTracker, DEFAULT_AUTOMINE_TIMEOUT};
+use alloy_primitives::B256;
+use jsonrpsee::core::middleware::{Batch, Notification};
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+use jsonrpsee::types::Request;
+use jsonrpsee::MethodResponse;
+use reth_tracing::tracing::warn;
+use std::future::Future;
+use std::time::Duration;
+use tower::Layer;
+
+/// Tower layer installing [`AutomineService`] as RPC middleware.
+#[derive(Debug, Clone)]
+pub struct AutomineLayer {
+    control: AutomineControl,
+    tracker: InclusionTracker,
+    timeout: Duration,
+}
+
+impl Default for AutomineLayer {
+    fn default() -> Self {
+        Self::new(AutomineControl::disabled(), InclusionTracker::new())
+    }
+}
+
+impl AutomineLayer {
+    /// Hold submissions until `tracker` reports them mined, while `control` is in
+    /// automine mode.
+    pub fn new(control: AutomineControl, tracker: InclusionTracker) -> Self {
+        Self {
+            control,
+            tracker,
+            timeout: DEFAULT_AUTOMINE_TIMEOUT,
+        }
+    }
+
+    /// Override how long a submission waits for its block.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl<S> Layer<S> for AutomineLayer {
+    type Service = AutomineService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AutomineService {
+            inner,
+            control: self.control.clone(),
+            tracker: self.tracker.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// RPC service that waits for `eth_sendRawTransaction` inclusion in automine mode.
+#[derive(Debug, Clone)]
+pub struct AutomineService<S> {
+    inner: S,
+    control: AutomineControl,
+    tracker: InclusionTracker,
+    timeout: Duration,
+}
+
+impl<S> RpcServiceT for AutomineService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type MethodResponse = MethodResponse;
+    type NotificationResponse = MethodResponse;
+    type BatchResponse = MethodResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let wait = req.method_name() == SEND_RAW_TRANSACTION && self.control.is_automine();
+        let inner = self.inner.clone();
+        let tracker = self.tracker.clone();
+        let timeout = self.timeout;
+        async move {
+            let response = inner.call(req).await;
+            if !wait || !response.is_success() {
+                return response;
+            }
+            if let Some(hash) = submitted_hash(response.as_json().get()) {
+                if let Err(err) = tracker.wait_for(hash, timeout).await {
+                    warn!(target: "poa::automine", %err, "Returning unmined transaction hash");
+                }
+            }
+            response
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Transaction hash from a successful `eth_sendRawTransaction` JSON-RPC response.
+pub fn submitted_hash(response: &str) -> Option<B256> {
+    let value: serde_json::Value = serde_json::from_str(response).ok()?;
+    value.get("result")?.as_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submitted_hash_parses_result() {
+        let hash = B256::repeat_byte(0xab);
+        let response = format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{hash}"}}"#);
+        assert_eq!(submitted_hash(&response), Some(hash));
+    }
+
+    #[test]
+    fn test_submitted_hash_ignores_errors() {
+        let response = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"nonce"}}"#;
+        assert_eq!(submitted_hash(response), None);
+        assert_eq!(submitted_hash("not json"), None);
+    }
+}
@@ -0,0 +1,261 @@
+//! RPC middleware that refuses transaction submissions while the pool is overloaded.
+//!
+//! [`BackpressureLayer`] wraps reth's RPC service. While the shared
+//! [`Backpressure`] controller is overloaded, `eth_sendRawTransaction` calls are
+//! answered with [`OVERLOADED_ERROR_CODE`](super::errors::OVERLOADED_ERROR_CODE) and a
+//! retry hint instead of reaching the pool. Each submission inside a JSON-RPC batch
+//! is gated the same way; every other method passes through unchanged.
+
+use super::errors::overload_error_to_rpc;
+use crate::node::backpressure::Backpressure;
+use jsonrpsee::core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification};
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+use jsonrpsee::types::{ErrorObjectOwned, Request};
+use jsonrpsee::MethodResponse;
+use std::future::Future;
+use tower::Layer;
+
+/// Method gated by back-pressure.
+pub const SEND_RAW_TRANSACTION: &str = "eth_sendRawTransaction";
+
+/// Tower layer installing [`BackpressureService`] as RPC middleware.
+#[derive(Debug, Clone, Default)]
+pub struct BackpressureLayer {
+    backpressure: Backpressure,
+}
+
+impl BackpressureLayer {
+    /// Gate submissions on `backpressure`.
+    pub fn new(backpressure: Backpressure) -> Self {
+        Self { backpressure }
+    }
+}
+
+impl<S> Layer<S> for BackpressureLayer {
+    type Service = BackpressureService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BackpressureService {
+            inner,
+            backpressure: self.backpressure.clone(),
+        }
+    }
+}
+
+/// RPC service that rejects `eth_sendRawTransaction` while overloaded.
+#[derive(Debug, Clone)]
+pub struct BackpressureService<S> {
+    inner: S,
+    backpressure: Backpressure,
+}
+
+impl<S> BackpressureService<S> {
+    /// Error for `req` if it is a submission refused by the controller.
+    fn rejection(&self, req: &Request<'_>) -> Option<ErrorObjectOwned> {
+        if req.method_name() != SEND_RAW_TRANSACTION {
+            return None;
+        }
+        let err = self.backpressure.check_rpc_submission().err()?;
+        Some(overload_error_to_rpc(&err))
+    }
+}
+
+impl<S> RpcServiceT for BackpressureService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type MethodResponse = MethodResponse;
+    type NotificationResponse = MethodResponse;
+    type BatchResponse = MethodResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let rejection = self.rejection(&req);
+        let inner = self.inner.clone();
+        async move {
+            match rejection {
+                Some(err) => MethodResponse::error(req.id().clone(), err),
+                None => inner.call(req).await,
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut req: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // A batch must not smuggle submissions past the gate: each one counts.
+        for entry in req.iter_mut() {
+            let Ok(BatchEntry::Call(call)) = entry else {
+                continue;
+            };
+            if let Some(err) = self.rejection(call) {
+                let id = call.id().clone();
+                *entry = Err(BatchEntryErr::new(id, err));
+            }
+        }
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::backpressure::{BackpressureConfig, OverloadState};
+    use jsonrpsee::types::{Id, ResponsePayload};
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction, TestPool};
+    use reth_transaction_pool::{TransactionOrigin, TransactionPool};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Stands in for reth's `eth_sendRawTransaction`: every call that reaches it is
+    /// added to the pool as a local transaction.
+    #[derive(Clone)]
+    struct PoolRpc {
+        pool: TestPool,
+        batch_rejected: Arc<AtomicUsize>,
+    }
+
+    impl PoolRpc {
+        async fn submit(&self) {
+            let tx = MockTransaction::eip1559();
+            self.pool
+                .add_transaction(TransactionOrigin::Local, tx)
+                .await
+                .unwrap();
+        }
+    }
+
+    fn ok(id: Id<'_>) -> MethodResponse {
+        MethodResponse::response(id, ResponsePayload::success(true), usize::MAX)
+    }
+
+    impl RpcServiceT for PoolRpc {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            let this = self.clone();
+            async move {
+                this.submit().await;
+                ok(req.id().clone())
+            }
+        }
+
+        fn batch<'a>(
+            &self,
+            mut req: Batch<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            let this = self.clone();
+            async move {
+                for entry in req.iter_mut() {
+                    match entry {
+                        Ok(_) => this.submit().await,
+                        Err(_) => {
+                            this.batch_rejected.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                ok(Id::Null)
+            }
+        }
+
+        fn notification<'a>(
+            &self,
+            _n: Notification<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            async { ok(Id::Null) }
+        }
+    }
+
+    fn submission(id: u64) -> Request<'static> {
+        Request::owned(SEND_RAW_TRANSACTION.to_string(), None, Id::Number(id))
+    }
+
+    /// Spam load through the RPC middleware into a real pool: 500 tx/s for 30 s (40
+    /// single calls and a batch of 10 every 100 ms) against canonical blocks that
+    /// include 600 transactions every 2 s, with the pool monitor sampling every 100 ms.
+    #[tokio::test(start_paused = true)]
+    async fn test_spam_load_stays_bounded_and_recovers() {
+        const HIGH: usize = 1_000;
+        const LOW: usize = 400;
+        const PER_TICK: usize = 50;
+        const TICK: Duration = Duration::from_millis(100);
+
+        let pool = testing_pool();
+        let backpressure = Backpressure::new(BackpressureConfig::new(HIGH, LOW));
+        let batch_rejected = Arc::new(AtomicUsize::new(0));
+        let rpc = BackpressureLayer::new(backpressure.clone()).layer(PoolRpc {
+            pool: pool.clone(),
+            batch_rejected: batch_rejected.clone(),
+        });
+        tokio::spawn(backpressure.clone().monitor_pool(pool.clone(), TICK));
+
+        let mut peak = 0usize;
+        let mut rejected = 0usize;
+        let mut block = 0u64;
+        let mut id = 0u64;
+        for tick in 0..600u64 {
+            if tick < 300 {
+                for _ in 0..PER_TICK - 10 {
+                    id += 1;
+                    let response = rpc.call(submission(id)).await;
+                    if response.is_error() {
+                        rejected += 1;
+                    }
+                }
+                let mut batch = Batch::new();
+                for _ in 0..10 {
+                    id += 1;
+                    batch.push(submission(id)).unwrap();
+                }
+                rpc.batch(batch).await;
+            }
+            if tick % 20 == 0 {
+                let included: Vec<_> = pool
+                    .best_transactions()
+                    .take(600)
+                    .map(|tx| *tx.hash())
+                    .collect();
+                let count = pool.remove_transactions(included).len();
+                block += 1;
+                let now = tokio::time::Instant::now().into_std();
+                backpressure.record_block(block, count, now);
+            }
+            peak = peak.max(pool.pool_size().total);
+            tokio::time::sleep(TICK).await;
+        }
+
+        // Ingress stops within a sampling tick or two of crossing the high watermark.
+        assert!(peak < HIGH + 2 * PER_TICK, "peak occupancy {peak}");
+        assert_eq!(pool.pool_size().total, 0);
+        assert_eq!(backpressure.state(), OverloadState::Normal);
+
+        let status = backpressure.status();
+        assert!(status.overload_entered >= 1);
+        assert_eq!(status.overload_entered, status.overload_cleared);
+        assert!(rejected > 0);
+        // Batched submissions were refused with the rest, not smuggled into the pool.
+        assert!(batch_rejected.load(Ordering::Relaxed) > 0);
+        assert_eq!(
+            status.rpc_rejected as usize,
+            rejected + batch_rejected.load(Ordering::Relaxed)
+        );
+        assert!(status.drain_rate > 0.0);
+    }
+}
@@ -7,10 +7,11 @@
 use alloy_primitives::{Address, B256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::Arc;
 
 use super::clique_types::*;
 use crate::chainspec::PoaChainSpec;
+use crate::consensus::{VoteType, VoterSet};
 use crate::signer::SignerManager;
 
 /// The `clique_*` RPC namespace - standard Clique POA API.
@@ -38,14 +39,21 @@ pub trait CliqueApi {
     /// Propose a new signer (authorize=true) or remove an existing one (authorize=false).
     /// This is a local proposal; the vote is included in subsequent blocks signed by us.
     #[method(name = "propose")]
-    async fn propose(&self, address: Address, authorize: bool) -> RpcResult<()>;
+    async fn propose(&self, address: Address, authorize: bool) -> RpcResult<()> {
+        let action = if authorize {
+            VoteType::Add
+        } else {
+            VoteType::Remove
+        };
+        self.votes.propose(address, action);
+        Ok(())
+    }
 
-    /// Remove a previously proposed signer vote.
-    #[method(name = "discard")]
-    async fn discard(&self, address: Address) -> RpcResult<()>;
+    async fn discard(&self, address: Address) -> RpcResult<()> {
+        self.votes.discard(&address);
+        Ok(())
+    }
 
-    /// Returns the current signing status/activity.
-    #[method(name = "status")]
     async fn status(&self) -> RpcResult<CliqueStatus>;
 
     /// Returns all current proposals.
@@ -60,9 +68,8 @@ pub struct CliqueRpc {
     /// Reserved for future use in status enrichment and historical block lookups.
     #[allow(dead_code)]
     signer_manager: Arc<SignerManager>,
-    /// Local proposals: address -> authorize (true=add, false=remove).
-    /// Protected by `RwLock` for concurrent access from RPC handlers.
-    proposals: Arc<RwLock<HashMap<Address, bool>>>,
+    /// Local proposals and the canonical vote tally, shared with the payload builder.
+    votes: VoterSet,
 }
 
 impl CliqueRpc {
@@ -71,33 +78,38 @@ impl CliqueRpc {
         Self {
             chain_spec,
             signer_manager,
-            proposals: Arc::new(RwLock::new(HashMap::new())),
+            votes: VoterSet::new(),
         }
     }
 
-    /// Build a snapshot from the current chain state and local proposals.
+    /// Keep proposals in, and report the tally of, the node's shared votes.
+    pub fn with_voter_set(mut self, votes: VoterSet) -> Self {
+        self.votes = votes;
+        self
+    }
+
+    /// Build a snapshot from the current chain state and the canonical vote tally.
     ///
     /// Uses `effective_signers()` to respect live on-chain governance changes
     /// (same as consensus and payload builder).
     fn current_snapshot(&self) -> CliqueSnapshot {
         let signers = self.chain_spec.effective_signers();
-        let proposals = self.proposals.read().unwrap_or_else(|e| e.into_inner());
-
-        // Build both `votes` and `tally` in a single pass over `proposals` to
-        // avoid iterating the map twice.
-        let mut votes = Vec::with_capacity(proposals.len());
-        let mut tally = HashMap::with_capacity(proposals.len());
-        for (addr, auth) in proposals.iter() {
-            votes.push(CliqueVote {
-                signer: Address::ZERO, // local node proposal
-                address: *addr,
-                authorize: *auth,
-            });
+        let pending = self.votes.pending(&signers);
+
+        let mut votes = Vec::new();
+        let mut tally = HashMap::with_capacity(pending.len());
+        for vote in pending {
+            let authorize = vote.action == VoteType::Add;
+            votes.extend(vote.votes_for.iter().map(|signer| CliqueVote {
+                signer: *signer,
+                address: vote.candidate,
+                authorize,
+            }));
             tally.insert(
-                *addr,
+                vote.candidate,
                 CliqueTally {
-                    authorize: *auth,
-                    votes: 1,
+                    authorize,
+                    votes: vote.votes_for.len() as u64,
                 },
             );
         }
@@ -137,12 +149,27 @@ impl CliqueApiServer for CliqueRpc {
     async fn propose(&self, address: Address, authorize: bool) -> RpcResult<()> {
         let mut proposals = self.proposals.write().unwrap_or_else(|e| e.into_inner());
         proposals.insert(address, authorize);
+        let action = if authorize {
+            VoteType::Add
+        } else {
+            VoteType::Remove
+        };
+        let signers = self.chain_spec.effective_signers();
+        for voter in self.signer_manager.signer_addresses() {
+            if self.votes.cast(&signers, voter, address, action).is_some() {
+                // Quorum reached; the change itself goes through the SignerRegistry.
+                break;
+            }
+        }
         Ok(())
     }
 
     async fn discard(&self, address: Address) -> RpcResult<()> {
         let mut proposals = self.proposals.write().unwrap_or_else(|e| e.into_inner());
         proposals.remove(&address);
+        for voter in self.signer_manager.signer_addresses() {
+            self.votes.discard(&voter, &address);
+        }
         Ok(())
     }
 
@@ -166,9 +193,12 @@ impl CliqueApiServer for CliqueRpc {
     }
 
     async fn proposals(&self) -> RpcResult<CliqueProposals> {
-        let proposals = self.proposals.read().unwrap_or_else(|e| e.into_inner());
+        let proposals = self.votes.proposals();
         Ok(CliqueProposals {
-            proposals: proposals.clone(),
+            proposals: proposals
+                .into_iter()
+                .map(|(address, action)| (address, action == VoteType::Add))
+                .collect(),
         })
     }
 }
@@ -362,25 +392,43 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_snapshot_includes_proposals_as_votes() {
-        let rpc = make_rpc(test_chain_spec());
+    async fn test_snapshot_reports_sealed_votes() {
+        let chain = test_chain_spec();
+        let signers = chain.signers().to_vec();
+        let votes = VoterSet::new();
+        let rpc = make_rpc(chain).with_voter_set(votes.clone());
         let addr = Address::with_last_byte(0x42);
 
+        // A local proposal is not a vote until a sealed block carries it.
         rpc.propose(addr, true).await.unwrap();
+        assert!(rpc.get_snapshot().await.unwrap().votes.is_empty());
 
+        votes.cast(&signers, signers[0], addr, VoteType::Add);
         let snapshot = rpc.get_snapshot().await.unwrap();
         assert_eq!(snapshot.votes.len(), 1);
         assert_eq!(snapshot.votes[0].address, addr);
         assert!(snapshot.votes[0].authorize);
-        assert_eq!(snapshot.votes[0].signer, Address::ZERO);
+        assert_eq!(snapshot.votes[0].signer, signers[0]);
 
-        // Tally should also reflect the proposal
+        // Tally should also reflect the vote
         assert_eq!(snapshot.tally.len(), 1);
         let tally = snapshot.tally.get(&addr).unwrap();
         assert!(tally.authorize);
         assert_eq!(tally.votes, 1);
     }
 
+    #[tokio::test]
+    async fn test_proposals_are_shared_with_voter_set() {
+        let votes = VoterSet::new();
+        let rpc = make_rpc(test_chain_spec()).with_voter_set(votes.clone());
+        let addr = Address::with_last_byte(0x42);
+
+        rpc.propose(addr, false).await.unwrap();
+        assert_eq!(votes.proposals().get(&addr), Some(&VoteType::Remove));
+        rpc.discard(addr).await.unwrap();
+        assert!(votes.proposals().is_empty());
+    }
+
     #[tokio::test]
     async fn test_snapshot_at_hash_returns_current() {
         let chain = test_chain_spec();
@@ -596,7 +644,7 @@ mod tests {
     // ── propose + snapshot interaction ──
 
     #[tokio::test]
-    async fn test_propose_discard_lifecycle_reflected_in_snapshot() {
+    async fn test_propose_discard_lifecycle_not_in_snapshot() {
         let rpc = make_rpc(test_chain_spec());
         let addr1 = Address::with_last_byte(0x01);
         let addr2 = Address::with_last_byte(0x02);
@@ -604,19 +652,18 @@ mod tests {
         // Propose two addresses
         rpc.propose(addr1, true).await.unwrap();
         rpc.propose(addr2, false).await.unwrap();
+        assert_eq!(rpc.proposals().await.unwrap().proposals.len(), 2);
 
+        // Nothing is sealed yet, so the snapshot holds no votes
         let snapshot = rpc.get_snapshot().await.unwrap();
-        assert_eq!(snapshot.votes.len(), 2);
-        assert_eq!(snapshot.tally.len(), 2);
+        assert!(snapshot.votes.is_empty());
+        assert!(snapshot.tally.is_empty());
 
         // Discard one
         rpc.discard(addr1).await.unwrap();
-
-        let snapshot = rpc.get_snapshot().await.unwrap();
-        assert_eq!(snapshot.votes.len(), 1);
-        assert_eq!(snapshot.tally.len(), 1);
-        assert!(snapshot.tally.contains_key(&addr2));
-        assert!(!snapshot.tally.contains_key(&addr1));
+        let proposals = rpc.proposals().await.unwrap().proposals;
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals.get(&addr2), Some(&false));
     }
 
     // ── signer_manager integration ──
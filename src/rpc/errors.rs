@@ -0,0 +1,271 @@
+//! JSON-RPC error mapping for POA seal, consensus and back-pressure failures.
+//!
+//! Errors carry the variant name in `data.kind` (see [`PoaConsensusError::kind`]) so
+//! clients can branch on the violated rule instead of parsing the message.
+
+use crate::consensus::PoaConsensusError;
+use crate::node::backpressure::OverloadError;
+use crate::node::ReplayError;
+use crate::rpc::rate_limit::RateLimitError;
+use crate::signer::SealError;
+use jsonrpsee::types::ErrorObjectOwned;
+use serde_json::json;
+
+/// Error code for a header whose seal cannot be verified.
+pub const SEAL_ERROR_CODE: i32 = -32010;
+
+/// Error code for a header or block that violates a POA consensus rule.
+pub const CONSENSUS_ERROR_CODE: i32 = -32011;
+
+/// Error code for a block this node failed to seal with its own key.
+pub const SIGNER_ERROR_CODE: i32 = -32012;
+
+/// Error code for a checkpoint chain that fails verification.
+pub const CHECKPOINT_ERROR_CODE: i32 = -32013;
+
+/// Error code for a block carrying a transaction from a sender refused by the ACL.
+pub const ACCESS_DENIED_ERROR_CODE: i32 = -32014;
+
+/// Error code for a block that could not be replayed.
+pub const REPLAY_ERROR_CODE: i32 = -32015;
+
+/// Error code for a transaction submission refused under back-pressure
+/// ("limit exceeded", as used by other Ethereum clients for rate limiting).
+pub const OVERLOADED_ERROR_CODE: i32 = -32005;
+
+/// JSON-RPC error code for a consensus rule violation, in the server-error range.
+///
+/// Seal failures share [`SEAL_ERROR_CODE`], checkpoint verification failures
+/// [`CHECKPOINT_ERROR_CODE`], refused senders [`ACCESS_DENIED_ERROR_CODE`] and local
+/// signing failures [`SIGNER_ERROR_CODE`]; other rule violations are
+/// [`CONSENSUS_ERROR_CODE`].
+pub fn consensus_error_code(err: &PoaConsensusError) -> i32 {
+    match err {
+        PoaConsensusError::InvalidSignature(_) | PoaConsensusError::InvalidSignatureAtBlock(_) => {
+            SEAL_ERROR_CODE
+        }
+        PoaConsensusError::SealingFailed(_) => SIGNER_ERROR_CODE,
+        PoaConsensusError::EmptyCheckpointChain
+        | PoaConsensusError::CheckpointGap { .. }
+        | PoaConsensusError::UnderivableSignerSet { .. }
+        | PoaConsensusError::CheckpointMismatch { .. } => CHECKPOINT_ERROR_CODE,
+        PoaConsensusError::BlacklistedSender(_) | PoaConsensusError::SenderNotWhitelisted(_) => {
+            ACCESS_DENIED_ERROR_CODE
+        }
+        PoaConsensusError::UnauthorizedSigner { .. }
+        | PoaConsensusError::ExtraDataTooShort { .. }
+        | PoaConsensusError::TimestampTooEarly { .. }
+        | PoaConsensusError::TimestampTooFarInFuture { .. }
+        | PoaConsensusError::WrongSigner { .. }
+        | PoaConsensusError::InvalidDifficulty
+        | PoaConsensusError::InvalidSignerList
+        | PoaConsensusError::InvalidBlockMetadata(_)
+        | PoaConsensusError::GasLimitTooLow { .. }
+        | PoaConsensusError::GasLimitTooHigh { .. }
+        | PoaConsensusError::BaseFeeBeforeLondon
+        | PoaConsensusError::InvalidVanity { .. }
+        | PoaConsensusError::TransactionsRootMismatch { .. }
+        | PoaConsensusError::NonEmptyOmmersHash { .. }
+        | PoaConsensusError::DynamicFeeTransaction { .. }
+        | PoaConsensusError::BlockTooLarge { .. }
+        | PoaConsensusError::InvalidParentBeaconRoot { .. }
+        | PoaConsensusError::EpochExtraDataMismatch { .. }
+        | PoaConsensusError::VoteInEpochBlock { .. }
+        | PoaConsensusError::InvalidVote { .. }
+        | PoaConsensusError::ReceiptBlockHashMismatch { .. }
+        | PoaConsensusError::BlockMetadataHashMismatch { .. }
+        | PoaConsensusError::DisallowedTransactionType { .. }
+        | PoaConsensusError::SignerUnderstaked { .. }
+        | PoaConsensusError::ParentStateUnavailable { .. }
+        | PoaConsensusError::InvalidSignerReward { .. } => CONSENSUS_ERROR_CODE,
+    }
+}
+
+/// Convert a seal verification failure into a JSON-RPC error.
+pub fn seal_error_to_rpc(err: &SealError) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        SEAL_ERROR_CODE,
+        err.to_string(),
+        Some(json!({ "kind": err.kind() })),
+    )
+}
+
+/// Convert a consensus rule violation into a JSON-RPC error.
+///
+/// The code comes from [`consensus_error_code`] and `data` is the
+/// serialized error. Signature failures also carry the seal cause as `data.seal`.
+pub fn consensus_error_to_rpc(err: &PoaConsensusError) -> ErrorObjectOwned {
+    let mut data = serde_json::to_value(err).unwrap_or_else(|_| json!({ "kind": err.kind() }));
+    if let Some(seal) = err.seal_error() {
+        data["seal"] = json!(seal.kind());
+    }
+    ErrorObjectOwned::owned(consensus_error_code(err), err.to_string(), Some(data))
+}
+
+/// Convert a back-pressure rejection into a JSON-RPC error.
+///
+/// `data.retryAfter` carries the retry hint in seconds.
+pub fn overload_error_to_rpc(err: &OverloadError) -> ErrorObjectOwned {
+    let OverloadError::Overloaded {
+        occupancy,
+        retry_after,
+    } = err;
+    ErrorObjectOwned::owned(
+        OVERLOADED_ERROR_CODE,
+        err.to_string(),
+        Some(json!({ "retryAfter": retry_after.as_secs(), "occupancy": occupancy })),
+    )
+}
+
+/// Convert a block replay failure into a JSON-RPC error.
+pub fn replay_error_to_rpc(err: &ReplayError) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(REPLAY_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+/// Convert a per-method rate limit rejection into a JSON-RPC error.
+///
+/// Shares [`OVERLOADED_ERROR_CODE`] ("limit exceeded") with back-pressure;
+/// `data.retryAfter` carries the retry hint in whole seconds, rounded up.
+pub fn rate_limit_error_to_rpc(err: &RateLimitError) -> ErrorObjectOwned {
+    let RateLimitError::LimitExceeded {
+        method,
+        max_per_minute,
+        retry_after,
+    } = err;
+    let retry_after = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    ErrorObjectOwned::owned(
+        OVERLOADED_ERROR_CODE,
+        err.to_string(),
+        Some(json!({
+            "retryAfter": retry_after,
+            "method": method,
+            "maxPerMinute": max_per_minute,
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::SignerError;
+    use alloy_primitives::{Address, B256};
+
+    fn data(err: &ErrorObjectOwned) -> serde_json::Value {
+        serde_json::from_str(err.data().unwrap().get()).unwrap()
+    }
+
+    #[test]
+    fn test_seal_error_maps_kind() {
+        let err = seal_error_to_rpc(&SealError::ExtraDataTooShort {
+            len: 3,
+            required: 65,
+        });
+        assert_eq!(err.code(), SEAL_ERROR_CODE);
+        assert_eq!(data(&err)["kind"], "extraDataTooShort");
+    }
+
+    #[test]
+    fn test_consensus_signature_error_reports_seal_cause() {
+        let cause = SealError::MalformedSignature(SignerError::InvalidRecoveryId(9));
+        let err = consensus_error_to_rpc(&PoaConsensusError::InvalidSignature(cause));
+        assert_eq!(err.code(), SEAL_ERROR_CODE);
+        assert_eq!(data(&err)["kind"], "invalidSignature");
+        assert_eq!(data(&err)["seal"], "malformedSignature");
+    }
+
+    #[test]
+    fn test_consensus_rule_error_maps_kind() {
+        let err = consensus_error_to_rpc(&PoaConsensusError::GasLimitTooLow { got: 1, min: 5000 });
+        assert_eq!(err.code(), CONSENSUS_ERROR_CODE);
+        assert_eq!(data(&err)["kind"], "gasLimitTooLow");
+        assert!(err.message().contains("below the minimum"));
+    }
+
+    #[test]
+    fn test_consensus_error_serializes_hex_fields() {
+        let err = PoaConsensusError::CheckpointMismatch {
+            number: 30_000,
+            expected: B256::repeat_byte(0xaa),
+            got: B256::repeat_byte(0xbb),
+        };
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["kind"], err.kind());
+        assert_eq!(value["details"]["number"], "0x7530");
+        assert_eq!(
+            value["details"]["expected"],
+            format!("{:?}", B256::repeat_byte(0xaa))
+        );
+
+        let data = data(&consensus_error_to_rpc(&err));
+        assert_eq!(data, value);
+    }
+
+    #[test]
+    fn test_consensus_error_codes_by_category() {
+        let signer = Address::repeat_byte(0x11);
+        assert_eq!(
+            consensus_error_code(&PoaConsensusError::InvalidSignatureAtBlock(7)),
+            SEAL_ERROR_CODE
+        );
+        assert_eq!(
+            consensus_error_code(&PoaConsensusError::SealingFailed(
+                SignerError::InvalidPrivateKey
+            )),
+            SIGNER_ERROR_CODE
+        );
+        assert_eq!(
+            consensus_error_code(&PoaConsensusError::EmptyCheckpointChain),
+            CHECKPOINT_ERROR_CODE
+        );
+        assert_eq!(
+            consensus_error_code(&PoaConsensusError::BlacklistedSender(signer)),
+            ACCESS_DENIED_ERROR_CODE
+        );
+        assert_eq!(
+            consensus_error_code(&PoaConsensusError::InvalidDifficulty),
+            CONSENSUS_ERROR_CODE
+        );
+        for code in [
+            SEAL_ERROR_CODE,
+            CONSENSUS_ERROR_CODE,
+            SIGNER_ERROR_CODE,
+            CHECKPOINT_ERROR_CODE,
+            ACCESS_DENIED_ERROR_CODE,
+        ] {
+            assert!((-32099..=-32000).contains(&code));
+        }
+    }
+
+    #[test]
+    fn test_signer_error_serializes_source_as_message() {
+        let err = PoaConsensusError::SealingFailed(SignerError::SigningFailed("hsm".into()));
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["kind"], "sealingFailed");
+        assert_eq!(value["details"]["kind"], "signingFailed");
+        assert_eq!(value["details"]["details"], "hsm");
+    }
+
+    #[test]
+    fn test_rate_limit_error_carries_retry_hint() {
+        let err = rate_limit_error_to_rpc(&RateLimitError::LimitExceeded {
+            method: "eth_getLogs".to_string(),
+            max_per_minute: 1,
+            retry_after: std::time::Duration::from_millis(58_500),
+        });
+        assert_eq!(err.code(), OVERLOADED_ERROR_CODE);
+        assert_eq!(data(&err)["retryAfter"], 59);
+        assert_eq!(data(&err)["method"], "eth_getLogs");
+        assert_eq!(data(&err)["maxPerMinute"], 1);
+    }
+
+    #[test]
+    fn test_overload_error_carries_retry_hint() {
+        let err = overload_error_to_rpc(&OverloadError::Overloaded {
+            occupancy: 9_000,
+            retry_after: std::time::Duration::from_secs(12),
+        });
+        assert_eq!(err.code(), OVERLOADED_ERROR_CODE);
+        assert_eq!(data(&err)["retryAfter"], 12);
+        assert_eq!(data(&err)["occupancy"], 9_000);
+    }
+}
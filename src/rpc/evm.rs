@@ -0,0 +1,76 @@
+//! EVM Dev RPC Namespace
+//!
+//! Hardhat/Anvil-compatible `evm_*` mining controls for `--automine` dev nodes.
+//! Registered only when the node runs with `--automine`.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::node::automine::AutomineControl;
+
+/// The `evm_*` RPC namespace - dev-mode mining controls.
+#[rpc(server, namespace = "evm")]
+pub trait EvmDevApi {
+    /// Enable or disable automine. Disabling falls back to interval mining, or to
+    /// no mining when no interval is set.
+    #[method(name = "setAutomine")]
+    async fn set_automine(&self, enabled: bool) -> RpcResult<()>;
+
+    /// Mine a block every `interval_ms` milliseconds (`0` disables interval mining).
+    /// A non-zero interval turns automine off.
+    #[method(name = "setIntervalMining")]
+    async fn set_interval_mining(&self, interval_ms: u64) -> RpcResult<()>;
+}
+
+/// Implementation of the `evm_*` RPC namespace.
+#[derive(Debug, Clone)]
+pub struct EvmDevRpc {
+    control: AutomineControl,
+}
+
+impl EvmDevRpc {
+    /// Drive the shared automine `control`.
+    pub fn new(control: AutomineControl) -> Self {
+        Self { control }
+    }
+}
+
+#[async_trait::async_trait]
+impl EvmDevApiServer for EvmDevRpc {
+    async fn set_automine(&self, enabled: bool) -> RpcResult<()> {
+        self.control.set_automine(enabled);
+        Ok(())
+    }
+
+    async fn set_interval_mining(&self, interval_ms: u64) -> RpcResult<()> {
+        self.control.set_interval_mining(interval_ms);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::automine::MiningMode;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_set_automine_and_interval_mining() {
+        let control = AutomineControl::new();
+        let rpc = EvmDevRpc::new(control.clone());
+
+        rpc.set_interval_mining(500).await.unwrap();
+        assert_eq!(
+            control.mode(),
+            MiningMode::Interval(Duration::from_millis(500))
+        );
+
+        rpc.set_automine(true).await.unwrap();
+        assert!(control.is_automine());
+
+        rpc.set_automine(false).await.unwrap();
+        assert_eq!(
+            control.mode(),
+            MiningMode::Interval(Duration::from_millis(500))
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Block Metadata RPC
+//!
+//! `poa_getBlockMetadata` returns the chain-specific metadata of a block (see
+//! `consensus::metadata`). Metadata embedded in `extra_data` is decoded directly; for
+//! blocks carrying only a commitment, the metadata comes from the node's
+//! [`BlockMetadataProvider`] and is returned only if it hashes to the commitment.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use std::sync::Arc;
+
+use super::{consensus_error_to_rpc, BlockExtraData};
+use crate::consensus::metadata::{decode_metadata, decode_metadata_hash, metadata_hash};
+use crate::consensus::PoaConsensusError;
+use crate::payload::{BlockMetadata, BlockMetadataProvider};
+
+/// Block metadata queries on the `poa_*` namespace.
+#[rpc(server, namespace = "poa")]
+pub trait PoaMetadataApi {
+    /// Returns the metadata of block `block_number`, or `null` if the block is
+    /// unknown, carries none, or its committed metadata is not available.
+    #[method(name = "getBlockMetadata")]
+    async fn get_block_metadata(&self, block_number: u64) -> RpcResult<Option<BlockMetadata>>;
+}
+
+/// Implementation of the block metadata queries.
+#[derive(Clone)]
+pub struct PoaMetadataRpc {
+    blocks: Arc<dyn BlockExtraData>,
+    provider: Option<BlockMetadataProvider>,
+}
+
+impl PoaMetadataRpc {
+    /// Serve metadata from the `extra_data` of `blocks`.
+    pub fn new(blocks: Arc<dyn BlockExtraData>) -> Self {
+        Self {
+            blocks,
+            provider: None,
+        }
+    }
+
+    /// Serve committed metadata from `provider` (the payload builder's source).
+    pub fn with_provider(mut self, provider: BlockMetadataProvider) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+}
+
+impl std::fmt::Debug for PoaMetadataRpc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoaMetadataRpc")
+            .field("provider", &self.provider)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl PoaMetadataApiServer for PoaMetadataRpc {
+    async fn get_block_metadata(&self, block_number: u64) -> RpcResult<Option<BlockMetadata>> {
+        let Some(extra_data) = self.blocks.extra_data(block_number) else {
+            return Ok(None);
+        };
+        let invalid = |e| consensus_error_to_rpc(&PoaConsensusError::InvalidBlockMetadata(e));
+        let Some(expected) = decode_metadata_hash(&extra_data) else {
+            return decode_metadata(&extra_data).map_err(invalid);
+        };
+        let Some(provider) = &self.provider else {
+            return Ok(None);
+        };
+        let metadata = provider.metadata(block_number);
+        let got = metadata_hash(&metadata).map_err(invalid)?;
+        if got != expected {
+            return Err(consensus_error_to_rpc(
+                &PoaConsensusError::BlockMetadataHashMismatch { expected, got },
+            ));
+        }
+        Ok(Some(metadata))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::metadata::{append_metadata, append_metadata_hash};
+    use crate::consensus::EXTRA_SEAL_LENGTH;
+    use alloy_primitives::Bytes;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    /// In-memory `extra_data` by block number.
+    struct Blocks(HashMap<u64, Bytes>);
+
+    impl BlockExtraData for Blocks {
+        fn extra_data(&self, number: u64) -> Option<Bytes> {
+            self.0.get(&number).cloned()
+        }
+    }
+
+    fn metadata(number: u64) -> BlockMetadata {
+        BlockMetadata::from([
+            ("batchSeq".to_string(), json!(number * 10)),
+            ("l1StateRoot".to_string(), json!("0xabc")),
+        ])
+    }
+
+    fn sealed_extra() -> Vec<u8> {
+        let mut extra = vec![0u8; 32];
+        extra.extend_from_slice(&[0xAA; EXTRA_SEAL_LENGTH]);
+        extra
+    }
+
+    fn committed_extra(number: u64) -> Bytes {
+        let mut extra = sealed_extra();
+        append_metadata_hash(&mut extra, &metadata(number)).unwrap();
+        extra.into()
+    }
+
+    fn provider() -> BlockMetadataProvider {
+        BlockMetadataProvider::new(Arc::new(metadata))
+    }
+
+    #[tokio::test]
+    async fn test_committed_metadata_matches_extra_data_hash() {
+        let extra = committed_extra(5);
+        assert_eq!(extra.len(), 129);
+        let rpc = PoaMetadataRpc::new(Arc::new(Blocks(HashMap::from([(5, extra.clone())]))))
+            .with_provider(provider());
+
+        let served = rpc.get_block_metadata(5).await.unwrap().unwrap();
+        assert_eq!(served, metadata(5));
+        assert_eq!(
+            decode_metadata_hash(&extra),
+            Some(metadata_hash(&served).unwrap())
+        );
+        assert_eq!(rpc.get_block_metadata(6).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_metadata_is_an_error() {
+        // The block commits to block 4's metadata; the provider serves block 5's.
+        let blocks = Blocks(HashMap::from([(5, committed_extra(4))]));
+        let rpc = PoaMetadataRpc::new(Arc::new(blocks)).with_provider(provider());
+        let err = rpc.get_block_metadata(5).await.unwrap_err();
+        assert_eq!(
+            err.data().unwrap().get(),
+            r#"{"kind":"blockMetadataHashMismatch"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embedded_and_missing_metadata() {
+        let mut framed = sealed_extra();
+        append_metadata(&mut framed, &metadata(1)).unwrap();
+        let blocks = Blocks(HashMap::from([
+            (1, framed.into()),
+            (2, sealed_extra().into()),
+            (3, committed_extra(3)),
+        ]));
+        let rpc = PoaMetadataRpc::new(Arc::new(blocks));
+
+        assert_eq!(rpc.get_block_metadata(1).await.unwrap(), Some(metadata(1)));
+        assert_eq!(rpc.get_block_metadata(2).await.unwrap(), None);
+        // Committed metadata without a provider is unavailable.
+        assert_eq!(rpc.get_block_metadata(3).await.unwrap(), None);
+    }
+}
@@ -6,28 +6,84 @@
 pub mod admin;
 pub mod admin_types;
 pub mod api;
+pub mod automine;
+pub mod backpressure;
 pub mod clique;
 pub mod clique_types;
+pub mod errors;
+pub mod evm;
+pub mod metadata;
+pub mod proofs;
+pub mod rate_limit;
+pub mod replay;
+pub mod sequencer;
+pub mod status;
+pub mod txpool;
 pub mod types;
+pub mod votes;
 
 pub use admin::{AdminApiServer, AdminRpc};
 pub use admin_types::NODE_VERSION;
 pub use api::MeowApiServer;
+pub use automine::AutomineLayer;
+pub use backpressure::BackpressureLayer;
 pub use clique::{CliqueApiServer, CliqueRpc};
-pub use types::{ChainConfigResponse, NodeInfoResponse};
+pub use errors::{consensus_error_to_rpc, seal_error_to_rpc};
+pub use evm::{EvmDevApiServer, EvmDevRpc};
+pub use metadata::{PoaMetadataApiServer, PoaMetadataRpc};
+pub use proofs::{
+    generate_tx_inclusion_proof, PoaProofApiServer, PoaProofRpc, TransactionBlocks,
+    TxInclusionProof,
+};
+pub use rate_limit::{MethodRateLimit, RateLimitLayer, TokenBucket};
+pub use replay::{PoaReplayApiServer, PoaReplayRpc};
+pub use sequencer::{PoaSequencerApiServer, PoaSequencerRpc, SequencerQueue};
+pub use status::{PoaStatusApiServer, PoaStatusRpc};
+pub use txpool::{MempoolContent, TxpoolApiServer, TxpoolRpc};
+pub use types::{BlockVanityResponse, ChainConfigResponse, NodeInfoResponse, PoaStatusResponse};
+pub use votes::{PoaVotesApiServer, PoaVotesRpc};
 
 use crate::chainspec::PoaChainSpec;
+use crate::consensus::vanity::{decode_vanity, invalid_vanity_byte};
+use crate::consensus::EXTRA_VANITY_LENGTH;
 use crate::genesis::{
     CHAIN_CONFIG_ADDRESS, GOVERNANCE_SAFE_ADDRESS, SIGNER_REGISTRY_ADDRESS, TREASURY_ADDRESS,
 };
 use crate::signer::SignerManager;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::Bytes;
+use reth_storage_api::HeaderProvider;
 use std::sync::Arc;
 
+/// Source of block `extra_data` for the `meow_blockVanity` method.
+///
+/// Implemented for every reth [`HeaderProvider`], so the node's provider can be
+/// passed directly.
+pub trait BlockExtraData: Send + Sync {
+    /// `extra_data` of the canonical block at `number`, if known.
+    fn extra_data(&self, number: u64) -> Option<Bytes>;
+}
+
+impl<P> BlockExtraData for P
+where
+    P: HeaderProvider + Send + Sync,
+    P::Header: BlockHeader,
+{
+    fn extra_data(&self, number: u64) -> Option<Bytes> {
+        self.header_by_number(number)
+            .ok()
+            .flatten()
+            .map(|header| header.extra_data().clone())
+    }
+}
+
 /// Implementation of the `meow_*` RPC namespace.
 pub struct MeowRpc {
     chain_spec: Arc<PoaChainSpec>,
     signer_manager: Arc<SignerManager>,
     dev_mode: bool,
+    /// Block lookup for per-block queries (`None` = per-block methods return `null`).
+    blocks: Option<Arc<dyn BlockExtraData>>,
 }
 
 impl MeowRpc {
@@ -41,8 +97,15 @@ impl MeowRpc {
             chain_spec,
             signer_manager,
             dev_mode,
+            blocks: None,
         }
     }
+
+    /// Attach a block source for per-block methods such as `meow_blockVanity`.
+    pub fn with_blocks(mut self, blocks: Arc<dyn BlockExtraData>) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -78,6 +141,22 @@ impl MeowApiServer for MeowRpc {
             authorized_signers: authorized.to_vec(),
         })
     }
+
+    async fn block_vanity(
+        &self,
+        number: u64,
+    ) -> jsonrpsee::core::RpcResult<Option<BlockVanityResponse>> {
+        let Some(extra_data) = self.blocks.as_ref().and_then(|b| b.extra_data(number)) else {
+            return Ok(None);
+        };
+        let raw = extra_data.slice(..extra_data.len().min(EXTRA_VANITY_LENGTH));
+        Ok(Some(BlockVanityResponse {
+            number,
+            vanity: decode_vanity(&raw),
+            printable: invalid_vanity_byte(&raw).is_none(),
+            raw,
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -232,6 +311,43 @@ mod tests {
         assert_eq!(config.treasury_contract, TREASURY_ADDRESS);
     }
 
+    /// In-memory block source keyed by block number.
+    struct MockBlocks(std::collections::HashMap<u64, Bytes>);
+
+    impl BlockExtraData for MockBlocks {
+        fn extra_data(&self, number: u64) -> Option<Bytes> {
+            self.0.get(&number).cloned()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_meow_block_vanity() {
+        let mut tagged = crate::consensus::vanity::client_vanity("meowchain/1.0").to_vec();
+        tagged.extend_from_slice(&[0xab; 65]);
+        let mut escaped = vec![0u8; 97];
+        escaped[..4].copy_from_slice(b"x\x1b[m");
+        let blocks = MockBlocks([(1, Bytes::from(tagged)), (2, Bytes::from(escaped))].into());
+        let rpc = MeowRpc::new(test_chain_spec(), Arc::new(SignerManager::new()), false)
+            .with_blocks(Arc::new(blocks));
+
+        let one = rpc.block_vanity(1).await.unwrap().unwrap();
+        assert_eq!(one.vanity, "meowchain/1.0");
+        assert_eq!(one.raw.len(), 32);
+        assert!(one.printable);
+
+        let two = rpc.block_vanity(2).await.unwrap().unwrap();
+        assert_eq!(two.vanity, "x\\x1b[m");
+        assert!(!two.printable);
+
+        assert!(rpc.block_vanity(3).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_meow_block_vanity_without_block_source() {
+        let rpc = MeowRpc::new(test_chain_spec(), Arc::new(SignerManager::new()), false);
+        assert!(rpc.block_vanity(0).await.unwrap().is_none());
+    }
+
     #[test]
     fn test_chain_config_response_json_serialization() {
         let config = ChainConfigResponse {
@@ -0,0 +1,248 @@
+//! Transaction inclusion proofs
+//!
+//! Bridge contracts verify that a transaction is part of a POA block from the block
+//! header alone. `poa_getTransactionProof` returns the Merkle-Patricia proof of the
+//! transaction in its block's transactions trie (key `rlp(index)`, value the
+//! EIP-2718 encoding), checked against the header's `transactions_root`.
+//!
+//! Proofs are built on demand from the stored block, so every canonical
+//! transaction can be proven, including blocks this node did not produce.
+
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Bytes, B256};
+use alloy_trie::proof::{verify_proof, ProofRetainer};
+use alloy_trie::root::adjust_index_for_rlp;
+use alloy_trie::{HashBuilder, Nibbles};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_ethereum::Block;
+use reth_storage_api::BlockReader;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Merkle-Patricia proof that a transaction is included in a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxInclusionProof {
+    pub block_hash: B256,
+    pub tx_hash: B256,
+    /// Position of the transaction in the block
+    pub index: usize,
+    /// Trie nodes from the root down to the transaction's leaf
+    pub proof: Vec<Bytes>,
+}
+
+impl TxInclusionProof {
+    /// Whether the proof shows `tx` at `index` under `transactions_root`.
+    pub fn verify<T: Encodable2718>(&self, transactions_root: B256, tx: &T) -> bool {
+        verify_proof(
+            transactions_root,
+            index_key(self.index),
+            Some(tx.encoded_2718()),
+            &self.proof,
+        )
+        .is_ok()
+    }
+}
+
+/// Trie key of the transaction at `index`.
+fn index_key(index: usize) -> Nibbles {
+    Nibbles::unpack(alloy_rlp::encode_fixed_size(&index))
+}
+
+/// Proof of the transaction at `tx_index` in `block`'s transactions trie.
+///
+/// # Panics
+///
+/// If `tx_index` is out of bounds for the block's transactions.
+pub fn generate_tx_inclusion_proof(block: &Block, tx_index: usize) -> TxInclusionProof {
+    let transactions = &block.body.transactions;
+    let tx_hash = *transactions[tx_index].tx_hash();
+
+    // Leaves must be added in key order, which for RLP-encoded indices is not
+    // numeric order (same walk as `calculate_transaction_root`).
+    let target = index_key(tx_index);
+    let mut builder = HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target]));
+    let mut value = Vec::new();
+    for i in 0..transactions.len() {
+        let index = adjust_index_for_rlp(i, transactions.len());
+        value.clear();
+        transactions[index].encode_2718(&mut value);
+        builder.add_leaf(index_key(index), &value);
+    }
+    builder.root();
+    let proof = builder
+        .take_proof_nodes()
+        .into_nodes_sorted()
+        .into_iter()
+        .map(|(_, node)| node)
+        .collect();
+
+    TxInclusionProof {
+        block_hash: block.header.hash_slow(),
+        tx_hash,
+        index: tx_index,
+        proof,
+    }
+}
+
+/// Lookup of the block containing a transaction, for `poa_getTransactionProof`.
+///
+/// Implemented for every reth [`BlockReader`], so the node's provider can be passed
+/// directly.
+pub trait TransactionBlocks: Send + Sync {
+    /// Canonical block containing `tx_hash` and the transaction's index in it.
+    fn block_with_transaction(&self, tx_hash: B256) -> Option<(Block, usize)>;
+}
+
+impl<P> TransactionBlocks for P
+where
+    P: BlockReader<Block = Block> + Send + Sync,
+{
+    fn block_with_transaction(&self, tx_hash: B256) -> Option<(Block, usize)> {
+        let (_, meta) = self.transaction_by_hash_with_meta(tx_hash).ok().flatten()?;
+        let block = self.block_by_hash(meta.block_hash).ok().flatten()?;
+        Some((block, meta.index as usize))
+    }
+}
+
+/// Transaction proofs on the `poa_*` namespace.
+#[rpc(server, namespace = "poa")]
+pub trait PoaProofApi {
+    /// Returns the inclusion proof of a mined transaction, or `null` if unknown.
+    #[method(name = "getTransactionProof")]
+    async fn get_transaction_proof(&self, tx_hash: B256) -> RpcResult<Option<TxInclusionProof>>;
+}
+
+/// Implementation of the transaction proof queries.
+#[derive(Clone)]
+pub struct PoaProofRpc {
+    blocks: Arc<dyn TransactionBlocks>,
+}
+
+impl PoaProofRpc {
+    /// Prove transactions found in `blocks`, normally the node's provider.
+    pub fn new(blocks: Arc<dyn TransactionBlocks>) -> Self {
+        Self { blocks }
+    }
+}
+
+#[async_trait::async_trait]
+impl PoaProofApiServer for PoaProofRpc {
+    async fn get_transaction_proof(&self, tx_hash: B256) -> RpcResult<Option<TxInclusionProof>> {
+        Ok(self
+            .blocks
+            .block_with_transaction(tx_hash)
+            .filter(|(block, index)| *index < block.body.transactions.len())
+            .map(|(block, index)| generate_tx_inclusion_proof(&block, index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{BlockBody, Header, SignableTransaction, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_ethereum::TransactionSigned;
+
+    /// Block with `count` signed transfers and a matching transactions root.
+    fn block(count: usize) -> Block {
+        let transactions: Vec<TransactionSigned> = (0..count)
+            .map(|nonce| {
+                let tx = TxLegacy {
+                    chain_id: Some(9323310),
+                    nonce: nonce as u64,
+                    gas_price: 1_000_000_000,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::with_last_byte(0xbe)),
+                    value: U256::from(1),
+                    ..Default::default()
+                };
+                let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[0].parse().unwrap();
+                let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                TransactionSigned::Legacy(tx.into_signed(signature))
+            })
+            .collect();
+        Block {
+            header: Header {
+                number: 7,
+                transactions_root: alloy_consensus::proofs::calculate_transaction_root(
+                    &transactions,
+                ),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_tx_inclusion_proof_verifies_against_transactions_root() {
+        let block = block(3);
+        let root = block.header.transactions_root;
+        let proof = generate_tx_inclusion_proof(&block, 0);
+
+        assert_eq!(proof.block_hash, block.header.hash_slow());
+        assert_eq!(proof.tx_hash, *block.body.transactions[0].tx_hash());
+        assert_eq!(proof.index, 0);
+        assert!(!proof.proof.is_empty());
+        assert!(proof.verify(root, &block.body.transactions[0]));
+
+        // The proof does not vouch for another transaction or another root.
+        assert!(!proof.verify(root, &block.body.transactions[1]));
+        assert!(!proof.verify(B256::repeat_byte(1), &block.body.transactions[0]));
+    }
+
+    #[test]
+    fn test_tx_inclusion_proof_every_index() {
+        // 130 transactions: indices past 127 encode to longer keys than index 0.
+        let block = block(130);
+        let root = block.header.transactions_root;
+        for index in [1, 2, 127, 128, 129] {
+            let proof = generate_tx_inclusion_proof(&block, index);
+            assert!(
+                proof.verify(root, &block.body.transactions[index]),
+                "index {index}"
+            );
+        }
+    }
+
+    /// Block source holding a single block.
+    struct SingleBlock(Block);
+
+    impl TransactionBlocks for SingleBlock {
+        fn block_with_transaction(&self, tx_hash: B256) -> Option<(Block, usize)> {
+            let index = self
+                .0
+                .body
+                .transactions
+                .iter()
+                .position(|tx| *tx.tx_hash() == tx_hash)?;
+            Some((self.0.clone(), index))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_proof() {
+        let block = block(3);
+        let tx_hash = *block.body.transactions[2].tx_hash();
+        let rpc = PoaProofRpc::new(Arc::new(SingleBlock(block.clone())));
+
+        let proof = rpc.get_transaction_proof(tx_hash).await.unwrap().unwrap();
+        assert_eq!(proof.index, 2);
+        assert!(proof.verify(block.header.transactions_root, &block.body.transactions[2]));
+        assert!(rpc
+            .get_transaction_proof(B256::ZERO)
+            .await
+            .unwrap()
+            .is_none());
+
+        let json = serde_json::to_value(&proof).unwrap();
+        assert!(json.get("blockHash").is_some());
+        assert!(json.get("txHash").is_some());
+    }
+}
@@ -0,0 +1,471 @@
+//! RPC middleware enforcing per-method request rate limits.
+//!
+//! Some methods are far more expensive than others: `eth_getLogs` over a 10,000
+//! block range can keep the node busy for seconds. [`RateLimitLayer`] wraps reth's
+//! RPC service and answers calls to a limited method with
+//! [`OVERLOADED_ERROR_CODE`](super::errors::OVERLOADED_ERROR_CODE) ("limit exceeded")
+//! and a retry hint once that client's budget for the last minute is spent. Every
+//! call inside a JSON-RPC batch is charged like a single call; unlimited methods
+//! pass through unchanged.
+//!
+//! Budgets are per client, the RPC connection a call arrived on (jsonrpsee's
+//! [`ConnectionId`]), so one busy client cannot exhaust the budget of the others.
+//! Each client has, per limited method, a bucket of `max_per_minute` tokens refilled
+//! continuously, so it may burst to its full budget and then runs at one call every
+//! `60s / max_per_minute`. A bucket is a single atomic word (the time at which it is
+//! full again), updated without locks; full buckets are dropped once many clients
+//! have been seen, as a full bucket is the same as a fresh one.
+
+use super::errors::rate_limit_error_to_rpc;
+use dashmap::DashMap;
+use jsonrpsee::core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification};
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+use jsonrpsee::server::ConnectionId;
+use jsonrpsee::types::{ErrorObjectOwned, Request};
+use jsonrpsee::MethodResponse;
+use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::Layer;
+
+/// Period over which `max_per_minute` is counted.
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Tracked buckets above which full ones are dropped before a new one is added.
+const SWEEP_THRESHOLD: usize = 4096;
+
+/// Client a call is charged to; None for calls that did not come over a connection.
+pub type RpcClient = Option<ConnectionId>;
+
+/// Most calls to `method` accepted per minute from one client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodRateLimit {
+    /// JSON-RPC method name, e.g. `eth_getLogs`
+    pub method: String,
+    /// Calls accepted per minute; 0 refuses every call
+    pub max_per_minute: u32,
+}
+
+impl MethodRateLimit {
+    /// Accept at most `max_per_minute` calls to `method` per minute.
+    pub fn new(method: impl Into<String>, max_per_minute: u32) -> Self {
+        Self {
+            method: method.into(),
+            max_per_minute,
+        }
+    }
+}
+
+/// Malformed `--rpc-rate-limit` value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid RPC rate limit {0:?}, expected <method:max_per_minute>")]
+pub struct ParseRateLimitError(String);
+
+impl FromStr for MethodRateLimit {
+    type Err = ParseRateLimitError;
+
+    /// Parse `<method>:<max_per_minute>`, e.g. `eth_getLogs:60`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseRateLimitError(s.to_string());
+        let (method, max) = s.trim().rsplit_once(':').ok_or_else(invalid)?;
+        if method.is_empty() {
+            return Err(invalid());
+        }
+        let max_per_minute = max.parse().map_err(|_| invalid())?;
+        Ok(Self::new(method, max_per_minute))
+    }
+}
+
+/// RPC call refused because its method is over its rate limit.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RateLimitError {
+    /// The client's budget for the method over the last minute is spent
+    #[error(
+        "Rate limit of {max_per_minute}/min exceeded for {method}, retry after {}s",
+        .retry_after.as_secs()
+    )]
+    LimitExceeded {
+        /// Limited method
+        method: String,
+        /// Configured limit
+        max_per_minute: u32,
+        /// Time until the next call is accepted
+        retry_after: Duration,
+    },
+}
+
+/// Per-client, per-method token buckets.
+#[derive(Debug)]
+pub struct TokenBucket {
+    /// Origin of the timestamps in `full_at`.
+    start: Instant,
+    /// Per limited method, its index in the bucket keys and its limit.
+    limits: HashMap<String, (usize, u32)>,
+    /// Per client and method index, nanoseconds after `start` at which the bucket is
+    /// full again. Clients without an entry have full buckets.
+    full_at: DashMap<(RpcClient, usize), AtomicU64>,
+}
+
+impl Default for TokenBucket {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl TokenBucket {
+    /// Buckets for `limits`, all full. A method listed twice keeps its last limit.
+    pub fn new(limits: &[MethodRateLimit]) -> Self {
+        let limits = limits
+            .iter()
+            .enumerate()
+            .map(|(index, limit)| (limit.method.clone(), (index, limit.max_per_minute)))
+            .collect();
+        Self {
+            start: Instant::now(),
+            limits,
+            full_at: DashMap::new(),
+        }
+    }
+
+    /// Whether no method is limited.
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty()
+    }
+
+    /// Take a token from `client`'s bucket for `method`; unlimited methods always
+    /// succeed.
+    pub fn try_acquire(&self, client: RpcClient, method: &str) -> Result<(), RateLimitError> {
+        self.try_acquire_at(client, method, Instant::now())
+    }
+
+    /// [`try_acquire`](Self::try_acquire) for a call arriving at `now`.
+    pub fn try_acquire_at(
+        &self,
+        client: RpcClient,
+        method: &str,
+        now: Instant,
+    ) -> Result<(), RateLimitError> {
+        let Some(&(index, max_per_minute)) = self.limits.get(method) else {
+            return Ok(());
+        };
+        let exceeded = |retry_after| RateLimitError::LimitExceeded {
+            method: method.to_string(),
+            max_per_minute,
+            retry_after,
+        };
+        if max_per_minute == 0 {
+            return Err(exceeded(WINDOW));
+        }
+
+        // GCRA: each call pushes the "full again" time one interval further; a call
+        // is refused when that time is more than a window (minus one interval) ahead.
+        let window = WINDOW.as_nanos() as u64;
+        let interval = window / u64::from(max_per_minute);
+        let tolerance = window - interval;
+        let now = now.saturating_duration_since(self.start).as_nanos() as u64;
+        let key = (client, index);
+        let full_at = match self.full_at.get(&key) {
+            Some(full_at) => full_at,
+            None => {
+                self.sweep(now);
+                self.full_at
+                    .entry(key)
+                    .or_insert_with(|| AtomicU64::new(0))
+                    .downgrade()
+            }
+        };
+        let mut current = full_at.load(Ordering::Acquire);
+        loop {
+            let from = current.max(now);
+            if from - now > tolerance {
+                return Err(exceeded(Duration::from_nanos(from - now - tolerance)));
+            }
+            match full_at.compare_exchange_weak(
+                current,
+                from + interval,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(()),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Drop the buckets already full at `now` (nanoseconds after `start`) once more
+    /// than [`SWEEP_THRESHOLD`] are tracked.
+    fn sweep(&self, now: u64) {
+        if self.full_at.len() > SWEEP_THRESHOLD {
+            self.full_at.retain(|_, full_at| *full_at.get_mut() > now);
+        }
+    }
+
+    /// Number of buckets currently tracked.
+    pub fn tracked(&self) -> usize {
+        self.full_at.len()
+    }
+}
+
+/// Tower layer installing [`RateLimitService`] as RPC middleware.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitLayer {
+    buckets: Arc<TokenBucket>,
+}
+
+impl RateLimitLayer {
+    /// Enforce `limits`; an empty list lets every call through.
+    pub fn new(limits: &[MethodRateLimit]) -> Self {
+        Self {
+            buckets: Arc::new(TokenBucket::new(limits)),
+        }
+    }
+
+    /// The shared per-method buckets.
+    pub fn buckets(&self) -> &TokenBucket {
+        &self.buckets
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            buckets: self.buckets.clone(),
+        }
+    }
+}
+
+/// RPC service that rejects calls to methods over their rate limit.
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    buckets: Arc<TokenBucket>,
+}
+
+impl<S> RateLimitService<S> {
+    /// Error for `req` if its client is over the limit of its method.
+    fn rejection(&self, req: &Request<'_>) -> Option<ErrorObjectOwned> {
+        let client = req.extensions().get::<ConnectionId>().copied();
+        let err = self.buckets.try_acquire(client, req.method_name()).err()?;
+        Some(rate_limit_error_to_rpc(&err))
+    }
+}
+
+impl<S> RpcServiceT for RateLimitService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type MethodResponse = MethodResponse;
+    type NotificationResponse = MethodResponse;
+    type BatchResponse = MethodResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let rejection = self.rejection(&req);
+        let inner = self.inner.clone();
+        async move {
+            match rejection {
+                Some(err) => MethodResponse::error(req.id().clone(), err),
+                None => inner.call(req).await,
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut req: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // A batch of N calls spends N tokens, so batching does not raise the limit.
+        for entry in req.iter_mut() {
+            let Ok(BatchEntry::Call(call)) = entry else {
+                continue;
+            };
+            if let Some(err) = self.rejection(call) {
+                let id = call.id().clone();
+                *entry = Err(BatchEntryErr::new(id, err));
+            }
+        }
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::{Id, ResponsePayload};
+    use std::sync::atomic::AtomicUsize;
+
+    const GET_LOGS: &str = "eth_getLogs";
+
+    #[test]
+    fn test_one_per_minute_blocks_second_call() {
+        let buckets = TokenBucket::new(&[MethodRateLimit::new(GET_LOGS, 1)]);
+        let t0 = Instant::now();
+
+        assert!(buckets.try_acquire_at(None, GET_LOGS, t0).is_ok());
+        let err = buckets
+            .try_acquire_at(None, GET_LOGS, t0 + Duration::from_secs(1))
+            .unwrap_err();
+        let RateLimitError::LimitExceeded { retry_after, .. } = err;
+        assert_eq!(retry_after, Duration::from_secs(59));
+        assert!(buckets
+            .try_acquire_at(None, GET_LOGS, t0 + Duration::from_secs(59))
+            .is_err());
+
+        // A minute later the token is back; other methods were never limited.
+        assert!(buckets.try_acquire_at(None, GET_LOGS, t0 + WINDOW).is_ok());
+        assert!(buckets.try_acquire_at(None, "eth_blockNumber", t0).is_ok());
+    }
+
+    #[test]
+    fn test_burst_up_to_limit_then_steady_rate() {
+        let buckets = TokenBucket::new(&[MethodRateLimit::new(GET_LOGS, 3)]);
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            assert!(buckets.try_acquire_at(None, GET_LOGS, t0).is_ok());
+        }
+        assert!(buckets.try_acquire_at(None, GET_LOGS, t0).is_err());
+        // One token refills every 20 seconds.
+        assert!(buckets
+            .try_acquire_at(None, GET_LOGS, t0 + Duration::from_secs(19))
+            .is_err());
+        assert!(buckets
+            .try_acquire_at(None, GET_LOGS, t0 + Duration::from_secs(20))
+            .is_ok());
+        assert!(buckets
+            .try_acquire_at(None, GET_LOGS, t0 + Duration::from_secs(20))
+            .is_err());
+    }
+
+    #[test]
+    fn test_zero_limit_refuses_every_call() {
+        let buckets = TokenBucket::new(&[MethodRateLimit::new("debug_traceBlock", 0)]);
+        assert!(buckets.try_acquire(None, "debug_traceBlock").is_err());
+        assert!(TokenBucket::default().is_empty());
+    }
+
+    #[test]
+    fn test_parse_method_rate_limit() {
+        let limit: MethodRateLimit = "eth_getLogs:60".parse().unwrap();
+        assert_eq!(limit, MethodRateLimit::new(GET_LOGS, 60));
+
+        for invalid in ["eth_getLogs", ":5", "eth_getLogs:-1", "eth_getLogs:many"] {
+            assert_eq!(
+                invalid.parse::<MethodRateLimit>(),
+                Err(ParseRateLimitError(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_clients_have_separate_budgets() {
+        let buckets = TokenBucket::new(&[MethodRateLimit::new(GET_LOGS, 1)]);
+        let t0 = Instant::now();
+        let (a, b) = (Some(ConnectionId(1)), Some(ConnectionId(2)));
+
+        assert!(buckets.try_acquire_at(a, GET_LOGS, t0).is_ok());
+        assert!(buckets.try_acquire_at(a, GET_LOGS, t0).is_err());
+        // Another client still has its own token.
+        assert!(buckets.try_acquire_at(b, GET_LOGS, t0).is_ok());
+        assert!(buckets.try_acquire_at(b, GET_LOGS, t0).is_err());
+    }
+
+    #[test]
+    fn test_full_buckets_are_swept() {
+        let buckets = TokenBucket::new(&[MethodRateLimit::new(GET_LOGS, 60)]);
+        let t0 = Instant::now();
+        for id in 0..=SWEEP_THRESHOLD {
+            assert!(buckets
+                .try_acquire_at(Some(ConnectionId(id)), GET_LOGS, t0)
+                .is_ok());
+        }
+        assert_eq!(buckets.tracked(), SWEEP_THRESHOLD + 1);
+
+        // A minute later every bucket is full again and is dropped for the new client.
+        let later = t0 + WINDOW;
+        assert!(buckets
+            .try_acquire_at(Some(ConnectionId(usize::MAX)), GET_LOGS, later)
+            .is_ok());
+        assert_eq!(buckets.tracked(), 1);
+    }
+
+    /// Counts the batch entries that reach it and those refused before it.
+    #[derive(Clone, Default)]
+    struct CountingRpc {
+        passed: Arc<AtomicUsize>,
+        refused: Arc<AtomicUsize>,
+    }
+
+    fn ok(id: Id<'_>) -> MethodResponse {
+        MethodResponse::response(id, ResponsePayload::success(true), usize::MAX)
+    }
+
+    impl RpcServiceT for CountingRpc {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            self.passed.fetch_add(1, Ordering::Relaxed);
+            async move { ok(req.id().clone()) }
+        }
+
+        fn batch<'a>(
+            &self,
+            mut req: Batch<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            for entry in req.iter_mut() {
+                let counter = if entry.is_ok() {
+                    &self.passed
+                } else {
+                    &self.refused
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+            async { ok(Id::Null) }
+        }
+
+        fn notification<'a>(
+            &self,
+            _n: Notification<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            async { ok(Id::Null) }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_calls_are_charged_one_by_one() {
+        let inner = CountingRpc::default();
+        let rpc = RateLimitLayer::new(&[MethodRateLimit::new(GET_LOGS, 2)]).layer(inner.clone());
+        let call = |id| Request::owned(GET_LOGS.to_string(), None, Id::Number(id));
+
+        let mut batch = Batch::new();
+        for id in 0..3 {
+            batch.push(call(id)).unwrap();
+        }
+        rpc.batch(batch).await;
+        assert_eq!(inner.passed.load(Ordering::Relaxed), 2);
+        assert_eq!(inner.refused.load(Ordering::Relaxed), 1);
+
+        // The batch used up the budget a single call would have had.
+        assert!(rpc.call(call(3)).await.is_error());
+    }
+}
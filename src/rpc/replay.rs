@@ -0,0 +1,207 @@
+//! Block Replay RPC
+//!
+//! `debug_replayBlock` re-executes a stored block on its parent state, optionally with
+//! per-account overrides, and returns the receipts, gas used and changed accounts. See
+//! [`node::replay`](crate::node::replay) for what is executed; nothing is persisted.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use super::errors::replay_error_to_rpc;
+use crate::node::replay::{self, AccountOverride, BlockReplayer, ReplayResult};
+use crate::statediff::AccountDiff;
+use alloy_primitives::{Address, Log, B256, U256};
+use serde::Serialize;
+
+/// Block replay on the `debug_*` namespace.
+#[rpc(server, namespace = "debug")]
+pub trait PoaReplayApi {
+    /// Replays block `block_number` on the state of its parent with `overrides`
+    /// applied (same shape as the `eth_call` state override).
+    #[method(name = "replayBlock")]
+    async fn replay_block(
+        &self,
+        block_number: u64,
+        overrides: Option<HashMap<Address, AccountOverride>>,
+    ) -> RpcResult<ReplayResponse>;
+}
+
+/// Response for `debug_replayBlock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayResponse {
+    /// One receipt per transaction, in block order
+    pub receipts: Vec<ReplayReceipt>,
+    pub gas_used: u64,
+    /// Accounts changed by the block, relative to the overridden parent state
+    pub state_changes: BTreeMap<Address, ReplayAccountChange>,
+}
+
+/// Receipt of a replayed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReceipt {
+    /// EIP-2718 transaction type
+    pub tx_type: u8,
+    pub success: bool,
+    pub cumulative_gas_used: u64,
+    pub logs: Vec<Log>,
+}
+
+/// Change of one account during a replay: `[before, after]` pairs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayAccountChange {
+    pub balance: Option<(U256, U256)>,
+    pub nonce: Option<(u64, u64)>,
+    pub code_changed: bool,
+    pub storage: BTreeMap<U256, (B256, B256)>,
+}
+
+impl From<AccountDiff> for ReplayAccountChange {
+    fn from(diff: AccountDiff) -> Self {
+        Self {
+            balance: diff.balance,
+            nonce: diff.nonce,
+            code_changed: diff.code_changed,
+            storage: diff
+                .storage
+                .into_iter()
+                .map(|(slot, change)| (slot, (change.old_value, change.new_value)))
+                .collect(),
+        }
+    }
+}
+
+impl From<ReplayResult> for ReplayResponse {
+    fn from(result: ReplayResult) -> Self {
+        Self {
+            receipts: result
+                .receipts
+                .into_iter()
+                .map(|receipt| ReplayReceipt {
+                    tx_type: receipt.tx_type as u8,
+                    success: receipt.success,
+                    cumulative_gas_used: receipt.cumulative_gas_used,
+                    logs: receipt.logs,
+                })
+                .collect(),
+            gas_used: result.gas_used,
+            state_changes: result
+                .state_changes
+                .into_iter()
+                .map(|(address, diff)| (address, diff.into()))
+                .collect(),
+        }
+    }
+}
+
+/// Implementation of `debug_replayBlock`.
+#[derive(Debug, Clone)]
+pub struct PoaReplayRpc {
+    replayer: Arc<dyn BlockReplayer>,
+}
+
+impl PoaReplayRpc {
+    /// Replay blocks with `replayer` (usually a
+    /// [`ProviderBlockReplayer`](crate::node::ProviderBlockReplayer) over the node's
+    /// provider and EVM configuration).
+    pub fn new(replayer: Arc<dyn BlockReplayer>) -> Self {
+        Self { replayer }
+    }
+}
+
+#[async_trait::async_trait]
+impl PoaReplayApiServer for PoaReplayRpc {
+    async fn replay_block(
+        &self,
+        block_number: u64,
+        overrides: Option<HashMap<Address, AccountOverride>>,
+    ) -> RpcResult<ReplayResponse> {
+        replay::replay_block(
+            self.replayer.clone(),
+            block_number,
+            overrides.unwrap_or_default(),
+        )
+        .await
+        .map(Into::into)
+        .map_err(|e| replay_error_to_rpc(&e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::replay::ReplayError;
+    use crate::rpc::errors::REPLAY_ERROR_CODE;
+    use alloy_consensus::TxType;
+    use reth_ethereum::Receipt;
+    use std::sync::Mutex;
+
+    /// Records the overrides it is called with and replays a single transfer.
+    #[derive(Debug, Default)]
+    struct RecordingReplayer {
+        seen: Mutex<Vec<HashMap<Address, AccountOverride>>>,
+    }
+
+    impl BlockReplayer for RecordingReplayer {
+        fn replay(
+            &self,
+            block_number: u64,
+            overrides: &HashMap<Address, AccountOverride>,
+        ) -> Result<ReplayResult, ReplayError> {
+            if block_number != 1 {
+                return Err(ReplayError::BlockNotFound(block_number));
+            }
+            self.seen.lock().unwrap().push(overrides.clone());
+            let diff = AccountDiff {
+                nonce: Some((0, 1)),
+                ..Default::default()
+            };
+            Ok(ReplayResult {
+                receipts: vec![Receipt {
+                    tx_type: TxType::Eip1559,
+                    success: true,
+                    cumulative_gas_used: 21_000,
+                    logs: vec![],
+                }],
+                gas_used: 21_000,
+                state_changes: HashMap::from([(Address::with_last_byte(1), diff)]),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_block_serves_result_and_passes_overrides() {
+        let replayer = Arc::new(RecordingReplayer::default());
+        let rpc = PoaReplayRpc::new(replayer.clone());
+        let overrides = HashMap::from([(
+            Address::with_last_byte(1),
+            AccountOverride {
+                balance: Some(U256::ZERO),
+                ..Default::default()
+            },
+        )]);
+
+        let response = rpc.replay_block(1, Some(overrides.clone())).await.unwrap();
+        assert_eq!(replayer.seen.lock().unwrap().as_slice(), [overrides]);
+        assert_eq!(response.gas_used, 21_000);
+        assert_eq!(response.receipts[0].tx_type, 2);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["receipts"][0]["cumulativeGasUsed"], 21_000);
+        assert_eq!(
+            json["stateChanges"]["0x0000000000000000000000000000000000000001"]["nonce"],
+            serde_json::json!([0, 1])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_block_maps_errors() {
+        let rpc = PoaReplayRpc::new(Arc::new(RecordingReplayer::default()));
+        let err = rpc.replay_block(7, None).await.unwrap_err();
+        assert_eq!(err.code(), REPLAY_ERROR_CODE);
+        assert!(err.message().contains("Block 7 not found"));
+    }
+}
@@ -0,0 +1,76 @@
+//! Sequencer RPC
+//!
+//! `poa_sequencerQueueDepth` reports how many valid transactions are waiting for a
+//! block. In sequencer mode (see `payload::sequencer`) every one of them is owed
+//! inclusion, so a depth that keeps growing means blocks are full.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool::TransactionPool;
+use std::sync::Arc;
+
+/// Source of the pending transaction count for `poa_sequencerQueueDepth`.
+///
+/// Implemented for every reth [`TransactionPool`], so the node's pool can be passed
+/// directly.
+pub trait SequencerQueue: Send + Sync {
+    /// Number of valid pending transactions not yet included in a block.
+    fn queue_depth(&self) -> usize;
+}
+
+impl<P: TransactionPool> SequencerQueue for P {
+    fn queue_depth(&self) -> usize {
+        self.pool_size().pending
+    }
+}
+
+/// Sequencer queries on the `poa_*` namespace.
+#[rpc(server, namespace = "poa")]
+pub trait PoaSequencerApi {
+    /// Returns the number of valid pending transactions not yet included.
+    #[method(name = "sequencerQueueDepth")]
+    async fn sequencer_queue_depth(&self) -> RpcResult<u64>;
+}
+
+/// Implementation of the sequencer queries.
+#[derive(Clone)]
+pub struct PoaSequencerRpc {
+    queue: Arc<dyn SequencerQueue>,
+}
+
+impl PoaSequencerRpc {
+    /// Report on `queue`, normally the node's transaction pool.
+    pub fn new(queue: Arc<dyn SequencerQueue>) -> Self {
+        Self { queue }
+    }
+}
+
+#[async_trait::async_trait]
+impl PoaSequencerApiServer for PoaSequencerRpc {
+    async fn sequencer_queue_depth(&self) -> RpcResult<u64> {
+        Ok(self.queue.queue_depth() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FixedQueue(AtomicUsize);
+
+    impl SequencerQueue for FixedQueue {
+        fn queue_depth(&self) -> usize {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sequencer_queue_depth() {
+        let queue = Arc::new(FixedQueue(AtomicUsize::new(0)));
+        let rpc = PoaSequencerRpc::new(queue.clone());
+        assert_eq!(rpc.sequencer_queue_depth().await.unwrap(), 0);
+
+        queue.0.store(3, Ordering::Relaxed);
+        assert_eq!(rpc.sequencer_queue_depth().await.unwrap(), 3);
+    }
+}
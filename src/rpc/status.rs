@@ -0,0 +1,208 @@
+//! POA Status RPC Namespace
+//!
+//! `poa_status` reports whether this node is currently producing blocks and, if
+//! not, why: an administrative revert pause, critical disk space, or transaction
+//! ingress back-pressure. It also reports the startup state warm-up.
+//!
+//! `poa_diagnostics` returns the redacted [`DiagnosticReport`] with the launched
+//! node's head block, peer count and pool size filled in.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use std::fmt;
+use std::sync::Arc;
+
+use super::types::PoaStatusResponse;
+use crate::node::backpressure::Backpressure;
+use crate::node::disk::DiskGuard;
+use crate::node::revert::ProductionPause;
+use crate::node::warmup::WarmupReport;
+use crate::node::{DiagnosticReport, RuntimeStats};
+
+/// The `poa_*` RPC namespace.
+#[rpc(server, namespace = "poa")]
+pub trait PoaStatusApi {
+    /// Returns block production and transaction ingress state.
+    #[method(name = "status")]
+    async fn status(&self) -> RpcResult<PoaStatusResponse>;
+
+    /// Returns the redacted diagnostic report with live statistics, or `null` when
+    /// the node serves none.
+    #[method(name = "diagnostics")]
+    async fn diagnostics(&self) -> RpcResult<Option<serde_json::Value>>;
+}
+
+/// Configuration snapshot and live statistics behind `poa_diagnostics`.
+#[derive(Clone)]
+struct Diagnostics {
+    report: DiagnosticReport,
+    stats: Arc<dyn RuntimeStats>,
+}
+
+impl fmt::Debug for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Diagnostics")
+            .field("report", &self.report)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Implementation of the `poa_*` RPC namespace.
+#[derive(Debug, Clone)]
+pub struct PoaStatusRpc {
+    production_pause: ProductionPause,
+    disk_guard: DiskGuard,
+    backpressure: Backpressure,
+    warmup: WarmupReport,
+    diagnostics: Option<Diagnostics>,
+}
+
+impl PoaStatusRpc {
+    /// Report on the node's shared production and ingress handles.
+    pub fn new(
+        production_pause: ProductionPause,
+        disk_guard: DiskGuard,
+        backpressure: Backpressure,
+    ) -> Self {
+        Self {
+            production_pause,
+            disk_guard,
+            backpressure,
+            warmup: WarmupReport::new(),
+            diagnostics: None,
+        }
+    }
+
+    /// Include the result of the startup warm-up.
+    pub fn with_warmup_report(mut self, report: WarmupReport) -> Self {
+        self.warmup = report;
+        self
+    }
+
+    /// Serve `report` on `poa_diagnostics`, with runtime fields read from `stats`.
+    pub fn with_diagnostics(
+        mut self,
+        report: DiagnosticReport,
+        stats: Arc<dyn RuntimeStats>,
+    ) -> Self {
+        self.diagnostics = Some(Diagnostics { report, stats });
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl PoaStatusApiServer for PoaStatusRpc {
+    async fn status(&self) -> RpcResult<PoaStatusResponse> {
+        Ok(PoaStatusResponse {
+            production_paused: self.production_pause.is_paused(),
+            disk_allows_building: self.disk_guard.allows_block_building(),
+            disk_available_bytes: self.disk_guard.available_bytes(),
+            backpressure: self.backpressure.status(),
+            warmup: self.warmup.stats(),
+        })
+    }
+
+    async fn diagnostics(&self) -> RpcResult<Option<serde_json::Value>> {
+        Ok(self.diagnostics.as_ref().map(|diagnostics| {
+            diagnostics
+                .report
+                .clone()
+                .with_stats_from(diagnostics.stats.as_ref())
+                .to_redacted_json()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::backpressure::{BackpressureConfig, OverloadState};
+
+    #[tokio::test]
+    async fn test_status_reports_overload() {
+        let backpressure = Backpressure::new(BackpressureConfig::new(10, 5));
+        let rpc = PoaStatusRpc::new(
+            ProductionPause::new(),
+            DiskGuard::new(),
+            backpressure.clone(),
+        );
+
+        let status = rpc.status().await.unwrap();
+        assert!(!status.production_paused);
+        assert_eq!(status.backpressure.state, OverloadState::Normal);
+
+        backpressure.observe(12);
+        let status = rpc.status().await.unwrap();
+        assert_eq!(status.backpressure.state, OverloadState::Overloaded);
+        assert_eq!(status.backpressure.occupancy, 12);
+        assert_eq!(status.backpressure.overload_entered, 1);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["backpressure"]["state"], "overloaded");
+        assert_eq!(json["productionPaused"], false);
+        assert!(json["warmup"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_warmup() {
+        use crate::node::warmup::WarmupStats;
+
+        let report = WarmupReport::new();
+        let rpc = PoaStatusRpc::new(
+            ProductionPause::new(),
+            DiskGuard::new(),
+            Backpressure::default(),
+        )
+        .with_warmup_report(report.clone());
+        report.record(WarmupStats {
+            blocks_scanned: 64,
+            accounts: 120,
+            slots: 900,
+            duration_ms: 350,
+            budget_exhausted: false,
+        });
+
+        let json = serde_json::to_value(rpc.status().await.unwrap()).unwrap();
+        assert_eq!(json["warmup"]["blocksScanned"], 64);
+        assert_eq!(json["warmup"]["slots"], 900);
+        assert_eq!(json["warmup"]["durationMs"], 350);
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_fill_runtime_stats() {
+        use crate::chainspec::PoaChainSpec;
+        use crate::node::PoaNode;
+
+        #[derive(Default)]
+        struct Stats(std::sync::atomic::AtomicUsize);
+        impl RuntimeStats for Stats {
+            fn latest_block_number(&self) -> Option<u64> {
+                Some(12)
+            }
+            fn peer_count(&self) -> usize {
+                4
+            }
+            fn mempool_size(&self) -> usize {
+                self.0.load(std::sync::atomic::Ordering::Relaxed)
+            }
+        }
+
+        let rpc = PoaStatusRpc::new(
+            ProductionPause::new(),
+            DiskGuard::new(),
+            Backpressure::default(),
+        );
+        assert!(rpc.diagnostics().await.unwrap().is_none());
+
+        let stats = Arc::new(Stats::default());
+        let report = PoaNode::new(Arc::new(PoaChainSpec::dev_chain()))
+            .diagnose()
+            .await;
+        let rpc = rpc.with_diagnostics(report, stats.clone());
+        stats.0.store(9, std::sync::atomic::Ordering::Relaxed);
+        let json = rpc.diagnostics().await.unwrap().unwrap();
+        assert_eq!(json["latestBlockNumber"], 12);
+        assert_eq!(json["peerCount"], 4);
+        // Read per call, not captured at registration.
+        assert_eq!(json["mempoolSize"], 9);
+    }
+}
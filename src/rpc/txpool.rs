@@ -0,0 +1,247 @@
+//! Geth-compatible mempool RPC
+//!
+//! Monitoring tools and block explorers read the mempool with geth's
+//! `txpool_content` (every transaction, by sender and nonce) and `txpool_inspect`
+//! (one-line summaries), in alloy's [`TxpoolContent`] / [`TxpoolInspect`] shapes.
+//! reth's `txpool` namespace serves the same methods; enabled with
+//! [`PoaNode::with_mempool_content_api`](crate::node::PoaNode::with_mempool_content_api),
+//! these are added to the transports that do not serve it. Pending transactions are
+//! executable now, queued ones wait behind a nonce gap.
+
+use alloy_consensus::Transaction;
+use alloy_primitives::Address;
+use alloy_rpc_types_txpool::{TxpoolContent, TxpoolInspect, TxpoolInspectSummary};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_ethereum::TransactionSigned;
+use reth_primitives_traits::Recovered;
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Source of the mempool transactions for the `txpool_*` methods.
+///
+/// Implemented for every reth [`TransactionPool`], so the node's pool can be passed
+/// directly.
+pub trait MempoolContent: Send + Sync {
+    /// Pending and queued transactions, in that order.
+    fn pending_and_queued(
+        &self,
+    ) -> (
+        Vec<Recovered<TransactionSigned>>,
+        Vec<Recovered<TransactionSigned>>,
+    );
+}
+
+impl<P> MempoolContent for P
+where
+    P: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    fn pending_and_queued(
+        &self,
+    ) -> (
+        Vec<Recovered<TransactionSigned>>,
+        Vec<Recovered<TransactionSigned>>,
+    ) {
+        let all = self.all_transactions();
+        let recovered = |txs: Vec<Arc<ValidPoolTransaction<P::Transaction>>>| -> Vec<_> {
+            txs.iter()
+                .map(|tx| tx.transaction.clone_into_consensus())
+                .collect()
+        };
+        (recovered(all.pending), recovered(all.queued))
+    }
+}
+
+/// Group `pending` and `queued` transactions by sender and nonce, as
+/// `txpool_content` returns them.
+pub fn txpool_content(
+    pending: Vec<Recovered<TransactionSigned>>,
+    queued: Vec<Recovered<TransactionSigned>>,
+) -> TxpoolContent<TransactionSigned> {
+    fn by_sender(
+        txs: Vec<Recovered<TransactionSigned>>,
+    ) -> BTreeMap<Address, BTreeMap<String, TransactionSigned>> {
+        let mut grouped: BTreeMap<_, BTreeMap<_, _>> = BTreeMap::new();
+        for tx in txs {
+            let (tx, sender) = tx.into_parts();
+            grouped
+                .entry(sender)
+                .or_default()
+                .insert(tx.nonce().to_string(), tx);
+        }
+        grouped
+    }
+    TxpoolContent {
+        pending: by_sender(pending),
+        queued: by_sender(queued),
+    }
+}
+
+/// One-line summary of every transaction in `content`, as `txpool_inspect` returns
+/// it.
+pub fn txpool_inspect(content: &TxpoolContent<TransactionSigned>) -> TxpoolInspect {
+    let summarize = |txs: &BTreeMap<Address, BTreeMap<String, TransactionSigned>>| {
+        txs.iter()
+            .map(|(sender, by_nonce)| {
+                let summaries = by_nonce
+                    .iter()
+                    .map(|(nonce, tx)| (nonce.clone(), summarize_transaction(tx)))
+                    .collect();
+                (*sender, summaries)
+            })
+            .collect()
+    };
+    TxpoolInspect {
+        pending: summarize(&content.pending),
+        queued: summarize(&content.queued),
+    }
+}
+
+/// Geth's summary of a transaction, serialized as
+/// `<to>: <value> wei + <gas> gas × <gas price> wei`.
+///
+/// The gas price of a dynamic-fee transaction is its max fee per gas.
+pub fn summarize_transaction(tx: &TransactionSigned) -> TxpoolInspectSummary {
+    TxpoolInspectSummary {
+        to: tx.to(),
+        value: tx.value(),
+        gas: tx.gas_limit(),
+        gas_price: tx.max_fee_per_gas(),
+    }
+}
+
+/// Geth's mempool queries on the `txpool_*` namespace.
+#[rpc(server, namespace = "txpool")]
+pub trait TxpoolApi {
+    /// Returns every pending and queued transaction, by sender and nonce.
+    #[method(name = "content")]
+    async fn content(&self) -> RpcResult<TxpoolContent<TransactionSigned>>;
+
+    /// Returns a one-line summary of every pending and queued transaction.
+    #[method(name = "inspect")]
+    async fn inspect(&self) -> RpcResult<TxpoolInspect>;
+}
+
+/// Implementation of the geth-compatible mempool queries.
+#[derive(Clone)]
+pub struct TxpoolRpc {
+    pool: Arc<dyn MempoolContent>,
+}
+
+impl TxpoolRpc {
+    /// Report on `pool`, normally the node's transaction pool.
+    pub fn new(pool: Arc<dyn MempoolContent>) -> Self {
+        Self { pool }
+    }
+
+    /// Current mempool transactions by sender and nonce.
+    pub async fn txpool_content(&self) -> TxpoolContent<TransactionSigned> {
+        let (pending, queued) = self.pool.pending_and_queued();
+        txpool_content(pending, queued)
+    }
+}
+
+#[async_trait::async_trait]
+impl TxpoolApiServer for TxpoolRpc {
+    async fn content(&self) -> RpcResult<TxpoolContent<TransactionSigned>> {
+        Ok(self.txpool_content().await)
+    }
+
+    async fn inspect(&self) -> RpcResult<TxpoolInspect> {
+        Ok(txpool_inspect(&self.txpool_content().await))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::dev::DEV_PRIVATE_KEYS;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_primitives::{address, TxKind, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_transaction_pool::blobstore::InMemoryBlobStore;
+    use reth_transaction_pool::test_utils::MockTransactionValidator;
+    use reth_transaction_pool::{
+        CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolConfig, TransactionOrigin,
+    };
+
+    const RECIPIENT: Address = address!("000000000000000000000000000000000000beef");
+
+    fn transfer(key: usize, nonce: u64) -> Recovered<TransactionSigned> {
+        let tx = TxLegacy {
+            chain_id: None,
+            nonce,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(RECIPIENT),
+            value: U256::from(7),
+            ..Default::default()
+        };
+        let signer: PrivateKeySigner = DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        TransactionSigned::Legacy(tx.into_signed(signature))
+            .try_into_recovered()
+            .unwrap()
+    }
+
+    /// Transaction pool holding `txs`; senders start at nonce 0.
+    async fn pool_with(txs: Vec<Recovered<TransactionSigned>>) -> Arc<dyn MempoolContent> {
+        let pool = Pool::new(
+            MockTransactionValidator::<EthPooledTransaction>::default(),
+            CoinbaseTipOrdering::default(),
+            InMemoryBlobStore::default(),
+            PoolConfig::default(),
+        );
+        for tx in txs {
+            let tx = EthPooledTransaction::try_from_consensus(tx).unwrap();
+            pool.add_transaction(TransactionOrigin::External, tx)
+                .await
+                .unwrap();
+        }
+        Arc::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_txpool_content_by_sender_and_nonce() {
+        let pending = vec![
+            transfer(0, 0),
+            transfer(0, 1),
+            transfer(0, 2),
+            transfer(1, 0),
+        ];
+        let queued = transfer(1, 5);
+        let (alice, bob) = (pending[0].signer(), pending[3].signer());
+        let mut txs = pending.clone();
+        txs.push(queued.clone());
+        let rpc = TxpoolRpc::new(pool_with(txs).await);
+
+        let content = rpc.txpool_content().await;
+        assert_eq!(content.pending.len(), 2);
+        let nonces: Vec<_> = content.pending[&alice].keys().cloned().collect();
+        assert_eq!(nonces, vec!["0", "1", "2"]);
+        assert_eq!(content.pending[&alice]["1"], *pending[1].inner());
+        assert_eq!(content.pending[&bob]["0"], *pending[3].inner());
+        assert_eq!(content.queued[&bob]["5"], *queued.inner());
+        assert!(!content.queued.contains_key(&alice));
+
+        let json = serde_json::to_value(&content).unwrap();
+        let queued = json["queued"].as_object().unwrap();
+        assert_eq!(queued.len(), 1);
+        assert!(queued.values().all(|by_nonce| by_nonce["5"].is_object()));
+    }
+
+    #[tokio::test]
+    async fn test_txpool_inspect_summaries() {
+        let rpc = TxpoolRpc::new(pool_with(vec![transfer(0, 0)]).await);
+        let inspect = rpc.inspect().await.unwrap();
+        let sender = transfer(0, 0).signer();
+        let json = serde_json::to_value(&inspect).unwrap();
+        assert_eq!(
+            json["pending"][sender.to_string()]["0"],
+            format!("{RECIPIENT}: 7 wei + 21000 gas × 1000000000 wei")
+        );
+        assert!(inspect.queued.is_empty());
+    }
+}
@@ -1,4 +1,6 @@
-use alloy_primitives::Address;
+use alloy_primitives::{Address, Bytes};
+use crate::node::backpressure::BackpressureStatus;
+use crate::node::warmup::WarmupStats;
 use serde::Serialize;
 
 /// Response for `meow_chainConfig`
@@ -27,3 +29,32 @@ pub struct NodeInfoResponse {
     pub local_signers: Vec<Address>,
     pub authorized_signers: Vec<Address>,
 }
+
+/// Response for `meow_blockVanity`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockVanityResponse {
+    pub number: u64,
+    /// Vanity with zero padding stripped and non-printable bytes escaped as `\xNN`
+    pub vanity: String,
+    /// Raw vanity bytes
+    pub raw: Bytes,
+    /// Whether the vanity satisfies the strict policy (printable ASCII + zero padding)
+    pub printable: bool,
+}
+
+/// Response for `poa_status`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoaStatusResponse {
+    /// Block production paused by an administrative revert
+    pub production_paused: bool,
+    /// Datadir free space is above the critical threshold
+    pub disk_allows_building: bool,
+    /// Free bytes on the datadir filesystem at the last check
+    pub disk_available_bytes: u64,
+    /// Transaction ingress back-pressure state
+    pub backpressure: BackpressureStatus,
+    /// Startup warm-up result; null when `--warmup` is off
+    pub warmup: Option<WarmupStats>,
+}
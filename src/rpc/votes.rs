@@ -0,0 +1,70 @@
+//! Signer-set Vote RPC
+//!
+//! `poa_listPendingVotes` reports signer additions and removals that have votes in
+//! canonical blocks since the last epoch but have not reached quorum, tallied against
+//! the live signer set.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use std::sync::Arc;
+
+use crate::chainspec::PoaChainSpec;
+use crate::consensus::{PendingVote, VoterSet};
+
+/// Signer-set governance queries on the `poa_*` namespace.
+#[rpc(server, namespace = "poa")]
+pub trait PoaVotesApi {
+    /// Returns every proposal below quorum, sorted by candidate.
+    #[method(name = "listPendingVotes")]
+    async fn list_pending_votes(&self) -> RpcResult<Vec<PendingVote>>;
+}
+
+/// Implementation of the signer-set vote queries.
+#[derive(Debug, Clone)]
+pub struct PoaVotesRpc {
+    chain_spec: Arc<PoaChainSpec>,
+    votes: VoterSet,
+}
+
+impl PoaVotesRpc {
+    /// Report on `votes`, the node's shared tally.
+    pub fn new(chain_spec: Arc<PoaChainSpec>, votes: VoterSet) -> Self {
+        Self { chain_spec, votes }
+    }
+}
+
+#[async_trait::async_trait]
+impl PoaVotesApiServer for PoaVotesRpc {
+    async fn list_pending_votes(&self) -> RpcResult<Vec<PendingVote>> {
+        Ok(self
+            .chain_spec
+            .with_effective_signers(|signers| self.votes.pending(signers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::VoteType;
+
+    #[tokio::test]
+    async fn test_list_pending_votes_json() {
+        let chain = Arc::new(PoaChainSpec::dev_chain());
+        let signers = chain.signers().to_vec();
+        let votes = VoterSet::new();
+        let rpc = PoaVotesRpc::new(chain, votes.clone());
+        assert!(rpc.list_pending_votes().await.unwrap().is_empty());
+
+        votes.cast(&signers, signers[0], signers[2], VoteType::Remove);
+        let json = serde_json::to_value(rpc.list_pending_votes().await.unwrap()).unwrap();
+        assert_eq!(
+            json[0]["candidate"],
+            serde_json::to_value(signers[2]).unwrap()
+        );
+        assert_eq!(json[0]["action"], "remove");
+        assert_eq!(
+            json[0]["votesFor"][0],
+            serde_json::to_value(signers[0]).unwrap()
+        );
+        assert_eq!(json[0]["quorum"], 2);
+    }
+}
@@ -0,0 +1,362 @@
+//! Tamper-evident log of block seals.
+//!
+//! With [`BlockSealer::with_audit_log`](super::BlockSealer::with_audit_log) every
+//! `seal_header` call appends one JSON line to the log: block number, timestamp,
+//! seal hash, signer and signature. Each line also carries the keccak256 hash of the
+//! line before it (zero for the first), so editing, removing or reordering a line
+//! breaks the chain at the next one; [`verify_audit_log`] walks it.
+//!
+//! The node does not log from its block builder, which seals every rebuild of a
+//! payload: with `--seal-audit-log` it records each canonical block sealed by one of
+//! its keys instead, so the log lists exactly the blocks it signed and published.
+//!
+//! A crash can leave the last line half-written. [`SealAuditLog::open`] drops such
+//! a line before continuing the chain; it was never acknowledged to the sealer.
+
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use reth_tracing::tracing::warn;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::consensus::metadata::seal_end;
+use crate::constants::EXTRA_SEAL_LENGTH;
+
+/// Failure to write or verify a seal audit log.
+#[derive(Debug, thiserror::Error)]
+pub enum SealAuditError {
+    /// An entry could not be encoded as JSON
+    #[error("Seal audit entry serialization failed: {0}")]
+    SerializationFailed(#[from] serde_json::Error),
+    /// A line of the log is not a JSON entry
+    #[error("Malformed seal audit entry at line {line}: {source}")]
+    MalformedEntry {
+        /// 1-based line number
+        line: usize,
+        /// The JSON error
+        source: serde_json::Error,
+    },
+    /// The log file could not be accessed
+    #[error("Seal audit log I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// A line does not carry the hash of the line before it
+    #[error(
+        "Seal audit log chain broken at line {line}: expected prev hash {expected}, got {actual}"
+    )]
+    ChainBroken {
+        /// 1-based line number
+        line: usize,
+        /// Hash of the previous line
+        expected: B256,
+        /// Hash recorded in the line
+        actual: B256,
+    },
+}
+
+/// What to do when an audit line cannot be written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuditLogMode {
+    /// Fail the seal: no block is signed without a record of it.
+    #[default]
+    Strict,
+    /// Log a warning and return the seal anyway.
+    Lenient,
+}
+
+/// One sealed header, as a line of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealAuditEntry {
+    /// Block number
+    pub block_number: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+    /// Hash that was signed
+    pub seal_hash: B256,
+    /// Address that signed it
+    pub signer: Address,
+    /// The 65-byte seal (r, s, v)
+    pub signature: Bytes,
+    /// keccak256 of the previous line; zero for the first
+    pub prev_hash: B256,
+}
+
+impl SealAuditEntry {
+    /// Entry of `header`, sealed by `signer` over `seal_hash`; the signature is read
+    /// from the header's extra data.
+    pub fn new(header: &Header, seal_hash: B256, signer: Address) -> Self {
+        let end = seal_end(&header.extra_data);
+        let signature = header
+            .extra_data
+            .slice(end.saturating_sub(EXTRA_SEAL_LENGTH)..end);
+        Self {
+            block_number: header.number,
+            timestamp: header.timestamp,
+            seal_hash,
+            signer,
+            signature,
+            prev_hash: B256::ZERO,
+        }
+    }
+}
+
+/// Append-only, hash-chained JSON-lines log of seals.
+#[derive(Debug)]
+pub struct SealAuditLog {
+    path: PathBuf,
+    /// Hash of the last line written, chained into the next one.
+    last_hash: Mutex<B256>,
+}
+
+impl SealAuditLog {
+    /// Append to the log at `path`, continuing the chain of an existing file.
+    ///
+    /// A last line without its newline, torn by a crash mid-write, is truncated
+    /// away. The file itself is created on the first write.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, SealAuditError> {
+        let path = path.into();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+        let complete = content.rfind('\n').map_or(0, |newline| newline + 1);
+        if complete < content.len() {
+            warn!(
+                target: "poa::signer",
+                path = %path.display(),
+                torn = %&content[complete..],
+                "Dropping the torn last line of the seal audit log"
+            );
+            OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_len(complete as u64)?;
+        }
+        let last_hash = content[..complete]
+            .lines()
+            .last()
+            .map_or(B256::ZERO, line_hash);
+        Ok(Self {
+            path,
+            last_hash: Mutex::new(last_hash),
+        })
+    }
+
+    /// The log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `entry`, with its `prev_hash` set to the hash of the last line.
+    pub fn append(&self, entry: SealAuditEntry) -> Result<(), SealAuditError> {
+        self.append_all(vec![entry])
+    }
+
+    /// Append `entries` in order, each chained to the line before it, in one write.
+    ///
+    /// All or nothing: a failed write is truncated away and the chain only advances
+    /// once every line is written.
+    pub fn append_all(&self, entries: Vec<SealAuditEntry>) -> Result<(), SealAuditError> {
+        let mut last_hash = self.last_hash.lock().unwrap();
+        let mut hash = *last_hash;
+        let mut lines = String::new();
+        for mut entry in entries {
+            entry.prev_hash = hash;
+            let line = serde_json::to_string(&entry)?;
+            hash = line_hash(&line);
+            lines.push_str(&line);
+            lines.push('\n');
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let len = file.metadata()?.len();
+        if let Err(err) = file
+            .write_all(lines.as_bytes())
+            .and_then(|()| file.sync_data())
+        {
+            // Leave no partial line for the next append to continue from.
+            let _ = file.set_len(len);
+            return Err(err.into());
+        }
+        *last_hash = hash;
+        Ok(())
+    }
+
+    /// [`append_all`](Self::append_all) on the blocking thread pool, keeping the file
+    /// I/O off the async workers.
+    pub async fn record(
+        self: &Arc<Self>,
+        entries: Vec<SealAuditEntry>,
+    ) -> Result<(), SealAuditError> {
+        let log = Arc::clone(self);
+        tokio::task::spawn_blocking(move || log.append_all(entries))
+            .await
+            .map_err(|err| SealAuditError::Io(io::Error::other(err)))?
+    }
+}
+
+/// Hash chained into the next line.
+fn line_hash(line: &str) -> B256 {
+    keccak256(line.as_bytes())
+}
+
+/// Check the hash chain of the log at `path`, returning its entries.
+pub fn verify_audit_log(path: impl AsRef<Path>) -> Result<Vec<SealAuditEntry>, SealAuditError> {
+    let content = fs::read_to_string(path)?;
+    let mut expected = B256::ZERO;
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let entry: SealAuditEntry =
+            serde_json::from_str(line).map_err(|source| SealAuditError::MalformedEntry {
+                line: i + 1,
+                source,
+            })?;
+        if entry.prev_hash != expected {
+            return Err(SealAuditError::ChainBroken {
+                line: i + 1,
+                expected,
+                actual: entry.prev_hash,
+            });
+        }
+        expected = line_hash(line);
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{dev, BlockSealer, SignerError, SignerManager};
+    use alloy_consensus::Header;
+    use std::sync::Arc;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("poa-seal-audit-{name}-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn header(number: u64) -> Header {
+        Header {
+            number,
+            timestamp: 1_700_000_000 + number * 2,
+            gas_limit: 30_000_000,
+            extra_data: vec![0u8; 32 + 65].into(),
+            ..Default::default()
+        }
+    }
+
+    fn dev_sealer() -> (BlockSealer, Address) {
+        let manager = Arc::new(SignerManager::new());
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0])
+            .unwrap();
+        (BlockSealer::new(manager), address)
+    }
+
+    #[tokio::test]
+    async fn test_seal_chain_verifies_and_detects_tampering() {
+        let path = temp_log("chain");
+        let (sealer, address) = dev_sealer();
+        let sealer = sealer.with_audit_log(&path).unwrap();
+        for number in 1..=3 {
+            let sealed = sealer.seal_header(header(number), &address).await.unwrap();
+            assert_eq!(BlockSealer::verify_signature(&sealed).unwrap(), address);
+        }
+
+        let entries = verify_audit_log(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].prev_hash, B256::ZERO);
+        assert_eq!(entries[1].block_number, 2);
+        assert_eq!(entries[1].timestamp, 1_700_000_004);
+        assert_eq!(entries[1].signer, address);
+        assert_eq!(entries[1].seal_hash, BlockSealer::seal_hash(&header(2)));
+        assert_eq!(entries[1].signature.len(), 65);
+
+        // A reopened log continues the chain.
+        let (sealer, _) = dev_sealer();
+        let sealer = sealer.with_audit_log(&path).unwrap();
+        sealer.seal_header(header(4), &address).await.unwrap();
+        assert_eq!(verify_audit_log(&path).unwrap().len(), 4);
+
+        // Rewriting the middle entry breaks the link to it from the next line.
+        let content = fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<String> = content.lines().map(str::to_owned).collect();
+        lines[1] = lines[1].replace("\"blockNumber\":2", "\"blockNumber\":20");
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        assert!(matches!(
+            verify_audit_log(&path),
+            Err(SealAuditError::ChainBroken { line: 3, .. })
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_recorded_in_order() {
+        let path = temp_log("batch");
+        let (sealer, address) = dev_sealer();
+        let sealer = sealer.with_audit_log(&path).unwrap();
+        sealer.seal_header(header(1), &address).await.unwrap();
+        let batch = (2..=5).map(header).collect();
+        sealer.batch_seal_headers(batch, &address).await.unwrap();
+
+        let entries = verify_audit_log(&path).unwrap();
+        let numbers: Vec<u64> = entries.iter().map(|entry| entry.block_number).collect();
+        assert_eq!(numbers, [1, 2, 3, 4, 5]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_torn_last_line_dropped_on_open() {
+        let path = temp_log("torn");
+        let (sealer, address) = dev_sealer();
+        let sealer = sealer.with_audit_log(&path).unwrap();
+        for number in 1..=2 {
+            sealer.seal_header(header(number), &address).await.unwrap();
+        }
+        // A crash mid-write leaves half a line without its newline.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"blockNumber":3,"timest"#).unwrap();
+
+        let (sealer, _) = dev_sealer();
+        let sealer = sealer.with_audit_log(&path).unwrap();
+        sealer.seal_header(header(3), &address).await.unwrap();
+        let entries = verify_audit_log(&path).unwrap();
+        let numbers: Vec<u64> = entries.iter().map(|entry| entry.block_number).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_failure_fails_only_strict_seals() {
+        // The log's directory is gone by the time of the first seal.
+        let dir = temp_log("unwritable");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let log = Arc::new(SealAuditLog::open(dir.join("seals.jsonl")).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+        let (sealer, address) = dev_sealer();
+
+        let strict = sealer.with_shared_audit_log(log.clone());
+        assert!(matches!(
+            strict.seal_header(header(1), &address).await,
+            Err(SignerError::AuditLog(SealAuditError::Io(_)))
+        ));
+
+        let batch = vec![header(1), header(2)];
+        assert!(strict.batch_seal_headers(batch, &address).await.is_err());
+
+        let lenient = strict.with_audit_log_mode(AuditLogMode::Lenient);
+        let sealed = lenient.seal_header(header(1), &address).await.unwrap();
+        assert_eq!(BlockSealer::verify_signature(&sealed).unwrap(), address);
+        assert!(!log.path().exists());
+    }
+}
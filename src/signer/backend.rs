@@ -0,0 +1,102 @@
+//! Signing backends for block seals.
+//!
+//! [`BlockSealer`](super::BlockSealer) signs seals through a [`BlockSigner`],
+//! normally the in-process [`SignerManager`].
+//!
+//! Each key of a [`SignerManager`] is a [`SigningBackend`]: a [`LocalSigningBackend`]
+//! for a key in process memory, a [`RemoteSigner`](super::RemoteSigner) keeping it on
+//! another host, or an implementation backed by an HSM or a cloud KMS so the key
+//! material never enters the node.
+
+use alloy_primitives::{Address, Signature, B256};
+use alloy_signer::Signer;
+use alloy_signer_local::PrivateKeySigner;
+use std::fmt::Debug;
+
+use super::errors::SignerError;
+use super::manager::SignerManager;
+
+/// Signs seal hashes with the key of a signer address.
+#[async_trait::async_trait]
+pub trait BlockSigner: Debug + Send + Sync {
+    /// Sign the 32-byte `hash` as is (no message prefix) with the key of `address`.
+    async fn sign_hash(&self, address: &Address, hash: B256) -> Result<Signature, SignerError>;
+
+    /// Sign a header seal with the key of `address`: `seal_hash` is the keccak256 of
+    /// `preimage`, the RLP of the header without its seal.
+    async fn sign_seal(
+        &self,
+        address: &Address,
+        seal_hash: B256,
+        preimage: &[u8],
+    ) -> Result<Signature, SignerError> {
+        let _ = preimage;
+        self.sign_hash(address, seal_hash).await
+    }
+}
+
+/// One signing key, wherever it is held.
+#[async_trait::async_trait]
+pub trait SigningBackend: Debug + Send + Sync {
+    /// Address of the key.
+    fn address(&self) -> Address;
+
+    /// Sign the 32-byte `hash` as is (no message prefix).
+    async fn sign_hash(&self, hash: B256) -> Result<Signature, SignerError>;
+
+    /// Sign a header seal: `seal_hash` is the keccak256 of `preimage`. Backends that
+    /// hash what they sign themselves take the preimage.
+    async fn sign_seal(&self, seal_hash: B256, preimage: &[u8]) -> Result<Signature, SignerError> {
+        let _ = preimage;
+        self.sign_hash(seal_hash).await
+    }
+
+    /// The private key when it is held in process; None for keys that never leave
+    /// their backend, which therefore cannot be exported.
+    fn local_key(&self) -> Option<&PrivateKeySigner> {
+        None
+    }
+}
+
+/// A private key held in process memory.
+#[derive(Debug, Clone)]
+pub struct LocalSigningBackend(pub PrivateKeySigner);
+
+impl From<PrivateKeySigner> for LocalSigningBackend {
+    fn from(signer: PrivateKeySigner) -> Self {
+        Self(signer)
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningBackend for LocalSigningBackend {
+    fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    async fn sign_hash(&self, hash: B256) -> Result<Signature, SignerError> {
+        Signer::sign_hash(&self.0, &hash)
+            .await
+            .map_err(|e| SignerError::SigningFailed(e.to_string()))
+    }
+
+    fn local_key(&self) -> Option<&PrivateKeySigner> {
+        Some(&self.0)
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSigner for SignerManager {
+    async fn sign_hash(&self, address: &Address, hash: B256) -> Result<Signature, SignerError> {
+        SignerManager::sign_hash(self, address, hash).await
+    }
+
+    async fn sign_seal(
+        &self,
+        address: &Address,
+        seal_hash: B256,
+        preimage: &[u8],
+    ) -> Result<Signature, SignerError> {
+        SignerManager::sign_seal(self, address, seal_hash, preimage).await
+    }
+}
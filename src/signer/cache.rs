@@ -0,0 +1,214 @@
+//! Memoized seal recovery.
+//!
+//! Recovering the sealer of a header costs a keccak of its RLP plus an ECDSA public
+//! key recovery, and during sync the same header is validated on several paths.
+//! [`SignatureCache`] keeps the recovered sealer of recent headers in a bounded LRU
+//! keyed on the header hash (seal included), so a repeated validation costs one
+//! header hash and a map lookup. Only successful recoveries are cached.
+
+use alloy_consensus::Header;
+use alloy_primitives::{Address, B256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use super::errors::SealError;
+use super::sealer::BlockSealer;
+use crate::cache::CacheStats;
+
+/// Headers whose sealer is remembered by default.
+pub const DEFAULT_SIGNATURE_CACHE_SIZE: usize = 4096;
+
+/// LRU cache of recovered sealers by header hash.
+///
+/// Same layout as [`HotStateCache`](crate::cache::HotStateCache): a `HashMap` for
+/// lookups and a `BTreeMap<clock, key>` whose minimum is the least-recently-used
+/// entry. Counters are atomics so [`stats`](Self::stats) never waits on the lock.
+#[derive(Debug)]
+pub struct SignatureCache {
+    entries: Mutex<Entries>,
+    max_entries: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Header hash → (sealer, clock of the last access).
+    map: HashMap<B256, (Address, u64)>,
+    /// Eviction index: clock → header hash.
+    lru: BTreeMap<u64, B256>,
+    clock: u64,
+}
+
+impl Entries {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+impl Default for SignatureCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIGNATURE_CACHE_SIZE)
+    }
+}
+
+impl SignatureCache {
+    /// Remember the sealers of up to `max_entries` headers.
+    pub fn new(max_entries: usize) -> Self {
+        assert!(max_entries > 0, "signature cache capacity must be > 0");
+        Self {
+            entries: Mutex::new(Entries::default()),
+            max_entries,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// The sealer of the header hashing to `header_hash`, from the cache or from
+    /// `recover`; a successful recovery is cached.
+    pub fn get_or_recover<E>(
+        &self,
+        header_hash: B256,
+        recover: impl FnOnce() -> Result<Address, E>,
+    ) -> Result<Address, E> {
+        if let Some(signer) = self.get(header_hash) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(signer);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let signer = recover()?;
+        self.insert(header_hash, signer);
+        Ok(signer)
+    }
+
+    /// [`BlockSealer::verify_signature`] through the cache.
+    pub fn verify(&self, header: &Header) -> Result<Address, SealError> {
+        self.get_or_recover(header.hash_slow(), || BlockSealer::verify_signature(header))
+    }
+
+    fn get(&self, header_hash: B256) -> Option<Address> {
+        let mut entries = self.entries.lock().ok()?;
+        let (signer, prev_clock) = *entries.map.get(&header_hash)?;
+        let clock = entries.tick();
+        entries.lru.remove(&prev_clock);
+        entries.lru.insert(clock, header_hash);
+        entries.map.insert(header_hash, (signer, clock));
+        Some(signer)
+    }
+
+    fn insert(&self, header_hash: B256, signer: Address) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        let clock = entries.tick();
+        match entries.map.insert(header_hash, (signer, clock)) {
+            // Recovered concurrently by another validation: refresh its position.
+            Some((_, prev_clock)) => {
+                entries.lru.remove(&prev_clock);
+            }
+            None if entries.map.len() > self.max_entries => {
+                if let Some((_, lru_hash)) = entries.lru.pop_first() {
+                    entries.map.remove(&lru_hash);
+                    self.evictions.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            None => {}
+        }
+        entries.lru.insert(clock, header_hash);
+    }
+
+    /// Number of cached sealers.
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |entries| entries.map.len())
+    }
+
+    /// Whether no sealer is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hit, miss and eviction counters.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            current_entries: self.len(),
+            max_entries: self.max_entries,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{dev, SignerManager};
+    use std::sync::Arc;
+
+    async fn sealed_header(number: u64) -> Header {
+        let manager = Arc::new(SignerManager::new());
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0])
+            .unwrap();
+        let header = Header {
+            number,
+            extra_data: vec![0u8; 32 + 65].into(),
+            ..Default::default()
+        };
+        BlockSealer::new(manager)
+            .seal_header(header, &address)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repeated_verification_recovers_once() {
+        let cache = SignatureCache::default();
+        let header = sealed_header(1).await;
+        let expected = BlockSealer::verify_signature(&header).unwrap();
+
+        for _ in 0..1000 {
+            assert_eq!(cache.verify(&header).unwrap(), expected);
+        }
+        let stats = cache.stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 999);
+        assert_eq!(stats.current_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let cache = SignatureCache::new(2);
+        let headers = [
+            sealed_header(1).await,
+            sealed_header(2).await,
+            sealed_header(3).await,
+        ];
+        cache.verify(&headers[0]).unwrap();
+        cache.verify(&headers[1]).unwrap();
+        // Touch header 1 so header 2 is the least recently used.
+        cache.verify(&headers[0]).unwrap();
+        cache.verify(&headers[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+
+        cache.verify(&headers[0]).unwrap();
+        assert_eq!(cache.stats().hits, 2);
+        cache.verify(&headers[1]).unwrap();
+        assert_eq!(cache.stats().misses, 4);
+    }
+
+    #[test]
+    fn test_failed_recovery_is_not_cached() {
+        let cache = SignatureCache::default();
+        let header = Header::default();
+        assert!(cache.verify(&header).is_err());
+        assert!(cache.verify(&header).is_err());
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().misses, 2);
+    }
+}
@@ -2,6 +2,7 @@ use alloy_signer_local::PrivateKeySigner;
 use std::sync::Arc;
 
 use super::manager::SignerManager;
+use crate::chainspec::PoaChainSpec;
 
 pub const DEV_PRIVATE_KEYS: &[&str] = &[
     "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
@@ -25,6 +26,33 @@ pub fn setup_dev_signers_sync() -> Arc<SignerManager> {
     manager
 }
 
+/// Dev signers for a node running `chain_spec`.
+///
+/// # Panics
+///
+/// If `chain_spec` is a production network; see [`add_dev_signers`].
+pub fn setup_dev_signers_for(chain_spec: &PoaChainSpec) -> Arc<SignerManager> {
+    let manager = Arc::new(SignerManager::new());
+    add_dev_signers(&manager, chain_spec);
+    manager
+}
+
+/// Load the first three dev keys into `manager` for a node running `chain_spec`.
+///
+/// # Panics
+///
+/// If `chain_spec` is a production network: the dev keys are public, so loading them
+/// would let anyone sign blocks.
+pub fn add_dev_signers(manager: &SignerManager, chain_spec: &PoaChainSpec) {
+    assert!(
+        !chain_spec.is_production(),
+        "dev signers must not be loaded on a production chain"
+    );
+    for key in DEV_PRIVATE_KEYS.iter().take(3) {
+        manager.add_signer_from_hex(key).expect("Dev keys should be valid");
+    }
+}
+
 /// Async wrapper retained for tests that run inside a tokio runtime.
 pub async fn setup_dev_signers() -> Arc<SignerManager> {
     setup_dev_signers_sync()
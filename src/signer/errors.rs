@@ -1,8 +1,18 @@
 use alloy_primitives::Address;
+use crate::consensus::errors::serialize_display;
+use crate::keystore::KeystoreError;
+use super::audit::SealAuditError;
+use serde::Serialize;
 use thiserror::Error;
 
 /// Errors that can occur during signing operations
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Serialize)]
+#[serde(
+    tag = "kind",
+    content = "details",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
 pub enum SignerError {
     /// No signing key available for the specified address
     #[error("No signer available for address {0}")]
@@ -12,7 +22,94 @@ pub enum SignerError {
     #[error("Signing failed: {0}")]
     SigningFailed(String),
 
+    /// The key is held by an external signing backend and cannot be read
+    #[error("Key of {0} is held by an external signing backend and cannot be exported")]
+    KeyNotExportable(Address),
+
     /// Invalid private key format
     #[error("Invalid private key")]
     InvalidPrivateKey,
+
+    /// Encoded signature is not 65 bytes
+    #[error("Invalid signature length: expected 65, got {0}")]
+    InvalidSignatureLength(usize),
+
+    /// Signature `v` byte is not a recovery id (0/1) or legacy value (27/28)
+    #[error("Invalid signature recovery id: {0} (expected 0, 1, 27 or 28)")]
+    InvalidRecoveryId(u8),
+
+    /// Signature `s` is in the upper half of the curve order (EIP-2)
+    #[error("Malleable signature: s is above half the secp256k1 curve order")]
+    MalleableSignature,
+
+    /// Mnemonic phrase is not valid BIP-39
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    /// Derivation path is not a valid BIP-32 path
+    #[error("Invalid derivation path '{path}': {reason}")]
+    InvalidDerivationPath {
+        /// The rejected path
+        path: String,
+        /// Why it was rejected
+        reason: String,
+    },
+
+    /// Exporting a key to a keystore file failed
+    #[error("Keystore export failed: {0}")]
+    Keystore(
+        #[from]
+        #[serde(serialize_with = "serialize_display")]
+        KeystoreError,
+    ),
+
+    /// Recording a seal in the audit log failed (strict mode)
+    #[error("Seal audit log write failed: {0}")]
+    AuditLog(
+        #[from]
+        #[serde(serialize_with = "serialize_display")]
+        SealAuditError,
+    ),
+}
+
+/// Errors from recovering the sealer of a block header
+#[derive(Debug, Error, Serialize)]
+#[serde(
+    tag = "kind",
+    content = "details",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum SealError {
+    /// `extra_data` is too short to hold the 65-byte seal
+    #[error("Extra data too short for seal: {len} bytes, need at least {required}")]
+    ExtraDataTooShort {
+        /// Actual `extra_data` length
+        len: usize,
+        /// Minimum length required
+        required: usize,
+    },
+
+    /// Seal bytes do not decode to a signature
+    #[error("Malformed seal signature: {0}")]
+    MalformedSignature(#[source] SignerError),
+
+    /// Signature is well-formed but no public key can be recovered from it
+    #[error("Seal signer recovery failed: {0}")]
+    RecoveryFailed(
+        #[source]
+        #[serde(serialize_with = "serialize_display")]
+        alloy_primitives::SignatureError,
+    ),
+}
+
+impl SealError {
+    /// Stable machine-readable name of the variant, used in RPC error data.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ExtraDataTooShort { .. } => "extraDataTooShort",
+            Self::MalformedSignature(_) => "malformedSignature",
+            Self::RecoveryFailed(_) => "recoveryFailed",
+        }
+    }
 }
@@ -1,16 +1,29 @@
 use alloy_primitives::{Address, Signature, B256};
-use alloy_signer::Signer;
-use alloy_signer_local::PrivateKeySigner;
+use alloy_signer_local::{
+    coins_bip39::English, LocalSignerError, MnemonicBuilder, PrivateKeySigner,
+};
+use reth_tracing::tracing::warn;
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
 
+use super::backend::{LocalSigningBackend, SigningBackend};
 use super::errors::SignerError;
+use super::watch::{KeystoreDirWatch, PasswordProvider};
+use crate::keystore::{self, KeystoreError};
+
+/// BIP-44 account path of Ethereum keys; key `i` is derived at `<path>/i`.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0";
 
 /// Manages signing keys for POA block production
 #[derive(Debug)]
 pub struct SignerManager {
-    /// Map of address to signer
-    signers: RwLock<HashMap<Address, PrivateKeySigner>>,
+    /// Map of address to the backend holding its key.
+    ///
+    /// `Arc` so a backend can be cloned out and the lock released before signing.
+    signers: RwLock<HashMap<Address, Arc<dyn SigningBackend>>>,
 }
 
 impl SignerManager {
@@ -24,14 +37,85 @@ impl SignerManager {
         let signer = private_key_hex
             .parse::<PrivateKeySigner>()
             .map_err(|_| SignerError::InvalidPrivateKey)?;
-        let address = signer.address();
-        self.signers.write().unwrap().insert(address, signer);
-        Ok(address)
+        Ok(self.add_signer(signer))
+    }
+
+    /// Decrypt the geth/web3 JSON keystore at `path` and add its key.
+    ///
+    /// Returns the key's address. A wrong password is
+    /// [`KeystoreError::InvalidPassword`], an unreadable keystore
+    /// [`KeystoreError::Malformed`].
+    pub fn add_signer_from_keystore(
+        &self,
+        path: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<Address, KeystoreError> {
+        let keystore = keystore::read_keystore(path)?;
+        let signer = keystore::decrypt_keystore(&keystore, password)?;
+        Ok(self.add_signer(signer))
+    }
+
+    /// Encrypt the key of `address` into a scrypt V3 keystore at `path`.
+    ///
+    /// An existing file is only replaced when `overwrite` is set.
+    pub fn export_keystore(
+        &self,
+        address: &Address,
+        password: &str,
+        path: impl AsRef<Path>,
+        overwrite: bool,
+    ) -> Result<(), SignerError> {
+        let backend = self
+            .signers
+            .read()
+            .unwrap()
+            .get(address)
+            .cloned()
+            .ok_or(SignerError::NoSignerForAddress(*address))?;
+        let signer = backend
+            .local_key()
+            .ok_or(SignerError::KeyNotExportable(*address))?;
+        let keystore = keystore::encrypt_signer_scrypt(signer, password)?;
+        keystore::write_keystore(path, &keystore, overwrite)?;
+        Ok(())
+    }
+
+    /// Derive `count` keys from a BIP-39 `phrase` and add them.
+    ///
+    /// Key `i` is derived at `<derivation_path>/i`, the path defaulting to
+    /// [`DEFAULT_DERIVATION_PATH`]. Returns the addresses in index order; nothing
+    /// is added if the phrase or path is invalid.
+    pub fn add_signers_from_mnemonic(
+        &self,
+        phrase: &str,
+        count: u32,
+        derivation_path: Option<&str>,
+    ) -> Result<Vec<Address>, SignerError> {
+        let base = derivation_path
+            .unwrap_or(DEFAULT_DERIVATION_PATH)
+            .trim_end_matches('/');
+        let signers = (0..count)
+            .map(|index| derive_mnemonic_signer(phrase, &format!("{base}/{index}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(signers
+            .into_iter()
+            .map(|signer| self.add_signer(signer))
+            .collect())
     }
 
+    /// Add an in-process key (a [`LocalSigningBackend`]).
     pub fn add_signer(&self, signer: PrivateKeySigner) -> Address {
-        let address = signer.address();
-        self.signers.write().unwrap().insert(address, signer);
+        self.add_backend(LocalSigningBackend(signer))
+    }
+
+    /// Add a key held by `backend`, e.g. an HSM or a cloud KMS, replacing any key
+    /// of the same address.
+    pub fn add_backend(&self, backend: impl SigningBackend + 'static) -> Address {
+        let address = backend.address();
+        self.signers
+            .write()
+            .unwrap()
+            .insert(address, Arc::new(backend));
         address
     }
 
@@ -49,28 +133,96 @@ impl SignerManager {
 
     pub fn first_signer_in(&self, authorized: &[Address]) -> Option<Address> {
         let signers = self.signers.read().unwrap();
-        authorized.iter().find(|a| signers.contains_key(*a)).copied()
+        authorized
+            .iter()
+            .find(|a| signers.contains_key(*a))
+            .copied()
     }
 
-    /// The only async method: alloy's `Signer` trait requires `.await` for sign_hash.
-    /// The lock is released before awaiting (signer is cloned out).
+    /// Async as a backend may sign over the network.
+    /// The lock is released before awaiting (the backend is cloned out).
     pub async fn sign_hash(&self, address: &Address, hash: B256) -> Result<Signature, SignerError> {
-        let signer = self
-            .signers
+        self.backend(address)?.sign_hash(hash).await
+    }
+
+    /// Sign a header seal with the key of `address`; see [`SigningBackend::sign_seal`].
+    pub async fn sign_seal(
+        &self,
+        address: &Address,
+        seal_hash: B256,
+        preimage: &[u8],
+    ) -> Result<Signature, SignerError> {
+        self.backend(address)?.sign_seal(seal_hash, preimage).await
+    }
+
+    /// The backend holding the key of `address`, cloned out of the lock.
+    fn backend(&self, address: &Address) -> Result<Arc<dyn SigningBackend>, SignerError> {
+        self.signers
             .read()
             .unwrap()
             .get(address)
             .cloned()
-            .ok_or(SignerError::NoSignerForAddress(*address))?;
-        signer
-            .sign_hash(&hash)
-            .await
-            .map_err(|e| SignerError::SigningFailed(e.to_string()))
+            .ok_or(SignerError::NoSignerForAddress(*address))
     }
 
     pub fn remove_signer(&self, address: &Address) -> bool {
         self.signers.write().unwrap().remove(address).is_some()
     }
+
+    /// Keep the signers in line with the keystores in `dir`, rescanning it every
+    /// `poll_interval`; see [`watch`](super::watch).
+    ///
+    /// Keys of keystore files already in `dir` are loaded before this returns. Load
+    /// the other key sources first: a key already held for an address is never
+    /// replaced or removed by the watcher. Abort the returned task to stop watching.
+    pub fn watch_keystore_dir(
+        self: &Arc<Self>,
+        dir: impl Into<PathBuf>,
+        password_provider: PasswordProvider,
+        poll_interval: Duration,
+    ) -> JoinHandle<()> {
+        let manager = Arc::clone(self);
+        let mut watch = KeystoreDirWatch::new(dir.into());
+        watch.poll(&manager, &password_provider);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let (manager, password) = (manager.clone(), password_provider.clone());
+                // Decryption is CPU-bound: keep it off the async workers.
+                let scan = tokio::task::spawn_blocking(move || {
+                    watch.poll(&manager, &password);
+                    watch
+                });
+                match scan.await {
+                    Ok(scanned) => watch = scanned,
+                    Err(err) => {
+                        warn!(target: "poa::signer", %err, "Keystore watcher stopped");
+                        return;
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Derive the key at the full BIP-32 `path` (e.g. `m/44'/60'/0'/0/0`) from a BIP-39
+/// `phrase`.
+pub fn derive_mnemonic_signer(phrase: &str, path: &str) -> Result<PrivateKeySigner, SignerError> {
+    let invalid_path = |e: LocalSignerError| SignerError::InvalidDerivationPath {
+        path: path.to_string(),
+        reason: e.to_string(),
+    };
+    MnemonicBuilder::<English>::default()
+        .phrase(phrase)
+        .derivation_path(path)
+        .map_err(invalid_path)?
+        .build()
+        .map_err(|e| match e {
+            LocalSignerError::Bip39Error(e) => SignerError::InvalidMnemonic(e.to_string()),
+            e => invalid_path(e),
+        })
 }
 
 impl Default for SignerManager {
@@ -3,22 +3,38 @@
 //! This module provides utilities for signing POA blocks, including:
 //! - Key management for authorized signers
 //! - Block sealing (signing)
-//! - Signature verification
-
+//! - Signature verification, memoized per header
+//! - Remote signing over HTTP, keeping keys off the block producer
+//! - Keystore directory hot-reload for key rotation
+//! - Tamper-evident audit log of seals
+
+pub mod audit;
+pub mod backend;
+pub mod cache;
 pub mod dev;
 pub mod errors;
 pub mod manager;
+pub mod remote;
 pub mod sealer;
-
-pub use errors::SignerError;
-pub use manager::SignerManager;
-pub use sealer::{bytes_to_signature, signature_to_bytes, BlockSealer};
+pub mod signature;
+pub mod watch;
+
+pub use audit::{verify_audit_log, AuditLogMode, SealAuditEntry, SealAuditError, SealAuditLog};
+pub use backend::{BlockSigner, LocalSigningBackend, SigningBackend};
+pub use cache::{SignatureCache, DEFAULT_SIGNATURE_CACHE_SIZE};
+pub use errors::{SealError, SignerError};
+pub use manager::{derive_mnemonic_signer, SignerManager, DEFAULT_DERIVATION_PATH};
+pub use remote::RemoteSigner;
+pub use sealer::{BlockSealer, MAX_CONCURRENT_SEALS};
+pub use signature::{bytes_to_signature, is_low_s, normalize_s, signature_to_bytes};
+pub use watch::{PasswordProvider, DEFAULT_KEYSTORE_POLL_INTERVAL};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloy_consensus::Header;
-    use alloy_primitives::{keccak256, Address, B256};
+    use alloy_primitives::{keccak256, Address, Signature, B256};
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
     #[tokio::test]
@@ -64,6 +80,28 @@ mod tests {
         assert!(addresses.contains(&expected_first));
     }
 
+    #[test]
+    fn test_dev_signers_for_dev_chain() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        assert_eq!(dev::setup_dev_signers_for(&chain).signer_addresses().len(), 3);
+    }
+
+    #[test]
+    fn test_add_dev_signers_keeps_loaded_keys() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain();
+        let manager = SignerManager::new();
+        manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[5]).unwrap();
+        dev::add_dev_signers(&manager, &chain);
+        assert_eq!(manager.signer_count(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "production chain")]
+    fn test_dev_signers_refused_on_production() {
+        let chain = crate::chainspec::PoaChainSpec::dev_chain().with_production(true);
+        dev::setup_dev_signers_for(&chain);
+    }
+
     #[tokio::test]
     async fn test_remove_signer() {
         let manager = SignerManager::new();
@@ -154,8 +192,214 @@ mod tests {
             extra_data: vec![0u8; 10].into(),
             ..Default::default()
         };
-        let result = BlockSealer::verify_signature(&header);
-        assert!(result.is_err());
+        match BlockSealer::verify_signature(&header) {
+            Err(SealError::ExtraDataTooShort { len, required }) => {
+                assert_eq!(len, 10);
+                assert_eq!(required, 65);
+            }
+            other => panic!("Expected ExtraDataTooShort, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_signature_malformed_seal() {
+        let mut extra_data = vec![0u8; 32 + 65];
+        *extra_data.last_mut().unwrap() = 5;
+        let header = Header {
+            extra_data: extra_data.into(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            BlockSealer::verify_signature(&header),
+            Err(SealError::MalformedSignature(SignerError::InvalidRecoveryId(5)))
+        ));
+    }
+
+    /// Signs like its manager, then returns the equivalent high-s signature.
+    #[derive(Debug)]
+    struct HighSSigner(SignerManager);
+
+    #[async_trait::async_trait]
+    impl BlockSigner for HighSSigner {
+        async fn sign_hash(&self, address: &Address, hash: B256) -> Result<Signature, SignerError> {
+            let sig = self.0.sign_hash(address, hash).await?;
+            Ok(Signature::new(sig.r(), signature::SECP256K1_ORDER - sig.s(), !sig.v()))
+        }
+    }
+
+    fn seal_test_header() -> Header {
+        Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; 32 + 65].into(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sealed_headers_carry_low_s() {
+        let manager = SignerManager::new();
+        let address = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+        let high_s = HighSSigner(manager);
+        let sig = high_s.sign_hash(&address, B256::repeat_byte(1)).await.unwrap();
+        assert!(!is_low_s(&sig));
+
+        let sealed = BlockSealer::new(Arc::new(high_s))
+            .seal_header(seal_test_header(), &address)
+            .await
+            .unwrap();
+        let seal = bytes_to_signature(&sealed.extra_data[32..]).unwrap();
+        assert!(is_low_s(&seal));
+        assert!(sealed.extra_data[96] <= 1);
+        assert_eq!(BlockSealer::verify_signature(&sealed).unwrap(), address);
+
+        // Same seal bytes as a signer producing low-s directly.
+        let manager = Arc::new(SignerManager::new());
+        manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+        let direct = BlockSealer::new(manager)
+            .seal_header(seal_test_header(), &address)
+            .await
+            .unwrap();
+        assert_eq!(sealed.extra_data, direct.extra_data);
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_rejects_high_s() {
+        let manager = Arc::new(SignerManager::new());
+        let address = manager.add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0]).unwrap();
+        let sealed = BlockSealer::new(manager)
+            .seal_header(seal_test_header(), &address)
+            .await
+            .unwrap();
+        let seal = bytes_to_signature(&sealed.extra_data[32..]).unwrap();
+        let with_seal = |bytes: [u8; 65]| {
+            let mut extra_data = sealed.extra_data.to_vec();
+            extra_data[32..].copy_from_slice(&bytes);
+            Header {
+                extra_data: extra_data.into(),
+                ..sealed.clone()
+            }
+        };
+
+        // The high-s twin recovers the same signer but is not a valid seal.
+        let high = Signature::new(seal.r(), signature::SECP256K1_ORDER - seal.s(), !seal.v());
+        let seal_hash = BlockSealer::seal_hash(&sealed);
+        assert_eq!(high.recover_address_from_prehash(&seal_hash).unwrap(), address);
+        assert!(matches!(
+            BlockSealer::verify_signature(&with_seal(signature_to_bytes(&high))),
+            Err(SealError::MalformedSignature(SignerError::MalleableSignature))
+        ));
+
+        // A legacy v of 27/28 is read as 0/1.
+        let mut legacy = signature_to_bytes(&seal);
+        legacy[64] += 27;
+        assert_eq!(BlockSealer::verify_signature(&with_seal(legacy)).unwrap(), address);
+    }
+
+    /// Signs like its manager after a delay that varies with the hash, so
+    /// concurrent signings complete out of order; tracks the most in flight at once.
+    #[derive(Debug, Default)]
+    struct JitterSigner {
+        manager: SignerManager,
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl BlockSigner for JitterSigner {
+        async fn sign_hash(&self, address: &Address, hash: B256) -> Result<Signature, SignerError> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(in_flight, Ordering::SeqCst);
+            let delay_ms = u64::from(hash[0] % 8);
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.manager.sign_hash(address, hash).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_seal_headers_preserves_order() {
+        let signer = Arc::new(JitterSigner::default());
+        let address = signer
+            .manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0])
+            .unwrap();
+        let sealer = BlockSealer::new(signer.clone());
+        let headers: Vec<Header> = (1..=100)
+            .map(|number| Header {
+                number,
+                timestamp: 12345 + number,
+                ..seal_test_header()
+            })
+            .collect();
+
+        let sealed = sealer.batch_seal_headers(headers.clone(), &address).await.unwrap();
+        assert_eq!(sealed.len(), 100);
+        for (header, sealed) in headers.iter().zip(&sealed) {
+            assert_eq!(sealed.number, header.number);
+            assert_eq!(BlockSealer::seal_hash(sealed), BlockSealer::seal_hash(header));
+            assert_eq!(BlockSealer::verify_signature(sealed).unwrap(), address);
+        }
+        let peak = signer.peak.load(Ordering::SeqCst);
+        assert!(
+            peak > 1 && peak <= MAX_CONCURRENT_SEALS,
+            "peak in flight: {peak}"
+        );
+
+        // One signing failure seals nothing.
+        let unknown = Address::repeat_byte(0x42);
+        assert!(matches!(
+            sealer.batch_seal_headers(headers, &unknown).await,
+            Err(SignerError::NoSignerForAddress(a)) if a == unknown
+        ));
+    }
+
+    /// Key held outside the manager, standing in for an HSM or a cloud KMS.
+    #[derive(Debug)]
+    struct ExternalBackend {
+        key: alloy_signer_local::PrivateKeySigner,
+        signed: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl SigningBackend for ExternalBackend {
+        fn address(&self) -> Address {
+            alloy_signer::Signer::address(&self.key)
+        }
+
+        async fn sign_hash(&self, hash: B256) -> Result<Signature, SignerError> {
+            self.signed.fetch_add(1, Ordering::Relaxed);
+            alloy_signer::SignerSync::sign_hash_sync(&self.key, &hash)
+                .map_err(|e| SignerError::SigningFailed(e.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seal_with_external_backend() {
+        let signed = Arc::new(AtomicUsize::new(0));
+        let manager = Arc::new(SignerManager::new());
+        let address = manager.add_backend(ExternalBackend {
+            key: dev::DEV_PRIVATE_KEYS[1].parse().unwrap(),
+            signed: signed.clone(),
+        });
+        assert!(manager.has_signer(&address));
+        assert_eq!(manager.signer_addresses(), vec![address]);
+
+        let sealed = BlockSealer::new(manager.clone())
+            .seal_header(seal_test_header(), &address)
+            .await
+            .unwrap();
+        assert_eq!(BlockSealer::verify_signature(&sealed).unwrap(), address);
+        assert_eq!(signed.load(Ordering::Relaxed), 1);
+
+        // The key never leaves the backend.
+        let path = std::env::temp_dir().join(format!("poa-external-{}", std::process::id()));
+        assert!(matches!(
+            manager.export_keystore(&address, "password", &path, false),
+            Err(SignerError::KeyNotExportable(a)) if a == address
+        ));
+        assert!(!path.exists());
     }
 
     #[test]
@@ -354,4 +598,44 @@ mod tests {
         let addresses = manager.signer_addresses();
         assert!(addresses.is_empty());
     }
+
+    const DEV_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_dev_mnemonic_derives_dev_accounts() {
+        let manager = SignerManager::new();
+        let dev_accounts = crate::genesis::dev_accounts();
+        let addresses = manager
+            .add_signers_from_mnemonic(DEV_MNEMONIC, dev_accounts.len() as u32, None)
+            .unwrap();
+        assert_eq!(addresses, dev_accounts);
+        assert_eq!(manager.signer_count(), dev_accounts.len());
+
+        // The explicit default path (trailing slash tolerated) derives the same keys.
+        let explicit = SignerManager::new()
+            .add_signers_from_mnemonic(DEV_MNEMONIC, 2, Some("m/44'/60'/0'/0/"))
+            .unwrap();
+        assert_eq!(explicit, dev_accounts[..2]);
+
+        // Another account path derives other keys.
+        let other = SignerManager::new()
+            .add_signers_from_mnemonic(DEV_MNEMONIC, 1, Some("m/44'/60'/1'/0"))
+            .unwrap();
+        assert!(!dev_accounts.contains(&other[0]));
+    }
+
+    #[test]
+    fn test_invalid_mnemonic_adds_nothing() {
+        let manager = SignerManager::new();
+        let bad_checksum = "test test test test test test test test test test test test";
+        assert!(matches!(
+            manager.add_signers_from_mnemonic(bad_checksum, 3, None),
+            Err(SignerError::InvalidMnemonic(_))
+        ));
+        assert!(matches!(
+            manager.add_signers_from_mnemonic(DEV_MNEMONIC, 3, Some("m/not/a/path")),
+            Err(SignerError::InvalidDerivationPath { .. })
+        ));
+        assert_eq!(manager.signer_count(), 0);
+    }
 }
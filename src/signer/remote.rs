@@ -0,0 +1,259 @@
+//! Remote block signing over HTTP, web3signer style.
+//!
+//! Validator keys can live on a separate signing host. [`RemoteSigner`] is the
+//! [`SigningBackend`] of one such key, added to the node's
+//! [`SignerManager`](super::SignerManager) like a local key (`--remote-signer`).
+//!
+//! web3signer's `eth1/sign` signs the keccak256 of the `data` it is given, so each
+//! seal is POSTed to `<url>/api/v1/eth1/sign/<address>` as
+//! `{"data": "0x<seal preimage>"}`, the RLP of the header without its seal, and the
+//! 65-byte `r || s || v` signature is expected back as a hex string, plain or
+//! JSON-quoted. A signature that does not recover to the key's address from the
+//! seal hash is rejected. Raw hashes have no preimage and cannot be signed.
+
+use alloy_primitives::{hex, Address, Signature, B256};
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+use super::backend::SigningBackend;
+use super::errors::SignerError;
+use super::signature::bytes_to_signature;
+
+/// How long a signing request may take before it fails.
+pub const DEFAULT_REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// [`SigningBackend`] of a key held by a remote signing service.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    url: String,
+    address: Address,
+    timeout: Duration,
+    client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    /// Sign with the key of `address` through the service at `url`
+    /// (e.g. `http://signer:9000`).
+    pub fn new(url: impl Into<String>, address: Address) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            address,
+            timeout: DEFAULT_REMOTE_SIGNER_TIMEOUT,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fail signing requests taking longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The signing service URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningBackend for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_hash(&self, _hash: B256) -> Result<Signature, SignerError> {
+        Err(SignerError::SigningFailed(format!(
+            "{}: the remote signer hashes what it signs and cannot sign a raw hash",
+            self.url
+        )))
+    }
+
+    async fn sign_seal(&self, seal_hash: B256, preimage: &[u8]) -> Result<Signature, SignerError> {
+        let address = self.address;
+        let url = format!("{}/api/v1/eth1/sign/{address}", self.url);
+        let failed = |reason: String| SignerError::SigningFailed(format!("{url}: {reason}"));
+
+        let data = hex::encode_prefixed(preimage);
+        let response = self
+            .client
+            .post(&url)
+            .timeout(self.timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::json!({ "data": data }).to_string())
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    failed(format!("timed out after {:?}", self.timeout))
+                } else {
+                    failed(e.to_string())
+                }
+            })?;
+        let status = response.status();
+        let body = response.text().await.map_err(|e| failed(e.to_string()))?;
+        if !status.is_success() {
+            return Err(failed(format!("HTTP {status}: {}", body.trim())));
+        }
+
+        let bytes = hex::decode(body.trim().trim_matches('"'))
+            .map_err(|e| failed(format!("invalid signature hex: {e}")))?;
+        let signature = bytes_to_signature(&bytes).map_err(|e| failed(e.to_string()))?;
+        let signer = signature
+            .recover_address_from_prehash(&seal_hash)
+            .map_err(|e| failed(e.to_string()))?;
+        if signer != address {
+            return Err(failed(format!(
+                "signature recovers to {signer}, expected {address}"
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{dev, signature_to_bytes, BlockSealer, SignerManager};
+    use alloy_consensus::Header;
+    use alloy_primitives::keccak256;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Response of the mock signer: `None` never answers.
+    type Respond = fn(&str, &str) -> Option<(u16, String)>;
+
+    /// HTTP server on a local port answering `respond(path, body)`; returns its URL.
+    async fn mock_signer(respond: Respond) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (path, body) = read_request(&mut stream).await;
+                let Some((status, body)) = respond(&path, &body) else {
+                    std::future::pending::<()>().await;
+                    return;
+                };
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\ncontent-length: {}\r\n\
+                     connection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    /// Path and body of one HTTP request.
+    async fn read_request(stream: &mut TcpStream) -> (String, String) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                assert!(n > 0, "connection closed mid-request");
+                continue;
+            };
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    line.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if body.len() >= length || n == 0 {
+                let path = head
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                return (path, body.to_string());
+            }
+        }
+    }
+
+    /// Sign the keccak256 of the request's `data` with dev key `key`, as web3signer's
+    /// eth1/sign does (`v` = 27/28).
+    fn sign_with(key: usize, body: &str) -> String {
+        let request: serde_json::Value = serde_json::from_str(body).unwrap();
+        let hash = keccak256(hex::decode(request["data"].as_str().unwrap()).unwrap());
+        let signer: PrivateKeySigner = dev::DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let mut bytes = signature_to_bytes(&signer.sign_hash_sync(&hash).unwrap());
+        bytes[64] += 27;
+        hex::encode_prefixed(bytes)
+    }
+
+    fn dev_address(key: usize) -> Address {
+        dev::DEV_PRIVATE_KEYS[key]
+            .parse::<PrivateKeySigner>()
+            .unwrap()
+            .address()
+    }
+
+    fn header() -> Header {
+        Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            timestamp: 12345,
+            extra_data: vec![0u8; 32 + 65].into(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_seals_header() {
+        let address = dev_address(0);
+        let url = mock_signer(|path, body| {
+            assert_eq!(path, format!("/api/v1/eth1/sign/{}", dev_address(0)));
+            Some((200, format!("\"{}\"", sign_with(0, body))))
+        })
+        .await;
+
+        // Sealed through the signer manager, as the payload builder does.
+        let manager = Arc::new(SignerManager::new());
+        manager.add_backend(RemoteSigner::new(format!("{url}/"), address));
+        assert!(manager.has_signer(&address));
+        let sealer = BlockSealer::new(manager.clone());
+        let sealed = sealer.seal_header(header(), &address).await.unwrap();
+        assert_eq!(BlockSealer::verify_signature(&sealed).unwrap(), address);
+
+        // Without a preimage there is nothing to send.
+        assert!(matches!(
+            manager.sign_hash(&address, B256::repeat_byte(7)).await,
+            Err(SignerError::SigningFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_failures_are_signing_errors() {
+        let address = dev_address(0);
+        let failure = |signer: RemoteSigner| async move {
+            let preimage = b"seal preimage";
+            match signer.sign_seal(keccak256(preimage), preimage).await {
+                Err(SignerError::SigningFailed(reason)) => reason,
+                other => panic!("expected SigningFailed, got {other:?}"),
+            }
+        };
+
+        let url = mock_signer(|_, _| Some((500, "key not loaded".into()))).await;
+        let reason = failure(RemoteSigner::new(url, address)).await;
+        assert!(reason.contains("HTTP 500") && reason.contains("key not loaded"));
+
+        // A signature by another key is refused.
+        let url = mock_signer(|_, body| Some((200, sign_with(1, body)))).await;
+        assert!(failure(RemoteSigner::new(url, address))
+            .await
+            .contains("recovers to"));
+
+        let url = mock_signer(|_, _| None).await;
+        let signer = RemoteSigner::new(url, address).with_timeout(Duration::from_millis(100));
+        assert!(failure(signer).await.contains("timed out"));
+    }
+}
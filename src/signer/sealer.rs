@@ -1,113 +1,218 @@
 use alloy_consensus::Header;
 use alloy_primitives::{keccak256, Address, Signature, B256};
+use futures_util::{stream, StreamExt};
+use reth_tracing::tracing::warn;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::errors::SignerError;
-use super::manager::SignerManager;
+use super::audit::{AuditLogMode, SealAuditEntry, SealAuditError, SealAuditLog};
+use super::backend::BlockSigner;
+use super::errors::{SealError, SignerError};
+use super::signature::{bytes_to_signature, is_low_s, normalize_s, signature_to_bytes};
+use crate::consensus::metadata::{seal_end, without_seal};
+
+/// Signatures [`BlockSealer::batch_seal_headers`] has in flight at once, so a remote
+/// signer is not sent a whole batch of requests together.
+pub const MAX_CONCURRENT_SEALS: usize = 16;
 
 /// Block sealing utilities for POA
 #[derive(Debug)]
 pub struct BlockSealer {
-    signer_manager: Arc<SignerManager>,
+    signer: Arc<dyn BlockSigner>,
+    /// Record of every seal; None = not recorded.
+    audit_log: Option<Arc<SealAuditLog>>,
+    audit_mode: AuditLogMode,
 }
 
 impl BlockSealer {
-    /// Create a new block sealer
-    pub fn new(signer_manager: Arc<SignerManager>) -> Self {
-        Self { signer_manager }
+    /// Create a new block sealer signing through `signer`, normally a
+    /// [`SignerManager`](super::SignerManager)
+    pub fn new(signer: Arc<dyn BlockSigner>) -> Self {
+        Self {
+            signer,
+            audit_log: None,
+            audit_mode: AuditLogMode::default(),
+        }
+    }
+
+    /// Record every seal in the hash-chained audit log at `path`
+    pub fn with_audit_log(mut self, path: impl Into<PathBuf>) -> Result<Self, SealAuditError> {
+        self.audit_log = Some(Arc::new(SealAuditLog::open(path)?));
+        Ok(self)
+    }
+
+    /// Record every seal in `log`, e.g. one shared by the sealer of each block
+    pub fn with_shared_audit_log(mut self, log: Arc<SealAuditLog>) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
+    /// Whether a failed audit write fails the seal (strict, the default) or only warns
+    pub fn with_audit_log_mode(mut self, mode: AuditLogMode) -> Self {
+        self.audit_mode = mode;
+        self
     }
 
     /// Calculate the seal hash for a header (hash without signature)
+    ///
+    /// Only the 65-byte signature is cut out of extra_data, so a metadata suffix after
+    /// the seal is covered (see `consensus::metadata`).
     #[inline]
     pub fn seal_hash(header: &Header) -> B256 {
-        // Clone the header struct, then strip the trailing 65-byte signature from
-        // extra_data using Bytes::slice so the truncated view shares the same
-        // underlying buffer (arc bump, O(1)) rather than allocating a new Vec.
-        let mut header_for_hash = header.clone();
+        keccak256(Self::seal_preimage(header))
+    }
 
-        const EXTRA_SEAL_LENGTH: usize = 65;
-        let extra_len = header.extra_data.len();
-        if extra_len >= EXTRA_SEAL_LENGTH {
-            header_for_hash.extra_data = header.extra_data.slice(..extra_len - EXTRA_SEAL_LENGTH);
-        }
+    /// RLP of the header without its signature, the preimage of
+    /// [`seal_hash`](Self::seal_hash)
+    pub fn seal_preimage(header: &Header) -> Vec<u8> {
+        // Clone the header struct, then strip the signature from extra_data. Without
+        // metadata this is a Bytes::slice sharing the underlying buffer (arc bump, O(1))
+        // rather than a new Vec.
+        let mut header_for_hash = header.clone();
+        header_for_hash.extra_data = without_seal(&header.extra_data);
 
-        keccak256(alloy_rlp::encode(&header_for_hash))
+        alloy_rlp::encode(&header_for_hash)
     }
 
     /// Seal a block header with a signature
     pub async fn seal_header(
         &self,
-        mut header: Header,
+        header: Header,
         signer_address: &Address,
     ) -> Result<Header, SignerError> {
         // Calculate seal hash
-        let seal_hash = Self::seal_hash(&header);
+        let preimage = Self::seal_preimage(&header);
+        let seal_hash = keccak256(&preimage);
 
         // Sign the hash
         let signature = self
-            .signer_manager
-            .sign_hash(signer_address, seal_hash)
+            .signer
+            .sign_seal(signer_address, seal_hash, &preimage)
             .await?;
 
-        // Encode signature as bytes (r, s, v)
-        let sig_bytes = signature_to_bytes(&signature);
+        let header = Self::apply_seal(header, &signature);
+        self.record(
+            std::slice::from_ref(&header),
+            vec![seal_hash],
+            signer_address,
+        )
+        .await?;
+        Ok(header)
+    }
+
+    /// Seal a sequence of headers, returned in the same order.
+    ///
+    /// Up to [`MAX_CONCURRENT_SEALS`] seal hashes are signed concurrently; if any
+    /// signing fails no header is sealed. The seals are then recorded in the audit
+    /// log in a single write: a failed write in strict mode leaves none of them
+    /// recorded.
+    pub async fn batch_seal_headers(
+        &self,
+        headers: Vec<Header>,
+        signer_address: &Address,
+    ) -> Result<Vec<Header>, SignerError> {
+        let preimages: Vec<Vec<u8>> = headers.iter().map(Self::seal_preimage).collect();
+        let seal_hashes: Vec<B256> = preimages.iter().map(keccak256).collect();
+        // buffered yields results in input order, however the futures complete.
+        let signatures = stream::iter(seal_hashes.iter().zip(&preimages))
+            .map(|(seal_hash, preimage)| {
+                self.signer.sign_seal(signer_address, *seal_hash, preimage)
+            })
+            .buffered(MAX_CONCURRENT_SEALS)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sealed: Vec<Header> = headers
+            .into_iter()
+            .zip(&signatures)
+            .map(|(header, signature)| Self::apply_seal(header, signature))
+            .collect();
+        self.record(&sealed, seal_hashes, signer_address).await?;
+        Ok(sealed)
+    }
+
+    /// Write `signature` into the seal slot of `header`'s extra data.
+    fn apply_seal(mut header: Header, signature: &Signature) -> Header {
+        // Encode signature as bytes (r, s, v): low-s, v as recovery id 0/1
+        let sig_bytes = signature_to_bytes(&normalize_s(signature));
 
-        // Update extra data with signature.
-        // Pre-size the Vec to the final length (vanity prefix + 65-byte sig) to
+        // Update extra data with signature, keeping any metadata suffix after it.
+        // Pre-size the Vec to the final length (prefix + 65-byte sig + suffix) to
         // avoid a second reallocation from extend_from_slice.
         const EXTRA_SEAL_LENGTH: usize = 65;
-        let prefix_len = if header.extra_data.len() >= EXTRA_SEAL_LENGTH {
-            header.extra_data.len() - EXTRA_SEAL_LENGTH
-        } else {
-            header.extra_data.len()
-        };
-        let mut extra_data = Vec::with_capacity(prefix_len + EXTRA_SEAL_LENGTH);
+        let end = seal_end(&header.extra_data);
+        let prefix_len = end.saturating_sub(EXTRA_SEAL_LENGTH);
+        let suffix = &header.extra_data[end..];
+        let mut extra_data = Vec::with_capacity(prefix_len + EXTRA_SEAL_LENGTH + suffix.len());
         extra_data.extend_from_slice(&header.extra_data[..prefix_len]);
         extra_data.extend_from_slice(&sig_bytes);
+        extra_data.extend_from_slice(suffix);
         header.extra_data = extra_data.into();
+        header
+    }
 
-        Ok(header)
+    /// Record the seals of `headers`, signed over `seal_hashes`, in the audit log, if
+    /// any.
+    async fn record(
+        &self,
+        headers: &[Header],
+        seal_hashes: Vec<B256>,
+        signer_address: &Address,
+    ) -> Result<(), SignerError> {
+        let (Some(log), Some(first)) = (&self.audit_log, headers.first()) else {
+            return Ok(());
+        };
+        let entries = headers
+            .iter()
+            .zip(seal_hashes)
+            .map(|(header, seal_hash)| SealAuditEntry::new(header, seal_hash, *signer_address))
+            .collect();
+        if let Err(err) = log.record(entries).await {
+            if self.audit_mode == AuditLogMode::Strict {
+                return Err(err.into());
+            }
+            warn!(
+                target: "poa::signer",
+                block = first.number,
+                count = headers.len(),
+                path = %log.path().display(),
+                %err,
+                "Failed to record seal in the audit log"
+            );
+        }
+        Ok(())
     }
 
-    /// Verify a block's signature
-    pub fn verify_signature(header: &Header) -> Result<Address, SignerError> {
+    /// Verify a block's signature, returning the address that sealed it.
+    ///
+    /// A legacy `v` of 27/28 is read as 0/1; a high-s signature is rejected as
+    /// [`SignerError::MalleableSignature`].
+    pub fn verify_signature(header: &Header) -> Result<Address, SealError> {
         let seal_hash = Self::seal_hash(header);
 
         let extra_data = &header.extra_data;
         const EXTRA_SEAL_LENGTH: usize = 65;
+        let end = seal_end(extra_data);
 
-        if extra_data.len() < EXTRA_SEAL_LENGTH {
-            return Err(SignerError::SigningFailed("Extra data too short".into()));
+        if end < EXTRA_SEAL_LENGTH {
+            return Err(SealError::ExtraDataTooShort {
+                len: extra_data.len(),
+                required: EXTRA_SEAL_LENGTH,
+            });
         }
 
-        let sig_bytes = &extra_data[extra_data.len() - EXTRA_SEAL_LENGTH..];
-        let signature = bytes_to_signature(sig_bytes)?;
+        let sig_bytes = &extra_data[end - EXTRA_SEAL_LENGTH..end];
+        let signature = bytes_to_signature(sig_bytes).map_err(SealError::MalformedSignature)?;
+        if !is_low_s(&signature) {
+            return Err(SealError::MalformedSignature(
+                SignerError::MalleableSignature,
+            ));
+        }
 
         signature
             .recover_address_from_prehash(&seal_hash)
-            .map_err(|e| SignerError::SigningFailed(e.to_string()))
-    }
-}
-
-/// Convert a signature to bytes (r || s || v)
-#[inline]
-pub fn signature_to_bytes(sig: &Signature) -> [u8; 65] {
-    let mut bytes = [0u8; 65];
-    bytes[..32].copy_from_slice(&sig.r().to_be_bytes::<32>());
-    bytes[32..64].copy_from_slice(&sig.s().to_be_bytes::<32>());
-    bytes[64] = sig.v() as u8;
-    bytes
-}
-
-/// Convert bytes to a signature
-pub fn bytes_to_signature(bytes: &[u8]) -> Result<Signature, SignerError> {
-    if bytes.len() != 65 {
-        return Err(SignerError::SigningFailed(format!(
-            "Invalid signature length: expected 65, got {}",
-            bytes.len()
-        )));
+            .map_err(SealError::RecoveryFailed)
     }
-
-    Signature::try_from(bytes)
-        .map_err(|e| SignerError::SigningFailed(format!("Invalid signature: {}", e)))
 }
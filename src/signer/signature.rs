@@ -0,0 +1,240 @@
+//! Canonical 65-byte seal signature encoding.
+//!
+//! Clique seals are `r (32) || s (32) || v (1)` where `v` is the raw secp256k1
+//! recovery id — `0` or `1`, *not* the legacy `27`/`28` used by `eth_sign`.
+//! Geth's clique engine recovers with the byte as-is, so writing 27/28 produces
+//! seals other implementations cannot verify.
+//!
+//! - [`signature_to_bytes`] always writes `v ∈ {0, 1}`.
+//! - [`bytes_to_signature`] accepts `0`/`1` and, for compatibility with signers
+//!   that emit Ethereum-style signatures, `27`/`28`. Any other `v` (including
+//!   EIP-155 values) is rejected with [`SignerError::InvalidRecoveryId`].
+//!
+//! `(r, s)` and `(r, n - s)` recover the same signer, so a seal is only canonical
+//! with `s` in the lower half of the curve order (EIP-2). Sealing writes low-s
+//! signatures ([`normalize_s`]); verification rejects high-s ones.
+
+use alloy_primitives::{Signature, U256};
+
+use super::errors::SignerError;
+
+/// Length of an encoded seal signature (`r || s || v`).
+pub const SIGNATURE_LENGTH: usize = 65;
+
+/// Order `n` of the secp256k1 curve.
+pub const SECP256K1_ORDER: U256 = U256::from_be_bytes(alloy_primitives::hex!(
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+));
+
+/// `n / 2`: the largest `s` of a low-s signature.
+const SECP256K1_HALF_ORDER: U256 = U256::from_be_bytes(alloy_primitives::hex!(
+    "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0"
+));
+
+/// Convert a signature to bytes (`r || s || v`), with `v` as recovery id 0/1.
+#[inline]
+pub fn signature_to_bytes(sig: &Signature) -> [u8; SIGNATURE_LENGTH] {
+    let mut bytes = [0u8; SIGNATURE_LENGTH];
+    bytes[..32].copy_from_slice(&sig.r().to_be_bytes::<32>());
+    bytes[32..64].copy_from_slice(&sig.s().to_be_bytes::<32>());
+    bytes[64] = recovery_id(sig);
+    bytes
+}
+
+/// Convert `r || s || v` bytes to a signature.
+///
+/// `v` may be `0`/`1` (canonical) or `27`/`28` (legacy); anything else is an error.
+pub fn bytes_to_signature(bytes: &[u8]) -> Result<Signature, SignerError> {
+    if bytes.len() != SIGNATURE_LENGTH {
+        return Err(SignerError::InvalidSignatureLength(bytes.len()));
+    }
+
+    let y_parity = parse_recovery_id(bytes[64])?;
+    let r = U256::from_be_slice(&bytes[..32]);
+    let s = U256::from_be_slice(&bytes[32..64]);
+    Ok(Signature::new(r, s, y_parity))
+}
+
+/// Whether `s` is in the lower half of the curve order.
+#[inline]
+pub fn is_low_s(sig: &Signature) -> bool {
+    sig.s() <= SECP256K1_HALF_ORDER
+}
+
+/// The low-s form of `sig`: a high `s` becomes `n - s` and the parity flips, so the
+/// signature still recovers to the same signer.
+pub fn normalize_s(sig: &Signature) -> Signature {
+    if is_low_s(sig) {
+        return *sig;
+    }
+    Signature::new(sig.r(), SECP256K1_ORDER - sig.s(), !sig.v())
+}
+
+/// Canonical recovery id (0 or 1) for a signature.
+#[inline]
+pub fn recovery_id(sig: &Signature) -> u8 {
+    sig.v() as u8
+}
+
+/// Map a seal `v` byte to the y-parity bit.
+#[inline]
+fn parse_recovery_id(v: u8) -> Result<bool, SignerError> {
+    match v {
+        0 | 27 => Ok(false),
+        1 | 28 => Ok(true),
+        other => Err(SignerError::InvalidRecoveryId(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{dev, SignerManager};
+    use alloy_primitives::{address, b256, hex, keccak256, Address, B256};
+
+    /// Hardhat/Anvil account #0 (`dev::DEV_PRIVATE_KEYS[0]`).
+    const REF_ADDRESS: Address = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+    /// `keccak256("meowchain clique seal")`.
+    const REF_HASH: B256 =
+        b256!("4388e89e0ca094382e07495c7f8003025c56287cddab6aa7a796aee905cd0f6b");
+
+    /// Signature of [`REF_HASH`] by [`REF_ADDRESS`], produced independently with
+    /// OpenSSL (RFC 6979 deterministic ECDSA, low-s normalized), recovery id 0.
+    const REF_SIG: [u8; 65] = hex!("965c1bcad1d283969f8e5a2e5695d5ef83fa6d6126fffed1a51cbe41ec1cf53b6e59cdc2b139d606e15f249186ab723586f6b96475e874e2d36a86023264cbe400");
+
+    fn with_v(mut bytes: [u8; 65], v: u8) -> [u8; 65] {
+        bytes[64] = v;
+        bytes
+    }
+
+    #[test]
+    fn test_ref_hash_matches_preimage() {
+        assert_eq!(keccak256(b"meowchain clique seal"), REF_HASH);
+    }
+
+    #[test]
+    fn test_reference_vector_recovers_expected_address() {
+        let sig = bytes_to_signature(&REF_SIG).unwrap();
+        assert_eq!(
+            sig.recover_address_from_prehash(&REF_HASH).unwrap(),
+            REF_ADDRESS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_signer_matches_reference_vector() {
+        let manager = SignerManager::new();
+        let addr = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0])
+            .unwrap();
+        assert_eq!(addr, REF_ADDRESS);
+
+        let sig = manager.sign_hash(&addr, REF_HASH).await.unwrap();
+        assert_eq!(signature_to_bytes(&sig), REF_SIG);
+    }
+
+    #[test]
+    fn test_encode_writes_recovery_id_zero_or_one() {
+        for v in [0u8, 1, 27, 28] {
+            let sig = bytes_to_signature(&with_v(REF_SIG, v)).unwrap();
+            let encoded = signature_to_bytes(&sig);
+            assert!(encoded[64] <= 1, "v={v} encoded as {}", encoded[64]);
+        }
+    }
+
+    #[test]
+    fn test_legacy_v_decodes_to_same_signature() {
+        let canonical = bytes_to_signature(&with_v(REF_SIG, 0)).unwrap();
+        let legacy = bytes_to_signature(&with_v(REF_SIG, 27)).unwrap();
+        assert_eq!(canonical, legacy);
+
+        let canonical = bytes_to_signature(&with_v(REF_SIG, 1)).unwrap();
+        let legacy = bytes_to_signature(&with_v(REF_SIG, 28)).unwrap();
+        assert_eq!(canonical, legacy);
+    }
+
+    #[test]
+    fn test_legacy_v_recovers_same_address() {
+        let sig = bytes_to_signature(&with_v(REF_SIG, 27)).unwrap();
+        assert_eq!(
+            sig.recover_address_from_prehash(&REF_HASH).unwrap(),
+            REF_ADDRESS
+        );
+    }
+
+    #[test]
+    fn test_wrong_parity_recovers_different_address() {
+        let sig = bytes_to_signature(&with_v(REF_SIG, 1)).unwrap();
+        let recovered = sig.recover_address_from_prehash(&REF_HASH);
+        assert!(recovered.map_or(true, |a| a != REF_ADDRESS));
+    }
+
+    #[test]
+    fn test_roundtrip_is_canonical() {
+        for v in [0u8, 1] {
+            let bytes = with_v(REF_SIG, v);
+            assert_eq!(
+                signature_to_bytes(&bytes_to_signature(&bytes).unwrap()),
+                bytes
+            );
+        }
+        for (legacy, canonical) in [(27u8, 0u8), (28, 1)] {
+            let sig = bytes_to_signature(&with_v(REF_SIG, legacy)).unwrap();
+            assert_eq!(signature_to_bytes(&sig), with_v(REF_SIG, canonical));
+        }
+    }
+
+    #[test]
+    fn test_rejects_every_other_v() {
+        for v in 0..=u8::MAX {
+            let result = bytes_to_signature(&with_v(REF_SIG, v));
+            if matches!(v, 0 | 1 | 27 | 28) {
+                assert!(result.is_ok(), "v={v} should be accepted");
+            } else {
+                match result {
+                    Err(SignerError::InvalidRecoveryId(got)) => assert_eq!(got, v),
+                    other => panic!("v={v}: expected InvalidRecoveryId, got {other:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_wrong_length() {
+        for len in [0usize, 64, 66, 97] {
+            match bytes_to_signature(&vec![0u8; len]) {
+                Err(SignerError::InvalidSignatureLength(got)) => assert_eq!(got, len),
+                other => panic!("len={len}: expected InvalidSignatureLength, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_s_flips_high_s_and_parity() {
+        let low = bytes_to_signature(&REF_SIG).unwrap();
+        assert!(is_low_s(&low));
+        assert_eq!(normalize_s(&low), low);
+
+        let high = Signature::new(low.r(), SECP256K1_ORDER - low.s(), !low.v());
+        assert!(!is_low_s(&high));
+        assert_eq!(
+            high.recover_address_from_prehash(&REF_HASH).unwrap(),
+            REF_ADDRESS
+        );
+        assert_eq!(normalize_s(&high), low);
+    }
+
+    #[tokio::test]
+    async fn test_all_dev_signers_roundtrip_and_recover() {
+        let manager = dev::setup_dev_signers().await;
+        let hash = keccak256(b"roundtrip");
+        for addr in manager.signer_addresses() {
+            let sig = manager.sign_hash(&addr, hash).await.unwrap();
+            let bytes = signature_to_bytes(&sig);
+            assert!(bytes[64] <= 1);
+            let decoded = bytes_to_signature(&bytes).unwrap();
+            assert_eq!(decoded.recover_address_from_prehash(&hash).unwrap(), addr);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Keystore directory hot-reload.
+//!
+//! Rotating a validator key should not need a restart.
+//! [`SignerManager::watch_keystore_dir`] polls a keystore directory: the key of a new
+//! keystore file is added, the key of a file that disappears is removed, and a file
+//! changed in place is reloaded. A file that fails to load (e.g. caught halfway
+//! through a non-atomic write) keeps its previous key and is retried on every poll.
+//! Only keys the watcher loaded itself are ever removed: a key of the same address
+//! loaded from `--signer-key`, a mnemonic or a remote signer is left alone.
+//!
+//! Removal is safe while a block is being sealed:
+//! [`SignerManager::sign_hash`] clones the key out of the map before signing, so the
+//! in-flight signature completes and only later ones fail with
+//! [`SignerError::NoSignerForAddress`](super::SignerError::NoSignerForAddress).
+
+use alloy_primitives::Address;
+use reth_tracing::tracing::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use super::manager::SignerManager;
+use crate::keystore::{self, KeystoreError};
+
+/// How often a watched keystore directory is rescanned by default.
+pub const DEFAULT_KEYSTORE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Supplies the password of a keystore file, asked on every (re)load.
+pub type PasswordProvider = Arc<dyn Fn(&Path) -> Result<String, KeystoreError> + Send + Sync>;
+
+/// Watched keystore files and the keys they loaded.
+#[derive(Debug)]
+pub(crate) struct KeystoreDirWatch {
+    dir: PathBuf,
+    files: HashMap<PathBuf, WatchedFile>,
+    /// Addresses whose key the watcher added; no other key is ever removed.
+    owned: HashSet<Address>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WatchedFile {
+    /// Modification time when last read; the file is reloaded when it changes.
+    modified: Option<SystemTime>,
+    /// Address of the key last loaded from the file, kept while a reload fails.
+    address: Option<Address>,
+    /// Whether the last read failed; such a file is retried on every poll.
+    failed: bool,
+}
+
+impl KeystoreDirWatch {
+    /// Watch the keystores in `dir`.
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            files: HashMap::new(),
+            owned: HashSet::new(),
+        }
+    }
+
+    /// Bring `manager` in line with the directory's keystore files.
+    ///
+    /// Decrypts every new or modified file, which may take a second each with
+    /// production scrypt parameters: run it off the async runtime.
+    pub(crate) fn poll(&mut self, manager: &SignerManager, password: &PasswordProvider) {
+        let mut paths = match keystore::keystore_files(&self.dir) {
+            Ok(paths) => paths,
+            Err(err) => {
+                warn!(
+                    target: "poa::signer",
+                    dir = %self.dir.display(),
+                    %err,
+                    "Failed to scan keystore directory"
+                );
+                return;
+            }
+        };
+
+        // A watched file that no longer parses as a keystore is most likely being
+        // rewritten: it is retried like any failed load, not treated as gone.
+        let present: HashSet<PathBuf> = paths.iter().cloned().collect();
+        let missing: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|path| !present.contains(*path))
+            .cloned()
+            .collect();
+        for path in missing {
+            if path.exists() {
+                paths.push(path);
+            } else if let Some(file) = self.files.remove(&path) {
+                self.release(manager, file.address, &path);
+            }
+        }
+
+        for path in paths {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+            let previous = self.files.get(&path).copied();
+            if previous.is_some_and(|file| !file.failed && file.modified == modified) {
+                continue;
+            }
+            let previous_address = previous.and_then(|file| file.address);
+            match self.load(manager, password, &path) {
+                Ok(address) => {
+                    if previous_address != Some(address) {
+                        info!(
+                            target: "poa::signer",
+                            %address,
+                            path = %path.display(),
+                            "Loaded signer from keystore"
+                        );
+                    }
+                    let file = WatchedFile {
+                        modified,
+                        address: Some(address),
+                        failed: false,
+                    };
+                    self.files.insert(path.clone(), file);
+                    if previous_address != Some(address) {
+                        self.release(manager, previous_address, &path);
+                    }
+                }
+                Err(err) => {
+                    // Warn once per failing version of the file, not on every retry.
+                    if !previous.is_some_and(|file| file.failed && file.modified == modified) {
+                        warn!(
+                            target: "poa::signer",
+                            path = %path.display(),
+                            %err,
+                            "Failed to load keystore, keeping its previous key"
+                        );
+                    }
+                    let file = WatchedFile {
+                        modified,
+                        address: previous_address,
+                        failed: true,
+                    };
+                    self.files.insert(path, file);
+                }
+            }
+        }
+    }
+
+    /// Decrypt the keystore at `path` and add its key, unless a key of that address
+    /// was loaded from another source.
+    fn load(
+        &mut self,
+        manager: &SignerManager,
+        password: &PasswordProvider,
+        path: &Path,
+    ) -> Result<Address, KeystoreError> {
+        let keystore = keystore::read_keystore(path)?;
+        let signer = keystore::decrypt_keystore(&keystore, &password(path)?)?;
+        let address = signer.address();
+        if self.owned.contains(&address) || !manager.has_signer(&address) {
+            manager.add_signer(signer);
+            self.owned.insert(address);
+        }
+        Ok(address)
+    }
+
+    /// Remove the key of `address` if the watcher loaded it and no other watched
+    /// file still holds it.
+    fn release(&mut self, manager: &SignerManager, address: Option<Address>, path: &Path) {
+        let Some(address) = address.filter(|address| self.owned.contains(address)) else {
+            return;
+        };
+        if self
+            .files
+            .values()
+            .any(|file| file.address == Some(address))
+        {
+            return;
+        }
+        self.owned.remove(&address);
+        if manager.remove_signer(&address) {
+            info!(
+                target: "poa::signer",
+                %address,
+                path = %path.display(),
+                "Removed signer: keystore file gone or replaced"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{dev, SignerError};
+    use alloy_primitives::B256;
+    use alloy_signer_local::PrivateKeySigner;
+
+    const PASSWORD: &str = "meowchain";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("poa-keystore-watch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn password() -> PasswordProvider {
+        Arc::new(|_| Ok(PASSWORD.to_string()))
+    }
+
+    /// Write dev key `key` as a keystore named `name` into `dir`.
+    fn write_dev_keystore(dir: &Path, name: &str, key: usize) -> Address {
+        let signer: PrivateKeySigner = dev::DEV_PRIVATE_KEYS[key].parse().unwrap();
+        let keystore = keystore::encrypt_signer_scrypt(&signer, PASSWORD).unwrap();
+        keystore::write_keystore(dir.join(name), &keystore, true).unwrap();
+        signer.address()
+    }
+
+    #[tokio::test]
+    async fn test_removed_keystore_removes_signer() {
+        let dir = temp_dir("remove");
+        let manager = SignerManager::new();
+        let mut watch = KeystoreDirWatch::new(dir.clone());
+        let address = write_dev_keystore(&dir, "UTC--a", 0);
+        fs::write(dir.join("not-a-keystore"), "{").unwrap();
+
+        watch.poll(&manager, &password());
+        assert!(manager.has_signer(&address));
+        assert_eq!(manager.signer_count(), 1);
+
+        assert!(manager
+            .sign_hash(&address, B256::repeat_byte(1))
+            .await
+            .is_ok());
+        fs::remove_file(dir.join("UTC--a")).unwrap();
+        watch.poll(&manager, &password());
+        assert!(!manager.has_signer(&address));
+        assert!(matches!(
+            manager.sign_hash(&address, B256::repeat_byte(1)).await,
+            Err(SignerError::NoSignerForAddress(a)) if a == address
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_key_kept_while_another_file_holds_it() {
+        let dir = temp_dir("duplicate");
+        let manager = SignerManager::new();
+        let mut watch = KeystoreDirWatch::new(dir.clone());
+        let address = write_dev_keystore(&dir, "UTC--a", 0);
+        write_dev_keystore(&dir, "UTC--b", 0);
+        watch.poll(&manager, &password());
+
+        fs::remove_file(dir.join("UTC--a")).unwrap();
+        watch.poll(&manager, &password());
+        assert!(manager.has_signer(&address));
+        fs::remove_file(dir.join("UTC--b")).unwrap();
+        watch.poll(&manager, &password());
+        assert!(!manager.has_signer(&address));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_key_and_retries() {
+        let dir = temp_dir("retry");
+        let manager = SignerManager::new();
+        let mut watch = KeystoreDirWatch::new(dir.clone());
+        let address = write_dev_keystore(&dir, "UTC--a", 0);
+        watch.poll(&manager, &password());
+
+        // Caught halfway through a rewrite: the old key stays until the file loads.
+        fs::write(dir.join("UTC--a"), r#"{"version": 3, "#).unwrap();
+        watch.poll(&manager, &password());
+        assert!(manager.has_signer(&address));
+        assert!(watch.files[&dir.join("UTC--a")].failed);
+        write_dev_keystore(&dir, "UTC--a", 0);
+        watch.poll(&manager, &password());
+        assert!(manager.has_signer(&address));
+        assert!(!watch.files[&dir.join("UTC--a")].failed);
+
+        // A failed load is retried on the next poll even though the file is unchanged.
+        let rotated = write_dev_keystore(&dir, "UTC--b", 1);
+        let unlocked = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = unlocked.clone();
+        let late_password: PasswordProvider = Arc::new(move |_| {
+            if flag.load(std::sync::atomic::Ordering::Relaxed) {
+                Ok(PASSWORD.to_string())
+            } else {
+                Err(KeystoreError::InvalidPassword)
+            }
+        });
+        watch.poll(&manager, &late_password);
+        assert!(!manager.has_signer(&rotated));
+        unlocked.store(true, std::sync::atomic::Ordering::Relaxed);
+        watch.poll(&manager, &late_password);
+        assert!(manager.has_signer(&rotated));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_keys_from_other_sources_are_kept() {
+        let dir = temp_dir("foreign");
+        let manager = SignerManager::new();
+        let address = manager
+            .add_signer_from_hex(dev::DEV_PRIVATE_KEYS[0])
+            .unwrap();
+        let mut watch = KeystoreDirWatch::new(dir.clone());
+        write_dev_keystore(&dir, "UTC--a", 0);
+        watch.poll(&manager, &password());
+
+        fs::remove_file(dir.join("UTC--a")).unwrap();
+        watch.poll(&manager, &password());
+        assert!(manager.has_signer(&address));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watcher_picks_up_new_keystore() {
+        let dir = temp_dir("watch");
+        let manager = Arc::new(SignerManager::new());
+        let watcher =
+            manager.watch_keystore_dir(dir.clone(), password(), Duration::from_millis(20));
+
+        let address = write_dev_keystore(&dir, "UTC--rotated", 1);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        while !manager.has_signer(&address) {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "watcher missed the keystore"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        fs::remove_file(dir.join("UTC--rotated")).unwrap();
+        while manager.has_signer(&address) {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "watcher kept a removed key"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        watcher.abort();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -20,6 +20,7 @@
 //! println!("{}", diff.summary());
 //! ```
 
+use alloy_evm::revm::database::BundleAccount;
 use alloy_primitives::{Address, B256, U256};
 use std::collections::HashMap;
 
@@ -285,6 +286,30 @@ impl StateDiffBuilder {
         }
     }
 
+    /// Record every change in an executed account from a revm bundle.
+    ///
+    /// Creation and destruction are recorded as code changes.
+    pub fn record_bundle_account(&mut self, addr: Address, account: &BundleAccount) {
+        match (&account.original_info, &account.info) {
+            (Some(old), Some(new)) => {
+                self.record_balance_change(addr, old.balance, new.balance);
+                self.record_nonce_change(addr, old.nonce, new.nonce);
+                if old.code_hash != new.code_hash {
+                    self.record_code_change(addr);
+                }
+            }
+            (None, Some(_)) | (Some(_), None) => self.record_code_change(addr),
+            (None, None) => {}
+        }
+        for (slot_key, slot) in &account.storage {
+            if slot.is_changed() {
+                let old = B256::from(slot.previous_or_original_value.to_be_bytes::<32>());
+                let new = B256::from(slot.present_value.to_be_bytes::<32>());
+                self.record_storage_change(addr, *slot_key, old, new);
+            }
+        }
+    }
+
     /// Consume the builder and produce the final [`StateDiff`].
     pub fn build(self) -> StateDiff {
         StateDiff {